@@ -0,0 +1,68 @@
+//! 追踪器与各处理阶段的criterion基准
+//!
+//! 与`flux-cli bench`子命令共用合成数据生成器（`services::benchmark`），
+//! 用于验证optimizations模块的优化效果。运行：
+//! `cargo bench --bench audit_benchmark`
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use flux_backend::benchmark::{
+    generate_synthetic_transactions, run_balance_method, run_fifo, SyntheticSpec,
+};
+use flux_backend::{Config, ExcelProcessor, UnifiedValidator};
+
+/// 两种追踪器与流水验证的纯内存吞吐
+fn tracker_benches(c: &mut Criterion) {
+    let spec = SyntheticSpec {
+        transaction_count: 5_000,
+        ..SyntheticSpec::default()
+    };
+    let transactions = generate_synthetic_transactions(&spec);
+    let config = Config::new();
+
+    c.bench_function("fifo_tracker_5k", |b| {
+        b.iter(|| run_fifo(&config, &transactions).unwrap());
+    });
+    c.bench_function("balance_method_tracker_5k", |b| {
+        b.iter(|| run_balance_method(&config, &transactions).unwrap());
+    });
+    c.bench_function("flow_validation_5k", |b| {
+        b.iter(|| {
+            let mut validator = UnifiedValidator::new();
+            validator.validate_flow_integrity(&transactions).unwrap()
+        });
+    });
+}
+
+/// Excel导出与读取（IO较重，降低采样数）
+fn excel_benches(c: &mut Criterion) {
+    let spec = SyntheticSpec {
+        transaction_count: 1_000,
+        ..SyntheticSpec::default()
+    };
+    let transactions = generate_synthetic_transactions(&spec);
+    let config = Config::new();
+    let summary = run_fifo(&config, &transactions).unwrap();
+    let processor = ExcelProcessor::new(config);
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let export_path = temp_dir.path().join("bench_result.xlsx");
+    let input_path = temp_dir.path().join("bench_input.xlsx");
+    processor.write_input_workbook(&transactions, &input_path).unwrap();
+
+    let mut group = c.benchmark_group("excel_1k");
+    group.sample_size(10);
+    group.bench_function("export", |b| {
+        b.iter(|| {
+            processor
+                .export_analysis_results(&transactions, &summary, &export_path)
+                .unwrap()
+        });
+    });
+    group.bench_function("read", |b| {
+        b.iter(|| processor.read_transactions(&input_path).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, tracker_benches, excel_benches);
+criterion_main!(benches);
@@ -4,12 +4,14 @@
 //! 对应Python版本的差额计算法资金追踪器完整功能
 
 use super::shared::{
-    TrackerBase, BehaviorAnalyzer, InvestmentPoolManager, FundFlowCommon, SummaryGenerator
+    TrackerBase, TrackerBaseSnapshot, BehaviorAnalyzer, ClassificationReason, InvestmentPoolManager, FundFlowCommon, SummaryGenerator,
+    OrderingAnomaly, PoolResetEvent, ProductRegistry, ProductMergeRecord,
 };
 use crate::data_models::{Config, AuditSummary, Transaction};
 use crate::errors::{AuditError, AuditResult};
 use chrono::NaiveDateTime;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 /// 差额计算法追踪器
 /// 
@@ -22,15 +24,31 @@ pub struct BalanceMethodTracker {
     base: TrackerBase,
     /// 行为分析器（挪用垫付分析）
     behavior_analyzer: BehaviorAnalyzer,
+    /// 最近一次支出触发的判定依据（挪用/垫付原因码）
+    last_classification_reasons: Vec<ClassificationReason>,
+}
+
+/// 差额计算法追踪器状态快照
+///
+/// 覆盖基础状态与行为分析器累计值，用于检查点与增量分析。
+/// 判定依据等单行瞬态缓存在每行处理时被重置，不纳入快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceMethodTrackerSnapshot {
+    /// 共享基础状态快照
+    pub base: TrackerBaseSnapshot,
+    /// 行为分析器累计状态
+    pub behavior_analyzer: BehaviorAnalyzer,
 }
 
 impl BalanceMethodTracker {
     /// 创建新的差额计算法追踪器
-    /// 对应Python版本的__init__方法
+    /// `对应Python版本的__init__方法`
+    #[must_use] 
     pub fn new(config: Config) -> Self {
         Self {
             base: TrackerBase::new(config),
             behavior_analyzer: BehaviorAnalyzer::new(),
+            last_classification_reasons: Vec::new(),
         }
     }
     
@@ -61,6 +79,9 @@ impl BalanceMethodTracker {
             transaction_date,
         );
         
+        // 流入行不产生判定依据
+        self.last_classification_reasons.clear();
+        
         Ok((personal_ratio, company_ratio, behavior))
     }
     
@@ -114,15 +135,22 @@ impl BalanceMethodTracker {
         &mut self,
         amount: Decimal,
         fund_attribute: &str,
-        transaction_date: Option<NaiveDateTime>,
+        _transaction_date: Option<NaiveDateTime>,
     ) -> AuditResult<(Decimal, Decimal, String)> {
         if !self.base.is_initialized() {
             return Err(AuditError::validation_error("追踪器未初始化"));
         }
         
-        // 使用差额计算法扣除函数 - 根据资金属性优先扣除对应账户
+        // 使用差额计算法扣除函数 - 根据资金属性优先扣除对应账户，
+        // 透支模式下缺口部分按归属方计为透支提取
         let (personal_deduction, company_deduction) = self.balance_method_deduction_by_attribute(amount, fund_attribute);
-        
+        let (personal_deduction, company_deduction) = FundFlowCommon::cover_shortfall_with_overdraft(
+            &mut self.base,
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+
         // 计算占比（基于原始金额）
         let (personal_ratio, company_ratio) = FundFlowCommon::calculate_ratios(
             personal_deduction,
@@ -140,6 +168,15 @@ impl BalanceMethodTracker {
             amount,
         );
         
+        // 记录结构化判定依据（挪用/垫付/缺口）
+        self.last_classification_reasons = BehaviorAnalyzer::explain_outflow_classification(
+            fund_attribute,
+            personal_deduction,
+            company_deduction,
+            amount,
+            &self.base.config,
+        );
+        
         Ok((personal_ratio, company_ratio, behavior))
     }
     
@@ -178,11 +215,17 @@ impl BalanceMethodTracker {
                 base.company_balance = (base.company_balance - company_deducted).max(Decimal::ZERO);
                 base.update_total_balance();
                 
+                // 记录投资挪用判定依据
+                self.last_classification_reasons = BehaviorAnalyzer::explain_investment_classification(
+                    fund_attribute,
+                    company_deducted,
+                );
+                
                 (personal_deducted, company_deducted)
             },
         );
         
-        result.map_err(|e| AuditError::validation_error(e))
+        result.map_err(AuditError::validation_error)
     }
     
     /// 处理投资产品赎回
@@ -223,16 +266,47 @@ impl BalanceMethodTracker {
     }
     
     /// 检查是否已初始化
+    #[must_use] 
     pub fn is_initialized(&self) -> bool {
         self.base.is_initialized()
     }
     
+    /// 获取时序异常记录（赎回早于申购等）
+    #[must_use]
+    pub fn get_ordering_anomalies(&self) -> &[OrderingAnomaly] {
+        &self.base.ordering_anomalies
+    }
+    
+    /// 为尚未关联行号的时序异常回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_anomaly_rows(&mut self, row: usize) {
+        self.base.assign_pending_anomaly_rows(row);
+    }
+    
+    /// 获取资金池盈利重置事件
+    #[must_use]
+    pub fn get_pool_reset_events(&self) -> &[PoolResetEvent] {
+        &self.base.pool_reset_events
+    }
+    
+    /// 为尚未关联行号的资金池重置事件回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_reset_rows(&mut self, row: usize) {
+        self.base.assign_pending_reset_rows(row);
+    }
+
+    /// 获取产品代码归并报告（同一规范代码下出现过的多种原始写法）
+    #[must_use]
+    pub fn get_product_merge_report(&self) -> Vec<ProductMergeRecord> {
+        ProductRegistry::merge_report(&self.base)
+    }
+    
     /// 获取算法名称
+    #[must_use] 
     pub fn get_name(&self) -> &'static str {
         "BALANCE_METHOD"
     }
     
     /// 获取算法描述
+    #[must_use] 
     pub fn get_description(&self) -> &'static str {
         "差额计算法 - 基于当前余额优先策略进行资金扣除"
     }
@@ -241,28 +315,134 @@ impl BalanceMethodTracker {
     pub fn reset(&mut self) -> AuditResult<()> {
         self.base.reset();
         self.behavior_analyzer = BehaviorAnalyzer::new();
+        self.last_classification_reasons.clear();
         Ok(())
     }
     
     /// 生成详细的摘要文本
+    #[must_use] 
     pub fn generate_detailed_summary_text(&self) -> String {
         SummaryGenerator::generate_detailed_summary_text(&self.base, "差额计算法")
     }
     
+    /// 累计单笔交易的按币种净流入（由服务层在配置了币种列时调用）
+    pub fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal) {
+        self.base.record_currency_flow(currency, net_flow);
+    }
+
+    /// 归集利息/手续费行的收支（由服务层逐行调用，命中口径时返回true）
+    pub fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool {
+        self.base.record_interest_fee(fund_attribute, income, expense)
+    }
+
     /// 获取场外资金池记录管理器
+    #[must_use]
     pub fn get_offsite_pool_records(&self) -> &crate::data_models::OffsitePoolRecordManager {
         &self.base.offsite_pool_records
     }
     
     /// 获取投资池数据（用于完整统计计算）
+    #[must_use] 
     pub fn get_investment_pools(&self) -> &std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool> {
         &self.base.investment_pools
     }
     
     /// 获取base引用（用于访问场外资金池记录）
+    #[must_use] 
     pub fn get_base(&self) -> &TrackerBase {
         &self.base
     }
+    
+    /// 格式化判定依据（无挪用/垫付时返回None）
+    #[must_use]
+    pub fn format_classification_reasons(&self) -> Option<String> {
+        if self.last_classification_reasons.is_empty() {
+            None
+        } else {
+            Some(
+                self.last_classification_reasons.iter()
+                    .map(ClassificationReason::format)
+                    .collect::<Vec<_>>()
+                    .join("；")
+            )
+        }
+    }
+}
+
+/// 差额计算法追踪器的公开接口
+/// 
+/// 提供与其他组件集成的API
+impl BalanceMethodTracker {
+    /// 获取内部状态（供测试和调试使用）
+    #[cfg(test)]
+    #[must_use] 
+    pub fn get_internal_state(&self) -> (&TrackerBase, &BehaviorAnalyzer) {
+        (&self.base, &self.behavior_analyzer)
+    }
+    
+    /// 更新交易记录的所有计算字段
+    /// 
+    /// 这个方法将当前追踪器的状态同步到Transaction结构中
+    pub fn update_transaction_fields(
+        &self,
+        transaction: &mut Transaction,
+        personal_ratio: Decimal,
+        company_ratio: Decimal,
+        behavior: &str,
+    ) -> AuditResult<()> {
+        // 获取当前摘要状态
+        let summary = self.get_summary()?;
+        
+        // 更新算法计算字段
+        transaction.personal_ratio = Some(personal_ratio);
+        transaction.company_ratio = Some(company_ratio);
+        transaction.behavior_nature = Some(behavior.to_string());
+        
+        // 挪用/垫付行附带结构化判定依据
+        transaction.behavior_explanation = self.format_classification_reasons();
+        
+        // 更新累计字段
+        transaction.cumulative_misappropriation = Some(summary.total_misappropriation);
+        transaction.cumulative_advance = Some(summary.total_advance_payment);
+        transaction.cumulative_company_principal_returned = Some(summary.total_company_principal_returned);
+        transaction.cumulative_personal_principal_returned = Some(summary.total_personal_principal_returned);
+        transaction.cumulative_personal_profit = Some(summary.total_personal_profit);
+        transaction.cumulative_company_profit = Some(summary.total_company_profit);
+        
+        // 更新余额字段
+        transaction.personal_balance = Some(summary.personal_balance);
+        transaction.company_balance = Some(summary.company_balance);
+        transaction.funding_gap = Some(summary.funding_gap);
+        
+        // 修复时间戳格式问题：确保完整的日期时间格式
+        if !transaction.transaction_time.contains('/') && !transaction.transaction_time.contains('-') {
+            // 如果transaction_time只是时间部分，合并日期和时间
+            transaction.transaction_time = transaction.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string();
+        }
+
+        Ok(())
+    }
+
+    /// 导出当前运行状态快照
+    #[must_use]
+    pub fn to_snapshot(&self) -> BalanceMethodTrackerSnapshot {
+        BalanceMethodTrackerSnapshot {
+            base: self.base.to_snapshot(),
+            behavior_analyzer: self.behavior_analyzer.clone(),
+        }
+    }
+
+    /// 从状态快照恢复追踪器
+    ///
+    /// 单行瞬态缓存（判定依据）置空，下一行处理时会照常重新生成
+    #[must_use]
+    pub fn from_snapshot(config: Config, snapshot: BalanceMethodTrackerSnapshot) -> Self {
+        Self {
+            base: TrackerBase::from_snapshot(config, snapshot.base),
+            behavior_analyzer: snapshot.behavior_analyzer,
+            last_classification_reasons: Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -312,6 +492,31 @@ mod tests {
         assert_eq!(tracker.base.personal_balance, Decimal::from(80000));
     }
     
+    #[test]
+    fn test_snapshot_round_trip_and_identical_continuation() {
+        let config = Config::new();
+        let mut tracker = BalanceMethodTracker::new(config.clone());
+
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(30000), "公司应收", None).unwrap();
+        tracker.process_outflow(Decimal::from(60000), "个人应付", None).unwrap();
+
+        // 经serde序列化往返后恢复，累计状态应无损
+        let snapshot = tracker.to_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored_snapshot: BalanceMethodTrackerSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_snapshot, snapshot);
+
+        let mut restored = BalanceMethodTracker::from_snapshot(config, restored_snapshot);
+        assert_eq!(restored.to_snapshot(), snapshot);
+
+        // 两个实例继续处理同一笔支出，结果与状态应一致
+        let original_result = tracker.process_outflow(Decimal::from(10000), "公司应付", None).unwrap();
+        let restored_result = restored.process_outflow(Decimal::from(10000), "公司应付", None).unwrap();
+        assert_eq!(restored_result, original_result);
+        assert_eq!(restored.to_snapshot(), tracker.to_snapshot());
+    }
+
     #[test]
     fn test_balance_method_priority() {
         let config = Config::new();
@@ -348,22 +553,22 @@ mod tests {
         tracker.initialize_balance(Decimal::from(30000), "个人").unwrap();
         tracker.process_inflow(Decimal::from(70000), "公司应收", None).unwrap();
         
-        // 流出80000，应该优先扣除公司资金，不足部分扣除个人资金
+        // 流出80000，差额计算法优先扣除个人资金，不足部分扣除公司资金
         let result = tracker.process_outflow(
             Decimal::from(80000),
             "个人应付", // 个人支出使用公司资金构成挪用
             None,
         );
-        
+
         assert!(result.is_ok());
         let (personal_ratio, company_ratio, behavior) = result.unwrap();
-        
-        // 公司余额更多，优先扣除：70000公司 + 10000个人
-        assert_eq!(personal_ratio, Decimal::new(125, 3)); // 10000/80000 = 0.125
-        assert_eq!(company_ratio, Decimal::new(875, 3)); // 70000/80000 = 0.875
+
+        // 个人余额优先扣除：30000个人 + 50000公司
+        assert_eq!(personal_ratio, Decimal::new(375, 3)); // 30000/80000 = 0.375
+        assert_eq!(company_ratio, Decimal::new(625, 3)); // 50000/80000 = 0.625
         assert!(behavior.contains("挪用")); // 个人支出使用公司资金构成挪用
-        assert_eq!(tracker.base.personal_balance, Decimal::from(20000)); // 30000-10000
-        assert_eq!(tracker.base.company_balance, Decimal::ZERO); // 70000-70000
+        assert_eq!(tracker.base.personal_balance, Decimal::ZERO); // 30000-30000
+        assert_eq!(tracker.base.company_balance, Decimal::from(20000)); // 70000-50000
     }
     
     #[test]
@@ -380,55 +585,4 @@ mod tests {
         assert_eq!(personal_ratio, Decimal::new(6, 1)); // 60000/100000 = 0.6
         assert_eq!(company_ratio, Decimal::new(4, 1)); // 40000/100000 = 0.4
     }
-}
-
-/// 差额计算法追踪器的公开接口
-/// 
-/// 提供与其他组件集成的API
-impl BalanceMethodTracker {
-    /// 获取内部状态（供测试和调试使用）
-    #[cfg(test)]
-    pub fn get_internal_state(&self) -> (&TrackerBase, &BehaviorAnalyzer) {
-        (&self.base, &self.behavior_analyzer)
-    }
-    
-    /// 更新交易记录的所有计算字段
-    /// 
-    /// 这个方法将当前追踪器的状态同步到Transaction结构中
-    pub fn update_transaction_fields(
-        &self,
-        transaction: &mut Transaction,
-        personal_ratio: Decimal,
-        company_ratio: Decimal,
-        behavior: &str,
-    ) -> AuditResult<()> {
-        // 获取当前摘要状态
-        let summary = self.get_summary()?;
-        
-        // 更新算法计算字段
-        transaction.personal_ratio = Some(personal_ratio);
-        transaction.company_ratio = Some(company_ratio);
-        transaction.behavior_nature = Some(behavior.to_string());
-        
-        // 更新累计字段
-        transaction.cumulative_misappropriation = Some(summary.total_misappropriation);
-        transaction.cumulative_advance = Some(summary.total_advance_payment);
-        transaction.cumulative_company_principal_returned = Some(summary.total_company_principal_returned);
-        transaction.cumulative_personal_principal_returned = Some(summary.total_personal_principal_returned);
-        transaction.cumulative_personal_profit = Some(summary.total_personal_profit);
-        transaction.cumulative_company_profit = Some(summary.total_company_profit);
-        
-        // 更新余额字段
-        transaction.personal_balance = Some(summary.personal_balance);
-        transaction.company_balance = Some(summary.company_balance);
-        transaction.funding_gap = Some(summary.funding_gap);
-        
-        // 修复时间戳格式问题：确保完整的日期时间格式
-        if !transaction.transaction_time.contains('/') && !transaction.transaction_time.contains('-') {
-            // 如果transaction_time只是时间部分，合并日期和时间
-            transaction.transaction_time = transaction.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string();
-        }
-        
-        Ok(())
-    }
 }
\ No newline at end of file
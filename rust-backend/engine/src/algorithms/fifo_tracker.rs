@@ -0,0 +1,1025 @@
+//! FIFO资金追踪器实现
+//!
+//! 基于新共享架构实现，使用TrackerBase作为基础状态管理
+//! `对应Python版本的FIFO资金追踪器完整功能`
+
+use super::shared::{
+    TrackerBase, TrackerBaseSnapshot, BehaviorAnalyzer, ClassificationReason, InvestmentPoolManager, FundFlowCommon, SummaryGenerator,
+    OrderingAnomaly, PoolResetEvent, ProductRegistry, ProductMergeRecord,
+};
+use crate::data_models::{Config, FifoTieBreaking, AuditSummary, Transaction};
+use crate::errors::{AuditError, AuditResult};
+use crate::optimizations::memory_pool::{ObjectPool, PoolStats};
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[cfg(feature = "fixed-point")]
+use crate::utils::fixed_amount::FixedAmount;
+
+/// FIFO资金追踪器
+/// 
+/// 基于共享架构实现先进先出的资金追踪算法
+/// `对应Python版本的FIFO资金追踪器`
+#[derive(Debug, Clone)]
+pub struct FifoTracker {
+    /// 共享基础状态（13个状态变量）
+    base: TrackerBase,
+    /// 行为分析器（挪用垫付分析）
+    behavior_analyzer: BehaviorAnalyzer,
+    
+    // === FIFO特有的数据结构 ===
+    /// 资金流入队列 - 对应Python版本的deque结构
+    /// 元素格式: (金额, 类型, 时间)
+    fund_inflow_queue: VecDeque<FundEntry>,
+    /// 最近一次支出的资金来源消耗明细（来源标签, 金额）
+    last_outflow_sources: Option<Vec<(String, Decimal)>>,
+    /// 最近一次支出触发的判定依据（挪用/垫付原因码）
+    last_classification_reasons: Vec<ClassificationReason>,
+    /// 资金条目对象池（回收完全消费的条目，复用String缓冲；不纳入快照）
+    entry_pool: ObjectPool<FundEntry>,
+}
+
+/// 资金条目（FIFO队列中的元素）
+/// 对应Python版本的(金额, 类型, 时间)元组结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FundEntry {
+    /// 资金金额
+    amount: Decimal,
+    /// 资金类型（"个人" 或 "公司"）
+    fund_type: String,
+    /// 流入时间
+    entry_time: Option<NaiveDateTime>,
+    /// 资金来源标签（如"个人流入"、"理财-X赎回"），用于支出来源明细
+    source: String,
+}
+
+/// FIFO追踪器状态快照
+///
+/// 覆盖基础状态、行为分析器累计值与FIFO资金流入队列，
+/// 用于检查点与增量分析。支出来源明细、判定依据等单行
+/// 瞬态缓存在每行处理时被重置，不纳入快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FifoTrackerSnapshot {
+    /// 共享基础状态快照
+    pub base: TrackerBaseSnapshot,
+    /// 行为分析器累计状态
+    pub behavior_analyzer: BehaviorAnalyzer,
+    /// FIFO资金流入队列
+    pub fund_inflow_queue: VecDeque<FundEntry>,
+}
+
+impl FifoTracker {
+    /// 创建新的FIFO追踪器
+    /// `对应Python版本的__init__方法`
+    #[must_use] 
+    pub fn new(config: Config) -> Self {
+        Self {
+            base: TrackerBase::new(config),
+            behavior_analyzer: BehaviorAnalyzer::new(),
+            fund_inflow_queue: VecDeque::new(),
+            last_outflow_sources: None,
+            last_classification_reasons: Vec::new(),
+            entry_pool: ObjectPool::default(),
+        }
+    }
+
+    /// 从对象池或新分配获得一个资金条目
+    ///
+    /// 命中池内回收条目时复用其`fund_type`的String缓冲
+    fn make_entry(
+        &mut self,
+        amount: Decimal,
+        fund_type: &str,
+        entry_time: Option<NaiveDateTime>,
+        source: String,
+    ) -> FundEntry {
+        match self.entry_pool.acquire() {
+            Some(mut entry) => {
+                entry.amount = amount;
+                entry.fund_type.clear();
+                entry.fund_type.push_str(fund_type);
+                entry.entry_time = entry_time;
+                entry.source = source;
+                entry
+            }
+            None => FundEntry {
+                amount,
+                fund_type: fund_type.to_string(),
+                entry_time,
+                source,
+            },
+        }
+    }
+
+    /// 资金条目对象池的命中统计
+    #[must_use]
+    pub fn entry_pool_stats(&self) -> PoolStats {
+        self.entry_pool.stats()
+    }
+    
+    /// 初始化余额
+    /// 对应Python版本的初始化余额处理
+    pub fn initialize_balance(&mut self, initial_balance: Decimal, balance_type: &str) -> AuditResult<()> {
+        // 使用基础类初始化
+        self.base.initialize_balance(initial_balance, balance_type)?;
+        
+        // 添加到FIFO队列
+        let entry = self.make_entry(
+            initial_balance, balance_type, None, format!("初始{balance_type}资金"));
+        self.fund_inflow_queue.push_back(entry);
+        
+        Ok(())
+    }
+    
+    /// 处理资金流入
+    /// 对应Python版本的处理资金流入方法
+    pub fn process_inflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+        
+        // 使用共同资金流处理逻辑
+        let (personal_ratio, company_ratio, behavior) = FundFlowCommon::process_fund_inflow(
+            &mut self.base,
+            amount,
+            fund_attribute,
+            transaction_date,
+        );
+        
+        // 添加到FIFO队列（按实际分配金额；条目取自对象池）
+        if personal_ratio > Decimal::ZERO {
+            let entry = self.make_entry(
+                amount * personal_ratio, "个人", transaction_date, "个人流入".to_string());
+            self.fund_inflow_queue.push_back(entry);
+        }
+        if company_ratio > Decimal::ZERO {
+            let entry = self.make_entry(
+                amount * company_ratio, "公司", transaction_date, "公司流入".to_string());
+            self.fund_inflow_queue.push_back(entry);
+        }
+        
+        // 流入行不产生来源明细与判定依据
+        self.last_outflow_sources = None;
+        self.last_classification_reasons.clear();
+        
+        Ok((personal_ratio, company_ratio, behavior))
+    }
+    
+    /// 选取下一个待消耗条目的下标
+    ///
+    /// 队列前端入队时间相同的连续条目视为一个平局组，
+    /// 按配置的平局规则（`FifoTieBreaking`）在组内优先选取目标资金类型；
+    /// 默认规则（入队顺序）下始终返回队首
+    fn next_deduction_index(&self) -> usize {
+        let preferred_type = match self.base.config.fifo.tie_breaking {
+            FifoTieBreaking::InsertionOrder => return 0,
+            FifoTieBreaking::PersonalFirst => "个人",
+            FifoTieBreaking::CompanyFirst => "公司",
+        };
+
+        let Some(front_time) = self.fund_inflow_queue.front().map(|e| e.entry_time) else {
+            return 0;
+        };
+
+        // 仅在与队首时间相同的连续条目中寻找优先类型，保持组内相对顺序
+        self.fund_inflow_queue.iter()
+            .take_while(|entry| entry.entry_time == front_time)
+            .position(|entry| entry.fund_type == preferred_type)
+            .unwrap_or(0)
+    }
+
+    /// FIFO资金扣除函数
+    /// `对应Python版本的FIFO队列扣除逻辑`
+    fn fifo_deduction(&mut self, amount: Decimal) -> (Decimal, Decimal) {
+        // fixed-point特性开启时优先走定点i64快速路径，
+        // 无法无损映射为分计数的金额回退Decimal路径
+        #[cfg(feature = "fixed-point")]
+        if let Some(result) = self.fifo_deduction_fixed(amount) {
+            return result;
+        }
+        self.fifo_deduction_decimal(amount)
+    }
+    
+    /// 定点快速路径：支出金额与队列全部条目都能无损映射为分计数时，
+    /// 用i64整数完成整个扣除循环，语义与Decimal路径完全一致；
+    /// 任一金额无法映射（如占比拆分产生的长小数）则返回None整体回退
+    #[cfg(feature = "fixed-point")]
+    fn fifo_deduction_fixed(&mut self, amount: Decimal) -> Option<(Decimal, Decimal)> {
+        let mut remaining_amount = FixedAmount::try_from_decimal(amount)?;
+        // 在任何状态变更前整体校验，保证回退时队列未被部分消费
+        let mut fixed_queue: VecDeque<FixedAmount> = self.fund_inflow_queue.iter()
+            .map(|entry| FixedAmount::try_from_decimal(entry.amount))
+            .collect::<Option<_>>()?;
+        
+        let mut personal_deducted = FixedAmount::ZERO;
+        let mut company_deducted = FixedAmount::ZERO;
+        let mut source_consumption: Vec<(String, FixedAmount)> = Vec::new();
+        
+        while !remaining_amount.is_zero() && !self.fund_inflow_queue.is_empty() {
+            let next_index = self.next_deduction_index();
+            let entry_amount = fixed_queue.remove(next_index)?;
+            if let Some(mut entry) = self.fund_inflow_queue.remove(next_index) {
+                let used_amount = remaining_amount.min(entry_amount);
+                
+                if self.base.config.is_personal_fund(&entry.fund_type) {
+                    personal_deducted += used_amount;
+                } else if self.base.config.is_company_fund(&entry.fund_type) {
+                    company_deducted += used_amount;
+                }
+                
+                // 累加到来源明细
+                if let Some(existing) = source_consumption.iter_mut().find(|(s, _)| *s == entry.source) {
+                    existing.1 += used_amount;
+                } else {
+                    source_consumption.push((entry.source.clone(), used_amount));
+                }
+                
+                remaining_amount -= used_amount;
+                
+                // 如果条目还有剩余，放回原位置保持队列顺序稳定
+                if entry_amount > used_amount {
+                    let left = entry_amount - used_amount;
+                    entry.amount = left.to_decimal();
+                    self.fund_inflow_queue.insert(next_index, entry);
+                    fixed_queue.insert(next_index, left);
+                } else {
+                    // 条目已完全消费，回收进对象池供后续流入复用
+                    self.entry_pool.release(entry);
+                }
+            } else {
+                break;
+            }
+        }
+        
+        self.last_outflow_sources = Some(
+            source_consumption.into_iter()
+                .map(|(source, used)| (source, used.to_decimal()))
+                .collect()
+        );
+        
+        let personal_deducted = personal_deducted.to_decimal();
+        let company_deducted = company_deducted.to_decimal();
+        
+        // 更新基础余额
+        FundFlowCommon::update_balances_with_deduction(
+            &mut self.base,
+            personal_deducted,
+            company_deducted,
+        );
+        
+        Some((personal_deducted, company_deducted))
+    }
+    
+    /// Decimal标准路径
+    fn fifo_deduction_decimal(&mut self, amount: Decimal) -> (Decimal, Decimal) {
+        let mut remaining_amount = amount;
+        let mut personal_deducted = Decimal::ZERO;
+        let mut company_deducted = Decimal::ZERO;
+        // 按消费顺序记录各来源的消耗金额（同来源合并）
+        let mut source_consumption: Vec<(String, Decimal)> = Vec::new();
+        
+        // 从队列前端开始消费资金（同时刻条目按配置的平局规则选取）
+        while remaining_amount > Decimal::ZERO && !self.fund_inflow_queue.is_empty() {
+            let next_index = self.next_deduction_index();
+            if let Some(mut entry) = self.fund_inflow_queue.remove(next_index) {
+                let used_amount = remaining_amount.min(entry.amount);
+                
+                if self.base.config.is_personal_fund(&entry.fund_type) {
+                    personal_deducted += used_amount;
+                } else if self.base.config.is_company_fund(&entry.fund_type) {
+                    company_deducted += used_amount;
+                }
+                
+                // 累加到来源明细
+                if let Some(existing) = source_consumption.iter_mut().find(|(s, _)| *s == entry.source) {
+                    existing.1 += used_amount;
+                } else {
+                    source_consumption.push((entry.source.clone(), used_amount));
+                }
+                
+                remaining_amount -= used_amount;
+                
+                // 如果条目还有剩余，放回原位置保持队列顺序稳定
+                if entry.amount > used_amount {
+                    entry.amount -= used_amount;
+                    self.fund_inflow_queue.insert(next_index, entry);
+                } else {
+                    // 条目已完全消费，回收进对象池供后续流入复用
+                    self.entry_pool.release(entry);
+                }
+            } else {
+                break;
+            }
+        }
+        
+        self.last_outflow_sources = Some(source_consumption);
+        
+        // 更新基础余额
+        FundFlowCommon::update_balances_with_deduction(
+            &mut self.base,
+            personal_deducted,
+            company_deducted,
+        );
+        
+        (personal_deducted, company_deducted)
+    }
+    
+    /// 处理普通资金流出
+    /// 对应Python版本的普通支出处理逻辑
+    pub fn process_outflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        _transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+        
+        // 使用FIFO扣除函数，透支模式下缺口部分按归属方计为透支提取
+        let (personal_deduction, company_deduction) = self.fifo_deduction(amount);
+        let (personal_deduction, company_deduction) = FundFlowCommon::cover_shortfall_with_overdraft(
+            &mut self.base,
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+
+        // 计算占比（基于原始金额）
+        let (personal_ratio, company_ratio) = FundFlowCommon::calculate_ratios(
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+        
+        // 分析行为性质
+        let behavior = FundFlowCommon::analyze_common_outflow_behavior(
+            &mut self.base,
+            &mut self.behavior_analyzer,
+            fund_attribute,
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+        
+        // 记录结构化判定依据（挪用/垫付/缺口）
+        self.last_classification_reasons = BehaviorAnalyzer::explain_outflow_classification(
+            fund_attribute,
+            personal_deduction,
+            company_deduction,
+            amount,
+            &self.base.config,
+        );
+        
+        Ok((personal_ratio, company_ratio, behavior))
+    }
+    /// 处理投资产品申购
+    /// 对应Python版本的投资产品申购逻辑
+    pub fn process_investment_purchase(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+        
+        // 使用共同投资处理逻辑，传入FIFO扣除函数
+        let result = FundFlowCommon::process_investment_purchase(
+            &mut self.base,
+            &self.behavior_analyzer,
+            amount,
+            fund_attribute,
+            transaction_date,
+            |base, amount| {
+                // FIFO扣除逻辑的闭包版本
+                let mut temp_tracker = FifoTracker {
+                    base: base.clone(),
+                    behavior_analyzer: BehaviorAnalyzer::new(),
+                    fund_inflow_queue: self.fund_inflow_queue.clone(),
+                    last_outflow_sources: None,
+                    last_classification_reasons: Vec::new(),
+                    entry_pool: self.entry_pool.clone(),
+                };
+                let (personal, company) = temp_tracker.fifo_deduction(amount);
+                // 更新原始base状态
+                base.personal_balance = temp_tracker.base.personal_balance;
+                base.company_balance = temp_tracker.base.company_balance;
+                base.update_total_balance();
+                // 更新队列状态、对象池、来源明细与判定依据
+                self.fund_inflow_queue = temp_tracker.fund_inflow_queue;
+                self.entry_pool = temp_tracker.entry_pool;
+                self.last_outflow_sources = temp_tracker.last_outflow_sources;
+                self.last_classification_reasons = BehaviorAnalyzer::explain_investment_classification(
+                    fund_attribute,
+                    company,
+                );
+                (personal, company)
+            },
+        );
+        
+        result.map_err(AuditError::validation_error)
+    }
+    
+    /// 处理投资产品赎回
+    /// 对应Python版本的投资产品赎回逻辑
+    pub fn process_investment_redemption(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+        
+        // 使用投资产品管理器处理赎回
+        let result = InvestmentPoolManager::process_investment_redemption(
+            &mut self.base,
+            fund_attribute,
+            amount,
+            transaction_date,
+        );
+        
+        match result {
+            Ok((personal_ratio, company_ratio, behavior)) => {
+                // 赎回金额重新进入FIFO队列（来源标注具体产品，支出时可追溯；条目取自对象池）
+                if personal_ratio > Decimal::ZERO {
+                    let entry = self.make_entry(
+                        amount * personal_ratio, "个人", transaction_date,
+                        format!("{fund_attribute}赎回"));
+                    self.fund_inflow_queue.push_back(entry);
+                }
+                if company_ratio > Decimal::ZERO {
+                    let entry = self.make_entry(
+                        amount * company_ratio, "公司", transaction_date,
+                        format!("{fund_attribute}赎回"));
+                    self.fund_inflow_queue.push_back(entry);
+                }
+                
+                // 赎回行不产生来源明细
+                self.last_outflow_sources = None;
+                self.last_classification_reasons.clear();
+                
+                Ok((personal_ratio, company_ratio, behavior))
+            }
+            Err(e) => Err(AuditError::validation_error(e)),
+        }
+    }
+    
+    /// 获取审计摘要
+    /// 对应Python版本的获取状态摘要方法
+    pub fn get_summary(&self) -> AuditResult<AuditSummary> {
+        Ok(SummaryGenerator::generate_audit_summary(&self.base))
+    }
+    
+    /// 获取当前余额占比
+    pub fn get_current_ratios(&self) -> AuditResult<(Decimal, Decimal)> {
+        Ok(self.base.get_current_ratios())
+    }
+    
+    /// 检查是否已初始化
+    #[must_use] 
+    pub fn is_initialized(&self) -> bool {
+        self.base.is_initialized()
+    }
+    
+    /// 获取时序异常记录（赎回早于申购等）
+    #[must_use]
+    pub fn get_ordering_anomalies(&self) -> &[OrderingAnomaly] {
+        &self.base.ordering_anomalies
+    }
+    
+    /// 为尚未关联行号的时序异常回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_anomaly_rows(&mut self, row: usize) {
+        self.base.assign_pending_anomaly_rows(row);
+    }
+    
+    /// 获取资金池盈利重置事件
+    #[must_use]
+    pub fn get_pool_reset_events(&self) -> &[PoolResetEvent] {
+        &self.base.pool_reset_events
+    }
+    
+    /// 为尚未关联行号的资金池重置事件回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_reset_rows(&mut self, row: usize) {
+        self.base.assign_pending_reset_rows(row);
+    }
+
+    /// 获取产品代码归并报告（同一规范代码下出现过的多种原始写法）
+    #[must_use]
+    pub fn get_product_merge_report(&self) -> Vec<ProductMergeRecord> {
+        ProductRegistry::merge_report(&self.base)
+    }
+
+    /// 获取算法名称
+    #[must_use] 
+    pub fn get_name(&self) -> &'static str {
+        "FIFO"
+    }
+    
+    /// 获取算法描述
+    #[must_use] 
+    pub fn get_description(&self) -> &'static str {
+        "先进先出算法 - 按资金流入时间顺序进行扣除"
+    }
+    
+    /// 重置追踪器状态
+    pub fn reset(&mut self) -> AuditResult<()> {
+        self.base.reset();
+        self.behavior_analyzer = BehaviorAnalyzer::new();
+        self.fund_inflow_queue.clear();
+        self.last_outflow_sources = None;
+        Ok(())
+    }
+    
+    /// 生成详细的摘要文本
+    #[must_use] 
+    pub fn generate_detailed_summary_text(&self) -> String {
+        SummaryGenerator::generate_detailed_summary_text(&self.base, "FIFO")
+    }
+    
+    /// 累计单笔交易的按币种净流入（由服务层在配置了币种列时调用）
+    pub fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal) {
+        self.base.record_currency_flow(currency, net_flow);
+    }
+
+    /// 归集利息/手续费行的收支（由服务层逐行调用，命中口径时返回true）
+    pub fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool {
+        self.base.record_interest_fee(fund_attribute, income, expense)
+    }
+
+    /// 获取场外资金池记录管理器
+    #[must_use]
+    pub fn get_offsite_pool_records(&self) -> &crate::data_models::OffsitePoolRecordManager {
+        &self.base.offsite_pool_records
+    }
+    
+    /// 获取投资池数据（用于完整统计计算）
+    #[must_use] 
+    pub fn get_investment_pools(&self) -> &std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool> {
+        &self.base.investment_pools
+    }
+    
+    /// 获取base引用（用于访问场外资金池记录）
+    #[must_use] 
+    pub fn get_base(&self) -> &TrackerBase {
+        &self.base
+    }
+    
+    /// 格式化最近一次支出的资金来源明细（取消耗金额最大的前3项）
+    ///
+    /// 返回形如"个人流入: 1200.00; 理财-X赎回: 300.00"的字符串，
+    /// 格式化判定依据（无挪用/垫付时返回None）
+    #[must_use]
+    pub fn format_classification_reasons(&self) -> Option<String> {
+        if self.last_classification_reasons.is_empty() {
+            None
+        } else {
+            Some(
+                self.last_classification_reasons.iter()
+                    .map(ClassificationReason::format)
+                    .collect::<Vec<_>>()
+                    .join("；")
+            )
+        }
+    }
+    
+    /// 超过3项时其余来源合并为"其他"
+    #[must_use]
+    pub fn format_source_breakdown(&self) -> Option<String> {
+        let sources = self.last_outflow_sources.as_ref()?;
+        if sources.is_empty() {
+            return None;
+        }
+        
+        let mut sorted = sources.clone();
+        sorted.sort_by_key(|(_, amount)| std::cmp::Reverse(*amount));
+        
+        let mut parts: Vec<String> = sorted.iter()
+            .take(3)
+            .map(|(source, amount)| format!("{source}: {amount:.2}"))
+            .collect();
+        
+        if sorted.len() > 3 {
+            let rest: Decimal = sorted[3..].iter().map(|(_, amount)| *amount).sum();
+            parts.push(format!("其他: {rest:.2}"));
+        }
+        
+        Some(parts.join("; "))
+    }
+    
+    /// 获取FIFO队列状态（用于调试）
+    #[must_use] 
+    pub fn get_queue_info(&self) -> String {
+        if self.fund_inflow_queue.is_empty() {
+            "FIFO队列为空".to_string()
+        } else {
+            let mut info = Vec::new();
+            info.push(format!("FIFO队列长度: {}", self.fund_inflow_queue.len()));
+            for (i, entry) in self.fund_inflow_queue.iter().enumerate() {
+                info.push(format!(
+                    "  [{}] {}: ¥{:.2} ({})",
+                    i,
+                    entry.fund_type,
+                    entry.amount,
+                    entry.entry_time.map_or("未知时间".to_string(), |t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                ));
+            }
+            info.join("\n")
+        }
+    }
+    
+}
+
+/// FIFO资金追踪器的公开接口
+/// 
+/// 提供与其他组件集成的API
+impl FifoTracker {
+    /// 获取内部状态（供测试和调试使用）
+    #[cfg(test)]
+    #[must_use] 
+    pub fn get_internal_state(&self) -> (&TrackerBase, &BehaviorAnalyzer, &VecDeque<FundEntry>) {
+        (&self.base, &self.behavior_analyzer, &self.fund_inflow_queue)
+    }
+    
+    /// 更新交易记录的所有计算字段
+    /// 
+    /// 这个方法将当前追踪器的状态同步到Transaction结构中
+    pub fn update_transaction_fields(
+        &self,
+        transaction: &mut Transaction,
+        personal_ratio: Decimal,
+        company_ratio: Decimal,
+        behavior: &str,
+    ) -> AuditResult<()> {
+        // 获取当前摘要状态
+        let summary = self.get_summary()?;
+        
+        // 更新算法计算字段
+        transaction.personal_ratio = Some(personal_ratio);
+        transaction.company_ratio = Some(company_ratio);
+        transaction.behavior_nature = Some(behavior.to_string());
+        
+        // 更新累计字段
+        transaction.cumulative_misappropriation = Some(summary.total_misappropriation);
+        transaction.cumulative_advance = Some(summary.total_advance_payment);
+        transaction.cumulative_company_principal_returned = Some(summary.total_company_principal_returned);
+        transaction.cumulative_personal_principal_returned = Some(summary.total_personal_principal_returned);
+        transaction.cumulative_personal_profit = Some(summary.total_personal_profit);
+        transaction.cumulative_company_profit = Some(summary.total_company_profit);
+        
+        // 更新余额字段
+        transaction.personal_balance = Some(summary.personal_balance);
+        transaction.company_balance = Some(summary.company_balance);
+        transaction.funding_gap = Some(summary.funding_gap);
+        
+        // 支出行附带资金来源明细（队列消耗的前3项来源）
+        transaction.fund_source_breakdown = self.format_source_breakdown();
+        
+        // 挪用/垫付行附带结构化判定依据
+        transaction.behavior_explanation = self.format_classification_reasons();
+        
+        // 修复时间戳格式问题：确保完整的日期时间格式
+        if !transaction.transaction_time.contains('/') && !transaction.transaction_time.contains('-') {
+            // 如果transaction_time只是时间部分，合并日期和时间
+            transaction.transaction_time = transaction.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string();
+        }
+
+        Ok(())
+    }
+
+    /// 导出当前运行状态快照
+    #[must_use]
+    pub fn to_snapshot(&self) -> FifoTrackerSnapshot {
+        FifoTrackerSnapshot {
+            base: self.base.to_snapshot(),
+            behavior_analyzer: self.behavior_analyzer.clone(),
+            fund_inflow_queue: self.fund_inflow_queue.clone(),
+        }
+    }
+
+    /// 从状态快照恢复追踪器
+    ///
+    /// 单行瞬态缓存（支出来源明细、判定依据）置空，
+    /// 下一行处理时会照常重新生成
+    #[must_use]
+    pub fn from_snapshot(config: Config, snapshot: FifoTrackerSnapshot) -> Self {
+        Self {
+            base: TrackerBase::from_snapshot(config, snapshot.base),
+            behavior_analyzer: snapshot.behavior_analyzer,
+            fund_inflow_queue: snapshot.fund_inflow_queue,
+            last_outflow_sources: None,
+            last_classification_reasons: Vec::new(),
+            entry_pool: ObjectPool::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_fifo_tracker_creation() {
+        let config = Config::new();
+        let tracker = FifoTracker::new(config);
+        
+        assert_eq!(tracker.get_name(), "FIFO");
+        assert!(!tracker.is_initialized());
+        assert_eq!(tracker.base.personal_balance, Decimal::ZERO);
+        assert_eq!(tracker.base.company_balance, Decimal::ZERO);
+    }
+    
+    #[test]
+    fn test_initialization() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+        
+        let result = tracker.initialize_balance(Decimal::from(100000), "个人");
+        assert!(result.is_ok());
+        assert!(tracker.is_initialized());
+        assert_eq!(tracker.base.personal_balance, Decimal::from(100000));
+    }
+    
+    #[test]
+    fn test_entry_pool_reuses_consumed_entries() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+
+        tracker.initialize_balance(Decimal::from(100), "个人").unwrap();
+        // 全额支出使初始条目完全消费并回收进对象池
+        tracker.process_outflow(Decimal::from(100), "个人应付", None).unwrap();
+        // 新流入应命中池内回收的条目，而非新分配
+        tracker.process_inflow(Decimal::from(50), "个人应收", None).unwrap();
+
+        let stats = tracker.entry_pool_stats();
+        assert_eq!(stats.releases, 1);
+        assert!(stats.hits >= 1);
+        assert!(stats.hit_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_process_inflow() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+        
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        
+        let result = tracker.process_inflow(
+            Decimal::from(30000),
+            "个人应收",
+            None,
+        );
+        
+        assert!(result.is_ok());
+        let (personal_ratio, company_ratio, behavior) = result.unwrap();
+        assert_eq!(personal_ratio, Decimal::ONE);
+        assert_eq!(company_ratio, Decimal::ZERO);
+        assert!(behavior.contains("个人资金流入"));
+        assert_eq!(tracker.base.personal_balance, Decimal::from(80000));
+    }
+    
+    #[test]
+    fn test_process_outflow() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+        
+        // 初始化混合余额
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(30000), "公司应收", None).unwrap();
+        
+        let result = tracker.process_outflow(
+            Decimal::from(20000),
+            "个人应付",
+            None,
+        );
+        
+        assert!(result.is_ok());
+        let (personal_ratio, _company_ratio, behavior) = result.unwrap();
+        // 应该优先使用个人资金（FIFO）
+        assert!(personal_ratio > Decimal::ZERO);
+        assert!(behavior.contains("个人支付") || behavior.contains("挪用"));
+    }
+    
+    #[test]
+    fn test_process_outflow_with_overdraft() {
+        let mut config = Config::new();
+        config.overdraft.allowed = true;
+        let mut tracker = FifoTracker::new(config);
+
+        tracker.initialize_balance(Decimal::from(10000), "个人").unwrap();
+
+        // 余额不足5000，透支模式下缺口计为公司授信透支
+        let result = tracker.process_outflow(
+            Decimal::from(15000),
+            "个人应付",
+            None,
+        );
+
+        assert!(result.is_ok());
+        let (_, _, behavior) = result.unwrap();
+        assert!(behavior.contains("透支提取：5000.00"), "行为描述缺少透支留痕: {behavior}");
+        assert!(!behavior.contains("资金缺口"));
+
+        // 公司余额转负，摘要中累计透支与资金缺口口径一致
+        assert_eq!(tracker.base.company_balance, Decimal::from(-5000));
+        assert_eq!(tracker.base.total_balance, Decimal::from(-5000));
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.total_overdraft, Decimal::from(5000));
+        // 个人支出动用公司授信构成挪用，资金缺口随之为5000
+        assert_eq!(summary.total_misappropriation, Decimal::from(5000));
+        assert_eq!(summary.funding_gap, Decimal::from(5000));
+        assert!(summary.validate().is_ok(), "透支模式下负余额应通过一致性校验");
+    }
+
+    #[test]
+    fn test_snapshot_restores_queue_and_continues_identically() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config.clone());
+
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(30000), "公司应收", None).unwrap();
+        tracker.process_outflow(Decimal::from(20000), "个人应付", None).unwrap();
+
+        // 经serde序列化往返后恢复，队列与累计状态应无损
+        let snapshot = tracker.to_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored_snapshot: FifoTrackerSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_snapshot, snapshot);
+
+        let mut restored = FifoTracker::from_snapshot(config, restored_snapshot);
+        assert_eq!(restored.to_snapshot(), snapshot);
+
+        // 两个实例继续处理同一笔支出，结果与摘要应一致
+        let original_result = tracker.process_outflow(Decimal::from(40000), "公司应付", None).unwrap();
+        let restored_result = restored.process_outflow(Decimal::from(40000), "公司应付", None).unwrap();
+        assert_eq!(restored_result, original_result);
+        assert_eq!(restored.to_snapshot(), tracker.to_snapshot());
+    }
+
+    #[test]
+    fn test_source_breakdown() {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+        
+        tracker.initialize_balance(Decimal::from(10000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(5000), "公司应收", None).unwrap();
+        
+        // 流入行不产生来源明细
+        assert!(tracker.format_source_breakdown().is_none());
+        
+        // 支出12000：消耗初始个人10000 + 公司流入2000
+        tracker.process_outflow(Decimal::from(12000), "个人应付", None).unwrap();
+        let breakdown = tracker.format_source_breakdown().unwrap();
+        assert!(breakdown.contains("初始个人资金: 10000.00"));
+        assert!(breakdown.contains("公司流入: 2000.00"));
+    }
+
+    /// 构造同时刻个人+公司条目的追踪器（按给定平局规则与入队顺序）
+    fn tie_breaking_tracker(policy: FifoTieBreaking, personal_first_in_queue: bool) -> FifoTracker {
+        let mut config = Config::new();
+        config.fifo.tie_breaking = policy;
+        let mut tracker = FifoTracker::new(config);
+
+        // 初始余额为0，确保队列中只有两个同时刻的流入条目
+        tracker.initialize_balance(Decimal::ZERO, "公司").unwrap();
+
+        let ts = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+            .and_hms_opt(10, 0, 0);
+        let attributes = if personal_first_in_queue {
+            ["个人应收", "公司应收"]
+        } else {
+            ["公司应收", "个人应收"]
+        };
+        for attribute in attributes {
+            tracker.process_inflow(Decimal::from(10000), attribute, ts).unwrap();
+        }
+        tracker
+    }
+
+    #[test]
+    fn test_tie_breaking_default_insertion_order() {
+        // 默认规则：同时刻条目按入队顺序消耗，与历史行为一致
+        assert_eq!(Config::new().fifo.tie_breaking, FifoTieBreaking::InsertionOrder);
+
+        let mut tracker = tie_breaking_tracker(FifoTieBreaking::InsertionOrder, true);
+        let (personal_ratio, company_ratio, _) = tracker
+            .process_outflow(Decimal::from(6000), "个人应付", None)
+            .unwrap();
+
+        assert_eq!(personal_ratio, Decimal::ONE);
+        assert_eq!(company_ratio, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_tie_breaking_company_first() {
+        // 个人条目先入队，但公司优先规则下同时刻支出先消耗公司资金 → 挪用
+        let mut tracker = tie_breaking_tracker(FifoTieBreaking::CompanyFirst, true);
+        let (personal_ratio, company_ratio, behavior) = tracker
+            .process_outflow(Decimal::from(6000), "个人应付", None)
+            .unwrap();
+
+        assert_eq!(personal_ratio, Decimal::ZERO);
+        assert_eq!(company_ratio, Decimal::ONE);
+        assert!(behavior.contains("挪用"));
+    }
+
+    #[test]
+    fn test_tie_breaking_personal_first() {
+        // 公司条目先入队，但个人优先规则下同时刻支出先消耗个人资金
+        let mut tracker = tie_breaking_tracker(FifoTieBreaking::PersonalFirst, false);
+        let (personal_ratio, company_ratio, _) = tracker
+            .process_outflow(Decimal::from(6000), "个人应付", None)
+            .unwrap();
+
+        assert_eq!(personal_ratio, Decimal::ONE);
+        assert_eq!(company_ratio, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_tie_breaking_respects_time_order() {
+        // 平局规则只在同时刻条目之间生效：更早的公司条目仍先于个人条目被消耗
+        let mut config = Config::new();
+        config.fifo.tie_breaking = FifoTieBreaking::PersonalFirst;
+        let mut tracker = FifoTracker::new(config);
+        tracker.initialize_balance(Decimal::ZERO, "公司").unwrap();
+
+        let earlier = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+            .and_hms_opt(9, 0, 0);
+        let later = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+            .and_hms_opt(10, 0, 0);
+        tracker.process_inflow(Decimal::from(10000), "公司应收", earlier).unwrap();
+        tracker.process_inflow(Decimal::from(10000), "个人应收", later).unwrap();
+
+        let (personal_ratio, company_ratio, _) = tracker
+            .process_outflow(Decimal::from(6000), "个人应付", None)
+            .unwrap();
+
+        assert_eq!(personal_ratio, Decimal::ZERO);
+        assert_eq!(company_ratio, Decimal::ONE);
+    }
+
+    /// 构造带混合余额的追踪器（定点/Decimal交叉验证用）
+    #[cfg(feature = "fixed-point")]
+    fn cross_validation_tracker() -> FifoTracker {
+        let config = Config::new();
+        let mut tracker = FifoTracker::new(config);
+        tracker.initialize_balance(Decimal::new(5_000_050, 2), "个人").unwrap(); // 50000.50
+        tracker.process_inflow(Decimal::new(3_000_025, 2), "公司应收", None).unwrap(); // 30000.25
+        tracker.process_inflow(Decimal::new(1_000_000, 2), "个人应收", None).unwrap(); // 10000.00
+        tracker
+    }
+
+    #[cfg(feature = "fixed-point")]
+    #[test]
+    fn test_fixed_point_matches_decimal_path() {
+        // 同一状态分别走定点路径与Decimal路径，结果必须完全一致
+        let mut fixed_tracker = cross_validation_tracker();
+        let mut decimal_tracker = cross_validation_tracker();
+        let amount = Decimal::new(6_500_075, 2); // 65000.75，跨越三个队列条目
+
+        let (fixed_personal, fixed_company) = fixed_tracker.fifo_deduction_fixed(amount).unwrap();
+        let (decimal_personal, decimal_company) = decimal_tracker.fifo_deduction_decimal(amount);
+
+        assert_eq!(fixed_personal, decimal_personal);
+        assert_eq!(fixed_company, decimal_company);
+        assert_eq!(fixed_tracker.base.personal_balance, decimal_tracker.base.personal_balance);
+        assert_eq!(fixed_tracker.base.company_balance, decimal_tracker.base.company_balance);
+        assert_eq!(fixed_tracker.last_outflow_sources, decimal_tracker.last_outflow_sources);
+        assert_eq!(fixed_tracker.fund_inflow_queue.len(), decimal_tracker.fund_inflow_queue.len());
+        for (fixed_entry, decimal_entry) in fixed_tracker.fund_inflow_queue.iter()
+            .zip(&decimal_tracker.fund_inflow_queue) {
+            assert_eq!(fixed_entry.amount, decimal_entry.amount);
+        }
+    }
+
+    #[cfg(feature = "fixed-point")]
+    #[test]
+    fn test_fixed_point_falls_back_on_sub_cent_amounts() {
+        // 小数位超过2位的金额无法映射为分计数，必须整体回退且不动队列
+        let mut tracker = cross_validation_tracker();
+        let queue_before = tracker.fund_inflow_queue.clone();
+
+        assert!(tracker.fifo_deduction_fixed(Decimal::new(1_234_567, 3)).is_none());
+        assert_eq!(tracker.fund_inflow_queue.len(), queue_before.len());
+        for (entry, before) in tracker.fund_inflow_queue.iter().zip(&queue_before) {
+            assert_eq!(entry.amount, before.amount);
+        }
+    }
+
+    #[test]
+    fn test_tie_breaking_noted_in_summary() {
+        let tracker = tie_breaking_tracker(FifoTieBreaking::CompanyFirst, true);
+        let summary_text = tracker.generate_detailed_summary_text();
+        assert!(summary_text.contains("同时刻资金条目平局规则: 公司资金优先（COMPANY_FIRST）"));
+    }
+}
\ No newline at end of file
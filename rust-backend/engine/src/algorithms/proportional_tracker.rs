@@ -0,0 +1,516 @@
+//! 按比例混同法追踪器实现
+//!
+//! 基于新共享架构实现，使用TrackerBase作为基础状态管理。
+//! 司法实践中的第三种常用口径：资金混同后不再区分先后，
+//! 每笔支出按当前个人/公司余额占比分摊扣除。
+
+use super::shared::{
+    TrackerBase, TrackerBaseSnapshot, BehaviorAnalyzer, ClassificationReason, InvestmentPoolManager, FundFlowCommon, SummaryGenerator,
+    OrderingAnomaly, PoolResetEvent, ProductRegistry, ProductMergeRecord,
+};
+use crate::data_models::{Config, AuditSummary, Transaction};
+use crate::errors::{AuditError, AuditResult};
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// 按比例混同法追踪器
+///
+/// 基于共享架构实现按比例混同法的资金追踪算法
+/// 特点：每笔支出按当前个人/公司余额占比分摊扣除，不考虑资金属性与先后顺序
+#[derive(Debug, Clone)]
+pub struct ProportionalTracker {
+    /// 共享基础状态（13个状态变量）
+    base: TrackerBase,
+    /// 行为分析器（挪用垫付分析）
+    behavior_analyzer: BehaviorAnalyzer,
+    /// 最近一次支出触发的判定依据（挪用/垫付原因码）
+    last_classification_reasons: Vec<ClassificationReason>,
+}
+
+/// 按比例混同法追踪器状态快照
+///
+/// 覆盖基础状态与行为分析器累计值，用于检查点与增量分析。
+/// 判定依据等单行瞬态缓存在每行处理时被重置，不纳入快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProportionalTrackerSnapshot {
+    /// 共享基础状态快照
+    pub base: TrackerBaseSnapshot,
+    /// 行为分析器累计状态
+    pub behavior_analyzer: BehaviorAnalyzer,
+}
+
+impl ProportionalTracker {
+    /// 创建新的按比例混同法追踪器
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        Self {
+            base: TrackerBase::new(config),
+            behavior_analyzer: BehaviorAnalyzer::new(),
+            last_classification_reasons: Vec::new(),
+        }
+    }
+
+    /// 初始化余额
+    pub fn initialize_balance(&mut self, initial_balance: Decimal, balance_type: &str) -> AuditResult<()> {
+        // 使用基础类初始化
+        self.base.initialize_balance(initial_balance, balance_type)
+    }
+
+    /// 处理资金流入
+    pub fn process_inflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+
+        // 流入逻辑与其他算法一致：按资金属性归入对应账户
+        let (personal_ratio, company_ratio, behavior) = FundFlowCommon::process_fund_inflow(
+            &mut self.base,
+            amount,
+            fund_attribute,
+            transaction_date,
+        );
+
+        // 流入行不产生判定依据
+        self.last_classification_reasons.clear();
+
+        Ok((personal_ratio, company_ratio, behavior))
+    }
+
+    /// 按比例分摊扣除
+    ///
+    /// 按扣除前的个人/公司余额占比拆分支出金额，
+    /// 个人份额先计算，公司份额取余数以保证两者之和等于实际扣除额
+    fn proportional_deduction(base: &mut TrackerBase, amount: Decimal) -> (Decimal, Decimal) {
+        let personal_balance = base.personal_balance;
+        let company_balance = base.company_balance;
+        let total = personal_balance + company_balance;
+
+        if total <= Decimal::ZERO {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+
+        // 超出总余额的部分形成资金缺口，不参与分摊
+        let available = amount.min(total);
+        let personal_deducted = (available * personal_balance / total).min(personal_balance);
+        let company_deducted = (available - personal_deducted).min(company_balance);
+
+        // 更新基础余额
+        FundFlowCommon::update_balances_with_deduction(
+            base,
+            personal_deducted,
+            company_deducted,
+        );
+
+        (personal_deducted, company_deducted)
+    }
+
+    /// 处理普通资金流出
+    pub fn process_outflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        _transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+
+        // 按当前余额占比分摊扣除，不区分资金属性，
+        // 透支模式下缺口部分按归属方计为透支提取
+        let (personal_deduction, company_deduction) = Self::proportional_deduction(&mut self.base, amount);
+        let (personal_deduction, company_deduction) = FundFlowCommon::cover_shortfall_with_overdraft(
+            &mut self.base,
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+
+        // 计算占比（基于原始金额）
+        let (personal_ratio, company_ratio) = FundFlowCommon::calculate_ratios(
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+
+        // 分析行为性质
+        let behavior = FundFlowCommon::analyze_common_outflow_behavior(
+            &mut self.base,
+            &mut self.behavior_analyzer,
+            fund_attribute,
+            personal_deduction,
+            company_deduction,
+            amount,
+        );
+
+        // 记录结构化判定依据（挪用/垫付/缺口）
+        self.last_classification_reasons = BehaviorAnalyzer::explain_outflow_classification(
+            fund_attribute,
+            personal_deduction,
+            company_deduction,
+            amount,
+            &self.base.config,
+        );
+
+        Ok((personal_ratio, company_ratio, behavior))
+    }
+
+    /// 处理投资产品申购
+    pub fn process_investment_purchase(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+
+        // 使用共同投资处理逻辑，申购同样按余额占比分摊扣除
+        let result = FundFlowCommon::process_investment_purchase(
+            &mut self.base,
+            &self.behavior_analyzer,
+            amount,
+            fund_attribute,
+            transaction_date,
+            |base, amount| {
+                let (personal_deducted, company_deducted) = Self::proportional_deduction(base, amount);
+
+                // 记录投资挪用判定依据
+                self.last_classification_reasons = BehaviorAnalyzer::explain_investment_classification(
+                    fund_attribute,
+                    company_deducted,
+                );
+
+                (personal_deducted, company_deducted)
+            },
+        );
+
+        result.map_err(AuditError::validation_error)
+    }
+
+    /// 处理投资产品赎回
+    pub fn process_investment_redemption(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)> {
+        if !self.base.is_initialized() {
+            return Err(AuditError::validation_error("追踪器未初始化"));
+        }
+
+        // 使用投资产品管理器处理赎回
+        let result = InvestmentPoolManager::process_investment_redemption(
+            &mut self.base,
+            fund_attribute,
+            amount,
+            transaction_date,
+        );
+
+        match result {
+            Ok(result) => Ok(result),
+            Err(e) => Err(AuditError::validation_error(e)),
+        }
+    }
+
+    /// 获取审计摘要
+    pub fn get_summary(&self) -> AuditResult<AuditSummary> {
+        Ok(SummaryGenerator::generate_audit_summary(&self.base))
+    }
+
+    /// 获取当前余额占比
+    pub fn get_current_ratios(&self) -> AuditResult<(Decimal, Decimal)> {
+        Ok(self.base.get_current_ratios())
+    }
+
+    /// 检查是否已初始化
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.base.is_initialized()
+    }
+
+    /// 获取时序异常记录（赎回早于申购等）
+    #[must_use]
+    pub fn get_ordering_anomalies(&self) -> &[OrderingAnomaly] {
+        &self.base.ordering_anomalies
+    }
+
+    /// 为尚未关联行号的时序异常回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_anomaly_rows(&mut self, row: usize) {
+        self.base.assign_pending_anomaly_rows(row);
+    }
+
+    /// 获取资金池盈利重置事件
+    #[must_use]
+    pub fn get_pool_reset_events(&self) -> &[PoolResetEvent] {
+        &self.base.pool_reset_events
+    }
+
+    /// 为尚未关联行号的资金池重置事件回填行号（由服务层在处理单行后调用）
+    pub fn assign_pending_reset_rows(&mut self, row: usize) {
+        self.base.assign_pending_reset_rows(row);
+    }
+
+    /// 获取产品代码归并报告（同一规范代码下出现过的多种原始写法）
+    #[must_use]
+    pub fn get_product_merge_report(&self) -> Vec<ProductMergeRecord> {
+        ProductRegistry::merge_report(&self.base)
+    }
+
+    /// 获取算法名称
+    #[must_use]
+    pub fn get_name(&self) -> &'static str {
+        "PROPORTIONAL"
+    }
+
+    /// 获取算法描述
+    #[must_use]
+    pub fn get_description(&self) -> &'static str {
+        "按比例混同法 - 每笔支出按当前个人/公司余额占比分摊"
+    }
+
+    /// 重置追踪器状态
+    pub fn reset(&mut self) -> AuditResult<()> {
+        self.base.reset();
+        self.behavior_analyzer = BehaviorAnalyzer::new();
+        self.last_classification_reasons.clear();
+        Ok(())
+    }
+
+    /// 生成详细的摘要文本
+    #[must_use]
+    pub fn generate_detailed_summary_text(&self) -> String {
+        SummaryGenerator::generate_detailed_summary_text(&self.base, "按比例混同法")
+    }
+
+    /// 累计单笔交易的按币种净流入（由服务层在配置了币种列时调用）
+    pub fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal) {
+        self.base.record_currency_flow(currency, net_flow);
+    }
+
+    /// 归集利息/手续费行的收支（由服务层逐行调用，命中口径时返回true）
+    pub fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool {
+        self.base.record_interest_fee(fund_attribute, income, expense)
+    }
+
+    /// 获取场外资金池记录管理器
+    #[must_use]
+    pub fn get_offsite_pool_records(&self) -> &crate::data_models::OffsitePoolRecordManager {
+        &self.base.offsite_pool_records
+    }
+
+    /// 获取投资池数据（用于完整统计计算）
+    #[must_use]
+    pub fn get_investment_pools(&self) -> &std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool> {
+        &self.base.investment_pools
+    }
+
+    /// 获取base引用（用于访问场外资金池记录）
+    #[must_use]
+    pub fn get_base(&self) -> &TrackerBase {
+        &self.base
+    }
+
+    /// 导出当前运行状态快照
+    #[must_use]
+    pub fn to_snapshot(&self) -> ProportionalTrackerSnapshot {
+        ProportionalTrackerSnapshot {
+            base: self.base.to_snapshot(),
+            behavior_analyzer: self.behavior_analyzer.clone(),
+        }
+    }
+
+    /// 从状态快照恢复追踪器
+    ///
+    /// 单行瞬态缓存（判定依据）置空，下一行处理时会照常重新生成
+    #[must_use]
+    pub fn from_snapshot(config: Config, snapshot: ProportionalTrackerSnapshot) -> Self {
+        Self {
+            base: TrackerBase::from_snapshot(config, snapshot.base),
+            behavior_analyzer: snapshot.behavior_analyzer,
+            last_classification_reasons: Vec::new(),
+        }
+    }
+
+    /// 格式化判定依据（无挪用/垫付时返回None）
+    #[must_use]
+    pub fn format_classification_reasons(&self) -> Option<String> {
+        if self.last_classification_reasons.is_empty() {
+            None
+        } else {
+            Some(
+                self.last_classification_reasons.iter()
+                    .map(ClassificationReason::format)
+                    .collect::<Vec<_>>()
+                    .join("；")
+            )
+        }
+    }
+}
+
+/// 按比例混同法追踪器的公开接口
+///
+/// 提供与其他组件集成的API
+impl ProportionalTracker {
+    /// 更新交易记录的所有计算字段
+    ///
+    /// 这个方法将当前追踪器的状态同步到Transaction结构中
+    pub fn update_transaction_fields(
+        &self,
+        transaction: &mut Transaction,
+        personal_ratio: Decimal,
+        company_ratio: Decimal,
+        behavior: &str,
+    ) -> AuditResult<()> {
+        // 获取当前摘要状态
+        let summary = self.get_summary()?;
+
+        // 更新算法计算字段
+        transaction.personal_ratio = Some(personal_ratio);
+        transaction.company_ratio = Some(company_ratio);
+        transaction.behavior_nature = Some(behavior.to_string());
+
+        // 挪用/垫付行附带结构化判定依据
+        transaction.behavior_explanation = self.format_classification_reasons();
+
+        // 更新累计字段
+        transaction.cumulative_misappropriation = Some(summary.total_misappropriation);
+        transaction.cumulative_advance = Some(summary.total_advance_payment);
+        transaction.cumulative_company_principal_returned = Some(summary.total_company_principal_returned);
+        transaction.cumulative_personal_principal_returned = Some(summary.total_personal_principal_returned);
+        transaction.cumulative_personal_profit = Some(summary.total_personal_profit);
+        transaction.cumulative_company_profit = Some(summary.total_company_profit);
+
+        // 更新余额字段
+        transaction.personal_balance = Some(summary.personal_balance);
+        transaction.company_balance = Some(summary.company_balance);
+        transaction.funding_gap = Some(summary.funding_gap);
+
+        // 修复时间戳格式问题：确保完整的日期时间格式
+        if !transaction.transaction_time.contains('/') && !transaction.transaction_time.contains('-') {
+            // 如果transaction_time只是时间部分，合并日期和时间
+            transaction.transaction_time = transaction.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proportional_tracker_creation() {
+        let config = Config::new();
+        let tracker = ProportionalTracker::new(config);
+
+        assert_eq!(tracker.get_name(), "PROPORTIONAL");
+        assert!(!tracker.is_initialized());
+        assert_eq!(tracker.base.personal_balance, Decimal::ZERO);
+        assert_eq!(tracker.base.company_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_proportional_deduction_split() {
+        let config = Config::new();
+        let mut tracker = ProportionalTracker::new(config);
+
+        // 设置初始余额：个人60000，公司40000（个人占比0.6）
+        tracker.initialize_balance(Decimal::from(60000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(40000), "公司应收", None).unwrap();
+
+        // 流出50000，应按0.6/0.4分摊：个人30000 + 公司20000
+        let result = tracker.process_outflow(
+            Decimal::from(50000),
+            "其他支出",
+            None,
+        );
+
+        assert!(result.is_ok());
+        let (personal_ratio, company_ratio, _behavior) = result.unwrap();
+
+        assert_eq!(personal_ratio, Decimal::new(6, 1)); // 30000/50000 = 0.6
+        assert_eq!(company_ratio, Decimal::new(4, 1)); // 20000/50000 = 0.4
+        assert_eq!(tracker.base.personal_balance, Decimal::from(30000)); // 60000-30000
+        assert_eq!(tracker.base.company_balance, Decimal::from(20000)); // 40000-20000
+    }
+
+    #[test]
+    fn test_proportional_misappropriation() {
+        let config = Config::new();
+        let mut tracker = ProportionalTracker::new(config);
+
+        // 设置初始余额：个人20000，公司80000（公司占比0.8）
+        tracker.initialize_balance(Decimal::from(20000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(80000), "公司应收", None).unwrap();
+
+        // 个人支出50000，按比例有40000来自公司资金，构成挪用
+        let result = tracker.process_outflow(
+            Decimal::from(50000),
+            "个人应付",
+            None,
+        );
+
+        assert!(result.is_ok());
+        let (personal_ratio, company_ratio, behavior) = result.unwrap();
+
+        assert_eq!(personal_ratio, Decimal::new(2, 1)); // 10000/50000 = 0.2
+        assert_eq!(company_ratio, Decimal::new(8, 1)); // 40000/50000 = 0.8
+        assert!(behavior.contains("挪用")); // 个人支出使用公司资金构成挪用
+        assert_eq!(tracker.base.personal_balance, Decimal::from(10000)); // 20000-10000
+        assert_eq!(tracker.base.company_balance, Decimal::from(40000)); // 80000-40000
+    }
+
+    #[test]
+    fn test_proportional_deduction_exceeds_balance() {
+        let config = Config::new();
+        let mut tracker = ProportionalTracker::new(config);
+
+        // 总余额100000，支出120000，超出部分形成资金缺口
+        tracker.initialize_balance(Decimal::from(60000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(40000), "公司应收", None).unwrap();
+
+        let result = tracker.process_outflow(
+            Decimal::from(120000),
+            "其他支出",
+            None,
+        );
+
+        assert!(result.is_ok());
+        // 仅可用的100000参与分摊，两个账户都被清零
+        assert_eq!(tracker.base.personal_balance, Decimal::ZERO);
+        assert_eq!(tracker.base.company_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_and_identical_continuation() {
+        let config = Config::new();
+        let mut tracker = ProportionalTracker::new(config.clone());
+
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        tracker.process_inflow(Decimal::from(30000), "公司应收", None).unwrap();
+        tracker.process_outflow(Decimal::from(60000), "个人应付", None).unwrap();
+
+        // 经serde序列化往返后恢复，累计状态应无损
+        let snapshot = tracker.to_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored_snapshot: ProportionalTrackerSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_snapshot, snapshot);
+
+        let mut restored = ProportionalTracker::from_snapshot(config, restored_snapshot);
+        assert_eq!(restored.to_snapshot(), snapshot);
+
+        // 两个实例继续处理同一笔支出，结果与状态应一致
+        let original_result = tracker.process_outflow(Decimal::from(10000), "公司应付", None).unwrap();
+        let restored_result = restored.process_outflow(Decimal::from(10000), "公司应付", None).unwrap();
+        assert_eq!(restored_result, original_result);
+        assert_eq!(restored.to_snapshot(), tracker.to_snapshot());
+    }
+}
@@ -1,16 +1,18 @@
 //! 行为分析器实现
 //! 
 //! 负责分析交易行为性质，判断是否构成挪用或垫付
-//! 完全对应Python版本的BehaviorAnalyzer类功能
+//! `完全对应Python版本的BehaviorAnalyzer类功能`
 
-use crate::data_models::Config;
+use crate::algorithms::shared::behavior_record::{render_records, BehaviorKind, BehaviorRecord};
+use crate::data_models::{Config, HeaderLanguage};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 /// 行为分析器
-/// 
+///
 /// 分析交易的行为性质，判断挪用、垫付等情况
-/// 完全对应Python版本的BehaviorAnalyzer类
-#[derive(Debug, Clone)]
+/// `完全对应Python版本的BehaviorAnalyzer类`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BehaviorAnalyzer {
     /// 累计挪用金额（个人使用公司资金）
     pub total_misappropriation: Decimal,
@@ -20,6 +22,7 @@ pub struct BehaviorAnalyzer {
 
 impl BehaviorAnalyzer {
     /// 创建新的行为分析器
+    #[must_use] 
     pub fn new() -> Self {
         Self {
             total_misappropriation: Decimal::ZERO,
@@ -48,12 +51,31 @@ impl BehaviorAnalyzer {
         total_amount: Decimal,
         config: &Config,
     ) -> String {
+        let records = self.analyze_behavior_records(
+            fund_attribute, personal_deduction, company_deduction, total_amount, config,
+        );
+        render_records(&records, HeaderLanguage::Chinese)
+    }
+
+    /// 分析行为性质，产出结构化记录（类别 + 金额）
+    ///
+    /// [`Self::analyze_behavior_nature`]的结构化版本：文案渲染交给
+    /// 本地化层（见`behavior_record`），导出与GUI可按语言重新渲染
+    /// 而无需重跑分析；累计挪用/垫付统计在此处累加
+    pub fn analyze_behavior_records(
+        &mut self,
+        fund_attribute: &str,
+        personal_deduction: Decimal,
+        company_deduction: Decimal,
+        total_amount: Decimal,
+        config: &Config,
+    ) -> Vec<BehaviorRecord> {
         if total_amount <= Decimal::ZERO {
-            return "无交易".to_string();
+            return vec![BehaviorRecord::marker(BehaviorKind::NoTransaction)];
         }
 
-        let mut behavior_descriptions = Vec::new();
-        
+        let mut records = Vec::new();
+
         // 判断资金属性类型
         let fund_type = self.determine_fund_attribute_type(fund_attribute, config);
 
@@ -64,11 +86,11 @@ impl BehaviorAnalyzer {
                     // 个人支出使用了公司资金 - 构成挪用
                     self.total_misappropriation += company_deduction;
                     self.total_misappropriation = self.format_number(self.total_misappropriation);
-                    behavior_descriptions.push(format!("挪用：{:.2}", company_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::Misappropriation, company_deduction));
                 }
                 if personal_deduction > Decimal::ZERO {
                     // 个人支出使用了个人资金 - 正常
-                    behavior_descriptions.push(format!("个人支付：{:.2}", personal_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::PersonalPayment, personal_deduction));
                 }
             }
             FundAttributeType::Company => {
@@ -77,29 +99,28 @@ impl BehaviorAnalyzer {
                     // 公司支出使用了个人资金 - 构成垫付
                     self.total_advance_payment += personal_deduction;
                     self.total_advance_payment = self.format_number(self.total_advance_payment);
-                    behavior_descriptions.push(format!("垫付：{:.2}", personal_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::AdvancePayment, personal_deduction));
                 }
                 if company_deduction > Decimal::ZERO {
                     // 公司支出使用了公司资金 - 正常
-                    behavior_descriptions.push(format!("公司支付：{:.2}", company_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::CompanyPayment, company_deduction));
                 }
             }
             FundAttributeType::Other => {
                 // 其他类型
                 if personal_deduction > Decimal::ZERO {
-                    behavior_descriptions.push(format!("个人支付：{:.2}", personal_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::PersonalPayment, personal_deduction));
                 }
                 if company_deduction > Decimal::ZERO {
-                    behavior_descriptions.push(format!("公司支付：{:.2}", company_deduction));
+                    records.push(BehaviorRecord::with_amount(BehaviorKind::CompanyPayment, company_deduction));
                 }
             }
         }
 
-        if behavior_descriptions.is_empty() {
-            "无明确行为".to_string()
-        } else {
-            behavior_descriptions.join("；")
+        if records.is_empty() {
+            records.push(BehaviorRecord::marker(BehaviorKind::NoClearBehavior));
         }
+        records
     }
 
     /// 分析投资行为
@@ -113,31 +134,47 @@ impl BehaviorAnalyzer {
     /// 
     /// # Returns
     /// (行为性质, 挪用金额)
+    #[must_use] 
     pub fn analyze_investment_behavior(
         &self,
         personal_deduction: Decimal,
         company_deduction: Decimal,
     ) -> (String, Decimal) {
+        let (records, misappropriation_amount) =
+            self.analyze_investment_records(personal_deduction, company_deduction);
+        (render_records(&records, HeaderLanguage::Chinese), misappropriation_amount)
+    }
+
+    /// 分析投资行为，产出结构化记录
+    ///
+    /// [`Self::analyze_investment_behavior`]的结构化版本
+    ///
+    /// # Returns
+    /// (行为记录, 挪用金额)
+    #[must_use]
+    pub fn analyze_investment_records(
+        &self,
+        personal_deduction: Decimal,
+        company_deduction: Decimal,
+    ) -> (Vec<BehaviorRecord>, Decimal) {
         let mut misappropriation_amount = Decimal::ZERO;
-        let mut behavior_descriptions = Vec::new();
+        let mut records = Vec::new();
 
         if company_deduction > Decimal::ZERO {
             // 投资使用公司资金构成挪用
             misappropriation_amount = company_deduction;
-            behavior_descriptions.push(format!("投资挪用：{:.2}", company_deduction));
+            records.push(BehaviorRecord::with_amount(BehaviorKind::InvestmentMisappropriation, company_deduction));
         }
 
         if personal_deduction > Decimal::ZERO {
-            behavior_descriptions.push(format!("个人投资：{:.2}", personal_deduction));
+            records.push(BehaviorRecord::with_amount(BehaviorKind::PersonalInvestment, personal_deduction));
         }
 
-        let behavior_nature = if behavior_descriptions.is_empty() {
-            "无投资".to_string()
-        } else {
-            behavior_descriptions.join("；")
-        };
+        if records.is_empty() {
+            records.push(BehaviorRecord::marker(BehaviorKind::NoInvestment));
+        }
 
-        (behavior_nature, misappropriation_amount)
+        (records, misappropriation_amount)
     }
 
     /// 分析收益分配
@@ -151,6 +188,7 @@ impl BehaviorAnalyzer {
     /// 
     /// # Returns
     /// (个人收益, 公司收益, 非法所得)
+    #[must_use] 
     pub fn analyze_profit_distribution(
         &self,
         profit: Decimal,
@@ -178,6 +216,7 @@ impl BehaviorAnalyzer {
     /// 
     /// # Returns
     /// (累计挪用金额, 累计垫付金额)
+    #[must_use] 
     pub fn get_cumulative_stats(&self) -> (Decimal, Decimal) {
         (self.total_misappropriation, self.total_advance_payment)
     }
@@ -190,6 +229,70 @@ impl BehaviorAnalyzer {
         self.total_advance_payment = Decimal::ZERO;
     }
 
+    /// 生成普通支出的判定依据（不修改累计统计）
+    /// 
+    /// 与`analyze_behavior_nature`的判定规则一一对应，
+    /// 产出结构化原因码供导出说明列使用
+    #[must_use]
+    pub fn explain_outflow_classification(
+        fund_attribute: &str,
+        personal_deduction: Decimal,
+        company_deduction: Decimal,
+        original_amount: Decimal,
+        config: &Config,
+    ) -> Vec<ClassificationReason> {
+        let mut reasons = Vec::new();
+        if original_amount <= Decimal::ZERO {
+            return reasons;
+        }
+
+        let attribute = fund_attribute.trim();
+        if config.is_personal_fund(attribute) && company_deduction > Decimal::ZERO {
+            reasons.push(ClassificationReason {
+                code: "MISAPPROPRIATION",
+                message: format!(
+                    "个人属性支出（{attribute}）共{original_amount:.2}，个人资金仅承担{personal_deduction:.2}，动用公司资金{company_deduction:.2}"
+                ),
+            });
+        } else if config.is_company_fund(attribute) && personal_deduction > Decimal::ZERO {
+            reasons.push(ClassificationReason {
+                code: "ADVANCE_PAYMENT",
+                message: format!(
+                    "公司属性支出（{attribute}）共{original_amount:.2}，公司资金仅承担{company_deduction:.2}，由个人资金垫付{personal_deduction:.2}"
+                ),
+            });
+        }
+
+        // 两类资金合计仍不足时记录缺口
+        let funding_gap = original_amount - personal_deduction - company_deduction;
+        if funding_gap > Decimal::new(1, 2) {
+            reasons.push(ClassificationReason {
+                code: "FUNDING_GAP",
+                message: format!("个人与公司资金合计仍不足，资金缺口{funding_gap:.2}"),
+            });
+        }
+
+        reasons
+    }
+
+    /// 生成投资申购的判定依据（投资属个人行为，动用公司资金即挪用）
+    #[must_use]
+    pub fn explain_investment_classification(
+        fund_attribute: &str,
+        company_deduction: Decimal,
+    ) -> Vec<ClassificationReason> {
+        if company_deduction > Decimal::ZERO {
+            vec![ClassificationReason {
+                code: "INVESTMENT_MISAPPROPRIATION",
+                message: format!(
+                    "投资申购（{fund_attribute}）动用公司资金{company_deduction:.2}，投资属个人行为，构成挪用"
+                ),
+            }]
+        } else {
+            vec![]
+        }
+    }
+
     /// 判断资金属性类型
     /// 
     /// 对应Python版本的`_判断资金属性类型`方法
@@ -207,7 +310,7 @@ impl BehaviorAnalyzer {
 
     /// 数值格式化处理
     /// 
-    /// 对应Python版本的Config.format_number功能
+    /// `对应Python版本的Config.format_number功能`
     fn format_number(&self, value: Decimal) -> Decimal {
         // 处理极小值，避免科学计数法显示
         if value.abs() < Decimal::new(1, 10) { // 小于0.0000000001
@@ -218,6 +321,26 @@ impl BehaviorAnalyzer {
     }
 }
 
+/// 判定依据 - 结构化原因码
+/// 
+/// 记录某行交易被判定为挪用/垫付时触发的规则与数据，
+/// 供导出时生成"判定依据"说明列
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationReason {
+    /// 原因码（如 MISAPPROPRIATION / `ADVANCE_PAYMENT`）
+    pub code: &'static str,
+    /// 人类可读的判定说明（含触发数据）
+    pub message: String,
+}
+
+impl ClassificationReason {
+    /// 格式化为"[原因码] 说明"形式
+    #[must_use]
+    pub fn format(&self) -> String {
+        format!("[{}] {}", self.code, self.message)
+    }
+}
+
 /// 资金属性类型
 /// 
 /// 对应Python版本中的资金属性分类逻辑
@@ -328,6 +451,44 @@ mod tests {
         assert_eq!(advance_payment, Decimal::from(1500));  // 垫付总额
     }
 
+    #[test]
+    fn test_explain_outflow_classification() {
+        let config = Config::new();
+
+        // 个人支出动用公司资金 → 挪用原因码
+        let reasons = BehaviorAnalyzer::explain_outflow_classification(
+            "个人应付",
+            Decimal::from(5000),
+            Decimal::from(3000),
+            Decimal::from(8000),
+            &config,
+        );
+        assert_eq!(reasons.len(), 1);
+        assert_eq!(reasons[0].code, "MISAPPROPRIATION");
+        assert!(reasons[0].message.contains("动用公司资金3000.00"));
+
+        // 全部由个人资金承担 → 无判定依据
+        let reasons = BehaviorAnalyzer::explain_outflow_classification(
+            "个人应付",
+            Decimal::from(8000),
+            Decimal::ZERO,
+            Decimal::from(8000),
+            &config,
+        );
+        assert!(reasons.is_empty());
+
+        // 资金不足 → 缺口原因码
+        let reasons = BehaviorAnalyzer::explain_outflow_classification(
+            "公司应付",
+            Decimal::from(1000),
+            Decimal::from(2000),
+            Decimal::from(5000),
+            &config,
+        );
+        assert!(reasons.iter().any(|r| r.code == "ADVANCE_PAYMENT"));
+        assert!(reasons.iter().any(|r| r.code == "FUNDING_GAP"));
+    }
+
     #[test]
     fn test_reset_stats() {
         let mut analyzer = BehaviorAnalyzer::new();
@@ -0,0 +1,226 @@
+//! 结构化行为记录
+//!
+//! 行为性质原本直接生成中文文案（"挪用：3000.00"等），导出与GUI
+//! 切换语言只能重跑分析。本模块把行为判定拆成结构化记录
+//! （类别枚举 + 金额），文案由本地化层按语言渲染；同时提供对
+//! 已落盘中文文案的解析回读，使历史结果也能按语言重新渲染。
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::data_models::HeaderLanguage;
+
+/// 行为类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorKind {
+    /// 挪用（个人支出动用公司资金）
+    Misappropriation,
+    /// 个人支付（个人支出使用个人资金）
+    PersonalPayment,
+    /// 垫付（公司支出动用个人资金）
+    AdvancePayment,
+    /// 公司支付（公司支出使用公司资金）
+    CompanyPayment,
+    /// 投资挪用（投资申购动用公司资金）
+    InvestmentMisappropriation,
+    /// 个人投资（投资申购使用个人资金）
+    PersonalInvestment,
+    /// 无交易（金额为零）
+    NoTransaction,
+    /// 无明确行为
+    NoClearBehavior,
+    /// 无投资
+    NoInvestment,
+}
+
+impl BehaviorKind {
+    /// 中文标签（与历史文案逐字一致，保证回读兼容）
+    #[must_use]
+    pub fn label_zh(self) -> &'static str {
+        match self {
+            Self::Misappropriation => "挪用",
+            Self::PersonalPayment => "个人支付",
+            Self::AdvancePayment => "垫付",
+            Self::CompanyPayment => "公司支付",
+            Self::InvestmentMisappropriation => "投资挪用",
+            Self::PersonalInvestment => "个人投资",
+            Self::NoTransaction => "无交易",
+            Self::NoClearBehavior => "无明确行为",
+            Self::NoInvestment => "无投资",
+        }
+    }
+
+    /// 英文标签
+    #[must_use]
+    pub fn label_en(self) -> &'static str {
+        match self {
+            Self::Misappropriation => "Misappropriation",
+            Self::PersonalPayment => "Personal Payment",
+            Self::AdvancePayment => "Advance Payment",
+            Self::CompanyPayment => "Company Payment",
+            Self::InvestmentMisappropriation => "Investment Misappropriation",
+            Self::PersonalInvestment => "Personal Investment",
+            Self::NoTransaction => "No Transaction",
+            Self::NoClearBehavior => "No Identified Behavior",
+            Self::NoInvestment => "No Investment",
+        }
+    }
+
+    /// 按中文标签回查类别（用于解析已落盘文案）
+    #[must_use]
+    pub fn from_label_zh(label: &str) -> Option<Self> {
+        [
+            Self::Misappropriation,
+            Self::PersonalPayment,
+            Self::AdvancePayment,
+            Self::CompanyPayment,
+            Self::InvestmentMisappropriation,
+            Self::PersonalInvestment,
+            Self::NoTransaction,
+            Self::NoClearBehavior,
+            Self::NoInvestment,
+        ]
+        .into_iter()
+        .find(|kind| kind.label_zh() == label)
+    }
+}
+
+/// 结构化行为记录（类别 + 金额）
+///
+/// 一行交易可能同时产生多条记录（如部分挪用部分个人支付），
+/// 渲染时按中文"；"/英文"; "连接
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BehaviorRecord {
+    /// 行为类别
+    pub kind: BehaviorKind,
+    /// 涉及金额（"无交易"等占位类别无金额）
+    pub amount: Option<Decimal>,
+}
+
+impl BehaviorRecord {
+    /// 创建带金额的记录
+    #[must_use]
+    pub fn with_amount(kind: BehaviorKind, amount: Decimal) -> Self {
+        Self { kind, amount: Some(amount) }
+    }
+
+    /// 创建占位记录（无交易/无明确行为/无投资）
+    #[must_use]
+    pub fn marker(kind: BehaviorKind) -> Self {
+        Self { kind, amount: None }
+    }
+
+    /// 按语言渲染单条记录
+    #[must_use]
+    pub fn render(&self, language: HeaderLanguage) -> String {
+        let label = match language {
+            HeaderLanguage::Chinese => self.kind.label_zh().to_string(),
+            HeaderLanguage::English => self.kind.label_en().to_string(),
+            HeaderLanguage::Bilingual => {
+                format!("{} / {}", self.kind.label_zh(), self.kind.label_en())
+            }
+        };
+        match (self.amount, language) {
+            (Some(amount), HeaderLanguage::Chinese) => format!("{label}：{amount:.2}"),
+            (Some(amount), _) => format!("{label}: {amount:.2}"),
+            (None, _) => label,
+        }
+    }
+}
+
+/// 按语言渲染整行行为记录
+#[must_use]
+pub fn render_records(records: &[BehaviorRecord], language: HeaderLanguage) -> String {
+    let separator = match language {
+        HeaderLanguage::Chinese => "；",
+        _ => "; ",
+    };
+    records.iter()
+        .map(|record| record.render(language))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// 解析已落盘的中文行为文案
+///
+/// 任一片段不符合已知模式（如"理财赎回-XX"等专有文案）时返回None，
+/// 调用方应保留原文，避免翻译与取证原件产生偏差
+#[must_use]
+pub fn parse_behavior_text(text: &str) -> Option<Vec<BehaviorRecord>> {
+    text.split('；')
+        .map(|segment| {
+            let segment = segment.trim();
+            if let Some((label, amount)) = segment.split_once('：') {
+                let kind = BehaviorKind::from_label_zh(label)?;
+                let amount = amount.trim().parse::<Decimal>().ok()?;
+                Some(BehaviorRecord::with_amount(kind, amount))
+            } else {
+                BehaviorKind::from_label_zh(segment).map(BehaviorRecord::marker)
+            }
+        })
+        .collect()
+}
+
+/// 按语言重新渲染已落盘的行为文案
+///
+/// 中文直接原样返回；英文/双语先解析为结构化记录再渲染，
+/// 解析不了的专有文案保持中文原样
+#[must_use]
+pub fn localize_behavior_text(text: &str, language: HeaderLanguage) -> String {
+    if text.is_empty() || language == HeaderLanguage::Chinese {
+        return text.to_string();
+    }
+    parse_behavior_text(text)
+        .map_or_else(|| text.to_string(), |records| render_records(&records, language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_matches_legacy_chinese_prose() {
+        let records = vec![
+            BehaviorRecord::with_amount(BehaviorKind::Misappropriation, Decimal::from(3000)),
+            BehaviorRecord::with_amount(BehaviorKind::PersonalPayment, Decimal::from(2000)),
+        ];
+        assert_eq!(
+            render_records(&records, HeaderLanguage::Chinese),
+            "挪用：3000.00；个人支付：2000.00"
+        );
+        assert_eq!(
+            render_records(&records, HeaderLanguage::English),
+            "Misappropriation: 3000.00; Personal Payment: 2000.00"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trips_known_prose() {
+        let text = "垫付：1500.00；公司支付：500.00";
+        let records = parse_behavior_text(text).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, BehaviorKind::AdvancePayment);
+        assert_eq!(records[0].amount, Some(Decimal::new(150_000, 2)));
+        assert_eq!(render_records(&records, HeaderLanguage::Chinese), text);
+
+        // 占位类别无金额
+        let markers = parse_behavior_text("无交易").unwrap();
+        assert_eq!(markers, vec![BehaviorRecord::marker(BehaviorKind::NoTransaction)]);
+    }
+
+    #[test]
+    fn test_localize_keeps_unknown_prose_verbatim() {
+        // 专有文案（理财赎回等）解析不了，保持中文原样不强行翻译
+        let text = "理财赎回-朝朝盈：5000.00";
+        assert_eq!(localize_behavior_text(text, HeaderLanguage::English), text);
+        // 已知文案按语言渲染
+        assert_eq!(
+            localize_behavior_text("挪用：3000.00", HeaderLanguage::English),
+            "Misappropriation: 3000.00"
+        );
+        assert_eq!(
+            localize_behavior_text("投资挪用：5000.00", HeaderLanguage::Bilingual),
+            "投资挪用 / Investment Misappropriation: 5000.00"
+        );
+    }
+}
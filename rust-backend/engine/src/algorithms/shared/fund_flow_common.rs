@@ -6,7 +6,7 @@
 use super::tracker_base::TrackerBase;
 use super::behavior_analyzer::BehaviorAnalyzer;
 use super::investment_pool::InvestmentPoolManager;
-// use crate::data_models::Config; // 暂时未使用
+use crate::data_models::OverdraftAttribution;
 use rust_decimal::Decimal;
 use chrono::NaiveDateTime;
 
@@ -36,7 +36,7 @@ impl FundFlowCommon {
         _transaction_date: Option<NaiveDateTime>, // 暂时未使用
     ) -> (Decimal, Decimal, String) {
         if amount <= Decimal::ZERO {
-            return (Decimal::ZERO, Decimal::ZERO, "".to_string());
+            return (Decimal::ZERO, Decimal::ZERO, String::new());
         }
 
         // 判断资金属性类型
@@ -46,13 +46,13 @@ impl FundFlowCommon {
             // 个人资金
             base.personal_balance += amount;
             base.update_total_balance();
-            return (Decimal::ONE, Decimal::ZERO, format!("个人资金流入：{:.2}", amount));
+            (Decimal::ONE, Decimal::ZERO, format!("个人资金流入：{amount:.2}"))
             
         } else if base.config.is_company_fund(fund_attribute_str) {
             // 公司资金
             base.company_balance += amount;
             base.update_total_balance();
-            return (Decimal::ZERO, Decimal::ONE, format!("公司资金流入：{:.2}", amount));
+            (Decimal::ZERO, Decimal::ONE, format!("公司资金流入：{amount:.2}"))
             
         } else {
             // 其他情况，按当前余额比例分配
@@ -65,11 +65,11 @@ impl FundFlowCommon {
                 base.personal_balance += personal_amount;
                 base.company_balance += company_amount;
                 base.update_total_balance();
-                return (
+                (
                     Decimal::new(5, 1), // 0.5
                     Decimal::new(5, 1), // 0.5
-                    format!("混合资金流入：个人{:.2}，公司{:.2}", personal_amount, company_amount)
-                );
+                    format!("混合资金流入：个人{personal_amount:.2}，公司{company_amount:.2}")
+                )
             } else {
                 let personal_ratio = base.personal_balance / total_balance;
                 let company_ratio = base.company_balance / total_balance;
@@ -78,11 +78,11 @@ impl FundFlowCommon {
                 base.personal_balance += personal_amount;
                 base.company_balance += company_amount;
                 base.update_total_balance();
-                return (
+                (
                     personal_ratio,
                     company_ratio,
-                    format!("混合资金流入：个人{:.2}，公司{:.2}", personal_amount, company_amount)
-                );
+                    format!("混合资金流入：个人{personal_amount:.2}，公司{company_amount:.2}")
+                )
             }
         }
     }
@@ -112,23 +112,31 @@ impl FundFlowCommon {
     where
         F: FnOnce(&mut TrackerBase, Decimal) -> (Decimal, Decimal), // 返回(个人扣除, 公司扣除)
     {
-        // 检查是否有足够的资金
+        // 检查是否有足够的资金（透支模式下允许余额不足继续申购）
+        let overdraft_enabled = base.config.overdraft.allowed;
         let total_available = base.personal_balance + base.company_balance;
-        if total_available <= Decimal::ZERO {
-            return Err(format!("资金池已空，无法申购{:.2}", amount));
+        if total_available <= Decimal::ZERO && !overdraft_enabled {
+            return Err(format!("资金池已空，无法申购{amount:.2}"));
         }
 
         // 添加误差容忍度处理
         let balance_tolerance = Decimal::new(1, 2); // 0.01
         let funding_gap = amount - total_available;
-        let actual_amount = if funding_gap > balance_tolerance {
-            total_available // 只扣除现有余额
-        } else {
+        let actual_amount = if overdraft_enabled || funding_gap <= balance_tolerance {
             amount
+        } else {
+            total_available // 只扣除现有余额
         };
 
-        // 通过传入的扣除函数获取个人和公司扣除金额
+        // 通过传入的扣除函数获取个人和公司扣除金额，
+        // 透支模式下缺口部分按归属方计为透支提取
         let (personal_deduction, company_deduction) = deduction_fn(base, actual_amount);
+        let (personal_deduction, company_deduction) = Self::cover_shortfall_with_overdraft(
+            base,
+            personal_deduction,
+            company_deduction,
+            actual_amount,
+        );
 
         // 计算占比
         let total_deducted = personal_deduction + company_deduction;
@@ -149,10 +157,17 @@ impl FundFlowCommon {
         // 构造行为性质描述
         let mut behavior_descriptions = Vec::new();
         if company_deduction > Decimal::ZERO {
-            behavior_descriptions.push(format!("投资挪用：{:.2}", company_deduction));
+            behavior_descriptions.push(format!("投资挪用：{company_deduction:.2}"));
         }
         if personal_deduction > Decimal::ZERO {
-            behavior_descriptions.push(format!("个人投资：{:.2}", personal_deduction));
+            behavior_descriptions.push(format!("个人投资：{personal_deduction:.2}"));
+        }
+        // 透支提取留痕（申购路径不经过通用流出行为分析，这里自行消费）
+        let overdraft_drawn = base.last_overdraft_drawn;
+        base.last_overdraft_drawn = Decimal::ZERO;
+        if overdraft_drawn > Decimal::ZERO {
+            let side = base.config.overdraft.attribution.label_zh();
+            behavior_descriptions.push(format!("透支提取：{overdraft_drawn:.2}（{side}授信）"));
         }
 
         let behavior_nature = if behavior_descriptions.is_empty() {
@@ -188,10 +203,46 @@ impl FundFlowCommon {
         Ok((
             personal_ratio, 
             company_ratio, 
-            format!("{}申购-{}：{}", prefix, fund_attribute, behavior_nature)
+            format!("{prefix}申购-{fund_attribute}：{behavior_nature}")
         ))
     }
 
+    /// 透支模式下补足扣除缺口
+    ///
+    /// 余额（或FIFO队列）不足以覆盖支出时，缺口部分按配置的归属方
+    /// 计为透支提取：对应余额转负、计入累计透支，并并入该方扣除额
+    /// 参与行为定性（公司授信支付个人支出仍构成挪用）。
+    /// 未启用透支或缺口在容差内时原样返回
+    pub fn cover_shortfall_with_overdraft(
+        base: &mut TrackerBase,
+        personal_deduction: Decimal,
+        company_deduction: Decimal,
+        requested_amount: Decimal,
+    ) -> (Decimal, Decimal) {
+        let balance_tolerance = Decimal::new(1, 2); // 0.01
+        let shortfall = requested_amount - personal_deduction - company_deduction;
+        if !base.config.overdraft.allowed || shortfall <= balance_tolerance {
+            return (personal_deduction, company_deduction);
+        }
+
+        base.total_overdraft += shortfall;
+        base.total_overdraft = base.format_decimal(base.total_overdraft);
+        base.last_overdraft_drawn = shortfall;
+
+        match base.config.overdraft.attribution {
+            OverdraftAttribution::Company => {
+                base.company_balance -= shortfall;
+                base.update_total_balance();
+                (personal_deduction, company_deduction + shortfall)
+            }
+            OverdraftAttribution::Personal => {
+                base.personal_balance -= shortfall;
+                base.update_total_balance();
+                (personal_deduction + shortfall, company_deduction)
+            }
+        }
+    }
+
     /// 处理普通资金流出（共同的行为分析逻辑）
     /// 
     /// 对应Python版本的行为分析器集成机制
@@ -233,9 +284,17 @@ impl FundFlowCommon {
         let funding_gap = original_amount - personal_deduction - company_deduction;
         let balance_tolerance = Decimal::new(1, 2); // 0.01
 
+        // 透支提取留痕（由cover_shortfall_with_overdraft登记，消费后清零）
+        let overdraft_drawn = base.last_overdraft_drawn;
+        base.last_overdraft_drawn = Decimal::ZERO;
+        if overdraft_drawn > Decimal::ZERO {
+            let side = base.config.overdraft.attribution.label_zh();
+            return format!("{base_behavior}；透支提取：{overdraft_drawn:.2}（{side}授信）");
+        }
+
         // 添加资金不足的说明
         if funding_gap > balance_tolerance {
-            format!("{}；资金缺口：{:.2}", base_behavior, funding_gap)
+            format!("{base_behavior}；资金缺口：{funding_gap:.2}")
         } else {
             base_behavior
         }
@@ -249,6 +308,7 @@ impl FundFlowCommon {
     /// 
     /// # Returns
     /// (实际可用金额, 资金缺口)
+    #[must_use] 
     pub fn check_available_funds(base: &TrackerBase, amount: Decimal) -> (Decimal, Decimal) {
         let total_available = base.personal_balance + base.company_balance;
         if total_available <= Decimal::ZERO {
@@ -272,9 +332,15 @@ impl FundFlowCommon {
         personal_deduction: Decimal,
         company_deduction: Decimal,
     ) {
-        // 更新余额，确保不会出现负数
-        base.personal_balance = (base.personal_balance - personal_deduction).max(Decimal::ZERO);
-        base.company_balance = (base.company_balance - company_deduction).max(Decimal::ZERO);
+        if base.config.overdraft.allowed {
+            // 透支模式下余额可以合法转负
+            base.personal_balance -= personal_deduction;
+            base.company_balance -= company_deduction;
+        } else {
+            // 更新余额，确保不会出现负数
+            base.personal_balance = (base.personal_balance - personal_deduction).max(Decimal::ZERO);
+            base.company_balance = (base.company_balance - company_deduction).max(Decimal::ZERO);
+        }
         base.update_total_balance();
     }
 
@@ -287,6 +353,7 @@ impl FundFlowCommon {
     /// 
     /// # Returns
     /// (个人占比, 公司占比)
+    #[must_use] 
     pub fn calculate_ratios(
         personal_deduction: Decimal,
         company_deduction: Decimal,
@@ -303,6 +370,7 @@ impl FundFlowCommon {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data_models::Config;
 
     #[test]
     fn test_process_fund_inflow_personal() {
@@ -424,6 +492,46 @@ mod tests {
         assert_eq!(company_ratio2, Decimal::ZERO);
     }
 
+    #[test]
+    fn test_cover_shortfall_with_overdraft() {
+        let mut config = Config::new();
+        config.overdraft.allowed = true;
+        let mut base = TrackerBase::new(config);
+        base.personal_balance = Decimal::ZERO;
+        base.company_balance = Decimal::ZERO;
+
+        // 缺口500按默认归属（公司授信）计为透支提取
+        let (personal, company) = FundFlowCommon::cover_shortfall_with_overdraft(
+            &mut base,
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1500),
+        );
+        assert_eq!(personal, Decimal::from(1000));
+        assert_eq!(company, Decimal::from(500));
+        assert_eq!(base.company_balance, Decimal::from(-500));
+        assert_eq!(base.total_overdraft, Decimal::from(500));
+        assert_eq!(base.last_overdraft_drawn, Decimal::from(500));
+    }
+
+    #[test]
+    fn test_cover_shortfall_disabled_keeps_deductions() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+
+        // 未启用透支时原样返回，不触碰余额
+        let (personal, company) = FundFlowCommon::cover_shortfall_with_overdraft(
+            &mut base,
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1500),
+        );
+        assert_eq!(personal, Decimal::from(1000));
+        assert_eq!(company, Decimal::ZERO);
+        assert_eq!(base.total_overdraft, Decimal::ZERO);
+        assert_eq!(base.company_balance, Decimal::ZERO);
+    }
+
     #[test]
     fn test_update_balances_with_deduction() {
         let config = Config::new();
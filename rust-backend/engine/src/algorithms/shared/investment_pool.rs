@@ -2,8 +2,9 @@
 //! 
 //! 对应Python版本的投资产品处理逻辑，包括申购、赎回、盈利实现等复杂机制
 
-use super::tracker_base::{TrackerBase, InvestmentPool, ProfitRecord};
-// use crate::data_models::Config; // 暂时未使用
+use super::tracker_base::{TrackerBase, InvestmentPool, ProfitRecord, OrderingAnomaly, PoolResetEvent};
+use super::product_registry::ProductRegistry;
+use crate::data_models::RedemptionBeforePurchasePolicy;
 use rust_decimal::Decimal;
 use chrono::NaiveDateTime;
 
@@ -24,12 +25,15 @@ impl InvestmentPoolManager {
         company_ratio: Decimal,
         transaction_date: Option<NaiveDateTime>,
     ) -> InvestmentPool {
+        // 产品代码先经注册表归并，确保同一产品的不同写法落入同一资金池
+        let product_code = ProductRegistry::canonical_code(base, product_code);
         let personal_amount = amount * personal_ratio;
         let company_amount = amount * company_ratio;
+        let mut reset_realized_profit = None;
 
         // 获取或创建投资产品池
-        let pool = base.investment_pools.entry(product_code.to_string())
-            .or_insert_with(InvestmentPool::default);
+        let pool = base.investment_pools.entry(product_code.clone())
+            .or_default();
 
         // 检查重置条件：当前总金额为负数时，表示已有盈利
         let current_total = pool.total_amount;
@@ -39,16 +43,15 @@ impl InvestmentPoolManager {
             
             // 创建历史盈利记录
             let reset_record = ProfitRecord {
-                reset_time: transaction_date
-                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-                    .unwrap_or_else(|| "未知时间".to_string()),
+                reset_time: transaction_date.map_or_else(|| "未知时间".to_string(), |dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
                 profit_amount: realized_profit,
-                description: format!("重置前实现盈利 ¥{:.2}", realized_profit),
+                description: format!("重置前实现盈利 ¥{realized_profit:.2}"),
             };
             
             // 更新累计盈利
             pool.historical_profit_records.push(reset_record);
             pool.cumulative_realized_profit += realized_profit;
+            reset_realized_profit = Some(realized_profit);
             
             // 重置资金池状态
             pool.personal_amount = personal_amount;
@@ -76,8 +79,22 @@ impl InvestmentPoolManager {
         pool.cumulative_purchase += amount;
         
         // 复制池数据用于记录（避免借用检查问题）
-        let pool_data = pool.clone();
-        pool_data
+        let pool_snapshot = pool.clone();
+        
+        // 重置事件留痕：主结果表触发行标注与事件流由服务层消费
+        if let Some(realized_profit) = reset_realized_profit {
+            base.pool_reset_events.push(PoolResetEvent {
+                row: None,
+                pool_name: product_code,
+                realized_profit,
+                reset_time: transaction_date.map_or_else(
+                    || "未知时间".to_string(),
+                    |dt| dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+                ),
+            });
+        }
+        
+        pool_snapshot
     }
 
     /// 处理投资产品赎回
@@ -89,17 +106,19 @@ impl InvestmentPoolManager {
         amount: Decimal,
         transaction_date: Option<NaiveDateTime>,
     ) -> Result<(Decimal, Decimal, String), String> {
+        // 产品代码经注册表归并后再查池，避免写法差异导致找不到已有池
+        let product_code = ProductRegistry::canonical_code(base, product_code);
+        let product_code = product_code.as_str();
+
         // 查找对应的投资产品记录
         if !base.investment_pools.contains_key(product_code) {
-            // 没有找到对应的投资产品记录，按个人应收处理
-            // 用户要求：优先判定为个人应收，但标记为存疑
-            base.personal_balance += amount;
-            let prefix = product_code.split('-').next().unwrap_or("投资");
-            return Ok((
-                Decimal::ONE, 
-                Decimal::ZERO, 
-                format!("{}收入-{}：个人应收{:.2}（无申购记录-存疑）", prefix, product_code, amount)
-            ));
+            // 赎回早于任何申购记录：按配置策略处理，并记入时序异常汇总报告
+            return Self::handle_redemption_before_purchase(
+                base,
+                product_code,
+                amount,
+                transaction_date,
+            );
         }
 
         // 提取pool的相关数据，避免同时借用
@@ -111,7 +130,7 @@ impl InvestmentPoolManager {
 
         // 检查是否有有效的占比记录
         if latest_personal_ratio == Decimal::ZERO && latest_company_ratio == Decimal::ZERO {
-            return Err(format!("错误：投资产品{}从未有过有效资金池，无法分配收益", product_code));
+            return Err(format!("错误：投资产品{product_code}从未有过有效资金池，无法分配收益"));
         }
 
         // 赎回时统一使用最新记录的占比
@@ -123,7 +142,6 @@ impl InvestmentPoolManager {
             if total_amount > Decimal::ZERO {
                 // 资金池有余额，按比例计算本金归还，暂无收益确认
                 let redemption_ratio = if total_amount > Decimal::ZERO { amount / total_amount } else { Decimal::ZERO };
-                let corresponding_purchase_cost = total_amount * redemption_ratio;
                 let profit = Decimal::ZERO; // 只要资金池还有余额，就暂不确认收益
                 
                 // 计算实际归还的本金
@@ -249,15 +267,62 @@ impl InvestmentPoolManager {
             )
         } else {
             format!(
-                "{}赎回-{}：个人{:.2}（本金{:.2}），公司{:.2}（本金{:.2}），无收益",
-                prefix, product_code, personal_return, returned_personal_principal,
-                company_return, returned_company_principal
+                "{prefix}赎回-{product_code}：个人{personal_return:.2}（本金{returned_personal_principal:.2}），公司{company_return:.2}（本金{returned_company_principal:.2}），无收益"
             )
         };
 
         Ok((latest_personal_ratio, latest_company_ratio, behavior_description))
     }
 
+    /// 处理赎回早于任何申购记录的情况
+    ///
+    /// 按`redemption_before_purchase`策略入账（或中止），
+    /// 并写入时序异常记录（行号由服务层回填）供汇总报告引用
+    fn handle_redemption_before_purchase(
+        base: &mut TrackerBase,
+        product_code: &str,
+        amount: Decimal,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> Result<(Decimal, Decimal, String), String> {
+        let policy = base.config.investment_products.redemption_before_purchase;
+        let prefix = product_code.split('-').next().unwrap_or("投资");
+        let time_text = transaction_date.map_or_else(
+            || "未知时间".to_string(),
+            |dt| dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+        );
+
+        if policy == RedemptionBeforePurchasePolicy::Reject {
+            return Err(format!(
+                "错误：投资产品{product_code}在任何申购记录之前发生赎回（{time_text}，金额{amount:.2}），\
+                 当前策略为中止分析，请补全取证区间之前的申购流水"
+            ));
+        }
+
+        base.ordering_anomalies.push(OrderingAnomaly {
+            row: None,
+            product_code: product_code.to_string(),
+            amount,
+            transaction_time: time_text,
+            disposition: policy.description().to_string(),
+        });
+
+        let (personal_ratio, company_ratio, side) =
+            if policy == RedemptionBeforePurchasePolicy::CompanySuspect {
+                base.company_balance += amount;
+                (Decimal::ZERO, Decimal::ONE, "公司")
+            } else {
+                base.personal_balance += amount;
+                (Decimal::ONE, Decimal::ZERO, "个人")
+            };
+        base.update_total_balance();
+
+        Ok((
+            personal_ratio,
+            company_ratio,
+            format!("{prefix}收入-{product_code}：{side}应收{amount:.2}（无申购记录-存疑）"),
+        ))
+    }
+
     /// 记录场外资金池交易
     pub fn record_off_site_transaction(
         base: &mut TrackerBase,
@@ -268,21 +333,25 @@ impl InvestmentPoolManager {
         pool: &InvestmentPool,
         transaction_type: &str,
     ) {
+        // 申购路径传入的是原始产品文本，这里同样经注册表归并（归并幂等）
+        let product_code = ProductRegistry::canonical_code(base, product_code);
+        let product_code = product_code.as_str();
+
         let updated_personal_balance = pool.personal_amount;
         let updated_company_balance = pool.company_amount;
         let updated_total_balance = pool.total_amount;
 
         // 计算资金占比描述
-        let fund_ratio = if updated_total_balance.abs() > Decimal::new(1, 2) { // 0.01
-            let personal_ratio_display = if updated_total_balance != Decimal::ZERO {
-                updated_personal_balance / updated_total_balance
-            } else {
+        let _fund_ratio = if updated_total_balance.abs() > Decimal::new(1, 2) { // 0.01
+            let personal_ratio_display = if updated_total_balance == Decimal::ZERO {
                 Decimal::ZERO
-            };
-            let company_ratio_display = if updated_total_balance != Decimal::ZERO {
-                updated_company_balance / updated_total_balance  
             } else {
+                updated_personal_balance / updated_total_balance
+            };
+            let company_ratio_display = if updated_total_balance == Decimal::ZERO {
                 Decimal::ZERO
+            } else {
+                updated_company_balance / updated_total_balance  
             };
             format!("个人{:.1}%，公司{:.1}%", personal_ratio_display * Decimal::from(100), company_ratio_display * Decimal::from(100))
         } else {
@@ -292,7 +361,7 @@ impl InvestmentPoolManager {
         // 使用新的场外资金池记录管理器添加记录
         let transaction_datetime = transaction_date.map(|dt| {
             use chrono::{Local, TimeZone};
-            Local.from_local_datetime(&dt).single().unwrap_or_else(|| Local::now())
+            Local.from_local_datetime(&dt).single().unwrap_or_else(Local::now)
         });
 
         if transaction_type == "申购" {
@@ -339,6 +408,7 @@ impl InvestmentPoolManager {
     }
     
     /// 计算投资池的完整统计信息（包含历史已实现盈利）
+    #[must_use] 
     pub fn calculate_complete_pool_stats(
         base: &TrackerBase,
         pool_name: &str,
@@ -431,6 +501,14 @@ mod tests {
         // 应该记录盈利
         assert_eq!(updated_pool.cumulative_realized_profit, Decimal::from(5000));
         assert_eq!(updated_pool.historical_profit_records.len(), 1);
+        
+        // 重置事件应留痕（行号由服务层回填）
+        assert_eq!(base.pool_reset_events.len(), 1);
+        assert_eq!(base.pool_reset_events[0].pool_name, "理财-TEST001");
+        assert_eq!(base.pool_reset_events[0].realized_profit, Decimal::from(5000));
+        assert!(base.pool_reset_events[0].row.is_none());
+        base.assign_pending_reset_rows(7);
+        assert_eq!(base.pool_reset_events[0].row, Some(7));
     }
 
     #[test]
@@ -452,6 +530,57 @@ mod tests {
         assert!(description.contains("个人应收"));
         assert!(description.contains("无申购记录"));
         assert_eq!(base.personal_balance, Decimal::from(5000));
+
+        // 默认策略下也要留下时序异常记录（行号由服务层回填）
+        assert_eq!(base.ordering_anomalies.len(), 1);
+        assert_eq!(base.ordering_anomalies[0].product_code, "理财-NOTFOUND");
+        assert!(base.ordering_anomalies[0].row.is_none());
+        base.assign_pending_anomaly_rows(42);
+        assert_eq!(base.ordering_anomalies[0].row, Some(42));
+    }
+
+    #[test]
+    fn test_redemption_before_purchase_company_policy() {
+        let mut config = Config::new();
+        config.investment_products.redemption_before_purchase =
+            RedemptionBeforePurchasePolicy::CompanySuspect;
+        let mut base = TrackerBase::new(config);
+
+        let result = InvestmentPoolManager::process_investment_redemption(
+            &mut base,
+            "理财-NOTFOUND",
+            Decimal::from(5000),
+            None,
+        );
+
+        let (personal_ratio, company_ratio, description) = result.unwrap();
+        assert_eq!(personal_ratio, Decimal::ZERO);
+        assert_eq!(company_ratio, Decimal::ONE);
+        assert!(description.contains("公司应收"));
+        assert_eq!(base.company_balance, Decimal::from(5000));
+        assert_eq!(base.total_balance, Decimal::from(5000));
+        assert_eq!(base.ordering_anomalies.len(), 1);
+    }
+
+    #[test]
+    fn test_redemption_before_purchase_reject_policy() {
+        let mut config = Config::new();
+        config.investment_products.redemption_before_purchase =
+            RedemptionBeforePurchasePolicy::Reject;
+        let mut base = TrackerBase::new(config);
+
+        let result = InvestmentPoolManager::process_investment_redemption(
+            &mut base,
+            "理财-NOTFOUND",
+            Decimal::from(5000),
+            None,
+        );
+
+        // 中止策略下直接报错，余额不变
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("补全"));
+        assert_eq!(base.personal_balance, Decimal::ZERO);
+        assert_eq!(base.company_balance, Decimal::ZERO);
     }
 
     #[test]
@@ -472,7 +601,7 @@ mod tests {
         };
         base.investment_pools.insert("理财-TEST001".to_string(), pool);
 
-        // 赎回12000（有2000收益）
+        // 超额赎回12000：资金池仍有余额时暂不确认收益，全部本金视为归还
         let result = InvestmentPoolManager::process_investment_redemption(
             &mut base,
             "理财-TEST001",
@@ -484,15 +613,15 @@ mod tests {
         let (personal_ratio, company_ratio, description) = result.unwrap();
         assert_eq!(personal_ratio, Decimal::new(6, 1)); // 0.6
         assert_eq!(company_ratio, Decimal::new(4, 1));  // 0.4
-        assert!(description.contains("总收益2000.00"));
+        assert!(description.contains("无收益"));
 
         // 检查资金返还
         assert_eq!(base.personal_balance, Decimal::from(7200)); // 12000 * 0.6
         assert_eq!(base.company_balance, Decimal::from(4800));  // 12000 * 0.4
 
-        // 检查收益分配
-        assert_eq!(base.total_personal_profit, Decimal::from(1200)); // 2000 * 0.6
-        assert_eq!(base.total_company_profit, Decimal::from(800));   // 2000 * 0.4
+        // 检查收益分配：余额未转负，收益延后到资金池清零后确认
+        assert_eq!(base.total_personal_profit, Decimal::ZERO);
+        assert_eq!(base.total_company_profit, Decimal::ZERO);
 
         // 检查本金归还
         assert_eq!(base.total_personal_principal_returned, Decimal::from(6000));
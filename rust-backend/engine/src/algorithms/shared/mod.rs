@@ -0,0 +1,21 @@
+//! 算法共享组件模块
+//! 
+//! 包含FIFO和差额计算法算法的共享组件
+//! 实现90%的共同功能，避免代码重复
+
+pub mod tracker_base;
+pub mod behavior_analyzer;
+pub mod behavior_record;
+pub mod investment_pool;
+pub mod product_registry;
+pub mod fund_flow_common;
+pub mod summary;
+
+// 重新导出主要类型
+pub use tracker_base::{TrackerBase, TrackerBaseSnapshot, InvestmentPool, ProfitRecord, OrderingAnomaly, PoolResetEvent};
+pub use behavior_analyzer::{BehaviorAnalyzer, ClassificationReason};
+pub use behavior_record::{BehaviorKind, BehaviorRecord, render_records, parse_behavior_text, localize_behavior_text};
+pub use investment_pool::InvestmentPoolManager;
+pub use product_registry::{ProductRegistry, ProductMergeRecord, normalize_product_code};
+pub use fund_flow_common::FundFlowCommon;
+pub use summary::SummaryGenerator;
\ No newline at end of file
@@ -0,0 +1,129 @@
+//! 投资产品代码注册表
+//!
+//! 流水中同一产品常存在多种写法（"理财-SL001"、"理财- SL001"、
+//! 全角"理财－ＳＬ００１"等），若按原始文本建池会把同一产品拆成
+//! 多个资金池，导致占比与盈亏统计碎片化。本模块在建池入口统一
+//! 做规范化与别名归并，并记录归并报告供审查人员确认。
+
+use serde::{Deserialize, Serialize};
+
+use super::tracker_base::TrackerBase;
+
+/// 规范化产品代码文本
+///
+/// 全角ASCII字符（含全角连字符、全角空格）转半角，并去除全部空白。
+/// 规范化是幂等的：已规范的代码再次处理不变
+#[must_use]
+pub fn normalize_product_code(raw: &str) -> String {
+    raw.chars()
+        .filter_map(|c| match c {
+            // 全角ASCII区（！..～）与半角相差0xFEE0
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0),
+            // 全角空格与普通空白一律去除
+            c if c.is_whitespace() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// 产品代码归并记录
+///
+/// 同一规范代码下观察到的全部原始写法（按首次出现顺序），
+/// 仅当写法不止一种时进入归并报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductMergeRecord {
+    /// 归并后的规范产品代码
+    pub canonical: String,
+    /// 流水中出现过的原始写法
+    pub raw_forms: Vec<String>,
+}
+
+/// 投资产品代码注册表
+///
+/// 负责产品代码的规范化、别名归并与归并报告生成
+pub struct ProductRegistry;
+
+impl ProductRegistry {
+    /// 计算产品代码的规范形式并登记原始写法
+    ///
+    /// 先做文本规范化，再查配置中的别名映射（别名键同样按规范化
+    /// 后匹配，别名目标也会被规范化），所有建池操作都应经由本方法取键
+    pub fn canonical_code(base: &mut TrackerBase, raw_code: &str) -> String {
+        let normalized = normalize_product_code(raw_code);
+        let canonical = base.config.investment_products.product_aliases.iter()
+            .find(|(alias, _)| normalize_product_code(alias) == normalized)
+            .map_or(normalized, |(_, target)| normalize_product_code(target));
+
+        let raw_forms = base.product_code_observations.entry(canonical.clone()).or_default();
+        let raw_trimmed = raw_code.trim();
+        if !raw_forms.iter().any(|form| form == raw_trimmed) {
+            raw_forms.push(raw_trimmed.to_string());
+        }
+        canonical
+    }
+
+    /// 生成产品代码归并报告
+    ///
+    /// 只列出存在多种原始写法的产品，按规范代码排序保证输出稳定
+    #[must_use]
+    pub fn merge_report(base: &TrackerBase) -> Vec<ProductMergeRecord> {
+        let mut records: Vec<ProductMergeRecord> = base.product_code_observations.iter()
+            .filter(|(_, raw_forms)| raw_forms.len() > 1)
+            .map(|(canonical, raw_forms)| ProductMergeRecord {
+                canonical: canonical.clone(),
+                raw_forms: raw_forms.clone(),
+            })
+            .collect();
+        records.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_models::Config;
+
+    #[test]
+    fn test_normalize_product_code() {
+        // 空白与全角差异归并到同一形式
+        assert_eq!(normalize_product_code("理财-SL001"), "理财-SL001");
+        assert_eq!(normalize_product_code("理财- SL001"), "理财-SL001");
+        assert_eq!(normalize_product_code(" 理财 - SL001 "), "理财-SL001");
+        assert_eq!(normalize_product_code("理财－ＳＬ００１"), "理财-SL001");
+        // 幂等
+        assert_eq!(normalize_product_code(&normalize_product_code("理财－ SL001")), "理财-SL001");
+    }
+
+    #[test]
+    fn test_canonical_code_applies_aliases_and_records_forms() {
+        let mut config = Config::new();
+        config.investment_products.product_aliases.insert(
+            "SL001理财".to_string(),
+            "理财-SL001".to_string(),
+        );
+        let mut base = TrackerBase::new(config);
+
+        assert_eq!(ProductRegistry::canonical_code(&mut base, "理财-SL001"), "理财-SL001");
+        assert_eq!(ProductRegistry::canonical_code(&mut base, "理财- SL001"), "理财-SL001");
+        assert_eq!(ProductRegistry::canonical_code(&mut base, "SL001理财"), "理财-SL001");
+
+        let report = ProductRegistry::merge_report(&base);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].canonical, "理财-SL001");
+        assert_eq!(report[0].raw_forms, vec!["理财-SL001", "理财- SL001", "SL001理财"]);
+    }
+
+    #[test]
+    fn test_merge_report_skips_single_form_products() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+
+        ProductRegistry::canonical_code(&mut base, "理财-A");
+        ProductRegistry::canonical_code(&mut base, "理财-A");
+        ProductRegistry::canonical_code(&mut base, "理财-B");
+
+        // 写法唯一的产品不进入报告
+        assert!(ProductRegistry::merge_report(&base).is_empty());
+    }
+}
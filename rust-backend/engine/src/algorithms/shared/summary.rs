@@ -16,6 +16,7 @@ impl SummaryGenerator {
     /// 生成审计摘要
     /// 
     /// 对应Python版本的`获取状态摘要`方法
+    #[must_use] 
     pub fn generate_audit_summary(base: &TrackerBase) -> AuditSummary {
         base.get_audit_summary()
     }
@@ -28,25 +29,35 @@ impl SummaryGenerator {
     /// 
     /// # Returns
     /// 格式化的状态摘要文本
+    #[must_use] 
     pub fn generate_detailed_summary_text(base: &TrackerBase, algorithm_name: &str) -> String {
         let mut summary = Vec::new();
         
-        summary.push(format!("=== {} 资金追踪审计摘要 ===", algorithm_name));
-        summary.push("".to_string());
-        
+        summary.push(format!("=== {algorithm_name} 资金追踪审计摘要 ==="));
+        summary.push(String::new());
+
+        // FIFO下同时刻条目的消耗顺序影响归属结果，摘要中必须注明生效规则
+        if algorithm_name.contains("FIFO") {
+            summary.push(format!(
+                "同时刻资金条目平局规则: {}",
+                base.config.fifo.tie_breaking.description()
+            ));
+            summary.push(String::new());
+        }
+
         // 基础余额信息
         summary.push("【基础余额状态】".to_string());
         summary.push(format!("个人余额: ¥{:.2}", base.personal_balance));
         summary.push(format!("公司余额: ¥{:.2}", base.company_balance));
         summary.push(format!("总余额: ¥{:.2}", base.total_balance));
-        summary.push("".to_string());
+        summary.push(String::new());
         
         // 累计统计信息
         summary.push("【累计统计信息】".to_string());
         summary.push(format!("累计挪用金额: ¥{:.2}", base.total_misappropriation));
         summary.push(format!("累计垫付金额: ¥{:.2}", base.total_advance_payment));
         summary.push(format!("资金缺口: ¥{:.2}", base.calculate_funding_gap()));
-        summary.push("".to_string());
+        summary.push(String::new());
         
         // 投资相关统计
         summary.push("【投资相关统计】".to_string());
@@ -56,13 +67,13 @@ impl SummaryGenerator {
         summary.push(format!("总计个人应分配利润: ¥{:.2}", base.total_personal_profit));
         summary.push(format!("总计公司应分配利润: ¥{:.2}", base.total_company_profit));
         summary.push(format!("累计非法所得: ¥{:.2}", base.total_illegal_gain));
-        summary.push("".to_string());
+        summary.push(String::new());
         
         // 投资产品详情
         if !base.investment_pools.is_empty() {
             summary.push("【投资产品详情】".to_string());
             for (product_name, pool) in &base.investment_pools {
-                summary.push(format!("产品: {}", product_name));
+                summary.push(format!("产品: {product_name}"));
                 summary.push(format!("  当前总金额: ¥{:.2}", pool.total_amount));
                 summary.push(format!("  个人金额: ¥{:.2} ({:.1}%)", 
                     pool.personal_amount, pool.latest_personal_ratio * Decimal::from(100)));
@@ -74,10 +85,28 @@ impl SummaryGenerator {
                 if !pool.historical_profit_records.is_empty() {
                     summary.push(format!("  历史盈利记录: {}次", pool.historical_profit_records.len()));
                 }
-                summary.push("".to_string());
+                summary.push(String::new());
             }
         }
         
+        // 时序异常报告（赎回早于申购等，通常指向流水数据缺失）
+        if !base.ordering_anomalies.is_empty() {
+            summary.push("【时序异常报告】".to_string());
+            summary.push(format!(
+                "共{}处赎回早于任何申购记录，请核查取证区间之前是否缺失申购流水：",
+                base.ordering_anomalies.len()
+            ));
+            for anomaly in &base.ordering_anomalies {
+                let row_text = anomaly.row.map_or_else(|| "行号未知".to_string(), |r| format!("第{r}行"));
+                summary.push(format!(
+                    "  {} {} 产品{} 赎回¥{:.2} → 按{}处理",
+                    row_text, anomaly.transaction_time, anomaly.product_code,
+                    anomaly.amount, anomaly.disposition
+                ));
+            }
+            summary.push(String::new());
+        }
+
         // 场外资金池记录统计
         if base.offsite_pool_records.record_count() > 0 {
             summary.push("【场外资金池记录统计】".to_string());
@@ -86,7 +115,7 @@ impl SummaryGenerator {
             // 按产品分组统计
             let grouped = base.offsite_pool_records.group_by_pool();
             
-            for (pool_name, records) in grouped.iter() {
+            for (pool_name, records) in &grouped {
                 let mut total_inflow = Decimal::ZERO;
                 let mut total_outflow = Decimal::ZERO;
                 
@@ -98,10 +127,10 @@ impl SummaryGenerator {
                 summary.push(format!("  {}: {} 条记录，入金 {:.2}，出金 {:.2}", 
                     pool_name, records.len(), total_inflow, total_outflow));
             }
-            summary.push("".to_string());
+            summary.push(String::new());
         }
         
-        summary.push(format!("=== {} 审计摘要结束 ===", algorithm_name));
+        summary.push(format!("=== {algorithm_name} 审计摘要结束 ==="));
         
         summary.join("\n")
     }
@@ -113,6 +142,7 @@ impl SummaryGenerator {
     /// 
     /// # Returns
     /// CSV格式的字符串
+    #[must_use] 
     pub fn generate_off_site_records_csv(base: &TrackerBase) -> String {
         if base.offsite_pool_records.record_count() == 0 {
             return "无场外资金池记录".to_string();
@@ -151,6 +181,7 @@ impl SummaryGenerator {
     /// 
     /// # Returns
     /// 投资产品盈利分析报告
+    #[must_use] 
     pub fn generate_investment_profit_report(base: &TrackerBase) -> String {
         if base.investment_pools.is_empty() {
             return "无投资产品数据".to_string();
@@ -158,7 +189,7 @@ impl SummaryGenerator {
         
         let mut report = Vec::new();
         report.push("=== 投资产品盈利分析报告 ===".to_string());
-        report.push("".to_string());
+        report.push(String::new());
         
         let mut total_investment = Decimal::ZERO;
         let mut total_redemption = Decimal::ZERO;
@@ -166,7 +197,7 @@ impl SummaryGenerator {
         let mut total_realized_profit = Decimal::ZERO;
         
         for (product_name, pool) in &base.investment_pools {
-            report.push(format!("【{}】", product_name));
+            report.push(format!("【{product_name}】"));
             
             let current_profit_loss = if pool.total_amount < Decimal::ZERO {
                 pool.total_amount.abs() // 负数表示盈利
@@ -181,19 +212,19 @@ impl SummaryGenerator {
             report.push(format!("  累计申购: ¥{:.2}", pool.cumulative_purchase));
             report.push(format!("  累计赎回: ¥{:.2}", pool.cumulative_redemption));
             report.push(format!("  当前价值: ¥{:.2}", pool.total_amount));
-            report.push(format!("  当前盈亏: ¥{:.2}", current_profit_loss));
+            report.push(format!("  当前盈亏: ¥{current_profit_loss:.2}"));
             report.push(format!("  已实现盈利: ¥{:.2}", pool.cumulative_realized_profit));
-            report.push(format!("  总盈亏: ¥{:.2}", total_profit_loss));
+            report.push(format!("  总盈亏: ¥{total_profit_loss:.2}"));
             
             if !pool.historical_profit_records.is_empty() {
-                report.push(format!("  历史盈利记录:"));
+                report.push("  历史盈利记录:".to_string());
                 for record in &pool.historical_profit_records {
                     report.push(format!("    {} - ¥{:.2} ({})", 
                         record.reset_time, record.profit_amount, record.description));
                 }
             }
             
-            report.push("".to_string());
+            report.push(String::new());
             
             // 累计统计
             total_investment += pool.cumulative_purchase;
@@ -204,27 +235,27 @@ impl SummaryGenerator {
         
         // 总体统计
         report.push("【总体统计】".to_string());
-        report.push(format!("总投资金额: ¥{:.2}", total_investment));
-        report.push(format!("总赎回金额: ¥{:.2}", total_redemption));
-        report.push(format!("当前总价值: ¥{:.2}", total_current_value));
-        report.push(format!("总已实现盈利: ¥{:.2}", total_realized_profit));
+        report.push(format!("总投资金额: ¥{total_investment:.2}"));
+        report.push(format!("总赎回金额: ¥{total_redemption:.2}"));
+        report.push(format!("当前总价值: ¥{total_current_value:.2}"));
+        report.push(format!("总已实现盈利: ¥{total_realized_profit:.2}"));
         
         let net_investment = total_investment - total_redemption;
         let unrealized_profit_loss = total_current_value - net_investment;
         let total_profit_loss = total_realized_profit + unrealized_profit_loss;
         
-        report.push(format!("净投资金额: ¥{:.2}", net_investment));
-        report.push(format!("未实现盈亏: ¥{:.2}", unrealized_profit_loss));
-        report.push(format!("总盈亏: ¥{:.2}", total_profit_loss));
+        report.push(format!("净投资金额: ¥{net_investment:.2}"));
+        report.push(format!("未实现盈亏: ¥{unrealized_profit_loss:.2}"));
+        report.push(format!("总盈亏: ¥{total_profit_loss:.2}"));
         
         let roi = if net_investment > Decimal::ZERO {
             (total_profit_loss / net_investment) * Decimal::from(100)
         } else {
             Decimal::ZERO
         };
-        report.push(format!("投资收益率: {:.2}%", roi));
+        report.push(format!("投资收益率: {roi:.2}%"));
         
-        report.push("".to_string());
+        report.push(String::new());
         report.push("=== 投资产品盈利分析报告结束 ===".to_string());
         
         report.join("\n")
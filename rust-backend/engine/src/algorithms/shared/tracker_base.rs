@@ -0,0 +1,620 @@
+//! 追踪器共享基础类
+//! 
+//! 包含FIFO和差额计算法共同的13个状态变量和基础功能
+//! `对应Python版本的FIFO资金追踪器核心状态管理`
+
+use crate::data_models::{Config, AuditSummary, OffsitePoolRecordManager};
+use crate::errors::{AuditResult, AuditError};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 追踪器共享基础类
+/// 
+/// 包含两种算法共同的状态变量和基础功能
+#[derive(Debug, Clone)]
+pub struct TrackerBase {
+    /// 配置对象
+    pub config: Config,
+    /// 是否已初始化
+    pub initialized: bool,
+    
+    // === 基础余额状态 ===
+    /// 个人资金池余额
+    pub personal_balance: Decimal,
+    /// 公司资金池余额  
+    pub company_balance: Decimal,
+    
+    // === 9个累计统计状态变量 ===
+    /// 累计挪用金额（个人使用公司资金，包括投资挪用）
+    pub total_misappropriation: Decimal,
+    /// 累计垫付金额（公司使用个人资金）
+    pub total_advance_payment: Decimal,
+    /// 累计由资金池回归公司余额本金（通过投资产品赎回归还的公司本金）
+    pub total_company_principal_returned: Decimal,
+    /// 累计由资金池回归个人余额本金（通过投资产品赎回归还的个人本金）
+    pub total_personal_principal_returned: Decimal,
+    /// 累计非法所得（投资收益中的非法部分）
+    pub total_illegal_gain: Decimal,
+    /// 总计个人应分配利润
+    pub total_personal_profit: Decimal,
+    /// 总计公司应分配利润  
+    pub total_company_profit: Decimal,
+    /// 投资产品数量统计
+    pub investment_product_count: u32,
+    /// 总余额（个人余额 + 公司余额）
+    pub total_balance: Decimal,
+    /// 累计透支提取（透支模式下余额不足时计入，归属方见配置）
+    pub total_overdraft: Decimal,
+    /// 最近一笔支出的透支提取额（行为定性描述消费后清零）
+    pub last_overdraft_drawn: Decimal,
+    /// 累计利息收入（银行结息等利息/手续费类流入，单独归集）
+    pub total_interest_income: Decimal,
+    /// 累计手续费支出（账户管理费、工本费等利息/手续费类流出，单独归集）
+    pub total_fee_expense: Decimal,
+
+    // === 投资产品资金池管理 ===
+    /// 投资产品资金池字典 - 对应Python的复杂10字段结构
+    pub investment_pools: HashMap<String, InvestmentPool>,
+    /// 场外资金池记录管理器 - 对应Python的场外资金池记录
+    pub offsite_pool_records: OffsitePoolRecordManager,
+    /// 产品代码观察记录（规范代码 → 出现过的原始写法，归并报告用）
+    pub product_code_observations: HashMap<String, Vec<String>>,
+
+    // === 多币种追踪 ===
+    /// 各币种折算后净流入（基准币种计，按原币种代码归类）
+    ///
+    /// 仅在配置了币种列的流水中累计，用于合并报告中区分
+    /// 各币种对总余额的贡献；未配置币种列时恒为空
+    pub currency_balances: HashMap<String, Decimal>,
+
+    // === 行为分析器增量管理 ===
+    /// 上次行为分析器挪用金额（用于增量计算）
+    pub last_analyzer_misappropriation: Decimal,
+    /// 上次行为分析器垫付金额（用于增量计算）
+    pub last_analyzer_advance_payment: Decimal,
+
+    // === 时序异常记录 ===
+    /// 赎回早于申购等时序异常（汇总报告用）
+    pub ordering_anomalies: Vec<OrderingAnomaly>,
+
+    // === 资金池重置事件 ===
+    /// 资金池盈利重置事件（主结果表标注与事件流用）
+    pub pool_reset_events: Vec<PoolResetEvent>,
+}
+
+/// 时序异常记录
+///
+/// 记录时间顺序上无法自洽的交易（如赎回早于任何申购），
+/// 这类异常通常指向流水数据缺失，需在报告中集中列出供人工核查
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderingAnomaly {
+    /// 相关流水行号（1开始，处理阶段由服务层回填）
+    pub row: Option<usize>,
+    /// 投资产品代码
+    pub product_code: String,
+    /// 交易金额
+    pub amount: Decimal,
+    /// 交易时间（无时间信息时为"未知时间"）
+    pub transaction_time: String,
+    /// 实际采用的处理方式描述
+    pub disposition: String,
+}
+
+/// 资金池盈利重置事件
+///
+/// 资金池总余额转负（已全额收回本金）后再次申购会触发重置并实现盈利。
+/// 重置此前仅体现在资金池明细表中，审查人员在主结果表上无从察觉，
+/// 因此在触发行上同步留痕
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolResetEvent {
+    /// 触发行号（1开始，处理阶段由服务层回填）
+    pub row: Option<usize>,
+    /// 资金池名称（投资产品编号）
+    pub pool_name: String,
+    /// 本次重置实现的盈利金额
+    pub realized_profit: Decimal,
+    /// 重置时间（无时间信息时为"未知时间"）
+    pub reset_time: String,
+}
+
+/// 投资产品资金池
+/// 对应Python版本的10字段复杂数据结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvestmentPool {
+    /// 个人投入资金
+    pub personal_amount: Decimal,
+    /// 公司投入资金
+    pub company_amount: Decimal,
+    /// 当前总金额（可为负数=盈利）
+    pub total_amount: Decimal,
+    /// 累计申购总额
+    pub cumulative_purchase: Decimal,
+    /// 累计赎回总额
+    pub cumulative_redemption: Decimal,
+    /// 最新个人占比（动态计算）
+    pub latest_personal_ratio: Decimal,
+    /// 最新公司占比（动态计算）
+    pub latest_company_ratio: Decimal,
+    /// 锁定的个人占比（资金池变负数时锁定）
+    pub locked_personal_ratio: Option<Decimal>,
+    /// 锁定的公司占比（资金池变负数时锁定）
+    pub locked_company_ratio: Option<Decimal>,
+    /// 历史盈利记录（每次重置的盈利记录）
+    pub historical_profit_records: Vec<ProfitRecord>,
+    /// 累计已实现盈利（所有重置盈利的累计）
+    pub cumulative_realized_profit: Decimal,
+}
+
+/// 盈利记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfitRecord {
+    /// 重置时间
+    pub reset_time: String,
+    /// 盈利金额
+    pub profit_amount: Decimal,
+    /// 描述信息
+    pub description: String,
+}
+
+/// 追踪器基础状态快照
+///
+/// `TrackerBase`中除配置外全部运行状态的可序列化副本，
+/// 用于检查点、增量分析以及中途状态的单元测试。
+/// `to_snapshot`/`from_snapshot`往返应无损
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackerBaseSnapshot {
+    /// 是否已初始化
+    pub initialized: bool,
+    /// 个人资金池余额
+    pub personal_balance: Decimal,
+    /// 公司资金池余额
+    pub company_balance: Decimal,
+    /// 累计挪用金额
+    pub total_misappropriation: Decimal,
+    /// 累计垫付金额
+    pub total_advance_payment: Decimal,
+    /// 累计由资金池回归公司余额本金
+    pub total_company_principal_returned: Decimal,
+    /// 累计由资金池回归个人余额本金
+    pub total_personal_principal_returned: Decimal,
+    /// 累计非法所得
+    pub total_illegal_gain: Decimal,
+    /// 总计个人应分配利润
+    pub total_personal_profit: Decimal,
+    /// 总计公司应分配利润
+    pub total_company_profit: Decimal,
+    /// 投资产品数量统计
+    pub investment_product_count: u32,
+    /// 总余额
+    pub total_balance: Decimal,
+    /// 累计透支提取（旧快照缺少该字段时为零）
+    #[serde(default)]
+    pub total_overdraft: Decimal,
+    /// 累计利息收入（旧快照缺少该字段时为零）
+    #[serde(default)]
+    pub total_interest_income: Decimal,
+    /// 累计手续费支出（旧快照缺少该字段时为零）
+    #[serde(default)]
+    pub total_fee_expense: Decimal,
+    /// 投资产品资金池字典
+    pub investment_pools: HashMap<String, InvestmentPool>,
+    /// 场外资金池记录管理器
+    pub offsite_pool_records: OffsitePoolRecordManager,
+    /// 产品代码观察记录（旧快照缺少该字段时为空，归并报告从恢复点重新累计）
+    #[serde(default)]
+    pub product_code_observations: HashMap<String, Vec<String>>,
+    /// 各币种折算后净流入（旧快照缺少该字段时为空）
+    #[serde(default)]
+    pub currency_balances: HashMap<String, Decimal>,
+    /// 上次行为分析器挪用金额
+    pub last_analyzer_misappropriation: Decimal,
+    /// 上次行为分析器垫付金额
+    pub last_analyzer_advance_payment: Decimal,
+    /// 时序异常记录
+    pub ordering_anomalies: Vec<OrderingAnomaly>,
+    /// 资金池盈利重置事件
+    pub pool_reset_events: Vec<PoolResetEvent>,
+}
+
+impl TrackerBase {
+    /// 创建新的追踪器基础
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            initialized: false,
+            personal_balance: Decimal::ZERO,
+            company_balance: Decimal::ZERO,
+            total_misappropriation: Decimal::ZERO,
+            total_advance_payment: Decimal::ZERO,
+            total_company_principal_returned: Decimal::ZERO,
+            total_personal_principal_returned: Decimal::ZERO,
+            total_illegal_gain: Decimal::ZERO,
+            total_personal_profit: Decimal::ZERO,
+            total_company_profit: Decimal::ZERO,
+            investment_product_count: 0,
+            total_balance: Decimal::ZERO,
+            total_overdraft: Decimal::ZERO,
+            last_overdraft_drawn: Decimal::ZERO,
+            total_interest_income: Decimal::ZERO,
+            total_fee_expense: Decimal::ZERO,
+            investment_pools: HashMap::new(),
+            offsite_pool_records: OffsitePoolRecordManager::new(),
+            product_code_observations: HashMap::new(),
+            currency_balances: HashMap::new(),
+            last_analyzer_misappropriation: Decimal::ZERO,
+            last_analyzer_advance_payment: Decimal::ZERO,
+            ordering_anomalies: Vec::new(),
+            pool_reset_events: Vec::new(),
+        }
+    }
+
+    /// 为尚未关联行号的时序异常回填行号
+    ///
+    /// 追踪器内部不感知流水行号，服务层在处理完单行交易后调用本方法
+    pub fn assign_pending_anomaly_rows(&mut self, row: usize) {
+        for anomaly in self.ordering_anomalies.iter_mut().filter(|a| a.row.is_none()) {
+            anomaly.row = Some(row);
+        }
+    }
+
+    /// 为尚未关联行号的资金池重置事件回填行号
+    ///
+    /// 与时序异常相同，行号由服务层在处理完单行交易后回填
+    pub fn assign_pending_reset_rows(&mut self, row: usize) {
+        for event in self.pool_reset_events.iter_mut().filter(|e| e.row.is_none()) {
+            event.row = Some(row);
+        }
+    }
+    
+    /// 初始化余额
+    pub fn initialize_balance(&mut self, initial_balance: Decimal, balance_type: &str) -> AuditResult<()> {
+        if self.config.is_personal_fund(balance_type) {
+            self.personal_balance = initial_balance;
+        } else if self.config.is_company_fund(balance_type) {
+            self.company_balance = initial_balance;
+        } else {
+            return Err(AuditError::validation_error(
+                format!("未知的余额类型: {balance_type}")
+            ));
+        }
+        
+        self.update_total_balance();
+        self.initialized = true;
+        Ok(())
+    }
+    
+    /// 更新总余额
+    pub fn update_total_balance(&mut self) {
+        self.total_balance = self.personal_balance + self.company_balance;
+    }
+    
+    /// 格式化数值（避免科学计数法）
+    #[must_use] 
+    pub fn format_decimal(&self, value: Decimal) -> Decimal {
+        // 处理极小值，避免科学计数法显示
+        if value.abs() < Decimal::new(1, 10) { // 小于0.0000000001
+            Decimal::ZERO
+        } else {
+            value.round_dp(2) // 保留2位小数
+        }
+    }
+    
+    /// 计算资金缺口
+    #[must_use] 
+    pub fn calculate_funding_gap(&self) -> Decimal {
+        self.total_misappropriation 
+            - self.total_company_principal_returned
+            - self.total_advance_payment
+    }
+    
+    /// 获取当前余额比例
+    #[must_use] 
+    pub fn get_current_ratios(&self) -> (Decimal, Decimal) {
+        if self.total_balance > Decimal::ZERO {
+            (
+                self.personal_balance / self.total_balance,
+                self.company_balance / self.total_balance,
+            )
+        } else {
+            (Decimal::ZERO, Decimal::ZERO)
+        }
+    }
+    
+    /// 生成审计摘要
+    #[must_use] 
+    pub fn get_audit_summary(&self) -> AuditSummary {
+        AuditSummary {
+            personal_balance: self.format_decimal(self.personal_balance),
+            company_balance: self.format_decimal(self.company_balance),
+            total_misappropriation: self.format_decimal(self.total_misappropriation),
+            total_advance_payment: self.format_decimal(self.total_advance_payment),
+            total_company_principal_returned: self.format_decimal(self.total_company_principal_returned),
+            total_personal_principal_returned: self.format_decimal(self.total_personal_principal_returned),
+            total_personal_profit: self.format_decimal(self.total_personal_profit),
+            total_company_profit: self.format_decimal(self.total_company_profit),
+            funding_gap: self.format_decimal(self.calculate_funding_gap()),
+            investment_product_count: self.investment_product_count,
+            total_balance: self.format_decimal(self.total_balance),
+            total_overdraft: self.format_decimal(self.total_overdraft),
+            total_interest_income: self.format_decimal(self.total_interest_income),
+            total_fee_expense: self.format_decimal(self.total_fee_expense),
+            // 销户时间由服务层检测后填写（追踪器不感知行级上下文）
+            account_closure_time: None,
+        }
+    }
+    
+    /// 归集利息/手续费行的收支
+    ///
+    /// 资金属性命中利息/手续费口径时计入单独累计并返回true；
+    /// 资金流本身仍按普通流入/流出处理，保持余额连贯
+    pub fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool {
+        if !self.config.is_interest_or_fee(fund_attribute) {
+            return false;
+        }
+        self.total_interest_income += income;
+        self.total_fee_expense += expense;
+        true
+    }
+
+    /// 累计单笔交易的按币种净流入
+    ///
+    /// `net_flow`为折算到基准币种后的流入-流出；币种代码按读入时
+    /// 已统一的大写形式归类。服务层在配置了币种列时逐行调用
+    pub fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal) {
+        *self.currency_balances.entry(currency.to_string()).or_insert(Decimal::ZERO) += net_flow;
+    }
+
+    /// 处理行为分析器增量累计
+    /// 对应Python版本的增量管理机制
+    pub fn process_analyzer_incremental(&mut self, analyzer_misappropriation: Decimal, analyzer_advance_payment: Decimal) {
+        let misappropriation_increment = analyzer_misappropriation - self.last_analyzer_misappropriation;
+        let advance_payment_increment = analyzer_advance_payment - self.last_analyzer_advance_payment;
+        
+        self.total_misappropriation += misappropriation_increment;
+        self.total_advance_payment += advance_payment_increment;
+        
+        // 更新记录的累计值
+        self.last_analyzer_misappropriation = analyzer_misappropriation;
+        self.last_analyzer_advance_payment = analyzer_advance_payment;
+        
+        // 格式化数值
+        self.total_misappropriation = self.format_decimal(self.total_misappropriation);
+        self.total_advance_payment = self.format_decimal(self.total_advance_payment);
+    }
+    
+    /// 重置追踪器状态
+    pub fn reset(&mut self) {
+        *self = Self::new(self.config.clone());
+    }
+    
+    /// 检查是否已初始化
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// 导出当前运行状态快照
+    ///
+    /// 配置不属于运行状态，恢复时由`from_snapshot`重新传入
+    #[must_use]
+    pub fn to_snapshot(&self) -> TrackerBaseSnapshot {
+        TrackerBaseSnapshot {
+            initialized: self.initialized,
+            personal_balance: self.personal_balance,
+            company_balance: self.company_balance,
+            total_misappropriation: self.total_misappropriation,
+            total_advance_payment: self.total_advance_payment,
+            total_company_principal_returned: self.total_company_principal_returned,
+            total_personal_principal_returned: self.total_personal_principal_returned,
+            total_illegal_gain: self.total_illegal_gain,
+            total_personal_profit: self.total_personal_profit,
+            total_company_profit: self.total_company_profit,
+            investment_product_count: self.investment_product_count,
+            total_balance: self.total_balance,
+            total_overdraft: self.total_overdraft,
+            total_interest_income: self.total_interest_income,
+            total_fee_expense: self.total_fee_expense,
+            investment_pools: self.investment_pools.clone(),
+            offsite_pool_records: self.offsite_pool_records.clone(),
+            product_code_observations: self.product_code_observations.clone(),
+            currency_balances: self.currency_balances.clone(),
+            last_analyzer_misappropriation: self.last_analyzer_misappropriation,
+            last_analyzer_advance_payment: self.last_analyzer_advance_payment,
+            ordering_anomalies: self.ordering_anomalies.clone(),
+            pool_reset_events: self.pool_reset_events.clone(),
+        }
+    }
+
+    /// 从状态快照恢复追踪器基础
+    #[must_use]
+    pub fn from_snapshot(config: Config, snapshot: TrackerBaseSnapshot) -> Self {
+        Self {
+            config,
+            initialized: snapshot.initialized,
+            personal_balance: snapshot.personal_balance,
+            company_balance: snapshot.company_balance,
+            total_misappropriation: snapshot.total_misappropriation,
+            total_advance_payment: snapshot.total_advance_payment,
+            total_company_principal_returned: snapshot.total_company_principal_returned,
+            total_personal_principal_returned: snapshot.total_personal_principal_returned,
+            total_illegal_gain: snapshot.total_illegal_gain,
+            total_personal_profit: snapshot.total_personal_profit,
+            total_company_profit: snapshot.total_company_profit,
+            investment_product_count: snapshot.investment_product_count,
+            total_balance: snapshot.total_balance,
+            total_overdraft: snapshot.total_overdraft,
+            // 透支留痕是单笔支出内的瞬态，快照只会在整行处理后生成
+            last_overdraft_drawn: Decimal::ZERO,
+            total_interest_income: snapshot.total_interest_income,
+            total_fee_expense: snapshot.total_fee_expense,
+            investment_pools: snapshot.investment_pools,
+            offsite_pool_records: snapshot.offsite_pool_records,
+            product_code_observations: snapshot.product_code_observations,
+            currency_balances: snapshot.currency_balances,
+            last_analyzer_misappropriation: snapshot.last_analyzer_misappropriation,
+            last_analyzer_advance_payment: snapshot.last_analyzer_advance_payment,
+            ordering_anomalies: snapshot.ordering_anomalies,
+            pool_reset_events: snapshot.pool_reset_events,
+        }
+    }
+}
+
+impl Default for InvestmentPool {
+    fn default() -> Self {
+        Self {
+            personal_amount: Decimal::ZERO,
+            company_amount: Decimal::ZERO,
+            total_amount: Decimal::ZERO,
+            cumulative_purchase: Decimal::ZERO,
+            cumulative_redemption: Decimal::ZERO,
+            latest_personal_ratio: Decimal::ZERO,
+            latest_company_ratio: Decimal::ZERO,
+            locked_personal_ratio: None,
+            locked_company_ratio: None,
+            historical_profit_records: Vec::new(),
+            cumulative_realized_profit: Decimal::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_tracker_base_creation() {
+        let config = Config::new();
+        let base = TrackerBase::new(config);
+        
+        assert!(!base.is_initialized());
+        assert_eq!(base.personal_balance, Decimal::ZERO);
+        assert_eq!(base.company_balance, Decimal::ZERO);
+        assert_eq!(base.total_misappropriation, Decimal::ZERO);
+    }
+    
+    #[test]
+    fn test_initialize_balance() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+        
+        let result = base.initialize_balance(Decimal::from(100000), "个人");
+        assert!(result.is_ok());
+        assert!(base.is_initialized());
+        assert_eq!(base.personal_balance, Decimal::from(100000));
+        assert_eq!(base.total_balance, Decimal::from(100000));
+    }
+    
+    #[test]
+    fn test_format_decimal() {
+        let config = Config::new();
+        let base = TrackerBase::new(config);
+        
+        // 测试极小值处理
+        let small_value = Decimal::new(1, 11); // 0.00000000001
+        assert_eq!(base.format_decimal(small_value), Decimal::ZERO);
+        
+        // 测试正常值
+        let normal_value = Decimal::new(123456, 2); // 1234.56
+        assert_eq!(base.format_decimal(normal_value), Decimal::new(123456, 2));
+    }
+    
+    #[test]
+    fn test_funding_gap_calculation() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+        
+        base.total_misappropriation = Decimal::from(10000);
+        base.total_company_principal_returned = Decimal::from(3000);
+        base.total_advance_payment = Decimal::from(2000);
+        
+        assert_eq!(base.calculate_funding_gap(), Decimal::from(5000));
+    }
+    
+    #[test]
+    fn test_snapshot_round_trip_is_lossless() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config.clone());
+        base.initialize_balance(Decimal::from(100_000), "个人").unwrap();
+        base.total_misappropriation = Decimal::from(5000);
+        base.investment_product_count = 2;
+        base.investment_pools.insert("理财-A".to_string(), InvestmentPool {
+            personal_amount: Decimal::from(3000),
+            cumulative_purchase: Decimal::from(3000),
+            ..InvestmentPool::default()
+        });
+        base.ordering_anomalies.push(OrderingAnomaly {
+            row: Some(7),
+            product_code: "理财-A".to_string(),
+            amount: Decimal::from(1000),
+            transaction_time: "2021-01-01 10:00:00".to_string(),
+            disposition: "按申购处理".to_string(),
+        });
+
+        // 经serde序列化往返后恢复，状态应与原始完全一致
+        let snapshot = base.to_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored_snapshot: TrackerBaseSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_snapshot, snapshot);
+
+        let restored = TrackerBase::from_snapshot(config, restored_snapshot);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert!(restored.is_initialized());
+        assert_eq!(restored.personal_balance, Decimal::from(100_000));
+        assert_eq!(restored.investment_pools["理财-A"].personal_amount, Decimal::from(3000));
+    }
+
+    #[test]
+    fn test_record_currency_flow_accumulates_per_currency() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+
+        base.record_currency_flow("CNY", Decimal::from(1000));
+        base.record_currency_flow("USD", Decimal::from(720));
+        base.record_currency_flow("USD", Decimal::from(-200));
+
+        assert_eq!(base.currency_balances["CNY"], Decimal::from(1000));
+        assert_eq!(base.currency_balances["USD"], Decimal::from(520));
+
+        // 按币种净流入纳入快照往返
+        let snapshot = base.to_snapshot();
+        let restored = TrackerBase::from_snapshot(Config::new(), snapshot);
+        assert_eq!(restored.currency_balances["USD"], Decimal::from(520));
+    }
+
+    #[test]
+    fn test_record_interest_fee_accumulates_only_matching_rows() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+
+        // 命中利息/手续费口径的行累计到独立口径
+        assert!(base.record_interest_fee("银行结息", Decimal::from(30), Decimal::ZERO));
+        assert!(base.record_interest_fee("账户管理费", Decimal::ZERO, Decimal::from(10)));
+        // 普通属性不命中、不累计
+        assert!(!base.record_interest_fee("个人应收", Decimal::from(500), Decimal::ZERO));
+
+        assert_eq!(base.total_interest_income, Decimal::from(30));
+        assert_eq!(base.total_fee_expense, Decimal::from(10));
+
+        // 利息/手续费累计纳入快照往返
+        let snapshot = base.to_snapshot();
+        let restored = TrackerBase::from_snapshot(Config::new(), snapshot);
+        assert_eq!(restored.total_interest_income, Decimal::from(30));
+        assert_eq!(restored.total_fee_expense, Decimal::from(10));
+    }
+
+    #[test]
+    fn test_current_ratios() {
+        let config = Config::new();
+        let mut base = TrackerBase::new(config);
+        
+        base.personal_balance = Decimal::from(6000);
+        base.company_balance = Decimal::from(4000);
+        base.update_total_balance();
+        
+        let (personal_ratio, company_ratio) = base.get_current_ratios();
+        assert_eq!(personal_ratio, Decimal::new(6, 1)); // 0.6
+        assert_eq!(company_ratio, Decimal::new(4, 1)); // 0.4
+    }
+}
\ No newline at end of file
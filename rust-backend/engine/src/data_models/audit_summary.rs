@@ -1,5 +1,6 @@
 //! 审计摘要数据模型
 
+use crate::data_models::decimal_serde::decimal_string;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -11,38 +12,47 @@ use std::fmt;
 pub struct AuditSummary {
     /// 当前个人余额
     #[serde(rename = "个人余额")]
+    #[serde(with = "decimal_string")]
     pub personal_balance: Decimal,
     
     /// 当前公司余额
     #[serde(rename = "公司余额")]
+    #[serde(with = "decimal_string")]
     pub company_balance: Decimal,
     
     /// 累计挪用金额
     #[serde(rename = "累计挪用金额")]
+    #[serde(with = "decimal_string")]
     pub total_misappropriation: Decimal,
     
     /// 累计垫付金额
     #[serde(rename = "累计垫付金额")]
+    #[serde(with = "decimal_string")]
     pub total_advance_payment: Decimal,
     
     /// 累计由资金池回归公司余额本金
     #[serde(rename = "累计由资金池回归公司余额本金")]
+    #[serde(with = "decimal_string")]
     pub total_company_principal_returned: Decimal,
     
     /// 累计由资金池回归个人余额本金
     #[serde(rename = "累计由资金池回归个人余额本金")]
+    #[serde(with = "decimal_string")]
     pub total_personal_principal_returned: Decimal,
     
     /// 总计个人应分配利润
     #[serde(rename = "总计个人应分配利润")]
+    #[serde(with = "decimal_string")]
     pub total_personal_profit: Decimal,
     
     /// 总计公司应分配利润
     #[serde(rename = "总计公司应分配利润")]
+    #[serde(with = "decimal_string")]
     pub total_company_profit: Decimal,
     
     /// 资金缺口
     #[serde(rename = "资金缺口")]
+    #[serde(with = "decimal_string")]
     pub funding_gap: Decimal,
     
     /// 投资产品数量
@@ -51,11 +61,33 @@ pub struct AuditSummary {
     
     /// 总余额
     #[serde(rename = "总余额")]
+    #[serde(with = "decimal_string")]
     pub total_balance: Decimal,
+
+    /// 累计透支提取（透支模式下余额不足时的授信提取，未启用时为零）
+    #[serde(rename = "累计透支提取")]
+    #[serde(default, with = "decimal_string")]
+    pub total_overdraft: Decimal,
+
+    /// 累计利息收入（银行结息等利息/手续费类流入，单独归集）
+    #[serde(rename = "累计利息收入")]
+    #[serde(default, with = "decimal_string")]
+    pub total_interest_income: Decimal,
+
+    /// 累计手续费支出（账户管理费、工本费等利息/手续费类流出，单独归集）
+    #[serde(rename = "累计手续费支出")]
+    #[serde(default, with = "decimal_string")]
+    pub total_fee_expense: Decimal,
+
+    /// 销户时间（检测到尾部持续零余额时填写）
+    #[serde(rename = "销户时间")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_closure_time: Option<String>,
 }
 
 impl AuditSummary {
     /// 创建新的审计摘要
+    #[must_use] 
     pub fn new() -> Self {
         Self {
             personal_balance: Decimal::ZERO,
@@ -69,12 +101,17 @@ impl AuditSummary {
             funding_gap: Decimal::ZERO,
             investment_product_count: 0,
             total_balance: Decimal::ZERO,
+            total_overdraft: Decimal::ZERO,
+            total_interest_income: Decimal::ZERO,
+            total_fee_expense: Decimal::ZERO,
+            account_closure_time: None,
         }
     }
     
     /// 计算资金缺口
     /// 
     /// 资金缺口 = 挪用金额 - 归还本金 - 垫付金额
+    #[must_use] 
     pub fn calculate_funding_gap(&self) -> Decimal {
         self.total_misappropriation 
             - self.total_company_principal_returned
@@ -82,16 +119,19 @@ impl AuditSummary {
     }
     
     /// 计算总余额
+    #[must_use] 
     pub fn calculate_total_balance(&self) -> Decimal {
         self.personal_balance + self.company_balance
     }
     
     /// 计算总利润
+    #[must_use] 
     pub fn calculate_total_profit(&self) -> Decimal {
         self.total_personal_profit + self.total_company_profit
     }
     
     /// 计算总归还本金
+    #[must_use] 
     pub fn calculate_total_principal_returned(&self) -> Decimal {
         self.total_company_principal_returned + self.total_personal_principal_returned
     }
@@ -104,13 +144,15 @@ impl AuditSummary {
     
     /// 检查数据一致性
     pub fn validate(&self) -> Result<(), String> {
-        // 检查余额是否为负（可能的异常情况）
-        if self.personal_balance < Decimal::ZERO {
-            return Err(format!("个人余额为负: {}", self.personal_balance));
-        }
-        
-        if self.company_balance < Decimal::ZERO {
-            return Err(format!("公司余额为负: {}", self.company_balance));
+        // 检查余额是否为负（透支模式下有累计透支留痕的负余额合法）
+        if self.total_overdraft == Decimal::ZERO {
+            if self.personal_balance < Decimal::ZERO {
+                return Err(format!("个人余额为负: {}", self.personal_balance));
+            }
+
+            if self.company_balance < Decimal::ZERO {
+                return Err(format!("公司余额为负: {}", self.company_balance));
+            }
         }
         
         // 检查总余额是否一致
@@ -134,21 +176,43 @@ impl AuditSummary {
         Ok(())
     }
     
+    /// 获取全部可比较指标（名称, 数值）
+    ///
+    /// 用于算法对比等需要按名称遍历指标的场景
+    #[must_use]
+    pub fn metric_values(&self) -> Vec<(&'static str, Decimal)> {
+        vec![
+            ("个人余额", self.personal_balance),
+            ("公司余额", self.company_balance),
+            ("总余额", self.total_balance),
+            ("累计挪用金额", self.total_misappropriation),
+            ("累计垫付金额", self.total_advance_payment),
+            ("累计归还公司本金", self.total_company_principal_returned),
+            ("累计归还个人本金", self.total_personal_principal_returned),
+            ("总计个人应分配利润", self.total_personal_profit),
+            ("总计公司应分配利润", self.total_company_profit),
+            ("资金缺口", self.funding_gap),
+            ("累计透支提取", self.total_overdraft),
+            ("投资产品数量", Decimal::from(self.investment_product_count)),
+        ]
+    }
+    
     /// 获取摘要统计信息
+    #[must_use] 
     pub fn get_statistics(&self) -> SummaryStatistics {
         SummaryStatistics {
             total_transactions_processed: 0, // 需要从外部传入
             has_funding_gap: self.funding_gap != Decimal::ZERO,
             net_profit_loss: self.calculate_total_profit(),
-            personal_balance_ratio: if self.total_balance != Decimal::ZERO {
-                self.personal_balance / self.total_balance
-            } else {
+            personal_balance_ratio: if self.total_balance == Decimal::ZERO {
                 Decimal::ZERO
-            },
-            company_balance_ratio: if self.total_balance != Decimal::ZERO {
-                self.company_balance / self.total_balance
             } else {
+                self.personal_balance / self.total_balance
+            },
+            company_balance_ratio: if self.total_balance == Decimal::ZERO {
                 Decimal::ZERO
+            } else {
+                self.company_balance / self.total_balance
             },
         }
     }
@@ -171,6 +235,9 @@ impl fmt::Display for AuditSummary {
         writeln!(f, "个人利润: {:>15}", self.total_personal_profit)?;
         writeln!(f, "公司利润: {:>15}", self.total_company_profit)?;
         writeln!(f, "资金缺口: {:>15}", self.funding_gap)?;
+        if self.total_overdraft != Decimal::ZERO {
+            writeln!(f, "累计透支: {:>15}", self.total_overdraft)?;
+        }
         writeln!(f, "投资产品数量: {}", self.investment_product_count)?;
         write!(f, "==================")
     }
@@ -0,0 +1,111 @@
+//! 结构化审计警告数据模型
+//!
+//! 许多情况（跳过的行、可疑赎回、顺序修复等）属于警告而非失败，
+//! 以前只散落在日志文本中。本模型提供带代码、行号引用和建议措施
+//! 的结构化警告，供GUI与CLI统一收集和展示。
+
+use serde::{Deserialize, Serialize};
+
+/// 结构化审计警告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditWarning {
+    /// 警告代码（如 `SCALE_ANOMALY`、`ROW_REORDERED`）
+    pub code: String,
+    /// 相关行号（1开始，无特定行时为None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<usize>,
+    /// 警告内容
+    pub message: String,
+    /// 建议措施
+    pub suggested_action: String,
+}
+
+impl AuditWarning {
+    /// 创建新的警告
+    #[must_use]
+    pub fn new(
+        code: impl Into<String>,
+        row: Option<usize>,
+        message: impl Into<String>,
+        suggested_action: impl Into<String>,
+    ) -> Self {
+        Self {
+            code: code.into(),
+            row,
+            message: message.into(),
+            suggested_action: suggested_action.into(),
+        }
+    }
+
+    /// 警告严重程度（用于异常汇总的排序与展示）
+    ///
+    /// 余额断点属于必须人工处理的错误；量级异常与存疑赎回需要
+    /// 重点核查；其余（顺序修复、销户等）为提示性信息
+    #[must_use]
+    pub fn severity(&self) -> &'static str {
+        match self.code.as_str() {
+            "BALANCE_DISCONTINUITY" | "BALANCE_RECONCILIATION" => "错误",
+            "SCALE_ANOMALY" | "REDEMPTION_BEFORE_PURCHASE" => "警告",
+            "ROW_REORDERED" | "ACCOUNT_CLOSED" | "IO_RETRY" => "提示",
+            _ => "警告",
+        }
+    }
+
+    /// 警告类别的中文标签（未知代码回退为代码本身）
+    #[must_use]
+    pub fn category_label(&self) -> &str {
+        match self.code.as_str() {
+            "SCALE_ANOMALY" => "金额量级异常",
+            "ROW_REORDERED" => "顺序自动修复",
+            "BALANCE_DISCONTINUITY" => "余额断点",
+            "BALANCE_RECONCILIATION" => "余额核对不符",
+            "ACCOUNT_CLOSED" => "账户销户",
+            "REDEMPTION_BEFORE_PURCHASE" => "存疑赎回",
+            "IO_RETRY" => "IO重试",
+            "RISK_PATTERN" => "可疑交易模式",
+            _ => &self.code,
+        }
+    }
+
+    /// 格式化为CLI可读的单行文本
+    #[must_use]
+    pub fn format_for_cli(&self) -> String {
+        match self.row {
+            Some(row) => format!(
+                "⚠️ [{}] 第{}行: {}（建议: {}）",
+                self.code, row, self.message, self.suggested_action
+            ),
+            None => format!(
+                "⚠️ [{}] {}（建议: {}）",
+                self.code, self.message, self.suggested_action
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_for_cli() {
+        let with_row = AuditWarning::new("SCALE_ANOMALY", Some(12), "金额量级突变", "核对金额单位");
+        assert!(with_row.format_for_cli().contains("第12行"));
+        assert!(with_row.format_for_cli().contains("SCALE_ANOMALY"));
+
+        let without_row = AuditWarning::new("ROW_REORDERED", None, "重排3处交易顺序", "核对交易顺序");
+        assert!(!without_row.format_for_cli().contains("第"));
+    }
+
+    #[test]
+    fn test_severity_and_category() {
+        let error = AuditWarning::new("BALANCE_DISCONTINUITY", Some(5), "余额不连续", "人工核对");
+        assert_eq!(error.severity(), "错误");
+        assert_eq!(error.category_label(), "余额断点");
+
+        // 未知代码回退为警告级，类别显示代码本身
+        let unknown = AuditWarning::new("CUSTOM_FLAG", None, "自定义", "核查");
+        assert_eq!(unknown.severity(), "警告");
+        assert_eq!(unknown.category_label(), "CUSTOM_FLAG");
+    }
+}
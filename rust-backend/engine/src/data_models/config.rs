@@ -0,0 +1,1192 @@
+//! 配置管理数据模型
+
+use crate::errors::{AuditError, AuditResult};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// 全局配置
+/// 
+/// 管理系统的所有配置参数和业务规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// 数值处理配置
+    pub numeric: NumericConfig,
+    
+    /// 资金属性识别规则
+    pub fund_attributes: FundAttributeConfig,
+    
+    /// 投资产品规则
+    pub investment_products: InvestmentProductConfig,
+    
+    /// 文件路径配置
+    pub file_paths: FilePathConfig,
+    
+    /// Excel列映射配置
+    pub excel_columns: ExcelColumnConfig,
+    
+    /// 运行结束通知配置（旧配置文件缺少该字段时默认关闭）
+    #[serde(default)]
+    pub notification: NotificationConfig,
+
+    /// FIFO算法配置（旧配置文件缺少该字段时使用入队顺序）
+    #[serde(default)]
+    pub fifo: FifoConfig,
+
+    /// 销户检测配置（旧配置文件缺少该字段时仅检测、不截断）
+    #[serde(default)]
+    pub account_closure: AccountClosureConfig,
+    
+    /// 透支处理配置（旧配置文件缺少该字段时禁止负余额，保持历史行为）
+    #[serde(default)]
+    pub overdraft: OverdraftConfig,
+
+    /// 同时间戳交易排序策略（旧配置文件缺少该字段时沿用贪心余额链修复）
+    #[serde(default)]
+    pub same_time_ordering: SameTimeOrderingConfig,
+
+    /// 自定义分类规则集（未加载时回退到内置关键字判定）
+    #[serde(default)]
+    pub classification_rules: Option<crate::utils::classification_rules::ClassificationRuleSet>,
+    
+    /// 文件IO重试配置（旧配置文件缺少该字段时使用默认退避策略）
+    #[serde(default)]
+    pub io_retry: IoRetryConfig,
+
+    /// 结果工作簿样式配置（旧配置文件缺少该字段时启用默认高亮与冻结表头）
+    #[serde(default)]
+    pub excel_style: ExcelStyleConfig,
+
+    /// 多币种配置（旧配置文件缺少该字段时按单一基准币种处理）
+    #[serde(default)]
+    pub currency: CurrencyConfig,
+}
+
+impl Config {
+    /// 创建默认配置
+    #[must_use] 
+    pub fn new() -> Self {
+        Self {
+            numeric: NumericConfig::new(),
+            fund_attributes: FundAttributeConfig::new(),
+            investment_products: InvestmentProductConfig::new(),
+            file_paths: FilePathConfig::new(),
+            excel_columns: ExcelColumnConfig::new(),
+            notification: NotificationConfig::default(),
+            fifo: FifoConfig::default(),
+            account_closure: AccountClosureConfig::default(),
+            overdraft: OverdraftConfig::default(),
+            same_time_ordering: SameTimeOrderingConfig::default(),
+            classification_rules: None,
+            io_retry: IoRetryConfig::default(),
+            excel_style: ExcelStyleConfig::default(),
+            currency: CurrencyConfig::default(),
+        }
+    }
+    
+    /// 从配置文件加载
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let config: Self = serde_json::from_str(&content)?;
+        Ok(config)
+    }
+    
+    /// 保存到配置文件
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// 判断是否为个人资金
+    ///
+    /// 已加载自定义分类规则时以规则为准（含"忽略"类别），
+    /// 规则未命中或未加载时回退到内置关键字
+    #[must_use] 
+    pub fn is_personal_fund(&self, fund_attribute: &str) -> bool {
+        if let Some(category) = self.classify_by_rules(fund_attribute) {
+            return category == crate::utils::classification_rules::FundCategory::Personal;
+        }
+        self.fund_attributes.personal_fund_keywords.iter()
+            .any(|keyword| fund_attribute.contains(keyword))
+    }
+    
+    /// 判断是否为公司资金
+    #[must_use] 
+    pub fn is_company_fund(&self, fund_attribute: &str) -> bool {
+        if let Some(category) = self.classify_by_rules(fund_attribute) {
+            return category == crate::utils::classification_rules::FundCategory::Company;
+        }
+        self.fund_attributes.company_fund_keywords.iter()
+            .any(|keyword| fund_attribute.contains(keyword))
+    }
+    
+    /// 判断是否为投资产品
+    #[must_use] 
+    pub fn is_investment_product(&self, fund_attribute: &str) -> bool {
+        if let Some(category) = self.classify_by_rules(fund_attribute) {
+            return category == crate::utils::classification_rules::FundCategory::InvestmentProduct;
+        }
+        self.investment_products.product_prefixes.iter()
+            .any(|prefix| fund_attribute.starts_with(prefix))
+    }
+    
+    /// 判断是否为利息/手续费行
+    ///
+    /// 银行结息、账户管理费、冲正等由系统产生的收支单独归集，
+    /// 不与经营性流入/流出混在一起污染挪用口径统计
+    #[must_use]
+    pub fn is_interest_or_fee(&self, fund_attribute: &str) -> bool {
+        if let Some(category) = self.classify_by_rules(fund_attribute) {
+            return category == crate::utils::classification_rules::FundCategory::InterestFee;
+        }
+        self.fund_attributes.interest_fee_keywords.iter()
+            .any(|keyword| fund_attribute.contains(keyword))
+    }
+
+    /// 按配置的分隔符构建资金属性层级解析器
+    #[must_use]
+    pub fn attribute_hierarchy(&self) -> crate::utils::attribute_hierarchy::AttributeHierarchy {
+        crate::utils::attribute_hierarchy::AttributeHierarchy::new(
+            self.fund_attributes.hierarchy_separator.clone())
+    }
+
+    /// 按自定义规则分类（未加载规则或未命中时为None）
+    fn classify_by_rules(&self, fund_attribute: &str) -> Option<crate::utils::classification_rules::FundCategory> {
+        self.classification_rules.as_ref()
+            .and_then(|rules| rules.classify(fund_attribute))
+    }
+    
+    /// 格式化数值精度
+    #[must_use] 
+    pub fn format_number(&self, value: Decimal) -> Decimal {
+        value.round_dp(self.numeric.decimal_places)
+    }
+    
+    /// 检查余额容差
+    #[must_use] 
+    pub fn is_balance_within_tolerance(&self, balance1: Decimal, balance2: Decimal) -> bool {
+        (balance1 - balance2).abs() <= self.numeric.balance_tolerance
+    }
+    
+    /// 加载列映射档案并应用到Excel列配置
+    pub fn load_column_mapping<P: AsRef<Path>>(&mut self, path: P) -> AuditResult<()> {
+        let profile = ColumnMappingProfile::load_from_file(path)?;
+        self.excel_columns.apply_mapping_profile(&profile);
+        Ok(())
+    }
+    
+    /// 加载自定义分类规则集
+    pub fn load_classification_rules<P: AsRef<Path>>(&mut self, path: P) -> AuditResult<()> {
+        let rules = crate::utils::classification_rules::ClassificationRuleSet::load_from_file(path)?;
+        self.classification_rules = Some(rules);
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 通知默认最大重试次数
+fn default_notification_max_retries() -> u32 {
+    3
+}
+
+/// 通知默认重试间隔（秒）
+fn default_notification_retry_interval_secs() -> u64 {
+    5
+}
+
+/// 运行结束通知配置
+///
+/// 启用后，分析完成或失败时将运行摘要推送到配置的Webhook或SMTP渠道
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// 是否启用通知
+    #[serde(default)]
+    pub enabled: bool,
+    
+    /// Webhook地址（仅支持http://，内网通知服务场景）
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    
+    /// SMTP邮件配置
+    #[serde(default)]
+    pub smtp: Option<SmtpNotificationConfig>,
+    
+    /// 发送失败最大重试次数
+    #[serde(default = "default_notification_max_retries")]
+    pub max_retries: u32,
+    
+    /// 重试间隔（秒）
+    #[serde(default = "default_notification_retry_interval_secs")]
+    pub retry_interval_secs: u64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhook_url: None,
+            smtp: None,
+            max_retries: default_notification_max_retries(),
+            retry_interval_secs: default_notification_retry_interval_secs(),
+        }
+    }
+}
+
+/// SMTP通知配置（明文SMTP，内网邮件网关场景）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpNotificationConfig {
+    /// SMTP服务器地址
+    pub server: String,
+    /// SMTP端口
+    pub port: u16,
+    /// 发件人地址
+    pub from: String,
+    /// 收件人地址列表
+    pub to: Vec<String>,
+}
+
+/// 销户检测配置
+///
+/// 部分银行流水在账户销户后仍带有若干零余额的行政性记录行
+/// （结息回执、清户凭证等）。检测到尾部持续零余额即视为销户，
+/// 可选择在销户行截断分析，销户时间会并入最终审计摘要。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountClosureConfig {
+    /// 是否启用销户检测
+    #[serde(default = "default_closure_detection_enabled")]
+    pub detection_enabled: bool,
+    /// 检测到销户后是否在销户行截断分析（销户行本身仍会被处理）
+    #[serde(default)]
+    pub stop_at_closure: bool,
+    /// 判定为销户所需的尾部连续零余额行数
+    #[serde(default = "default_min_trailing_zero_rows")]
+    pub min_trailing_zero_rows: usize,
+}
+
+impl Default for AccountClosureConfig {
+    fn default() -> Self {
+        Self {
+            detection_enabled: default_closure_detection_enabled(),
+            stop_at_closure: false,
+            min_trailing_zero_rows: default_min_trailing_zero_rows(),
+        }
+    }
+}
+
+fn default_closure_detection_enabled() -> bool {
+    true
+}
+
+/// 透支处理配置
+///
+/// 部分对公账户存在银行授信透支，余额可以合法转负。启用后
+/// 余额不足的支出不再按"资金缺口"截断，缺口部分计为透支提取，
+/// 对应归属方余额转负并在摘要中单独列示累计透支
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OverdraftConfig {
+    /// 是否允许余额透支（默认关闭，保持历史的非负余额行为）
+    #[serde(default)]
+    pub allowed: bool,
+
+    /// 透支归属方（银行授信通常开立在公司名下，默认归属公司）
+    #[serde(default)]
+    pub attribution: OverdraftAttribution,
+}
+
+/// 透支归属方
+///
+/// 决定透支提取从哪一方余额透支、并按该方资金参与行为定性
+/// （例如公司授信支付个人支出仍构成挪用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverdraftAttribution {
+    /// 透支计入公司余额（银行授信）
+    #[default]
+    #[serde(rename = "COMPANY")]
+    Company,
+    /// 透支计入个人余额
+    #[serde(rename = "PERSONAL")]
+    Personal,
+}
+
+impl OverdraftAttribution {
+    /// 中文展示名（行为性质描述用）
+    #[must_use]
+    pub fn label_zh(self) -> &'static str {
+        match self {
+            Self::Company => "公司",
+            Self::Personal => "个人",
+        }
+    }
+}
+
+/// 同时间戳交易排序策略配置
+///
+/// 流水中同一秒内的多笔交易在文件里的先后顺序未必等于实际发生顺序，
+/// 不同银行的落库顺序保证也不同。验证器据此配置选择重排口径
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SameTimeOrderingConfig {
+    /// 排序策略（旧配置文件缺少该字段时沿用贪心余额链修复）
+    #[serde(default)]
+    pub strategy: SameTimeOrderingStrategy,
+
+    /// `TIEBREAK_COLUMN`策略使用的仲裁列名（如"流水号"，其余策略忽略）
+    #[serde(default)]
+    pub tiebreak_column: Option<String>,
+}
+
+/// 同时间戳交易的排序策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SameTimeOrderingStrategy {
+    /// 贪心余额链修复（历史默认行为）：逐笔挑选使余额连贯的下一笔
+    #[default]
+    #[serde(rename = "GREEDY_BALANCE_CHAIN")]
+    GreedyBalanceChain,
+    /// 保持文件原始顺序，不做任何重排（银行已保证落库顺序时使用）
+    #[serde(rename = "KEEP_ORIGINAL")]
+    KeepOriginal,
+    /// 收入行先于支出行（同为收入/支出时保持原始相对顺序）
+    #[serde(rename = "INCOME_FIRST")]
+    IncomeFirst,
+    /// 按用户指定的仲裁列（如流水号）排序
+    #[serde(rename = "TIEBREAK_COLUMN")]
+    TiebreakColumn,
+}
+
+impl SameTimeOrderingStrategy {
+    /// 报告中展示的策略描述
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::GreedyBalanceChain => "贪心余额链修复（GREEDY_BALANCE_CHAIN）",
+            Self::KeepOriginal => "保持原始顺序（KEEP_ORIGINAL）",
+            Self::IncomeFirst => "收入先于支出（INCOME_FIRST）",
+            Self::TiebreakColumn => "按仲裁列排序（TIEBREAK_COLUMN）",
+        }
+    }
+}
+
+/// 文件IO重试配置
+///
+/// 网络盘与同步目录（OneDrive等）偶发读写失败，重试几次通常即可恢复。
+/// 打开/保存文件失败时按指数退避重试，避免几分钟的分析因瞬时IO错误整体失败
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IoRetryConfig {
+    /// 最大重试次数（0为不重试）
+    #[serde(default = "default_io_max_retries")]
+    pub max_retries: u32,
+    /// 首次重试前的等待毫秒数（此后每次翻倍）
+    #[serde(default = "default_io_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+impl Default for IoRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_io_max_retries(),
+            backoff_ms: default_io_backoff_ms(),
+        }
+    }
+}
+
+fn default_io_max_retries() -> u32 {
+    2
+}
+
+fn default_io_backoff_ms() -> u64 {
+    500
+}
+
+fn default_min_trailing_zero_rows() -> usize {
+    3
+}
+
+/// FIFO算法配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FifoConfig {
+    /// 同一时刻入队资金条目的消耗顺序（平局规则）
+    #[serde(default)]
+    pub tie_breaking: FifoTieBreaking,
+}
+
+/// FIFO同时刻资金条目的平局规则
+///
+/// 当队列前端多个条目的入队时间完全相同（同一笔混合流入拆分、
+/// 或同一时间戳的多笔流入）时，消耗顺序会直接影响挪用/垫付归属，
+/// 因此必须显式可配，默认保持入队顺序以兼容历史结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FifoTieBreaking {
+    /// 按入队顺序消耗（历史默认行为）
+    #[default]
+    #[serde(rename = "INSERTION_ORDER")]
+    InsertionOrder,
+    /// 同时刻条目优先消耗个人资金
+    #[serde(rename = "PERSONAL_FIRST")]
+    PersonalFirst,
+    /// 同时刻条目优先消耗公司资金
+    #[serde(rename = "COMPANY_FIRST")]
+    CompanyFirst,
+}
+
+impl FifoTieBreaking {
+    /// 摘要文本中展示的规则描述
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::InsertionOrder => "入队顺序（INSERTION_ORDER）",
+            Self::PersonalFirst => "个人资金优先（PERSONAL_FIRST）",
+            Self::CompanyFirst => "公司资金优先（COMPANY_FIRST）",
+        }
+    }
+}
+
+/// 资金占比默认小数位（旧配置文件缺少该字段时使用）
+fn default_ratio_decimal_places() -> u32 {
+    6
+}
+
+/// 数值处理配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NumericConfig {
+    /// 余额计算容差
+    pub balance_tolerance: Decimal,
+    
+    /// 小数位精度
+    pub decimal_places: u32,
+    
+    /// 资金占比小数位精度（导出时使用）
+    #[serde(default = "default_ratio_decimal_places")]
+    pub ratio_decimal_places: u32,
+    
+    /// 最小有效金额（小于此金额视为0）
+    pub minimum_amount: Decimal,
+
+    /// 精度安全导出：超出f64安全整数范围(2^53)的金额按精确字符串写入Excel
+    #[serde(default)]
+    pub precision_safe_export: bool,
+}
+
+impl NumericConfig {
+    /// 创建默认数值配置
+    #[must_use] 
+    pub fn new() -> Self {
+        Self {
+            balance_tolerance: Decimal::from_f64_retain(0.01).unwrap(),
+            decimal_places: 2,
+            ratio_decimal_places: 6,
+            minimum_amount: Decimal::from_f64_retain(0.01).unwrap(),
+            precision_safe_export: false,
+        }
+    }
+}
+
+impl Default for NumericConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 资金属性配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundAttributeConfig {
+    /// 个人资金关键词
+    pub personal_fund_keywords: HashSet<String>,
+    
+    /// 公司资金关键词
+    pub company_fund_keywords: HashSet<String>,
+
+    /// 层级属性分隔符（如"公司/子公司A/备用金"，汇总与筛选按该分隔符上卷）
+    #[serde(default = "default_hierarchy_separator")]
+    pub hierarchy_separator: String,
+
+    /// 利息/手续费关键词（旧配置文件缺少该字段时使用内置银行常见词面）
+    #[serde(default = "default_interest_fee_keywords")]
+    pub interest_fee_keywords: HashSet<String>,
+}
+
+fn default_hierarchy_separator() -> String {
+    crate::utils::attribute_hierarchy::AttributeHierarchy::DEFAULT_SEPARATOR.to_string()
+}
+
+fn default_interest_fee_keywords() -> HashSet<String> {
+    ["利息", "结息", "手续费", "账户管理费", "工本费", "冲正"]
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+impl FundAttributeConfig {
+    /// 创建默认资金属性配置
+    #[must_use] 
+    pub fn new() -> Self {
+        let mut personal_keywords = HashSet::new();
+        personal_keywords.insert("个人".to_string());
+        personal_keywords.insert("个人应收".to_string());
+        personal_keywords.insert("个人应付".to_string());
+        
+        let mut company_keywords = HashSet::new();
+        company_keywords.insert("公司".to_string());
+        company_keywords.insert("公司应收".to_string());
+        company_keywords.insert("公司应付".to_string());
+        
+        Self {
+            personal_fund_keywords: personal_keywords,
+            company_fund_keywords: company_keywords,
+            hierarchy_separator: default_hierarchy_separator(),
+            interest_fee_keywords: default_interest_fee_keywords(),
+        }
+    }
+}
+
+impl Default for FundAttributeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 投资产品配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvestmentProductConfig {
+    /// 投资产品前缀列表
+    pub product_prefixes: Vec<String>,
+
+    /// 赎回早于任何申购时的处理策略（旧配置文件缺少该字段时按个人应收存疑）
+    #[serde(default)]
+    pub redemption_before_purchase: RedemptionBeforePurchasePolicy,
+
+    /// 资金池统计的盈亏分摊策略（旧配置文件缺少该字段时沿用负余额周期法）
+    #[serde(default)]
+    pub profit_allocation: PoolProfitAllocationStrategy,
+
+    /// 产品代码别名映射（别名 → 规范代码，键值均按规范化后匹配）
+    ///
+    /// 用于归并无法靠空白/全角规范化识别的写法差异，
+    /// 如"SL001理财" → "理财-SL001"（旧配置文件缺少该字段时为空）
+    #[serde(default)]
+    pub product_aliases: HashMap<String, String>,
+
+    /// 场外资金池记录并入主结果工作簿的"场外资金池"工作表
+    ///
+    /// 开启后不再生成独立的资金池记录文件，便于一次性移交单个文件
+    /// （旧配置文件缺少该字段时保持独立文件导出）
+    #[serde(default)]
+    pub embed_offsite_pool_sheet: bool,
+}
+
+impl InvestmentProductConfig {
+    /// 创建默认投资产品配置
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            product_prefixes: vec![
+                "理财-".to_string(),
+                "投资-".to_string(),
+                "保险-".to_string(),
+                "关联银行卡-".to_string(),
+                "资金池-".to_string(),
+            ],
+            redemption_before_purchase: RedemptionBeforePurchasePolicy::default(),
+            profit_allocation: PoolProfitAllocationStrategy::default(),
+            product_aliases: HashMap::new(),
+            embed_offsite_pool_sheet: false,
+        }
+    }
+}
+
+/// 赎回早于任何申购记录时的处理策略
+///
+/// 时间顺序上先赎回后申购通常意味着流水缺失（申购发生在取证区间之前），
+/// 归属判定依赖人工假设，因此策略必须显式可配并在时序异常报告中留痕
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RedemptionBeforePurchasePolicy {
+    /// 按个人应收入账并标记存疑（历史默认行为）
+    #[default]
+    #[serde(rename = "PERSONAL_SUSPECT")]
+    PersonalSuspect,
+    /// 按公司应收入账并标记存疑
+    #[serde(rename = "COMPANY_SUSPECT")]
+    CompanySuspect,
+    /// 视为硬错误中止分析，要求补全申购流水
+    #[serde(rename = "REJECT")]
+    Reject,
+}
+
+impl RedemptionBeforePurchasePolicy {
+    /// 报告中展示的策略描述
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::PersonalSuspect => "个人应收存疑（PERSONAL_SUSPECT）",
+            Self::CompanySuspect => "公司应收存疑（COMPANY_SUSPECT）",
+            Self::Reject => "中止分析（REJECT）",
+        }
+    }
+}
+
+impl Default for InvestmentProductConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 资金池统计中盈亏在个人/公司之间的分摊策略
+///
+/// `calculate_pool_stats`历史上内嵌了一套负余额周期启发式，外部无法复核
+/// 计算口径。现将分摊口径显式命名并可配，统计结果中的累计个人/公司盈亏
+/// 均按所选策略计算，便于与书面审计说明相互印证
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PoolProfitAllocationStrategy {
+    /// 负余额周期法（历史默认行为）：
+    /// 盈利按已实现的负余额周期累加，亏损按最终余额比例分摊
+    #[default]
+    #[serde(rename = "REALIZED_CYCLE")]
+    RealizedCycle,
+    /// 最终比例法：盈亏一律按最后一条记录的个人/公司余额占比分摊
+    #[serde(rename = "FINAL_RATIO")]
+    FinalRatio,
+    /// 出资比例法：盈亏按个人/公司累计申购出资占比分摊
+    #[serde(rename = "PROPORTIONAL_TO_CONTRIBUTION")]
+    ProportionalToContribution,
+}
+
+impl PoolProfitAllocationStrategy {
+    /// 报告中展示的策略描述
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::RealizedCycle => "负余额周期法（REALIZED_CYCLE）",
+            Self::FinalRatio => "最终比例法（FINAL_RATIO）",
+            Self::ProportionalToContribution => "出资比例法（PROPORTIONAL_TO_CONTRIBUTION）",
+        }
+    }
+}
+
+/// 文件路径配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePathConfig {
+    /// 默认输入文件路径
+    pub default_input_path: String,
+    
+    /// 默认输出目录
+    pub default_output_dir: String,
+    
+    /// 日志目录
+    pub log_dir: String,
+    
+    /// 配置文件目录
+    pub config_dir: String,
+}
+
+impl FilePathConfig {
+    /// 创建默认文件路径配置
+    #[must_use] 
+    pub fn new() -> Self {
+        Self {
+            default_input_path: "流水.xlsx".to_string(),
+            default_output_dir: "./".to_string(),
+            log_dir: "logs/".to_string(),
+            config_dir: "config/".to_string(),
+        }
+    }
+}
+
+impl Default for FilePathConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 结果工作簿样式配置
+///
+/// 可疑行高亮与冻结表头/自动筛选让移交的工作簿开箱可读；
+/// 各开关可独立关闭，全部关闭后保持纯数据输出
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExcelStyleConfig {
+    /// 高亮可疑行：挪用行红底、垫付行橙底、资金缺口扩大的行加粗
+    #[serde(default = "default_style_enabled")]
+    pub highlight_suspicious_rows: bool,
+
+    /// 冻结表头行（滚动时表头保持可见）
+    #[serde(default = "default_style_enabled")]
+    pub freeze_header_row: bool,
+
+    /// 表头行添加自动筛选
+    #[serde(default = "default_style_enabled")]
+    pub header_autofilter: bool,
+}
+
+impl Default for ExcelStyleConfig {
+    fn default() -> Self {
+        Self {
+            highlight_suspicious_rows: true,
+            freeze_header_row: true,
+            header_autofilter: true,
+        }
+    }
+}
+
+fn default_style_enabled() -> bool {
+    true
+}
+
+/// 多币种配置
+///
+/// 流水中混有外币行（如同一账户的美元子账户）时，按本表的静态汇率
+/// 折算到基准币种后再进入算法核算；未配置汇率的外币会在读入阶段
+/// 报验证错误，避免不同币种的金额被静默相加
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyConfig {
+    /// 基准币种代码（累计量、余额与合并报告均以该币种计）
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+
+    /// 静态汇率表：币种代码 → 1单位该币种折合的基准币种金额
+    ///
+    /// 例如基准为CNY时，`{"USD": "7.2"}`表示1美元折合7.2元。
+    /// 静态汇率不随交易日期变化，折算结果仅供合并口径参考
+    #[serde(default)]
+    pub exchange_rates: std::collections::HashMap<String, Decimal>,
+}
+
+impl CurrencyConfig {
+    /// 判断币种代码是否为基准币种（忽略大小写与首尾空白）
+    #[must_use]
+    pub fn is_base(&self, currency: &str) -> bool {
+        currency.trim().eq_ignore_ascii_case(&self.base_currency)
+    }
+
+    /// 查询币种兑基准币种的汇率
+    ///
+    /// 基准币种恒为1；其余按汇率表查找（键忽略大小写），
+    /// 未配置时返回None，由调用方决定报错方式
+    #[must_use]
+    pub fn rate_to_base(&self, currency: &str) -> Option<Decimal> {
+        if self.is_base(currency) {
+            return Some(Decimal::ONE);
+        }
+        let normalized = currency.trim();
+        self.exchange_rates.iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(normalized))
+            .map(|(_, rate)| *rate)
+    }
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            base_currency: default_base_currency(),
+            exchange_rates: std::collections::HashMap::new(),
+        }
+    }
+}
+
+fn default_base_currency() -> String {
+    "CNY".to_string()
+}
+
+/// 导出表头语言
+///
+/// 涉外项目中可输出英文或中英双语表头（数据行保持中文原样），
+/// GUI按`AppConfig.language`自动选择，旧配置文件缺少该字段时为中文
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HeaderLanguage {
+    /// 中文表头（历史默认行为）
+    #[default]
+    #[serde(rename = "zh")]
+    Chinese,
+    /// 英文表头
+    #[serde(rename = "en")]
+    English,
+    /// 中英双语表头（"中文 / English"）
+    #[serde(rename = "bilingual")]
+    Bilingual,
+}
+
+impl HeaderLanguage {
+    /// 根据GUI的`AppConfig.language`值选择表头语言
+    ///
+    /// `en`开头的语言代码（如`en`、`en-US`）映射为英文，其余保持中文
+    #[must_use]
+    pub fn from_app_language(language: &str) -> Self {
+        if language.to_lowercase().starts_with("en") {
+            Self::English
+        } else {
+            Self::Chinese
+        }
+    }
+}
+
+/// Excel列映射配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExcelColumnConfig {
+    /// 交易日期列名
+    pub transaction_date_column: String,
+    
+    /// 交易时间列名
+    pub transaction_time_column: String,
+    
+    /// 交易收入金额列名
+    pub income_amount_column: String,
+    
+    /// 交易支出金额列名
+    pub expense_amount_column: String,
+    
+    /// 余额列名
+    pub balance_column: String,
+    
+    /// 资金属性列名
+    pub fund_attribute_column: String,
+    
+    /// 交易日期列的别名列表（不同银行的表头写法）
+    #[serde(default)]
+    pub transaction_date_aliases: Vec<String>,
+    
+    /// 交易时间列的别名列表
+    #[serde(default)]
+    pub transaction_time_aliases: Vec<String>,
+    
+    /// 交易收入金额列的别名列表
+    #[serde(default)]
+    pub income_amount_aliases: Vec<String>,
+    
+    /// 交易支出金额列的别名列表
+    #[serde(default)]
+    pub expense_amount_aliases: Vec<String>,
+    
+    /// 余额列的别名列表
+    #[serde(default)]
+    pub balance_aliases: Vec<String>,
+    
+    /// 资金属性列的别名列表
+    #[serde(default)]
+    pub fund_attribute_aliases: Vec<String>,
+
+    /// 流水号列名（可选；配置后读入并优先按流水号排序）
+    #[serde(default)]
+    pub sequence_number_column: Option<String>,
+
+    /// 流水号列的别名列表
+    #[serde(default)]
+    pub sequence_number_aliases: Vec<String>,
+
+    /// 币种列名（可选；配置后读入各行币种，按多币种配置折算）
+    #[serde(default)]
+    pub currency_column: Option<String>,
+
+    /// 币种列的别名列表
+    #[serde(default)]
+    pub currency_aliases: Vec<String>,
+
+    /// 导出表头语言（旧配置文件缺少该字段时为中文）
+    #[serde(default)]
+    pub header_language: HeaderLanguage,
+}
+
+impl ExcelColumnConfig {
+    /// 创建默认Excel列配置
+    #[must_use] 
+    pub fn new() -> Self {
+        Self {
+            transaction_date_column: "交易日期".to_string(),
+            transaction_time_column: "交易时间".to_string(),
+            income_amount_column: "交易收入金额".to_string(),
+            expense_amount_column: "交易支出金额".to_string(),
+            balance_column: "余额".to_string(),
+            fund_attribute_column: "资金属性".to_string(),
+            transaction_date_aliases: Vec::new(),
+            transaction_time_aliases: Vec::new(),
+            income_amount_aliases: Vec::new(),
+            expense_amount_aliases: Vec::new(),
+            balance_aliases: Vec::new(),
+            fund_attribute_aliases: Vec::new(),
+            sequence_number_column: None,
+            sequence_number_aliases: Vec::new(),
+            currency_column: None,
+            currency_aliases: Vec::new(),
+            header_language: HeaderLanguage::default(),
+        }
+    }
+    
+    /// 判断表头名是否匹配某列（主列名或其别名之一）
+    #[must_use]
+    pub fn column_matches(primary: &str, aliases: &[String], name: &str) -> bool {
+        name == primary || aliases.iter().any(|alias| alias == name)
+    }
+    
+    /// 应用列映射档案
+    /// 
+    /// 档案中每列的首个名称作为主列名（用于校验报告等展示），
+    /// 其余作为别名；空列表保持当前配置不变
+    pub fn apply_mapping_profile(&mut self, profile: &ColumnMappingProfile) {
+        fn apply(primary: &mut String, aliases: &mut Vec<String>, names: &[String]) {
+            if let Some((first, rest)) = names.split_first() {
+                first.clone_into(primary);
+                *aliases = rest.to_vec();
+            }
+        }
+        
+        apply(&mut self.transaction_date_column, &mut self.transaction_date_aliases, &profile.transaction_date);
+        apply(&mut self.transaction_time_column, &mut self.transaction_time_aliases, &profile.transaction_time);
+        apply(&mut self.income_amount_column, &mut self.income_amount_aliases, &profile.income_amount);
+        apply(&mut self.expense_amount_column, &mut self.expense_amount_aliases, &profile.expense_amount);
+        apply(&mut self.balance_column, &mut self.balance_aliases, &profile.balance);
+        apply(&mut self.fund_attribute_column, &mut self.fund_attribute_aliases, &profile.fund_attribute);
+
+        // 流水号列为可选列，档案给出名称才启用
+        if let Some((first, rest)) = profile.sequence_number.split_first() {
+            self.sequence_number_column = Some(first.clone());
+            self.sequence_number_aliases = rest.to_vec();
+        }
+
+        // 币种列同为可选列
+        if let Some((first, rest)) = profile.currency.split_first() {
+            self.currency_column = Some(first.clone());
+            self.currency_aliases = rest.to_vec();
+        }
+    }
+    
+    /// 获取所有必需列名
+    #[must_use] 
+    pub fn get_required_columns(&self) -> Vec<String> {
+        vec![
+            self.transaction_date_column.clone(),
+            self.transaction_time_column.clone(),
+            self.income_amount_column.clone(),
+            self.expense_amount_column.clone(),
+            self.balance_column.clone(),
+            self.fund_attribute_column.clone(),
+        ]
+    }
+}
+
+impl Default for ExcelColumnConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 列映射档案 - 从用户提供的JSON/TOML文件加载
+/// 
+/// 每列是一个可接受的表头名称列表：首个名称作为主列名，其余作为别名。
+/// 不同银行导出的流水无需手工改名表头即可直接读入，例如：
+/// 
+/// ```toml
+/// transaction_date = ["Transaction Date", "交易日期", "记账日期"]
+/// balance = ["Balance", "账户余额"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ColumnMappingProfile {
+    /// 交易日期列的可接受名称
+    #[serde(default)]
+    pub transaction_date: Vec<String>,
+    
+    /// 交易时间列的可接受名称
+    #[serde(default)]
+    pub transaction_time: Vec<String>,
+    
+    /// 交易收入金额列的可接受名称
+    #[serde(default)]
+    pub income_amount: Vec<String>,
+    
+    /// 交易支出金额列的可接受名称
+    #[serde(default)]
+    pub expense_amount: Vec<String>,
+    
+    /// 余额列的可接受名称
+    #[serde(default)]
+    pub balance: Vec<String>,
+    
+    /// 资金属性列的可接受名称
+    #[serde(default)]
+    pub fund_attribute: Vec<String>,
+
+    /// 流水号列的可接受名称（可选列，给出名称即启用按流水号排序）
+    #[serde(default)]
+    pub sequence_number: Vec<String>,
+
+    /// 币种列的可接受名称（可选列，给出名称即启用多币种读入）
+    #[serde(default)]
+    pub currency: Vec<String>,
+}
+
+impl ColumnMappingProfile {
+    /// 从JSON/TOML文件加载列映射档案（按扩展名区分格式）
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> AuditResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AuditError::config_error(format!("无法读取列映射档案 {}: {e}", path.display())))?;
+        
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content)
+                .map_err(|e| AuditError::config_error(format!("列映射档案TOML解析失败: {e}"))),
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| AuditError::config_error(format!("列映射档案JSON解析失败: {e}"))),
+            _ => Err(AuditError::config_error(
+                format!("不支持的列映射档案格式: {}（仅支持.toml/.json）", path.display())
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_column_mapping_profile() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        
+        // TOML格式：首个名称为主列名，其余为别名
+        let toml_path = temp_dir.path().join("mapping.toml");
+        std::fs::write(&toml_path,
+            "transaction_date = [\"Transaction Date\", \"记账日期\"]\nbalance = [\"Balance\"]\n").unwrap();
+        let profile = ColumnMappingProfile::load_from_file(&toml_path).unwrap();
+        
+        let mut columns = ExcelColumnConfig::new();
+        columns.apply_mapping_profile(&profile);
+        assert_eq!(columns.transaction_date_column, "Transaction Date");
+        assert_eq!(columns.transaction_date_aliases, vec!["记账日期".to_string()]);
+        assert_eq!(columns.balance_column, "Balance");
+        // 未指定的列保持默认
+        assert_eq!(columns.fund_attribute_column, "资金属性");
+        
+        // 主列名与别名均可匹配
+        assert!(ExcelColumnConfig::column_matches(
+            &columns.transaction_date_column, &columns.transaction_date_aliases, "Transaction Date"));
+        assert!(ExcelColumnConfig::column_matches(
+            &columns.transaction_date_column, &columns.transaction_date_aliases, "记账日期"));
+        assert!(!ExcelColumnConfig::column_matches(
+            &columns.transaction_date_column, &columns.transaction_date_aliases, "交易日期"));
+        
+        // JSON格式
+        let json_path = temp_dir.path().join("mapping.json");
+        std::fs::write(&json_path, r#"{"income_amount": ["Credit", "贷方发生额"]}"#).unwrap();
+        let profile = ColumnMappingProfile::load_from_file(&json_path).unwrap();
+        assert_eq!(profile.income_amount, vec!["Credit".to_string(), "贷方发生额".to_string()]);
+        
+        // 不支持的扩展名报错
+        let bad_path = temp_dir.path().join("mapping.yaml");
+        std::fs::write(&bad_path, "x: 1").unwrap();
+        assert!(ColumnMappingProfile::load_from_file(&bad_path).is_err());
+    }
+
+    #[test]
+    fn test_config_creation() {
+        let config = Config::new();
+        
+        assert_eq!(config.numeric.decimal_places, 2);
+        assert!(config.fund_attributes.personal_fund_keywords.contains("个人"));
+        assert!(config.fund_attributes.company_fund_keywords.contains("公司"));
+        assert!(config.investment_products.product_prefixes.contains(&"理财-".to_string()));
+    }
+    
+    #[test]
+    fn test_fund_classification() {
+        let config = Config::new();
+        
+        assert!(config.is_personal_fund("个人应收"));
+        assert!(config.is_company_fund("公司应付"));
+        assert!(config.is_investment_product("理财-SL100613100620"));
+        
+        assert!(!config.is_personal_fund("公司应收"));
+        assert!(!config.is_company_fund("个人应付"));
+        assert!(!config.is_investment_product("其他资金"));
+    }
+
+    #[test]
+    fn test_custom_classification_rules_override_keywords() {
+        use crate::utils::classification_rules::{
+            ClassificationRule, ClassificationRuleSet, FundCategory, RuleMatcher,
+        };
+
+        let mut config = Config::new();
+        config.classification_rules = Some(ClassificationRuleSet {
+            rules: vec![ClassificationRule {
+                pattern: "股东".to_string(),
+                matcher: RuleMatcher::Contains,
+                category: FundCategory::Personal,
+                priority: 0,
+            }],
+        });
+
+        // 自定义规则命中：内置关键字不认识的"股东借款"归为个人
+        assert!(config.is_personal_fund("股东借款"));
+        // 规则未命中时回退到内置关键字
+        assert!(config.is_company_fund("公司应付"));
+    }
+    
+    #[test]
+    fn test_number_formatting() {
+        let config = Config::new();
+        let value = Decimal::from_f64_retain(123.456789).unwrap();
+        let formatted = config.format_number(value);
+        
+        // 使用字符串比较来避免浮点精度问题
+        assert_eq!(formatted.to_string(), "123.46");
+    }
+    
+    #[test]
+    fn test_balance_tolerance() {
+        let config = Config::new();
+        let balance1 = Decimal::from_f64_retain(100.00).unwrap();
+        let balance2 = Decimal::from_f64_retain(100.005).unwrap();
+        let balance3 = Decimal::from_f64_retain(100.02).unwrap();
+        
+        assert!(config.is_balance_within_tolerance(balance1, balance2));
+        assert!(!config.is_balance_within_tolerance(balance1, balance3));
+    }
+    
+    #[test]
+    fn test_currency_rate_lookup() {
+        let mut currency = CurrencyConfig::default();
+        currency.exchange_rates.insert("USD".to_string(), Decimal::new(72, 1)); // 7.2
+
+        // 基准币种恒为1（忽略大小写）
+        assert!(currency.is_base("cny"));
+        assert_eq!(currency.rate_to_base("CNY"), Some(Decimal::ONE));
+        // 外币按汇率表查找（忽略大小写），未配置返回None
+        assert_eq!(currency.rate_to_base("usd"), Some(Decimal::new(72, 1)));
+        assert_eq!(currency.rate_to_base("EUR"), None);
+    }
+
+    #[test]
+    fn test_interest_fee_classification() {
+        use crate::utils::classification_rules::{
+            ClassificationRule, ClassificationRuleSet, FundCategory, RuleMatcher,
+        };
+
+        let mut config = Config::new();
+        // 内置词面：银行结息、账户管理费命中，普通属性不命中
+        assert!(config.is_interest_or_fee("银行结息"));
+        assert!(config.is_interest_or_fee("账户管理费"));
+        assert!(!config.is_interest_or_fee("个人应收"));
+
+        // 自定义规则优先于关键字词面
+        config.classification_rules = Some(ClassificationRuleSet {
+            rules: vec![ClassificationRule {
+                pattern: "汇兑损益".to_string(),
+                matcher: RuleMatcher::Contains,
+                category: FundCategory::InterestFee,
+                priority: 0,
+            }],
+        });
+        assert!(config.is_interest_or_fee("汇兑损益调整"));
+    }
+
+    #[test]
+    fn test_required_columns() {
+        let excel_config = ExcelColumnConfig::new();
+        let columns = excel_config.get_required_columns();
+        
+        assert_eq!(columns.len(), 6);
+        assert!(columns.contains(&"交易日期".to_string()));
+        assert!(columns.contains(&"交易时间".to_string()));
+        assert!(columns.contains(&"资金属性".to_string()));
+    }
+}
\ No newline at end of file
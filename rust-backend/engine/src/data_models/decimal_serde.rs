@@ -0,0 +1,178 @@
+//! Decimal统一序列化策略
+//!
+//! `rust_decimal`默认将金额序列化为JSON数字，超过`Number.MAX_SAFE_INTEGER`
+//! 的大额资金在前端JS中会丢失精度。本模块提供统一的序列化策略：
+//!
+//! - 序列化：按固定小数位数输出字符串（金额2位，占比4位）
+//! - 反序列化：同时接受字符串和数字，保证旧版payload可以继续解析
+//!
+//! 所有面向Tauri前端的结构体的`Decimal`字段应使用
+//! `#[serde(with = "decimal_string")]`（金额）或
+//! `#[serde(with = "decimal_string_ratio")]`（占比）标注。
+
+use rust_decimal::Decimal;
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// 金额字段的序列化小数位数（与`NumericConfig::decimal_places`保持一致）
+pub const AMOUNT_SCALE: u32 = 2;
+
+/// 占比字段的序列化小数位数
+pub const RATIO_SCALE: u32 = 4;
+
+/// 按指定精度将Decimal格式化为字符串
+///
+/// 例如 `format_with_scale(dec, 2)` 输出 `"12345.60"`
+#[must_use]
+pub fn format_with_scale(value: Decimal, scale: u32) -> String {
+    format!("{:.*}", scale as usize, value.round_dp(scale))
+}
+
+/// 字符串或数字形式的Decimal中间表示
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DecimalRepr {
+    Str(String),
+    Num(f64),
+}
+
+/// 从字符串或数字反序列化Decimal
+fn decimal_from_repr<E: DeError>(repr: DecimalRepr) -> Result<Decimal, E> {
+    match repr {
+        DecimalRepr::Str(s) => s.trim().parse::<Decimal>()
+            .map_err(|e| E::custom(format!("无效的Decimal字符串 '{s}': {e}"))),
+        DecimalRepr::Num(n) => Decimal::from_f64_retain(n)
+            .ok_or_else(|| E::custom(format!("无效的Decimal数值: {n}"))),
+    }
+}
+
+/// 金额字段序列化策略（2位小数字符串）
+pub mod decimal_string {
+    use super::{decimal_from_repr, format_with_scale, Decimal, DecimalRepr, Deserialize, Deserializer, Serializer, AMOUNT_SCALE};
+
+    /// 序列化为固定2位小数的字符串
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_with_scale(*value, AMOUNT_SCALE))
+    }
+
+    /// 从字符串或数字反序列化
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        let repr = DecimalRepr::deserialize(deserializer)?;
+        decimal_from_repr(repr)
+    }
+}
+
+/// 可选金额字段序列化策略
+pub mod decimal_string_option {
+    use super::{decimal_from_repr, format_with_scale, Decimal, DecimalRepr, Deserialize, Deserializer, Serializer, AMOUNT_SCALE};
+
+    /// `None`序列化为null，`Some`序列化为2位小数字符串
+    pub fn serialize<S: Serializer>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_str(&format_with_scale(*v, AMOUNT_SCALE)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// 从字符串、数字或null反序列化
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Decimal>, D::Error> {
+        let repr = Option::<DecimalRepr>::deserialize(deserializer)?;
+        repr.map(decimal_from_repr).transpose()
+    }
+}
+
+/// 占比字段序列化策略（4位小数字符串，避免0-1之间的比例被过度舍入）
+pub mod decimal_string_ratio {
+    use super::{decimal_from_repr, format_with_scale, Decimal, DecimalRepr, Deserialize, Deserializer, Serializer, RATIO_SCALE};
+
+    /// 序列化为固定4位小数的字符串
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_with_scale(*value, RATIO_SCALE))
+    }
+
+    /// 从字符串或数字反序列化
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        let repr = DecimalRepr::deserialize(deserializer)?;
+        decimal_from_repr(repr)
+    }
+}
+
+/// 可选占比字段序列化策略
+pub mod decimal_string_ratio_option {
+    use super::{decimal_from_repr, format_with_scale, Decimal, DecimalRepr, Deserialize, Deserializer, Serializer, RATIO_SCALE};
+
+    /// `None`序列化为null，`Some`序列化为4位小数字符串
+    pub fn serialize<S: Serializer>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_str(&format_with_scale(*v, RATIO_SCALE)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// 从字符串、数字或null反序列化
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Decimal>, D::Error> {
+        let repr = Option::<DecimalRepr>::deserialize(deserializer)?;
+        repr.map(decimal_from_repr).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        #[serde(with = "decimal_string")]
+        amount: Decimal,
+        #[serde(default, with = "decimal_string_option")]
+        optional_amount: Option<Decimal>,
+        #[serde(with = "decimal_string_ratio")]
+        ratio: Decimal,
+    }
+
+    #[test]
+    fn test_format_with_scale() {
+        assert_eq!(format_with_scale(Decimal::from(100), 2), "100.00");
+        assert_eq!(format_with_scale(Decimal::new(123456, 3), 2), "123.46");
+        assert_eq!(format_with_scale(Decimal::new(375, 3), 4), "0.3750");
+    }
+
+    #[test]
+    fn test_serialize_as_string() {
+        let payload = Payload {
+            amount: Decimal::new(1234567890123456789, 2), // 12345678901234567.89
+            optional_amount: None,
+            ratio: Decimal::new(375, 3), // 0.375
+        };
+
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.contains("\"amount\":\"12345678901234567.89\""));
+        assert!(json.contains("\"optional_amount\":null"));
+        assert!(json.contains("\"ratio\":\"0.3750\""));
+    }
+
+    #[test]
+    fn test_round_trip_large_amount() {
+        // 超过f64安全整数范围的大额资金，字符串序列化不丢失精度
+        let payload = Payload {
+            amount: Decimal::new(9007199254740993_i64 * 100 + 12, 2), // 9007199254740993.12
+            optional_amount: Some(Decimal::new(-98765432109876543, 2)),
+            ratio: Decimal::ONE,
+        };
+
+        let json = serde_json::to_string(&payload).unwrap();
+        let restored: Payload = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, payload);
+    }
+
+    #[test]
+    fn test_deserialize_accepts_numbers() {
+        // 旧版payload中的JSON数字仍然可以解析
+        let json = r#"{"amount": 1234.56, "optional_amount": 78.9, "ratio": 0.5}"#;
+        let payload: Payload = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.amount, Decimal::from_f64_retain(1234.56).unwrap());
+        assert_eq!(payload.optional_amount, Decimal::from_f64_retain(78.9));
+        assert_eq!(payload.ratio, Decimal::new(5, 1));
+    }
+}
@@ -65,6 +65,7 @@ pub struct FundPoolRecord {
 
 impl FundPoolRecord {
     /// 创建新的资金池记录
+    #[must_use] 
     pub fn new(
         transaction_time: String,
         pool_name: String,
@@ -98,21 +99,25 @@ impl FundPoolRecord {
     }
     
     /// 获取净现金流
+    #[must_use] 
     pub fn net_cash_flow(&self) -> Decimal {
         self.inflow - self.outflow
     }
     
     /// 判断是否为申购操作
+    #[must_use] 
     pub fn is_purchase(&self) -> bool {
         self.inflow > Decimal::ZERO && self.outflow == Decimal::ZERO
     }
     
     /// 判断是否为赎回操作
+    #[must_use] 
     pub fn is_redemption(&self) -> bool {
         self.outflow > Decimal::ZERO && self.inflow == Decimal::ZERO
     }
     
     /// 计算盈亏情况
+    #[must_use] 
     pub fn calculate_profit_loss(&self) -> Decimal {
         self.net_profit_loss
     }
@@ -153,6 +158,7 @@ pub struct FundPoolSummary {
 
 impl FundPoolSummary {
     /// 从资金池记录列表创建汇总
+    #[must_use] 
     pub fn from_records(pool_name: &str, records: &[FundPoolRecord]) -> Self {
         if records.is_empty() {
             return Self {
@@ -170,7 +176,7 @@ impl FundPoolSummary {
         
         let total_inflow = records.iter().map(|r| r.inflow).sum();
         let total_outflow = records.iter().map(|r| r.outflow).sum();
-        let net_profit_loss = records.last().map(|r| r.calculate_profit_loss()).unwrap_or(Decimal::ZERO);
+        let net_profit_loss = records.last().map_or(Decimal::ZERO, FundPoolRecord::calculate_profit_loss);
         
         let status = if net_profit_loss > Decimal::ZERO {
             "盈利"
@@ -180,7 +186,7 @@ impl FundPoolSummary {
             "持平"
         }.to_string();
         
-        let current_balance = records.last().map(|r| r.total_balance).unwrap_or(Decimal::ZERO);
+        let current_balance = records.last().map_or(Decimal::ZERO, |r| r.total_balance);
         
         Self {
             pool_name: pool_name.to_string(),
@@ -196,6 +202,7 @@ impl FundPoolSummary {
     }
     
     /// 计算投资回报率
+    #[must_use] 
     pub fn calculate_return_rate(&self) -> Option<Decimal> {
         if self.total_inflow > Decimal::ZERO {
             Some(self.net_profit_loss / self.total_inflow)
@@ -216,6 +223,7 @@ pub struct FundPoolManager {
 
 impl FundPoolManager {
     /// 创建新的资金池管理器
+    #[must_use] 
     pub fn new() -> Self {
         Self {
             pools: HashMap::new(),
@@ -226,16 +234,18 @@ impl FundPoolManager {
     pub fn add_record(&mut self, record: FundPoolRecord) {
         self.pools
             .entry(record.pool_name.clone())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(record);
     }
     
     /// 获取指定资金池的记录
+    #[must_use] 
     pub fn get_pool_records(&self, pool_name: &str) -> Option<&Vec<FundPoolRecord>> {
         self.pools.get(pool_name)
     }
     
     /// 获取指定资金池的汇总信息
+    #[must_use] 
     pub fn get_pool_summary(&self, pool_name: &str) -> Option<FundPoolSummary> {
         self.pools.get(pool_name).map(|records| {
             FundPoolSummary::from_records(pool_name, records)
@@ -243,14 +253,16 @@ impl FundPoolManager {
     }
     
     /// 获取所有资金池名称
+    #[must_use] 
     pub fn get_all_pool_names(&self) -> Vec<String> {
         self.pools.keys().cloned().collect()
     }
     
     /// 获取总体统计信息
+    #[must_use] 
     pub fn get_overall_statistics(&self) -> OverallPoolStatistics {
         let total_pools = self.pools.len();
-        let total_records: usize = self.pools.values().map(|records| records.len()).sum();
+        let total_records: usize = self.pools.values().map(std::vec::Vec::len).sum();
         
         let summaries: Vec<FundPoolSummary> = self.pools.iter()
             .map(|(name, records)| FundPoolSummary::from_records(name, records))
@@ -4,17 +4,22 @@
 
 pub mod transaction;
 pub mod audit_summary;
+pub mod audit_warning;
 pub mod fund_pool;
 pub mod config;
+pub mod decimal_serde;
 pub mod tauri_types;
 pub mod offsite_pool_record;
+pub mod opening_balance;
 // pub mod investment; // 已删除，功能合并到algorithms/shared
 
 // 重新导出主要类型
 pub use transaction::*;
 pub use audit_summary::*;
+pub use audit_warning::*;
 pub use fund_pool::*;
 pub use config::*;
 pub use tauri_types::*;
 pub use offsite_pool_record::*;
+pub use opening_balance::*;
 // pub use investment::*; // 已删除
\ No newline at end of file
@@ -3,13 +3,16 @@
 //! 用于跟踪和记录投资产品的资金流入流出情况
 
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDateTime};
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use std::collections::HashMap;
 
+use crate::data_models::PoolProfitAllocationStrategy;
+
 /// 场外资金池记录
 /// 记录每笔投资产品的申购/赎回交易详情
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OffsitePoolRecord {
     /// 交易时间
     pub transaction_time: String,
@@ -50,6 +53,7 @@ pub struct OffsitePoolRecord {
 
 impl OffsitePoolRecord {
     /// 创建申购记录
+    #[must_use] 
     pub fn new_purchase(
         transaction_time: Option<DateTime<Local>>,
         pool_name: String,
@@ -82,8 +86,7 @@ impl OffsitePoolRecord {
         
         // 行为性质描述
         let behavior_nature = format!(
-            "入金（个人{:.0}，公司{:.0}）",
-            personal_amount, company_amount
+            "入金（个人{personal_amount:.0}，公司{company_amount:.0}）"
         );
         
         Self {
@@ -103,6 +106,7 @@ impl OffsitePoolRecord {
     }
     
     /// 创建赎回记录
+    #[must_use] 
     pub fn new_redemption(
         transaction_time: Option<DateTime<Local>>,
         pool_name: String,
@@ -136,8 +140,7 @@ impl OffsitePoolRecord {
         
         // 行为性质描述
         let behavior_nature = format!(
-            "出金（个人{:.0}，公司{:.0}，收益{:.0}）",
-            personal_return, company_return, profit
+            "出金（个人{personal_return:.0}，公司{company_return:.0}，收益{profit:.0}）"
         );
         
         Self {
@@ -159,7 +162,7 @@ impl OffsitePoolRecord {
 
 /// 场外资金池记录管理器
 /// 负责收集、组织和导出场外资金池记录
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct OffsitePoolRecordManager {
     /// 记录列表
     pub records: Vec<OffsitePoolRecord>,
@@ -167,6 +170,7 @@ pub struct OffsitePoolRecordManager {
 
 impl OffsitePoolRecordManager {
     /// 创建新的记录管理器
+    #[must_use] 
     pub fn new() -> Self {
         Self {
             records: Vec::new(),
@@ -244,6 +248,7 @@ impl OffsitePoolRecordManager {
     }
     
     /// 获取记录数量
+    #[must_use] 
     pub fn record_count(&self) -> usize {
         self.records.len()
     }
@@ -273,6 +278,7 @@ impl OffsitePoolRecordManager {
     }
     
     /// 获取指定资金池的记录
+    #[must_use] 
     pub fn get_pool_records(&self, pool_name: &str) -> Vec<&OffsitePoolRecord> {
         self.records
             .iter()
@@ -280,8 +286,19 @@ impl OffsitePoolRecordManager {
             .collect()
     }
     
-    /// 计算指定资金池的统计信息
+    /// 计算指定资金池的统计信息（使用默认的负余额周期分摊策略）
+    #[must_use] 
     pub fn calculate_pool_stats(&self, pool_name: &str) -> Option<PoolStatistics> {
+        self.calculate_pool_stats_with_strategy(pool_name, PoolProfitAllocationStrategy::default())
+    }
+    
+    /// 计算指定资金池的统计信息，盈亏按指定策略在个人/公司间分摊
+    #[must_use]
+    pub fn calculate_pool_stats_with_strategy(
+        &self,
+        pool_name: &str,
+        strategy: PoolProfitAllocationStrategy,
+    ) -> Option<PoolStatistics> {
         let pool_records = self.get_pool_records(pool_name);
         
         if pool_records.is_empty() {
@@ -293,9 +310,6 @@ impl OffsitePoolRecordManager {
         let mut final_balance = Decimal::ZERO;
         let mut final_personal_balance = Decimal::ZERO;
         let mut final_company_balance = Decimal::ZERO;
-        let cumulative_profit_loss = Decimal::ZERO;
-        let mut cumulative_personal_profit_loss = Decimal::ZERO;
-        let mut cumulative_company_profit_loss = Decimal::ZERO;
         
         for record in &pool_records {
             total_purchase += record.inflow;
@@ -312,7 +326,177 @@ impl OffsitePoolRecordManager {
         // 计算总净盈亏：直接使用累计申购赎回数据（最简单最可靠的方法）
         let profit_loss = total_redemption - total_purchase;
         
-        // 新的通用逻辑：区分盈利和亏损状态的不同计算方式
+        // 个人/公司盈亏分摊按配置策略计算，口径见各策略文档
+        let (cumulative_personal_profit_loss, cumulative_company_profit_loss) = match strategy {
+            PoolProfitAllocationStrategy::RealizedCycle => {
+                Self::allocate_by_realized_cycle(&pool_records, profit_loss)
+            }
+            PoolProfitAllocationStrategy::FinalRatio => {
+                Self::allocate_by_final_ratio(&pool_records, profit_loss)
+            }
+            PoolProfitAllocationStrategy::ProportionalToContribution => {
+                Self::allocate_by_contribution(&pool_records, profit_loss)
+            }
+        };
+        
+        let status = if profit_loss > Decimal::ZERO {
+            "盈利"
+        } else if profit_loss < Decimal::ZERO {
+            "亏损"
+        } else {
+            "持平"
+        };
+
+        let (annualized_return_pct, xirr_pct) =
+            Self::calculate_return_metrics(&pool_records, total_purchase, profit_loss);
+
+        Some(PoolStatistics {
+            pool_name: pool_name.to_string(),
+            total_purchase,
+            total_redemption,
+            final_balance,
+            final_personal_balance,
+            final_company_balance,
+            profit_loss,
+            cumulative_personal_profit_loss,
+            cumulative_company_profit_loss,
+            status: status.to_string(),
+            record_count: pool_records.len(),
+            annualized_return_pct,
+            xirr_pct,
+        })
+    }
+
+    /// 计算时间加权收益指标（简单年化收益率与XIRR）
+    ///
+    /// 现金流取审查对象视角：申购为出资（负流），赎回为回收（正流），
+    /// 资金池最终正余额视为期末可回收价值（正流）。交易时间无法解析
+    /// 或持有期不足一天时返回None，不做猜测
+    fn calculate_return_metrics(
+        pool_records: &[&OffsitePoolRecord],
+        total_purchase: Decimal,
+        profit_loss: Decimal,
+    ) -> (Option<Decimal>, Option<Decimal>) {
+        let mut flows: Vec<(NaiveDateTime, f64)> = Vec::new();
+        for record in pool_records {
+            let Some(time) = Self::parse_record_time(&record.transaction_time) else {
+                continue;
+            };
+            if record.inflow > Decimal::ZERO {
+                flows.push((time, -record.inflow.to_f64().unwrap_or(0.0)));
+            }
+            if record.outflow > Decimal::ZERO {
+                flows.push((time, record.outflow.to_f64().unwrap_or(0.0)));
+            }
+        }
+
+        let Some((&(first_time, _), &(last_time, _))) = flows.first().zip(flows.last()) else {
+            return (None, None);
+        };
+        let span_days = (last_time - first_time).num_seconds() as f64 / 86_400.0;
+        if span_days < 1.0 {
+            return (None, None);
+        }
+
+        // 期末正余额作为可回收价值计入最后一笔现金流
+        if let Some(last_record) = pool_records.last() {
+            if last_record.total_balance > Decimal::ZERO {
+                flows.push((last_time, last_record.total_balance.to_f64().unwrap_or(0.0)));
+            }
+        }
+
+        // 简单年化 = 净盈亏 / 总申购 / 持有年数
+        let annualized = if total_purchase > Decimal::ZERO {
+            let rate = profit_loss.to_f64().unwrap_or(0.0)
+                / total_purchase.to_f64().unwrap_or(1.0)
+                / (span_days / 365.0);
+            Decimal::from_f64_retain(rate * 100.0).map(|d| d.round_dp(2))
+        } else {
+            None
+        };
+
+        let xirr = Self::xirr(&flows, first_time)
+            .and_then(|rate| Decimal::from_f64_retain(rate * 100.0))
+            .map(|d| d.round_dp(2));
+
+        (annualized, xirr)
+    }
+
+    /// 解析记录时间文本（"未知时间"等无法解析的返回None）
+    fn parse_record_time(text: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").ok()
+    }
+
+    /// 计算内部收益率XIRR（年化，牛顿迭代+二分回退）
+    ///
+    /// 要求现金流同时存在出资与回收（异号），否则无解返回None；
+    /// 迭代不收敛或解落在无意义区间（≤-100%）同样返回None
+    fn xirr(flows: &[(NaiveDateTime, f64)], anchor: NaiveDateTime) -> Option<f64> {
+        let has_outflow = flows.iter().any(|&(_, cf)| cf < 0.0);
+        let has_inflow = flows.iter().any(|&(_, cf)| cf > 0.0);
+        if !has_outflow || !has_inflow {
+            return None;
+        }
+
+        // 以首笔现金流为时间零点，按年计息
+        let dated: Vec<(f64, f64)> = flows.iter()
+            .map(|&(time, cf)| ((time - anchor).num_seconds() as f64 / 86_400.0 / 365.0, cf))
+            .collect();
+        let npv = |rate: f64| -> f64 {
+            dated.iter().map(|&(years, cf)| cf / (1.0 + rate).powf(years)).sum()
+        };
+
+        // 牛顿迭代（数值导数），失败时在[-0.9999, 10]区间二分
+        let mut rate = 0.1_f64;
+        for _ in 0..60 {
+            let value = npv(rate);
+            if value.abs() < 1e-6 {
+                return (rate > -1.0).then_some(rate);
+            }
+            let delta = 1e-7;
+            let derivative = (npv(rate + delta) - value) / delta;
+            if derivative.abs() < f64::EPSILON {
+                break;
+            }
+            let next = rate - value / derivative;
+            if !next.is_finite() || next <= -1.0 {
+                break;
+            }
+            rate = next;
+        }
+
+        let (mut low, mut high) = (-0.9999_f64, 10.0_f64);
+        if npv(low) * npv(high) > 0.0 {
+            return None;
+        }
+        for _ in 0..200 {
+            let mid = f64::midpoint(low, high);
+            let value = npv(mid);
+            if value.abs() < 1e-6 {
+                return Some(mid);
+            }
+            if npv(low) * value < 0.0 {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Some(f64::midpoint(low, high))
+    }
+    
+    /// 负余额周期法（历史默认行为）
+    /// 
+    /// 盈利时：资金池总余额转负代表已全额收回本金、开始产生净收益，
+    /// 每个以重置（余额归零/转正或"资金池清空"）收尾的负余额周期，
+    /// 按周期末的个人/公司负余额绝对值累加为已实现收益；
+    /// 亏损时：按最终余额比例分摊亏损（等价于最终比例法）。
+    fn allocate_by_realized_cycle(
+        pool_records: &[&OffsitePoolRecord],
+        profit_loss: Decimal,
+    ) -> (Decimal, Decimal) {
+        let mut cumulative_personal_profit_loss = Decimal::ZERO;
+        let mut cumulative_company_profit_loss = Decimal::ZERO;
+        
         if profit_loss > Decimal::ZERO {
             // 盈利状态：累加所有负余额（已实现收益）
             let mut i = 0;
@@ -379,31 +563,72 @@ impl OffsitePoolRecordManager {
             }
         }
         
-        let status = if profit_loss > Decimal::ZERO {
-            "盈利"
-        } else if profit_loss < Decimal::ZERO {
-            "亏损"
-        } else {
-            "持平"
+        (cumulative_personal_profit_loss, cumulative_company_profit_loss)
+    }
+    
+    /// 最终比例法
+    /// 
+    /// 盈亏一律按最后一条记录的个人/公司余额占比分摊。
+    /// 最终总余额不为正时余额占比失去意义，回退为出资比例法。
+    fn allocate_by_final_ratio(
+        pool_records: &[&OffsitePoolRecord],
+        profit_loss: Decimal,
+    ) -> (Decimal, Decimal) {
+        if profit_loss == Decimal::ZERO {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+        
+        let Some(last_record) = pool_records.last() else {
+            return (Decimal::ZERO, Decimal::ZERO);
         };
+        if last_record.total_balance <= Decimal::ZERO {
+            return Self::allocate_by_contribution(pool_records, profit_loss);
+        }
         
-        Some(PoolStatistics {
-            pool_name: pool_name.to_string(),
-            total_purchase,
-            total_redemption,
-            final_balance,
-            final_personal_balance,
-            final_company_balance,
-            profit_loss,
-            cumulative_personal_profit_loss,
-            cumulative_company_profit_loss,
-            status: status.to_string(),
-            record_count: pool_records.len(),
-        })
+        let total = profit_loss.abs();
+        let personal = total * last_record.personal_balance / last_record.total_balance;
+        (personal, total - personal)
+    }
+    
+    /// 出资比例法
+    /// 
+    /// 盈亏按个人/公司累计申购出资占比分摊。出资额由每笔申购前后的
+    /// 个人/公司余额增量还原（申购只会增加余额），无申购记录时不分摊。
+    fn allocate_by_contribution(
+        pool_records: &[&OffsitePoolRecord],
+        profit_loss: Decimal,
+    ) -> (Decimal, Decimal) {
+        if profit_loss == Decimal::ZERO {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+        
+        let mut personal_contribution = Decimal::ZERO;
+        let mut company_contribution = Decimal::ZERO;
+        let mut previous_personal = Decimal::ZERO;
+        let mut previous_company = Decimal::ZERO;
+        
+        for record in pool_records {
+            if record.inflow > Decimal::ZERO {
+                personal_contribution += (record.personal_balance - previous_personal).max(Decimal::ZERO);
+                company_contribution += (record.company_balance - previous_company).max(Decimal::ZERO);
+            }
+            previous_personal = record.personal_balance;
+            previous_company = record.company_balance;
+        }
+        
+        let total_contribution = personal_contribution + company_contribution;
+        if total_contribution <= Decimal::ZERO {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+        
+        let total = profit_loss.abs();
+        let personal = total * personal_contribution / total_contribution;
+        (personal, total - personal)
     }
     
     /// 解析赎回行为性质描述，提取个人和公司的赎回金额
     /// 解析格式：出金（个人304200，公司2155799，收益0）
+    #[allow(dead_code)]
     fn parse_redemption_amounts(behavior_nature: &str) -> (Decimal, Decimal) {
         // 使用正则表达式解析个人和公司金额
         if let Some(start) = behavior_nature.find("出金（") {
@@ -477,9 +702,17 @@ pub struct PoolStatistics {
     
     /// 盈亏状态
     pub status: String,
-    
+
     /// 记录数量
     pub record_count: usize,
+
+    /// 简单年化收益率（%，净盈亏/总申购/持有年数；持有期不足一天时为None）
+    #[serde(default)]
+    pub annualized_return_pct: Option<Decimal>,
+
+    /// 内部收益率XIRR（年化%，基于带日期现金流；现金流同号或不收敛时为None）
+    #[serde(default)]
+    pub xirr_pct: Option<Decimal>,
 }
 
 #[cfg(test)]
@@ -581,6 +814,162 @@ mod tests {
         assert_eq!(stats.total_purchase, Decimal::from(1000));
         assert_eq!(stats.total_redemption, Decimal::from(500));
         assert_eq!(stats.record_count, 2);
+        // 赎回500 < 申购1000，按累计申购赎回口径为亏损
+        assert_eq!(stats.status, "亏损");
+    }
+    
+    /// 构造盈利场景：申购1000（个人600/公司400），赎回1500后余额-500（个人-300/公司-200）
+    fn profitable_manager() -> OffsitePoolRecordManager {
+        let mut manager = OffsitePoolRecordManager::new();
+        manager.add_purchase_record(
+            Some(Local::now()),
+            "测试池".to_string(),
+            Decimal::from(1000),
+            Decimal::from(1000),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::ZERO,
+        );
+        manager.add_redemption_record(
+            Some(Local::now()),
+            "测试池".to_string(),
+            Decimal::from(1500),
+            Decimal::from(-500),
+            Decimal::from(-300),
+            Decimal::from(-200),
+            Decimal::from(900),
+            Decimal::from(600),
+            Decimal::from(500),
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::from(1500),
+        );
+        manager
+    }
+    
+    #[test]
+    fn test_allocation_realized_cycle_profit() {
+        let manager = profitable_manager();
+        let stats = manager.calculate_pool_stats_with_strategy(
+            "测试池", PoolProfitAllocationStrategy::RealizedCycle
+        ).unwrap();
+        
+        // 最终负余额未重置：按最后一笔负余额的绝对值计入已实现收益
+        assert_eq!(stats.profit_loss, Decimal::from(500));
+        assert_eq!(stats.cumulative_personal_profit_loss, Decimal::from(300));
+        assert_eq!(stats.cumulative_company_profit_loss, Decimal::from(200));
         assert_eq!(stats.status, "盈利");
     }
+    
+    #[test]
+    fn test_allocation_final_ratio_loss() {
+        let mut manager = OffsitePoolRecordManager::new();
+        manager.add_purchase_record(
+            Some(Local::now()),
+            "测试池".to_string(),
+            Decimal::from(1000),
+            Decimal::from(1000),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::ZERO,
+        );
+        manager.add_redemption_record(
+            Some(Local::now()),
+            "测试池".to_string(),
+            Decimal::from(500),
+            Decimal::from(500),
+            Decimal::from(300),
+            Decimal::from(200),
+            Decimal::from(300),
+            Decimal::from(200),
+            Decimal::ZERO,
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::from(500),
+        );
+        
+        let stats = manager.calculate_pool_stats_with_strategy(
+            "测试池", PoolProfitAllocationStrategy::FinalRatio
+        ).unwrap();
+        
+        // 亏损500，按最终余额比例（个人300/总500、公司200/总500）分摊
+        assert_eq!(stats.profit_loss, Decimal::from(-500));
+        assert_eq!(stats.cumulative_personal_profit_loss, Decimal::from(300));
+        assert_eq!(stats.cumulative_company_profit_loss, Decimal::from(200));
+    }
+    
+    #[test]
+    fn test_return_metrics_one_year_holding() {
+        use chrono::TimeZone;
+        let mut manager = OffsitePoolRecordManager::new();
+        // 2021-01-01申购1000，整一年后赎回1500：年化与XIRR均应为50%
+        manager.add_purchase_record(
+            Some(Local.with_ymd_and_hms(2021, 1, 1, 10, 0, 0).unwrap()),
+            "测试池".to_string(),
+            Decimal::from(1000),
+            Decimal::from(1000),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::from(600),
+            Decimal::from(400),
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::ZERO,
+        );
+        manager.add_redemption_record(
+            Some(Local.with_ymd_and_hms(2022, 1, 1, 10, 0, 0).unwrap()),
+            "测试池".to_string(),
+            Decimal::from(1500),
+            Decimal::from(-500),
+            Decimal::from(-300),
+            Decimal::from(-200),
+            Decimal::from(900),
+            Decimal::from(600),
+            Decimal::from(500),
+            Decimal::new(6, 1),
+            Decimal::new(4, 1),
+            Decimal::from(1000),
+            Decimal::from(1500),
+        );
+
+        let stats = manager.calculate_pool_stats("测试池").unwrap();
+        assert_eq!(stats.annualized_return_pct, Some(Decimal::from(50)));
+        let xirr = stats.xirr_pct.expect("一年期异号现金流应有XIRR解");
+        assert!((xirr - Decimal::from(50)).abs() < Decimal::ONE, "XIRR偏差过大: {xirr}");
+    }
+
+    #[test]
+    fn test_return_metrics_unavailable_without_dates() {
+        let manager = profitable_manager();
+        let stats = manager.calculate_pool_stats("测试池").unwrap();
+
+        // 同日申赎（持有期不足一天），收益指标不做猜测
+        assert_eq!(stats.annualized_return_pct, None);
+        assert_eq!(stats.xirr_pct, None);
+    }
+
+    #[test]
+    fn test_allocation_proportional_to_contribution() {
+        let manager = profitable_manager();
+        let stats = manager.calculate_pool_stats_with_strategy(
+            "测试池", PoolProfitAllocationStrategy::ProportionalToContribution
+        ).unwrap();
+        
+        // 盈利500，按申购出资比例（个人600/总1000、公司400/总1000）分摊
+        assert_eq!(stats.cumulative_personal_profit_loss, Decimal::from(300));
+        assert_eq!(stats.cumulative_company_profit_loss, Decimal::from(200));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,151 @@
+//! 期初余额核定值
+//!
+//! 审计人员往往掌握经核实的期初余额构成（个人/公司拆分）；
+//! 设置后覆盖首笔交易的启发式推断（见`smart_initialize`）。
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::errors::{AuditError, AuditResult};
+
+/// 期初余额的人工核定值（覆盖首行启发式推断）
+///
+/// 三个字段均可单独设置：未核定总额时沿用首笔交易回推的期初余额，
+/// 未核定拆分时沿用首行资金属性的归属判定
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningBalanceOverride {
+    /// 核定的期初余额总额（None时沿用首笔交易回推值）
+    #[serde(default)]
+    pub total: Option<Decimal>,
+    /// 期初余额中的个人部分金额（与`personal_ratio`互斥）
+    #[serde(default)]
+    pub personal_amount: Option<Decimal>,
+    /// 期初余额中个人部分的占比（0到1，与`personal_amount`互斥）
+    #[serde(default)]
+    pub personal_ratio: Option<Decimal>,
+}
+
+impl OpeningBalanceOverride {
+    /// 是否未设置任何核定值
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.total.is_none() && self.personal_amount.is_none() && self.personal_ratio.is_none()
+    }
+
+    /// 解析为（个人、公司）期初金额
+    ///
+    /// `inferred_total`为首笔交易回推的期初余额，`first_is_personal`
+    /// 为首行资金属性的启发式归属判定；未核定的部分沿用推断值
+    pub fn resolve(
+        &self,
+        inferred_total: Decimal,
+        first_is_personal: bool,
+    ) -> AuditResult<(Decimal, Decimal)> {
+        if self.personal_amount.is_some() && self.personal_ratio.is_some() {
+            return Err(AuditError::validation_error(
+                "期初余额的个人金额与个人占比只能二选一",
+            ));
+        }
+
+        let total = self.total.unwrap_or(inferred_total);
+        if total < Decimal::ZERO {
+            return Err(AuditError::validation_error(format!(
+                "核定期初余额不能为负数: {total}"
+            )));
+        }
+
+        let personal = if let Some(amount) = self.personal_amount {
+            amount
+        } else if let Some(ratio) = self.personal_ratio {
+            if ratio < Decimal::ZERO || ratio > Decimal::ONE {
+                return Err(AuditError::validation_error(format!(
+                    "期初余额个人占比应在0到1之间: {ratio}"
+                )));
+            }
+            (total * ratio).round_dp(2)
+        } else if first_is_personal {
+            total
+        } else {
+            Decimal::ZERO
+        };
+
+        if personal < Decimal::ZERO || personal > total {
+            return Err(AuditError::validation_error(format!(
+                "期初余额个人部分{personal}超出总额{total}的范围"
+            )));
+        }
+
+        Ok((personal, total - personal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_with_explicit_amounts() {
+        let over = OpeningBalanceOverride {
+            total: Some(Decimal::from(100_000)),
+            personal_amount: Some(Decimal::from(30_000)),
+            personal_ratio: None,
+        };
+        let (personal, company) = over.resolve(Decimal::ZERO, false).unwrap();
+        assert_eq!(personal, Decimal::from(30_000));
+        assert_eq!(company, Decimal::from(70_000));
+    }
+
+    #[test]
+    fn test_resolve_ratio_applies_to_inferred_total() {
+        // 未核定总额：沿用回推值80000，按占比0.25拆分
+        let over = OpeningBalanceOverride {
+            total: None,
+            personal_amount: None,
+            personal_ratio: Some(Decimal::new(25, 2)),
+        };
+        let (personal, company) = over.resolve(Decimal::from(80_000), false).unwrap();
+        assert_eq!(personal, Decimal::from(20_000));
+        assert_eq!(company, Decimal::from(60_000));
+    }
+
+    #[test]
+    fn test_resolve_without_split_follows_first_row_attribution() {
+        // 只核定总额：拆分沿用首行归属判定
+        let over = OpeningBalanceOverride {
+            total: Some(Decimal::from(50_000)),
+            personal_amount: None,
+            personal_ratio: None,
+        };
+        let (personal, _) = over.resolve(Decimal::ZERO, true).unwrap();
+        assert_eq!(personal, Decimal::from(50_000));
+        let (personal, company) = over.resolve(Decimal::ZERO, false).unwrap();
+        assert_eq!(personal, Decimal::ZERO);
+        assert_eq!(company, Decimal::from(50_000));
+    }
+
+    #[test]
+    fn test_resolve_rejects_invalid_combinations() {
+        // 金额与占比互斥
+        let over = OpeningBalanceOverride {
+            total: None,
+            personal_amount: Some(Decimal::from(100)),
+            personal_ratio: Some(Decimal::new(5, 1)),
+        };
+        assert!(over.resolve(Decimal::from(1000), false).is_err());
+
+        // 占比越界
+        let over = OpeningBalanceOverride {
+            total: None,
+            personal_amount: None,
+            personal_ratio: Some(Decimal::from(2)),
+        };
+        assert!(over.resolve(Decimal::from(1000), false).is_err());
+
+        // 个人部分超出总额
+        let over = OpeningBalanceOverride {
+            total: Some(Decimal::from(100)),
+            personal_amount: Some(Decimal::from(200)),
+            personal_ratio: None,
+        };
+        assert!(over.resolve(Decimal::ZERO, false).is_err());
+    }
+}
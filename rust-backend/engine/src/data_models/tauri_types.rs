@@ -0,0 +1,826 @@
+//! Tauri GUI兼容的数据类型定义
+//! 
+//! 对应前端TypeScript中定义的接口类型，确保序列化兼容性
+
+use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use crate::data_models::{AuditSummary, AuditWarning, OpeningBalanceOverride, SameTimeOrderingConfig, SameTimeOrderingStrategy};
+
+/// 审计配置（与前端AuditConfig对应）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriAuditConfig {
+    pub algorithm: String,
+    pub input_file: String,
+    pub output_file: Option<String>,
+    /// 仅分析该日期（含）之后的交易，格式`YYYY-MM-DD`（部分期间审计）
+    #[serde(default)]
+    pub date_from: Option<String>,
+    /// 仅分析该日期（含）之前的交易，格式`YYYY-MM-DD`
+    #[serde(default)]
+    pub date_to: Option<String>,
+    /// 要读取的工作表名（None时读取第一个工作表）
+    #[serde(default)]
+    pub sheet_name: Option<String>,
+    /// 核定期初余额总额（None时沿用首笔交易回推值）
+    #[serde(default)]
+    pub opening_balance: Option<String>,
+    /// 期初余额中的个人部分金额（与`opening_ratio`互斥）
+    #[serde(default)]
+    pub opening_personal: Option<String>,
+    /// 期初余额中个人部分的占比（0到1，与`opening_personal`互斥）
+    #[serde(default)]
+    pub opening_ratio: Option<String>,
+    /// 同时间戳交易排序策略（None时沿用贪心余额链修复）
+    #[serde(default)]
+    pub same_time_ordering: Option<String>,
+    /// `TIEBREAK_COLUMN`策略使用的仲裁列名（如"流水号"）
+    #[serde(default)]
+    pub tiebreak_column: Option<String>,
+}
+
+/// 配置字段级校验错误（与前端约定的结构，GUI据此高亮对应输入框）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriConfigFieldError {
+    /// 出错字段名（algorithm/`input_file`/`output_file`）
+    pub field: String,
+    /// 错误码（前端据此做本地化与分类处理）
+    pub code: String,
+    /// 错误描述
+    pub message: String,
+}
+
+impl TauriConfigFieldError {
+    /// 创建字段级错误
+    #[must_use]
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl TauriAuditConfig {
+    /// 支持的算法白名单
+    pub const SUPPORTED_ALGORITHMS: [&'static str; 2] = ["FIFO", "BALANCE_METHOD"];
+
+    /// 输入文件允许的扩展名
+    const INPUT_EXTENSIONS: [&'static str; 2] = ["xlsx", "xls"];
+
+    /// 输出文件允许的扩展名（超大结果集按扩展名选择分表Excel或流式CSV）
+    const OUTPUT_EXTENSIONS: [&'static str; 2] = ["xlsx", "csv"];
+
+    /// 支持的同时间戳交易排序策略
+    pub const ORDERING_STRATEGIES: [&'static str; 4] = [
+        "GREEDY_BALANCE_CHAIN", "KEEP_ORIGINAL", "INCOME_FIRST", "TIEBREAK_COLUMN",
+    ];
+
+    /// 严格校验前端传入的配置
+    ///
+    /// 一次性收集全部字段级错误（而非遇错即返），
+    /// 便于GUI同时高亮所有有问题的输入项
+    pub fn validate(&self) -> Result<(), Vec<TauriConfigFieldError>> {
+        let mut errors = Vec::new();
+
+        // 算法白名单
+        if !Self::SUPPORTED_ALGORITHMS.contains(&self.algorithm.as_str()) {
+            errors.push(TauriConfigFieldError::new(
+                "algorithm",
+                "UNSUPPORTED_ALGORITHM",
+                format!(
+                    "不支持的算法\"{}\"，可选值: {}",
+                    self.algorithm,
+                    Self::SUPPORTED_ALGORITHMS.join("、")
+                ),
+            ));
+        }
+
+        // 输入文件：非空、扩展名、存在性、可读性
+        let input = self.input_file.trim();
+        if input.is_empty() {
+            errors.push(TauriConfigFieldError::new(
+                "input_file",
+                "EMPTY_PATH",
+                "未选择输入文件",
+            ));
+        } else {
+            let path = std::path::Path::new(input);
+            let extension = path.extension()
+                .and_then(|e| e.to_str())
+                .map(str::to_lowercase);
+            if !extension.as_deref().is_some_and(|e| Self::INPUT_EXTENSIONS.contains(&e)) {
+                errors.push(TauriConfigFieldError::new(
+                    "input_file",
+                    "UNSUPPORTED_EXTENSION",
+                    format!("输入文件扩展名需为 {}", Self::INPUT_EXTENSIONS.join("/")),
+                ));
+            }
+            if !path.exists() {
+                errors.push(TauriConfigFieldError::new(
+                    "input_file",
+                    "FILE_NOT_FOUND",
+                    format!("输入文件不存在: {input}"),
+                ));
+            } else if !path.is_file() {
+                errors.push(TauriConfigFieldError::new(
+                    "input_file",
+                    "NOT_A_FILE",
+                    format!("输入路径不是文件: {input}"),
+                ));
+            } else if std::fs::File::open(path).is_err() {
+                errors.push(TauriConfigFieldError::new(
+                    "input_file",
+                    "FILE_UNREADABLE",
+                    format!("输入文件无法读取（权限或占用）: {input}"),
+                ));
+            }
+        }
+
+        // 输出文件（可选）：扩展名、父目录存在且可写
+        if let Some(output) = self.output_file.as_deref().map(str::trim) {
+            if output.is_empty() {
+                errors.push(TauriConfigFieldError::new(
+                    "output_file",
+                    "EMPTY_PATH",
+                    "输出路径不能为空字符串（使用默认路径时应省略该字段）",
+                ));
+            } else {
+                let path = std::path::Path::new(output);
+                let extension = path.extension()
+                    .and_then(|e| e.to_str())
+                    .map(str::to_lowercase);
+                if !extension.as_deref().is_some_and(|e| Self::OUTPUT_EXTENSIONS.contains(&e)) {
+                    errors.push(TauriConfigFieldError::new(
+                        "output_file",
+                        "UNSUPPORTED_EXTENSION",
+                        format!("输出文件扩展名需为 {}", Self::OUTPUT_EXTENSIONS.join("/")),
+                    ));
+                }
+                // 父目录为空字符串表示相对当前目录，视为存在
+                let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+                if let Some(parent) = parent {
+                    if !parent.is_dir() {
+                        errors.push(TauriConfigFieldError::new(
+                            "output_file",
+                            "OUTPUT_DIR_NOT_FOUND",
+                            format!("输出目录不存在: {}", parent.display()),
+                        ));
+                    } else if std::fs::metadata(parent)
+                        .map_or(true, |m| m.permissions().readonly())
+                    {
+                        errors.push(TauriConfigFieldError::new(
+                            "output_file",
+                            "OUTPUT_DIR_READONLY",
+                            format!("输出目录只读，无法写入: {}", parent.display()),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // 工作表（可选）：非空白（存在性在打开工作簿时校验并列出可选项）
+        if self.sheet_name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            errors.push(TauriConfigFieldError::new(
+                "sheet_name",
+                "EMPTY_SHEET_NAME",
+                "工作表名不能为空字符串（使用第一个工作表时应省略该字段）",
+            ));
+        }
+
+        // 期初余额核定值（可选）：金额格式、占比范围与互斥关系
+        let parse_amount = |field: &'static str, value: Option<&str>, errors: &mut Vec<TauriConfigFieldError>| {
+            value.and_then(|raw| {
+                let parsed = raw.trim().parse::<Decimal>().ok();
+                if parsed.is_none() {
+                    errors.push(TauriConfigFieldError::new(
+                        field,
+                        "INVALID_AMOUNT",
+                        format!("金额格式无效: {raw}"),
+                    ));
+                }
+                parsed
+            })
+        };
+        for (field, value) in [
+            ("opening_balance", self.opening_balance.as_deref()),
+            ("opening_personal", self.opening_personal.as_deref()),
+        ] {
+            if parse_amount(field, value, &mut errors).is_some_and(|amount| amount < Decimal::ZERO) {
+                errors.push(TauriConfigFieldError::new(
+                    field,
+                    "NEGATIVE_AMOUNT",
+                    "核定金额不能为负数",
+                ));
+            }
+        }
+        if let Some(raw) = self.opening_ratio.as_deref() {
+            match raw.trim().parse::<Decimal>() {
+                Ok(ratio) if ratio >= Decimal::ZERO && ratio <= Decimal::ONE => {}
+                Ok(ratio) => errors.push(TauriConfigFieldError::new(
+                    "opening_ratio",
+                    "RATIO_OUT_OF_RANGE",
+                    format!("个人占比应在0到1之间: {ratio}"),
+                )),
+                Err(_) => errors.push(TauriConfigFieldError::new(
+                    "opening_ratio",
+                    "INVALID_RATIO",
+                    format!("占比格式无效: {raw}"),
+                )),
+            }
+        }
+        if self.opening_personal.is_some() && self.opening_ratio.is_some() {
+            errors.push(TauriConfigFieldError::new(
+                "opening_personal",
+                "CONFLICTING_SPLIT",
+                "期初余额的个人金额与个人占比只能二选一",
+            ));
+        }
+
+        // 同时间排序策略（可选）：策略白名单与仲裁列名非空
+        if let Some(strategy) = self.same_time_ordering.as_deref() {
+            if !Self::ORDERING_STRATEGIES.contains(&strategy) {
+                errors.push(TauriConfigFieldError::new(
+                    "same_time_ordering",
+                    "UNSUPPORTED_ORDERING",
+                    format!(
+                        "不支持的同时间排序策略\"{strategy}\"，可选值: {}",
+                        Self::ORDERING_STRATEGIES.join("、")
+                    ),
+                ));
+            }
+        }
+        if self.tiebreak_column.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            errors.push(TauriConfigFieldError::new(
+                "tiebreak_column",
+                "EMPTY_COLUMN_NAME",
+                "仲裁列名不能为空字符串（不使用仲裁列时应省略该字段）",
+            ));
+        }
+        if self.same_time_ordering.as_deref() == Some("TIEBREAK_COLUMN") && self.tiebreak_column.is_none() {
+            errors.push(TauriConfigFieldError::new(
+                "tiebreak_column",
+                "MISSING_COLUMN_NAME",
+                "TIEBREAK_COLUMN策略需要指定仲裁列名（如\"流水号\"）",
+            ));
+        }
+
+        // 时间窗（可选）：日期格式与起止顺序
+        let parse_window_date = |field: &'static str, value: Option<&str>, errors: &mut Vec<TauriConfigFieldError>| {
+            value.map(|raw| {
+                chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").map_err(|_| {
+                    errors.push(TauriConfigFieldError::new(
+                        field,
+                        "INVALID_DATE",
+                        format!("日期格式应为YYYY-MM-DD: {raw}"),
+                    ));
+                }).ok()
+            })
+        };
+        let from = parse_window_date("date_from", self.date_from.as_deref(), &mut errors).flatten();
+        let to = parse_window_date("date_to", self.date_to.as_deref(), &mut errors).flatten();
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                errors.push(TauriConfigFieldError::new(
+                    "date_from",
+                    "INVALID_DATE_RANGE",
+                    format!("起始日期{from}晚于结束日期{to}"),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// 构造期初余额核定值（应在validate通过后调用）
+    ///
+    /// 三个核定字段都未设置时返回None，分析沿用首行启发式推断
+    #[must_use]
+    pub fn opening_balance_override(&self) -> Option<OpeningBalanceOverride> {
+        let parse = |value: &Option<String>| {
+            value.as_deref().and_then(|raw| raw.trim().parse::<Decimal>().ok())
+        };
+        let over = OpeningBalanceOverride {
+            total: parse(&self.opening_balance),
+            personal_amount: parse(&self.opening_personal),
+            personal_ratio: parse(&self.opening_ratio),
+        };
+        (!over.is_empty()).then_some(over)
+    }
+
+    /// 构造同时间戳交易排序策略配置（应在validate通过后调用）
+    ///
+    /// 未指定策略时返回None，分析沿用引擎配置中的策略
+    #[must_use]
+    pub fn same_time_ordering_config(&self) -> Option<SameTimeOrderingConfig> {
+        let strategy = match self.same_time_ordering.as_deref()? {
+            "KEEP_ORIGINAL" => SameTimeOrderingStrategy::KeepOriginal,
+            "INCOME_FIRST" => SameTimeOrderingStrategy::IncomeFirst,
+            "TIEBREAK_COLUMN" => SameTimeOrderingStrategy::TiebreakColumn,
+            _ => SameTimeOrderingStrategy::GreedyBalanceChain,
+        };
+        Some(SameTimeOrderingConfig {
+            strategy,
+            tiebreak_column: self.tiebreak_column.clone(),
+        })
+    }
+}
+
+/// 审计结果（与前端AuditResult对应）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriAuditResult {
+    pub success: bool,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<TauriResultData>,
+    pub output_files: Vec<String>,
+    /// 运行过程中收集的结构化警告
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AuditWarning>,
+}
+
+/// 审计结果数据部分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriResultData {
+    pub summary: AuditSummary,
+    pub transaction_count: usize,
+    pub processing_time: f64,
+    pub algorithm: String,
+    /// 验证阶段未能修复的错误数
+    #[serde(default)]
+    pub validation_errors: usize,
+    /// 验证阶段自动重排修复的组数
+    #[serde(default)]
+    pub validation_fixes: usize,
+}
+
+/// 进程状态（与前端ProcessStatus对应）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriProcessStatus {
+    pub running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub output_log: Vec<String>,
+}
+
+/// 时点查询配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriTimePointQuery {
+    pub file_path: String,
+    pub row_number: usize,
+    pub algorithm: String,
+}
+
+/// 时点查询结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriQueryResult {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processing_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_row: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_rows: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_time: Option<String>,
+}
+
+/// 文件信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriFileInfo {
+    pub path: String,
+    pub name: String,
+    pub size: u64,
+    pub modified: String,
+    pub exists: bool,
+}
+
+/// 文件对话框扩展名过滤器
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriFileDialogFilter {
+    pub name: String,
+    pub extensions: Vec<String>,
+}
+
+/// 打开文件对话框配置（由后端统一下发，避免各前端页面重复定义）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriFileDialogConfig {
+    /// 默认打开目录
+    pub default_directory: String,
+    /// 上次使用的目录（优先于默认目录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_directory: Option<String>,
+    /// 扩展名过滤器
+    pub filters: Vec<TauriFileDialogFilter>,
+}
+
+impl TauriFileDialogConfig {
+    /// 创建打开流水文件的默认对话框配置
+    ///
+    /// `workspace_dir`为应用工作目录，默认打开其下的`input`子目录；
+    /// `last_used_directory`存在且有效时前端应优先使用
+    #[must_use]
+    pub fn open_statement_dialog(workspace_dir: &str, last_used_directory: Option<String>) -> Self {
+        let default_directory = std::path::Path::new(workspace_dir)
+            .join("input")
+            .to_string_lossy()
+            .to_string();
+
+        Self {
+            default_directory,
+            last_used_directory,
+            filters: vec![
+                TauriFileDialogFilter {
+                    name: "Excel/CSV文件".to_string(),
+                    extensions: vec!["xlsx".to_string(), "xls".to_string(), "csv".to_string()],
+                },
+                TauriFileDialogFilter {
+                    name: "所有文件".to_string(),
+                    extensions: vec!["*".to_string()],
+                },
+            ],
+        }
+    }
+
+    /// 获取前端实际应打开的目录（上次使用的目录优先）
+    #[must_use]
+    pub fn effective_directory(&self) -> &str {
+        self.last_used_directory.as_deref().unwrap_or(&self.default_directory)
+    }
+}
+
+/// 后端信息（用于前端版本协商）
+///
+/// 前端启动时获取该信息，据此禁用后端不支持的功能入口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriBackendInfo {
+    /// 后端crate名称
+    pub name: String,
+    /// 后端crate版本
+    pub version: String,
+    /// 支持的算法
+    pub supported_algorithms: Vec<String>,
+    /// 支持的导出格式
+    pub export_formats: Vec<String>,
+    /// 支持的功能特性标志
+    pub features: Vec<String>,
+    /// 兼容的最低前端版本
+    pub min_frontend_version: String,
+}
+
+impl TauriBackendInfo {
+    /// 构建当前后端构建的能力描述
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            name: crate::NAME.to_string(),
+            version: crate::VERSION.to_string(),
+            supported_algorithms: vec!["FIFO".to_string(), "BALANCE_METHOD".to_string()],
+            export_formats: vec!["xlsx".to_string(), "csv".to_string()],
+            features: vec![
+                "time_point_query".to_string(),
+                "file_cache".to_string(),
+                "offsite_pool_records".to_string(),
+                "streaming_export".to_string(),
+            ],
+            min_frontend_version: "3.0.0".to_string(),
+        }
+    }
+}
+
+/// 应用配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TauriAppConfig {
+    pub default_algorithm: String,
+    pub auto_export: bool,
+    pub max_history: usize,
+    pub language: String,
+    pub theme: String,
+}
+
+impl TauriAuditResult {
+    /// 创建成功的审计结果
+    #[must_use] 
+    pub fn success(summary: AuditSummary, transaction_count: usize, processing_time: f64, algorithm: String, output_files: Vec<String>) -> Self {
+        Self {
+            success: true,
+            message: format!("{algorithm}算法分析完成，处理 {transaction_count} 条交易记录"),
+            data: Some(TauriResultData {
+                summary,
+                transaction_count,
+                processing_time,
+                algorithm,
+                validation_errors: 0,
+                validation_fixes: 0,
+            }),
+            output_files,
+            warnings: vec![],
+        }
+    }
+    
+    /// 附加运行期收集的结构化警告
+    #[must_use]
+    pub fn with_warnings(mut self, warnings: Vec<AuditWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+    
+    /// 附加验证阶段的错误与修复计数
+    #[must_use]
+    pub fn with_validation_counts(mut self, errors: usize, fixes: usize) -> Self {
+        if let Some(ref mut data) = self.data {
+            data.validation_errors = errors;
+            data.validation_fixes = fixes;
+        }
+        self
+    }
+    
+    /// 创建失败的审计结果
+    #[must_use] 
+    pub fn failure(message: String) -> Self {
+        Self {
+            success: false,
+            message,
+            data: None,
+            output_files: vec![],
+            warnings: vec![],
+        }
+    }
+}
+
+impl TauriProcessStatus {
+    /// 创建运行中状态
+    #[must_use] 
+    pub fn running(progress: f64, message: String) -> Self {
+        Self {
+            running: true,
+            command: Some("audit_analysis".to_string()),
+            progress: Some(progress),
+            message: Some(message),
+            output_log: vec![],
+        }
+    }
+    
+    /// 创建空闲状态
+    #[must_use] 
+    pub fn idle() -> Self {
+        Self {
+            running: false,
+            command: None,
+            progress: None,
+            message: None,
+            output_log: vec![],
+        }
+    }
+    
+    /// 添加日志消息
+    #[must_use] 
+    pub fn with_log(mut self, log_message: String) -> Self {
+        self.output_log.push(log_message);
+        self
+    }
+}
+
+impl Default for TauriAppConfig {
+    fn default() -> Self {
+        Self {
+            default_algorithm: "FIFO".to_string(),
+            auto_export: true,
+            max_history: 100,
+            language: "zh-CN".to_string(),
+            theme: "light".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_collects_all_field_errors() {
+        let config = TauriAuditConfig {
+            algorithm: "LIFO".to_string(),
+            input_file: "/不存在/流水.txt".to_string(),
+            output_file: Some("/不存在的目录/结果.exe".to_string()),
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        let codes: Vec<(&str, &str)> = errors.iter()
+            .map(|e| (e.field.as_str(), e.code.as_str()))
+            .collect();
+        assert!(codes.contains(&("algorithm", "UNSUPPORTED_ALGORITHM")));
+        assert!(codes.contains(&("input_file", "UNSUPPORTED_EXTENSION")));
+        assert!(codes.contains(&("input_file", "FILE_NOT_FOUND")));
+        assert!(codes.contains(&("output_file", "UNSUPPORTED_EXTENSION")));
+        assert!(codes.contains(&("output_file", "OUTPUT_DIR_NOT_FOUND")));
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("流水.xlsx");
+        std::fs::write(&input_path, b"stub").unwrap();
+
+        let config = TauriAuditConfig {
+            algorithm: "FIFO".to_string(),
+            input_file: input_path.to_string_lossy().to_string(),
+            output_file: Some(dir.path().join("结果.xlsx").to_string_lossy().to_string()),
+            date_from: Some("2021-01-01".to_string()),
+            date_to: Some("2021-12-31".to_string()),
+            sheet_name: None,
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_input() {
+        let config = TauriAuditConfig {
+            algorithm: "BALANCE_METHOD".to_string(),
+            input_file: "  ".to_string(),
+            output_file: None,
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "input_file");
+        assert_eq!(errors[0].code, "EMPTY_PATH");
+    }
+
+    #[test]
+    fn test_validate_rejects_blank_sheet_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("流水.xlsx");
+        std::fs::write(&input_path, b"stub").unwrap();
+
+        let config = TauriAuditConfig {
+            algorithm: "FIFO".to_string(),
+            input_file: input_path.to_string_lossy().to_string(),
+            output_file: None,
+            date_from: None,
+            date_to: None,
+            sheet_name: Some("  ".to_string()),
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "sheet_name");
+        assert_eq!(errors[0].code, "EMPTY_SHEET_NAME");
+    }
+
+    #[test]
+    fn test_validate_opening_balance_fields_and_builds_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("流水.xlsx");
+        std::fs::write(&input_path, b"stub").unwrap();
+
+        let mut config = TauriAuditConfig {
+            algorithm: "FIFO".to_string(),
+            input_file: input_path.to_string_lossy().to_string(),
+            output_file: None,
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            opening_balance: Some("一百万".to_string()),
+            opening_personal: Some("30000".to_string()),
+            opening_ratio: Some("1.5".to_string()),
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        let codes: Vec<(&str, &str)> = errors.iter()
+            .map(|e| (e.field.as_str(), e.code.as_str()))
+            .collect();
+        assert!(codes.contains(&("opening_balance", "INVALID_AMOUNT")));
+        assert!(codes.contains(&("opening_ratio", "RATIO_OUT_OF_RANGE")));
+        assert!(codes.contains(&("opening_personal", "CONFLICTING_SPLIT")));
+
+        // 合法取值：校验通过并构造出核定值
+        config.opening_balance = Some("100000".to_string());
+        config.opening_ratio = None;
+        assert!(config.validate().is_ok());
+        let over = config.opening_balance_override().unwrap();
+        assert_eq!(over.total, Some(Decimal::from(100_000)));
+        assert_eq!(over.personal_amount, Some(Decimal::from(30_000)));
+        assert_eq!(over.personal_ratio, None);
+    }
+
+    #[test]
+    fn test_validate_time_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("流水.xlsx");
+        std::fs::write(&input_path, b"stub").unwrap();
+
+        let mut config = TauriAuditConfig {
+            algorithm: "FIFO".to_string(),
+            input_file: input_path.to_string_lossy().to_string(),
+            output_file: None,
+            date_from: Some("2021/01/01".to_string()),
+            date_to: Some("2021-06-31".to_string()),
+            sheet_name: None,
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: None,
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        let codes: Vec<(&str, &str)> = errors.iter()
+            .map(|e| (e.field.as_str(), e.code.as_str()))
+            .collect();
+        assert!(codes.contains(&("date_from", "INVALID_DATE")));
+        assert!(codes.contains(&("date_to", "INVALID_DATE")));
+
+        // 起始晚于结束
+        config.date_from = Some("2021-07-01".to_string());
+        config.date_to = Some("2021-01-01".to_string());
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "INVALID_DATE_RANGE");
+    }
+
+    #[test]
+    fn test_validate_same_time_ordering_and_builds_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let input_path = dir.path().join("流水.xlsx");
+        std::fs::write(&input_path, b"stub").unwrap();
+
+        let mut config = TauriAuditConfig {
+            algorithm: "FIFO".to_string(),
+            input_file: input_path.to_string_lossy().to_string(),
+            output_file: None,
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            opening_balance: None,
+            opening_personal: None,
+            opening_ratio: None,
+            same_time_ordering: Some("BY_LUCK".to_string()),
+            tiebreak_column: None,
+        };
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "same_time_ordering");
+        assert_eq!(errors[0].code, "UNSUPPORTED_ORDERING");
+
+        // TIEBREAK_COLUMN策略必须指定仲裁列名
+        config.same_time_ordering = Some("TIEBREAK_COLUMN".to_string());
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "MISSING_COLUMN_NAME");
+
+        // 合法取值：校验通过并构造出策略配置
+        config.tiebreak_column = Some("流水号".to_string());
+        assert!(config.validate().is_ok());
+        let ordering = config.same_time_ordering_config().unwrap();
+        assert_eq!(ordering.strategy, SameTimeOrderingStrategy::TiebreakColumn);
+        assert_eq!(ordering.tiebreak_column.as_deref(), Some("流水号"));
+
+        // 未指定策略时沿用引擎配置
+        config.same_time_ordering = None;
+        assert!(config.same_time_ordering_config().is_none());
+    }
+}
@@ -1,5 +1,6 @@
 //! 交易记录数据模型
 
+use crate::data_models::decimal_serde::{decimal_string, decimal_string_option, decimal_string_ratio_option};
 use chrono::NaiveDateTime;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -17,58 +18,96 @@ pub struct Transaction {
     pub transaction_time: String,
     
     /// 交易收入金额（资金流入）
+    #[serde(with = "decimal_string")]
     pub income_amount: Decimal,
     
     /// 交易支出金额（资金流出）
+    #[serde(with = "decimal_string")]
     pub expense_amount: Decimal,
     
     /// 交易后余额
+    #[serde(with = "decimal_string")]
     pub balance: Decimal,
     
     /// 资金属性（如：个人应收、公司应付、理财-产品代码等）
     pub fund_attribute: String,
-    
+
+    /// 流水号/凭证号原文（配置了流水号列或同时间排序仲裁列时读入，否则为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<String>,
+
+    /// 币种代码（配置了币种列时读入；空表示基准币种）
+    ///
+    /// 外币行的金额在读入阶段已按静态汇率折算为基准币种，
+    /// 本字段保留原币种代码供按币种追踪与留痕
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
     // === 系统计算字段 ===
     
     /// 个人资金占比（0-1之间）
+    #[serde(default, with = "decimal_string_ratio_option")]
     pub personal_ratio: Option<Decimal>,
     
     /// 公司资金占比（0-1之间）
+    #[serde(default, with = "decimal_string_ratio_option")]
     pub company_ratio: Option<Decimal>,
     
     /// 行为性质（挪用、垫付、正常、投资等）
     pub behavior_nature: Option<String>,
     
     /// 截至当前交易的累计挪用金额
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_misappropriation: Option<Decimal>,
     
     /// 截至当前交易的累计垫付金额
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_advance: Option<Decimal>,
     
     /// 截至当前交易累计已归还公司本金
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_company_principal_returned: Option<Decimal>,
     
     /// 截至当前交易累计已归还个人本金
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_personal_principal_returned: Option<Decimal>,
     
     /// 截至当前交易总计个人应分配利润
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_personal_profit: Option<Decimal>,
     
     /// 截至当前交易总计公司应分配利润
+    #[serde(default, with = "decimal_string_option")]
     pub cumulative_company_profit: Option<Decimal>,
     
     /// 资金缺口
+    #[serde(default, with = "decimal_string_option")]
     pub funding_gap: Option<Decimal>,
     
     /// 当前个人余额
+    #[serde(default, with = "decimal_string_option")]
     pub personal_balance: Option<Decimal>,
     
     /// 当前公司余额
+    #[serde(default, with = "decimal_string_option")]
     pub company_balance: Option<Decimal>,
+    
+    /// 资金来源明细（仅FIFO支出行，展示本笔支出消耗的前几项资金来源）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fund_source_breakdown: Option<String>,
+    
+    /// 判定依据（挪用/垫付行的结构化原因说明，其余行为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior_explanation: Option<String>,
+
+    /// 风险标记（可疑模式检测结果，多个标记以"; "连接，无标记为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_flags: Option<String>,
 }
 
 impl Transaction {
     /// 创建新的交易记录
+    #[must_use] 
     pub fn new(
         transaction_date: NaiveDateTime,
         transaction_time: String,
@@ -84,6 +123,8 @@ impl Transaction {
             expense_amount,
             balance,
             fund_attribute,
+            sequence_number: None,
+            currency: None,
             personal_ratio: None,
             company_ratio: None,
             behavior_nature: None,
@@ -96,25 +137,32 @@ impl Transaction {
             funding_gap: None,
             personal_balance: None,
             company_balance: None,
+            fund_source_breakdown: None,
+            behavior_explanation: None,
+            risk_flags: None,
         }
     }
     
     /// 获取交易净金额（收入-支出）
+    #[must_use] 
     pub fn net_amount(&self) -> Decimal {
         self.income_amount - self.expense_amount
     }
     
     /// 判断是否为收入交易
+    #[must_use] 
     pub fn is_income(&self) -> bool {
         self.income_amount > Decimal::ZERO
     }
     
     /// 判断是否为支出交易
+    #[must_use] 
     pub fn is_expense(&self) -> bool {
         self.expense_amount > Decimal::ZERO
     }
     
     /// 获取交易金额的绝对值
+    #[must_use]
     pub fn abs_amount(&self) -> Decimal {
         if self.is_income() {
             self.income_amount
@@ -122,7 +170,23 @@ impl Transaction {
             self.expense_amount
         }
     }
-    
+
+    /// 流水号排序键：纯数字按数值比较（避免"9"排在"10"之后的字典序陷阱），其余按原文
+    ///
+    /// 无流水号的记录排最前，调用方应先自行检查缺失情况
+    #[must_use]
+    pub fn sequence_sort_key(&self) -> (u8, u128, String) {
+        let Some(value) = self.sequence_number.as_deref() else {
+            return (0, 0, String::new());
+        };
+        let trimmed = value.trim();
+        match trimmed.parse::<u128>() {
+            Ok(number) => (1, number, String::new()),
+            Err(_) => (2, 0, trimmed.to_string()),
+        }
+    }
+
+
     /// 设置计算字段
     pub fn set_calculated_fields(
         &mut self,
@@ -154,13 +218,14 @@ impl Transaction {
     }
     
     /// 格式化时间字段用于显示
+    #[must_use] 
     pub fn formatted_time(&self) -> String {
         // 如果是6位数字格式（如143025），转换为时分秒格式
         if self.transaction_time.len() == 6 && self.transaction_time.chars().all(|c| c.is_ascii_digit()) {
             let hours = &self.transaction_time[0..2];
             let minutes = &self.transaction_time[2..4];
             let seconds = &self.transaction_time[4..6];
-            format!("{}:{}:{}", hours, minutes, seconds)
+            format!("{hours}:{minutes}:{seconds}")
         } else {
             self.transaction_time.clone()
         }
@@ -55,6 +55,10 @@ pub enum AuditError {
     #[error("不支持的操作: {0}")]
     UnsupportedOperation(String),
     
+    /// 分析被用户取消
+    #[error("分析已取消: {0}")]
+    Cancelled(String),
+    
     /// 内部系统错误
     #[error("内部系统错误: {0}")]
     InternalError(String),
@@ -109,6 +113,11 @@ impl AuditError {
         Self::UnsupportedOperation(msg.into())
     }
     
+    /// 创建取消错误
+    pub fn cancelled<S: Into<String>>(msg: S) -> Self {
+        Self::Cancelled(msg.into())
+    }
+    
     /// 创建内部错误
     pub fn internal_error<S: Into<String>>(msg: S) -> Self {
         Self::InternalError(msg.into())
@@ -0,0 +1,9 @@
+//! 对外接口模块
+//!
+//! 发布跨算法的统一trait，供外部crate与GUI层对具体算法泛型化
+
+pub mod registry;
+pub mod tracker;
+
+pub use registry::*;
+pub use tracker::*;
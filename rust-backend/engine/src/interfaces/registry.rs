@@ -0,0 +1,181 @@
+//! 插件式算法注册表
+//!
+//! 按算法名维护"名称 → 工厂"映射，服务层、CLI参数校验与算法信息
+//! 列表均从注册表取数，新增算法（含下游自定义追踪器）只需注册一个
+//! 工厂，无需改动各处的硬编码分支
+
+use crate::data_models::Config;
+
+use super::tracker::Tracker;
+
+/// 追踪器工厂：按配置创建一个算法追踪器实例
+pub type TrackerFactory = Box<dyn Fn(Config) -> Box<dyn Tracker> + Send + Sync>;
+
+/// 算法元信息（列表展示与输出文件命名用）
+#[derive(Debug, Clone)]
+pub struct AlgorithmInfo {
+    /// 算法标识（如"FIFO"，注册表键，约定全大写）
+    pub name: String,
+    /// 展示名（如"差额计算法"，用于输出文件名与日志）
+    pub display_name: String,
+    /// 一行描述（算法列表展示用）
+    pub description: String,
+}
+
+/// 算法注册表条目
+struct AlgorithmEntry {
+    info: AlgorithmInfo,
+    factory: TrackerFactory,
+}
+
+/// 插件式算法注册表
+///
+/// 条目按注册顺序保存，列表输出顺序稳定；重复注册同名算法时
+/// 后注册者覆盖先注册者（下游可藉此替换内置实现）
+pub struct AlgorithmRegistry {
+    entries: Vec<AlgorithmEntry>,
+}
+
+impl AlgorithmRegistry {
+    /// 创建空注册表
+    #[must_use]
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// 创建含三种内置算法的注册表
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(
+            AlgorithmInfo {
+                name: "FIFO".to_string(),
+                display_name: "FIFO".to_string(),
+                description: "先进先出算法 - 按时间顺序追踪资金流向".to_string(),
+            },
+            Box::new(|config| Box::new(crate::algorithms::FifoTracker::new(config))),
+        );
+        registry.register(
+            AlgorithmInfo {
+                name: "BALANCE_METHOD".to_string(),
+                display_name: "差额计算法".to_string(),
+                description: "差额计算法 - 基于余额变化计算资金占比".to_string(),
+            },
+            Box::new(|config| Box::new(crate::algorithms::BalanceMethodTracker::new(config))),
+        );
+        registry.register(
+            AlgorithmInfo {
+                name: "PROPORTIONAL".to_string(),
+                display_name: "按比例混同法".to_string(),
+                description: "按比例混同法 - 每笔支出按当前个人/公司余额占比分摊".to_string(),
+            },
+            Box::new(|config| Box::new(crate::algorithms::ProportionalTracker::new(config))),
+        );
+        registry
+    }
+
+    /// 注册算法（同名条目被覆盖，保留原注册位置）
+    pub fn register(&mut self, info: AlgorithmInfo, factory: TrackerFactory) {
+        let entry = AlgorithmEntry { info, factory };
+        match self.entries.iter_mut().find(|e| e.info.name == entry.info.name) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// 按名称创建追踪器实例（未注册返回None）
+    #[must_use]
+    pub fn create(&self, name: &str, config: Config) -> Option<Box<dyn Tracker>> {
+        self.entries.iter()
+            .find(|entry| entry.info.name == name)
+            .map(|entry| (entry.factory)(config))
+    }
+
+    /// 是否注册了该算法
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.info.name == name)
+    }
+
+    /// 按名称获取算法元信息
+    #[must_use]
+    pub fn get_info(&self, name: &str) -> Option<&AlgorithmInfo> {
+        self.entries.iter()
+            .find(|entry| entry.info.name == name)
+            .map(|entry| &entry.info)
+    }
+
+    /// 算法展示名（未注册时回退为标识本身）
+    #[must_use]
+    pub fn display_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.get_info(name).map_or(name, |info| info.display_name.as_str())
+    }
+
+    /// 全部已注册算法的标识（按注册顺序）
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|entry| entry.info.name.as_str()).collect()
+    }
+
+    /// 全部已注册算法的元信息（按注册顺序）
+    pub fn infos(&self) -> impl Iterator<Item = &AlgorithmInfo> {
+        self.entries.iter().map(|entry| &entry.info)
+    }
+}
+
+impl Default for AlgorithmRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_builtin_registry_creates_all_algorithms() {
+        let registry = AlgorithmRegistry::with_builtins();
+        assert_eq!(registry.names(), vec!["FIFO", "BALANCE_METHOD", "PROPORTIONAL"]);
+        assert_eq!(registry.display_name("BALANCE_METHOD"), "差额计算法");
+        assert_eq!(registry.display_name("UNKNOWN"), "UNKNOWN");
+
+        for name in registry.names() {
+            let tracker = registry.create(name, Config::new()).unwrap();
+            assert_eq!(tracker.get_name(), name);
+        }
+        assert!(registry.create("UNKNOWN", Config::new()).is_none());
+    }
+
+    #[test]
+    fn test_register_custom_and_override() {
+        let mut registry = AlgorithmRegistry::with_builtins();
+
+        // 自定义注册：用现有追踪器冒充一个新算法名
+        registry.register(
+            AlgorithmInfo {
+                name: "CUSTOM".to_string(),
+                display_name: "自定义算法".to_string(),
+                description: "测试用自定义条目".to_string(),
+            },
+            Box::new(|config| Box::new(crate::algorithms::BalanceMethodTracker::new(config))),
+        );
+        assert!(registry.contains("CUSTOM"));
+        let mut tracker = registry.create("CUSTOM", Config::new()).unwrap();
+        tracker.initialize_balance(Decimal::from(1000), "个人").unwrap();
+        assert!(tracker.is_initialized());
+
+        // 同名覆盖：条目位置不变，元信息被替换
+        registry.register(
+            AlgorithmInfo {
+                name: "FIFO".to_string(),
+                display_name: "FIFO改".to_string(),
+                description: "覆盖内置实现".to_string(),
+            },
+            Box::new(|config| Box::new(crate::algorithms::FifoTracker::new(config))),
+        );
+        assert_eq!(registry.names()[0], "FIFO");
+        assert_eq!(registry.display_name("FIFO"), "FIFO改");
+    }
+}
@@ -0,0 +1,417 @@
+//! 追踪器统一接口
+//!
+//! 发布`Tracker` trait，覆盖初始化、逐笔处理与审计摘要能力，
+//! 三种算法追踪器均实现该trait。trait保持对象安全，
+//! 算法注册表可按名称创建`Box<dyn Tracker>`（见[`super::registry`]）；
+//! 含关联类型的快照能力拆分到[`SnapshotableTracker`]，供泛型场景使用
+
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+
+use crate::algorithms::shared::tracker_base::InvestmentPool;
+use crate::algorithms::{
+    BalanceMethodTracker, BalanceMethodTrackerSnapshot, FifoTracker, FifoTrackerSnapshot,
+    OrderingAnomaly, PoolResetEvent, ProductMergeRecord, ProportionalTracker,
+    ProportionalTrackerSnapshot,
+};
+use crate::data_models::{AuditSummary, Config, OffsitePoolRecordManager, Transaction};
+use crate::errors::AuditResult;
+use crate::optimizations::memory_pool::PoolStats;
+
+/// 资金追踪算法的统一接口（对象安全）
+///
+/// 必选方法一一对应各追踪器已有的固有方法（固有方法在具体类型上优先解析，
+/// 行为不受trait引入影响）；初始化与逐笔分派逻辑以默认实现提供，
+/// 自定义追踪器只需实现资金流处理与状态访问方法
+pub trait Tracker: Send {
+    /// 算法标识（如"FIFO"、`"BALANCE_METHOD"`、`"PROPORTIONAL"`）
+    fn get_name(&self) -> &'static str;
+
+    /// 初始化余额
+    fn initialize_balance(&mut self, initial_balance: Decimal, balance_type: &str) -> AuditResult<()>;
+
+    /// 检查是否已初始化
+    fn is_initialized(&self) -> bool;
+
+    /// 处理资金流入
+    fn process_inflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)>;
+
+    /// 处理普通资金流出
+    fn process_outflow(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)>;
+
+    /// 处理投资产品申购
+    fn process_investment_purchase(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)>;
+
+    /// 处理投资产品赎回
+    fn process_investment_redemption(
+        &mut self,
+        amount: Decimal,
+        fund_attribute: &str,
+        transaction_date: Option<NaiveDateTime>,
+    ) -> AuditResult<(Decimal, Decimal, String)>;
+
+    /// 将追踪器当前状态同步到交易记录的计算字段
+    fn update_transaction_fields(
+        &self,
+        transaction: &mut Transaction,
+        personal_ratio: Decimal,
+        company_ratio: Decimal,
+        behavior: &str,
+    ) -> AuditResult<()>;
+
+    /// 获取审计摘要
+    fn get_summary(&self) -> AuditResult<AuditSummary>;
+
+    /// 获取全部时序异常记录（赎回早于申购等）
+    fn ordering_anomalies(&self) -> &[OrderingAnomaly];
+
+    /// 为尚未关联行号的时序异常回填行号（由服务层在处理单行后调用）
+    fn assign_pending_anomaly_rows(&mut self, row: usize);
+
+    /// 获取资金池盈利重置事件
+    fn pool_reset_events(&self) -> &[PoolResetEvent];
+
+    /// 为尚未关联行号的资金池重置事件回填行号（由服务层在处理单行后调用）
+    fn assign_pending_reset_rows(&mut self, row: usize);
+
+    /// 获取产品代码归并报告
+    fn product_merge_report(&self) -> Vec<ProductMergeRecord>;
+
+    /// 累计单笔交易的按币种净流入（由服务层在配置了币种列时调用）
+    fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal);
+
+    /// 归集利息/手续费行的收支，命中口径时返回true
+    fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool;
+
+    /// 获取场外资金池记录管理器
+    fn get_offsite_pool_records(&self) -> &OffsitePoolRecordManager;
+
+    /// 获取投资池数据（用于完整统计计算）
+    fn get_investment_pools(&self) -> &HashMap<String, InvestmentPool>;
+
+    /// 内部对象池命中统计（仅使用对象池的算法返回Some）
+    fn memory_pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
+
+    /// 已记录的时序异常数量
+    fn ordering_anomaly_count(&self) -> usize {
+        self.ordering_anomalies().len()
+    }
+
+    /// 已记录的资金池重置事件数量
+    fn pool_reset_count(&self) -> usize {
+        self.pool_reset_events().len()
+    }
+
+    /// 智能初始化：基于第一笔交易推断期初余额构成
+    fn smart_initialize(&mut self, first_transaction: &Transaction) -> AuditResult<()> {
+        let pre_balance = first_transaction.balance - first_transaction.income_amount
+            + first_transaction.expense_amount;
+
+        if first_transaction.fund_attribute.contains("个人") {
+            self.initialize_balance(pre_balance, "个人")?;
+        } else {
+            self.initialize_balance(Decimal::ZERO, "个人")?;
+            if pre_balance > Decimal::ZERO {
+                self.process_inflow(pre_balance, "公司初始余额", Some(first_transaction.transaction_date))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按核定的期初构成初始化（覆盖首行启发式推断）
+    fn initialize_opening(
+        &mut self,
+        personal: Decimal,
+        company: Decimal,
+        first_transaction: &Transaction,
+    ) -> AuditResult<()> {
+        self.initialize_balance(personal, "个人")?;
+        if company > Decimal::ZERO {
+            self.process_inflow(company, "公司初始余额", Some(first_transaction.transaction_date))?;
+        }
+        Ok(())
+    }
+
+    /// 处理单笔交易，返回填好计算字段的交易副本
+    ///
+    /// 默认实现：收入行按是否带投资标记（资金属性含"-"）分派到赎回/流入，
+    /// 支出行分派到申购/流出；处理失败时保留原始数据
+    fn process_transaction(&mut self, transaction: &Transaction) -> AuditResult<Transaction> {
+        let mut processed_tx = transaction.clone();
+
+        // 根据交易类型调用相应的处理方法
+        let result = if transaction.income_amount > Decimal::ZERO {
+            if transaction.fund_attribute.contains('-') {
+                self.process_investment_redemption(
+                    transaction.income_amount,
+                    &transaction.fund_attribute,
+                    Some(transaction.transaction_date),
+                )
+            } else {
+                self.process_inflow(
+                    transaction.income_amount,
+                    &transaction.fund_attribute,
+                    Some(transaction.transaction_date),
+                )
+            }
+        } else if transaction.expense_amount > Decimal::ZERO {
+            if transaction.fund_attribute.contains('-') {
+                self.process_investment_purchase(
+                    transaction.expense_amount,
+                    &transaction.fund_attribute,
+                    Some(transaction.transaction_date),
+                )
+            } else {
+                self.process_outflow(
+                    transaction.expense_amount,
+                    &transaction.fund_attribute,
+                    Some(transaction.transaction_date),
+                )
+            }
+        } else {
+            Ok((Decimal::ZERO, Decimal::ZERO, "无变化".to_string()))
+        };
+
+        // 更新交易字段，处理失败时保持原始数据
+        if let Ok((personal_ratio, company_ratio, behavior)) = result {
+            self.update_transaction_fields(&mut processed_tx, personal_ratio, company_ratio, &behavior)?;
+        }
+
+        Ok(processed_tx)
+    }
+}
+
+/// 追踪器的状态快照能力
+///
+/// 含关联快照类型，不对象安全；检查点与增量分析等
+/// 需要具体类型的泛型场景使用该trait
+pub trait SnapshotableTracker: Tracker + Sized {
+    /// 可序列化的状态快照类型
+    type Snapshot: Serialize + DeserializeOwned + Clone + PartialEq;
+
+    /// 创建追踪器
+    fn new(config: Config) -> Self;
+
+    /// 导出当前运行状态快照
+    fn to_snapshot(&self) -> Self::Snapshot;
+
+    /// 从状态快照恢复追踪器
+    fn from_snapshot(config: Config, snapshot: Self::Snapshot) -> Self;
+}
+
+/// 宏：为追踪器实现Tracker与SnapshotableTracker
+///
+/// 三个追踪器的固有方法签名完全一致，仅快照类型不同，统一用委托展开；
+/// 额外的item（如对象池统计覆写）原样并入Tracker实现
+macro_rules! impl_tracker {
+    ($tracker:ty, $snapshot:ty $(, $extra:item)*) => {
+        impl Tracker for $tracker {
+            fn get_name(&self) -> &'static str {
+                self.get_name()
+            }
+
+            fn initialize_balance(&mut self, initial_balance: Decimal, balance_type: &str) -> AuditResult<()> {
+                self.initialize_balance(initial_balance, balance_type)
+            }
+
+            fn is_initialized(&self) -> bool {
+                self.is_initialized()
+            }
+
+            fn process_inflow(
+                &mut self,
+                amount: Decimal,
+                fund_attribute: &str,
+                transaction_date: Option<NaiveDateTime>,
+            ) -> AuditResult<(Decimal, Decimal, String)> {
+                self.process_inflow(amount, fund_attribute, transaction_date)
+            }
+
+            fn process_outflow(
+                &mut self,
+                amount: Decimal,
+                fund_attribute: &str,
+                transaction_date: Option<NaiveDateTime>,
+            ) -> AuditResult<(Decimal, Decimal, String)> {
+                self.process_outflow(amount, fund_attribute, transaction_date)
+            }
+
+            fn process_investment_purchase(
+                &mut self,
+                amount: Decimal,
+                fund_attribute: &str,
+                transaction_date: Option<NaiveDateTime>,
+            ) -> AuditResult<(Decimal, Decimal, String)> {
+                self.process_investment_purchase(amount, fund_attribute, transaction_date)
+            }
+
+            fn process_investment_redemption(
+                &mut self,
+                amount: Decimal,
+                fund_attribute: &str,
+                transaction_date: Option<NaiveDateTime>,
+            ) -> AuditResult<(Decimal, Decimal, String)> {
+                self.process_investment_redemption(amount, fund_attribute, transaction_date)
+            }
+
+            fn update_transaction_fields(
+                &self,
+                transaction: &mut Transaction,
+                personal_ratio: Decimal,
+                company_ratio: Decimal,
+                behavior: &str,
+            ) -> AuditResult<()> {
+                self.update_transaction_fields(transaction, personal_ratio, company_ratio, behavior)
+            }
+
+            fn get_summary(&self) -> AuditResult<AuditSummary> {
+                self.get_summary()
+            }
+
+            fn ordering_anomalies(&self) -> &[OrderingAnomaly] {
+                self.get_ordering_anomalies()
+            }
+
+            fn assign_pending_anomaly_rows(&mut self, row: usize) {
+                self.assign_pending_anomaly_rows(row);
+            }
+
+            fn pool_reset_events(&self) -> &[PoolResetEvent] {
+                self.get_pool_reset_events()
+            }
+
+            fn assign_pending_reset_rows(&mut self, row: usize) {
+                self.assign_pending_reset_rows(row);
+            }
+
+            fn product_merge_report(&self) -> Vec<ProductMergeRecord> {
+                self.get_product_merge_report()
+            }
+
+            fn record_currency_flow(&mut self, currency: &str, net_flow: Decimal) {
+                self.record_currency_flow(currency, net_flow);
+            }
+
+            fn record_interest_fee(&mut self, fund_attribute: &str, income: Decimal, expense: Decimal) -> bool {
+                self.record_interest_fee(fund_attribute, income, expense)
+            }
+
+            fn get_offsite_pool_records(&self) -> &OffsitePoolRecordManager {
+                self.get_offsite_pool_records()
+            }
+
+            fn get_investment_pools(&self) -> &HashMap<String, InvestmentPool> {
+                self.get_investment_pools()
+            }
+
+            $($extra)*
+        }
+
+        impl SnapshotableTracker for $tracker {
+            type Snapshot = $snapshot;
+
+            fn new(config: Config) -> Self {
+                Self::new(config)
+            }
+
+            fn to_snapshot(&self) -> Self::Snapshot {
+                self.to_snapshot()
+            }
+
+            fn from_snapshot(config: Config, snapshot: Self::Snapshot) -> Self {
+                Self::from_snapshot(config, snapshot)
+            }
+        }
+    };
+}
+
+impl_tracker!(FifoTracker, FifoTrackerSnapshot,
+    fn memory_pool_stats(&self) -> Option<PoolStats> {
+        Some(self.entry_pool_stats())
+    }
+);
+impl_tracker!(BalanceMethodTracker, BalanceMethodTrackerSnapshot);
+impl_tracker!(ProportionalTracker, ProportionalTrackerSnapshot);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn sample_transaction(income: i64, expense: i64, balance: i64, attribute: &str) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        Transaction::new(
+            date,
+            "10:00:00".to_string(),
+            Decimal::from(income),
+            Decimal::from(expense),
+            Decimal::from(balance),
+            attribute.to_string(),
+        )
+    }
+
+    /// 对任意算法走同一段泛型流程：初始化、逐笔处理、快照往返、摘要
+    fn run_generic_flow<T: SnapshotableTracker>(expected_name: &str) {
+        let config = Config::new();
+        let mut tracker = T::new(config.clone());
+        assert_eq!(tracker.get_name(), expected_name);
+        assert!(!tracker.is_initialized());
+
+        tracker.initialize_balance(Decimal::from(50000), "个人").unwrap();
+        assert!(tracker.is_initialized());
+
+        let inflow = sample_transaction(30000, 0, 80000, "公司应收");
+        let processed = tracker.process_transaction(&inflow).unwrap();
+        assert!(processed.behavior_nature.is_some());
+
+        let outflow = sample_transaction(0, 60000, 20000, "个人应付");
+        tracker.process_transaction(&outflow).unwrap();
+
+        // 快照往返后摘要应与原实例一致
+        let restored = T::from_snapshot(config, tracker.to_snapshot());
+        let summary = tracker.get_summary().unwrap();
+        let restored_summary = restored.get_summary().unwrap();
+        assert_eq!(restored_summary.total_misappropriation, summary.total_misappropriation);
+        assert_eq!(restored_summary.personal_balance, summary.personal_balance);
+        assert_eq!(restored_summary.company_balance, summary.company_balance);
+    }
+
+    #[test]
+    fn test_tracker_trait_generic_over_all_algorithms() {
+        run_generic_flow::<FifoTracker>("FIFO");
+        run_generic_flow::<BalanceMethodTracker>("BALANCE_METHOD");
+        run_generic_flow::<ProportionalTracker>("PROPORTIONAL");
+    }
+
+    #[test]
+    fn test_tracker_trait_is_object_safe() {
+        // 经Box<dyn Tracker>走完整逐笔流程，验证对象安全与默认分派
+        let mut tracker: Box<dyn Tracker> = Box::new(FifoTracker::new(Config::new()));
+        tracker.smart_initialize(&sample_transaction(30000, 0, 80000, "公司应收")).unwrap();
+        let processed = tracker
+            .process_transaction(&sample_transaction(0, 20000, 60000, "个人应付"))
+            .unwrap();
+        assert!(processed.behavior_nature.is_some());
+        assert!(tracker.memory_pool_stats().is_some());
+    }
+}
@@ -0,0 +1,65 @@
+/*
+ * FLUX资金追踪分析系统 v3.3.4
+ * Copyright (c) 2025 刘光浚
+ * 开发完成日期: 2025年8月28日
+ *
+ * 纯分析引擎：算法、数据模型、验证器与导出器。
+ */
+
+//! FLUX资金追踪分析系统 - 纯分析引擎
+//!
+//! 包含算法层、数据模型、验证器与Excel/CSV导出器，
+//! 不依赖tokio/clap/tauri，可被服务端或FFI集成以最小依赖树嵌入。
+//! `flux-backend` crate在此之上提供服务层（审计服务、时点查询、通知等）并完整重导出本crate。
+
+#![warn(clippy::all, clippy::pedantic)]
+// pedantic中噪音较大的lint统一豁免，保留其余检查
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::must_use_candidate,
+    clippy::return_self_not_must_use,
+    clippy::unreadable_literal,
+    clippy::unused_self,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::similar_names,
+    clippy::items_after_statements,
+    clippy::type_complexity,
+    clippy::unnecessary_wraps,
+    clippy::comparison_chain,
+    clippy::unused_async,
+    clippy::used_underscore_binding,
+    clippy::match_same_arms,
+    clippy::needless_pass_by_value
+)]
+
+pub mod algorithms;
+pub mod data_models;
+pub mod interfaces;
+pub mod optimizations;
+pub mod utils;
+pub mod errors;
+
+// 重新导出核心类型
+pub use algorithms::*;
+pub use data_models::*;
+pub use errors::*;
+pub use interfaces::*;
+pub use optimizations::*;
+pub use utils::*;
+
+// 重新导出常用的外部依赖
+pub use rust_decimal;
+
+/// 引擎版本信息（与flux-backend同步发布）
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 引擎crate名称
+pub const NAME: &str = env!("CARGO_PKG_NAME");
+
+/// 引擎描述
+pub const DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
@@ -0,0 +1,378 @@
+//! 带TTL与内存上限的LRU缓存
+//!
+//! 时点查询按文件缓存完整的分析结果，单个条目可能包含数十万条
+//! 处理后交易——只按条目数淘汰会让内存峰值随文件大小失控，
+//! 逐次全表扫描找最老条目也撑不起高频查询。这里实现通用LRU：
+//! 条目数、存活时间、估算内存占用三条策略任一越界即按最久未使用
+//! 淘汰，运行指标通过[`CacheMetrics`]暴露，由服务层转给前端诊断
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// 缓存淘汰策略
+#[derive(Debug, Clone, Copy)]
+pub struct CachePolicy {
+    /// 最大条目数
+    pub max_entries: usize,
+    /// 条目存活时间（自写入起计，访问不续期）
+    pub ttl: Duration,
+    /// 估算内存占用上限（字节）
+    pub max_memory_bytes: usize,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: 16,
+            ttl: Duration::from_hours(1),
+            max_memory_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// 缓存条目的内存占用估算
+///
+/// 淘汰按估算值执行，不要求精确到字节——量级正确即可
+pub trait CacheWeight {
+    /// 估算的内存占用（字节）
+    fn weight_bytes(&self) -> usize;
+}
+
+/// 缓存运行指标
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CacheMetrics {
+    /// 当前条目数（含已过期但尚未清理的条目）
+    pub entries: usize,
+    /// 当前估算内存占用（字节）
+    pub memory_bytes: usize,
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数（含访问时发现已过期）
+    pub misses: u64,
+    /// 因条目数或内存上限被淘汰的条目数
+    pub evictions: u64,
+    /// 因过期被移除的条目数
+    pub expirations: u64,
+}
+
+impl CacheMetrics {
+    /// 命中率（0-1；尚无访问时为0）
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// 单个缓存条目及其簿记信息
+struct CacheSlot<V> {
+    value: V,
+    inserted_at: Instant,
+    last_used: u64,
+    weight: usize,
+}
+
+/// 通用LRU缓存
+///
+/// 热度通过单调递增的使用序号维护：每次访问只在队尾追加
+/// (序号, 键)记录，淘汰时从队首跳过已过时的记录找到真正
+/// 最久未使用的条目，单次操作均摊O(1)。注意内存上限作用于
+/// 整个缓存：单个条目估算值就超限时会被立即淘汰（本来就放不下）
+pub struct LruCache<K, V> {
+    slots: HashMap<K, CacheSlot<V>>,
+    /// 懒惰维护的热度队列，过时记录在淘汰与压缩时跳过
+    recency: VecDeque<(u64, K)>,
+    policy: CachePolicy,
+    stamp: u64,
+    memory_bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    expirations: u64,
+}
+
+impl<K: Eq + Hash + Clone, V: CacheWeight> LruCache<K, V> {
+    /// 热度队列允许的过时记录冗余，超过后整体压缩一次
+    const COMPACT_SLACK: usize = 64;
+
+    /// 创建指定策略的缓存
+    #[must_use]
+    pub fn new(policy: CachePolicy) -> Self {
+        Self {
+            slots: HashMap::new(),
+            recency: VecDeque::new(),
+            policy,
+            stamp: 0,
+            memory_bytes: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            expirations: 0,
+        }
+    }
+
+    /// 获取条目并计入命中统计、提升热度（主路径使用）
+    ///
+    /// 已过期的条目在此处移除并按未命中处理
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (owned_key, expired) = match self.slots.get_key_value(key) {
+            None => {
+                self.misses += 1;
+                return None;
+            }
+            Some((stored_key, slot)) => (
+                stored_key.clone(),
+                slot.inserted_at.elapsed() >= self.policy.ttl,
+            ),
+        };
+        if expired {
+            self.remove_slot(key);
+            self.expirations += 1;
+            self.misses += 1;
+            return None;
+        }
+
+        self.hits += 1;
+        self.stamp += 1;
+        let stamp = self.stamp;
+        self.recency.push_back((stamp, owned_key));
+        self.compact_recency();
+        let slot = self.slots.get_mut(key)?;
+        slot.last_used = stamp;
+        Some(&slot.value)
+    }
+
+    /// 只读访问：不计入命中统计也不提升热度（供&self查询路径使用）
+    ///
+    /// 已过期的条目返回None但留待[`Self::cleanup_expired`]移除
+    #[must_use]
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.slots
+            .get(key)
+            .filter(|slot| slot.inserted_at.elapsed() < self.policy.ttl)
+            .map(|slot| &slot.value)
+    }
+
+    /// 写入条目（同键覆盖旧值），返回本次因超限被淘汰的键
+    pub fn insert(&mut self, key: K, value: V) -> Vec<K> {
+        let weight = value.weight_bytes();
+        if self.slots.contains_key(&key) {
+            self.remove_slot(&key);
+        }
+        self.stamp += 1;
+        let stamp = self.stamp;
+        self.memory_bytes += weight;
+        self.slots.insert(
+            key.clone(),
+            CacheSlot {
+                value,
+                inserted_at: Instant::now(),
+                last_used: stamp,
+                weight,
+            },
+        );
+        self.recency.push_back((stamp, key));
+        self.evict_over_limit()
+    }
+
+    /// 移除全部已过期条目，返回被移除的键
+    pub fn cleanup_expired(&mut self) -> Vec<K> {
+        let expired: Vec<K> = self
+            .slots
+            .iter()
+            .filter(|(_, slot)| slot.inserted_at.elapsed() >= self.policy.ttl)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.remove_slot(key);
+        }
+        self.expirations += expired.len() as u64;
+        expired
+    }
+
+    /// 遍历全部未过期条目的值（不提升热度）
+    pub fn values_valid(&self) -> impl Iterator<Item = &V> {
+        self.slots
+            .values()
+            .filter(|slot| slot.inserted_at.elapsed() < self.policy.ttl)
+            .map(|slot| &slot.value)
+    }
+
+    /// 当前条目数（含已过期但尚未清理的条目）
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// 缓存是否为空
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// 运行指标快照
+    #[must_use]
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            entries: self.slots.len(),
+            memory_bytes: self.memory_bytes,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            expirations: self.expirations,
+        }
+    }
+
+    /// 移除条目并回收其内存记账
+    fn remove_slot<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.slots.remove(key)?;
+        self.memory_bytes -= slot.weight;
+        Some(slot.value)
+    }
+
+    /// 按LRU淘汰直到条目数与内存占用都回到上限内
+    fn evict_over_limit(&mut self) -> Vec<K> {
+        let mut evicted = Vec::new();
+        while self.slots.len() > self.policy.max_entries
+            || self.memory_bytes > self.policy.max_memory_bytes
+        {
+            let Some((stamp, key)) = self.recency.pop_front() else {
+                break;
+            };
+            // 热度队列中的过时记录（该键此后又被访问或已移除）直接跳过
+            let is_current = self
+                .slots
+                .get(&key)
+                .is_some_and(|slot| slot.last_used == stamp);
+            if is_current {
+                self.remove_slot(&key);
+                self.evictions += 1;
+                evicted.push(key);
+            }
+        }
+        evicted
+    }
+
+    /// 压缩热度队列中的过时记录，防止高频访问让队列无界增长
+    fn compact_recency(&mut self) {
+        if self.recency.len() <= self.slots.len() * 2 + Self::COMPACT_SLACK {
+            return;
+        }
+        let slots = &self.slots;
+        self.recency.retain(|(stamp, key)| {
+            slots
+                .get(key)
+                .is_some_and(|slot| slot.last_used == *stamp)
+        });
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: CacheWeight> Default for LruCache<K, V> {
+    fn default() -> Self {
+        Self::new(CachePolicy::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 固定权重的测试值
+    struct Blob(usize);
+
+    impl CacheWeight for Blob {
+        fn weight_bytes(&self) -> usize {
+            self.0
+        }
+    }
+
+    fn policy(max_entries: usize, max_memory_bytes: usize) -> CachePolicy {
+        CachePolicy {
+            max_entries,
+            ttl: Duration::from_hours(1),
+            max_memory_bytes,
+        }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache: LruCache<String, Blob> = LruCache::new(policy(2, usize::MAX));
+        cache.insert("a".to_string(), Blob(1));
+        cache.insert("b".to_string(), Blob(1));
+        // 访问a后b成为最久未使用
+        assert!(cache.get("a").is_some());
+
+        let evicted = cache.insert("c".to_string(), Blob(1));
+        assert_eq!(evicted, vec!["b".to_string()]);
+        assert!(cache.peek("a").is_some());
+        assert!(cache.peek("c").is_some());
+        assert_eq!(cache.metrics().evictions, 1);
+    }
+
+    #[test]
+    fn test_memory_limit_evicts_before_entry_limit() {
+        let mut cache: LruCache<String, Blob> = LruCache::new(policy(10, 100));
+        cache.insert("a".to_string(), Blob(60));
+        let evicted = cache.insert("b".to_string(), Blob(60));
+
+        // 两条共120字节超过100字节上限，最老的a被淘汰
+        assert_eq!(evicted, vec!["a".to_string()]);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.metrics().memory_bytes, 60);
+    }
+
+    #[test]
+    fn test_ttl_expires_entries() {
+        let mut cache: LruCache<String, Blob> = LruCache::new(CachePolicy {
+            max_entries: 10,
+            ttl: Duration::ZERO,
+            max_memory_bytes: usize::MAX,
+        });
+        cache.insert("a".to_string(), Blob(1));
+
+        // TTL为零时写入即过期：只读访问查不到，主路径访问按未命中移除
+        assert!(cache.peek("a").is_none());
+        assert!(cache.get("a").is_none());
+        let metrics = cache.metrics();
+        assert_eq!(metrics.entries, 0);
+        assert_eq!(metrics.expirations, 1);
+        assert_eq!(metrics.misses, 1);
+
+        cache.insert("b".to_string(), Blob(1));
+        assert_eq!(cache.cleanup_expired(), vec!["b".to_string()]);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_metrics_track_hit_rate() {
+        let mut cache: LruCache<String, Blob> = LruCache::new(policy(4, usize::MAX));
+        cache.insert("a".to_string(), Blob(1));
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("缺失").is_none());
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.misses, 1);
+        assert!((metrics.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+}
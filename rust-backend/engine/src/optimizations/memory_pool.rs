@@ -0,0 +1,126 @@
+//! 小对象池
+//!
+//! FIFO追踪器在热循环中反复创建与丢弃资金条目——百万行流水会产生
+//! 数百万次小对象分配。对象池回收完全消费的条目供后续流入复用其
+//! 已分配的缓冲，把热路径上的堆分配压到常数级。命中统计通过
+//! [`PoolStats`]暴露，由服务层并入性能统计
+
+use serde::{Deserialize, Serialize};
+
+/// 对象池命中统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolStats {
+    /// 请求对象的总次数
+    pub acquires: usize,
+    /// 命中池内回收对象的次数（未命中时调用方新分配）
+    pub hits: usize,
+    /// 回收进池的对象数（池已满时直接丢弃，不计入）
+    pub releases: usize,
+}
+
+impl PoolStats {
+    /// 命中率（0-1；尚无请求时为0）
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        if self.acquires == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.acquires as f64
+        }
+    }
+}
+
+/// 定容对象池
+///
+/// 不负责对象的构造与字段重置——[`Self::acquire`]命中时返回的对象
+/// 保留上次使用的字段值，由调用方覆写后使用（这正是复用String等
+/// 内部缓冲的来源）。池满时[`Self::release`]直接丢弃对象，
+/// 避免异常数据把池撑成第二个内存峰值
+#[derive(Debug, Clone)]
+pub struct ObjectPool<T> {
+    free: Vec<T>,
+    capacity: usize,
+    stats: PoolStats,
+}
+
+impl<T> ObjectPool<T> {
+    /// 默认池容量
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// 创建指定容量的对象池
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            free: Vec::new(),
+            capacity,
+            stats: PoolStats::default(),
+        }
+    }
+
+    /// 取出一个回收的对象（池空时返回None，由调用方新分配）
+    pub fn acquire(&mut self) -> Option<T> {
+        self.stats.acquires += 1;
+        let value = self.free.pop();
+        if value.is_some() {
+            self.stats.hits += 1;
+        }
+        value
+    }
+
+    /// 回收一个对象供后续复用（池满时丢弃）
+    pub fn release(&mut self, value: T) {
+        if self.free.len() < self.capacity {
+            self.free.push(value);
+            self.stats.releases += 1;
+        }
+    }
+
+    /// 当前池内可复用的对象数
+    #[must_use]
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    /// 命中统计
+    #[must_use]
+    pub fn stats(&self) -> PoolStats {
+        self.stats
+    }
+}
+
+impl<T> Default for ObjectPool<T> {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_miss_then_hit() {
+        let mut pool: ObjectPool<String> = ObjectPool::with_capacity(4);
+        assert!(pool.acquire().is_none());
+
+        pool.release("回收".to_string());
+        assert_eq!(pool.acquire().as_deref(), Some("回收"));
+
+        let stats = pool.stats();
+        assert_eq!(stats.acquires, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.releases, 1);
+        assert!((stats.hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_release_discards_when_full() {
+        let mut pool: ObjectPool<u64> = ObjectPool::with_capacity(2);
+        pool.release(1);
+        pool.release(2);
+        pool.release(3); // 池已满，丢弃
+
+        assert_eq!(pool.free_count(), 2);
+        assert_eq!(pool.stats().releases, 2);
+    }
+}
@@ -0,0 +1,14 @@
+//! 性能优化模块
+//!
+//! 不改变分析语义的性能手段集中在这里：
+//! - `parallel`: 基于rayon的逐行数据并行（保持输出顺序确定）
+//! - `memory_pool`: 热循环小对象的回收复用（FIFO队列条目）
+//! - `cache`: 带TTL与内存上限的LRU缓存（文件级分析结果）
+
+pub mod cache;
+pub mod memory_pool;
+pub mod parallel;
+
+pub use cache::*;
+pub use memory_pool::*;
+pub use parallel::*;
@@ -0,0 +1,55 @@
+//! 数据并行辅助工具
+//!
+//! Excel逐行解析与逐行验证相互独立，大文件上是纯粹的数据并行问题。
+//! 本模块封装rayon的带索引映射：结果按输入顺序收集，调用方看到的
+//! 输出与顺序执行完全一致；小输入自动走顺序路径，避免为几百行数据
+//! 付出线程调度开销。
+
+use rayon::prelude::*;
+
+/// 低于该行数时不值得并行，直接顺序处理
+pub const PARALLEL_ROW_THRESHOLD: usize = 512;
+
+/// 带索引的并行映射，结果按输入顺序返回
+///
+/// 索引为元素在`items`中的位置（0开始），供调用方换算行号。
+/// 映射函数在多线程上执行，需避免副作用依赖执行顺序——
+/// 日志等顺序敏感的输出应放在对返回结果的顺序遍历中
+pub fn indexed_map<T, R, F>(items: &[T], map: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync + Send,
+{
+    if items.len() < PARALLEL_ROW_THRESHOLD {
+        return items.iter().enumerate().map(|(idx, item)| map(idx, item)).collect();
+    }
+    items.par_iter().enumerate().map(|(idx, item)| map(idx, item)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_map_matches_sequential_small_input() {
+        let items: Vec<u64> = (0..10).collect();
+        let result = indexed_map(&items, |idx, value| idx as u64 + value * 2);
+        let expected: Vec<u64> = items.iter().enumerate()
+            .map(|(idx, value)| idx as u64 + value * 2)
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_indexed_map_preserves_order_above_threshold() {
+        // 超过阈值走rayon路径，输出仍须与输入顺序一致
+        let items: Vec<u64> = (0..(PARALLEL_ROW_THRESHOLD as u64 * 4)).collect();
+        let result = indexed_map(&items, |idx, value| {
+            assert_eq!(idx as u64, *value);
+            value * 3
+        });
+        let expected: Vec<u64> = items.iter().map(|value| value * 3).collect();
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,318 @@
+//! 可疑交易模式检测器
+//!
+//! 在主分析完成后对处理结果做一轮模式扫描，标记审计实务中
+//! 常见的可疑行为：拆分转账（反复出现略低于申报阈值的金额）、
+//! 快进快出（同额资金短期一进一出）、凌晨交易、相对历史明显
+//! 偏大的转账。检测结果以逐行"风险标记"列与汇总表的形式
+//! 附在导出结果中，仅作线索提示，不改变算法的认定结论。
+
+use crate::data_models::Transaction;
+use chrono::Timelike;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// 可疑模式类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// 反复出现略低于申报阈值的金额（疑似拆分规避申报）
+    Structuring,
+    /// 同额资金短期内一进一出（疑似过账通道）
+    RapidInOut,
+    /// 凌晨时段交易
+    MidnightActivity,
+    /// 相对历史金额明显偏大的转账
+    UnusuallyLarge,
+}
+
+impl AnomalyKind {
+    /// 类别的中文标签（用于风险标记列与汇总表）
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Structuring => "疑似拆分",
+            Self::RapidInOut => "快进快出",
+            Self::MidnightActivity => "凌晨交易",
+            Self::UnusuallyLarge => "金额异常偏大",
+        }
+    }
+}
+
+/// 单条可疑模式发现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyFinding {
+    /// 相关数据行号（1开始）
+    pub row: usize,
+    /// 模式类别
+    pub kind: AnomalyKind,
+    /// 人类可读说明
+    pub message: String,
+}
+
+/// 可疑交易模式检测器
+///
+/// 各阈值提供审计实务中的常用默认值，可按案件口径调整后再检测。
+/// 检测只读输入数据，逐模式独立扫描，同一行可叠加多个标记
+#[derive(Debug, Clone)]
+pub struct AnomalyDetector {
+    /// 申报阈值（拆分检测基准，默认5万元对应大额现金申报标准）
+    pub reporting_threshold: Decimal,
+    /// 略低于阈值的比例下限（默认0.9，即阈值的90%~100%视为贴线）
+    pub near_threshold_ratio: Decimal,
+    /// 贴线金额出现次数达到该值即标记（默认3）
+    pub structuring_min_count: usize,
+    /// 快进快出的最大间隔天数（默认2天）
+    pub rapid_in_out_max_days: i64,
+    /// 凌晨时段的结束小时（默认6，即00:00-05:59）
+    pub midnight_end_hour: u32,
+    /// 偏大判定倍数：金额达到近期中位数的该倍数即标记（默认10）
+    pub large_multiple: Decimal,
+    /// 偏大判定参考的近期非零金额笔数（默认20）
+    pub history_window: usize,
+}
+
+impl Default for AnomalyDetector {
+    fn default() -> Self {
+        Self {
+            reporting_threshold: Decimal::from(50_000),
+            near_threshold_ratio: Decimal::new(9, 1),
+            structuring_min_count: 3,
+            rapid_in_out_max_days: 2,
+            midnight_end_hour: 6,
+            large_multiple: Decimal::from(10),
+            history_window: 20,
+        }
+    }
+}
+
+impl AnomalyDetector {
+    /// 使用默认阈值创建检测器
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 扫描全部可疑模式，按行号升序返回发现列表
+    #[must_use]
+    pub fn detect(&self, transactions: &[Transaction]) -> Vec<AnomalyFinding> {
+        let mut findings = Vec::new();
+        findings.extend(self.detect_structuring(transactions));
+        findings.extend(self.detect_rapid_in_out(transactions));
+        findings.extend(self.detect_midnight_activity(transactions));
+        findings.extend(self.detect_unusually_large(transactions));
+        findings.sort_by_key(|finding| finding.row);
+        findings
+    }
+
+    /// 拆分检测：略低于申报阈值的金额反复出现
+    fn detect_structuring(&self, transactions: &[Transaction]) -> Vec<AnomalyFinding> {
+        let lower = self.reporting_threshold * self.near_threshold_ratio;
+        let near_rows: Vec<usize> = transactions.iter().enumerate()
+            .filter(|(_, tx)| {
+                let amount = tx.abs_amount();
+                amount >= lower && amount < self.reporting_threshold
+            })
+            .map(|(idx, _)| idx + 1)
+            .collect();
+
+        if near_rows.len() < self.structuring_min_count {
+            return Vec::new();
+        }
+        let total = near_rows.len();
+        near_rows.into_iter()
+            .map(|row| AnomalyFinding {
+                row,
+                kind: AnomalyKind::Structuring,
+                message: format!(
+                    "金额贴近申报阈值{}（{}%~100%区间），全表共{}笔，疑似拆分规避申报",
+                    self.reporting_threshold,
+                    self.near_threshold_ratio * Decimal::from(100),
+                    total
+                ),
+            })
+            .collect()
+    }
+
+    /// 快进快出检测：同额收入后短期内出现同额支出
+    fn detect_rapid_in_out(&self, transactions: &[Transaction]) -> Vec<AnomalyFinding> {
+        let mut findings = Vec::new();
+        let mut matched_expense = vec![false; transactions.len()];
+
+        for (in_idx, income_tx) in transactions.iter().enumerate() {
+            if !income_tx.is_income() {
+                continue;
+            }
+            // 向后寻找最近的同额支出（每笔支出只配对一次）
+            for (offset, expense_tx) in transactions[in_idx + 1..].iter().enumerate() {
+                let out_idx = in_idx + 1 + offset;
+                let gap_days = (expense_tx.transaction_date.date()
+                    - income_tx.transaction_date.date()).num_days();
+                if gap_days > self.rapid_in_out_max_days {
+                    break;
+                }
+                if matched_expense[out_idx] || !expense_tx.is_expense()
+                    || expense_tx.expense_amount != income_tx.income_amount
+                {
+                    continue;
+                }
+                matched_expense[out_idx] = true;
+                let message = format!(
+                    "金额{}于{}天内一进（第{}行）一出（第{}行），疑似过账通道",
+                    income_tx.income_amount, gap_days, in_idx + 1, out_idx + 1
+                );
+                findings.push(AnomalyFinding {
+                    row: in_idx + 1,
+                    kind: AnomalyKind::RapidInOut,
+                    message: message.clone(),
+                });
+                findings.push(AnomalyFinding {
+                    row: out_idx + 1,
+                    kind: AnomalyKind::RapidInOut,
+                    message,
+                });
+                break;
+            }
+        }
+        findings
+    }
+
+    /// 凌晨交易检测
+    fn detect_midnight_activity(&self, transactions: &[Transaction]) -> Vec<AnomalyFinding> {
+        transactions.iter().enumerate()
+            .filter(|(_, tx)| {
+                // 未携带具体时间（00:00:00占位）的行不计入，避免大面积误报
+                let time = tx.transaction_date.time();
+                time.hour() < self.midnight_end_hour
+                    && !(time.hour() == 0 && time.minute() == 0 && time.second() == 0)
+            })
+            .map(|(idx, tx)| AnomalyFinding {
+                row: idx + 1,
+                kind: AnomalyKind::MidnightActivity,
+                message: format!(
+                    "交易发生在凌晨{}（00:00-0{}:00为非常规操作时段）",
+                    tx.transaction_date.format("%H:%M:%S"),
+                    self.midnight_end_hour
+                ),
+            })
+            .collect()
+    }
+
+    /// 偏大转账检测：金额达到近期非零金额中位数的若干倍
+    ///
+    /// 与验证层的量级突变检查（×100，怀疑单位混用）不同，
+    /// 这里按×10量级提示"相对历史明显偏大"的单笔资金移动
+    fn detect_unusually_large(&self, transactions: &[Transaction]) -> Vec<AnomalyFinding> {
+        let mut findings = Vec::new();
+        let mut recent: Vec<Decimal> = Vec::new();
+
+        for (idx, tx) in transactions.iter().enumerate() {
+            let amount = tx.abs_amount();
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+            if recent.len() >= 3 {
+                let mut sorted = recent.clone();
+                sorted.sort_unstable();
+                let median = sorted[sorted.len() / 2];
+                if median > Decimal::ZERO && amount >= median * self.large_multiple {
+                    findings.push(AnomalyFinding {
+                        row: idx + 1,
+                        kind: AnomalyKind::UnusuallyLarge,
+                        message: format!(
+                            "金额{}达到近期{}笔交易中位数{}的{}倍以上",
+                            amount, recent.len(), median, self.large_multiple
+                        ),
+                    });
+                }
+            }
+            recent.push(amount);
+            if recent.len() > self.history_window {
+                recent.remove(0);
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn tx(day: u32, hour: u32, income: i64, expense: i64) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, 30, 0)
+            .unwrap();
+        Transaction::new(
+            date,
+            format!("{hour:02}:30:00"),
+            Decimal::from(income),
+            Decimal::from(expense),
+            Decimal::from(100_000),
+            "个人应收".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_detect_structuring_needs_repetition() {
+        let detector = AnomalyDetector::new();
+
+        // 49000元（阈值50000的98%）出现3次，全部标记
+        let transactions = vec![
+            tx(1, 10, 49_000, 0),
+            tx(2, 10, 49_000, 0),
+            tx(3, 10, 49_000, 0),
+        ];
+        let findings = detector.detect(&transactions);
+        let structuring: Vec<_> = findings.iter()
+            .filter(|f| f.kind == AnomalyKind::Structuring)
+            .collect();
+        assert_eq!(structuring.len(), 3);
+        assert_eq!(structuring[0].row, 1);
+
+        // 只出现2次不足以构成模式
+        let sparse = vec![tx(1, 10, 49_000, 0), tx(2, 10, 49_000, 0)];
+        assert!(detector.detect(&sparse).iter()
+            .all(|f| f.kind != AnomalyKind::Structuring));
+    }
+
+    #[test]
+    fn test_detect_rapid_in_out_pairs_rows() {
+        let detector = AnomalyDetector::new();
+
+        let transactions = vec![
+            tx(1, 10, 30_000, 0),
+            tx(2, 10, 0, 30_000),
+            // 间隔超过2天的同额支出不配对
+            tx(10, 10, 20_000, 0),
+            tx(15, 10, 0, 20_000),
+        ];
+        let findings = detector.detect(&transactions);
+        let rapid: Vec<_> = findings.iter()
+            .filter(|f| f.kind == AnomalyKind::RapidInOut)
+            .collect();
+        assert_eq!(rapid.len(), 2);
+        assert_eq!(rapid[0].row, 1);
+        assert_eq!(rapid[1].row, 2);
+    }
+
+    #[test]
+    fn test_detect_midnight_and_large() {
+        let detector = AnomalyDetector::new();
+
+        let mut transactions: Vec<Transaction> = (1..=5)
+            .map(|day| tx(day, 10, 2_000, 0))
+            .collect();
+        // 凌晨3点交易
+        transactions.push(tx(6, 3, 2_000, 0));
+        // 相对中位数2000偏大10倍以上
+        transactions.push(tx(7, 10, 30_000, 0));
+
+        let findings = detector.detect(&transactions);
+        assert!(findings.iter()
+            .any(|f| f.kind == AnomalyKind::MidnightActivity && f.row == 6));
+        assert!(findings.iter()
+            .any(|f| f.kind == AnomalyKind::UnusuallyLarge && f.row == 7));
+    }
+}
@@ -0,0 +1,157 @@
+//! 资金属性层级工具
+//!
+//! 部分案件的资金属性按层级记录（如"公司/子公司A/备用金"）：
+//! 追踪始终在叶子粒度进行，但汇总与筛选常常需要在任意中间层级
+//! 查看合并口径。本模块提供层级拆分、祖先路径展开与上卷聚合，
+//! 分隔符可在配置中调整（见`FundAttributeConfig::hierarchy_separator`）。
+
+/// 资金属性层级解析器
+#[derive(Debug, Clone)]
+pub struct AttributeHierarchy {
+    /// 层级分隔符
+    separator: String,
+}
+
+impl AttributeHierarchy {
+    /// 默认层级分隔符
+    pub const DEFAULT_SEPARATOR: &'static str = "/";
+
+    /// 使用指定分隔符创建解析器
+    #[must_use]
+    pub fn new(separator: impl Into<String>) -> Self {
+        let separator = separator.into();
+        Self {
+            separator: if separator.is_empty() {
+                Self::DEFAULT_SEPARATOR.to_string()
+            } else {
+                separator
+            },
+        }
+    }
+
+    /// 属性是否带层级（包含分隔符）
+    #[must_use]
+    pub fn is_hierarchical(&self, attribute: &str) -> bool {
+        attribute.contains(self.separator.as_str())
+    }
+
+    /// 拆分为各层级名称（逐级去除首尾空白，忽略空段）
+    #[must_use]
+    pub fn levels<'a>(&self, attribute: &'a str) -> Vec<&'a str> {
+        attribute.split(self.separator.as_str())
+            .map(str::trim)
+            .filter(|level| !level.is_empty())
+            .collect()
+    }
+
+    /// 从根到叶展开全部祖先路径（含叶子自身）
+    ///
+    /// `公司/子公司A/备用金` 展开为 `公司`、`公司/子公司A`、`公司/子公司A/备用金`
+    #[must_use]
+    pub fn ancestors(&self, attribute: &str) -> Vec<String> {
+        let levels = self.levels(attribute);
+        let mut paths = Vec::with_capacity(levels.len());
+        let mut current = String::new();
+        for level in levels {
+            if !current.is_empty() {
+                current.push_str(&self.separator);
+            }
+            current.push_str(level);
+            paths.push(current.clone());
+        }
+        paths
+    }
+
+    /// 叶子属性是否归属于给定层级路径（等于该路径或位于其下）
+    #[must_use]
+    pub fn rolls_up_to(&self, attribute: &str, level_path: &str) -> bool {
+        let target = level_path.trim();
+        !target.is_empty() && self.ancestors(attribute).iter().any(|path| path == target)
+    }
+
+    /// 把叶子粒度的计数上卷到所有层级路径
+    ///
+    /// 每个叶子计入自身及全部祖先路径；结果按计数降序、
+    /// 同计数按路径升序排列，与数据集概览的排序口径一致
+    #[must_use]
+    pub fn rollup_counts(&self, leaf_counts: &[(String, usize)]) -> Vec<(String, usize)> {
+        let mut totals: Vec<(String, usize)> = Vec::new();
+        for (attribute, count) in leaf_counts {
+            for path in self.ancestors(attribute) {
+                match totals.iter_mut().find(|(name, _)| *name == path) {
+                    Some((_, total)) => *total += count,
+                    None => totals.push((path, *count)),
+                }
+            }
+        }
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        totals
+    }
+}
+
+impl Default for AttributeHierarchy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEPARATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ancestors_expand_from_root_to_leaf() {
+        let hierarchy = AttributeHierarchy::default();
+
+        assert_eq!(
+            hierarchy.ancestors("公司/子公司A/备用金"),
+            vec!["公司", "公司/子公司A", "公司/子公司A/备用金"]
+        );
+        // 平铺属性的祖先只有自身
+        assert_eq!(hierarchy.ancestors("个人应收"), vec!["个人应收"]);
+        // 层级间空白被规整
+        assert_eq!(hierarchy.ancestors("公司 / 子公司A"), vec!["公司", "公司/子公司A"]);
+    }
+
+    #[test]
+    fn test_rolls_up_to_matches_level_boundaries() {
+        let hierarchy = AttributeHierarchy::default();
+
+        assert!(hierarchy.rolls_up_to("公司/子公司A/备用金", "公司"));
+        assert!(hierarchy.rolls_up_to("公司/子公司A/备用金", "公司/子公司A"));
+        // 子串不构成层级归属："子公司A"不是从根开始的路径
+        assert!(!hierarchy.rolls_up_to("公司/子公司A/备用金", "子公司A"));
+        assert!(!hierarchy.rolls_up_to("公司/子公司A/备用金", "公"));
+    }
+
+    #[test]
+    fn test_rollup_counts_aggregate_to_every_level() {
+        let hierarchy = AttributeHierarchy::default();
+        let leaf_counts = vec![
+            ("公司/子公司A/备用金".to_string(), 3),
+            ("公司/子公司A/工资户".to_string(), 2),
+            ("公司/子公司B".to_string(), 1),
+        ];
+
+        let rollup = hierarchy.rollup_counts(&leaf_counts);
+        let count_of = |path: &str| rollup.iter()
+            .find(|(name, _)| name == path)
+            .map(|(_, count)| *count);
+
+        assert_eq!(count_of("公司"), Some(6));
+        assert_eq!(count_of("公司/子公司A"), Some(5));
+        assert_eq!(count_of("公司/子公司A/备用金"), Some(3));
+        assert_eq!(count_of("公司/子公司B"), Some(1));
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let hierarchy = AttributeHierarchy::new("|");
+
+        assert!(hierarchy.rolls_up_to("公司|备用金", "公司"));
+        assert!(!hierarchy.is_hierarchical("公司/备用金"));
+        // 空分隔符回退为默认值，避免split产生逐字符拆分
+        let fallback = AttributeHierarchy::new("");
+        assert!(fallback.rolls_up_to("公司/备用金", "公司"));
+    }
+}
@@ -0,0 +1,240 @@
+//! 资金属性分类规则引擎
+//!
+//! `is_personal_fund`/`is_company_fund`/`is_investment_product`原本只有
+//! 编译期写死的关键字列表。本模块提供可从JSON/TOML文件加载的分类规则，
+//! 用户无需重新编译即可自定义"个人/公司/投资产品/忽略"的判定口径。
+//! 规则按优先级匹配，首条命中生效；匹配方式支持包含/前缀/完全相等，
+//! 刻意不引入正则依赖——审计场景的属性口径均为固定词面。
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::errors::{AuditError, AuditResult};
+
+/// 资金属性类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FundCategory {
+    /// 个人资金
+    Personal,
+    /// 公司资金
+    Company,
+    /// 投资产品
+    InvestmentProduct,
+    /// 利息/手续费（银行结息、账户管理费、冲正等，单独归集不计入挪用口径）
+    InterestFee,
+    /// 忽略（不参与个人/公司归属判定）
+    Ignore,
+}
+
+impl FundCategory {
+    /// 类别的中文标签
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Personal => "个人资金",
+            Self::Company => "公司资金",
+            Self::InvestmentProduct => "投资产品",
+            Self::InterestFee => "利息/手续费",
+            Self::Ignore => "忽略",
+        }
+    }
+}
+
+/// 规则匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMatcher {
+    /// 属性包含该词面（默认）
+    #[default]
+    Contains,
+    /// 属性以该词面开头
+    Prefix,
+    /// 属性与该词面完全相等
+    Exact,
+}
+
+/// 单条分类规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassificationRule {
+    /// 匹配词面
+    pub pattern: String,
+    /// 匹配方式（缺省为包含匹配）
+    #[serde(default)]
+    pub matcher: RuleMatcher,
+    /// 命中后归入的类别
+    pub category: FundCategory,
+    /// 优先级（数值大者先匹配，缺省为0；同优先级按定义顺序）
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl ClassificationRule {
+    /// 该规则是否命中给定资金属性
+    #[must_use]
+    pub fn matches(&self, fund_attribute: &str) -> bool {
+        match self.matcher {
+            RuleMatcher::Contains => fund_attribute.contains(&self.pattern),
+            RuleMatcher::Prefix => fund_attribute.starts_with(&self.pattern),
+            RuleMatcher::Exact => fund_attribute == self.pattern,
+        }
+    }
+}
+
+/// 分类规则集 - 从用户提供的JSON/TOML文件加载
+///
+/// 例如：
+///
+/// ```toml
+/// [[rules]]
+/// pattern = "个人"
+/// category = "personal"
+///
+/// [[rules]]
+/// pattern = "理财-"
+/// matcher = "prefix"
+/// category = "investment_product"
+/// priority = 10
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClassificationRuleSet {
+    /// 全部规则
+    #[serde(default)]
+    pub rules: Vec<ClassificationRule>,
+}
+
+impl ClassificationRuleSet {
+    /// 从JSON/TOML文件加载规则集（按扩展名区分格式）
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> AuditResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AuditError::config_error(format!("无法读取分类规则文件 {}: {e}", path.display())))?;
+
+        let rule_set: Self = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content)
+                .map_err(|e| AuditError::config_error(format!("分类规则TOML解析失败: {e}")))?,
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| AuditError::config_error(format!("分类规则JSON解析失败: {e}")))?,
+            _ => return Err(AuditError::config_error(
+                format!("不支持的分类规则格式: {}（仅支持.toml/.json）", path.display())
+            )),
+        };
+
+        if rule_set.rules.is_empty() {
+            return Err(AuditError::config_error(
+                format!("分类规则文件不含任何规则: {}", path.display())
+            ));
+        }
+        for rule in &rule_set.rules {
+            if rule.pattern.is_empty() {
+                return Err(AuditError::config_error("分类规则的匹配词面不能为空"));
+            }
+        }
+
+        Ok(rule_set)
+    }
+
+    /// 对资金属性分类
+    ///
+    /// 按优先级从高到低匹配，首条命中生效；
+    /// 无规则命中时返回None（由调用方回退到内置关键字判定）
+    #[must_use]
+    pub fn classify(&self, fund_attribute: &str) -> Option<FundCategory> {
+        // 规则量级很小（几十条），每次按优先级稳定排序的开销可忽略
+        let mut ordered: Vec<&ClassificationRule> = self.rules.iter().collect();
+        ordered.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        ordered.iter()
+            .find(|rule| rule.matches(fund_attribute))
+            .map(|rule| rule.category)
+    }
+
+    /// 对资金属性分类并返回命中的规则（规则测试展示用）
+    #[must_use]
+    pub fn classify_with_rule(&self, fund_attribute: &str) -> Option<&ClassificationRule> {
+        let mut ordered: Vec<&ClassificationRule> = self.rules.iter().collect();
+        ordered.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        ordered.into_iter().find(|rule| rule.matches(fund_attribute))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rule_set() -> ClassificationRuleSet {
+        ClassificationRuleSet {
+            rules: vec![
+                ClassificationRule {
+                    pattern: "个人".to_string(),
+                    matcher: RuleMatcher::Contains,
+                    category: FundCategory::Personal,
+                    priority: 0,
+                },
+                ClassificationRule {
+                    pattern: "公司".to_string(),
+                    matcher: RuleMatcher::Contains,
+                    category: FundCategory::Company,
+                    priority: 0,
+                },
+                ClassificationRule {
+                    pattern: "理财-".to_string(),
+                    matcher: RuleMatcher::Prefix,
+                    category: FundCategory::InvestmentProduct,
+                    priority: 10,
+                },
+                ClassificationRule {
+                    pattern: "利息".to_string(),
+                    matcher: RuleMatcher::Exact,
+                    category: FundCategory::Ignore,
+                    priority: 0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_classify_by_priority_and_matcher() {
+        let rules = sample_rule_set();
+
+        assert_eq!(rules.classify("个人应收"), Some(FundCategory::Personal));
+        assert_eq!(rules.classify("公司应付"), Some(FundCategory::Company));
+        // "理财-"前缀规则优先级更高，即使词面同时包含"个人"
+        assert_eq!(rules.classify("理财-个人账户"), Some(FundCategory::InvestmentProduct));
+        // Exact只匹配完全相等
+        assert_eq!(rules.classify("利息"), Some(FundCategory::Ignore));
+        assert_eq!(rules.classify("利息收入"), None);
+        assert_eq!(rules.classify("其他资金"), None);
+    }
+
+    #[test]
+    fn test_load_from_toml_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let toml_path = dir.path().join("rules.toml");
+        std::fs::write(&toml_path, concat!(
+            "[[rules]]\n",
+            "pattern = \"个人\"\n",
+            "category = \"personal\"\n",
+            "\n",
+            "[[rules]]\n",
+            "pattern = \"理财-\"\n",
+            "matcher = \"prefix\"\n",
+            "category = \"investment_product\"\n",
+            "priority = 10\n",
+        )).unwrap();
+        let rules = ClassificationRuleSet::load_from_file(&toml_path).unwrap();
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.classify("理财-SL100613"), Some(FundCategory::InvestmentProduct));
+
+        let json_path = dir.path().join("rules.json");
+        std::fs::write(&json_path, r#"{"rules":[{"pattern":"公司","category":"company"}]}"#).unwrap();
+        let rules = ClassificationRuleSet::load_from_file(&json_path).unwrap();
+        assert_eq!(rules.classify("公司应收"), Some(FundCategory::Company));
+
+        // 空规则集与不支持的扩展名都应报错
+        let empty_path = dir.path().join("empty.json");
+        std::fs::write(&empty_path, r#"{"rules":[]}"#).unwrap();
+        assert!(ClassificationRuleSet::load_from_file(&empty_path).is_err());
+        assert!(ClassificationRuleSet::load_from_file(dir.path().join("rules.yaml")).is_err());
+    }
+}
@@ -0,0 +1,3506 @@
+//! 统一的Excel文件处理工具
+//! 
+//! 合并所有Excel相关功能，消除重复代码。
+//! 提供完整的Excel读取、写入、数据解析和格式化功能。
+
+use crate::errors::{AuditError, AuditResult};
+use crate::data_models::{Transaction, AuditSummary, FundPoolRecord, Config};
+use crate::utils::TimeProcessor;
+use calamine::{Reader, Xlsx, open_workbook, DataType};
+use chrono::NaiveDateTime;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use log::{info, warn, debug};
+
+// 使用rust_xlsxwriter进行Excel写入
+use rust_xlsxwriter::{Workbook, Worksheet, Format, Color};
+
+/// 分块读取的进度信息
+#[derive(Debug, Clone)]
+pub struct ChunkProgress {
+    /// 当前块序号（从0开始）
+    pub chunk_index: usize,
+    /// 已扫描的数据行数（不含表头）
+    pub rows_scanned: usize,
+    /// 数据总行数（不含表头）
+    pub total_rows: usize,
+    /// 累计成功解析的交易条数
+    pub transactions_parsed: usize,
+}
+
+/// 预检中单个逻辑列的匹配情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InspectedColumn {
+    /// 逻辑列的中文名（如"交易日期"）
+    pub field: String,
+    /// 是否为必需列
+    pub required: bool,
+    /// 匹配到的表头原文（未匹配时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_header: Option<String>,
+    /// 匹配到的列序号（0开始，未匹配时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_index: Option<usize>,
+}
+
+/// 输入文件预检报告
+///
+/// 分析前的快速体检：只读取工作簿结构与数据概况，不执行完整分析，
+/// 供前端在运行前向用户展示将要发生什么并尽早暴露列映射问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInspection {
+    /// 工作簿中的全部工作表名（按文件内顺序）
+    pub sheets: Vec<String>,
+    /// 本次预检实际读取的工作表
+    pub inspected_sheet: String,
+    /// 检测到的表头所在Excel行号（1开始；一列都没匹配到时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_row: Option<usize>,
+    /// 各逻辑列的匹配情况
+    pub columns: Vec<InspectedColumn>,
+    /// 未匹配到的必需列名
+    pub missing_required_columns: Vec<String>,
+    /// 裁剪尾部空白行后的数据行数
+    pub data_row_count: usize,
+    /// 数据区最早的交易日期（`YYYY-MM-DD`，无法解析任何日期时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_from: Option<String>,
+    /// 数据区最晚的交易日期（`YYYY-MM-DD`，无法解析任何日期时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_to: Option<String>,
+    /// 表头行各单元格原文（未检测到表头时为空）
+    pub preview_headers: Vec<String>,
+    /// 数据区前若干行的单元格原文预览
+    pub preview_rows: Vec<Vec<String>>,
+}
+
+/// Excel处理器
+/// 
+/// 负责Excel文件的读取、写入和数据转换
+#[derive(Debug)]
+pub struct ExcelProcessor {
+    /// 配置信息
+    config: Config,
+    /// 重试后才成功的IO操作记录（服务层取走后转为结构化警告）
+    io_retry_events: std::sync::Mutex<Vec<String>>,
+    /// 验证修复报告（设置后随结果工作簿导出为独立工作表）
+    validation_report: Option<crate::utils::unified_validator::ValidationReport>,
+    /// 可疑模式发现（设置后随结果工作簿导出"风险标记汇总"工作表）
+    anomaly_findings: Option<Vec<crate::utils::anomaly_detector::AnomalyFinding>>,
+    /// 按期汇总报告（设置后随结果工作簿导出"按期汇总"工作表）
+    period_summary: Option<crate::utils::period_summary::PeriodSummaryReport>,
+    /// 场外资金池记录（设置后随结果工作簿导出"场外资金池"工作表）
+    offsite_pool_records: Option<crate::data_models::OffsitePoolRecordManager>,
+    /// 要读取的工作表名（None时读取第一个工作表，与pandas默认行为一致）
+    sheet_name: Option<String>,
+}
+
+impl ExcelProcessor {
+    /// 创建新的Excel处理器
+    #[must_use] 
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            io_retry_events: std::sync::Mutex::new(Vec::new()),
+            validation_report: None,
+            anomaly_findings: None,
+            period_summary: None,
+            offsite_pool_records: None,
+            sheet_name: None,
+        }
+    }
+
+    /// 指定要读取的工作表
+    ///
+    /// 多表工作簿中流水数据未必在第一个工作表；指定后所有读取
+    /// 接口都从该工作表取数，工作表不存在时报错并列出可选项
+    #[must_use]
+    pub fn with_sheet_name(mut self, sheet_name: impl Into<String>) -> Self {
+        self.sheet_name = Some(sheet_name.into());
+        self
+    }
+    
+    /// 附带验证修复报告
+    /// 
+    /// 导出分析结果时会把报告写入"顺序修复报告"工作表（仅在有
+    /// 修复或未修复错误时生成；CSV模式不支持多表，报告不随CSV导出）
+    #[must_use]
+    pub fn with_validation_report(mut self, report: crate::utils::unified_validator::ValidationReport) -> Self {
+        self.validation_report = Some(report);
+        self
+    }
+
+    /// 附带可疑模式发现列表
+    ///
+    /// 导出分析结果时会把发现写入"风险标记汇总"工作表（为空时
+    /// 不生成；CSV模式不支持多表，汇总不随CSV导出，但逐行的
+    /// 风险标记列仍在CSV中保留）
+    #[must_use]
+    pub fn with_anomaly_findings(mut self, findings: Vec<crate::utils::anomaly_detector::AnomalyFinding>) -> Self {
+        self.anomaly_findings = Some(findings);
+        self
+    }
+
+    /// 附带按期汇总报告
+    ///
+    /// 导出分析结果时会把按日/按月汇总写入"按期汇总"工作表
+    /// （两个粒度都为空时不生成；CSV模式不支持多表，汇总不随CSV导出）
+    #[must_use]
+    pub fn with_period_summary(mut self, report: crate::utils::period_summary::PeriodSummaryReport) -> Self {
+        self.period_summary = Some(report);
+        self
+    }
+
+    /// 附带场外资金池记录
+    ///
+    /// 导出分析结果时会把记录按池分组连同小计行写入"场外资金池"
+    /// 工作表（无记录时不生成；CSV模式不支持多表，记录不随CSV导出），
+    /// 供核查人员移交单个文件而不是主结果加资金池记录两份
+    #[must_use]
+    pub fn with_offsite_pool_records(mut self, record_manager: crate::data_models::OffsitePoolRecordManager) -> Self {
+        self.offsite_pool_records = Some(record_manager);
+        self
+    }
+    
+    /// 带退避的IO重试执行
+    /// 
+    /// 网络盘与同步目录（OneDrive等）偶发读写失败，按配置的退避策略
+    /// 重试打开/保存操作；重试过才成功的操作记入事件列表，
+    /// 由服务层通过[`Self::take_io_retry_events`]取走转为结构化警告
+    fn with_io_retry<T>(
+        &self,
+        operation: &str,
+        mut op: impl FnMut() -> AuditResult<T>,
+    ) -> AuditResult<T> {
+        let retry = &self.config.io_retry;
+        let mut attempt = 0u32;
+        loop {
+            match op() {
+                Ok(value) => {
+                    if attempt > 0 {
+                        let event = format!("{operation}经{attempt}次重试后成功");
+                        warn!("⚠️ {event}");
+                        self.io_retry_events.lock().unwrap().push(event);
+                    }
+                    return Ok(value);
+                }
+                Err(e) if attempt < retry.max_retries => {
+                    attempt += 1;
+                    // 指数退避，封顶64倍避免移位溢出
+                    let delay = retry.backoff_ms.saturating_mul(1u64 << (attempt - 1).min(6));
+                    warn!("⚠️ {operation}失败，{delay}ms后第{attempt}次重试: {e}");
+                    std::thread::sleep(std::time::Duration::from_millis(delay));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    
+    /// 取走重试后才成功的IO操作记录（清空内部列表）
+    #[must_use]
+    pub fn take_io_retry_events(&self) -> Vec<String> {
+        std::mem::take(&mut *self.io_retry_events.lock().unwrap())
+    }
+
+    /// 列出工作簿中的全部工作表名（按文件内顺序）
+    ///
+    /// 供前端与CLI在分析前发现可选工作表，配合[`Self::with_sheet_name`]
+    /// 让用户从多表工作簿中选择流水数据所在的工作表
+    pub fn list_sheets<P: AsRef<Path>>(&self, file_path: P) -> AuditResult<Vec<String>> {
+        let path = file_path.as_ref();
+        let workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+        let sheet_names = workbook.sheet_names();
+        if sheet_names.is_empty() {
+            return Err(AuditError::excel_error("Excel文件中没有工作表"));
+        }
+        Ok(sheet_names)
+    }
+
+    /// 确定本次读取的工作表名
+    ///
+    /// 未指定时沿用第一个工作表；指定的工作表不存在时报
+    /// 验证错误并列出可选工作表，避免静默读错数据
+    fn resolve_sheet_name(&self, sheet_names: &[String]) -> AuditResult<String> {
+        if sheet_names.is_empty() {
+            return Err(AuditError::excel_error("Excel文件中没有工作表"));
+        }
+        match &self.sheet_name {
+            Some(selected) => {
+                if sheet_names.iter().any(|name| name == selected) {
+                    Ok(selected.clone())
+                } else {
+                    Err(AuditError::validation_error(format!(
+                        "工作表\"{selected}\"不存在，可选工作表: {}",
+                        sheet_names.join("、")
+                    )))
+                }
+            }
+            None => Ok(sheet_names[0].clone()),
+        }
+    }
+
+    /// 为显式选择的工作表附加错误上下文
+    ///
+    /// 用户指定的工作表缺少表头或必需列时，错误信息需要指明
+    /// 是哪个工作表不可用；默认读第一个表时保持原错误不变
+    fn with_sheet_context(&self, error: AuditError) -> AuditError {
+        match &self.sheet_name {
+            Some(selected) => AuditError::validation_error(format!(
+                "工作表\"{selected}\"无法用于分析: {error}"
+            )),
+            None => error,
+        }
+    }
+    
+    /// 从Excel文件读取交易记录
+    /// Python来源: `src/utils/data_processor.py:39` `df = pd.read_excel(file_path)`
+    /// 
+    /// # Arguments
+    /// * `file_path` - Excel文件路径
+    /// 
+    /// # Returns
+    /// * `AuditResult<Vec<Transaction>>` - 交易记录列表
+    pub fn read_transactions<P: AsRef<Path>>(&self, file_path: P) -> AuditResult<Vec<Transaction>> {
+        let path = file_path.as_ref();
+        // Python来源: src/utils/data_processor.py:38 `audit_logger.info("正在读取Excel文件...")`
+        info!("开始读取Excel文件: {}", path.display());
+        
+        // Python来源: src/utils/data_processor.py:39 `df = pd.read_excel(file_path)`
+        // 打开Excel工作簿（瞬时IO失败按配置重试）
+        let mut workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+        
+        // 未指定工作表时读第一个（Python中pandas默认读取第一个sheet）
+        let sheet_name = self.resolve_sheet_name(&workbook.sheet_names())?;
+        info!("读取工作表: {sheet_name}");
+
+        let range = workbook.worksheet_range(&sheet_name)
+            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+
+        // 解析数据
+        self.parse_transactions_from_range(range)
+            .map_err(|e| self.with_sheet_context(e))
+    }
+
+    /// 统计有效数据行数（不解析具体字段）
+    ///
+    /// 供运行前估算等场景快速获取规模：定位表头、裁剪尾部空白行后
+    /// 返回数据区行数，跳过逐行解析的开销
+    pub fn count_data_rows<P: AsRef<Path>>(&self, file_path: P) -> AuditResult<usize> {
+        let path = file_path.as_ref();
+        let mut workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+
+        let sheet_name = self.resolve_sheet_name(&workbook.sheet_names())?;
+        let range = workbook.worksheet_range(&sheet_name)
+            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+
+        let rows: Vec<_> = range.rows().collect();
+        if rows.is_empty() {
+            return Err(self.with_sheet_context(AuditError::excel_error("Excel工作表为空")));
+        }
+        let (header_idx, _) = self.locate_header_row(&rows)
+            .map_err(|e| self.with_sheet_context(e))?;
+        Ok(Self::trim_trailing_blank_rows(&rows[header_idx + 1..]).len())
+    }
+
+    /// 预检报告中的数据行预览条数上限
+    const PREVIEW_ROWS: usize = 20;
+
+    /// 预检输入文件（不执行完整分析）
+    ///
+    /// 打开工作簿并报告工作表列表、表头位置、各列匹配/缺失情况、
+    /// 数据行数、日期范围与前[`Self::PREVIEW_ROWS`]行预览。与正式读取
+    /// 不同，必需列缺失不报错而是记入报告，供前端在运行前提示用户
+    /// 修正列映射
+    pub fn inspect_file<P: AsRef<Path>>(&self, file_path: P) -> AuditResult<FileInspection> {
+        let path = file_path.as_ref();
+        info!("🔍 开始预检输入文件: {}", path.display());
+
+        let mut workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+
+        let sheets = workbook.sheet_names();
+        let inspected_sheet = self.resolve_sheet_name(&sheets)?;
+        let range = workbook.worksheet_range(&inspected_sheet)
+            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+        let rows: Vec<_> = range.rows().collect();
+        if rows.is_empty() {
+            return Err(self.with_sheet_context(AuditError::excel_error("Excel工作表为空")));
+        }
+
+        // 与正式读取相同的表头探测范围，取匹配列数最多的候选行
+        let mut header_idx = 0;
+        let mut header_indices = self.match_column_indices(rows[0]);
+        let mut matched = header_indices.matched_count();
+        for (idx, row) in rows.iter().enumerate().take(Self::MAX_TITLE_ROWS + 1).skip(1) {
+            let indices = self.match_column_indices(row);
+            if indices.matched_count() > matched {
+                header_idx = idx;
+                matched = indices.matched_count();
+                header_indices = indices;
+            }
+        }
+
+        // 一列都没匹配到：给出原始前几行预览，让用户自己看出表头问题
+        if matched == 0 {
+            warn!("⚠️ 预检未在前{}行中找到任何可识别的列", Self::MAX_TITLE_ROWS + 1);
+            return Ok(FileInspection {
+                sheets,
+                inspected_sheet,
+                header_row: None,
+                columns: Vec::new(),
+                missing_required_columns: ColumnIndices::new().labeled().iter()
+                    .filter(|(_, _, required)| *required)
+                    .map(|(field, _, _)| (*field).to_string())
+                    .collect(),
+                data_row_count: 0,
+                date_from: None,
+                date_to: None,
+                preview_headers: Vec::new(),
+                preview_rows: rows.iter().take(Self::PREVIEW_ROWS)
+                    .map(|row| row.iter().map(ToString::to_string).collect())
+                    .collect(),
+            });
+        }
+
+        let header_row_cells = rows[header_idx];
+        let data_rows = Self::trim_trailing_blank_rows(&rows[header_idx + 1..]);
+
+        // 流水号列未配置时不出现在报告中，避免"缺少可选列"的噪音
+        let sequence_enabled = self.config.excel_columns.sequence_number_column.is_some()
+            || self.config.same_time_ordering.tiebreak_column.is_some();
+        let columns: Vec<InspectedColumn> = header_indices.labeled().iter()
+            .filter(|(_, _, required)| *required || sequence_enabled)
+            .map(|(field, index, required)| InspectedColumn {
+                field: (*field).to_string(),
+                required: *required,
+                matched_header: index
+                    .and_then(|i| header_row_cells.get(i))
+                    .and_then(calamine::DataType::as_string),
+                column_index: *index,
+            })
+            .collect();
+        let missing_required_columns: Vec<String> = columns.iter()
+            .filter(|column| column.required && column.column_index.is_none())
+            .map(|column| column.field.clone())
+            .collect();
+
+        // 日期范围：逐行解析交易日期列，无法解析的行跳过
+        let mut date_range: Option<(NaiveDateTime, NaiveDateTime)> = None;
+        if let Some(date_idx) = header_indices.transaction_date {
+            for row in data_rows {
+                let Some(cell) = row.get(date_idx) else { continue };
+                let Ok(date) = TimeProcessor::parse_excel_date(cell) else { continue };
+                date_range = Some(match date_range {
+                    None => (date, date),
+                    Some((from, to)) => (from.min(date), to.max(date)),
+                });
+            }
+        }
+
+        info!("✅ 预检完成: 表头第{}行，匹配{matched}列，数据{}行",
+            header_idx + 1, data_rows.len());
+        Ok(FileInspection {
+            sheets,
+            inspected_sheet,
+            header_row: Some(header_idx + 1),
+            columns,
+            missing_required_columns,
+            data_row_count: data_rows.len(),
+            date_from: date_range.map(|(from, _)| from.format("%Y-%m-%d").to_string()),
+            date_to: date_range.map(|(_, to)| to.format("%Y-%m-%d").to_string()),
+            preview_headers: header_row_cells.iter().map(ToString::to_string).collect(),
+            preview_rows: data_rows.iter().take(Self::PREVIEW_ROWS)
+                .map(|row| row.iter().map(ToString::to_string).collect())
+                .collect(),
+        })
+    }
+
+    /// 分块流式读取交易记录
+    /// 
+    /// [`Self::read_transactions`]会把整个工作表一次性解析为`Vec<Transaction>`，
+    /// 50万行以上的流水文件会出现内存峰值并阻塞界面。本方法按`chunk_size`
+    /// 分块解析，每解析完一块立即通过`callback`交给调用方消费并附带进度信息，
+    /// 便于算法端增量处理与前端按块展示进度。
+    /// 
+    /// # Arguments
+    /// * `file_path` - Excel文件路径
+    /// * `chunk_size` - 每块交易条数（必须大于0）
+    /// * `callback` - 每块数据的消费回调，返回Err会中止读取
+    /// 
+    /// # Returns
+    /// * `AuditResult<usize>` - 成功解析的交易总条数
+    pub fn read_transactions_chunked<P, F>(
+        &self,
+        file_path: P,
+        chunk_size: usize,
+        mut callback: F,
+    ) -> AuditResult<usize>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Vec<Transaction>, &ChunkProgress) -> AuditResult<()>,
+    {
+        if chunk_size == 0 {
+            return Err(AuditError::validation_error("chunk_size必须大于0"));
+        }
+        
+        let path = file_path.as_ref();
+        info!("开始分块读取Excel文件: {} (每块{}条)", path.display(), chunk_size);
+        
+        let mut workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+        
+        let sheet_name = self.resolve_sheet_name(&workbook.sheet_names())?;
+        let range = workbook.worksheet_range(&sheet_name)
+            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+
+        // 逐行消费迭代器，避免再collect一份完整的行列表
+        let mut rows = range.rows();
+        let mut header_idx = 0usize;
+        let column_indices = loop {
+            let header_row = rows.next()
+                .ok_or_else(|| self.with_sheet_context(AuditError::excel_error("Excel工作表为空")))?;
+            match self.find_column_indices(header_row) {
+                Ok(indices) => {
+                    if header_idx > 0 {
+                        info!("📋 检测到第1-{header_idx}行为标题说明行，已跳过，表头位于第{}行", header_idx + 1);
+                    }
+                    break indices;
+                }
+                Err(e) => {
+                    if header_idx >= Self::MAX_TITLE_ROWS {
+                        return Err(self.with_sheet_context(e));
+                    }
+                    header_idx += 1;
+                }
+            }
+        };
+        
+        // 尾部空白行同样会放大进度总数，先做一次轻量扫描确定有效末行
+        let last_data_row = range.rows().rposition(|row| !Self::is_blank_row(row));
+        let total_rows = last_data_row
+            .map_or(0, |last| last.saturating_sub(header_idx));
+        if total_rows == 0 {
+            return Err(AuditError::excel_error(
+                "Excel文件只有表头没有数据行，请确认导出时是否包含了数据区域"
+            ));
+        }
+        let trimmed = range.height().saturating_sub(header_idx + 1 + total_rows);
+        if trimmed > 0 {
+            info!("📋 已裁剪尾部 {} 行空白行，有效数据区域: 第{}-{}行",
+                trimmed, header_idx + 2, header_idx + 1 + total_rows);
+        }
+        let mut buffer: Vec<Transaction> = Vec::with_capacity(chunk_size);
+        let mut parsed_count = 0usize;
+        let mut chunk_index = 0usize;
+        
+        for (row_idx, row) in rows.enumerate() {
+            // 有效数据区域之后全部为空白行，直接结束
+            if header_idx + 1 + row_idx > last_data_row.unwrap_or(0) {
+                break;
+            }
+            if Self::is_trailing_total_row(row, &column_indices) {
+                info!("📋 检测到第{}行为合计行，已跳过", header_idx + row_idx + 2);
+                continue;
+            }
+            match self.parse_transaction_row(row, &column_indices) {
+                Ok(transaction) => buffer.push(transaction),
+                Err(e) => {
+                    warn!("解析第{}行数据失败: {}", header_idx + row_idx + 2, e);
+                    // 与全量读取一致：跳过坏行，不中断整个流程
+                }
+            }
+            
+            if buffer.len() >= chunk_size {
+                parsed_count += buffer.len();
+                let progress = ChunkProgress {
+                    chunk_index,
+                    rows_scanned: row_idx + 1,
+                    total_rows,
+                    transactions_parsed: parsed_count,
+                };
+                info!("⏳ 分块读取进度: {}/{} ({:.1}%)",
+                    progress.rows_scanned,
+                    total_rows,
+                    progress.rows_scanned as f64 / total_rows.max(1) as f64 * 100.0
+                );
+                let chunk = std::mem::replace(&mut buffer, Vec::with_capacity(chunk_size));
+                callback(chunk, &progress)?;
+                chunk_index += 1;
+            }
+        }
+        
+        // 最后不足一块的剩余数据
+        if !buffer.is_empty() {
+            parsed_count += buffer.len();
+            let progress = ChunkProgress {
+                chunk_index,
+                rows_scanned: total_rows,
+                total_rows,
+                transactions_parsed: parsed_count,
+            };
+            callback(buffer, &progress)?;
+        }
+        
+        info!("✅ Excel分块读取完成，共解析 {parsed_count} 条交易记录");
+        Ok(parsed_count)
+    }
+
+    /// 判断文件是否为本工具导出的结果工作簿
+    ///
+    /// 以是否存在"分析摘要"工作表为标志；
+    /// 文件打不开或不是xlsx格式时按普通输入文件处理，返回false
+    #[must_use]
+    pub fn is_result_workbook<P: AsRef<Path>>(file_path: P) -> bool {
+        open_workbook::<Xlsx<_>, _>(file_path.as_ref())
+            .is_ok_and(|workbook| workbook.sheet_names().iter().any(|name| name == "分析摘要"))
+    }
+
+    /// 读回已导出的结果工作簿
+    ///
+    /// 逆向解析[`Self::write_transaction_row`]写出的21列结果布局与
+    /// "分析摘要"工作表，不重跑算法即可恢复处理后交易与审计摘要，
+    /// 供时点查询直接以结果文件为数据源。分表导出的
+    /// `分析结果_1/_2/...`按编号顺序拼接；单表导出读第一个工作表
+    pub fn read_analysis_results<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+    ) -> AuditResult<(Vec<Transaction>, AuditSummary)> {
+        let path = file_path.as_ref();
+        info!("开始读回结果工作簿: {}", path.display());
+
+        let mut workbook: Xlsx<_> = self.with_io_retry("打开Excel文件", || {
+            open_workbook(path)
+                .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {e}")))
+        })?;
+
+        let sheet_names = workbook.sheet_names();
+        if !sheet_names.iter().any(|name| name == "分析摘要") {
+            return Err(AuditError::validation_error(
+                "该文件不是本工具导出的结果工作簿（缺少\"分析摘要\"工作表）"
+            ));
+        }
+
+        // 分表导出时数据分布在"分析结果_N"；单表导出时数据在第一个工作表
+        let mut sheeted: Vec<(usize, String)> = sheet_names.iter()
+            .filter_map(|name| {
+                name.strip_prefix("分析结果_")
+                    .and_then(|suffix| suffix.parse::<usize>().ok())
+                    .map(|index| (index, name.clone()))
+            })
+            .collect();
+        sheeted.sort_by_key(|(index, _)| *index);
+        let data_sheets: Vec<String> = if sheeted.is_empty() {
+            vec![sheet_names[0].clone()]
+        } else {
+            sheeted.into_iter().map(|(_, name)| name).collect()
+        };
+
+        let mut transactions = Vec::new();
+        for sheet_name in &data_sheets {
+            let range = workbook.worksheet_range(sheet_name)
+                .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+            self.parse_result_rows(&range, sheet_name, &mut transactions)?;
+        }
+
+        let summary_range = workbook.worksheet_range("分析摘要")
+            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {e}")))?;
+        let summary = self.parse_result_summary(&summary_range)?;
+
+        info!("✅ 结果工作簿读回完成，共 {} 条交易记录", transactions.len());
+        Ok((transactions, summary))
+    }
+
+    /// 解析单个结果数据工作表
+    ///
+    /// 表头首列必须是"交易时间"（或其当前配置语言的译名），
+    /// 防止把普通流水文件误按结果布局解析。坏行与正式读取一致：
+    /// 警告后跳过，不中断整个流程
+    fn parse_result_rows(
+        &self,
+        range: &calamine::Range<calamine::Data>,
+        sheet_name: &str,
+        transactions: &mut Vec<Transaction>,
+    ) -> AuditResult<()> {
+        let rows: Vec<_> = range.rows().collect();
+        let Some((header, data_rows)) = rows.split_first() else {
+            return Err(AuditError::excel_error(format!("工作表\"{sheet_name}\"为空")));
+        };
+
+        let first_header = header.first()
+            .and_then(calamine::DataType::as_string)
+            .unwrap_or_default();
+        let localized = crate::utils::i18n::header_label(
+            "交易时间", self.config.excel_columns.header_language);
+        if first_header.trim() != "交易时间" && first_header.trim() != localized {
+            return Err(AuditError::validation_error(format!(
+                "工作表\"{sheet_name}\"不是结果布局（首列应为\"交易时间\"，实际为\"{first_header}\"）"
+            )));
+        }
+
+        for (row_index, row) in Self::trim_trailing_blank_rows(data_rows).iter().enumerate() {
+            match self.parse_result_row(row) {
+                Ok(transaction) => transactions.push(transaction),
+                Err(e) => {
+                    warn!("读回工作表\"{sheet_name}\"第{}行失败: {e}", row_index + 2);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 解析结果布局的单行交易（[`Self::write_transaction_row`]的逆操作）
+    fn parse_result_row(&self, row: &[calamine::Data]) -> AuditResult<Transaction> {
+        let cell = |col: usize| row.get(col).unwrap_or(&calamine::Data::Empty);
+
+        // 第0列是合并的日期时间字符串，原样保留到transaction_time，
+        // 再次导出时write_transaction_row检测到完整格式会直接复用
+        let datetime_cell = cell(0);
+        let transaction_date = self.parse_date(datetime_cell)?;
+        let transaction_time = datetime_cell.as_string().unwrap_or_default();
+
+        let mut tx = Transaction::new(
+            transaction_date,
+            transaction_time,
+            self.parse_decimal(cell(1))?,
+            self.parse_decimal(cell(2))?,
+            self.parse_decimal(cell(3))?,
+            cell(4).as_string().unwrap_or_default(),
+        );
+
+        tx.personal_ratio = Some(self.parse_decimal(cell(5))?);
+        tx.company_ratio = Some(self.parse_decimal(cell(6))?);
+        tx.behavior_nature = Self::non_empty_string(cell(7));
+        tx.cumulative_misappropriation = Some(self.parse_decimal(cell(8))?);
+        tx.cumulative_advance = Some(self.parse_decimal(cell(9))?);
+        tx.cumulative_company_principal_returned = Some(self.parse_decimal(cell(10))?);
+        tx.cumulative_personal_principal_returned = Some(self.parse_decimal(cell(11))?);
+        tx.cumulative_personal_profit = Some(self.parse_decimal(cell(12))?);
+        tx.cumulative_company_profit = Some(self.parse_decimal(cell(13))?);
+        tx.personal_balance = Some(self.parse_decimal(cell(14))?);
+        tx.company_balance = Some(self.parse_decimal(cell(15))?);
+        // 第16列总余额是个人+公司的派生列，读回时不单独存储
+        tx.funding_gap = Some(self.parse_decimal(cell(17))?);
+        tx.fund_source_breakdown = Self::non_empty_string(cell(18));
+        tx.behavior_explanation = Self::non_empty_string(cell(19));
+        tx.risk_flags = Self::non_empty_string(cell(20));
+
+        Ok(tx)
+    }
+
+    /// 解析"分析摘要"工作表的指标/数值对
+    ///
+    /// 按[`Self::write_summary_worksheet`]写出的指标名称匹配；
+    /// 未识别的指标（如后续版本新增）读回时忽略
+    fn parse_result_summary(
+        &self,
+        range: &calamine::Range<calamine::Data>,
+    ) -> AuditResult<AuditSummary> {
+        let mut summary = AuditSummary::new();
+        for row in range.rows().skip(1) {
+            let Some(name) = row.first().and_then(calamine::DataType::as_string) else {
+                continue;
+            };
+            let value = self.parse_decimal(row.get(1).unwrap_or(&calamine::Data::Empty))?;
+            match name.trim() {
+                "个人余额" => summary.personal_balance = value,
+                "公司余额" => summary.company_balance = value,
+                "总余额" => summary.total_balance = value,
+                "累计挪用金额" => summary.total_misappropriation = value,
+                "累计垫付金额" => summary.total_advance_payment = value,
+                "累计利息收入" => summary.total_interest_income = value,
+                "累计手续费支出" => summary.total_fee_expense = value,
+                "累计归还公司本金" => summary.total_company_principal_returned = value,
+                "累计归还个人本金" => summary.total_personal_principal_returned = value,
+                "总计个人利润" => summary.total_personal_profit = value,
+                "总计公司利润" => summary.total_company_profit = value,
+                "资金缺口" => summary.funding_gap = value,
+                _ => {}
+            }
+        }
+        Ok(summary)
+    }
+
+    /// 读取字符串单元格，空白视为未填写
+    fn non_empty_string(cell: &calamine::Data) -> Option<String> {
+        cell.as_string()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// 从Excel范围解析交易记录
+    /// Python来源: src/utils/data_processor.py:41-80 的数据预处理逻辑
+    fn parse_transactions_from_range(
+        &self,
+        range: calamine::Range<calamine::Data>
+    ) -> AuditResult<Vec<Transaction>> {
+        let mut transactions = Vec::new();
+        let rows: Vec<_> = range.rows().collect();
+        
+        if rows.is_empty() {
+            return Err(AuditError::excel_error("Excel工作表为空"));
+        }
+        
+        // Python来源: src/utils/data_processor.py:45 检查列名逻辑
+        // 查找表头（自动跳过表头上方的合并标题说明行）
+        let (header_idx, column_indices) = self.locate_header_row(&rows)?;
+        info!("找到列索引: {column_indices:?}");
+        
+        // Python来源: src/utils/data_processor.py:47 `audit_logger.info("正在预处理数据...")`
+        // 解析数据行（先裁剪尾部仅有格式没有数据的空白行，
+        // 否则会放大进度总数并对每个空行产生解析警告）
+        let data_rows = Self::trim_trailing_blank_rows(&rows[header_idx + 1..]); // 跳过表头
+        let trimmed = rows.len() - (header_idx + 1) - data_rows.len();
+        if data_rows.is_empty() {
+            return Err(AuditError::excel_error(
+                "Excel文件只有表头没有数据行，请确认导出时是否包含了数据区域"
+            ));
+        }
+        if trimmed > 0 {
+            info!("📋 已裁剪尾部 {} 行空白行，有效数据区域: 第{}-{}行",
+                trimmed, header_idx + 2, header_idx + 1 + data_rows.len());
+        }
+        info!("开始解析 {} 行数据", data_rows.len());
+        
+        // Python来源: src/utils/data_processor.py:203-228 批量处理交易的循环逻辑
+        // 逐行解析相互独立，大文件走rayon并行；结果按原行序收集，
+        // 合计行与解析失败的日志在下面的顺序遍历中输出，保持输出确定
+        let row_results = crate::optimizations::parallel::indexed_map(data_rows, |_, row| {
+            if Self::is_trailing_total_row(row, &column_indices) {
+                None
+            } else {
+                Some(self.parse_transaction_row(row, &column_indices))
+            }
+        });
+        for (row_idx, result) in row_results.into_iter().enumerate() {
+            let excel_row = header_idx + row_idx + 2; // 1开始的Excel行号
+            match result {
+                None => info!("📋 检测到第{excel_row}行为合计行，已跳过"),
+                Some(Ok(transaction)) => transactions.push(transaction),
+                Some(Err(e)) => {
+                    // 继续处理其他行，不中断整个流程
+                    warn!("解析第{excel_row}行数据失败: {e}");
+                }
+            }
+
+            // Python来源: src/utils/data_processor.py:221-222 进度报告逻辑
+            // 定期报告进度
+            if (row_idx + 1) % 1000 == 0 {
+                info!("⏳ 处理进度: {}/{} ({:.1}%)",
+                    row_idx + 1,
+                    data_rows.len(),
+                    (row_idx + 1) as f64 / data_rows.len() as f64 * 100.0
+                );
+            }
+        }
+        
+        // 配置了流水号列时优先按流水号整体排序（银行落库顺序的直接凭据）
+        self.sort_by_sequence_number(&mut transactions);
+
+        // Python来源: src/utils/data_processor.py:80 `audit_logger.info("数据预处理完成")`
+        info!("✅ Excel数据读取完成，共解析 {} 条交易记录", transactions.len());
+        Ok(transactions)
+    }
+
+    /// 按流水号列整体重排交易记录
+    ///
+    /// 流水号是银行落库顺序的直接凭据，排序可靠性高于时间戳+余额链修复。
+    /// 仅当配置了流水号列且全部记录都读到流水号时才重排；有缺失时保持
+    /// 文件原序并退回时间戳+同时间重排修复路径
+    fn sort_by_sequence_number(&self, transactions: &mut [Transaction]) {
+        if self.config.excel_columns.sequence_number_column.is_none() || transactions.is_empty() {
+            return;
+        }
+        let missing = transactions.iter()
+            .filter(|tx| tx.sequence_number.is_none())
+            .count();
+        if missing > 0 {
+            warn!("⚠️ {missing} 条记录未读到流水号，保持文件原序（退回时间戳+重排修复）");
+            return;
+        }
+        if transactions.windows(2).all(|pair| pair[0].sequence_sort_key() <= pair[1].sequence_sort_key()) {
+            return; // 文件本身已按流水号有序
+        }
+        transactions.sort_by_cached_key(Transaction::sequence_sort_key);
+        info!("📋 已按流水号列重排 {} 条交易记录", transactions.len());
+    }
+
+
+    /// 表头上方最多容忍的标题说明行数
+    const MAX_TITLE_ROWS: usize = 5;
+    
+    /// 在工作表前几行中定位表头行
+    /// 
+    /// 银行导出的流水常在表头上方带1-2行合并单元格的标题说明行，
+    /// 直接按第一行找列会报令人困惑的"缺少必需的列"。这里向下最多
+    /// 探测[`Self::MAX_TITLE_ROWS`]行，找到表头即跳过说明行并提示
+    fn locate_header_row(
+        &self,
+        rows: &[&[calamine::Data]]
+    ) -> AuditResult<(usize, ColumnIndices)> {
+        let mut first_error = None;
+        for (idx, row) in rows.iter().take(Self::MAX_TITLE_ROWS + 1).enumerate() {
+            match self.find_column_indices(row) {
+                Ok(indices) => {
+                    if idx > 0 {
+                        info!("📋 检测到第1-{idx}行为标题说明行，已跳过，表头位于第{}行", idx + 1);
+                    }
+                    return Ok((idx, indices));
+                }
+                Err(e) => {
+                    // 报首行的缺列错误，比报第N行的更贴近用户看到的表格
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+        Err(first_error.unwrap_or_else(|| AuditError::excel_error("无法获取表头行")))
+    }
+    
+    /// 判断整行是否为空白（全部为空单元格或仅含空白字符的字符串）
+    fn is_blank_row(row: &[calamine::Data]) -> bool {
+        row.iter().all(|cell| match cell {
+            calamine::Data::Empty => true,
+            calamine::Data::String(s) => s.trim().is_empty(),
+            _ => false,
+        })
+    }
+    
+    /// 裁剪尾部空白行
+    /// 
+    /// 带格式的空行会被calamine计入数据区域，银行导出或人工编辑的
+    /// 文件尾部常有数千行这种"占位行"，统一在解析前裁掉
+    fn trim_trailing_blank_rows<'a>(rows: &'a [&'a [calamine::Data]]) -> &'a [&'a [calamine::Data]] {
+        let len = rows.iter()
+            .rposition(|row| !Self::is_blank_row(row))
+            .map_or(0, |idx| idx + 1);
+        &rows[..len]
+    }
+    
+    /// 判断是否为表尾合计行
+    /// 
+    /// 银行导出的流水末尾常见"合计/总计/小计"行，金额列有值但交易日期
+    /// 为空，按数据行解析只会留下一条令人困惑的日期解析失败警告
+    fn is_trailing_total_row(row: &[calamine::Data], indices: &ColumnIndices) -> bool {
+        let has_marker = row.iter().any(|cell| {
+            cell.as_string().is_some_and(|text| {
+                let text = text.trim().to_string();
+                ["合计", "总计", "小计"].iter().any(|marker| text.starts_with(marker))
+            })
+        });
+        if !has_marker {
+            return false;
+        }
+        
+        // 合计行没有可解析的交易日期；正文中带"合计"字样但日期完整的行不跳过
+        let date_cell = indices.transaction_date
+            .and_then(|idx| row.get(idx))
+            .unwrap_or(&calamine::Data::Empty);
+        TimeProcessor::parse_excel_date(date_cell).is_err()
+    }
+    
+    /// 查找列索引
+    /// Python来源: src/utils/data_processor.py:89-103 的列名检查逻辑
+    fn find_column_indices(
+        &self,
+        header_row: &[calamine::Data]
+    ) -> AuditResult<ColumnIndices> {
+        let indices = self.match_column_indices(header_row);
+        // 验证必需列是否都找到了
+        indices.validate()?;
+        Ok(indices)
+    }
+
+    /// 按配置匹配表头行中的各列（不验证必需列，供预检报告缺列情况）
+    fn match_column_indices(&self, header_row: &[calamine::Data]) -> ColumnIndices {
+        let mut indices = ColumnIndices::new();
+        
+        // Python来源: src/utils/data_processor.py:94-96 遍历列名并记录索引
+        // 每列按主列名+别名匹配（别名来自列映射档案，支持不同银行的表头写法）
+        let columns = &self.config.excel_columns;
+        use crate::data_models::ExcelColumnConfig;
+        for (idx, cell) in header_row.iter().enumerate() {
+            if let Some(column_name) = cell.as_string() {
+                match column_name {
+                    name if ExcelColumnConfig::column_matches(&columns.transaction_date_column, &columns.transaction_date_aliases, name.as_str()) => {
+                        indices.transaction_date = Some(idx);
+                    }
+                    name if ExcelColumnConfig::column_matches(&columns.transaction_time_column, &columns.transaction_time_aliases, name.as_str()) => {
+                        indices.transaction_time = Some(idx);
+                    }
+                    name if ExcelColumnConfig::column_matches(&columns.income_amount_column, &columns.income_amount_aliases, name.as_str()) => {
+                        indices.income_amount = Some(idx);
+                    }
+                    name if ExcelColumnConfig::column_matches(&columns.expense_amount_column, &columns.expense_amount_aliases, name.as_str()) => {
+                        indices.expense_amount = Some(idx);
+                    }
+                    name if ExcelColumnConfig::column_matches(&columns.balance_column, &columns.balance_aliases, name.as_str()) => {
+                        indices.balance = Some(idx);
+                    }
+                    name if ExcelColumnConfig::column_matches(&columns.fund_attribute_column, &columns.fund_attribute_aliases, name.as_str()) => {
+                        indices.fund_attribute = Some(idx);
+                    }
+                    // 流水号列（可选）：独立的流水号列配置或同时间排序仲裁列名，命中任一即读入
+                    name if columns.sequence_number_column.as_deref().is_some_and(
+                            |primary| ExcelColumnConfig::column_matches(primary, &columns.sequence_number_aliases, name.as_str()))
+                        || self.config.same_time_ordering.tiebreak_column.as_deref() == Some(name.as_str()) => {
+                        indices.sequence_number = Some(idx);
+                    }
+                    // 币种列（可选）：配置了币种列名才查找
+                    name if columns.currency_column.as_deref().is_some_and(
+                            |primary| ExcelColumnConfig::column_matches(primary, &columns.currency_aliases, name.as_str())) => {
+                        indices.currency = Some(idx);
+                    }
+                    _ => {} // 忽略其他列
+                }
+            }
+        }
+
+        indices
+    }
+    
+    /// 解析单行交易数据
+    /// Python来源: src/utils/data_processor.py:162-201 处理单行交易逻辑
+    fn parse_transaction_row(
+        &self,
+        row: &[calamine::Data],
+        indices: &ColumnIndices
+    ) -> AuditResult<Transaction> {
+        // Python来源: src/utils/data_processor.py:51 `df['交易日期'] = pd.to_datetime(df['交易日期'])`
+        // 解析交易日期
+        let transaction_date = self.parse_date(
+            row.get(indices.transaction_date.unwrap()).unwrap_or(&calamine::Data::Empty)
+        )?;
+        
+        // Python来源: src/utils/data_processor.py:54 `df['交易时间_格式化'] = df['交易时间'].apply(...)`
+        // 解析交易时间
+        let transaction_time = self.parse_time_string(
+            row.get(indices.transaction_time.unwrap()).unwrap_or(&calamine::Data::Empty)
+        )?;
+        
+        // Python来源: src/utils/data_processor.py:175-176 提取交易金额
+        // 解析金额字段
+        let income_amount = self.parse_decimal(
+            row.get(indices.income_amount.unwrap()).unwrap_or(&calamine::Data::Empty)
+        ).unwrap_or(Decimal::ZERO);
+        
+        let expense_amount = self.parse_decimal(
+            row.get(indices.expense_amount.unwrap()).unwrap_or(&calamine::Data::Empty)
+        ).unwrap_or(Decimal::ZERO);
+        
+        let balance = self.parse_decimal(
+            row.get(indices.balance.unwrap()).unwrap_or(&calamine::Data::Empty)
+        )?;
+        
+        // Python来源: src/utils/data_processor.py:177 `资金属性 = str(row['资金属性'])`
+        // 解析资金属性
+        let fund_attribute = row.get(indices.fund_attribute.unwrap())
+            .and_then(calamine::DataType::as_string)
+            .unwrap_or_default();
+        
+        if fund_attribute.is_empty() {
+            return Err(AuditError::validation_error("资金属性不能为空"));
+        }
+        
+        // 创建完整的时间戳（日期+时间）
+        let complete_timestamp = TimeProcessor::create_complete_timestamp(transaction_date, &transaction_time);
+
+        let mut transaction = Transaction::new(
+            complete_timestamp,
+            transaction_time,
+            income_amount,
+            expense_amount,
+            balance,
+            fund_attribute,
+        );
+
+        // 读入同时间排序仲裁列原文（未配置仲裁列时无此索引）
+        if let Some(idx) = indices.sequence_number {
+            transaction.sequence_number = row.get(idx)
+                .and_then(calamine::DataType::as_string)
+                .filter(|value| !value.trim().is_empty());
+        }
+
+        // 读入币种代码（未配置币种列时无此索引；统一转大写便于汇率匹配）
+        if let Some(idx) = indices.currency {
+            transaction.currency = row.get(idx)
+                .and_then(calamine::DataType::as_string)
+                .map(|value| value.trim().to_uppercase())
+                .filter(|value| !value.is_empty());
+        }
+
+        Ok(transaction)
+    }
+    
+    /// 解析日期
+    /// Python来源: 
+    /// - `src/utils/data_processor.py:51` `df['交易日期'] = pd.to_datetime(df['交易日期'])`
+    /// - src/utils/validators.py:156 `pd.to_datetime(date_value)`
+    /// - 合并多处重复的日期解析逻辑
+    fn parse_date(&self, cell: &calamine::Data) -> AuditResult<NaiveDateTime> {
+        // 使用TimeProcessor统一处理，消除重复代码
+        TimeProcessor::parse_excel_date(cell)
+    }
+    
+    /// 解析时间字符串\
+    /// Python来源:
+    /// - `src/models/flow_analyzer.py:91` `解析交易时间(self, 时间值)`
+    /// - `src/utils/data_processor.py:54` 处理交易时间的逻辑
+    fn parse_time_string(&self, cell: &calamine::Data) -> AuditResult<String> {
+        // 使用TimeProcessor统一处理，消除重复代码
+        Ok(TimeProcessor::parse_transaction_time(cell))
+    }
+    
+    /// 解析十进制数
+    /// Python来源: 
+    /// - src/utils/data_processor.py:175-176 `float(row['交易收入金额'])` 和 `float(row['交易支出金额'])`
+    /// - src/utils/validators.py:128,140 `amount = float(收入金额)` 和 `amount = float(支出金额)`
+    /// - 合并所有数值解析的重复逻辑
+    fn parse_decimal(&self, cell: &calamine::Data) -> AuditResult<Decimal> {
+        match cell {
+            // Python来源: Excel中的数值类型，对应pandas读取的float类型
+            calamine::Data::Float(f) => {
+                Decimal::from_f64_retain(*f)
+                    .ok_or_else(|| AuditError::calculation_error(format!("无法转换数值: {f}")))
+            }
+            calamine::Data::Int(i) => {
+                Ok(Decimal::from(*i))
+            }
+            // Python来源: src/utils/validators.py:263 处理字符串数值的逻辑
+            calamine::Data::String(s) => {
+                // 移除千位分隔符和其他非数字字符（Python中pandas会自动处理）
+                let cleaned = s.chars()
+                    .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+                    .collect::<String>();
+                
+                if cleaned.is_empty() {
+                    return Ok(Decimal::ZERO);
+                }
+                
+                cleaned.parse::<Decimal>()
+                    .map_err(|_| AuditError::calculation_error(format!("无法解析数值: {s}")))
+            }
+            // Python来源: pandas中的NaN值处理，在Python中pd.isna()检查后返回0.0
+            calamine::Data::Empty => Ok(Decimal::ZERO),
+            _ => Err(AuditError::calculation_error("不支持的数值格式"))
+        }
+    }
+    
+    /// 超过该行数后切换为大结果集导出模式
+    ///
+    /// 请求.csv输出时走流式CSV（逐行写入，内存占用恒定）；
+    /// 请求.xlsx输出时走分表批量写入（见[`Self::export_analysis_results_sheeted`]）。
+    pub const STREAMING_ROW_THRESHOLD: usize = 100_000;
+
+    /// Excel单工作表的最大行数（含表头，xlsx格式硬上限）
+    pub const EXCEL_SHEET_MAX_ROWS: usize = 1_048_576;
+
+    /// 大结果集导出时每写完一批报告一次进度
+    const EXPORT_PROGRESS_BATCH: usize = 10_000;
+
+    /// 导出分析结果（自动选择导出模式）
+    ///
+    /// 行数低于 [`Self::STREAMING_ROW_THRESHOLD`] 时导出单表Excel；
+    /// 超过阈值时，按请求的扩展名选择流式CSV或分表批量Excel。
+    /// 返回实际写入的文件路径。
+    pub fn export_analysis_results<P: AsRef<Path>>(
+        &self,
+        transactions: &[Transaction],
+        summary: &AuditSummary,
+        output_path: P,
+    ) -> AuditResult<std::path::PathBuf> {
+        self.export_analysis_results_with_progress(transactions, summary, output_path, None)
+    }
+
+    /// 导出分析结果并报告进度
+    ///
+    /// `progress`以（已写行数, 总行数）调用：单表Excel与CSV模式在完成时
+    /// 报告一次，分表批量模式每批报告一次，百万行导出不再是进度黑洞
+    pub fn export_analysis_results_with_progress<P: AsRef<Path>>(
+        &self,
+        transactions: &[Transaction],
+        summary: &AuditSummary,
+        output_path: P,
+        progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    ) -> AuditResult<std::path::PathBuf> {
+        let path = output_path.as_ref();
+
+        if transactions.len() >= Self::STREAMING_ROW_THRESHOLD {
+            // 请求CSV时流式写出；否则分表批量写入Excel
+            if path.extension().and_then(|e| e.to_str()) == Some("csv") {
+                self.export_analysis_results_csv(transactions, summary, path)?;
+                if let Some(report) = progress {
+                    report(transactions.len(), transactions.len());
+                }
+                return Ok(path.to_path_buf());
+            }
+            return self.export_analysis_results_sheeted(transactions, summary, path, progress);
+        }
+
+        // Python来源: src/utils/data_processor.py:331 `audit_logger.info(f"分析结果已保存到: {output_file}")`
+        info!("开始导出分析结果到: {}", path.display());
+
+        // 使用rust_xlsxwriter创建真正的Excel文件
+        let mut workbook = Workbook::new();
+        
+        // 创建主工作表
+        let worksheet = workbook.add_worksheet();
+        
+        // 设置格式
+        let header_format = Format::new()
+            .set_bold()
+            .set_background_color(Color::Blue)
+            .set_font_color(Color::White);
+        
+        let number_format = Format::new()
+            .set_num_format("#,##0.00");
+        
+        let date_format = Format::new()
+            .set_num_format("yyyy-mm-dd");
+        
+        // 写入表头和数据
+        self.write_excel_headers(worksheet, &header_format)?;
+        self.write_excel_data(worksheet, transactions, &number_format, &date_format)?;
+        
+        // 创建摘要工作表
+        self.write_summary_worksheet(&mut workbook, summary)?;
+        
+        // 验证修复报告工作表（如有修复或错误）
+        self.write_validation_report_worksheet(&mut workbook)?;
+
+        // 风险标记汇总工作表（如有可疑模式发现）
+        self.write_anomaly_summary_worksheet(&mut workbook)?;
+
+        // 按期汇总工作表（如已附带按期汇总报告）
+        self.write_period_summary_worksheet(&mut workbook)?;
+
+        // 场外资金池工作表（如已附带记录）
+        self.write_offsite_pool_worksheet(&mut workbook)?;
+
+        // 仪表盘工作表（如已附带按期汇总报告）
+        self.write_dashboard_worksheet(&mut workbook, summary)?;
+
+        // 保存文件
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存Excel文件失败: {e}")))
+        })?;
+
+        if let Some(report) = progress {
+            report(transactions.len(), transactions.len());
+        }
+        info!("✅ Excel分析结果导出完成");
+        Ok(path.to_path_buf())
+    }
+
+    /// 分表批量导出大结果集到Excel
+    ///
+    /// 超过单表行数上限时自动拆分为 `分析结果_1/_2/...` 多个工作表；
+    /// 每批[`Self::EXPORT_PROGRESS_BATCH`]行报告一次进度。
+    /// 注意：xlsxwriter保存前整簿驻留内存，百万行约占数百MB，
+    /// 内存紧张的环境应改用.csv输出
+    pub fn export_analysis_results_sheeted<P: AsRef<Path>>(
+        &self,
+        transactions: &[Transaction],
+        summary: &AuditSummary,
+        output_path: P,
+        progress: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    ) -> AuditResult<std::path::PathBuf> {
+        let path = output_path.as_ref();
+        let total = transactions.len();
+        let rows_per_sheet = Self::EXCEL_SHEET_MAX_ROWS - 1; // 每表留一行表头
+        let sheet_count = total.div_ceil(rows_per_sheet).max(1);
+        info!("开始分表导出分析结果到: {}（{} 行，拆分为 {} 个工作表）",
+            path.display(), total, sheet_count);
+
+        let mut workbook = Workbook::new();
+        let header_format = Format::new()
+            .set_bold()
+            .set_background_color(Color::Blue)
+            .set_font_color(Color::White);
+
+        let mut rows_written = 0usize;
+        let mut previous_gap = Decimal::ZERO; // 可疑行高亮的缺口对比跨分表延续
+        for (sheet_index, chunk) in transactions.chunks(rows_per_sheet).enumerate() {
+            let worksheet = workbook.add_worksheet()
+                .set_name(format!("分析结果_{}", sheet_index + 1))?;
+            self.write_excel_headers(worksheet, &header_format)?;
+
+            for (row_index, tx) in chunk.iter().enumerate() {
+                let row = (row_index + 1) as u32;
+                self.write_transaction_row(worksheet, row, tx)?;
+                if let Some(format) = self.suspicious_row_format(tx, &mut previous_gap) {
+                    worksheet.set_row_format(row, &format)?;
+                }
+                rows_written += 1;
+                if rows_written.is_multiple_of(Self::EXPORT_PROGRESS_BATCH) {
+                    if let Some(report) = progress {
+                        report(rows_written, total);
+                    }
+                    debug!("分表Excel写入进度: {rows_written}/{total}");
+                }
+            }
+            self.apply_header_styling(worksheet, chunk.len() as u32)?;
+        }
+
+        self.write_summary_worksheet(&mut workbook, summary)?;
+        self.write_validation_report_worksheet(&mut workbook)?;
+        self.write_anomaly_summary_worksheet(&mut workbook)?;
+
+        // 按期汇总工作表（如已附带按期汇总报告）
+        self.write_period_summary_worksheet(&mut workbook)?;
+
+        // 场外资金池工作表（如已附带记录）
+        self.write_offsite_pool_worksheet(&mut workbook)?;
+
+        // 仪表盘工作表（如已附带按期汇总报告）
+        self.write_dashboard_worksheet(&mut workbook, summary)?;
+
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存Excel文件失败: {e}")))
+        })?;
+
+        if let Some(report) = progress {
+            report(total, total);
+        }
+        info!("✅ 分表Excel导出完成: {total} 行 / {sheet_count} 个工作表");
+        Ok(path.to_path_buf())
+    }
+
+    /// 流式CSV导出分析结果（恒定内存占用）
+    ///
+    /// 逐行写入并定期刷新缓冲区，不在内存中累积整个结果集。
+    /// 摘要数据附加在数据区之后，以空行分隔。
+    pub fn export_analysis_results_csv<P: AsRef<Path>>(
+        &self,
+        transactions: &[Transaction],
+        summary: &AuditSummary,
+        output_path: P,
+    ) -> AuditResult<()> {
+        use std::io::Write;
+
+        let path = output_path.as_ref();
+        info!("开始流式导出分析结果到: {}", path.display());
+
+        let file = self.with_io_retry("创建CSV文件", || {
+            std::fs::File::create(path)
+                .map_err(|e| AuditError::excel_error(format!("创建CSV文件失败: {e}")))
+        })?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        // UTF-8 BOM，保证Excel打开中文表头不乱码
+        writer.write_all(b"\xEF\xBB\xBF")
+            .map_err(|e| AuditError::excel_error(format!("写入CSV文件失败: {e}")))?;
+
+        let headers = crate::utils::i18n::header_labels(&[
+            "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性",
+            "个人资金占比", "公司资金占比", "行为性质", "累计挪用", "累计垫付",
+            "累计已归还公司本金", "累计已归还个人本金", "总计个人应分配利润",
+            "总计公司应分配利润", "个人余额", "公司余额", "总余额", "资金缺口",
+            "资金来源明细", "判定依据", "风险标记"
+        ], self.config.excel_columns.header_language);
+        writeln!(writer, "{}", headers.join(","))
+            .map_err(|e| AuditError::excel_error(format!("写入CSV表头失败: {e}")))?;
+
+        for (row_idx, tx) in transactions.iter().enumerate() {
+            let datetime_str = if tx.transaction_time.contains('/') || tx.transaction_time.contains('-') {
+                tx.transaction_time.clone()
+            } else {
+                tx.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string()
+            };
+            let behavior = tx.behavior_nature.as_deref().unwrap_or("");
+            let total_balance = tx.personal_balance.unwrap_or(Decimal::ZERO)
+                + tx.company_balance.unwrap_or(Decimal::ZERO);
+
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                Self::csv_escape(&datetime_str),
+                tx.income_amount,
+                tx.expense_amount,
+                tx.balance,
+                Self::csv_escape(&tx.fund_attribute),
+                tx.personal_ratio.unwrap_or(Decimal::ZERO).round_dp(self.config.numeric.ratio_decimal_places),
+                tx.company_ratio.unwrap_or(Decimal::ZERO).round_dp(self.config.numeric.ratio_decimal_places),
+                Self::csv_escape(behavior),
+                tx.cumulative_misappropriation.unwrap_or(Decimal::ZERO),
+                tx.cumulative_advance.unwrap_or(Decimal::ZERO),
+                tx.cumulative_company_principal_returned.unwrap_or(Decimal::ZERO),
+                tx.cumulative_personal_principal_returned.unwrap_or(Decimal::ZERO),
+                tx.cumulative_personal_profit.unwrap_or(Decimal::ZERO),
+                tx.cumulative_company_profit.unwrap_or(Decimal::ZERO),
+                tx.personal_balance.unwrap_or(Decimal::ZERO),
+                tx.company_balance.unwrap_or(Decimal::ZERO),
+                total_balance,
+                tx.funding_gap.unwrap_or(Decimal::ZERO),
+                Self::csv_escape(tx.fund_source_breakdown.as_deref().unwrap_or("")),
+                Self::csv_escape(tx.behavior_explanation.as_deref().unwrap_or("")),
+                Self::csv_escape(tx.risk_flags.as_deref().unwrap_or("")),
+            ).map_err(|e| AuditError::excel_error(format!("写入CSV数据失败: {e}")))?;
+
+            if (row_idx + 1) % 10000 == 0 {
+                debug!("CSV写入进度: {}/{}", row_idx + 1, transactions.len());
+            }
+        }
+
+        // 摘要区（与Excel导出的摘要工作表字段一致）
+        let summary_items = [
+            ("个人余额", summary.personal_balance),
+            ("公司余额", summary.company_balance),
+            ("总余额", summary.total_balance),
+            ("累计挪用金额", summary.total_misappropriation),
+            ("累计垫付金额", summary.total_advance_payment),
+            ("累计利息收入", summary.total_interest_income),
+            ("累计手续费支出", summary.total_fee_expense),
+            ("累计归还公司本金", summary.total_company_principal_returned),
+            ("累计归还个人本金", summary.total_personal_principal_returned),
+            ("总计个人利润", summary.total_personal_profit),
+            ("总计公司利润", summary.total_company_profit),
+            ("资金缺口", summary.funding_gap),
+        ];
+        writeln!(writer).map_err(|e| AuditError::excel_error(format!("写入CSV文件失败: {e}")))?;
+        writeln!(writer, "指标,数值")
+            .map_err(|e| AuditError::excel_error(format!("写入CSV摘要失败: {e}")))?;
+        for (name, value) in &summary_items {
+            writeln!(writer, "{name},{value}")
+                .map_err(|e| AuditError::excel_error(format!("写入CSV摘要失败: {e}")))?;
+        }
+
+        writer.flush()
+            .map_err(|e| AuditError::excel_error(format!("刷新CSV缓冲区失败: {e}")))?;
+
+        info!("✅ CSV分析结果流式导出完成，共 {} 行", transactions.len());
+        Ok(())
+    }
+
+    /// 导出算法对比报告
+    /// 
+    /// 将两种算法在同一份流水上的结果并排写入一个工作簿：
+    /// "逐行对比"表给出两侧个人占比与行为性质及分歧标记，
+    /// "摘要对比"表给出摘要指标的差异。供CLI `compare --output`与GUI调用
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_comparison_results<P: AsRef<Path>>(
+        &self,
+        output_path: P,
+        left_name: &str,
+        left_summary: &AuditSummary,
+        left_transactions: &[Transaction],
+        right_name: &str,
+        right_summary: &AuditSummary,
+        right_transactions: &[Transaction],
+    ) -> AuditResult<()> {
+        let path = output_path.as_ref();
+        info!("开始导出算法对比报告到: {}", path.display());
+        
+        if left_transactions.len() != right_transactions.len() {
+            warn!("两侧结果行数不一致: {} vs {}，仅对比公共前缀",
+                left_transactions.len(), right_transactions.len());
+        }
+        
+        let mut workbook = Workbook::new();
+        
+        // 逐行对比表
+        let worksheet = workbook.add_worksheet().set_name("逐行对比")
+            .map_err(|e| AuditError::excel_error(format!("创建对比工作表失败: {e}")))?;
+        
+        let headers = [
+            "行号".to_string(),
+            "交易时间".to_string(),
+            "交易收入金额".to_string(),
+            "交易支出金额".to_string(),
+            "余额".to_string(),
+            "资金属性".to_string(),
+            format!("个人占比({left_name})"),
+            format!("个人占比({right_name})"),
+            "占比差异".to_string(),
+            format!("行为性质({left_name})"),
+            format!("行为性质({right_name})"),
+            "行为分歧".to_string(),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)
+                .map_err(|e| AuditError::excel_error(format!("写入对比表头失败: {e}")))?;
+        }
+        
+        let ratio_places = self.config.numeric.ratio_decimal_places;
+        let mut divergent_rows = 0usize;
+        for (row_idx, (left_tx, right_tx)) in left_transactions.iter().zip(right_transactions).enumerate() {
+            let row = (row_idx + 1) as u32;
+            
+            let left_ratio = left_tx.personal_ratio.unwrap_or(Decimal::ZERO).round_dp(ratio_places);
+            let right_ratio = right_tx.personal_ratio.unwrap_or(Decimal::ZERO).round_dp(ratio_places);
+            let left_behavior = left_tx.behavior_nature.as_deref().unwrap_or("");
+            let right_behavior = right_tx.behavior_nature.as_deref().unwrap_or("");
+            let diverged = left_behavior != right_behavior;
+            if diverged {
+                divergent_rows += 1;
+            }
+            
+            worksheet.write_number(row, 0, (row_idx + 1) as f64)?;
+            worksheet.write_string(row, 1, left_tx.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string())?;
+            self.write_amount(worksheet, row, 2, left_tx.income_amount)?;
+            self.write_amount(worksheet, row, 3, left_tx.expense_amount)?;
+            self.write_amount(worksheet, row, 4, left_tx.balance)?;
+            worksheet.write_string(row, 5, &left_tx.fund_attribute)?;
+            worksheet.write_number(row, 6, left_ratio.to_f64().unwrap_or(0.0))?;
+            worksheet.write_number(row, 7, right_ratio.to_f64().unwrap_or(0.0))?;
+            worksheet.write_number(row, 8, (right_ratio - left_ratio).to_f64().unwrap_or(0.0))?;
+            worksheet.write_string(row, 9, left_behavior)?;
+            worksheet.write_string(row, 10, right_behavior)?;
+            worksheet.write_string(row, 11, if diverged { "⚠️" } else { "" })?;
+        }
+        
+        // 摘要对比表
+        let summary_sheet = workbook.add_worksheet().set_name("摘要对比")
+            .map_err(|e| AuditError::excel_error(format!("创建摘要对比工作表失败: {e}")))?;
+        
+        summary_sheet.write_string(0, 0, "指标")?;
+        summary_sheet.write_string(0, 1, left_name)?;
+        summary_sheet.write_string(0, 2, right_name)?;
+        summary_sheet.write_string(0, 3, "差异")?;
+        
+        let metric_pairs = [
+            ("个人余额", left_summary.personal_balance, right_summary.personal_balance),
+            ("公司余额", left_summary.company_balance, right_summary.company_balance),
+            ("总余额", left_summary.total_balance, right_summary.total_balance),
+            ("累计挪用金额", left_summary.total_misappropriation, right_summary.total_misappropriation),
+            ("累计垫付金额", left_summary.total_advance_payment, right_summary.total_advance_payment),
+            ("累计利息收入", left_summary.total_interest_income, right_summary.total_interest_income),
+            ("累计手续费支出", left_summary.total_fee_expense, right_summary.total_fee_expense),
+            ("累计归还公司本金", left_summary.total_company_principal_returned, right_summary.total_company_principal_returned),
+            ("累计归还个人本金", left_summary.total_personal_principal_returned, right_summary.total_personal_principal_returned),
+            ("总计个人利润", left_summary.total_personal_profit, right_summary.total_personal_profit),
+            ("总计公司利润", left_summary.total_company_profit, right_summary.total_company_profit),
+            ("资金缺口", left_summary.funding_gap, right_summary.funding_gap),
+        ];
+        for (idx, (name, left_value, right_value)) in metric_pairs.iter().enumerate() {
+            let row = (idx + 1) as u32;
+            summary_sheet.write_string(row, 0, *name)?;
+            self.write_amount(summary_sheet, row, 1, *left_value)?;
+            self.write_amount(summary_sheet, row, 2, *right_value)?;
+            self.write_amount(summary_sheet, row, 3, *right_value - *left_value)?;
+        }
+        let divergence_row = (metric_pairs.len() + 2) as u32;
+        summary_sheet.write_string(divergence_row, 0, "行为分歧行数")?;
+        summary_sheet.write_number(divergence_row, 1, divergent_rows as f64)?;
+        
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存对比报告失败: {e}")))
+        })?;
+        
+        info!("✅ 算法对比报告导出完成，行为分歧 {divergent_rows} 行");
+        Ok(())
+    }
+    
+    /// CSV字段转义（包含逗号、引号或换行时加引号包裹）
+    fn csv_escape(value: &str) -> String {
+        if value.contains(',') || value.contains('"') || value.contains('\n') {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// 将交易集写成输入格式的流水工作簿（仅基础六列，列名取当前配置的主列名）
+    ///
+    /// 供基准与测试构造可被[`Self::read_transactions`]读回的合成输入文件
+    pub fn write_input_workbook<P: AsRef<Path>>(
+        &self,
+        transactions: &[Transaction],
+        output_path: P,
+    ) -> AuditResult<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        let columns = &self.config.excel_columns;
+        let headers = [
+            columns.transaction_date_column.as_str(),
+            columns.transaction_time_column.as_str(),
+            columns.income_amount_column.as_str(),
+            columns.expense_amount_column.as_str(),
+            columns.balance_column.as_str(),
+            columns.fund_attribute_column.as_str(),
+        ];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header)
+                .map_err(|e| AuditError::excel_error(format!("写入表头失败: {e}")))?;
+        }
+
+        for (idx, tx) in transactions.iter().enumerate() {
+            let row = idx as u32 + 1;
+            worksheet.write_string(row, 0, tx.transaction_date.format("%Y-%m-%d").to_string())
+                .map_err(|e| AuditError::excel_error(format!("写入交易日期失败: {e}")))?;
+            worksheet.write_string(row, 1, &tx.transaction_time)
+                .map_err(|e| AuditError::excel_error(format!("写入交易时间失败: {e}")))?;
+            self.write_amount(worksheet, row, 2, tx.income_amount)?;
+            self.write_amount(worksheet, row, 3, tx.expense_amount)?;
+            self.write_amount(worksheet, row, 4, tx.balance)?;
+            worksheet.write_string(row, 5, &tx.fund_attribute)
+                .map_err(|e| AuditError::excel_error(format!("写入资金属性失败: {e}")))?;
+        }
+
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(output_path.as_ref())
+                .map_err(|e| AuditError::excel_error(format!("保存Excel文件失败: {e}")))
+        })
+    }
+
+    /// 写入Excel表头
+    /// Python来源: `src/utils/data_processor.py` `结果DataFrame的列名`
+    fn write_excel_headers(&self, worksheet: &mut Worksheet, _format: &Format) -> AuditResult<()> {
+        let headers = crate::utils::i18n::header_labels(&[
+            "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性",
+            "个人资金占比", "公司资金占比", "行为性质", "累计挪用", "累计垫付",
+            "累计已归还公司本金", "累计已归还个人本金", "总计个人应分配利润", 
+            "总计公司应分配利润", "个人余额", "公司余额", "总余额", "资金缺口",
+            "资金来源明细", "判定依据", "风险标记"
+        ], self.config.excel_columns.header_language);
+        
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)
+                .map_err(|e| AuditError::excel_error(format!("写入表头失败: {e}")))?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 判断Decimal金额能否无损经f64写入
+    ///
+    /// f64的安全整数上限是2^53，十进制系数超过该范围后，
+    /// `to_f64`会丢失分位精度（数十亿级流水的累计金额可能触及）。
+    fn decimal_fits_f64(value: Decimal) -> bool {
+        value.mantissa().unsigned_abs() <= (1u128 << 53)
+    }
+
+    /// 写入金额单元格
+    ///
+    /// 默认按f64数值写入；启用`numeric.precision_safe_export`后，
+    /// 超出f64安全整数范围的金额按精确的Decimal字符串写入，保证分位不丢失。
+    fn write_amount(&self, worksheet: &mut Worksheet, row: u32, col: u16, value: Decimal) -> AuditResult<()> {
+        if self.config.numeric.precision_safe_export && !Self::decimal_fits_f64(value) {
+            worksheet.write_string(row, col, value.normalize().to_string())?;
+        } else {
+            worksheet.write_number(row, col, value.to_f64().unwrap_or(0.0))?;
+        }
+        Ok(())
+    }
+
+    /// 写入Excel数据
+    /// Python来源: src/utils/data_processor.py:203-228 逐行写入交易数据
+    fn write_excel_data(
+        &self,
+        worksheet: &mut Worksheet,
+        transactions: &[Transaction],
+        _number_format: &Format,
+        _date_format: &Format,
+    ) -> AuditResult<()> {
+        let mut previous_gap = Decimal::ZERO;
+        for (row_idx, tx) in transactions.iter().enumerate() {
+            let row = (row_idx + 1) as u32; // 跳过表头行
+            self.write_transaction_row(worksheet, row, tx)?;
+            if let Some(format) = self.suspicious_row_format(tx, &mut previous_gap) {
+                worksheet.set_row_format(row, &format)?;
+            }
+
+            // 定期报告进度
+            if row.is_multiple_of(1000) {
+                debug!("Excel写入进度: {}/{}", row, transactions.len());
+            }
+        }
+        self.apply_header_styling(worksheet, transactions.len() as u32)?;
+
+        Ok(())
+    }
+
+    /// 可疑行的整行格式：挪用行红底、垫付行橙底、资金缺口扩大的行加粗
+    ///
+    /// 返回None表示该行无需高亮；缺口是否扩大与上一行比较，
+    /// 调用方负责跨行传递`previous_gap`
+    fn suspicious_row_format(&self, tx: &Transaction, previous_gap: &mut Decimal) -> Option<Format> {
+        if !self.config.excel_style.highlight_suspicious_rows {
+            return None;
+        }
+        let gap = tx.funding_gap.unwrap_or(*previous_gap);
+        let gap_increased = gap > *previous_gap;
+        *previous_gap = gap;
+
+        let behavior = tx.behavior_nature.as_deref().unwrap_or("");
+        let mut format = Format::new();
+        let mut styled = false;
+        if behavior.contains("挪用") {
+            format = format.set_background_color(Color::RGB(0x00FF_C7CE)); // 浅红
+            styled = true;
+        } else if behavior.contains("垫付") {
+            format = format.set_background_color(Color::RGB(0x00FC_D5B4)); // 浅橙
+            styled = true;
+        }
+        if gap_increased {
+            format = format.set_bold();
+            styled = true;
+        }
+        styled.then_some(format)
+    }
+
+    /// 冻结表头行并添加自动筛选（按样式配置开关）
+    fn apply_header_styling(&self, worksheet: &mut Worksheet, data_rows: u32) -> AuditResult<()> {
+        if self.config.excel_style.freeze_header_row {
+            worksheet.set_freeze_panes(1, 0)?;
+        }
+        if self.config.excel_style.header_autofilter && data_rows > 0 {
+            // 结果表共21列（0..=20，含资金来源明细/判定依据/风险标记）
+            worksheet.autofilter(0, 0, data_rows, 20)?;
+        }
+        Ok(())
+    }
+    
+    /// 写入单行交易数据（单表与分表导出共用）
+    fn write_transaction_row(
+        &self,
+        worksheet: &mut Worksheet,
+        row: u32,
+        tx: &Transaction,
+    ) -> AuditResult<()> {
+        // Python来源: 对应DataFrame各列的数据写入
+        // 写入合并的日期时间（修复：使用transaction_time如果已经是完整格式，否则合并）
+        let datetime_str = if tx.transaction_time.contains('/') || tx.transaction_time.contains('-') {
+            tx.transaction_time.clone()  // 已经是完整格式
+        } else {
+            tx.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string()  // 合并格式
+        };
+        worksheet.write_string(row, 0, &datetime_str)
+            .map_err(|e| AuditError::excel_error(format!("写入日期时间失败: {e}")))?;
+        
+        self.write_amount(worksheet, row, 1, tx.income_amount)?;
+        
+        self.write_amount(worksheet, row, 2, tx.expense_amount)?;
+        
+        self.write_amount(worksheet, row, 3, tx.balance)?;
+        
+        worksheet.write_string(row, 4, &tx.fund_attribute)
+            .map_err(|e| AuditError::excel_error(format!("写入资金属性失败: {e}")))?;
+        
+        // 写入计算结果字段（修复：调整列索引，因为删除了交易日期列）
+        // 占比按配置精度舍入后再写入，避免f64转换产生0.33333333333之类的显示噪声
+        let ratio_dp = self.config.numeric.ratio_decimal_places;
+        let personal_ratio = tx.personal_ratio.unwrap_or(Decimal::ZERO).round_dp(ratio_dp);
+        let company_ratio = tx.company_ratio.unwrap_or(Decimal::ZERO).round_dp(ratio_dp);
+        // 行为性质按配置语言渲染（结构化记录可解析的部分；专有文案保持中文）
+        let behavior = crate::algorithms::shared::behavior_record::localize_behavior_text(
+            tx.behavior_nature.as_deref().unwrap_or(""),
+            self.config.excel_columns.header_language,
+        );
+
+        // 校验占比之和：个人+公司应在容差范围内等于1
+        if tx.personal_ratio.is_some() || tx.company_ratio.is_some() {
+            let ratio_sum = tx.personal_ratio.unwrap_or(Decimal::ZERO)
+                + tx.company_ratio.unwrap_or(Decimal::ZERO);
+            if (ratio_sum - Decimal::ONE).abs() > self.config.numeric.balance_tolerance {
+                warn!("第{}行资金占比之和异常: 个人{} + 公司{} = {}，应为1",
+                    row, tx.personal_ratio.unwrap_or(Decimal::ZERO),
+                    tx.company_ratio.unwrap_or(Decimal::ZERO), ratio_sum);
+            }
+        }
+        
+        self.write_amount(worksheet, row, 5, personal_ratio)?;
+        self.write_amount(worksheet, row, 6, company_ratio)?;
+        worksheet.write_string(row, 7, behavior.as_str())?;
+        
+        // 累计数据字段
+        let cum_misap = tx.cumulative_misappropriation.unwrap_or(Decimal::ZERO);
+        let cum_advance = tx.cumulative_advance.unwrap_or(Decimal::ZERO);
+        let cum_company_returned = tx.cumulative_company_principal_returned.unwrap_or(Decimal::ZERO);
+        let cum_personal_returned = tx.cumulative_personal_principal_returned.unwrap_or(Decimal::ZERO);
+        let total_personal_profit = tx.cumulative_personal_profit.unwrap_or(Decimal::ZERO);
+        let total_company_profit = tx.cumulative_company_profit.unwrap_or(Decimal::ZERO);
+        let personal_balance = tx.personal_balance.unwrap_or(Decimal::ZERO);
+        let company_balance = tx.company_balance.unwrap_or(Decimal::ZERO);
+        let total_balance = tx.personal_balance.unwrap_or(Decimal::ZERO) + tx.company_balance.unwrap_or(Decimal::ZERO);
+        let funding_gap = tx.funding_gap.unwrap_or(Decimal::ZERO);
+        
+        self.write_amount(worksheet, row, 8, cum_misap)?;
+        self.write_amount(worksheet, row, 9, cum_advance)?;
+        self.write_amount(worksheet, row, 10, cum_company_returned)?;
+        self.write_amount(worksheet, row, 11, cum_personal_returned)?;
+        self.write_amount(worksheet, row, 12, total_personal_profit)?;
+        self.write_amount(worksheet, row, 13, total_company_profit)?;
+        self.write_amount(worksheet, row, 14, personal_balance)?;
+        self.write_amount(worksheet, row, 15, company_balance)?;
+        self.write_amount(worksheet, row, 16, total_balance)?;
+        self.write_amount(worksheet, row, 17, funding_gap)?;
+        
+        // 资金来源明细（仅FIFO支出行有值）
+        if let Some(breakdown) = &tx.fund_source_breakdown {
+            worksheet.write_string(row, 18, breakdown)?;
+        }
+        
+        // 判定依据（仅挪用/垫付行有值）
+        if let Some(explanation) = &tx.behavior_explanation {
+            worksheet.write_string(row, 19, explanation)?;
+        }
+
+        // 风险标记（仅被可疑模式检测命中的行有值）
+        if let Some(flags) = &tx.risk_flags {
+            worksheet.write_string(row, 20, flags)?;
+        }
+
+        Ok(())
+    }
+    
+    /// 创建摘要工作表
+    /// Python来源: `AuditSummary的各个字段`
+    fn write_summary_worksheet(&self, workbook: &mut Workbook, summary: &AuditSummary) -> AuditResult<()> {
+        let worksheet = workbook.add_worksheet().set_name("分析摘要")?;
+        
+        let _header_format = Format::new()
+            .set_bold()
+            .set_background_color(Color::Gray);
+        
+        let _number_format = Format::new()
+            .set_num_format("#,##0.00");
+        
+        // 写入摘要数据
+        let summary_items = [
+            ("个人余额", summary.personal_balance),
+            ("公司余额", summary.company_balance),
+            ("总余额", summary.total_balance),
+            ("累计挪用金额", summary.total_misappropriation),
+            ("累计垫付金额", summary.total_advance_payment),
+            ("累计利息收入", summary.total_interest_income),
+            ("累计手续费支出", summary.total_fee_expense),
+            ("累计归还公司本金", summary.total_company_principal_returned),
+            ("累计归还个人本金", summary.total_personal_principal_returned),
+            ("总计个人利润", summary.total_personal_profit),
+            ("总计公司利润", summary.total_company_profit),
+            ("资金缺口", summary.funding_gap),
+        ];
+        
+        let language = self.config.excel_columns.header_language;
+        worksheet.write_string(0, 0, crate::utils::i18n::header_label("指标", language).as_str())?;
+        worksheet.write_string(0, 1, crate::utils::i18n::header_label("数值", language).as_str())?;
+        
+        for (row, (name, value)) in summary_items.iter().enumerate() {
+            let row = (row + 1) as u32;
+            worksheet.write_string(row, 0, *name)?;
+            self.write_amount(worksheet, row, 1, *value)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 写入结果数据
+    #[allow(dead_code)]
+    fn write_result_data(
+        &self,
+        worksheet: &mut Worksheet,
+        transactions: &[Transaction],
+        _number_format: &Format,
+        _date_format: &Format,
+    ) -> AuditResult<()> {
+        for (row, tx) in transactions.iter().enumerate() {
+            let row = (row + 1) as u32; // 跳过表头行
+            
+            // 写入原始数据
+            worksheet.write_string(row, 0, tx.transaction_date.format("%Y-%m-%d %H:%M:%S").to_string())
+                .map_err(|e| AuditError::excel_error(format!("写入日期失败: {e}")))?;
+            
+            worksheet.write_string(row, 1, &tx.transaction_time)
+                .map_err(|e| AuditError::excel_error(format!("写入时间失败: {e}")))?;
+            
+            self.write_amount(worksheet, row, 2, tx.income_amount)?;
+            
+            self.write_amount(worksheet, row, 3, tx.expense_amount)?;
+            
+            self.write_amount(worksheet, row, 4, tx.balance)?;
+            
+            worksheet.write_string(row, 5, &tx.fund_attribute)
+                .map_err(|e| AuditError::excel_error(format!("写入资金属性失败: {e}")))?;
+            
+            // 写入计算字段
+            let col_offset = 6;
+            if let Some(personal_ratio) = tx.personal_ratio {
+                self.write_amount(worksheet, row, col_offset, personal_ratio)?;
+            }
+            
+            if let Some(company_ratio) = tx.company_ratio {
+                self.write_amount(worksheet, row, col_offset + 1, company_ratio)?;
+            }
+            
+            if let Some(behavior) = &tx.behavior_nature {
+                worksheet.write_string(row, col_offset + 2, behavior)
+                    .map_err(|e| AuditError::excel_error(format!("写入行为性质失败: {e}")))?;
+            }
+            
+            // 继续写入其他计算字段...
+            if let Some(cum_misap) = tx.cumulative_misappropriation {
+                self.write_amount(worksheet, row, col_offset + 3, cum_misap)?;
+            }
+            
+            // 为了简洁，这里省略其他字段的写入代码，实际实现中需要完整写入所有字段
+            
+            // 定期报告进度
+            if row.is_multiple_of(1000) {
+                debug!("Excel写入进度: {}/{}", row, transactions.len());
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 写入摘要工作表
+    #[allow(dead_code)]
+    fn write_summary_sheet(&self, workbook: &mut Workbook, summary: &AuditSummary) -> AuditResult<()> {
+        let worksheet = workbook.add_worksheet();
+        
+        // 格式化功能暂时简化
+        // let header_format = Format::new().set_bold();
+        // let number_format = Format::new();
+        
+        // 写入摘要数据
+        let summary_items = [
+            ("个人余额", summary.personal_balance),
+            ("公司余额", summary.company_balance),
+            ("总余额", summary.total_balance),
+            ("累计挪用金额", summary.total_misappropriation),
+            ("累计垫付金额", summary.total_advance_payment),
+            ("累计利息收入", summary.total_interest_income),
+            ("累计手续费支出", summary.total_fee_expense),
+            ("累计归还公司本金", summary.total_company_principal_returned),
+            ("累计归还个人本金", summary.total_personal_principal_returned),
+            ("总计个人利润", summary.total_personal_profit),
+            ("总计公司利润", summary.total_company_profit),
+            ("资金缺口", summary.funding_gap),
+        ];
+        
+        let language = self.config.excel_columns.header_language;
+        worksheet.write_string(0, 0, crate::utils::i18n::header_label("指标", language).as_str())
+            .map_err(|e| AuditError::excel_error(format!("写入摘要表头失败: {e}")))?;
+        worksheet.write_string(0, 1, crate::utils::i18n::header_label("数值", language).as_str())
+            .map_err(|e| AuditError::excel_error(format!("写入摘要表头失败: {e}")))?;
+        
+        for (row, (name, value)) in summary_items.iter().enumerate() {
+            let row = (row + 1) as u32;
+            worksheet.write_string(row, 0, *name)
+                .map_err(|e| AuditError::excel_error(format!("写入摘要名称失败: {e}")))?;
+            self.write_amount(worksheet, row, 1, *value)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 导出资金池记录
+    pub fn export_fund_pool_records<P: AsRef<Path>>(
+        &self,
+        records: &[FundPoolRecord],
+        output_path: P,
+    ) -> AuditResult<()> {
+        let path = output_path.as_ref();
+        info!("开始导出资金池记录到: {}", path.display());
+        
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        
+        // 格式化功能暂时简化
+        // let header_format = Format::new().set_bold();
+        // let number_format = Format::new();
+        
+        // 写入表头（12个字段，与场外资金池记录格式一致）
+        let headers = crate::utils::i18n::header_labels(&[
+            "交易时间", "资金池名称", "入金", "出金", "总余额",
+            "个人余额", "公司余额", "资金占比", "行为性质", "累计申购", "累计赎回", "净盈亏"
+        ], self.config.excel_columns.header_language);
+        
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)?;
+        }
+        
+        // 按资金池分组并按时间排序
+        use std::collections::HashMap;
+        let mut grouped_records: HashMap<String, Vec<&FundPoolRecord>> = HashMap::new();
+        
+        for record in records {
+            grouped_records.entry(record.pool_name.clone())
+                           .or_default()
+                           .push(record);
+        }
+        
+        // 对每个资金池内的记录按交易时间排序
+        for pool_records in grouped_records.values_mut() {
+            pool_records.sort_by(|a, b| a.transaction_time.cmp(&b.transaction_time));
+        }
+        
+        let mut sorted_pools: Vec<_> = grouped_records.keys().collect();
+        sorted_pools.sort();
+        
+        let mut current_row = 1u32;
+        
+        // 按分组写入数据
+        for pool_name in sorted_pools {
+            let pool_records = &grouped_records[pool_name];
+            
+            // 写入该资金池的所有记录（12个字段）
+            for record in pool_records {
+                worksheet.write_string(current_row, 0, &record.transaction_time)?;
+                worksheet.write_string(current_row, 1, &record.pool_name)?;
+                self.write_amount(worksheet, current_row, 2, record.inflow)?;
+                self.write_amount(worksheet, current_row, 3, record.outflow)?;
+                self.write_amount(worksheet, current_row, 4, record.total_balance)?;
+                self.write_amount(worksheet, current_row, 5, record.personal_balance)?;
+                self.write_amount(worksheet, current_row, 6, record.company_balance)?;
+                worksheet.write_string(current_row, 7, &record.single_fund_ratio)?; // 资金占比
+                worksheet.write_string(current_row, 8, &record.behavior_nature)?;
+                self.write_amount(worksheet, current_row, 9, record.cumulative_purchase)?;
+                self.write_amount(worksheet, current_row, 10, record.cumulative_redemption)?;
+                self.write_amount(worksheet, current_row, 11, record.net_profit_loss)?;
+                current_row += 1;
+            }
+            
+            // 添加总计行（参考场外资金池的实现）
+            if let Some(last_record) = pool_records.last() {
+                let total_purchase = last_record.cumulative_purchase;
+                let total_redemption = last_record.cumulative_redemption;
+                let final_balance = last_record.total_balance;
+                let profit_loss = last_record.net_profit_loss;
+                let status = if profit_loss > rust_decimal::Decimal::ZERO {
+                    "盈利"
+                } else if profit_loss < rust_decimal::Decimal::ZERO {
+                    "亏损"
+                } else {
+                    "持平"
+                };
+                
+                // 计算个人、公司盈亏金额（参考场外资金池逻辑）
+                let (cumulative_personal_profit_loss, cumulative_company_profit_loss) = if profit_loss == rust_decimal::Decimal::ZERO {
+                    (rust_decimal::Decimal::ZERO, rust_decimal::Decimal::ZERO)
+                } else {
+                    // 计算个人、公司占比（从最后一条记录获取）
+                    let personal_ratio = if last_record.total_balance == rust_decimal::Decimal::ZERO {
+                        rust_decimal::Decimal::new(5, 1) // 默认50%
+                    } else {
+                        last_record.personal_balance / last_record.total_balance
+                    };
+                    let company_ratio = rust_decimal::Decimal::ONE - personal_ratio;
+                    
+                    // 分配盈亏到个人和公司
+                    let total_abs = profit_loss.abs();
+                    let personal_share = total_abs * personal_ratio;
+                    let company_share = total_abs * company_ratio;
+                    
+                    (personal_share, company_share)
+                };
+                
+                // 总计行（12个字段，与场外资金池格式一致）
+                worksheet.write_string(current_row, 0, "── 总计 ──")?;
+                worksheet.write_string(current_row, 1, format!("{pool_name} 汇总"))?;
+                worksheet.write_string(current_row, 2, format!("总申购: ¥{total_purchase:.0}"))?;
+                worksheet.write_string(current_row, 3, format!("总赎回: ¥{total_redemption:.0}"))?;
+                worksheet.write_string(current_row, 4, format!("最终余额: ¥{final_balance:.0}"))?;
+                worksheet.write_string(current_row, 5, format!("个人{status}: ¥{cumulative_personal_profit_loss:.0}"))?;
+                worksheet.write_string(current_row, 6, format!("公司{status}: ¥{cumulative_company_profit_loss:.0}"))?;
+                worksheet.write_string(current_row, 7, format!("净盈亏: ¥{profit_loss:.0}"))?;
+                worksheet.write_string(current_row, 8, format!("状态: {status}"))?;
+                self.write_amount(worksheet, current_row, 9, total_purchase)?;
+                self.write_amount(worksheet, current_row, 10, total_redemption)?;
+                self.write_amount(worksheet, current_row, 11, profit_loss)?;
+                current_row += 1;
+                
+                // 添加空白行分隔（参考场外资金池的做法）
+                for col in 0..12 {
+                    worksheet.write_string(current_row, col, "")?;
+                }
+                current_row += 1;
+            }
+        }
+        
+        // 添加全局总计行
+        if !records.is_empty() {
+            let mut pool_final_values: HashMap<String, &FundPoolRecord> = HashMap::new();
+            
+            // 为每个资金池找到最后一条记录（最终状态）
+            for record in records {
+                match pool_final_values.entry(record.pool_name.clone()) {
+                    std::collections::hash_map::Entry::Vacant(e) => {
+                        e.insert(record);
+                    }
+                    std::collections::hash_map::Entry::Occupied(mut e) => {
+                        // 比较交易时间，保留最新的记录
+                        if record.transaction_time > e.get().transaction_time {
+                            e.insert(record);
+                        }
+                    }
+                }
+            }
+            
+            // 计算全局总计（基于每个资金池的最终状态）
+            let global_total_balance: rust_decimal::Decimal = pool_final_values.values().map(|r| r.total_balance).sum();
+            let global_total_purchase: rust_decimal::Decimal = pool_final_values.values().map(|r| r.cumulative_purchase).sum();
+            let global_total_redemption: rust_decimal::Decimal = pool_final_values.values().map(|r| r.cumulative_redemption).sum();
+            let global_net_profit_loss = global_total_redemption - global_total_purchase;
+            
+            worksheet.write_string(current_row, 0, "═════ 全局总计 ═════")?;
+            worksheet.write_string(current_row, 1, format!("共 {} 个资金池", pool_final_values.len()))?;
+            worksheet.write_string(current_row, 2, "")?;
+            worksheet.write_string(current_row, 3, "")?;
+            self.write_amount(worksheet, current_row, 4, global_total_balance)?;
+            worksheet.write_string(current_row, 5, format!("总余额: ¥{global_total_balance:.2}"))?;
+            worksheet.write_string(current_row, 6, format!("净盈亏: ¥{global_net_profit_loss:.2}"))?;
+            worksheet.write_string(current_row, 7, "全局汇总")?;
+            self.write_amount(worksheet, current_row, 8, global_total_purchase)?;
+            self.write_amount(worksheet, current_row, 9, global_total_redemption)?;
+        }
+        
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存资金池记录失败: {e}")))
+        })?;
+        
+        let pool_count = grouped_records.len();
+        info!("✅ 资金池记录导出完成，共 {} 条记录，按 {} 个资金池分组，已为每个池添加总计行", records.len(), pool_count);
+        Ok(())
+    }
+    
+    /// 导出场外资金池记录到Excel
+    /// Python来源: `src/models/fifo_algorithm.py:651` `生成场外资金池记录Excel`
+    /// 
+    /// # Arguments
+    /// * `record_manager` - 场外资金池记录管理器
+    /// * `output_path` - 输出文件路径
+    /// 
+    /// # Returns
+    /// * `AuditResult<()>` - 导出结果
+    pub fn export_offsite_pool_records<P: AsRef<Path>>(
+        &self,
+        record_manager: &crate::data_models::OffsitePoolRecordManager,
+        output_path: P,
+    ) -> AuditResult<()> {
+        let path = output_path.as_ref();
+        info!("开始导出场外资金池记录到: {}", path.display());
+        
+        if record_manager.record_count() == 0 {
+            info!("📋 没有场外资金池记录，跳过Excel生成");
+            return Ok(());
+        }
+        
+        info!("📋 检测到 {} 条场外资金池记录，开始处理...", record_manager.record_count());
+        
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        self.write_offsite_pool_rows(worksheet, record_manager)?;
+        
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存场外资金池记录失败: {e}")))
+        })?;
+        
+        info!("✅ 场外资金池记录已保存至: {}", path.display());
+        info!("📊 共记录 {} 笔资金池交易，按资金池分组排序", record_manager.record_count());
+        Ok(())
+    }
+
+    /// 写入"仪表盘"工作表：关键指标、余额走势折线图与按月挪用/归还柱状图
+    ///
+    /// 图表序列取自已附带的按期汇总报告（未附带或按日序列为空时
+    /// 不生成该表）；图表数据区与图表放在同一工作表，
+    /// 移交的工作簿打开即可演示，不需要收件人自己画图
+    fn write_dashboard_worksheet(
+        &self,
+        workbook: &mut Workbook,
+        summary: &AuditSummary,
+    ) -> AuditResult<()> {
+        use rust_xlsxwriter::{Chart, ChartType};
+
+        let Some(report) = &self.period_summary else {
+            return Ok(());
+        };
+        if report.daily.is_empty() {
+            return Ok(());
+        }
+
+        let worksheet = workbook.add_worksheet().set_name("仪表盘")?;
+
+        // 关键指标块
+        worksheet.write_string(0, 0, "关键指标")?;
+        let kpi_items = [
+            ("累计挪用金额", summary.total_misappropriation),
+            ("累计垫付金额", summary.total_advance_payment),
+            ("累计利息收入", summary.total_interest_income),
+            ("累计手续费支出", summary.total_fee_expense),
+            ("累计归还公司本金", summary.total_company_principal_returned),
+            ("累计归还个人本金", summary.total_personal_principal_returned),
+            ("总计个人利润", summary.total_personal_profit),
+            ("总计公司利润", summary.total_company_profit),
+            ("资金缺口", summary.funding_gap),
+        ];
+        for (row, (name, value)) in kpi_items.iter().enumerate() {
+            let row = (row + 1) as u32;
+            worksheet.write_string(row, 0, *name)?;
+            self.write_amount(worksheet, row, 1, *value)?;
+        }
+
+        // 图表数据区：按日余额序列（折线图用）
+        const DAILY_DATA_COL: u16 = 8;
+        worksheet.write_string(0, DAILY_DATA_COL, "日期")?;
+        worksheet.write_string(0, DAILY_DATA_COL + 1, "个人余额")?;
+        worksheet.write_string(0, DAILY_DATA_COL + 2, "公司余额")?;
+        for (index, period) in report.daily.iter().enumerate() {
+            let row = (index + 1) as u32;
+            worksheet.write_string(row, DAILY_DATA_COL, &period.period)?;
+            worksheet.write_number(row, DAILY_DATA_COL + 1,
+                period.end_personal_balance.to_f64().unwrap_or_default())?;
+            worksheet.write_number(row, DAILY_DATA_COL + 2,
+                period.end_company_balance.to_f64().unwrap_or_default())?;
+        }
+
+        // 图表数据区：按月挪用/归还增量（柱状图用），累计量差分还原为当月量
+        const MONTHLY_DATA_COL: u16 = 12;
+        worksheet.write_string(0, MONTHLY_DATA_COL, "月份")?;
+        worksheet.write_string(0, MONTHLY_DATA_COL + 1, "当月挪用")?;
+        worksheet.write_string(0, MONTHLY_DATA_COL + 2, "当月归还")?;
+        let mut previous_misappropriation = Decimal::ZERO;
+        let mut previous_returned = Decimal::ZERO;
+        for (index, period) in report.monthly.iter().enumerate() {
+            let row = (index + 1) as u32;
+            let monthly_misappropriation = period.cumulative_misappropriation - previous_misappropriation;
+            let monthly_returned = period.cumulative_returned - previous_returned;
+            previous_misappropriation = period.cumulative_misappropriation;
+            previous_returned = period.cumulative_returned;
+            worksheet.write_string(row, MONTHLY_DATA_COL, &period.period)?;
+            worksheet.write_number(row, MONTHLY_DATA_COL + 1,
+                monthly_misappropriation.to_f64().unwrap_or_default())?;
+            worksheet.write_number(row, MONTHLY_DATA_COL + 2,
+                monthly_returned.to_f64().unwrap_or_default())?;
+        }
+
+        // 折线图：个人与公司余额随时间走势
+        let daily_rows = report.daily.len() as u32;
+        let mut balance_chart = Chart::new(ChartType::Line);
+        balance_chart.title().set_name("个人与公司余额走势");
+        balance_chart.add_series()
+            .set_name("个人余额")
+            .set_categories(("仪表盘", 1, DAILY_DATA_COL, daily_rows, DAILY_DATA_COL))
+            .set_values(("仪表盘", 1, DAILY_DATA_COL + 1, daily_rows, DAILY_DATA_COL + 1));
+        balance_chart.add_series()
+            .set_name("公司余额")
+            .set_categories(("仪表盘", 1, DAILY_DATA_COL, daily_rows, DAILY_DATA_COL))
+            .set_values(("仪表盘", 1, DAILY_DATA_COL + 2, daily_rows, DAILY_DATA_COL + 2));
+        worksheet.insert_chart(10, 0, &balance_chart)?;
+
+        // 柱状图：按月挪用与归还对照
+        let monthly_rows = report.monthly.len() as u32;
+        let mut flow_chart = Chart::new(ChartType::Column);
+        flow_chart.title().set_name("按月挪用与归还");
+        flow_chart.add_series()
+            .set_name("当月挪用")
+            .set_categories(("仪表盘", 1, MONTHLY_DATA_COL, monthly_rows, MONTHLY_DATA_COL))
+            .set_values(("仪表盘", 1, MONTHLY_DATA_COL + 1, monthly_rows, MONTHLY_DATA_COL + 1));
+        flow_chart.add_series()
+            .set_name("当月归还")
+            .set_categories(("仪表盘", 1, MONTHLY_DATA_COL, monthly_rows, MONTHLY_DATA_COL))
+            .set_values(("仪表盘", 1, MONTHLY_DATA_COL + 2, monthly_rows, MONTHLY_DATA_COL + 2));
+        worksheet.insert_chart(26, 0, &flow_chart)?;
+
+        info!("📊 仪表盘工作表已生成: {} 天余额走势 / {} 个月挪用归还对照",
+            report.daily.len(), report.monthly.len());
+        Ok(())
+    }
+
+    /// 写入场外资金池记录工作表（如已附带记录）
+    ///
+    /// 表名"场外资金池"，无记录时不生成
+    fn write_offsite_pool_worksheet(&self, workbook: &mut Workbook) -> AuditResult<()> {
+        let Some(record_manager) = &self.offsite_pool_records else {
+            return Ok(());
+        };
+        if record_manager.record_count() == 0 {
+            return Ok(());
+        }
+        let worksheet = workbook.add_worksheet().set_name("场外资金池")?;
+        self.write_offsite_pool_rows(worksheet, record_manager)
+    }
+
+    /// 按资金池分组写入场外记录与各池小计行（独立文件与内嵌工作表共用）
+    fn write_offsite_pool_rows(
+        &self,
+        worksheet: &mut Worksheet,
+        record_manager: &crate::data_models::OffsitePoolRecordManager,
+    ) -> AuditResult<()> {
+        // Python来源: 写入表头
+        let headers = crate::utils::i18n::header_labels(&[
+            "交易时间", "资金池名称", "入金", "出金", "总余额",
+            "个人余额", "公司余额", "资金占比", "行为性质", "累计申购", "累计赎回", "净盈亏"
+        ], self.config.excel_columns.header_language);
+        
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)?;
+        }
+        
+        // Python来源: 按资金池名称分组，每组内按时间排序
+        let grouped_records = record_manager.group_by_pool();
+        let mut sorted_pools: Vec<_> = grouped_records.keys().collect();
+        sorted_pools.sort();
+        
+        info!("📋 按资金池分组: 共 {} 个资金池", sorted_pools.len());
+        for pool_name in &sorted_pools {
+            info!("  └─ {}: {} 条记录", pool_name, grouped_records[*pool_name].len());
+        }
+        
+        let mut current_row = 1u32;
+        
+        // 按分组写入数据
+        for pool_name in sorted_pools {
+            let pool_records = &grouped_records[pool_name];
+            
+            // 写入该资金池的所有记录
+            for record in pool_records {
+                worksheet.write_string(current_row, 0, &record.transaction_time)?;
+                worksheet.write_string(current_row, 1, &record.pool_name)?;
+                
+                // 入金金额
+                self.write_amount(worksheet, current_row, 2, record.inflow)?;
+
+                // 出金金额
+                self.write_amount(worksheet, current_row, 3, record.outflow)?;
+
+                // 总余额
+                self.write_amount(worksheet, current_row, 4, record.total_balance)?;
+
+                // 个人余额
+                self.write_amount(worksheet, current_row, 5, record.personal_balance)?;
+
+                // 公司余额
+                self.write_amount(worksheet, current_row, 6, record.company_balance)?;
+
+                // 资金占比
+                worksheet.write_string(current_row, 7, &record.fund_ratio)?;
+
+                // 行为性质
+                worksheet.write_string(current_row, 8, &record.behavior_nature)?;
+
+                // 累计申购
+                self.write_amount(worksheet, current_row, 9, record.cumulative_purchase)?;
+
+                // 累计赎回
+                self.write_amount(worksheet, current_row, 10, record.cumulative_redemption)?;
+
+                // 净盈亏
+                self.write_amount(worksheet, current_row, 11, record.net_profit_loss)?;
+                
+                current_row += 1;
+            }
+            
+            // Python来源: 添加总计行
+            if let Some(stats) = record_manager.calculate_pool_stats_with_strategy(
+                pool_name,
+                self.config.investment_products.profit_allocation,
+            ) {
+                // 总计行
+                worksheet.write_string(current_row, 0, "── 总计 ──")?;
+                worksheet.write_string(current_row, 1, format!("{pool_name} 汇总"))?;
+                worksheet.write_string(current_row, 2, format!("总申购: ¥{:.0}", stats.total_purchase))?;
+                worksheet.write_string(current_row, 3, format!("总赎回: ¥{:.0}", stats.total_redemption))?;
+                worksheet.write_string(current_row, 4, format!("最终余额: ¥{:.0}", stats.final_balance))?;
+                worksheet.write_string(current_row, 5, format!("个人{}: ¥{:.0}", stats.status, stats.cumulative_personal_profit_loss))?;
+                worksheet.write_string(current_row, 6, format!("公司{}: ¥{:.0}", stats.status, stats.cumulative_company_profit_loss))?;
+                worksheet.write_string(current_row, 7, format!("净盈亏: ¥{:.0}", stats.profit_loss))?;
+                worksheet.write_string(current_row, 8, format!("状态: {}", stats.status))?;
+                self.write_amount(worksheet, current_row, 9, stats.total_purchase)?;
+                self.write_amount(worksheet, current_row, 10, stats.total_redemption)?;
+                // 时间加权收益指标（持有期/现金流不足以计算时显示"—"）
+                let annualized_text = stats.annualized_return_pct
+                    .map_or_else(|| "—".to_string(), |rate| format!("{rate}%"));
+                let xirr_text = stats.xirr_pct
+                    .map_or_else(|| "—".to_string(), |rate| format!("{rate}%"));
+                worksheet.write_string(current_row, 11, format!("年化: {annualized_text}，XIRR: {xirr_text}"))?;
+
+                current_row += 1;
+
+                // Python来源: 添加空白行分隔
+                for col in 0..12 {
+                    worksheet.write_string(current_row, col, "")?;
+                }
+                current_row += 1;
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// 写入验证修复报告工作表
+    ///
+    /// 逐组列出被自动重排的同时间交易（原顺序/新顺序/余额差），
+    /// 让用户能核对验证阶段"到底改了什么"。没有修复也没有
+    /// 未修复错误时不生成该表
+    fn write_validation_report_worksheet(&self, workbook: &mut Workbook) -> AuditResult<()> {
+        let Some(report) = &self.validation_report else {
+            return Ok(());
+        };
+        if report.repair_count == 0 && report.errors_count == 0
+            && report.reconciliation_mismatches.is_empty() {
+            return Ok(());
+        }
+
+        let worksheet = workbook.add_worksheet().set_name("顺序修复报告")?;
+
+        worksheet.write_string(0, 0, "验证总行数")?;
+        worksheet.write_number(0, 1, report.total_rows as f64)?;
+        worksheet.write_string(1, 0, "成功修复组数")?;
+        worksheet.write_number(1, 1, report.repair_count as f64)?;
+        worksheet.write_string(2, 0, "未修复错误数")?;
+        worksheet.write_number(2, 1, report.errors_count as f64)?;
+
+        let format_order = |order: &[usize]| order.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let headers = ["序号", "断点行号", "原顺序(行号)", "修复后顺序(行号)", "期望余额", "实际余额", "余额差"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(4, col as u16, *header)?;
+        }
+        for (index, group) in report.repaired_groups.iter().enumerate() {
+            let current_row = index as u32 + 5;
+            worksheet.write_number(current_row, 0, (index + 1) as f64)?;
+            worksheet.write_number(current_row, 1, group.problem_row as f64)?;
+            worksheet.write_string(current_row, 2, format_order(&group.original_order))?;
+            worksheet.write_string(current_row, 3, format_order(&group.new_order))?;
+            self.write_amount(worksheet, current_row, 4, group.expected_balance)?;
+            self.write_amount(worksheet, current_row, 5, group.actual_balance)?;
+            self.write_amount(worksheet, current_row, 6, group.balance_delta)?;
+        }
+
+        // 全程余额核对不符区间，附在修复明细之后单列一节
+        if !report.reconciliation_mismatches.is_empty() {
+            let section_row = report.repaired_groups.len() as u32 + 6;
+            worksheet.write_string(section_row, 0, "余额核对不符区间（余额列与按收支重算的余额）")?;
+
+            let headers = ["序号", "起始行", "结束行", "重算余额", "实际余额", "累计差额"];
+            for (col, header) in headers.iter().enumerate() {
+                worksheet.write_string(section_row + 1, col as u16, *header)?;
+            }
+            for (index, mismatch) in report.reconciliation_mismatches.iter().enumerate() {
+                let current_row = section_row + 2 + index as u32;
+                worksheet.write_number(current_row, 0, (index + 1) as f64)?;
+                worksheet.write_number(current_row, 1, mismatch.start_row as f64)?;
+                worksheet.write_number(current_row, 2, mismatch.end_row as f64)?;
+                self.write_amount(worksheet, current_row, 3, mismatch.expected_balance)?;
+                self.write_amount(worksheet, current_row, 4, mismatch.actual_balance)?;
+                self.write_amount(worksheet, current_row, 5, mismatch.balance_delta)?;
+            }
+        }
+
+        info!("📋 已写入顺序修复报告: {} 组修复, {} 个未修复错误, {} 处余额核对不符",
+            report.repair_count, report.errors_count, report.reconciliation_mismatches.len());
+        Ok(())
+    }
+
+    /// 写入风险标记汇总工作表
+    ///
+    /// 逐条列出可疑模式检测的发现（行号/类别/说明），与结果表的
+    /// 逐行"风险标记"列互为索引。没有发现时不生成该表
+    fn write_anomaly_summary_worksheet(&self, workbook: &mut Workbook) -> AuditResult<()> {
+        let Some(findings) = &self.anomaly_findings else {
+            return Ok(());
+        };
+        if findings.is_empty() {
+            return Ok(());
+        }
+
+        let worksheet = workbook.add_worksheet().set_name("风险标记汇总")?;
+
+        let headers = ["序号", "行号", "类别", "说明"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header)?;
+        }
+        for (index, finding) in findings.iter().enumerate() {
+            let current_row = index as u32 + 1;
+            worksheet.write_number(current_row, 0, (index + 1) as f64)?;
+            worksheet.write_number(current_row, 1, finding.row as f64)?;
+            worksheet.write_string(current_row, 2, finding.kind.label())?;
+            worksheet.write_string(current_row, 3, &finding.message)?;
+        }
+
+        info!("📋 已写入风险标记汇总: {} 条可疑模式发现", findings.len());
+        Ok(())
+    }
+
+    /// 写入按期汇总工作表
+    ///
+    /// 按日与按月两个区块上下排列，各期一行：流入/流出合计、
+    /// 期末余额拆分、累计挪用/垫付与资金缺口轨迹。
+    /// 两个粒度都为空时不生成该表
+    fn write_period_summary_worksheet(&self, workbook: &mut Workbook) -> AuditResult<()> {
+        let Some(report) = &self.period_summary else {
+            return Ok(());
+        };
+        if report.daily.is_empty() && report.monthly.is_empty() {
+            return Ok(());
+        }
+
+        let worksheet = workbook.add_worksheet().set_name("按期汇总")?;
+        let headers = [
+            "期间", "笔数", "流入合计", "流出合计", "期末余额",
+            "期末个人余额", "期末公司余额", "累计挪用", "累计垫付", "资金缺口",
+        ];
+
+        let mut current_row = 0u32;
+        for (title, periods) in [("按日汇总", &report.daily), ("按月汇总", &report.monthly)] {
+            if periods.is_empty() {
+                continue;
+            }
+            worksheet.write_string(current_row, 0, title)?;
+            current_row += 1;
+            for (col, header) in headers.iter().enumerate() {
+                worksheet.write_string(current_row, col as u16, *header)?;
+            }
+            current_row += 1;
+            for period in periods {
+                worksheet.write_string(current_row, 0, &period.period)?;
+                worksheet.write_number(current_row, 1, period.transaction_count as f64)?;
+                self.write_amount(worksheet, current_row, 2, period.total_inflow)?;
+                self.write_amount(worksheet, current_row, 3, period.total_outflow)?;
+                self.write_amount(worksheet, current_row, 4, period.end_balance)?;
+                self.write_amount(worksheet, current_row, 5, period.end_personal_balance)?;
+                self.write_amount(worksheet, current_row, 6, period.end_company_balance)?;
+                self.write_amount(worksheet, current_row, 7, period.cumulative_misappropriation)?;
+                self.write_amount(worksheet, current_row, 8, period.cumulative_advance)?;
+                self.write_amount(worksheet, current_row, 9, period.funding_gap)?;
+                current_row += 1;
+            }
+            // 区块之间空一行
+            current_row += 1;
+        }
+
+        info!("📋 已写入按期汇总: {} 个按日期间, {} 个按月期间", report.daily.len(), report.monthly.len());
+        Ok(())
+    }
+
+
+    /// 导出异常汇总工作簿
+    ///
+    /// 把流程各环节产生的结构化警告（跳过行、顺序修复、存疑赎回、
+    /// 余额断点等）汇总到单独的"异常汇总"工作簿，按严重程度
+    /// （错误→警告→提示）再按行号排序，复核从一张清单开始
+    pub fn export_exceptions_workbook<P: AsRef<Path>>(
+        &self,
+        warnings: &[crate::data_models::AuditWarning],
+        output_path: P,
+    ) -> AuditResult<()> {
+        let path = output_path.as_ref();
+
+        if warnings.is_empty() {
+            info!("📋 没有异常警告，跳过异常汇总生成");
+            return Ok(());
+        }
+
+        info!("📋 检测到 {} 条异常警告，开始生成异常汇总...", warnings.len());
+
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("异常汇总")?;
+
+        let headers = ["序号", "类别", "严重程度", "代码", "行号", "说明", "建议措施"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header)?;
+        }
+
+        // 严重程度高的排前面，同级按行号升序，便于从头往下复核
+        let severity_rank = |severity: &str| match severity {
+            "错误" => 0u8,
+            "警告" => 1,
+            _ => 2,
+        };
+        let mut sorted: Vec<_> = warnings.iter().collect();
+        sorted.sort_by_key(|w| (severity_rank(w.severity()), w.row.unwrap_or(usize::MAX)));
+
+        for (index, warning) in sorted.iter().enumerate() {
+            let current_row = index as u32 + 1;
+            worksheet.write_number(current_row, 0, (index + 1) as f64)?;
+            worksheet.write_string(current_row, 1, warning.category_label())?;
+            worksheet.write_string(current_row, 2, warning.severity())?;
+            worksheet.write_string(current_row, 3, &warning.code)?;
+            match warning.row {
+                Some(row) => worksheet.write_number(current_row, 4, row as f64)?,
+                None => worksheet.write_string(current_row, 4, "-")?,
+            };
+            worksheet.write_string(current_row, 5, &warning.message)?;
+            worksheet.write_string(current_row, 6, &warning.suggested_action)?;
+        }
+
+        self.with_io_retry("保存Excel文件", || {
+            workbook.save(path)
+                .map_err(|e| AuditError::excel_error(format!("保存异常汇总失败: {e}")))
+        })?;
+
+        info!("✅ 异常汇总已保存至: {}", path.display());
+        Ok(())
+    }
+}
+
+/// 列索引结构
+#[derive(Debug, Clone)]
+struct ColumnIndices {
+    transaction_date: Option<usize>,
+    transaction_time: Option<usize>,
+    income_amount: Option<usize>,
+    expense_amount: Option<usize>,
+    balance: Option<usize>,
+    fund_attribute: Option<usize>,
+    /// 同时间排序仲裁列（可选，仅在配置了仲裁列名时查找）
+    sequence_number: Option<usize>,
+    /// 币种列（可选，仅在配置了币种列名时查找）
+    currency: Option<usize>,
+}
+
+impl ColumnIndices {
+    fn new() -> Self {
+        Self {
+            transaction_date: None,
+            transaction_time: None,
+            income_amount: None,
+            expense_amount: None,
+            balance: None,
+            fund_attribute: None,
+            sequence_number: None,
+            currency: None,
+        }
+    }
+
+    /// 各逻辑列的中文标签、匹配索引与是否必需
+    fn labeled(&self) -> [(&'static str, Option<usize>, bool); 8] {
+        [
+            ("交易日期", self.transaction_date, true),
+            ("交易时间", self.transaction_time, true),
+            ("交易收入金额", self.income_amount, true),
+            ("交易支出金额", self.expense_amount, true),
+            ("余额", self.balance, true),
+            ("资金属性", self.fund_attribute, true),
+            ("流水号", self.sequence_number, false),
+            ("币种", self.currency, false),
+        ]
+    }
+
+    /// 匹配到的列数（供预检时挑选最像表头的候选行）
+    fn matched_count(&self) -> usize {
+        self.labeled().iter().filter(|(_, index, _)| index.is_some()).count()
+    }
+
+    fn validate(&self) -> AuditResult<()> {
+        for (name, index, required) in &self.labeled() {
+            if *required && index.is_none() {
+                return Err(AuditError::validation_error(
+                    format!("找不到必需的列: {name}")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_column_indices_validation() {
+        let mut indices = ColumnIndices::new();
+        assert!(indices.validate().is_err());
+        
+        indices.transaction_date = Some(0);
+        indices.transaction_time = Some(1);
+        indices.income_amount = Some(2);
+        indices.expense_amount = Some(3);
+        indices.balance = Some(4);
+        indices.fund_attribute = Some(5);
+        
+        assert!(indices.validate().is_ok());
+    }
+    
+    #[test]
+    fn test_decimal_parsing() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+        
+        // 测试数字解析
+        let float_data = calamine::Data::Float(123.45);
+        let result = processor.parse_decimal(&float_data).unwrap();
+        assert_eq!(result, Decimal::from_f64_retain(123.45).unwrap());
+        
+        // 测试字符串解析
+        let string_data = calamine::Data::String("1,234.56".to_string());
+        let result = processor.parse_decimal(&string_data).unwrap();
+        assert_eq!(result, Decimal::new(123456, 2));
+        
+        // 测试空值
+        let empty_data = calamine::Data::Empty;
+        let result = processor.parse_decimal(&empty_data).unwrap();
+        assert_eq!(result, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sort_by_sequence_number_prefers_serial_order() {
+        use chrono::NaiveDate;
+
+        let mut config = Config::new();
+        config.excel_columns.sequence_number_column = Some("流水号".to_string());
+        let processor = ExcelProcessor::new(config);
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let make = |sequence: Option<&str>, balance: i64| {
+            let mut tx = Transaction::new(
+                date,
+                "100000".to_string(),
+                Decimal::from(1000),
+                Decimal::ZERO,
+                Decimal::from(balance),
+                "个人应收".to_string(),
+            );
+            tx.sequence_number = sequence.map(str::to_string);
+            tx
+        };
+
+        // 纯数字流水号按数值比较："2"应排在"10"之前
+        let mut transactions = vec![make(Some("10"), 2000), make(Some("2"), 1000)];
+        processor.sort_by_sequence_number(&mut transactions);
+        assert_eq!(transactions[0].sequence_number.as_deref(), Some("2"));
+        assert_eq!(transactions[1].sequence_number.as_deref(), Some("10"));
+
+        // 任一记录缺少流水号时保持文件原序
+        let mut transactions = vec![make(Some("10"), 2000), make(None, 1000)];
+        processor.sort_by_sequence_number(&mut transactions);
+        assert_eq!(transactions[0].sequence_number.as_deref(), Some("10"));
+
+        // 未配置流水号列时即便读到了流水号也不重排
+        let mut transactions = vec![make(Some("10"), 2000), make(Some("2"), 1000)];
+        ExcelProcessor::new(Config::new()).sort_by_sequence_number(&mut transactions);
+        assert_eq!(transactions[0].sequence_number.as_deref(), Some("10"));
+    }
+
+    #[test]
+    fn test_inspect_file_reports_structure() {
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        let rows = [
+            ("2021-01-01", "100000", 1000.0, 0.0, 1000.0, "个人应收"),
+            ("2021-03-05", "090000", 0.0, 200.0, 800.0, "个人应付"),
+        ];
+
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        // 表头上方的合并标题说明行，预检应自动跳过
+        sheet.write(0, 0, "某银行交易流水明细").unwrap();
+        for (col, header) in headers.iter().enumerate() {
+            sheet.write(1, col as u16, *header).unwrap();
+        }
+        for (idx, (date, time, income, expense, balance, attribute)) in rows.iter().enumerate() {
+            let row = idx as u32 + 2;
+            sheet.write(row, 0, *date).unwrap();
+            sheet.write(row, 1, *time).unwrap();
+            sheet.write(row, 2, *income).unwrap();
+            sheet.write(row, 3, *expense).unwrap();
+            sheet.write(row, 4, *balance).unwrap();
+            sheet.write(row, 5, *attribute).unwrap();
+        }
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("input.xlsx");
+        workbook.save(&path).unwrap();
+
+        let inspection = ExcelProcessor::new(Config::new()).inspect_file(&path).unwrap();
+        assert_eq!(inspection.sheets.len(), 1);
+        assert_eq!(inspection.header_row, Some(2));
+        assert!(inspection.missing_required_columns.is_empty());
+        assert_eq!(inspection.data_row_count, 2);
+        assert_eq!(inspection.date_from.as_deref(), Some("2021-01-01"));
+        assert_eq!(inspection.date_to.as_deref(), Some("2021-03-05"));
+        assert_eq!(inspection.preview_headers[0], "交易日期");
+        assert_eq!(inspection.preview_rows.len(), 2);
+    }
+
+    #[test]
+    fn test_inspect_file_reports_missing_columns() {
+        // 缺少"余额"和"资金属性"列：正式读取会报错，预检应记入缺失列表
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额"];
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        for (col, header) in headers.iter().enumerate() {
+            sheet.write(0, col as u16, *header).unwrap();
+        }
+        sheet.write(1, 0, "2021-01-01").unwrap();
+        sheet.write(1, 1, "100000").unwrap();
+        sheet.write(1, 2, 1000.0).unwrap();
+        sheet.write(1, 3, 0.0).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("input.xlsx");
+        workbook.save(&path).unwrap();
+
+        let inspection = ExcelProcessor::new(Config::new()).inspect_file(&path).unwrap();
+        assert_eq!(inspection.header_row, Some(1));
+        assert_eq!(inspection.missing_required_columns, vec!["余额", "资金属性"]);
+        assert_eq!(inspection.data_row_count, 1);
+    }
+
+    #[test]
+    fn test_csv_streaming_export() {
+        use chrono::NaiveDate;
+
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let transactions = vec![Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        )];
+        let summary = AuditSummary::new();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let csv_path = temp_dir.path().join("result.csv");
+        processor.export_analysis_results_csv(&transactions, &summary, &csv_path).unwrap();
+
+        let content = std::fs::read_to_string(&csv_path).unwrap();
+        // 表头 + 1行数据 + 摘要区
+        assert!(content.contains("交易时间,交易收入金额"));
+        assert!(content.contains("个人应收"));
+        assert!(content.contains("指标,数值"));
+    }
+
+    #[test]
+    fn test_export_reports_progress() {
+        use chrono::NaiveDate;
+
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let transactions = vec![Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        )];
+        let summary = AuditSummary::new();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let xlsx_path = temp_dir.path().join("result.xlsx");
+
+        // 小结果集走单表路径，完成时应报告一次 (len, len)
+        let reports = std::sync::Mutex::new(Vec::new());
+        let progress = |current: usize, total: usize| {
+            reports.lock().unwrap().push((current, total));
+        };
+        let written = processor
+            .export_analysis_results_with_progress(&transactions, &summary, &xlsx_path, Some(&progress))
+            .unwrap();
+
+        assert_eq!(written, xlsx_path);
+        assert!(xlsx_path.exists());
+        assert_eq!(*reports.lock().unwrap(), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_export_writes_validation_report_sheet() {
+        use crate::utils::unified_validator::{ReconciliationMismatch, RepairedGroup, ValidationReport};
+        use chrono::NaiveDate;
+
+        let report = ValidationReport {
+            total_rows: 3,
+            errors_count: 0,
+            repair_count: 1,
+            repaired_groups: vec![RepairedGroup {
+                problem_row: 2,
+                original_order: vec![2, 3],
+                new_order: vec![3, 2],
+                expected_balance: Decimal::from(700),
+                actual_balance: Decimal::from(500),
+                balance_delta: Decimal::from(-200),
+            }],
+            reconciliation_mismatches: vec![ReconciliationMismatch {
+                start_row: 3,
+                end_row: 4,
+                expected_balance: Decimal::from(700),
+                actual_balance: Decimal::from(400),
+                balance_delta: Decimal::from(-300),
+            }],
+        };
+        let processor = ExcelProcessor::new(Config::new()).with_validation_report(report);
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let transactions = vec![Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        )];
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带修复报告.xlsx");
+        processor.export_analysis_results(&transactions, &AuditSummary::new(), &path).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert!(workbook.sheet_names().contains(&"顺序修复报告".to_string()));
+        let range = workbook.worksheet_range("顺序修复报告").unwrap();
+        // 明细区第一组：原顺序"2, 3"重排为"3, 2"
+        let original = range.get_value((5, 2)).unwrap();
+        assert_eq!(original.to_string(), "2, 3");
+        let reordered = range.get_value((5, 3)).unwrap();
+        assert_eq!(reordered.to_string(), "3, 2");
+        // 余额核对不符区间附在修复明细之后：1组修复时小节标题位于第7行
+        let section_title = range.get_value((7, 0)).unwrap();
+        assert!(section_title.to_string().contains("余额核对不符区间"));
+        let mismatch_start = range.get_value((9, 1)).unwrap();
+        assert_eq!(mismatch_start.to_string(), "3");
+        let mismatch_delta = range.get_value((9, 5)).unwrap();
+        assert_eq!(mismatch_delta.to_string(), "-300");
+    }
+
+    #[test]
+    fn test_export_writes_period_summary_sheet() {
+        use crate::utils::period_summary::PeriodSummaryReport;
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        );
+        tx.personal_balance = Some(Decimal::from(1000));
+        tx.company_balance = Some(Decimal::ZERO);
+        let transactions = vec![tx];
+
+        let processor = ExcelProcessor::new(Config::new())
+            .with_period_summary(PeriodSummaryReport::from_transactions(&transactions));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带按期汇总.xlsx");
+        processor.export_analysis_results(&transactions, &AuditSummary::new(), &path).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert!(workbook.sheet_names().contains(&"按期汇总".to_string()));
+        let range = workbook.worksheet_range("按期汇总").unwrap();
+        // 按日区块：标题、表头、一行数据
+        assert_eq!(range.get_value((0, 0)).unwrap().to_string(), "按日汇总");
+        assert_eq!(range.get_value((2, 0)).unwrap().to_string(), "2021-01-01");
+        assert_eq!(range.get_value((2, 2)).unwrap().to_string(), "1000");
+        // 按月区块紧随其后（空行分隔）
+        assert_eq!(range.get_value((4, 0)).unwrap().to_string(), "按月汇总");
+        assert_eq!(range.get_value((6, 0)).unwrap().to_string(), "2021-01");
+    }
+
+    #[test]
+    fn test_export_embeds_offsite_pool_sheet() {
+        use chrono::NaiveDate;
+
+        let mut record_manager = crate::data_models::OffsitePoolRecordManager::new();
+        for pool_name in ["理财-B002", "理财-A001"] {
+            record_manager.add_purchase_record(
+                None,
+                pool_name.to_string(),
+                Decimal::from(1000),
+                Decimal::from(1000),
+                Decimal::from(1000),
+                Decimal::ZERO,
+                Decimal::from(1000),
+                Decimal::ZERO,
+                Decimal::ONE,
+                Decimal::ZERO,
+                Decimal::from(1000),
+                Decimal::ZERO,
+            );
+        }
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let transactions = vec![Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        )];
+        let processor = ExcelProcessor::new(Config::new())
+            .with_offsite_pool_records(record_manager);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带场外资金池.xlsx");
+        processor.export_analysis_results(&transactions, &AuditSummary::new(), &path).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert!(workbook.sheet_names().contains(&"场外资金池".to_string()));
+        let range = workbook.worksheet_range("场外资金池").unwrap();
+        // 表头与按池分组：A001排在B002之前，每池数据行后跟小计行
+        assert_eq!(range.get_value((0, 0)).unwrap().to_string(), "交易时间");
+        assert_eq!(range.get_value((1, 1)).unwrap().to_string(), "理财-A001");
+        assert_eq!(range.get_value((2, 0)).unwrap().to_string(), "── 总计 ──");
+        assert_eq!(range.get_value((2, 1)).unwrap().to_string(), "理财-A001 汇总");
+        // 空行分隔后是下一个资金池
+        assert_eq!(range.get_value((4, 1)).unwrap().to_string(), "理财-B002");
+    }
+
+    #[test]
+    fn test_export_writes_dashboard_sheet_with_charts() {
+        use crate::utils::period_summary::PeriodSummaryReport;
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        );
+        tx.personal_balance = Some(Decimal::from(600));
+        tx.company_balance = Some(Decimal::from(400));
+        tx.cumulative_misappropriation = Some(Decimal::from(200));
+        tx.cumulative_company_principal_returned = Some(Decimal::from(50));
+        let transactions = vec![tx];
+
+        let processor = ExcelProcessor::new(Config::new())
+            .with_period_summary(PeriodSummaryReport::from_transactions(&transactions));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带仪表盘.xlsx");
+        processor.export_analysis_results(&transactions, &AuditSummary::new(), &path).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        assert!(workbook.sheet_names().contains(&"仪表盘".to_string()));
+        let range = workbook.worksheet_range("仪表盘").unwrap();
+        // 关键指标块与两块图表数据区
+        assert_eq!(range.get_value((0, 0)).unwrap().to_string(), "关键指标");
+        assert_eq!(range.get_value((1, 0)).unwrap().to_string(), "累计挪用金额");
+        assert_eq!(range.get_value((0, 8)).unwrap().to_string(), "日期");
+        assert_eq!(range.get_value((1, 8)).unwrap().to_string(), "2021-01-01");
+        assert_eq!(range.get_value((1, 9)).unwrap().to_string(), "600");
+        assert_eq!(range.get_value((0, 12)).unwrap().to_string(), "月份");
+        // 累计量已差分为当月增量
+        assert_eq!(range.get_value((1, 13)).unwrap().to_string(), "200");
+        assert_eq!(range.get_value((1, 14)).unwrap().to_string(), "50");
+    }
+
+    #[test]
+    fn test_read_analysis_results_round_trips_computed_columns() {
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            Decimal::from(9000),
+            "公司应付".to_string(),
+        );
+        tx.personal_ratio = Some(Decimal::new(25, 2));
+        tx.company_ratio = Some(Decimal::new(75, 2));
+        tx.behavior_nature = Some("挪用".to_string());
+        tx.cumulative_misappropriation = Some(Decimal::from(250));
+        tx.cumulative_advance = Some(Decimal::from(40));
+        tx.cumulative_company_principal_returned = Some(Decimal::from(30));
+        tx.personal_balance = Some(Decimal::from(2000));
+        tx.company_balance = Some(Decimal::from(7000));
+        tx.funding_gap = Some(Decimal::from(180));
+        tx.behavior_explanation = Some("公司应付支出挤占个人资金".to_string());
+        tx.risk_flags = Some("整额交易".to_string());
+
+        let mut summary = AuditSummary::new();
+        summary.personal_balance = Decimal::from(2000);
+        summary.company_balance = Decimal::from(7000);
+        summary.total_balance = Decimal::from(9000);
+        summary.total_misappropriation = Decimal::from(250);
+        summary.funding_gap = Decimal::from(180);
+
+        let processor = ExcelProcessor::new(Config::new());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("结果.xlsx");
+        processor.export_analysis_results(&[tx], &summary, &path).unwrap();
+
+        // 带"分析摘要"工作表的才算结果工作簿
+        assert!(ExcelProcessor::is_result_workbook(&path));
+        assert!(!ExcelProcessor::is_result_workbook(temp_dir.path().join("不存在.xlsx")));
+
+        let (restored, restored_summary) = processor.read_analysis_results(&path).unwrap();
+        assert_eq!(restored.len(), 1);
+        let tx = &restored[0];
+        assert_eq!(tx.transaction_date, date);
+        assert_eq!(tx.expense_amount, Decimal::from(1000));
+        assert_eq!(tx.fund_attribute, "公司应付");
+        assert_eq!(tx.personal_ratio, Some(Decimal::new(25, 2)));
+        assert_eq!(tx.behavior_nature.as_deref(), Some("挪用"));
+        assert_eq!(tx.cumulative_misappropriation, Some(Decimal::from(250)));
+        assert_eq!(tx.personal_balance, Some(Decimal::from(2000)));
+        assert_eq!(tx.funding_gap, Some(Decimal::from(180)));
+        assert_eq!(tx.behavior_explanation.as_deref(), Some("公司应付支出挤占个人资金"));
+        assert_eq!(tx.risk_flags.as_deref(), Some("整额交易"));
+        // 未导出的列读回为未填写
+        assert!(tx.fund_source_breakdown.is_none());
+
+        assert_eq!(restored_summary.total_misappropriation, Decimal::from(250));
+        assert_eq!(restored_summary.total_balance, Decimal::from(9000));
+        assert_eq!(restored_summary.funding_gap, Decimal::from(180));
+    }
+
+    #[test]
+    fn test_read_analysis_results_rejects_plain_input_file() {
+        // 普通流水文件没有"分析摘要"工作表，应直接报验证错误
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet();
+        for (col, header) in headers.iter().enumerate() {
+            sheet.write(0, col as u16, *header).unwrap();
+        }
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("流水.xlsx");
+        workbook.save(&path).unwrap();
+
+        assert!(!ExcelProcessor::is_result_workbook(&path));
+        let error = ExcelProcessor::new(Config::new())
+            .read_analysis_results(&path)
+            .unwrap_err();
+        assert!(error.to_string().contains("分析摘要"));
+    }
+
+    #[test]
+    fn test_suspicious_row_format_flags_misappropriation_and_gap() {
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let make_tx = || Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            Decimal::from(9000),
+            "公司应付".to_string(),
+        );
+
+        let processor = ExcelProcessor::new(Config::new());
+        let mut gap = Decimal::ZERO;
+
+        // 挪用且缺口扩大的行需要高亮
+        let mut suspicious = make_tx();
+        suspicious.behavior_nature = Some("挪用".to_string());
+        suspicious.funding_gap = Some(Decimal::from(100));
+        assert!(processor.suspicious_row_format(&suspicious, &mut gap).is_some());
+        assert_eq!(gap, Decimal::from(100));
+
+        // 行为正常且缺口未扩大的行不高亮
+        let mut normal = make_tx();
+        normal.funding_gap = Some(Decimal::from(100));
+        assert!(processor.suspicious_row_format(&normal, &mut gap).is_none());
+
+        // 垫付行高亮
+        let mut advance = make_tx();
+        advance.behavior_nature = Some("垫付".to_string());
+        advance.funding_gap = Some(Decimal::from(100));
+        assert!(processor.suspicious_row_format(&advance, &mut gap).is_some());
+
+        // 关闭高亮开关后一律不高亮
+        let mut config = Config::new();
+        config.excel_style.highlight_suspicious_rows = false;
+        let plain = ExcelProcessor::new(config);
+        let mut gap = Decimal::ZERO;
+        assert!(plain.suspicious_row_format(&suspicious, &mut gap).is_none());
+    }
+
+    #[test]
+    fn test_io_retry_records_events_and_gives_up() {
+        let mut config = Config::new();
+        config.io_retry.max_retries = 2;
+        config.io_retry.backoff_ms = 0;
+        let processor = ExcelProcessor::new(config);
+
+        // 前两次失败、第三次成功：结果正常返回并记录一条重试事件
+        let mut failures_left = 2;
+        let value = processor.with_io_retry("打开Excel文件", || {
+            if failures_left > 0 {
+                failures_left -= 1;
+                return Err(AuditError::excel_error("瞬时IO失败"));
+            }
+            Ok(42)
+        }).unwrap();
+        assert_eq!(value, 42);
+        let events = processor.take_io_retry_events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("2次重试"));
+        // 取走后列表清空
+        assert!(processor.take_io_retry_events().is_empty());
+
+        // 重试耗尽后返回最后一次的错误，不记录成功事件
+        let result: AuditResult<()> = processor.with_io_retry("保存Excel文件", || {
+            Err(AuditError::excel_error("持续失败"))
+        });
+        assert!(result.is_err());
+        assert!(processor.take_io_retry_events().is_empty());
+    }
+
+    #[test]
+    fn test_export_exceptions_workbook() {
+        use crate::data_models::AuditWarning;
+
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let warnings = vec![
+            AuditWarning::new("ROW_REORDERED", None, "重排2处交易顺序", "核对交易顺序"),
+            AuditWarning::new("BALANCE_DISCONTINUITY", Some(12), "余额不连续", "人工核对"),
+        ];
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("异常汇总_FIFO_analysis.xlsx");
+        processor.export_exceptions_workbook(&warnings, &path).unwrap();
+
+        // 回读验证：错误级排在提示级前面
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let range = workbook.worksheet_range("异常汇总").unwrap();
+        let rows: Vec<_> = range.rows().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][1].to_string(), "类别");
+        assert_eq!(rows[1][1].to_string(), "余额断点");
+        assert_eq!(rows[1][2].to_string(), "错误");
+        assert_eq!(rows[2][1].to_string(), "顺序自动修复");
+
+        // 空警告不生成文件
+        let empty_path = temp_dir.path().join("空.xlsx");
+        processor.export_exceptions_workbook(&[], &empty_path).unwrap();
+        assert!(!empty_path.exists());
+    }
+
+    /// 构造带默认列名的测试用xlsx文件，返回文件路径
+    fn write_test_workbook(dir: &std::path::Path, data_rows: usize) -> std::path::PathBuf {
+        let path = dir.join("流水.xlsx");
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header).unwrap();
+        }
+        for row in 0..data_rows {
+            let excel_row = (row + 1) as u32;
+            worksheet.write_string(excel_row, 0, "2021-01-01").unwrap();
+            worksheet.write_string(excel_row, 1, "100000").unwrap();
+            worksheet.write_number(excel_row, 2, 1000.0).unwrap();
+            worksheet.write_number(excel_row, 3, 0.0).unwrap();
+            worksheet.write_number(excel_row, 4, 1000.0 * (row + 1) as f64).unwrap();
+            worksheet.write_string(excel_row, 5, "个人应收").unwrap();
+        }
+        workbook.save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_transactions_chunked() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+        
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = write_test_workbook(temp_dir.path(), 5);
+        
+        let mut chunk_sizes = Vec::new();
+        let mut last_progress: Option<ChunkProgress> = None;
+        let total = processor.read_transactions_chunked(&path, 2, |chunk, progress| {
+            chunk_sizes.push(chunk.len());
+            last_progress = Some(progress.clone());
+            Ok(())
+        }).unwrap();
+        
+        // 5行数据按每块2条交付：2 + 2 + 1
+        assert_eq!(total, 5);
+        assert_eq!(chunk_sizes, vec![2, 2, 1]);
+        let progress = last_progress.unwrap();
+        assert_eq!(progress.chunk_index, 2);
+        assert_eq!(progress.total_rows, 5);
+        assert_eq!(progress.transactions_parsed, 5);
+    }
+
+    #[test]
+    fn test_read_transactions_chunked_rejects_zero_chunk_size() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+        
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = write_test_workbook(temp_dir.path(), 1);
+        
+        let result = processor.read_transactions_chunked(&path, 0, |_, _| Ok(()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_skips_title_rows_above_header() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带标题.xlsx");
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        // 表头上方两行合并标题说明
+        worksheet.write_string(0, 0, "某某银行交易流水明细清单").unwrap();
+        worksheet.write_string(1, 0, "账号：622848******1234").unwrap();
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(2, col as u16, *header).unwrap();
+        }
+        worksheet.write_string(3, 0, "2021-01-01").unwrap();
+        worksheet.write_string(3, 1, "100000").unwrap();
+        worksheet.write_number(3, 2, 1000.0).unwrap();
+        worksheet.write_number(3, 3, 0.0).unwrap();
+        worksheet.write_number(3, 4, 1000.0).unwrap();
+        worksheet.write_string(3, 5, "个人应收").unwrap();
+        workbook.save(&path).unwrap();
+
+        let transactions = processor.read_transactions(&path).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].fund_attribute, "个人应收");
+    }
+
+    #[test]
+    fn test_reads_user_selected_sheet_in_multi_sheet_workbook() {
+        let config = Config::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("多表.xlsx");
+
+        // 第一个工作表是封面说明，流水数据在第二个工作表
+        let mut workbook = Workbook::new();
+        let cover = workbook.add_worksheet();
+        cover.set_name("封面").unwrap();
+        cover.write_string(0, 0, "某某银行交易流水明细（数据见\"需计算\"工作表）").unwrap();
+        let data = workbook.add_worksheet();
+        data.set_name("需计算").unwrap();
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        for (col, header) in headers.iter().enumerate() {
+            data.write_string(0, col as u16, *header).unwrap();
+        }
+        data.write_string(1, 0, "2021-01-01").unwrap();
+        data.write_string(1, 1, "100000").unwrap();
+        data.write_number(1, 2, 1000.0).unwrap();
+        data.write_number(1, 3, 0.0).unwrap();
+        data.write_number(1, 4, 1000.0).unwrap();
+        data.write_string(1, 5, "个人应收").unwrap();
+        workbook.save(&path).unwrap();
+
+        // 工作表发现：按文件内顺序列出
+        let processor = ExcelProcessor::new(config.clone());
+        assert_eq!(processor.list_sheets(&path).unwrap(), vec!["封面", "需计算"]);
+
+        // 指定工作表后各读取接口都从该表取数
+        let processor = ExcelProcessor::new(config.clone()).with_sheet_name("需计算");
+        let transactions = processor.read_transactions(&path).unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].fund_attribute, "个人应收");
+        assert_eq!(processor.count_data_rows(&path).unwrap(), 1);
+
+        // 指定的工作表不存在：报错并列出可选工作表
+        let processor = ExcelProcessor::new(config.clone()).with_sheet_name("不存在的表");
+        let err = processor.read_transactions(&path).unwrap_err().to_string();
+        assert!(err.contains("工作表\"不存在的表\"不存在"));
+        assert!(err.contains("封面"));
+        assert!(err.contains("需计算"));
+
+        // 指定的工作表缺少必需列：错误指明是哪个工作表不可用
+        let processor = ExcelProcessor::new(config).with_sheet_name("封面");
+        let err = processor.read_transactions(&path).unwrap_err().to_string();
+        assert!(err.contains("工作表\"封面\"无法用于分析"));
+    }
+
+    #[test]
+    fn test_only_header_reports_actionable_error() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = write_test_workbook(temp_dir.path(), 0);
+
+        let err = processor.read_transactions(&path).unwrap_err();
+        assert!(err.to_string().contains("只有表头没有数据行"));
+    }
+
+    #[test]
+    fn test_trims_trailing_blank_rows() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // 数据区之后带"有格式无数据"的空白行（空字符串/空白字符单元格）
+        let path = {
+            let blank_path = temp_dir.path().join("带空白尾.xlsx");
+            let mut workbook = Workbook::new();
+            let worksheet = workbook.add_worksheet();
+            let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+            for (col, header) in headers.iter().enumerate() {
+                worksheet.write_string(0, col as u16, *header).unwrap();
+            }
+            for row in 1..=3u32 {
+                worksheet.write_string(row, 0, "2021-01-01").unwrap();
+                worksheet.write_string(row, 1, "100000").unwrap();
+                worksheet.write_number(row, 2, 1000.0).unwrap();
+                worksheet.write_number(row, 3, 0.0).unwrap();
+                worksheet.write_number(row, 4, 1000.0).unwrap();
+                worksheet.write_string(row, 5, "个人应收").unwrap();
+            }
+            for row in 4..20u32 {
+                worksheet.write_string(row, 0, "  ").unwrap();
+                worksheet.write_string(row, 5, "").unwrap();
+            }
+            workbook.save(&blank_path).unwrap();
+            blank_path
+        };
+
+        // 全量读取：空白尾行被裁剪，不产生逐行解析警告
+        let transactions = processor.read_transactions(&path).unwrap();
+        assert_eq!(transactions.len(), 3);
+
+        // 分块读取：进度总数按有效数据区域计算
+        let mut last_progress: Option<ChunkProgress> = None;
+        let total = processor.read_transactions_chunked(&path, 2, |_, progress| {
+            last_progress = Some(progress.clone());
+            Ok(())
+        }).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(last_progress.unwrap().total_rows, 3);
+    }
+
+    #[test]
+    fn test_skips_trailing_total_row() {
+        let config = Config::new();
+        let processor = ExcelProcessor::new(config);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("带合计.xlsx");
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let headers = ["交易日期", "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性"];
+        for (col, header) in headers.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header).unwrap();
+        }
+        worksheet.write_string(1, 0, "2021-01-01").unwrap();
+        worksheet.write_string(1, 1, "100000").unwrap();
+        worksheet.write_number(1, 2, 1000.0).unwrap();
+        worksheet.write_number(1, 3, 0.0).unwrap();
+        worksheet.write_number(1, 4, 1000.0).unwrap();
+        worksheet.write_string(1, 5, "个人应收").unwrap();
+        // 表尾合计行：日期为空，金额列有值
+        worksheet.write_string(2, 0, "合计").unwrap();
+        worksheet.write_number(2, 2, 1000.0).unwrap();
+        worksheet.write_number(2, 3, 0.0).unwrap();
+        workbook.save(&path).unwrap();
+
+        let transactions = processor.read_transactions(&path).unwrap();
+        // 合计行被识别并跳过，不计入交易也不产生解析失败警告
+        assert_eq!(transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_decimal_fits_f64() {
+        // 常规金额在f64安全整数范围内
+        assert!(ExcelProcessor::decimal_fits_f64(Decimal::new(123_456_789, 2)));
+        // 2^53边界值
+        assert!(ExcelProcessor::decimal_fits_f64(Decimal::from(1i64 << 53)));
+        // 系数超过2^53后to_f64不再精确
+        let huge = Decimal::new(90_071_992_547_409_931, 2); // 900,719,925,474,099.31
+        assert!(!ExcelProcessor::decimal_fits_f64(huge));
+        let round_trip = Decimal::from_f64_retain(huge.to_f64().unwrap()).unwrap();
+        assert_ne!(round_trip.round_dp(2), huge);
+    }
+
+    #[test]
+    fn test_precision_safe_export_writes_exact_string() {
+        use chrono::NaiveDate;
+
+        let mut config = Config::new();
+        config.numeric.precision_safe_export = true;
+        let processor = ExcelProcessor::new(config);
+
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        // 余额超过2^53分位精度极限（数十亿级流水的累计金额量级）
+        let huge_balance = Decimal::new(90_071_992_547_409_931, 2);
+        let transactions = vec![Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            huge_balance,
+            "个人应收".to_string(),
+        )];
+        let summary = AuditSummary::new();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("结果.xlsx");
+        processor.export_analysis_results(&transactions, &summary, &path).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let sheet_names = workbook.sheet_names();
+        let range = workbook.worksheet_range(&sheet_names[0]).unwrap();
+        // 余额列（第3列）按精确字符串写入，分位不丢失
+        let cell = range.get_value((1, 3)).unwrap();
+        assert_eq!(cell, &calamine::Data::String("900719925474099.31".to_string()));
+        // 常规金额仍按数值写入
+        let income = range.get_value((1, 1)).unwrap();
+        assert_eq!(income, &calamine::Data::Float(1000.0));
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        // 含逗号的字段需要引号包裹
+        assert_eq!(ExcelProcessor::csv_escape("a,b"), "\"a,b\"");
+        // 含引号的字段需要转义
+        assert_eq!(ExcelProcessor::csv_escape("a\"b"), "\"a\"\"b\"");
+        // 普通字段原样返回
+        assert_eq!(ExcelProcessor::csv_escape("个人应收"), "个人应收");
+    }
+}
\ No newline at end of file
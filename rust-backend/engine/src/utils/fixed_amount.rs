@@ -0,0 +1,111 @@
+//! 定点金额表示（以分为单位的i64）
+//!
+//! `rust_decimal`的加减比较需要128位运算与标度对齐，在FIFO这类
+//! 以加减比较为主的热循环中开销可观。流水金额几乎都是到分的两位
+//! 小数，可无损映射为i64的分计数，让热循环退化为原生整数运算
+//! （编译器可自动向量化）。仅在`fixed-point`特性开启时编译；
+//! 边界处与Decimal互转，无法无损映射的金额由调用方回退Decimal路径。
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// 以分（0.01元）为单位的定点金额
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct FixedAmount(i64);
+
+impl FixedAmount {
+    /// 零值
+    pub const ZERO: Self = Self(0);
+
+    /// 尝试从Decimal无损转换
+    ///
+    /// 小数位超过2位或分计数超出i64范围时返回None，
+    /// 调用方应回退到Decimal路径而不是截断
+    #[must_use]
+    pub fn try_from_decimal(value: Decimal) -> Option<Self> {
+        let cents = value.checked_mul(Decimal::from(100))?;
+        if cents != cents.trunc() {
+            return None;
+        }
+        cents.to_i64().map(Self)
+    }
+
+    /// 转回Decimal（恒为2位小数标度）
+    #[must_use]
+    pub fn to_decimal(self) -> Decimal {
+        Decimal::new(self.0, 2)
+    }
+
+    /// 取较小值
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    /// 是否为零
+    #[must_use]
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::ops::Add for FixedAmount {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::AddAssign for FixedAmount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl std::ops::Sub for FixedAmount {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl std::ops::SubAssign for FixedAmount {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_two_decimal_places() {
+        let value = Decimal::new(123_456, 2); // 1234.56
+        let fixed = FixedAmount::try_from_decimal(value).unwrap();
+        assert_eq!(fixed.to_decimal(), value);
+    }
+
+    #[test]
+    fn test_rejects_more_than_two_decimal_places() {
+        // 占比拆分可能产生长小数，必须回退Decimal路径而不是截断
+        let value = Decimal::new(1_234_567, 3); // 1234.567
+        assert!(FixedAmount::try_from_decimal(value).is_none());
+    }
+
+    #[test]
+    fn test_rejects_overflow() {
+        let value = Decimal::MAX;
+        assert!(FixedAmount::try_from_decimal(value).is_none());
+    }
+
+    #[test]
+    fn test_arithmetic_matches_decimal() {
+        let a = FixedAmount::try_from_decimal(Decimal::new(10_050, 2)).unwrap(); // 100.50
+        let b = FixedAmount::try_from_decimal(Decimal::new(2_525, 2)).unwrap(); // 25.25
+        assert_eq!((a + b).to_decimal(), Decimal::new(12_575, 2));
+        assert_eq!((a - b).to_decimal(), Decimal::new(7_525, 2));
+        assert_eq!(a.min(b), b);
+        assert!(FixedAmount::ZERO.is_zero());
+    }
+}
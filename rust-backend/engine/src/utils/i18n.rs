@@ -0,0 +1,99 @@
+//! 导出层表头i18n目录
+//!
+//! 涉外项目中审查人员不一定懂中文，导出工作簿的表头可按
+//! `ExcelColumnConfig::header_language`输出英文或中英双语。
+//! 数据行中行为性质一列由结构化行为记录按语言渲染（见
+//! `algorithms::shared::behavior_record`）；资金属性等其余业务文本
+//! 保持中文原样，避免与取证原件产生表述偏差。
+
+use crate::data_models::HeaderLanguage;
+
+/// 表头中英对照目录（覆盖主结果表、摘要表与场外资金池记录表）
+static HEADER_CATALOG: &[(&str, &str)] = &[
+    ("交易时间", "Transaction Time"),
+    ("交易收入金额", "Income Amount"),
+    ("交易支出金额", "Expense Amount"),
+    ("余额", "Balance"),
+    ("资金属性", "Fund Attribute"),
+    ("个人资金占比", "Personal Fund Ratio"),
+    ("公司资金占比", "Company Fund Ratio"),
+    ("行为性质", "Behavior Nature"),
+    ("累计挪用", "Cumulative Misappropriation"),
+    ("累计垫付", "Cumulative Advance Payment"),
+    ("累计已归还公司本金", "Company Principal Returned"),
+    ("累计已归还个人本金", "Personal Principal Returned"),
+    ("总计个人应分配利润", "Total Personal Profit"),
+    ("总计公司应分配利润", "Total Company Profit"),
+    ("个人余额", "Personal Balance"),
+    ("公司余额", "Company Balance"),
+    ("总余额", "Total Balance"),
+    ("资金缺口", "Funding Gap"),
+    ("资金来源明细", "Fund Source Breakdown"),
+    ("判定依据", "Classification Basis"),
+    ("风险标记", "Risk Flags"),
+    ("指标", "Metric"),
+    ("数值", "Value"),
+    ("资金池名称", "Pool Name"),
+    ("入金", "Inflow"),
+    ("出金", "Outflow"),
+    ("资金占比", "Fund Ratio"),
+    ("累计申购", "Cumulative Purchase"),
+    ("累计赎回", "Cumulative Redemption"),
+    ("净盈亏", "Net Profit/Loss"),
+];
+
+/// 按目录查找表头的英文译名（未收录的表头返回None）
+fn english_label(zh: &str) -> Option<&'static str> {
+    HEADER_CATALOG.iter()
+        .find(|(key, _)| *key == zh)
+        .map(|(_, en)| *en)
+}
+
+/// 按配置语言渲染表头
+///
+/// 未收录进目录的表头一律回退为中文原文，保证导出不因缺译而失败
+#[must_use]
+pub fn header_label(zh: &str, language: HeaderLanguage) -> String {
+    match language {
+        HeaderLanguage::Chinese => zh.to_string(),
+        HeaderLanguage::English => english_label(zh).unwrap_or(zh).to_string(),
+        HeaderLanguage::Bilingual => english_label(zh)
+            .map_or_else(|| zh.to_string(), |en| format!("{zh} / {en}")),
+    }
+}
+
+/// 按配置语言批量渲染表头（保持原顺序）
+#[must_use]
+pub fn header_labels(zh_headers: &[&str], language: HeaderLanguage) -> Vec<String> {
+    zh_headers.iter()
+        .map(|zh| header_label(zh, language))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_label_languages() {
+        assert_eq!(header_label("交易时间", HeaderLanguage::Chinese), "交易时间");
+        assert_eq!(header_label("交易时间", HeaderLanguage::English), "Transaction Time");
+        assert_eq!(
+            header_label("交易时间", HeaderLanguage::Bilingual),
+            "交易时间 / Transaction Time"
+        );
+    }
+
+    #[test]
+    fn test_header_label_fallback_for_unknown_term() {
+        // 未收录的表头回退为中文原文
+        assert_eq!(header_label("未知列", HeaderLanguage::English), "未知列");
+        assert_eq!(header_label("未知列", HeaderLanguage::Bilingual), "未知列");
+    }
+
+    #[test]
+    fn test_header_labels_keeps_order() {
+        let labels = header_labels(&["余额", "资金属性"], HeaderLanguage::English);
+        assert_eq!(labels, vec!["Balance".to_string(), "Fund Attribute".to_string()]);
+    }
+}
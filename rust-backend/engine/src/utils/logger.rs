@@ -11,6 +11,7 @@ pub struct AuditLogger;
 
 impl AuditLogger {
     /// 创建新的日志实例
+    #[must_use] 
     pub fn new(_name: &str) -> Self {
         Self
     }
@@ -37,8 +38,8 @@ impl AuditLogger {
     
     /// 记录算法分析开始
     pub fn log_analysis_start(algorithm: &str, input_file: &str) {
-        info!("🚀 启动算法: {}", algorithm);
-        info!("📂 输入文件: {}", input_file);
+        info!("🚀 启动算法: {algorithm}");
+        info!("📂 输入文件: {input_file}");
     }
     
     /// 记录处理进度
@@ -49,28 +50,28 @@ impl AuditLogger {
             0.0
         };
         
-        info!("⏳ 处理进度: {}/{} ({:.1}%)", current, total, percentage);
+        info!("⏳ 处理进度: {current}/{total} ({percentage:.1}%)");
     }
     
     /// 记录算法完成
     pub fn log_analysis_complete(algorithm: &str, record_count: usize) {
-        info!("✅ {}算法分析完成", algorithm);
-        info!("📊 处理记录数: {}", record_count);
+        info!("✅ {algorithm}算法分析完成");
+        info!("📊 处理记录数: {record_count}");
     }
     
     /// 记录错误信息
     pub fn log_error(context: &str, error: &dyn std::error::Error) {
-        error!("❌ {}: {}", context, error);
+        error!("❌ {context}: {error}");
     }
     
     /// 记录警告信息
     pub fn log_warning(message: &str) {
-        warn!("⚠️ {}", message);
+        warn!("⚠️ {message}");
     }
     
     /// 记录调试信息
     pub fn log_debug(message: &str) {
-        debug!("🔍 {}", message);
+        debug!("🔍 {message}");
     }
     
     /// 记录交易处理
@@ -104,12 +105,7 @@ impl AuditLogger {
         company_amount: Decimal,
     ) {
         info!(
-            "💰 资金池[{}] {}: 总金额={}, 个人={}, 公司={}",
-            product_name,
-            operation,
-            amount,
-            personal_amount,
-            company_amount
+            "💰 资金池[{product_name}] {operation}: 总金额={amount}, 个人={personal_amount}, 公司={company_amount}"
         );
     }
     
@@ -120,9 +116,9 @@ impl AuditLogger {
         total_illegal_gains: Decimal,
     ) {
         info!("📊 ========== 统计汇总 ==========");
-        info!("💸 累计挪用金额: {}", total_misappropriation);
-        info!("💳 累计垫付金额: {}", total_advance);
-        info!("🚫 累计非法所得: {}", total_illegal_gains);
+        info!("💸 累计挪用金额: {total_misappropriation}");
+        info!("💳 累计垫付金额: {total_advance}");
+        info!("🚫 累计非法所得: {total_illegal_gains}");
         info!("📊 ==============================");
     }
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+//! 工具模块
+//! 
+//! 提供系统所需的各种工具函数和辅助类。
+
+pub mod anomaly_detector;    // 可疑交易模式检测器
+pub mod attribute_hierarchy; // 资金属性层级工具
+pub mod classification_rules; // 资金属性分类规则引擎
+pub mod excel_processor;     // API已修复，重新启用
+pub mod period_summary;      // 按期余额汇总报告
+pub mod time_processor;      // 时间处理模块
+pub mod unified_validator;   // 统一数据验证器模块
+pub mod logger;              // 日志记录模块
+pub mod i18n;                // 导出表头i18n目录
+pub mod result_digest;       // 结果摘要哈希（回归测试用）
+pub mod result_filter;       // 结果行筛选（聚焦导出）
+#[cfg(feature = "fixed-point")]
+pub mod fixed_amount;        // 定点金额表示（fixed-point特性）
+
+// 重新导出主要工具
+pub use anomaly_detector::*;
+pub use attribute_hierarchy::*;
+pub use classification_rules::*;
+pub use excel_processor::*;
+pub use period_summary::*;
+pub use time_processor::*;
+pub use unified_validator::*;
+pub use logger::*;
+pub use i18n::*;
+pub use result_digest::result_digest;
+pub use result_filter::{filter_by_behavior, filter_by_amount_range, filter_by_fund_attribute};
+#[cfg(feature = "fixed-point")]
+pub use fixed_amount::FixedAmount;
\ No newline at end of file
@@ -0,0 +1,184 @@
+//! 按期余额汇总报告
+//!
+//! 在主分析完成后把处理结果按日与按月滚动汇总：各期流入/流出合计、
+//! 期末个人/公司余额、期末累计挪用与资金缺口轨迹。期末数值直接取
+//! 各期处理顺序的最后一行（算法已把累计量写回逐行交易），
+//! 供仪表盘画趋势图与导出"按期汇总"工作表使用
+
+use crate::data_models::Transaction;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 单个汇总期（一天或一个月）的聚合结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodBalanceSummary {
+    /// 期标签（按日为"YYYY-MM-DD"，按月为"YYYY-MM"）
+    pub period: String,
+    /// 期内交易笔数
+    pub transaction_count: usize,
+    /// 期内流入合计
+    pub total_inflow: Decimal,
+    /// 期内流出合计
+    pub total_outflow: Decimal,
+    /// 期末总余额
+    pub end_balance: Decimal,
+    /// 期末个人余额
+    pub end_personal_balance: Decimal,
+    /// 期末公司余额
+    pub end_company_balance: Decimal,
+    /// 期末累计挪用金额
+    pub cumulative_misappropriation: Decimal,
+    /// 期末累计垫付金额
+    pub cumulative_advance: Decimal,
+    /// 期末累计归还本金（公司+个人；旧序列化样本没有该字段）
+    #[serde(default)]
+    pub cumulative_returned: Decimal,
+    /// 期末资金缺口
+    pub funding_gap: Decimal,
+}
+
+impl PeriodBalanceSummary {
+    fn new(period: String) -> Self {
+        Self {
+            period,
+            transaction_count: 0,
+            total_inflow: Decimal::ZERO,
+            total_outflow: Decimal::ZERO,
+            end_balance: Decimal::ZERO,
+            end_personal_balance: Decimal::ZERO,
+            end_company_balance: Decimal::ZERO,
+            cumulative_misappropriation: Decimal::ZERO,
+            cumulative_advance: Decimal::ZERO,
+            cumulative_returned: Decimal::ZERO,
+            funding_gap: Decimal::ZERO,
+        }
+    }
+}
+
+/// 按期汇总报告（按日与按月两个粒度，各按期标签升序）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeriodSummaryReport {
+    /// 按日汇总
+    pub daily: Vec<PeriodBalanceSummary>,
+    /// 按月汇总
+    pub monthly: Vec<PeriodBalanceSummary>,
+}
+
+impl PeriodSummaryReport {
+    /// 从算法处理后的交易构建按期汇总
+    ///
+    /// 输入按处理顺序遍历，同标签行归并到同一期；
+    /// 处理顺序已按时间排序，期末数值即各期最后一行的状态
+    #[must_use]
+    pub fn from_transactions(transactions: &[Transaction]) -> Self {
+        Self {
+            daily: Self::roll_up(transactions, |tx| tx.transaction_date.format("%Y-%m-%d").to_string()),
+            monthly: Self::roll_up(transactions, |tx| tx.transaction_date.format("%Y-%m").to_string()),
+        }
+    }
+
+    /// 按给定期标签函数做一轮滚动汇总
+    fn roll_up(
+        transactions: &[Transaction],
+        label: impl Fn(&Transaction) -> String,
+    ) -> Vec<PeriodBalanceSummary> {
+        let mut periods: Vec<PeriodBalanceSummary> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        for tx in transactions {
+            let period = label(tx);
+            let position = *index.entry(period.clone()).or_insert_with(|| {
+                periods.push(PeriodBalanceSummary::new(period));
+                periods.len() - 1
+            });
+
+            let entry = &mut periods[position];
+            entry.transaction_count += 1;
+            entry.total_inflow += tx.income_amount;
+            entry.total_outflow += tx.expense_amount;
+            entry.end_balance = tx.balance;
+            entry.end_personal_balance = tx.personal_balance.unwrap_or(Decimal::ZERO);
+            entry.end_company_balance = tx.company_balance.unwrap_or(Decimal::ZERO);
+            entry.cumulative_misappropriation = tx.cumulative_misappropriation.unwrap_or(Decimal::ZERO);
+            entry.cumulative_advance = tx.cumulative_advance.unwrap_or(Decimal::ZERO);
+            entry.cumulative_returned = tx.cumulative_company_principal_returned.unwrap_or(Decimal::ZERO)
+                + tx.cumulative_personal_principal_returned.unwrap_or(Decimal::ZERO);
+            entry.funding_gap = tx.funding_gap.unwrap_or(Decimal::ZERO);
+        }
+
+        periods.sort_by(|a, b| a.period.cmp(&b.period));
+        periods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn transaction(day: u32, income: i64, expense: i64, balance: i64) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, if day > 20 { 2 } else { 1 }, day % 20 + 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(income),
+            Decimal::from(expense),
+            Decimal::from(balance),
+            "个人应收".to_string(),
+        );
+        tx.personal_balance = Some(Decimal::from(balance));
+        tx.company_balance = Some(Decimal::ZERO);
+        tx.cumulative_misappropriation = Some(Decimal::from(expense));
+        tx.funding_gap = Some(Decimal::from(expense));
+        tx
+    }
+
+    #[test]
+    fn test_daily_roll_up_sums_and_takes_period_end() {
+        // 1月2日两笔、1月3日一笔
+        let transactions = vec![
+            transaction(1, 1000, 0, 1000),
+            transaction(1, 500, 200, 1300),
+            transaction(2, 0, 300, 1000),
+        ];
+
+        let report = PeriodSummaryReport::from_transactions(&transactions);
+        assert_eq!(report.daily.len(), 2);
+
+        let first = &report.daily[0];
+        assert_eq!(first.period, "2021-01-02");
+        assert_eq!(first.transaction_count, 2);
+        assert_eq!(first.total_inflow, Decimal::from(1500));
+        assert_eq!(first.total_outflow, Decimal::from(200));
+        // 期末数值取当期最后一行
+        assert_eq!(first.end_balance, Decimal::from(1300));
+        assert_eq!(first.cumulative_misappropriation, Decimal::from(200));
+
+        let second = &report.daily[1];
+        assert_eq!(second.period, "2021-01-03");
+        assert_eq!(second.end_balance, Decimal::from(1000));
+        assert_eq!(second.funding_gap, Decimal::from(300));
+    }
+
+    #[test]
+    fn test_monthly_roll_up_spans_months_in_order() {
+        // 1月两笔、2月一笔（day>20映射到2月）
+        let transactions = vec![
+            transaction(1, 1000, 0, 1000),
+            transaction(2, 0, 400, 600),
+            transaction(21, 2000, 0, 2600),
+        ];
+
+        let report = PeriodSummaryReport::from_transactions(&transactions);
+        assert_eq!(report.monthly.len(), 2);
+        assert_eq!(report.monthly[0].period, "2021-01");
+        assert_eq!(report.monthly[0].total_inflow, Decimal::from(1000));
+        assert_eq!(report.monthly[0].end_balance, Decimal::from(600));
+        assert_eq!(report.monthly[1].period, "2021-02");
+        assert_eq!(report.monthly[1].end_balance, Decimal::from(2600));
+    }
+}
@@ -0,0 +1,130 @@
+//! 结果摘要哈希（回归测试用）
+//!
+//! 对处理完成的交易列表的计算列取确定性SHA-256哈希：同一份输入在
+//! 同一套算法语义下，不论导出格式、平台或引擎内部实现如何调整，
+//! 哈希都应保持不变。CI与用户可据此在历史案件上验证引擎升级没有
+//! 悄然改变任何数字——哈希一变即说明某个计算列的数值发生了变化。
+//!
+//! 哈希只覆盖算法计算列（占比、行为性质、累计量、余额拆分等），
+//! 不包含输入原始列（它们由输入文件本身保证）；风险标记列同样
+//! 排除在外，因可疑模式检测规则可独立于算法配置调整。
+
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+
+use crate::data_models::Transaction;
+
+/// 摘要哈希覆盖的计算列数（列序固定，加列时需同步更新文档与测试）
+pub const DIGEST_COLUMN_COUNT: usize = 14;
+
+/// 把可选金额规范化为与表示无关的文本（2.50与2.5000哈希一致）
+fn canonical_decimal(value: Option<Decimal>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| v.normalize().to_string())
+}
+
+/// 把可选文本规范化（None与空串区分开）
+fn canonical_text(value: Option<&String>) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("\"{v}\""))
+}
+
+/// 单笔交易计算列的规范化记录（列序固定，`|`分隔）
+fn canonical_record(tx: &Transaction) -> String {
+    [
+        canonical_decimal(tx.personal_ratio),
+        canonical_decimal(tx.company_ratio),
+        canonical_text(tx.behavior_nature.as_ref()),
+        canonical_decimal(tx.cumulative_misappropriation),
+        canonical_decimal(tx.cumulative_advance),
+        canonical_decimal(tx.cumulative_company_principal_returned),
+        canonical_decimal(tx.cumulative_personal_principal_returned),
+        canonical_decimal(tx.cumulative_personal_profit),
+        canonical_decimal(tx.cumulative_company_profit),
+        canonical_decimal(tx.funding_gap),
+        canonical_decimal(tx.personal_balance),
+        canonical_decimal(tx.company_balance),
+        canonical_text(tx.fund_source_breakdown.as_ref()),
+        canonical_text(tx.behavior_explanation.as_ref()),
+    ]
+    .join("|")
+}
+
+/// 计算处理结果的确定性摘要哈希（SHA-256十六进制小写）
+///
+/// 交易按传入顺序逐行取规范化记录，行间以换行分隔后整体取哈希；
+/// 空结果集返回空串的哈希（仍是确定值）
+#[must_use]
+pub fn result_digest(transactions: &[Transaction]) -> String {
+    let mut hasher = Sha256::new();
+    for tx in transactions {
+        hasher.update(canonical_record(tx).as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn processed_transaction(ratio: Decimal, behavior: &str) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(1000),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            "个人应收".to_string(),
+        );
+        tx.personal_ratio = Some(ratio);
+        tx.company_ratio = Some(Decimal::ONE - ratio);
+        tx.behavior_nature = Some(behavior.to_string());
+        tx.cumulative_misappropriation = Some(Decimal::ZERO);
+        tx
+    }
+
+    #[test]
+    fn test_digest_is_stable_and_formatting_independent() {
+        let txs = vec![processed_transaction(Decimal::new(5, 1), "个人支付：1000.00")];
+
+        // 同一数值的不同小数表示（0.5与0.5000）哈希一致
+        let mut reformatted = txs.clone();
+        reformatted[0].personal_ratio = Some(Decimal::new(5000, 4));
+        assert_eq!(result_digest(&txs), result_digest(&reformatted));
+
+        // 重复计算结果一致（确定性）
+        assert_eq!(result_digest(&txs), result_digest(&txs));
+    }
+
+    #[test]
+    fn test_digest_changes_when_computed_value_changes() {
+        let base = vec![processed_transaction(Decimal::new(5, 1), "个人支付：1000.00")];
+
+        let mut changed_ratio = base.clone();
+        changed_ratio[0].personal_ratio = Some(Decimal::new(6, 1));
+        assert_ne!(result_digest(&base), result_digest(&changed_ratio));
+
+        let mut changed_behavior = base.clone();
+        changed_behavior[0].behavior_nature = Some("挪用：1000.00".to_string());
+        assert_ne!(result_digest(&base), result_digest(&changed_behavior));
+
+        // None与空串是不同的状态
+        let mut cleared = base.clone();
+        cleared[0].behavior_nature = Some(String::new());
+        let mut none = base;
+        none[0].behavior_nature = None;
+        assert_ne!(result_digest(&cleared), result_digest(&none));
+    }
+
+    #[test]
+    fn test_digest_ignores_raw_input_and_risk_flags() {
+        let base = vec![processed_transaction(Decimal::new(5, 1), "个人支付：1000.00")];
+
+        // 原始列与风险标记不影响哈希
+        let mut raw_changed = base.clone();
+        raw_changed[0].fund_attribute = "公司应付".to_string();
+        raw_changed[0].risk_flags = Some("整数大额".to_string());
+        assert_eq!(result_digest(&base), result_digest(&raw_changed));
+    }
+}
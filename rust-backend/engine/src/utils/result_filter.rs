@@ -0,0 +1,129 @@
+//! 结果行筛选（聚焦导出）
+//!
+//! 分析完成后按行为性质、金额区间或资金属性筛出行子集，供导出
+//! 聚焦工作簿（例如只含挪用行的专项底稿）。筛选不改动任何计算列，
+//! 行保持原有顺序，累计量仍是全量口径——聚焦工作簿是全量结果的
+//! 行子集，不是重新计算。
+
+use rust_decimal::Decimal;
+
+use crate::algorithms::shared::behavior_record::{parse_behavior_text, BehaviorKind};
+use crate::data_models::Transaction;
+
+/// 按行为性质筛选：保留行为记录中含指定类别的行
+///
+/// 行为性质列无法结构化解析的行（历史数据中的自由文本）退化为
+/// 词面包含匹配，保证老结果文件同样可筛
+#[must_use]
+pub fn filter_by_behavior(transactions: &[Transaction], kind: BehaviorKind) -> Vec<Transaction> {
+    transactions.iter()
+        .filter(|tx| {
+            let Some(text) = tx.behavior_nature.as_deref() else {
+                return false;
+            };
+            match parse_behavior_text(text) {
+                Some(records) => records.iter().any(|record| record.kind == kind),
+                None => text.contains(kind.label_zh()),
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// 按流水金额区间筛选（闭区间，边界可省略）
+///
+/// 流水金额取收入与支出中非零的一侧；收支均为零的行只在未设下限时保留
+#[must_use]
+pub fn filter_by_amount_range(
+    transactions: &[Transaction],
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+) -> Vec<Transaction> {
+    transactions.iter()
+        .filter(|tx| {
+            let amount = if tx.income_amount > Decimal::ZERO {
+                tx.income_amount
+            } else {
+                tx.expense_amount
+            };
+            min.is_none_or(|min| amount >= min) && max.is_none_or(|max| amount <= max)
+        })
+        .cloned()
+        .collect()
+}
+
+/// 按资金属性筛选（词面包含匹配，如"个人"匹配全部个人类属性）
+#[must_use]
+pub fn filter_by_fund_attribute(transactions: &[Transaction], keyword: &str) -> Vec<Transaction> {
+    transactions.iter()
+        .filter(|tx| tx.fund_attribute.contains(keyword))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn tx(income: i64, expense: i64, attribute: &str, behavior: Option<&str>) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        let mut tx = Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(income),
+            Decimal::from(expense),
+            Decimal::from(income - expense),
+            attribute.to_string(),
+        );
+        tx.behavior_nature = behavior.map(str::to_string);
+        tx
+    }
+
+    #[test]
+    fn test_filter_by_behavior_parses_and_falls_back_to_contains() {
+        let transactions = vec![
+            tx(0, 3000, "个人应付", Some("挪用：3000.00")),
+            tx(0, 500, "个人应付", Some("个人支付：500.00")),
+            // 结构化解析失败的自由文本，退化为词面匹配
+            tx(0, 800, "个人应付", Some("理财赎回-朝朝盈（疑似挪用）")),
+            tx(1000, 0, "公司应收", None),
+        ];
+
+        let misappropriation = filter_by_behavior(&transactions, BehaviorKind::Misappropriation);
+        assert_eq!(misappropriation.len(), 2);
+        assert_eq!(misappropriation[0].expense_amount, Decimal::from(3000));
+        assert_eq!(misappropriation[1].expense_amount, Decimal::from(800));
+    }
+
+    #[test]
+    fn test_filter_by_amount_range_uses_flow_amount() {
+        let transactions = vec![
+            tx(100, 0, "个人应收", None),
+            tx(0, 5000, "公司应付", None),
+            tx(20000, 0, "公司应收", None),
+        ];
+
+        let mid = filter_by_amount_range(&transactions,
+            Some(Decimal::from(1000)), Some(Decimal::from(10000)));
+        assert_eq!(mid.len(), 1);
+        assert_eq!(mid[0].expense_amount, Decimal::from(5000));
+
+        // 只设下限
+        let large = filter_by_amount_range(&transactions, Some(Decimal::from(1000)), None);
+        assert_eq!(large.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_fund_attribute_contains_match() {
+        let transactions = vec![
+            tx(100, 0, "个人应收", None),
+            tx(0, 200, "个人应付", None),
+            tx(300, 0, "公司应收", None),
+        ];
+
+        assert_eq!(filter_by_fund_attribute(&transactions, "个人").len(), 2);
+        assert_eq!(filter_by_fund_attribute(&transactions, "应收").len(), 2);
+        assert!(filter_by_fund_attribute(&transactions, "投资").is_empty());
+    }
+}
@@ -12,49 +12,85 @@ pub struct TimeProcessor;
 
 impl TimeProcessor {
     /// 解析Excel日期
-    /// 对应Python版本的data_processor.py中的日期处理逻辑
+    /// `对应Python版本的data_processor.py中的日期处理逻辑`
     pub fn parse_excel_date(cell: &calamine::Data) -> AuditResult<NaiveDateTime> {
         match cell {
             calamine::Data::DateTime(dt) => {
-                // Excel日期时间格式转换
-                // Excel的日期是从1900年1月1日开始的天数
-                let excel_epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
-                let days = dt.as_f64() as i64;
-                let nanos = ((dt.as_f64() - days as f64) * 86_400_000_000_000f64) as i64;
-                
-                // Excel有个bug：1900年2月29日不存在，但Excel认为存在，所以要减2天
-                let actual_days = if days > 59 { days - 2 } else { days - 1 };
-                
-                let date = excel_epoch + chrono::Duration::days(actual_days);
-                let time = chrono::Duration::nanoseconds(nanos);
-                Ok(date.and_hms_opt(0, 0, 0).unwrap() + time)
+                Self::parse_excel_serial(dt.as_f64())
             },
             calamine::Data::String(date_str) => {
                 Self::parse_date_string(date_str)
             }
             calamine::Data::Float(days) => {
                 // Excel日期数字格式
-                let base_date = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
-                let target_date = base_date + chrono::Duration::days(*days as i64 - 2);
-                Ok(target_date.and_hms_opt(0, 0, 0).unwrap())
+                Self::parse_excel_serial(*days)
+            }
+            calamine::Data::Int(days) => {
+                Self::parse_excel_serial(*days as f64)
             }
             _ => Err(AuditError::time_parse_error("不支持的日期格式"))
         }
     }
-    
+
+    /// 解析Excel日期序列号
+    ///
+    /// Excel的日期是从1900年1月1日开始的天数（序列号1对应1900-01-01）。
+    /// 序列号小于1没有对应日期，静默回退会产生虚假的最早记录，
+    /// 影响初始余额判断，因此直接返回解析错误。
+    fn parse_excel_serial(serial: f64) -> AuditResult<NaiveDateTime> {
+        let days = serial as i64;
+        if days < 1 {
+            return Err(AuditError::time_parse_error(
+                format!("无效的Excel日期序列号: {serial}")
+            ));
+        }
+
+        let excel_epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        let nanos = ((serial - days as f64) * 86_400_000_000_000f64) as i64;
+
+        // Excel有个bug：1900年2月29日不存在，但Excel认为存在，
+        // 所以1900-03-01（序列号61）之后要减2天，之前只减1天
+        let actual_days = if days > 59 { days - 2 } else { days - 1 };
+
+        let date = excel_epoch + chrono::Duration::days(actual_days);
+        let time = chrono::Duration::nanoseconds(nanos);
+        Ok(date.and_hms_opt(0, 0, 0).unwrap() + time)
+    }
+
     /// 解析日期字符串
     fn parse_date_string(date_str: &str) -> AuditResult<NaiveDateTime> {
+        let date_str = date_str.trim();
+
+        // 纯8位数字按yyyymmdd处理（chrono的%Y会贪婪吞掉全部数字，需要单独切分）
+        if date_str.len() == 8 && date_str.chars().all(|c| c.is_ascii_digit()) {
+            if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y%m%d") {
+                return Ok(date.and_hms_opt(0, 0, 0).unwrap());
+            }
+            if let (Ok(year), Ok(month), Ok(day)) = (
+                date_str[..4].parse::<i32>(),
+                date_str[4..6].parse::<u32>(),
+                date_str[6..8].parse::<u32>(),
+            ) {
+                if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                    return Ok(date.and_hms_opt(0, 0, 0).unwrap());
+                }
+            }
+            return Err(AuditError::time_parse_error(format!("无法解析日期: {date_str}")));
+        }
+
         // 尝试多种日期格式
         let formats = [
             "%Y-%m-%d",
             "%Y/%m/%d",
+            "%Y.%m.%d",
             "%Y年%m月%d日",
             "%m/%d/%Y",
             "%d/%m/%Y",
             "%Y-%m-%d %H:%M:%S",
             "%Y/%m/%d %H:%M:%S",
+            "%Y年%m月%d日 %H:%M:%S",
         ];
-        
+
         for format in &formats {
             // 先尝试带时间的格式
             if let Ok(datetime) = NaiveDateTime::parse_from_str(date_str, format) {
@@ -66,11 +102,12 @@ impl TimeProcessor {
             }
         }
         
-        Err(AuditError::time_parse_error(format!("无法解析日期: {}", date_str)))
+        Err(AuditError::time_parse_error(format!("无法解析日期: {date_str}")))
     }
     
     /// 解析交易时间
     /// 对应Python版本FlowAnalyzer.解析交易时间方法
+    #[must_use] 
     pub fn parse_transaction_time(time_value: &calamine::Data) -> String {
         match time_value {
             calamine::Data::Empty => "00:00:00".to_string(),
@@ -78,7 +115,7 @@ impl TimeProcessor {
                 Self::format_time_string(time_str)
             }
             calamine::Data::Int(time_int) => {
-                Self::format_time_from_number(*time_int as i64)
+                Self::format_time_from_number(*time_int)
             }
             calamine::Data::Float(time_float) => {
                 Self::format_time_from_number(*time_float as i64)
@@ -111,7 +148,7 @@ impl TimeProcessor {
             return "00:00:00".to_string();
         }
         
-        let time_str = format!("{:06}", time_num); // 补齐到6位
+        let time_str = format!("{time_num:06}"); // 补齐到6位
         if time_str.len() >= 6 {
             let hour = &time_str[..2];
             let minute = &time_str[2..4];
@@ -120,7 +157,7 @@ impl TimeProcessor {
             // 验证时间有效性
             if let (Ok(h), Ok(m), Ok(s)) = (hour.parse::<u32>(), minute.parse::<u32>(), second.parse::<u32>()) {
                 if h < 24 && m < 60 && s < 60 {
-                    return format!("{}:{}:{}", hour, minute, second);
+                    return format!("{hour}:{minute}:{second}");
                 }
             }
         }
@@ -130,6 +167,7 @@ impl TimeProcessor {
     
     /// 创建完整时间戳
     /// 对应Python版本FlowAnalyzer.创建完整时间戳方法
+    #[must_use] 
     pub fn create_complete_timestamp(date: NaiveDateTime, time_str: &str) -> NaiveDateTime {
         // 解析时间字符串
         if let Ok(time) = Self::parse_time_from_string(time_str) {
@@ -138,7 +176,7 @@ impl TimeProcessor {
         }
         
         // 如果时间解析失败，返回原日期
-        warn!("创建时间戳失败，时间字符串: {}", time_str);
+        warn!("创建时间戳失败，时间字符串: {time_str}");
         date
     }
     
@@ -165,15 +203,16 @@ impl TimeProcessor {
             }
         }
         
-        Err(AuditError::time_parse_error(format!("无法解析时间: {}", time_str)))
+        Err(AuditError::time_parse_error(format!("无法解析时间: {time_str}")))
     }
     
     /// 验证时间戳合理性
+    #[must_use] 
     pub fn validate_timestamp(timestamp: &NaiveDateTime) -> bool {
         // 检查年份是否在合理范围内（2000-2050）
         let year = timestamp.year();
-        if year < 2000 || year > 2050 {
-            debug!("时间戳年份超出合理范围: {}", year);
+        if !(2000..=2050).contains(&year) {
+            debug!("时间戳年份超出合理范围: {year}");
             return false;
         }
         
@@ -229,6 +268,42 @@ mod tests {
         // 测试无效格式
         assert!(TimeProcessor::parse_date_string("invalid").is_err());
     }
+
+    #[test]
+    fn test_parse_date_string_extended_formats() {
+        // yyyy.mm.dd格式
+        let dotted = TimeProcessor::parse_date_string("2023.01.15").unwrap();
+        assert_eq!(dotted.date(), NaiveDate::from_ymd_opt(2023, 1, 15).unwrap());
+
+        // yyyymmdd紧凑格式
+        let compact = TimeProcessor::parse_date_string("20230115").unwrap();
+        assert_eq!(compact.date(), NaiveDate::from_ymd_opt(2023, 1, 15).unwrap());
+
+        // 带首尾空白
+        assert!(TimeProcessor::parse_date_string(" 2023-01-15 ").is_ok());
+
+        // 8位数字但不是有效日期
+        assert!(TimeProcessor::parse_date_string("20231345").is_err());
+    }
+
+    #[test]
+    fn test_parse_excel_serial() {
+        // 序列号1对应1900-01-01
+        let first = TimeProcessor::parse_excel_serial(1.0).unwrap();
+        assert_eq!(first.date(), NaiveDate::from_ymd_opt(1900, 1, 1).unwrap());
+
+        // 1900-03-01之前（序列号59 = 1900-02-28，不受闰年bug影响）
+        let before_bug = TimeProcessor::parse_excel_serial(59.0).unwrap();
+        assert_eq!(before_bug.date(), NaiveDate::from_ymd_opt(1900, 2, 28).unwrap());
+
+        // 1900-03-01之后需要修正闰年bug（序列号61 = 1900-03-01）
+        let after_bug = TimeProcessor::parse_excel_serial(61.0).unwrap();
+        assert_eq!(after_bug.date(), NaiveDate::from_ymd_opt(1900, 3, 1).unwrap());
+
+        // 序列号小于1不再静默回退，直接报错
+        assert!(TimeProcessor::parse_excel_serial(0.0).is_err());
+        assert!(TimeProcessor::parse_excel_serial(-5.0).is_err());
+    }
     
     #[test]
     fn test_validate_timestamp() {
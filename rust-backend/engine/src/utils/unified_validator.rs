@@ -0,0 +1,1128 @@
+//! 统一数据验证器模块
+//! 合并Python版本中分散的验证逻辑，专注于流水完整性验证和修复
+//! 
+//! Python来源: 
+//! - `src/utils/flow_integrity_validator.py:FlowIntegrityValidator` (主要功能)
+//! - `src/utils/validators.py:DataValidator::validate_required_columns`
+//! - `src/utils/data_processor.py` 验证相关函数
+//! 
+//! 移除功能: 大额交易验证、日期范围验证(用户明确要求移除)
+
+use crate::data_models::{SameTimeOrderingConfig, SameTimeOrderingStrategy, Transaction};
+use crate::errors::{AuditError, AuditResult};
+use crate::utils::logger::AuditLogger;
+use rust_decimal::Decimal;
+use chrono::{DateTime, Utc};
+use log::{info, warn, error, debug};
+
+/// 验证结果结构
+#[derive(Debug, Clone)]
+pub struct ValidationResult {
+    /// 是否验证通过
+    pub is_valid: bool,
+    /// 错误数量
+    pub errors_count: usize,
+    /// 修复次数
+    pub optimizations_count: usize,
+    /// 修复是否失败
+    pub optimization_failed: bool,
+    /// 是否有数据修改
+    pub has_modifications: bool,
+    /// 验证错误列表
+    pub errors: Vec<ValidationError>,
+    /// 金额精度/量级异常警告（不影响验证通过与否）
+    pub scale_warnings: Vec<ValidationError>,
+    /// 修复后的交易数据(如果有修复)
+    pub fixed_transactions: Option<Vec<Transaction>>,
+    /// 机器可读的修复报告
+    pub report: ValidationReport,
+    /// 验证总结
+    pub summary: String,
+}
+
+/// 单次行移动（原行号→新行号，均1开始）
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RowMove {
+    pub from_row: usize,
+    pub to_row: usize,
+}
+
+/// 修复建议（咨询模式产物，不改动数据）
+///
+/// 描述一处余额断点与拟执行的同时间交易重排，
+/// 供GUI逐条展示让用户采纳或拒绝后再正式分析
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RepairSuggestion {
+    /// 建议编号（0开始，按发现顺序）
+    pub id: usize,
+    /// 发现余额断点的行号（1开始）
+    pub problem_row: usize,
+    /// 按上笔余额推算的期望余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub expected_balance: Decimal,
+    /// 流水中实际记录的余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub actual_balance: Decimal,
+    /// 拟执行的行移动列表
+    pub row_moves: Vec<RowMove>,
+    /// 人类可读说明
+    pub description: String,
+}
+
+/// 单个被重排修复的同时间交易组
+///
+/// 行号均为1开始的数据行号；`original_order`即文件中的原始顺序，
+/// `new_order`为修复后各位置放置的原行号
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RepairedGroup {
+    /// 发现余额断点的行号
+    pub problem_row: usize,
+    /// 涉及的同时间交易行号（重排前顺序）
+    pub original_order: Vec<usize>,
+    /// 修复后顺序
+    pub new_order: Vec<usize>,
+    /// 断点处按上笔余额推算的期望余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub expected_balance: Decimal,
+    /// 断点处实际记录的余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub actual_balance: Decimal,
+    /// 余额差（实际 - 期望）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub balance_delta: Decimal,
+}
+
+/// 全程余额核对中发现的不符区间
+///
+/// 从首行起按收支金额重算累计余额并与余额列对账；重算不随余额列
+/// 回表，因此一处缺行会让其后所有行带上同一个累计差额——连续且
+/// 差额相同的行合并为一个区间，每个区间通常对应一处缺失或多记的交易
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReconciliationMismatch {
+    /// 区间起始行号（1开始）
+    pub start_row: usize,
+    /// 区间结束行号（含）
+    pub end_row: usize,
+    /// 起始行按收支重算的期望余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub expected_balance: Decimal,
+    /// 起始行实际记录的余额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub actual_balance: Decimal,
+    /// 累计差额（实际 - 重算）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub balance_delta: Decimal,
+}
+
+/// 机器可读的验证修复报告
+///
+/// 此前重排修复只散落在日志文本里，用户无从核对"到底改了什么"。
+/// 本报告逐组记录修复内容，随验证结果返回并可导出为工作表
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ValidationReport {
+    /// 验证的总行数
+    pub total_rows: usize,
+    /// 未能修复的错误数
+    pub errors_count: usize,
+    /// 成功修复的组数
+    pub repair_count: usize,
+    /// 各修复组明细
+    pub repaired_groups: Vec<RepairedGroup>,
+    /// 全程余额核对发现的不符区间（余额列与按收支重算的余额偏差超过容差）
+    #[serde(default)]
+    pub reconciliation_mismatches: Vec<ReconciliationMismatch>,
+}
+
+/// 验证错误信息
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// 错误行号(1-based)
+    pub row: usize,
+    /// 错误消息
+    pub message: String,
+    /// 错误时间戳
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 统一数据验证器
+/// Python来源: `src/utils/flow_integrity_validator.py:FlowIntegrityValidator`
+#[derive(Debug)]
+pub struct UnifiedValidator {
+    /// 余额容差
+    /// Python来源: `flow_integrity_validator.py:18` `self.tolerance = 0.01`
+    tolerance: Decimal,
+    /// 验证错误列表
+    validation_errors: Vec<ValidationError>,
+    /// 优化计数
+    optimization_count: usize,
+    /// 优化失败标志
+    optimization_failed: bool,
+    /// 金额精度/量级异常警告
+    scale_warnings: Vec<ValidationError>,
+    /// 本次验证中被重排修复的同时间交易组
+    repaired_groups: Vec<RepairedGroup>,
+    /// 同时间戳交易排序策略（不同银行对同秒交易的落库顺序保证不同）
+    ordering: SameTimeOrderingConfig,
+    /// 日志记录器
+    #[allow(dead_code)]
+    logger: AuditLogger,
+}
+
+impl UnifiedValidator {
+    /// 创建新的统一验证器
+    /// Python来源: flow_integrity_validator.py:16-21 `__init__`
+    #[must_use] 
+    pub fn new() -> Self {
+        Self {
+            tolerance: Decimal::from_f64_retain(0.01).unwrap_or_default(),
+            validation_errors: Vec::new(),
+            optimization_count: 0,
+            optimization_failed: false,
+            scale_warnings: Vec::new(),
+            repaired_groups: Vec::new(),
+            ordering: SameTimeOrderingConfig::default(),
+            logger: AuditLogger::new("UnifiedValidator"),
+        }
+    }
+
+    /// 指定同时间戳交易的排序策略（默认贪心余额链修复）
+    #[must_use]
+    pub fn with_same_time_ordering(mut self, ordering: SameTimeOrderingConfig) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// 验证必需列
+    /// Python来源: `src/utils/validators.py:DataValidator::validate_required_columns`
+    pub fn validate_required_columns(&self, transactions: &[Transaction]) -> AuditResult<()> {
+        if transactions.is_empty() {
+            return Err(AuditError::validation_error("数据为空"));
+        }
+
+        // 基本数据完整性检查 - 所有交易都应该有必要的字段
+        // 逐行检查相互独立，大数据集并行扫描；仍报最靠前的问题行
+        let missing_time = crate::optimizations::parallel::indexed_map(
+            transactions,
+            |_, transaction| transaction.transaction_time.is_empty(),
+        );
+        if let Some(index) = missing_time.iter().position(|missing| *missing) {
+            return Err(AuditError::validation_error(
+                format!("第{}行缺少交易时间", index + 1)
+            ));
+        }
+
+        info!("✅ 必需列验证通过, 共{}条记录", transactions.len());
+        Ok(())
+    }
+
+    /// 验证流水完整性并尝试修复
+    /// Python来源: flow_integrity_validator.py:23-70 `validate_flow_integrity`
+    pub fn validate_flow_integrity(&mut self, transactions: &[Transaction]) -> AuditResult<ValidationResult> {
+        info!("开始原始流水完整性验证...");
+        
+        // 重置状态
+        self.validation_errors.clear();
+        self.optimization_count = 0;
+        self.optimization_failed = false;
+        self.scale_warnings.clear();
+        self.repaired_groups.clear();
+
+        if transactions.is_empty() {
+            return Ok(ValidationResult {
+                is_valid: true,
+                errors_count: 0,
+                optimizations_count: 0,
+                optimization_failed: false,
+                has_modifications: false,
+                errors: Vec::new(),
+                scale_warnings: Vec::new(),
+                fixed_transactions: None,
+                report: ValidationReport::default(),
+                summary: "数据为空，无需验证".to_string(),
+            });
+        }
+
+        // 创建副本用于验证和修复，保持源数据不变
+        let mut result_transactions = transactions.to_vec();
+
+        // 逐行验证余额连贯性
+        // Python来源: flow_integrity_validator.py:45-68 主验证循环
+        for i in 1..result_transactions.len() {
+            if !self.check_balance_continuity(&result_transactions[i-1], &result_transactions[i], i)? {
+                // 余额不连贯，尝试修复
+                if let Some(fixed_transactions) = self.attempt_reorder_fix(&result_transactions, i)? {
+                    info!("✅ 第{}行余额问题已通过重排序修复", i + 1);
+                    self.optimization_count += 1;
+                    result_transactions = fixed_transactions;
+                    
+                    // 重新验证修复后的行
+                    if !self.check_balance_continuity(&result_transactions[i-1], &result_transactions[i], i)? {
+                        self.record_error(i, "重排序后仍无法修复余额连贯性");
+                        self.optimization_failed = true;
+                        break;
+                    }
+                } else {
+                    self.record_error(i, "余额不连贯且无法通过重排序修复，可能存在数据丢失");
+                    self.optimization_failed = true;
+                    break;
+                }
+            }
+        }
+
+        // 生成验证报告
+        self.generate_validation_report(&result_transactions, transactions)
+    }
+
+    /// 检查两笔交易间的余额连贯性
+    /// Python来源: flow_integrity_validator.py:72-115 `_check_balance_continuity`
+    fn check_balance_continuity(&self, previous: &Transaction, current: &Transaction, row_idx: usize) -> AuditResult<bool> {
+        let prev_balance = previous.balance;
+        let curr_balance = current.balance;
+        let income = current.income_amount;
+        let expense = current.expense_amount;
+
+        // 计算期望余额：上一笔余额 + 收入 - 支出
+        let expected_balance = prev_balance + income - expense;
+
+        // 检查是否在容差范围内
+        let difference = (curr_balance - expected_balance).abs();
+        if difference <= self.tolerance {
+            Ok(true)
+        } else {
+            warn!(
+                "第{}行余额不连贯: 上笔余额{} + 收入{} - 支出{} = 期望{}, 实际{}, 差异{}",
+                row_idx + 1, prev_balance, income, expense, expected_balance, curr_balance, curr_balance - expected_balance
+            );
+            Ok(false)
+        }
+    }
+
+    /// 尝试通过重新排序同时间交易来修复余额问题
+    /// Python来源: flow_integrity_validator.py:137-177 `_attempt_reorder_fix`
+    fn attempt_reorder_fix(&mut self, transactions: &[Transaction], problem_row_idx: usize) -> AuditResult<Option<Vec<Transaction>>> {
+        // 创建完整时间戳用于比较(组合日期和时间)
+        let current_datetime = self.create_full_timestamp(&transactions[problem_row_idx])?;
+
+        // 找出所有同时间交易
+        let same_time_indices: Vec<usize> = transactions.iter().enumerate()
+            .filter_map(|(idx, tx)| {
+                if let Ok(datetime) = self.create_full_timestamp(tx) {
+                    if datetime == current_datetime {
+                        Some(idx)
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if same_time_indices.len() <= 1 {
+            info!("第{}行无同时间交易，无法重排序修复", problem_row_idx + 1);
+            return Ok(None);
+        }
+
+        info!("发现第{}行有{}笔同时间交易，尝试重排序...", problem_row_idx + 1, same_time_indices.len());
+
+        // 尝试找到最佳排序
+        if let Some(best_order) = self.find_best_order(transactions, &same_time_indices)? {
+            if best_order != same_time_indices {
+                let fixed_transactions = self.create_reordered_transactions(transactions, &same_time_indices, &best_order);
+                // 记入修复报告（断点行号从1起；问题行由调用方保证大于0）
+                let expected_balance = transactions[problem_row_idx - 1].balance
+                    + transactions[problem_row_idx].income_amount
+                    - transactions[problem_row_idx].expense_amount;
+                let actual_balance = transactions[problem_row_idx].balance;
+                self.repaired_groups.push(RepairedGroup {
+                    problem_row: problem_row_idx + 1,
+                    original_order: same_time_indices.iter().map(|&idx| idx + 1).collect(),
+                    new_order: best_order.iter().map(|&idx| idx + 1).collect(),
+                    expected_balance,
+                    actual_balance,
+                    balance_delta: actual_balance - expected_balance,
+                });
+                info!("✅ 成功重排序交易");
+                return Ok(Some(fixed_transactions));
+            }
+        }
+
+        warn!("❌ 未找到有效的重排序方案");
+        Ok(None)
+    }
+
+    /// 找到最佳的交易排序(按配置的排序策略)
+    /// Python来源: flow_integrity_validator.py:179-206 `_find_best_order`
+    fn find_best_order(&self, transactions: &[Transaction], indices: &[usize]) -> AuditResult<Option<Vec<usize>>> {
+        match self.ordering.strategy {
+            SameTimeOrderingStrategy::GreedyBalanceChain => {
+                info!("使用贪心策略寻找正确顺序，共{}笔同时间交易...", indices.len());
+
+                if let Some(result_order) = self.greedy_order_search(transactions, indices)? {
+                    info!("✅ 贪心策略找到正确顺序");
+                    Ok(Some(result_order))
+                } else {
+                    warn!("❌ 贪心策略未找到有效顺序");
+                    Ok(None)
+                }
+            }
+            SameTimeOrderingStrategy::KeepOriginal => {
+                info!("按配置保持原始顺序，不重排{}笔同时间交易", indices.len());
+                Ok(None)
+            }
+            SameTimeOrderingStrategy::IncomeFirst => {
+                info!("按收入先于支出重排{}笔同时间交易", indices.len());
+                Ok(Some(Self::income_first_order(transactions, indices)))
+            }
+            SameTimeOrderingStrategy::TiebreakColumn => {
+                self.tiebreak_column_order(transactions, indices)
+            }
+        }
+    }
+
+    /// 收入行先于支出行的固定顺序（同为收入/支出时保持原始相对顺序）
+    fn income_first_order(transactions: &[Transaction], indices: &[usize]) -> Vec<usize> {
+        let mut order: Vec<usize> = indices.iter().copied()
+            .filter(|&idx| transactions[idx].is_income())
+            .collect();
+        order.extend(indices.iter().copied().filter(|&idx| !transactions[idx].is_income()));
+        order
+    }
+
+    /// 按配置的仲裁列（如流水号）排序；任一行缺少仲裁值时放弃重排
+    fn tiebreak_column_order(&self, transactions: &[Transaction], indices: &[usize]) -> AuditResult<Option<Vec<usize>>> {
+        let column = self.ordering.tiebreak_column.as_deref().unwrap_or("流水号");
+        info!("按仲裁列\"{column}\"重排{}笔同时间交易", indices.len());
+
+        let mut keyed = Vec::with_capacity(indices.len());
+        for &idx in indices {
+            if transactions[idx].sequence_number.is_none() {
+                warn!("❌ 第{}行未读到仲裁列\"{column}\"的值，放弃按仲裁列重排", idx + 1);
+                return Ok(None);
+            }
+            keyed.push((transactions[idx].sequence_sort_key(), idx));
+        }
+        // 排序键带行索引，仲裁值相同的行保持原始相对顺序
+        keyed.sort();
+        Ok(Some(keyed.into_iter().map(|(_, idx)| idx).collect()))
+    }
+
+    /// 使用贪心策略寻找正确的交易顺序
+    /// Python来源: flow_integrity_validator.py:208-278 `_greedy_order_search`
+    fn greedy_order_search(&self, transactions: &[Transaction], indices: &[usize]) -> AuditResult<Option<Vec<usize>>> {
+        if indices.len() <= 1 {
+            return Ok(Some(indices.to_vec()));
+        }
+
+        let min_pos = *indices.iter().min().unwrap();
+        
+        // 如果第一个位置是索引0，无法获取前一行
+        if min_pos == 0 {
+            return Ok(Some(indices.to_vec()));
+        }
+
+        // 获取前一行数据用于计算期望余额
+        let prev_balance = transactions[min_pos - 1].balance;
+        
+        let mut result_order = Vec::new();
+        let mut remaining_indices = indices.to_vec();
+        let mut current_balance = prev_balance;
+
+        // 逐步构建正确顺序
+        for position in 0..indices.len() {
+            let mut found_next = false;
+            
+            debug!("  寻找第{}笔交易，当前余额: {}", position + 1, current_balance);
+
+            // 在剩余交易中找到下一笔符合余额连贯性的交易
+            for (i, &candidate_idx) in remaining_indices.iter().enumerate() {
+                let candidate = &transactions[candidate_idx];
+                
+                let income = candidate.income_amount;
+                let expense = candidate.expense_amount;
+                let expected_balance = current_balance + income - expense;
+                let actual_balance = candidate.balance;
+
+                // 检查是否符合余额连贯性
+                if (actual_balance - expected_balance).abs() <= self.tolerance {
+                    // 找到符合的交易
+                    result_order.push(candidate_idx);
+                    remaining_indices.remove(i);
+                    current_balance = actual_balance;
+                    found_next = true;
+                    
+                    info!("    ✅ 找到第{}笔: 第{}行, 收入{}, 支出{}, 余额{}",
+                          position + 1, candidate_idx + 1, income, expense, actual_balance);
+                    break;
+                }
+                debug!("    ❌ 第{}行不符合: 期望{}, 实际{}, 差异{}",
+                       candidate_idx + 1, expected_balance, actual_balance, actual_balance - expected_balance);
+            }
+
+            if !found_next {
+                warn!("  ❌ 无法找到第{}笔符合余额连贯性的交易", position + 1);
+                return Ok(None);
+            }
+        }
+
+        info!("✅ 贪心策略成功找到完整顺序");
+        Ok(Some(result_order))
+    }
+
+    /// 创建重新排序后的交易数据
+    /// Python来源: flow_integrity_validator.py:319-349 `_create_reordered_dataframe`
+    fn create_reordered_transactions(&self, transactions: &[Transaction], original_indices: &[usize], new_order: &[usize]) -> Vec<Transaction> {
+        let mut result = transactions.to_vec();
+        
+        // 按原始位置更新数据
+        let mut sorted_positions = original_indices.to_vec();
+        sorted_positions.sort_unstable();
+        
+        for (i, &new_idx) in new_order.iter().enumerate() {
+            if let (Some(&pos), Some(new_data)) = (sorted_positions.get(i), transactions.get(new_idx)) {
+                if let Some(target) = result.get_mut(pos) {
+                    *target = new_data.clone();
+                }
+            }
+        }
+        
+        result
+    }
+
+    /// 记录验证错误
+    /// Python来源: flow_integrity_validator.py:351-359 `_record_error`
+    fn record_error(&mut self, row_idx: usize, message: &str) {
+        let error = ValidationError {
+            row: row_idx + 1,
+            message: message.to_string(),
+            timestamp: Utc::now(),
+        };
+        self.validation_errors.push(error);
+        error!("第{}行: {}", row_idx + 1, message);
+    }
+
+    /// 生成验证报告
+    /// Python来源: flow_integrity_validator.py:361-401 `_generate_validation_report`
+    fn generate_validation_report(&self, result_transactions: &[Transaction], original_transactions: &[Transaction]) -> AuditResult<ValidationResult> {
+        let has_modifications = self.optimization_count > 0;
+        let is_valid = self.validation_errors.is_empty();
+        
+        let summary = format!("验证完成: {}行数据, {}个错误, {}次重排序修复",
+                            original_transactions.len(), self.validation_errors.len(), self.optimization_count);
+
+        // 输出摘要
+        info!("{}", "=".repeat(60));
+        info!("原始流水完整性验证报告");
+        info!("{}", "=".repeat(60));
+        info!("总行数: {}", original_transactions.len());
+        info!("发现错误: {}个", self.validation_errors.len());
+        info!("成功修复: {}个", self.optimization_count);
+
+        if self.optimization_failed {
+            warn!("⚠️  优化失败 - 源文件保持不变");
+            warn!("建议：请检查数据完整性，可能存在缺失交易或数据错误");
+            warn!("解决方案：");
+            warn!("1. 检查银行流水数据是否完整");
+            warn!("2. 确认是否有遗漏的交易记录");
+            warn!("3. 验证余额计算是否正确");
+        }
+
+        info!("验证结果: {}", if is_valid { "✅ 通过" } else { "❌ 失败" });
+        info!("数据修改: {}", if has_modifications { "是" } else { "否（源文件保持只读）" });
+
+        if !self.validation_errors.is_empty() {
+            info!("\n错误详情:");
+            for error in &self.validation_errors {
+                error!("  第{}行: {}", error.row, error.message);
+            }
+        }
+
+        if !self.scale_warnings.is_empty() {
+            warn!("金额精度/量级异常: {}处", self.scale_warnings.len());
+            for warning in &self.scale_warnings {
+                warn!("  第{}行起: {}", warning.row, warning.message);
+            }
+        }
+
+        // 全程余额核对在重排修复后的序列上进行，避免顺序问题被误报为缺行
+        let reconciliation_mismatches = self.reconcile_running_balance(result_transactions);
+        if !reconciliation_mismatches.is_empty() {
+            warn!("余额核对不符: {}处区间（余额列与按收支重算的余额偏差超过容差）", reconciliation_mismatches.len());
+            for mismatch in &reconciliation_mismatches {
+                warn!("  第{}-{}行: 重算余额{}, 实际{}, 累计差额{}",
+                      mismatch.start_row, mismatch.end_row,
+                      mismatch.expected_balance, mismatch.actual_balance, mismatch.balance_delta);
+            }
+        }
+
+        info!("{}", "=".repeat(60));
+
+        Ok(ValidationResult {
+            is_valid,
+            errors_count: self.validation_errors.len(),
+            optimizations_count: self.optimization_count,
+            optimization_failed: self.optimization_failed,
+            has_modifications,
+            errors: self.validation_errors.clone(),
+            scale_warnings: self.scale_warnings.clone(),
+            fixed_transactions: if has_modifications { Some(result_transactions.to_vec()) } else { None },
+            report: ValidationReport {
+                total_rows: original_transactions.len(),
+                errors_count: self.validation_errors.len(),
+                repair_count: self.optimization_count,
+                repaired_groups: self.repaired_groups.clone(),
+                reconciliation_mismatches,
+            },
+            summary,
+        })
+    }
+
+    /// 创建完整时间戳（组合日期和时间）
+    /// Python来源: `flow_integrity_validator.py使用` '完整时间戳' 字段进行比较
+    fn create_full_timestamp(&self, transaction: &Transaction) -> AuditResult<DateTime<Utc>> {
+        // 使用时间处理器来解析和组合时间
+        // 这里简化处理，直接使用transaction_date作为完整时间戳的基础
+        let base_date = transaction.transaction_date.date();
+        
+        // 解析时间字符串并创建完整的DateTime
+        let datetime = base_date.and_hms_opt(0, 0, 0)
+            .ok_or_else(|| AuditError::validation_error("无法创建时间戳"))?
+            .and_utc();
+            
+        Ok(datetime)
+    }
+
+    /// 兼容方法：验证交易数据
+    /// 提供与原Validator接口兼容的方法
+    pub fn validate_transactions(&mut self, transactions: &[Transaction]) -> AuditResult<ValidationResult> {
+        // 先验证必需列
+        self.validate_required_columns(transactions)?;
+        
+        // 金额精度/量级异常检查（仅产生警告，不阻断分析）
+        let scale_warnings = self.check_scale_anomalies(transactions);
+        
+        // 然后进行流水完整性验证
+        let mut result = self.validate_flow_integrity(transactions)?;
+        result.scale_warnings = scale_warnings;
+        Ok(result)
+    }
+
+    /// 金额精度与量级异常检查
+    /// 
+    /// 在正式分析前运行，标记两类可疑数据：
+    /// 1. 精度噪声：金额小数位超过余额精度（2位），疑似换算残留
+    /// 2. 量级突变：金额相对近期中位数突变约×100，疑似元/分单位混用
+    #[must_use]
+    pub fn check_scale_anomalies(&self, transactions: &[Transaction]) -> Vec<ValidationError> {
+        const MAGNITUDE_WINDOW: usize = 10;
+        let shift_factor = Decimal::from(100);
+
+        // 精度噪声检查逐行独立，大数据集并行扫描；结果按行序收集
+        let mut warnings: Vec<ValidationError> = crate::optimizations::parallel::indexed_map(
+            transactions,
+            |idx, tx| {
+                let mut row_warnings = Vec::new();
+                // 精度噪声检查：收入/支出/余额任一字段小数位超过2位
+                for (field_name, value) in [
+                    ("收入", tx.income_amount),
+                    ("支出", tx.expense_amount),
+                    ("余额", tx.balance),
+                ] {
+                    if value != Decimal::ZERO && value.normalize().scale() > 2 {
+                        row_warnings.push(ValidationError {
+                            row: idx + 1,
+                            message: format!("{field_name}金额{value}存在超过余额精度的分位噪声，疑似换算残留"),
+                            timestamp: Utc::now(),
+                        });
+                    }
+                }
+                row_warnings
+            },
+        ).into_iter().flatten().collect();
+
+        // 量级突变行集合（用于合并为区间报告）
+        // 滑动窗口依赖前序行的中位数，该部分保持顺序执行
+        let mut shift_rows: Vec<usize> = Vec::new();
+        let mut recent_amounts: Vec<Decimal> = Vec::new();
+
+        for (idx, tx) in transactions.iter().enumerate() {
+            let amount = if tx.income_amount > Decimal::ZERO {
+                tx.income_amount
+            } else {
+                tx.expense_amount
+            };
+
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+
+            // 量级突变检查：与近期非零金额的中位数比较
+            if recent_amounts.len() >= 3 {
+                let mut sorted = recent_amounts.clone();
+                sorted.sort_unstable();
+                let median = sorted[sorted.len() / 2];
+                if median > Decimal::ZERO
+                    && (amount >= median * shift_factor || amount * shift_factor <= median)
+                {
+                    shift_rows.push(idx + 1);
+                }
+            }
+
+            recent_amounts.push(amount);
+            if recent_amounts.len() > MAGNITUDE_WINDOW {
+                recent_amounts.remove(0);
+            }
+        }
+
+        // 将连续的量级突变行合并为区间
+        let mut i = 0;
+        while i < shift_rows.len() {
+            let start = shift_rows[i];
+            let mut end = start;
+            while i + 1 < shift_rows.len() && shift_rows[i + 1] == shift_rows[i] + 1 {
+                i += 1;
+                end = shift_rows[i];
+            }
+            let range_desc = if start == end {
+                format!("第{start}行")
+            } else {
+                format!("第{start}-{end}行")
+            };
+            warnings.push(ValidationError {
+                row: start,
+                message: format!("{range_desc}金额量级相对近期交易突变约×100，疑似元/分单位混用"),
+                timestamp: Utc::now(),
+            });
+            i += 1;
+        }
+
+        warnings
+    }
+
+    /// 全程余额核对
+    ///
+    /// 以首行余额为基准，逐行按"收入-支出"重算累计余额并与余额列对账。
+    /// 与逐行连贯性检查不同，重算不随余额列回表，也不在首个断点处停下：
+    /// 一处缺行会让其后所有行带上同一个累计差额，差额的每次变化都指向
+    /// 一处新的缺失或多记，连续且差额相同的行合并为一个区间报告
+    #[must_use]
+    pub fn reconcile_running_balance(&self, transactions: &[Transaction]) -> Vec<ReconciliationMismatch> {
+        let mut mismatches: Vec<ReconciliationMismatch> = Vec::new();
+        let Some(first) = transactions.first() else {
+            return mismatches;
+        };
+
+        // 首行余额无前序可核对，作为重算基准
+        let mut running_balance = first.balance;
+        for (idx, tx) in transactions.iter().enumerate().skip(1) {
+            running_balance += tx.income_amount - tx.expense_amount;
+            let delta = tx.balance - running_balance;
+            if delta.abs() <= self.tolerance {
+                continue;
+            }
+            match mismatches.last_mut() {
+                // 与上一不符行连续且差额未变：并入同一区间
+                Some(last) if last.end_row == idx && last.balance_delta == delta => {
+                    last.end_row = idx + 1;
+                }
+                _ => mismatches.push(ReconciliationMismatch {
+                    start_row: idx + 1,
+                    end_row: idx + 1,
+                    expected_balance: running_balance,
+                    actual_balance: tx.balance,
+                    balance_delta: delta,
+                }),
+            }
+        }
+        mismatches
+    }
+
+    /// 咨询模式：计算修复建议但不改动数据
+    ///
+    /// 与[`Self::validate_flow_integrity`]走同一套断点检测与贪心重排，
+    /// 区别在于修复只用于继续向后扫描（后续断点的判定依赖前面的建议
+    /// 被采纳后的顺序），对外仅返回建议列表。无法修复的断点不产生
+    /// 建议，留给正式验证阶段报错
+    pub fn propose_repairs(&mut self, transactions: &[Transaction]) -> AuditResult<Vec<RepairSuggestion>> {
+        // 咨询模式不发布修复报告，仅借用同一套重排逻辑
+        self.repaired_groups.clear();
+        let mut suggestions = Vec::new();
+        if transactions.is_empty() {
+            return Ok(suggestions);
+        }
+
+        let mut working = transactions.to_vec();
+        for i in 1..working.len() {
+            if self.check_balance_continuity(&working[i - 1], &working[i], i)? {
+                continue;
+            }
+            let expected_balance = working[i - 1].balance + working[i].income_amount - working[i].expense_amount;
+            let actual_balance = working[i].balance;
+
+            let Some(fixed) = self.attempt_reorder_fix(&working, i)? else {
+                break;
+            };
+            if !self.check_balance_continuity(&fixed[i - 1], &fixed[i], i)? {
+                break;
+            }
+
+            let row_moves = Self::diff_row_moves(&working, &fixed);
+            suggestions.push(RepairSuggestion {
+                id: suggestions.len(),
+                problem_row: i + 1,
+                expected_balance,
+                actual_balance,
+                description: format!(
+                    "第{}行余额断裂（期望{expected_balance}，实际{actual_balance}），拟重排同时间交易，涉及{}处行移动",
+                    i + 1, row_moves.len()
+                ),
+                row_moves,
+            });
+            working = fixed;
+        }
+        Ok(suggestions)
+    }
+
+    /// 应用用户采纳的修复建议，返回调整后的交易序列
+    ///
+    /// 建议按编号顺序应用；各建议的行移动局限在各自的同时间窗口内，
+    /// 因此跳过被拒绝的建议不影响其余建议的行号含义。
+    /// 越界的行移动（数据在建议生成后被改动）跳过并告警
+    #[must_use]
+    pub fn apply_approved_repairs(
+        transactions: &[Transaction],
+        suggestions: &[RepairSuggestion],
+        approved_ids: &[usize],
+    ) -> Vec<Transaction> {
+        let mut working = transactions.to_vec();
+        for suggestion in suggestions.iter().filter(|s| approved_ids.contains(&s.id)) {
+            let snapshot = working.clone();
+            for row_move in &suggestion.row_moves {
+                if row_move.from_row == 0 || row_move.from_row > snapshot.len()
+                    || row_move.to_row == 0 || row_move.to_row > working.len()
+                {
+                    warn!("⚠️ 修复建议{}的行移动{}→{}越界，已跳过",
+                        suggestion.id, row_move.from_row, row_move.to_row);
+                    continue;
+                }
+                working[row_move.to_row - 1] = snapshot[row_move.from_row - 1].clone();
+            }
+        }
+        working
+    }
+
+    /// 对比重排前后的序列，提取行移动列表
+    ///
+    /// 重排只发生在同一时间窗口内，在变化位置中按内容匹配原行
+    fn diff_row_moves(before: &[Transaction], after: &[Transaction]) -> Vec<RowMove> {
+        let changed: Vec<usize> = (0..before.len())
+            .filter(|&idx| before[idx] != after[idx])
+            .collect();
+
+        let mut used = vec![false; before.len()];
+        let mut moves = Vec::new();
+        for &to in &changed {
+            let from = changed.iter().copied()
+                .find(|&from| !used[from] && before[from] == after[to]);
+            if let Some(from) = from {
+                used[from] = true;
+                moves.push(RowMove { from_row: from + 1, to_row: to + 1 });
+            }
+        }
+        moves
+    }
+}
+
+impl Default for UnifiedValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_transaction(balance: Decimal, income: Decimal, expense: Decimal) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Transaction::new(
+            date,
+            "100000".to_string(),
+            income,
+            expense,
+            balance,
+            "个人应收".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_validate_required_columns() {
+        let validator = UnifiedValidator::new();
+
+        // 测试空数据
+        let empty_transactions: Vec<Transaction> = Vec::new();
+        assert!(validator.validate_required_columns(&empty_transactions).is_err());
+
+        // 测试有效数据
+        let transactions = vec![
+            create_test_transaction(Decimal::from(1000), Decimal::ZERO, Decimal::ZERO)
+        ];
+        assert!(validator.validate_required_columns(&transactions).is_ok());
+    }
+
+    #[test]
+    fn test_balance_continuity_check() {
+        let validator = UnifiedValidator::new();
+
+        let prev = create_test_transaction(Decimal::from(1000), Decimal::ZERO, Decimal::ZERO);
+        let curr = create_test_transaction(Decimal::from(1100), Decimal::from(100), Decimal::ZERO);
+
+        // 余额连贯: 1000 + 100 - 0 = 1100
+        assert!(validator.check_balance_continuity(&prev, &curr, 1).unwrap());
+
+        let curr_bad = create_test_transaction(Decimal::from(1200), Decimal::from(100), Decimal::ZERO);
+        // 余额不连贯: 1000 + 100 - 0 = 1100, 但实际是1200
+        assert!(!validator.check_balance_continuity(&prev, &curr_bad, 1).unwrap());
+    }
+
+    fn create_dated_transaction(day: u32, balance: Decimal, income: Decimal, expense: Decimal) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Transaction::new(
+            date,
+            "100000".to_string(),
+            income,
+            expense,
+            balance,
+            "个人应收".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_propose_repairs_reports_moves_without_mutating() {
+        let mut validator = UnifiedValidator::new();
+
+        // 正确顺序为A,B,C；文件中B、C（同一天）被颠倒
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let b = create_dated_transaction(2, Decimal::from(800), Decimal::ZERO, Decimal::from(200));
+        let c = create_dated_transaction(2, Decimal::from(500), Decimal::ZERO, Decimal::from(300));
+        let transactions = vec![a, c.clone(), b.clone()];
+
+        let suggestions = validator.propose_repairs(&transactions).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].problem_row, 2);
+        assert_eq!(suggestions[0].row_moves, vec![
+            RowMove { from_row: 3, to_row: 2 },
+            RowMove { from_row: 2, to_row: 3 },
+        ]);
+        // 咨询模式不改动输入数据
+        assert_eq!(transactions[1], c);
+        assert_eq!(transactions[2], b);
+    }
+
+    #[test]
+    fn test_validation_report_records_repaired_groups() {
+        let mut validator = UnifiedValidator::new();
+
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let b = create_dated_transaction(2, Decimal::from(800), Decimal::ZERO, Decimal::from(200));
+        let c = create_dated_transaction(2, Decimal::from(500), Decimal::ZERO, Decimal::from(300));
+        let transactions = vec![a, c, b];
+
+        let result = validator.validate_transactions(&transactions).unwrap();
+        assert!(result.is_valid);
+
+        let report = &result.report;
+        assert_eq!(report.total_rows, 3);
+        assert_eq!(report.repair_count, 1);
+        assert_eq!(report.errors_count, 0);
+        assert_eq!(report.repaired_groups.len(), 1);
+
+        let group = &report.repaired_groups[0];
+        assert_eq!(group.problem_row, 2);
+        assert_eq!(group.original_order, vec![2, 3]);
+        assert_eq!(group.new_order, vec![3, 2]);
+        assert_eq!(group.expected_balance, Decimal::from(700));
+        assert_eq!(group.actual_balance, Decimal::from(500));
+        assert_eq!(group.balance_delta, Decimal::from(-200));
+    }
+
+    #[test]
+    fn test_keep_original_strategy_never_reorders() {
+        let mut validator = UnifiedValidator::new()
+            .with_same_time_ordering(SameTimeOrderingConfig {
+                strategy: SameTimeOrderingStrategy::KeepOriginal,
+                tiebreak_column: None,
+            });
+
+        // 贪心策略可修复的同一组数据：保持原始顺序策略下不做任何重排
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let b = create_dated_transaction(2, Decimal::from(800), Decimal::ZERO, Decimal::from(200));
+        let c = create_dated_transaction(2, Decimal::from(500), Decimal::ZERO, Decimal::from(300));
+        let transactions = vec![a, c, b];
+
+        let result = validator.validate_transactions(&transactions).unwrap();
+        assert!(!result.is_valid);
+        assert!(result.fixed_transactions.is_none());
+        assert_eq!(result.report.repair_count, 0);
+    }
+
+    #[test]
+    fn test_income_first_strategy_orders_income_before_expense() {
+        let mut validator = UnifiedValidator::new()
+            .with_same_time_ordering(SameTimeOrderingConfig {
+                strategy: SameTimeOrderingStrategy::IncomeFirst,
+                tiebreak_column: None,
+            });
+
+        // 实际顺序：先收入500（余额1500），后支出300（余额1200）；文件中颠倒
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let income = create_dated_transaction(2, Decimal::from(1500), Decimal::from(500), Decimal::ZERO);
+        let expense = create_dated_transaction(2, Decimal::from(1200), Decimal::ZERO, Decimal::from(300));
+        let transactions = vec![a, expense, income];
+
+        let result = validator.validate_transactions(&transactions).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.report.repair_count, 1);
+        assert_eq!(result.report.repaired_groups[0].new_order, vec![3, 2]);
+    }
+
+    #[test]
+    fn test_tiebreak_column_strategy_sorts_numerically() {
+        let ordering = SameTimeOrderingConfig {
+            strategy: SameTimeOrderingStrategy::TiebreakColumn,
+            tiebreak_column: Some("流水号".to_string()),
+        };
+        let mut validator = UnifiedValidator::new().with_same_time_ordering(ordering.clone());
+
+        // 流水号9先于10（字典序会得到相反结论），按数值比较应还原正确顺序
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let mut first = create_dated_transaction(2, Decimal::from(1500), Decimal::from(500), Decimal::ZERO);
+        first.sequence_number = Some("9".to_string());
+        let mut second = create_dated_transaction(2, Decimal::from(1200), Decimal::ZERO, Decimal::from(300));
+        second.sequence_number = Some("10".to_string());
+        let transactions = vec![a.clone(), second.clone(), first.clone()];
+
+        let result = validator.validate_transactions(&transactions).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.report.repaired_groups[0].new_order, vec![3, 2]);
+
+        // 任一行未读到仲裁值时放弃重排
+        let mut validator = UnifiedValidator::new().with_same_time_ordering(ordering);
+        let mut missing = first;
+        missing.sequence_number = None;
+        let result = validator.validate_transactions(&[a, second, missing]).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.report.repair_count, 0);
+    }
+
+    #[test]
+    fn test_apply_approved_repairs_respects_selection() {
+        let mut validator = UnifiedValidator::new();
+
+        let a = create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO);
+        let b = create_dated_transaction(2, Decimal::from(800), Decimal::ZERO, Decimal::from(200));
+        let c = create_dated_transaction(2, Decimal::from(500), Decimal::ZERO, Decimal::from(300));
+        let transactions = vec![a, c, b];
+        let suggestions = validator.propose_repairs(&transactions).unwrap();
+
+        // 采纳建议：重排后流水应连贯
+        let repaired = UnifiedValidator::apply_approved_repairs(&transactions, &suggestions, &[0]);
+        assert!(validator.check_balance_continuity(&repaired[0], &repaired[1], 1).unwrap());
+        assert!(validator.check_balance_continuity(&repaired[1], &repaired[2], 2).unwrap());
+
+        // 全部拒绝：数据保持原样
+        let untouched = UnifiedValidator::apply_approved_repairs(&transactions, &suggestions, &[]);
+        assert_eq!(untouched, transactions);
+    }
+
+    #[test]
+    fn test_reconcile_running_balance_merges_gap_into_region() {
+        let validator = UnifiedValidator::new();
+
+        // 第2行之后缺了一笔300元支出：其后各行余额列均比重算值低300
+        let transactions = vec![
+            create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO),
+            create_dated_transaction(2, Decimal::from(900), Decimal::ZERO, Decimal::from(100)),
+            create_dated_transaction(3, Decimal::from(400), Decimal::ZERO, Decimal::from(200)),
+            create_dated_transaction(4, Decimal::from(300), Decimal::ZERO, Decimal::from(100)),
+        ];
+
+        let mismatches = validator.reconcile_running_balance(&transactions);
+        assert_eq!(mismatches.len(), 1);
+        let region = &mismatches[0];
+        assert_eq!(region.start_row, 3);
+        assert_eq!(region.end_row, 4);
+        assert_eq!(region.expected_balance, Decimal::from(700));
+        assert_eq!(region.actual_balance, Decimal::from(400));
+        assert_eq!(region.balance_delta, Decimal::from(-300));
+    }
+
+    #[test]
+    fn test_validation_report_carries_reconciliation_mismatches() {
+        let mut validator = UnifiedValidator::new();
+
+        let transactions = vec![
+            create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO),
+            create_dated_transaction(2, Decimal::from(500), Decimal::ZERO, Decimal::from(200)),
+        ];
+
+        let result = validator.validate_transactions(&transactions).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.report.reconciliation_mismatches.len(), 1);
+        assert_eq!(result.report.reconciliation_mismatches[0].balance_delta, Decimal::from(-300));
+
+        // 连贯数据不产生不符区间
+        let clean = vec![
+            create_dated_transaction(1, Decimal::from(1000), Decimal::from(1000), Decimal::ZERO),
+            create_dated_transaction(2, Decimal::from(800), Decimal::ZERO, Decimal::from(200)),
+        ];
+        let result = validator.validate_transactions(&clean).unwrap();
+        assert!(result.report.reconciliation_mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_scale_anomaly_precision_noise() {
+        let validator = UnifiedValidator::new();
+
+        // 收入金额小数位超过2位，应产生精度噪声警告
+        let transactions = vec![
+            create_test_transaction(Decimal::from(1000), Decimal::new(1000345, 3), Decimal::ZERO),
+        ];
+        let warnings = validator.check_scale_anomalies(&transactions);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("分位噪声"));
+
+        // 正常2位小数金额不应报警
+        let clean = vec![
+            create_test_transaction(Decimal::new(100050, 2), Decimal::new(100050, 2), Decimal::ZERO),
+        ];
+        assert!(validator.check_scale_anomalies(&clean).is_empty());
+    }
+
+    #[test]
+    fn test_scale_anomaly_magnitude_shift() {
+        let validator = UnifiedValidator::new();
+
+        // 前几笔为百元量级，随后突变为万元量级（×100），疑似元/分混用
+        let mut transactions: Vec<Transaction> = (0..5)
+            .map(|_| create_test_transaction(Decimal::from(1000), Decimal::from(200), Decimal::ZERO))
+            .collect();
+        transactions.push(create_test_transaction(
+            Decimal::from(1000),
+            Decimal::from(20000),
+            Decimal::ZERO,
+        ));
+
+        let warnings = validator.check_scale_anomalies(&transactions);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].row, 6);
+        assert!(warnings[0].message.contains("单位混用"));
+    }
+}
\ No newline at end of file
@@ -7,9 +7,10 @@ use std::io::{self, Write};
 use std::collections::HashMap;
 
 use clap::{Args, Parser, Subcommand};
-use tokio;
 
-use audit_backend::AuditService;
+use flux_backend::AuditService;
+
+mod tui;
 
 /// FIFO资金追踪审计系统 v3.2 - 支持双算法
 #[derive(Parser)]
@@ -20,8 +21,8 @@ struct Cli {
     command: Option<Commands>,
     
     /// 选择算法类型：FIFO（先进先出）或 BALANCE_METHOD（差额计算法）
-    #[arg(short, long, value_enum, default_value_t = Algorithm::Fifo)]
-    algorithm: Algorithm,
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
     
     /// 输入Excel文件路径
     #[arg(short, long, default_value = "流水.xlsx")]
@@ -34,9 +35,18 @@ struct Cli {
     /// 安静模式，减少输出信息
     #[arg(short, long)]
     quiet: bool,
+    
+    /// 性能剖析模式：将各阶段耗时写出为chrome-tracing JSON（排障用）
+    #[arg(long)]
+    trace_profile: bool,
+    
+    /// 列映射档案路径（JSON/TOML），用于不同银行的表头写法
+    #[arg(long)]
+    mapping: Option<String>,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // CLI解析只构造一次，装箱参数结构无收益
 enum Commands {
     /// 列出所有可用算法
     ListAlgorithms,
@@ -46,6 +56,152 @@ enum Commands {
     Interactive,
     /// 运行单算法分析（默认命令）
     Analyze(AnalyzeArgs),
+    /// 快速扫描：抽样粗算挪用/垫付（初步估算）
+    QuickScan(QuickScanArgs),
+    /// 数据集概览统计（分析前确认文件内容）
+    Stats(StatsArgs),
+    /// 按源表行号查询处理结果（定位源工作簿中某一行）
+    Query(QueryArgs),
+    /// 批量时点查询（一次返回多行的追踪器状态快照）
+    BatchQuery(BatchQueryArgs),
+    /// 按期汇总报告（按日/按月的余额与缺口走势）
+    Report(ReportArgs),
+    /// 测试资金属性分类规则（不运行分析）
+    TestRules(TestRulesArgs),
+    /// 运行前估算：预测分析耗时与结果文件大小
+    Estimate(EstimateArgs),
+    /// 启动HTTP服务（无头服务器部署，提交任务→轮询→下载结果）
+    Serve(ServeArgs),
+    /// 校验审计轨迹：重算哈希链并对结果文件重新取指纹
+    Verify(VerifyArgs),
+    /// 合成数据基准：测量追踪器与各阶段吞吐（验证优化效果）
+    Bench(BenchArgs),
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// 合成交易笔数
+    #[arg(long, default_value_t = 10_000)]
+    transactions: usize,
+    /// 个人属性交易占比（百分比，其余为公司属性）
+    #[arg(long, default_value_t = 40)]
+    personal_percent: u32,
+    /// 理财申购/赎回交易占比（百分比）
+    #[arg(long, default_value_t = 10)]
+    investment_percent: u32,
+    /// 随机种子（相同种子生成相同数据，保证结果可复现）
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// 机器可读基准报告的输出路径（JSON）
+    #[arg(short, long, default_value = "bench_report.json")]
+    output: String,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// 审计轨迹文件路径
+    #[arg(short, long, default_value = flux_backend::DEFAULT_TRAIL_FILE)]
+    trail: String,
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// 监听地址
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// 监听端口
+    #[arg(short, long, default_value_t = 7878)]
+    port: u16,
+}
+
+#[derive(Args)]
+struct EstimateArgs {
+    /// 选择算法类型
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
+    
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+    
+    /// 吞吐统计文件路径（默认与程序工作目录下的统计文件）
+    #[arg(long)]
+    stats: Option<String>,
+}
+
+#[derive(Args)]
+struct TestRulesArgs {
+    /// 分类规则文件路径（JSON/TOML）
+    #[arg(short, long)]
+    rules: String,
+    
+    /// 从Excel文件提取全部不重复资金属性进行测试
+    #[arg(short, long)]
+    input: Option<String>,
+    
+    /// 待测试的资金属性词面（可多个）
+    attributes: Vec<String>,
+}
+
+#[derive(Args)]
+struct QueryArgs {
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+    
+    /// 选择算法类型
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
+    
+    /// 源Excel中的数据行号（1开始，不含表头）
+    #[arg(short, long)]
+    row: usize,
+}
+
+#[derive(Args)]
+struct BatchQueryArgs {
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+
+    /// 选择算法类型
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
+
+    /// 逗号分隔的行号列表（处理结果行号，1开始）
+    #[arg(short, long)]
+    rows: Option<String>,
+
+    /// 行号文件路径（每行一个行号，可与--rows合用）
+    #[arg(long, value_name = "FILE")]
+    rows_file: Option<String>,
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+
+    /// 选择算法类型
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
+
+    /// 同时打印按日汇总（默认只打印按月汇总）
+    #[arg(long)]
+    daily: bool,
+}
+
+#[derive(Args)]
+struct StatsArgs {
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+    
+    /// 资金属性分布最多显示的条目数
+    #[arg(short = 'n', long, default_value_t = 20)]
+    top: usize,
 }
 
 #[derive(Args)]
@@ -53,13 +209,29 @@ struct CompareArgs {
     /// 输入Excel文件路径
     #[arg(short, long, default_value = "流水.xlsx")]
     input: String,
+    
+    /// 对比的指标列表（逗号分隔的指标名，默认对比全部摘要指标）
+    #[arg(short, long)]
+    metrics: Option<String>,
+    
+    /// 相对差异阈值（百分比），超过该阈值的指标会被标记需要书面说明
+    #[arg(short, long, default_value_t = 5.0)]
+    threshold: f64,
+    
+    /// 是否包含各资金池的最终统计指标
+    #[arg(long)]
+    include_pools: bool,
+    
+    /// 导出对比报告到指定xlsx文件（逐行并排结果+摘要差异表）
+    #[arg(short, long)]
+    output: Option<String>,
 }
 
 #[derive(Args)]
 struct AnalyzeArgs {
     /// 选择算法类型
-    #[arg(short, long, value_enum, default_value_t = Algorithm::Fifo)]
-    algorithm: Algorithm,
+    #[arg(short, long, default_value = "FIFO", value_parser = parse_algorithm)]
+    algorithm: String,
     
     /// 输入Excel文件路径
     #[arg(short, long, default_value = "流水.xlsx")]
@@ -72,21 +244,152 @@ struct AnalyzeArgs {
     /// 安静模式，减少输出信息
     #[arg(short, long)]
     quiet: bool,
+    
+    /// 性能剖析模式：将各阶段耗时写出为chrome-tracing JSON（排障用）
+    #[arg(long)]
+    trace_profile: bool,
+    
+    /// 列映射档案路径（JSON/TOML），用于不同银行的表头写法
+    #[arg(long)]
+    mapping: Option<String>,
+    
+    /// 分类规则文件路径（JSON/TOML），自定义个人/公司/投资产品判定口径
+    #[arg(long)]
+    rules: Option<String>,
+    
+    /// 分析完成后进入终端结果浏览器（TUI）
+    #[arg(long)]
+    browse: bool,
+    
+    /// 仅分析该日期（含）之后的交易，格式YYYY-MM-DD（部分期间审计）
+    #[arg(long, value_name = "DATE")]
+    from: Option<String>,
+    
+    /// 仅分析该日期（含）之前的交易，格式YYYY-MM-DD
+    #[arg(long, value_name = "DATE")]
+    to: Option<String>,
+
+    /// 要分析的工作表名（多表工作簿；省略时读取第一个工作表）
+    #[arg(long, value_name = "NAME")]
+    sheet: Option<String>,
+
+    /// 合并分析的附加输入文件（可多次指定，按年拆分的流水与--input拼接为一条时间线）
+    #[arg(long, value_name = "FILE")]
+    merge: Vec<String>,
+
+    /// 核定期初余额总额（覆盖首笔交易回推值）
+    #[arg(long, value_name = "AMOUNT")]
+    opening_balance: Option<String>,
+
+    /// 期初余额中的个人部分金额（与--opening-ratio互斥）
+    #[arg(long, value_name = "AMOUNT", conflicts_with = "opening_ratio")]
+    opening_personal: Option<String>,
+
+    /// 期初余额中个人部分占比（0到1）
+    #[arg(long, value_name = "RATIO")]
+    opening_ratio: Option<String>,
+
+    /// 同时间戳交易排序策略（不同银行对同秒交易的落库顺序保证不同）
+    #[arg(long, value_enum, value_name = "STRATEGY")]
+    same_time_ordering: Option<SameTimeOrdering>,
+
+    /// TIEBREAK_COLUMN策略使用的仲裁列名（如"流水号"）
+    #[arg(long, value_name = "NAME", requires = "same_time_ordering")]
+    tiebreak_column: Option<String>,
+
+    /// 严格核对模式：余额列与按收支重算的余额不符（疑似缺行）时中止分析
+    #[arg(long)]
+    strict_balance: bool,
+
+    /// 审计轨迹文件路径：分析完成后追加防篡改运行记录（verify命令校验）
+    #[arg(long, value_name = "FILE")]
+    trail: Option<String>,
+
+    /// 额外导出聚焦工作簿：只含指定行为性质的行（如 挪用、垫付）
+    #[arg(long, value_name = "KIND")]
+    only_behavior: Option<String>,
+
+    /// 额外导出聚焦工作簿：只含资金属性词面匹配的行（可与行为筛选叠加）
+    #[arg(long, value_name = "KEYWORD")]
+    only_attribute: Option<String>,
+
+    /// 聚焦工作簿的最小流水金额（含）
+    #[arg(long, value_name = "AMOUNT")]
+    amount_min: Option<String>,
+
+    /// 聚焦工作簿的最大流水金额（含）
+    #[arg(long, value_name = "AMOUNT")]
+    amount_max: Option<String>,
+}
+
+/// 聚焦导出筛选条件（分析完成后生效，多个条件为与关系）
+struct FocusFilter {
+    behavior: Option<String>,
+    attribute: Option<String>,
+    amount_min: Option<String>,
+    amount_max: Option<String>,
+}
+
+impl FocusFilter {
+    fn from_args(args: &AnalyzeArgs) -> Option<Self> {
+        if args.only_behavior.is_none() && args.only_attribute.is_none()
+            && args.amount_min.is_none() && args.amount_max.is_none() {
+            return None;
+        }
+        Some(Self {
+            behavior: args.only_behavior.clone(),
+            attribute: args.only_attribute.clone(),
+            amount_min: args.amount_min.clone(),
+            amount_max: args.amount_max.clone(),
+        })
+    }
+}
+
+#[derive(Args)]
+struct QuickScanArgs {
+    /// 输入Excel文件路径
+    #[arg(short, long, default_value = "流水.xlsx")]
+    input: String,
+    
+    /// 抽样间隔（每N行取1行）
+    #[arg(short = 'n', long, default_value_t = 10)]
+    interval: usize,
+    
+    /// 大额全量纳入阈值
+    #[arg(short, long, default_value = "100000")]
+    threshold: String,
+}
+
+/// 校验算法标识（大小写不敏感，合法值来自算法注册表）
+fn parse_algorithm(value: &str) -> Result<String, String> {
+    let registry = flux_backend::interfaces::AlgorithmRegistry::with_builtins();
+    let canonical = value.to_uppercase();
+    if registry.contains(&canonical) {
+        Ok(canonical)
+    } else {
+        Err(format!("不支持的算法: {value}（可用: {}）", registry.names().join(", ")))
+    }
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
-enum Algorithm {
-    #[value(name = "FIFO")]
-    Fifo,
-    #[value(name = "BALANCE_METHOD")]
-    BalanceMethod,
+enum SameTimeOrdering {
+    #[value(name = "GREEDY_BALANCE_CHAIN")]
+    GreedyBalanceChain,
+    #[value(name = "KEEP_ORIGINAL")]
+    KeepOriginal,
+    #[value(name = "INCOME_FIRST")]
+    IncomeFirst,
+    #[value(name = "TIEBREAK_COLUMN")]
+    TiebreakColumn,
 }
 
-impl Algorithm {
-    fn to_string(&self) -> &'static str {
+impl SameTimeOrdering {
+    fn to_engine(&self) -> flux_backend::SameTimeOrderingStrategy {
         match self {
-            Algorithm::Fifo => "FIFO",
-            Algorithm::BalanceMethod => "BALANCE_METHOD",
+            SameTimeOrdering::GreedyBalanceChain => flux_backend::SameTimeOrderingStrategy::GreedyBalanceChain,
+            SameTimeOrdering::KeepOriginal => flux_backend::SameTimeOrderingStrategy::KeepOriginal,
+            SameTimeOrdering::IncomeFirst => flux_backend::SameTimeOrderingStrategy::IncomeFirst,
+            SameTimeOrdering::TiebreakColumn => flux_backend::SameTimeOrderingStrategy::TiebreakColumn,
         }
     }
 }
@@ -104,27 +407,98 @@ async fn main() {
             list_algorithms().await
         }
         Some(Commands::Compare(args)) => {
-            compare_algorithms(&args.input).await
+            compare_algorithms(args).await
         }
         Some(Commands::Interactive) => {
             interactive_mode().await
         }
+        Some(Commands::QuickScan(args)) => {
+            quick_scan(args).await
+        }
+        Some(Commands::Stats(args)) => {
+            dataset_stats(args).await
+        }
+        Some(Commands::BatchQuery(args)) => {
+            batch_query(args).await
+        }
+        Some(Commands::Report(args)) => {
+            period_report(args).await
+        }
+        Some(Commands::Query(args)) => {
+            query_source_row(args).await
+        }
+        Some(Commands::TestRules(args)) => {
+            test_classification_rules(args)
+        }
+        Some(Commands::Estimate(args)) => {
+            estimate_run(args)
+        }
+        Some(Commands::Serve(args)) => {
+            flux_backend::server_service::serve(&args.host, args.port).await
+                .map_err(Into::into)
+        }
+        Some(Commands::Verify(args)) => {
+            verify_audit_trail(&args.trail)
+        }
+        Some(Commands::Bench(args)) => {
+            run_benchmark(args)
+        }
         Some(Commands::Analyze(args)) => {
-            run_single_analysis(
-                args.algorithm.to_string(),
-                &args.input,
-                args.output.as_deref(),
-                args.quiet,
-            ).await
+            let focus = FocusFilter::from_args(args);
+            let ordering = args.same_time_ordering.as_ref().map(|strategy| {
+                flux_backend::SameTimeOrderingConfig {
+                    strategy: strategy.to_engine(),
+                    tiebreak_column: args.tiebreak_column.clone(),
+                }
+            });
+            match parse_opening_override(
+                args.opening_balance.as_deref(),
+                args.opening_personal.as_deref(),
+                args.opening_ratio.as_deref(),
+            ) {
+                Ok(opening) => run_single_analysis(
+                    &args.algorithm,
+                    &args.input,
+                    args.output.as_deref(),
+                    args.quiet,
+                    args.trace_profile,
+                    args.mapping.as_deref(),
+                    args.rules.as_deref(),
+                    args.browse,
+                    args.from.as_deref(),
+                    args.to.as_deref(),
+                    args.sheet.as_deref(),
+                    &args.merge,
+                    opening,
+                    ordering,
+                    args.strict_balance,
+                    args.trail.as_deref(),
+                    focus.as_ref(),
+                ).await,
+                Err(e) => Err(e.into()),
+            }
         }
         None => {
             // 默认行为：如果有输入参数就分析，否则进入交互模式
             if std::env::args().len() > 1 {
                 run_single_analysis(
-                    cli.algorithm.to_string(),
+                    &cli.algorithm,
                     &cli.input,
                     cli.output.as_deref(),
                     cli.quiet,
+                    cli.trace_profile,
+                    cli.mapping.as_deref(),
+                    None,
+                    false,
+                    None,
+                    None,
+                    None,
+                    &[],
+                    None,
+                    None,
+                    false,
+                    None,
+                    None,
                 ).await
             } else {
                 interactive_mode().await
@@ -152,36 +526,291 @@ async fn list_algorithms() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// 把一次成功运行的吞吐记入统计文件（供estimate命令估算与阶段权重学习）
+fn record_run_sample(
+    algorithm: &str,
+    rows: usize,
+    duration_secs: f64,
+    output_files: &[String],
+    stage_secs: Vec<(String, f64)>,
+    fifo_pool_hit_rate: Option<f64>,
+) {
+    use flux_backend::{PerformanceStatsService, RunSample};
+
+    let output_bytes = output_files.first()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map_or(0, |meta| meta.len());
+    let result = PerformanceStatsService::open(PerformanceStatsService::DEFAULT_STATS_FILE)
+        .and_then(|(mut stats, _)| stats.record(RunSample {
+            algorithm: algorithm.to_string(),
+            rows,
+            duration_secs,
+            output_bytes,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            stage_secs,
+            fifo_pool_hit_rate,
+        }));
+    if let Err(e) = result {
+        log::warn!("⚠️ 记录吞吐统计失败（不影响分析结果）: {e}");
+    }
+}
+
+/// 解析命令行的期初余额核定参数（全部省略时返回None）
+fn parse_opening_override(
+    total: Option<&str>,
+    personal: Option<&str>,
+    ratio: Option<&str>,
+) -> Result<Option<flux_backend::OpeningBalanceOverride>, String> {
+    let parse = |label: &str, value: Option<&str>| -> Result<Option<rust_decimal::Decimal>, String> {
+        value.map(|raw| raw.trim().parse::<rust_decimal::Decimal>()
+            .map_err(|_| format!("{label}金额格式无效: {raw}"))).transpose()
+    };
+    let over = flux_backend::OpeningBalanceOverride {
+        total: parse("--opening-balance", total)?,
+        personal_amount: parse("--opening-personal", personal)?,
+        personal_ratio: parse("--opening-ratio", ratio)?,
+    };
+    Ok((!over.is_empty()).then_some(over))
+}
+
+/// 按聚焦条件筛选处理结果行（多个条件为与关系）
+fn apply_focus_filter(
+    transactions: &[flux_backend::Transaction],
+    focus: &FocusFilter,
+) -> Result<Vec<flux_backend::Transaction>, Box<dyn std::error::Error>> {
+    let parse_amount = |label: &str, value: &Option<String>| -> Result<Option<flux_backend::rust_decimal::Decimal>, String> {
+        value.as_deref().map(|raw| {
+            raw.trim().parse()
+                .map_err(|_| format!("{label}金额无法解析: {raw}"))
+        }).transpose()
+    };
+
+    let mut rows = transactions.to_vec();
+    if let Some(label) = &focus.behavior {
+        let kind = flux_backend::BehaviorKind::from_label_zh(label)
+            .ok_or_else(|| format!(
+                "未知行为性质: {label}（可选：挪用、垫付、个人支付、公司支付、投资挪用、个人投资）"))?;
+        rows = flux_backend::filter_by_behavior(&rows, kind);
+    }
+    if let Some(keyword) = &focus.attribute {
+        rows = flux_backend::filter_by_fund_attribute(&rows, keyword);
+    }
+    let amount_min = parse_amount("--amount-min", &focus.amount_min)?;
+    let amount_max = parse_amount("--amount-max", &focus.amount_max)?;
+    if amount_min.is_some() || amount_max.is_some() {
+        rows = flux_backend::filter_by_amount_range(&rows, amount_min, amount_max);
+    }
+    Ok(rows)
+}
+
+/// 聚焦工作簿路径：主结果文件名加"_筛选"后缀
+fn focused_output_path(main_output: &str) -> std::path::PathBuf {
+    let path = std::path::Path::new(main_output);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("分析结果");
+    let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("xlsx");
+    path.with_file_name(format!("{stem}_筛选.{ext}"))
+}
+
+/// 校验审计轨迹：重算哈希链并对结果文件重新取指纹
+/// 合成数据基准：分阶段测量吞吐并写出JSON报告
+fn run_benchmark(args: &BenchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::benchmark::{run_full_benchmark, SyntheticSpec};
+
+    let spec = SyntheticSpec {
+        transaction_count: args.transactions,
+        personal_percent: args.personal_percent,
+        investment_percent: args.investment_percent,
+        seed: args.seed,
+    };
+    println!("🚀 合成数据基准: {}笔交易（种子{}，个人{}%，理财{}%）",
+        spec.transaction_count, spec.seed, spec.personal_percent, spec.investment_percent);
+
+    let report = run_full_benchmark(&spec)?;
+
+    println!("
+{}", "=".repeat(60));
+    println!("📊 分阶段吞吐");
+    println!("{}", "=".repeat(60));
+    for stage in &report.stages {
+        println!("{:<12} {:>10.1}ms {:>12.0}条/秒",
+            stage.stage, stage.elapsed_ms, stage.throughput_per_sec);
+    }
+
+    std::fs::write(&args.output, serde_json::to_string_pretty(&report)?)?;
+    println!("
+💾 基准报告已写入: {}", args.output);
+    Ok(())
+}
+
+fn verify_audit_trail(trail_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !std::path::Path::new(trail_path).exists() {
+        return Err(format!("审计轨迹文件不存在: {trail_path}").into());
+    }
+
+    let trail = flux_backend::AuditTrailService::open(trail_path)?;
+    println!("🔏 审计轨迹: {trail_path}（共{}条记录）", trail.len());
+
+    let verification = trail.verify()?;
+    println!("   哈希链: {}", if verification.chain_intact { "✅ 完好" } else { "❌ 被改动" });
+    println!("   结果文件: {}", if verification.outputs_intact { "✅ 指纹一致" } else { "❌ 缺失或已变化" });
+
+    if verification.issues.is_empty() {
+        println!("✅ 轨迹校验通过，自记录以来未发现改动");
+        Ok(())
+    } else {
+        println!("\n❌ 发现 {} 个问题:", verification.issues.len());
+        for issue in &verification.issues {
+            println!("   第{}条 [{}] {}", issue.sequence, issue.code, issue.message);
+        }
+        Err("审计轨迹校验失败".into())
+    }
+}
+
 /// 运行单算法分析
+#[allow(clippy::too_many_arguments)]
 async fn run_single_analysis(
     algorithm: &str,
     input_file: &str,
     output_file: Option<&str>,
     quiet: bool,
+    trace_profile: bool,
+    mapping: Option<&str>,
+    rules: Option<&str>,
+    browse: bool,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+    sheet: Option<&str>,
+    merge: &[String],
+    opening: Option<flux_backend::OpeningBalanceOverride>,
+    ordering: Option<flux_backend::SameTimeOrderingConfig>,
+    strict_balance: bool,
+    trail: Option<&str>,
+    focus: Option<&FocusFilter>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    // 解析时间窗日期（部分期间审计）
+    let parse_date = |label: &str, value: Option<&str>| -> Result<Option<chrono::NaiveDate>, String> {
+        value.map(|raw| {
+            chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("{label}日期格式应为YYYY-MM-DD: {raw}"))
+        }).transpose()
+    };
+    let date_from = parse_date("--from", date_from)?;
+    let date_to = parse_date("--to", date_to)?;
+    if let (Some(from), Some(to)) = (date_from, date_to) {
+        if from > to {
+            return Err(format!("--from {from} 晚于 --to {to}").into());
+        }
+    }
+    
     
     if !quiet {
         let service = AuditService::new();
         let algorithms = service.get_algorithms_info();
-        let algo_desc = algorithms.get(algorithm).unwrap_or(&"未知算法");
+        let algo_desc = algorithms.get(algorithm).map_or("未知算法", String::as_str);
         
         println!("🚀 启动算法: {}", algorithm);
         println!("📝 算法描述: {}", algo_desc);
         println!("📂 输入文件: {}", input_file);
+        if !merge.is_empty() {
+            println!("📂 合并文件: {}", merge.join("、"));
+        }
+        if let Some(sheet) = sheet {
+            println!("📋 工作表: {sheet}");
+        }
+        if opening.is_some() {
+            println!("💰 使用核定期初余额（覆盖首行启发式推断）");
+        }
+        if let Some(ordering) = &ordering {
+            println!("🔀 同时间排序策略: {}", ordering.strategy.description());
+        }
+        if date_from.is_some() || date_to.is_some() {
+            println!("📅 时间窗: {} ~ {}",
+                date_from.map_or("最早".to_string(), |d| d.to_string()),
+                date_to.map_or("最晚".to_string(), |d| d.to_string()));
+        }
         io::stdout().flush()?;
     }
     
-    // 创建审计服务
-    let service = AuditService::new().with_suppress_output(quiet);
+    let opening_specified = opening.is_some();
+
+    // 创建审计服务（指定列映射档案/分类规则时先加载并应用）
+    let service = if mapping.is_some() || rules.is_some() {
+        let mut config = flux_backend::Config::new();
+        if let Some(profile_path) = mapping {
+            config.load_column_mapping(profile_path)?;
+            if !quiet {
+                println!("📑 已加载列映射档案: {profile_path}");
+            }
+        }
+        if let Some(rules_path) = rules {
+            config.load_classification_rules(rules_path)?;
+            if !quiet {
+                println!("📑 已加载分类规则: {rules_path}");
+            }
+        }
+        AuditService::with_config(config)
+    } else {
+        AuditService::new()
+    }
+        .with_same_time_ordering(ordering.clone())
+        .with_suppress_output(quiet)
+        .with_trace_profile(trace_profile)
+        .with_time_range(date_from, date_to)
+        .with_sheet_name(sheet.map(str::to_string))
+        .with_additional_inputs(merge.to_vec())
+        .with_opening_balance(opening)
+        .with_strict_reconciliation(strict_balance);
     
     // 分析数据
+    let run_start = std::time::Instant::now();
     let result = service.analyze_financial_data(algorithm, input_file, output_file).await;
     
     match result {
-        Ok((summary, transactions, _output_files)) => {
+        Ok((summary, transactions, output_files)) => {
+            // 记录吞吐样本供estimate命令使用（统计失败不影响分析结果）
+            record_run_sample(algorithm, transactions.len(),
+                run_start.elapsed().as_secs_f64(), &output_files,
+                service.take_stage_durations().await,
+                service.fifo_pool_stats().await.map(|stats| stats.hit_rate()));
+
+            // 追加审计轨迹记录（取证模式；记录失败按错误返回，
+            // 避免出现没有轨迹记录的结果文件）
+            if let Some(trail_path) = trail {
+                let parameters = serde_json::json!({
+                    "output_file": output_file,
+                    "sheet": sheet,
+                    "merge": merge,
+                    "date_from": date_from.map(|d| d.to_string()),
+                    "date_to": date_to.map(|d| d.to_string()),
+                    "opening_override": opening_specified,
+                    "strict_balance": strict_balance,
+                });
+                let mut trail_service = flux_backend::AuditTrailService::open(trail_path)?;
+                let entry = trail_service.record_run(
+                    algorithm, parameters, input_file, &summary, &output_files)?;
+                if !quiet {
+                    println!("🔏 审计轨迹已记录: {trail_path} 第{}条", entry.sequence);
+                }
+            }
+
+            // 聚焦工作簿导出：按行为性质/资金属性/金额区间筛出行子集
+            if let Some(focus) = focus {
+                let filtered = apply_focus_filter(&transactions, focus)?;
+                let focused_path = focused_output_path(
+                    output_files.first().map_or("分析结果.xlsx", String::as_str));
+                let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+                processor.export_analysis_results(&filtered, &summary, &focused_path)?;
+                if !quiet {
+                    println!("🎯 聚焦工作簿: {}（筛出{}行/共{}行）",
+                        focused_path.display(), filtered.len(), transactions.len());
+                }
+            }
+
             if !quiet {
                 println!("✅ {}算法分析完成！", algorithm);
                 println!("📊 处理行数: {}", transactions.len());
+                // 计算列的确定性哈希：引擎升级后在同一案件上比对，数字未变则哈希不变
+                println!("🔐 结果哈希: {}", flux_backend::result_digest(&transactions));
                 
                 let output_name = if let Some(output) = output_file {
                     output.to_string()
@@ -195,6 +824,23 @@ async fn run_single_analysis(
                 println!("   💰 个人余额: {:.2}", summary.personal_balance);
                 println!("   🏢 公司余额: {:.2}", summary.company_balance);
                 println!("   📊 总余额: {:.2}", summary.total_balance);
+                if let Some(closure_time) = &summary.account_closure_time {
+                    println!("   🔒 销户时间: {closure_time}");
+                }
+                
+                // 显示运行期收集的结构化警告
+                let warnings = service.get_warnings().await;
+                if !warnings.is_empty() {
+                    println!("\n⚠️ 警告 ({} 条):", warnings.len());
+                    for warning in &warnings {
+                        println!("   {}", warning.format_for_cli());
+                    }
+                }
+            }
+            
+            // 进入终端结果浏览器
+            if browse {
+                tui::browse_results(&transactions, &summary)?;
             }
             Ok(())
         }
@@ -205,23 +851,415 @@ async fn run_single_analysis(
     }
 }
 
+/// 快速扫描：抽样粗算，输出带局限性说明的初步估算
+async fn quick_scan(args: &QuickScanArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use std::str::FromStr;
+    
+    let threshold = rust_decimal::Decimal::from_str(&args.threshold)
+        .map_err(|e| format!("大额阈值格式错误 {}: {e}", args.threshold))?;
+    
+    println!("⚡ 快速扫描（初步估算）: {}", args.input);
+    println!("📋 抽样策略: 每{}行抽1行，金额≥{:.2}的大额行全量纳入", args.interval, threshold);
+    
+    let service = AuditService::new().with_suppress_output(true);
+    let result = service.quick_scan(&args.input, args.interval, threshold).await?;
+    
+    println!("\n{}", "=".repeat(60));
+    println!("⚡ 快速扫描结果（初步估算，以完整分析为准）");
+    println!("{}", "=".repeat(60));
+    println!("抽样行数: {}/{} (支出金额覆盖率 {:.2}%)",
+        result.sampled_rows, result.total_rows,
+        result.expense_coverage * rust_decimal::Decimal::from(100));
+    println!("挪用金额估算: ¥{:.2}", result.estimated_misappropriation);
+    println!("垫付金额估算: ¥{:.2}", result.estimated_advance_payment);
+    println!("扫描耗时: {:.2}秒", result.scan_time_secs);
+    
+    println!("\n⚠️ 局限性说明:");
+    for (idx, caveat) in result.caveats.iter().enumerate() {
+        println!("{}. {}", idx + 1, caveat);
+    }
+    
+    Ok(())
+}
+
+/// 数据集概览统计
+async fn dataset_stats(args: &StatsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📊 数据集概览统计: {}", args.input);
+    
+    let service = AuditService::new().with_suppress_output(true);
+    let stats = service.dataset_stats(&args.input).await?;
+    
+    println!("
+{}", "=".repeat(60));
+    println!("📊 数据集概览（仅读取，未做完整性验证）");
+    println!("{}", "=".repeat(60));
+    println!("总行数: {}", stats.total_rows);
+    println!("时间范围: {} ~ {}",
+        stats.start_time.as_deref().unwrap_or("未知"),
+        stats.end_time.as_deref().unwrap_or("未知"));
+    println!("总收入: ¥{:.2}", stats.total_income);
+    println!("总支出: ¥{:.2}", stats.total_expense);
+    
+    println!("
+资金属性分布（前{}项，共{}种）:", 
+        args.top.min(stats.fund_attribute_counts.len()), stats.fund_attribute_counts.len());
+    for (attribute, count) in stats.fund_attribute_counts.iter().take(args.top) {
+        println!("  {attribute}: {count}笔");
+    }
+
+    if !stats.fund_attribute_rollup.is_empty() {
+        println!("
+层级上卷分布（前{}项，共{}个层级路径）:",
+            args.top.min(stats.fund_attribute_rollup.len()), stats.fund_attribute_rollup.len());
+        for (path, count) in stats.fund_attribute_rollup.iter().take(args.top) {
+            println!("  {path}: {count}笔");
+        }
+    }
+
+    if stats.investment_products.is_empty() {
+        println!("
+疑似投资产品: 无");
+    } else {
+        println!("
+疑似投资产品（{}个）:", stats.investment_products.len());
+        for (product, count) in &stats.investment_products {
+            println!("  {product}: {count}笔");
+        }
+    }
+    
+    println!("
+统计耗时: {:.2}秒", stats.scan_time_secs);
+    
+    Ok(())
+}
+
+/// 按源表行号查询处理结果
+async fn query_source_row(args: &QueryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::{FileCache, TimePointService, TimePointQueryRequest};
+    
+    let algorithm = args.algorithm.clone();
+    println!("🔍 源表行查询: {} 第{}行（{}算法）", args.input, args.row, algorithm);
+    
+    let fingerprint = FileCache::new().generate_fingerprint(&args.input, &algorithm)?;
+    let mut service = TimePointService::new(algorithm.clone())?;
+    
+    // 先执行一次缓存时点查询以填充文件缓存（按处理顺序第1行即可）
+    service.query_time_point_cached(TimePointQueryRequest {
+        file_path: args.input.clone(),
+        row_number: 1,
+        algorithm: algorithm.clone(),
+    }).await?;
+    
+    let result = service.get_transaction_by_source_row(&fingerprint, &algorithm, args.row)?;
+    
+    println!("\n{}", "=".repeat(60));
+    println!("📍 源表第{}行 -> 处理结果第{}行{}",
+        result.original_row_number,
+        result.processed_row_number,
+        if result.position_changed { "（验证阶段已重排）" } else { "" });
+    println!("{}", "=".repeat(60));
+    let tx = &result.transaction;
+    println!("交易时间: {}", tx.timestamp);
+    println!("收入: ¥{} / 支出: ¥{}", tx.income_amount, tx.expense_amount);
+    println!("余额: ¥{}", tx.balance);
+    println!("资金属性: {}", tx.fund_attr);
+    if let Some(behavior) = &tx.behavior {
+        println!("行为性质: {behavior}");
+    }
+    if let (Some(personal), Some(company)) = (tx.personal_ratio, tx.company_ratio) {
+        println!("资金占比: 个人{personal} / 公司{company}");
+    }
+    
+    Ok(())
+}
+
+/// 批量时点查询：一次分析返回多行的追踪器状态快照
+async fn batch_query(args: &BatchQueryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::{TimePointService, BatchTimePointQueryRequest};
+
+    let algorithm = args.algorithm.clone();
+
+    // 合并--rows与--rows-file给出的行号
+    let mut row_numbers: Vec<usize> = Vec::new();
+    if let Some(rows) = &args.rows {
+        for part in rows.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            row_numbers.push(part.parse::<usize>()
+                .map_err(|_| format!("无效的行号: {part}"))?);
+        }
+    }
+    if let Some(path) = &args.rows_file {
+        for line in std::fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            row_numbers.push(line.parse::<usize>()
+                .map_err(|_| format!("行号文件中存在无效行号: {line}"))?);
+        }
+    }
+    if row_numbers.is_empty() {
+        return Err("请通过--rows或--rows-file给出至少一个行号".into());
+    }
+
+    println!("🔍 批量时点查询: {} 共{}个行号（{}算法）", args.input, row_numbers.len(), algorithm);
+
+    let mut service = TimePointService::new(algorithm.clone())?;
+    let result = service.query_time_points_batch(BatchTimePointQueryRequest {
+        file_path: args.input.clone(),
+        algorithm,
+        row_numbers,
+    }).await?;
+
+    println!("\n{}", "=".repeat(60));
+    println!("📍 批量时点查询: {}个快照 / 共{}行（耗时{:.3}秒）",
+        result.snapshots.len(), result.total_rows, result.processing_time);
+    println!("{}", "=".repeat(60));
+    for snapshot in &result.snapshots {
+        let state = &snapshot.tracker_state;
+        println!("第{}行 {} | 余额¥{} 个人¥{} 公司¥{} | 累计挪用¥{} 累计垫付¥{}",
+            snapshot.row_number,
+            snapshot.transaction.timestamp,
+            state.current_balance,
+            state.personal_balance,
+            state.company_balance,
+            state.misappropriation_amount,
+            state.advance_amount);
+    }
+    if !result.invalid_rows.is_empty() {
+        println!("\n⚠️ 越界行号已跳过: {:?}（有效范围1-{}）", result.invalid_rows, result.total_rows);
+    }
+
+    Ok(())
+}
+
+/// 按期汇总报告：按日/按月打印余额与缺口走势
+async fn period_report(args: &ReportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::{TimePointService, PeriodSummaryQueryRequest};
+
+    let algorithm = args.algorithm.clone();
+    println!("📊 按期汇总报告: {}（{}算法）", args.input, algorithm);
+
+    let mut service = TimePointService::new(algorithm.clone())?;
+    let report = service.query_period_summary(PeriodSummaryQueryRequest {
+        file_path: args.input.clone(),
+        algorithm,
+    }).await?;
+
+    let sections: Vec<(&str, &Vec<_>)> = if args.daily {
+        vec![("按日汇总", &report.daily), ("按月汇总", &report.monthly)]
+    } else {
+        vec![("按月汇总", &report.monthly)]
+    };
+    for (title, periods) in sections {
+        println!("\n{}", "=".repeat(60));
+        println!("📅 {}（{}个期间）", title, periods.len());
+        println!("{}", "=".repeat(60));
+        for period in periods {
+            println!("{} | 流入¥{} 流出¥{} | 期末余额¥{}（个人¥{} 公司¥{}）| 累计挪用¥{} 缺口¥{}",
+                period.period,
+                period.total_inflow,
+                period.total_outflow,
+                period.end_balance,
+                period.end_personal_balance,
+                period.end_company_balance,
+                period.cumulative_misappropriation,
+                period.funding_gap);
+        }
+    }
+
+    Ok(())
+}
+
+/// 测试资金属性分类规则：逐个词面展示归类结果与命中的规则
+fn test_classification_rules(args: &TestRulesArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::utils::classification_rules::ClassificationRuleSet;
+
+    let rule_set = ClassificationRuleSet::load_from_file(&args.rules)?;
+    println!("📑 已加载分类规则: {}（{} 条）", args.rules, rule_set.rules.len());
+
+    // 待测词面：命令行参数 + 可选的Excel文件中全部不重复资金属性
+    let mut attributes: Vec<(String, usize)> = args.attributes.iter()
+        .map(|attr| (attr.clone(), 0))
+        .collect();
+    if let Some(input) = &args.input {
+        let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+        let transactions = processor.read_transactions(input)?;
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for tx in &transactions {
+            *counts.entry(tx.fund_attribute.clone()).or_insert(0) += 1;
+        }
+        let mut from_file: Vec<(String, usize)> = counts.into_iter().collect();
+        from_file.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        println!("📂 从 {} 提取到 {} 个不重复资金属性", input, from_file.len());
+        attributes.extend(from_file);
+    }
+
+    if attributes.is_empty() {
+        return Err("未提供任何待测资金属性（直接附加词面参数或指定 --input）".into());
+    }
+
+    println!("\n{}", "=".repeat(60));
+    let mut unmatched = 0usize;
+    for (attribute, count) in &attributes {
+        let count_display = if *count > 0 { format!("（{count}笔）") } else { String::new() };
+        match rule_set.classify_with_rule(attribute) {
+            Some(rule) => {
+                println!("✅ {attribute}{count_display} -> {}（规则\"{}\" 优先级{}）",
+                    rule.category.label(), rule.pattern, rule.priority);
+            }
+            None => {
+                unmatched += 1;
+                println!("⚠️ {attribute}{count_display} -> 未命中（分析时回退内置关键字判定）");
+            }
+        }
+    }
+    println!("{}", "=".repeat(60));
+    println!("共测试 {} 个词面，未命中 {} 个", attributes.len(), unmatched);
+
+    Ok(())
+}
+
+/// 运行前估算：按历史吞吐预测耗时与输出大小（不运行分析）
+fn estimate_run(args: &EstimateArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use flux_backend::{EstimateBasis, PerformanceStatsService};
+
+    let algorithm = args.algorithm.as_str();
+    println!("📊 运行前估算: {} ({})", args.input, algorithm);
+
+    // 只定位表头并裁剪空白尾行，不做逐行解析
+    let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+    let rows = processor.count_data_rows(&args.input)?;
+    println!("📋 有效数据行数: {rows}");
+
+    let stats_path = args.stats.as_deref()
+        .unwrap_or(PerformanceStatsService::DEFAULT_STATS_FILE);
+    let (stats, _) = PerformanceStatsService::open(stats_path)?;
+    let estimate = stats.estimate(algorithm, rows);
+
+    let basis_display = match estimate.basis {
+        EstimateBasis::AlgorithmHistory =>
+            format!("基于{}算法的{}次历史运行", algorithm, estimate.sample_count),
+        EstimateBasis::AnyHistory =>
+            format!("该算法无历史样本，基于全部算法的{}次运行", estimate.sample_count),
+        EstimateBasis::BuiltinDefaults =>
+            "无历史样本，使用内置默认吞吐（完成首次分析后会更准确）".to_string(),
+    };
+
+    println!("\n{}", "=".repeat(60));
+    println!("⏳ 预计耗时: {}", format_duration_secs(estimate.estimated_secs));
+    println!("💾 预计结果文件大小: {}", format_bytes(estimate.estimated_output_bytes));
+    println!("📑 估算依据: {basis_display}");
+    println!("{}", "=".repeat(60));
+    println!("⚠️ 估算仅供安排时间参考，实际耗时受数据特征与硬件负载影响");
+
+    Ok(())
+}
+
+/// 把秒数格式化为可读时长
+fn format_duration_secs(secs: f64) -> String {
+    if secs < 60.0 {
+        format!("约{secs:.0}秒")
+    } else if secs < 3600.0 {
+        format!("约{:.0}分{:.0}秒", (secs / 60.0).floor(), secs % 60.0)
+    } else {
+        format!("约{:.0}小时{:.0}分", (secs / 3600.0).floor(), (secs % 3600.0) / 60.0)
+    }
+}
+
+/// 把字节数格式化为可读大小
+fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes < MB {
+        format!("{:.0} KB", bytes / 1024.0)
+    } else if bytes < 1024.0 * MB {
+        format!("{:.1} MB", bytes / MB)
+    } else {
+        format!("{:.2} GB", bytes / (1024.0 * MB))
+    }
+}
+
+/// 收集单个算法的全部可比较指标（摘要指标 + 可选的各资金池统计）
+fn collect_comparison_metrics(
+    summary: &flux_backend::AuditSummary,
+    pool_records: &flux_backend::OffsitePoolRecordManager,
+    include_pools: bool,
+) -> Vec<(String, rust_decimal::Decimal)> {
+    let mut metrics: Vec<(String, rust_decimal::Decimal)> = summary
+        .metric_values()
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    
+    if include_pools {
+        let mut pool_names: Vec<_> = pool_records.group_by_pool().keys().cloned().collect();
+        pool_names.sort();
+        for pool_name in pool_names {
+            if let Some(stats) = pool_records.calculate_pool_stats(&pool_name) {
+                metrics.push((format!("资金池[{}]最终余额", pool_name), stats.final_balance));
+                metrics.push((format!("资金池[{}]净盈亏", pool_name), stats.profit_loss));
+            }
+        }
+    }
+    
+    metrics
+}
+
 /// 比较两种算法的结果
-async fn compare_algorithms(input_file: &str) -> Result<(), Box<dyn std::error::Error>> {
+async fn compare_algorithms(args: &CompareArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let input_file = args.input.as_str();
     println!("🔄 开始比较FIFO与差额计算法...");
     println!("📂 输入文件: {}", input_file);
     
     let mut results = HashMap::new();
     let algorithms = ["FIFO", "BALANCE_METHOD"];
     
-    // 运行两种算法
-    for &algorithm in &algorithms {
-        println!("\n正在运行 {} 算法...", algorithm);
-        
+    // 两种算法并发运行，进度经聚合器合并为单一视图后输出
+    let aggregator = flux_backend::ProgressAggregator::new(&algorithms);
+    
+    // 进度监控任务：合并百分比每跨过10个点打印一行
+    let mut progress_rx = aggregator.subscribe();
+    let monitor = tokio::spawn(async move {
+        let mut last_printed = 0u32;
+        while progress_rx.changed().await.is_ok() {
+            let progress = progress_rx.borrow_and_update().clone();
+            let step = (progress.combined_percentage / 10.0) as u32;
+            if step > last_printed {
+                last_printed = step;
+                println!("⏳ 总进度 {:.0}%  {}", progress.combined_percentage, progress.message);
+            }
+        }
+    });
+    
+    let mut tasks = Vec::new();
+    for (run_index, &algorithm) in algorithms.iter().enumerate() {
         let service = AuditService::new().with_suppress_output(true);
-        
-        match service.analyze_financial_data(algorithm, input_file, None::<&str>).await {
+        let forwarder = aggregator.attach(run_index, service.subscribe_progress());
+        let aggregator = aggregator.clone();
+        let input_file = input_file.to_string();
+        tasks.push(tokio::spawn(async move {
+            let result = service.analyze_financial_data(algorithm, input_file.as_str(), None::<&str>).await;
+            aggregator.mark_finished(run_index).await;
+            let pool_records = service.get_offsite_pool_records().await;
+            forwarder.abort();
+            (algorithm, result, pool_records)
+        }));
+    }
+    
+    for task in tasks {
+        let (algorithm, result, pool_records) = task.await?;
+        match result {
             Ok((summary, transactions, _output_files)) => {
-                results.insert(algorithm, (summary, transactions.len()));
+                let metrics = collect_comparison_metrics(
+                    &summary,
+                    &pool_records,
+                    args.include_pools,
+                );
+                results.insert(algorithm, (metrics, summary, transactions));
                 println!("✅ {} 算法完成", algorithm);
             }
             Err(e) => {
@@ -230,34 +1268,98 @@ async fn compare_algorithms(input_file: &str) -> Result<(), Box<dyn std::error::
             }
         }
     }
+    monitor.abort();
+    
+    // 用户指定的指标筛选（逗号分隔；未指定则对比全部）
+    let selected: Option<Vec<String>> = args.metrics.as_ref().map(|m| {
+        m.split(',').map(|name| name.trim().to_string()).collect()
+    });
     
     // 显示比较结果
-    println!("\n{}", "=".repeat(80));
+    println!("\n{}", "=".repeat(96));
     println!("📊 算法对比结果");
-    println!("{}", "=".repeat(80));
+    println!("{}", "=".repeat(96));
     
-    println!("{:<20} {:<20} {:<20} {:<15}", "指标", "FIFO算法", "差额计算法", "差异");
-    println!("{}", "-".repeat(80));
+    println!("{:<24} {:<18} {:<18} {:<14} {:<12}", "指标", "FIFO算法", "差额计算法", "差异", "相对差异");
+    println!("{}", "-".repeat(96));
     
-    if let (Some((fifo_summary, fifo_count)), Some((balance_summary, balance_count))) = 
+    if let (Some((fifo_metrics, fifo_summary, fifo_transactions)), Some((balance_metrics, balance_summary, balance_transactions))) = 
         (results.get("FIFO"), results.get("BALANCE_METHOD")) {
         
-        let metrics = vec![
-            ("个人余额", fifo_summary.personal_balance, balance_summary.personal_balance),
-            ("公司余额", fifo_summary.company_balance, balance_summary.company_balance),
-            ("总余额", fifo_summary.total_balance, balance_summary.total_balance),
-        ];
+        let balance_map: HashMap<&str, rust_decimal::Decimal> = balance_metrics
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+            .collect();
         
-        for (metric, fifo_val, balance_val) in metrics {
+        let mut flagged: Vec<(String, f64)> = Vec::new();
+        let hundred = rust_decimal::Decimal::from(100);
+        
+        for (metric, fifo_val) in fifo_metrics {
+            if let Some(names) = &selected {
+                if !names.iter().any(|name| name == metric) {
+                    continue;
+                }
+            }
+            let Some(&balance_val) = balance_map.get(metric.as_str()) else {
+                continue;
+            };
+            
             let diff = balance_val - fifo_val;
-            println!("{:<20} {:<20.2} {:<20.2} {:<15.2}", metric, fifo_val, balance_val, diff);
+            // 相对差异以FIFO值为基准；基准为0且存在差异时视为无穷大差异
+            let relative_pct = if fifo_val.is_zero() {
+                if diff.is_zero() { Some(0.0) } else { None }
+            } else {
+                use rust_decimal::prelude::ToPrimitive;
+                (diff / fifo_val * hundred).to_f64()
+            };
+            
+            let relative_text = match relative_pct {
+                Some(pct) => format!("{:.2}%", pct),
+                None => "N/A".to_string(),
+            };
+            let exceeds = match relative_pct {
+                Some(pct) => pct.abs() > args.threshold,
+                None => true, // 基准为0但存在差异，必须说明
+            };
+            let marker = if exceeds { " ⚠️" } else { "" };
+            
+            println!("{:<24} {:<18.2} {:<18.2} {:<14.2} {:<12}{}", 
+                metric, fifo_val, balance_val, diff, relative_text, marker);
+            
+            if exceeds {
+                flagged.push((metric.clone(), relative_pct.unwrap_or(f64::INFINITY)));
+            }
+        }
+        
+        if !flagged.is_empty() {
+            println!("\n⚠️ 以下 {} 项指标差异超过阈值 {:.1}%，报告中需书面说明差异原因:", 
+                flagged.len(), args.threshold);
+            for (metric, pct) in &flagged {
+                if pct.is_finite() {
+                    println!("   - {} (相对差异 {:.2}%)", metric, pct);
+                } else {
+                    println!("   - {} (基准值为0，无法计算相对差异)", metric);
+                }
+            }
         }
         
         println!("\n📋 对比说明:");
         println!("1. FIFO算法：按先进先出原则分配资金来源");  
         println!("2. 差额计算法：个人余额优先扣除，简化计算逻辑");
         println!("3. 差异：正数表示差额计算法数值更大，负数表示更小");
-        println!("4. 处理记录数：FIFO {} 条，差额计算法 {} 条", fifo_count, balance_count);
+        println!("4. 相对差异以FIFO算法数值为基准");
+        println!("5. 处理记录数：FIFO {} 条，差额计算法 {} 条", fifo_transactions.len(), balance_transactions.len());
+        
+        // 按需导出并排对比报告
+        if let Some(output_path) = &args.output {
+            let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+            processor.export_comparison_results(
+                output_path,
+                "FIFO", fifo_summary, fifo_transactions,
+                "BALANCE_METHOD", balance_summary, balance_transactions,
+            )?;
+            println!("\n📊 对比报告已导出: {}", output_path);
+        }
     }
     
     Ok(())
@@ -276,7 +1378,7 @@ async fn interactive_mode() -> Result<(), Box<dyn std::error::Error>> {
     
     println!("\n可选算法:");
     for (i, algo) in algo_list.iter().enumerate() {
-        let desc = algorithms.get(*algo).unwrap();
+        let desc = &algorithms[algo.as_str()];
         println!("  {}. {}: {}", i + 1, algo, desc);
     }
     
@@ -318,7 +1420,7 @@ async fn interactive_mode() -> Result<(), Box<dyn std::error::Error>> {
     };
     
     // 运行分析
-    run_single_analysis(algorithm, input_file, None, false).await?;
+    run_single_analysis(algorithm, input_file, None, false, false, None, None, false, None, None, None, &[], None, None, false, None, None).await?;
     
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,293 @@
+//! 分析结果终端浏览器（TUI）
+//!
+//! `analyze --browse`完成后进入的交互式结果查看界面：
+//! 可滚动的交易结果表、按行为性质过滤、跳转到指定行、
+//! 查看光标所在行的追踪快照（累计挪用/垫付、余额构成等），
+//! 无需打开Excel或GUI即可快速核查结果。
+
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use flux_backend::data_models::{AuditSummary, Transaction};
+
+/// 输入模式
+enum InputMode {
+    /// 普通浏览
+    Normal,
+    /// 正在输入行为性质过滤关键词
+    Filter(String),
+    /// 正在输入跳转目标行号
+    Jump(String),
+}
+
+/// 浏览器状态
+struct Browser<'a> {
+    transactions: &'a [Transaction],
+    summary: &'a AuditSummary,
+    /// 当前过滤后的行索引（指向transactions）
+    visible: Vec<usize>,
+    /// 生效的行为性质过滤关键词
+    filter: Option<String>,
+    input_mode: InputMode,
+    table_state: TableState,
+    /// 是否显示光标行的追踪快照
+    show_snapshot: bool,
+}
+
+impl<'a> Browser<'a> {
+    fn new(transactions: &'a [Transaction], summary: &'a AuditSummary) -> Self {
+        let mut table_state = TableState::default();
+        if !transactions.is_empty() {
+            table_state.select(Some(0));
+        }
+        Self {
+            transactions,
+            summary,
+            visible: (0..transactions.len()).collect(),
+            filter: None,
+            input_mode: InputMode::Normal,
+            table_state,
+            show_snapshot: false,
+        }
+    }
+
+    /// 应用行为性质过滤并重置光标
+    fn apply_filter(&mut self, keyword: Option<String>) {
+        self.visible = match &keyword {
+            Some(kw) if !kw.is_empty() => self.transactions.iter().enumerate()
+                .filter(|(_, tx)| tx.behavior_nature.as_deref().unwrap_or("").contains(kw.as_str()))
+                .map(|(idx, _)| idx)
+                .collect(),
+            _ => (0..self.transactions.len()).collect(),
+        };
+        self.filter = keyword.filter(|kw| !kw.is_empty());
+        self.table_state.select(if self.visible.is_empty() { None } else { Some(0) });
+    }
+
+    /// 跳转到源结果中的指定行号（1开始），过滤生效时定位到其后最近的可见行
+    fn jump_to_row(&mut self, row: usize) {
+        if row == 0 || self.visible.is_empty() {
+            return;
+        }
+        let target = self.visible.iter()
+            .position(|&idx| idx + 1 >= row)
+            .unwrap_or(self.visible.len() - 1);
+        self.table_state.select(Some(target));
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0) as isize;
+        let max = self.visible.len() as isize - 1;
+        let next = (current + delta).clamp(0, max);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// 光标所在的交易（过滤后）
+    fn selected_transaction(&self) -> Option<(usize, &'a Transaction)> {
+        let selected = self.table_state.selected()?;
+        let idx = *self.visible.get(selected)?;
+        Some((idx, &self.transactions[idx]))
+    }
+}
+
+/// 进入结果浏览界面，q退出
+pub fn browse_results(transactions: &[Transaction], summary: &AuditSummary) -> io::Result<()> {
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::CrosstermBackend::new(io::stdout()))?;
+
+    let mut browser = Browser::new(transactions, summary);
+    let result = run_event_loop(&mut terminal, &mut browser);
+
+    // 无论正常退出还是报错都恢复终端状态
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    result
+}
+
+fn run_event_loop(
+    terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    browser: &mut Browser,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, browser))?;
+
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match &mut browser.input_mode {
+            InputMode::Normal => match key.code {
+                KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => browser.move_cursor(-1),
+                KeyCode::Down | KeyCode::Char('j') => browser.move_cursor(1),
+                KeyCode::PageUp => browser.move_cursor(-20),
+                KeyCode::PageDown => browser.move_cursor(20),
+                KeyCode::Home => browser.move_cursor(isize::MIN + 1),
+                KeyCode::End => browser.move_cursor(isize::MAX - 1),
+                KeyCode::Char('f') | KeyCode::Char('/') => {
+                    browser.input_mode = InputMode::Filter(String::new());
+                }
+                KeyCode::Char('g') => {
+                    browser.input_mode = InputMode::Jump(String::new());
+                }
+                KeyCode::Enter | KeyCode::Char('i') => {
+                    browser.show_snapshot = !browser.show_snapshot;
+                }
+                KeyCode::Esc => {
+                    if browser.show_snapshot {
+                        browser.show_snapshot = false;
+                    } else {
+                        browser.apply_filter(None);
+                    }
+                }
+                _ => {}
+            },
+            InputMode::Filter(buffer) => match key.code {
+                KeyCode::Enter => {
+                    let keyword = buffer.clone();
+                    browser.input_mode = InputMode::Normal;
+                    browser.apply_filter(Some(keyword));
+                }
+                KeyCode::Esc => browser.input_mode = InputMode::Normal,
+                KeyCode::Backspace => { buffer.pop(); }
+                KeyCode::Char(c) => buffer.push(c),
+                _ => {}
+            },
+            InputMode::Jump(buffer) => match key.code {
+                KeyCode::Enter => {
+                    let row = buffer.parse::<usize>().unwrap_or(0);
+                    browser.input_mode = InputMode::Normal;
+                    browser.jump_to_row(row);
+                }
+                KeyCode::Esc => browser.input_mode = InputMode::Normal,
+                KeyCode::Backspace => { buffer.pop(); }
+                KeyCode::Char(c) if c.is_ascii_digit() => buffer.push(c),
+                _ => {}
+            },
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, browser: &mut Browser) {
+    let [header_area, table_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+
+    // 摘要行
+    let header = format!(
+        "📊 共{}行  个人余额 ¥{:.2}  公司余额 ¥{:.2}  累计挪用 ¥{:.2}  累计垫付 ¥{:.2}{}",
+        browser.transactions.len(),
+        browser.summary.personal_balance,
+        browser.summary.company_balance,
+        browser.summary.total_misappropriation,
+        browser.summary.total_advance_payment,
+        browser.filter.as_ref().map_or(String::new(), |kw| format!("  [过滤: {kw} -> {}行]", browser.visible.len())),
+    );
+    frame.render_widget(Paragraph::new(header).style(Style::default().add_modifier(Modifier::BOLD)), header_area);
+
+    // 结果表
+    let rows: Vec<Row> = browser.visible.iter().map(|&idx| {
+        let tx = &browser.transactions[idx];
+        Row::new(vec![
+            format!("{}", idx + 1),
+            tx.transaction_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+            format!("{:.2}", tx.income_amount),
+            format!("{:.2}", tx.expense_amount),
+            format!("{:.2}", tx.balance),
+            tx.fund_attribute.clone(),
+            tx.behavior_nature.clone().unwrap_or_default(),
+        ])
+    }).collect();
+
+    let table = Table::new(rows, [
+        Constraint::Length(7),
+        Constraint::Length(19),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(16),
+        Constraint::Min(20),
+    ])
+        .header(Row::new(vec!["行号", "交易时间", "收入", "支出", "余额", "资金属性", "行为性质"])
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+        .row_highlight_style(Style::default().bg(Color::DarkGray))
+        .block(Block::default().borders(Borders::ALL).title("分析结果"));
+    frame.render_stateful_widget(table, table_area, &mut browser.table_state);
+
+    // 底部提示/输入行
+    let footer = match &browser.input_mode {
+        InputMode::Normal =>
+            "↑↓滚动  f过滤行为  g跳转行号  Enter快照  Esc清除过滤  q退出".to_string(),
+        InputMode::Filter(buffer) => format!("行为性质过滤: {buffer}█ (Enter确认, Esc取消)"),
+        InputMode::Jump(buffer) => format!("跳转到行号: {buffer}█ (Enter确认, Esc取消)"),
+    };
+    frame.render_widget(Paragraph::new(footer).style(Style::default().fg(Color::Cyan)), footer_area);
+
+    // 追踪快照弹窗
+    if browser.show_snapshot {
+        if let Some((idx, tx)) = browser.selected_transaction() {
+            draw_snapshot_popup(frame, idx, tx);
+        }
+    }
+}
+
+/// 光标行的追踪快照：该行处理完成后追踪器的累计状态
+fn draw_snapshot_popup(frame: &mut Frame, idx: usize, tx: &Transaction) {
+    use rust_decimal::Decimal;
+
+    let fmt_opt = |value: Option<Decimal>| value.map_or("-".to_string(), |v| format!("¥{v:.2}"));
+    let fmt_ratio = |value: Option<Decimal>| value.map_or("-".to_string(), |v| format!("{:.2}%", v * Decimal::from(100)));
+
+    let mut lines = vec![
+        Line::from(format!("资金占比: 个人 {} / 公司 {}", fmt_ratio(tx.personal_ratio), fmt_ratio(tx.company_ratio))),
+        Line::from(format!("余额构成: 个人 {} / 公司 {}", fmt_opt(tx.personal_balance), fmt_opt(tx.company_balance))),
+        Line::from(format!("累计挪用: {}  累计垫付: {}", fmt_opt(tx.cumulative_misappropriation), fmt_opt(tx.cumulative_advance))),
+        Line::from(format!("已归还本金: 公司 {} / 个人 {}",
+            fmt_opt(tx.cumulative_company_principal_returned),
+            fmt_opt(tx.cumulative_personal_principal_returned))),
+        Line::from(format!("应分配利润: 个人 {} / 公司 {}",
+            fmt_opt(tx.cumulative_personal_profit), fmt_opt(tx.cumulative_company_profit))),
+        Line::from(format!("资金缺口: {}", fmt_opt(tx.funding_gap))),
+    ];
+    if let Some(breakdown) = &tx.fund_source_breakdown {
+        lines.push(Line::from(format!("资金来源: {breakdown}")));
+    }
+    if let Some(explanation) = &tx.behavior_explanation {
+        lines.push(Line::from(format!("判定依据: {explanation}")));
+    }
+
+    let area = centered_rect(frame.area(), 70, (lines.len() + 2) as u16);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL)
+                .title(format!("第{}行追踪快照 (Enter/Esc关闭)", idx + 1))),
+        area,
+    );
+}
+
+/// 居中弹窗区域
+fn centered_rect(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let width = area.width * percent_x / 100;
+    Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height: height.min(area.height),
+    }
+}
@@ -36,13 +36,39 @@
 
 // #![deny(missing_docs)]  // 暂时禁用，待完成文档后再启用
 #![warn(clippy::all, clippy::pedantic)]
-#![allow(clippy::module_name_repetitions)]
+// pedantic中噪音较大的lint统一豁免，保留其余检查
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::must_use_candidate,
+    clippy::return_self_not_must_use,
+    clippy::unreadable_literal,
+    clippy::unused_self,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::too_many_arguments,
+    clippy::too_many_lines,
+    clippy::similar_names,
+    clippy::items_after_statements,
+    clippy::type_complexity,
+    clippy::unnecessary_wraps,
+    clippy::comparison_chain,
+    clippy::unused_async,
+    clippy::used_underscore_binding,
+    clippy::match_same_arms,
+    clippy::needless_pass_by_value
+)]
 
-pub mod algorithms;
 pub mod services;
-pub mod data_models;
-pub mod utils;
-pub mod errors;
+
+// Python绑定（可选python特性，经maturin构建为扩展模块）
+#[cfg(feature = "python")]
+mod python_api;
+
+// 纯分析引擎（算法、数据模型、验证器、导出器）拆分至flux-engine crate，
+// 这里完整重导出，既有调用方的使用路径不变
+pub use flux_engine::{algorithms, data_models, errors, interfaces, optimizations, utils};
 
 // 重新导出核心类型
 pub use algorithms::*;
@@ -52,7 +78,7 @@ pub use errors::*;
 pub use utils::*;
 
 // 重新导出常用的外部依赖
-pub use rust_decimal;
+pub use flux_engine::rust_decimal;
 
 /// 库版本信息
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
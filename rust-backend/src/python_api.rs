@@ -0,0 +1,146 @@
+//! Python绑定（可选`python`特性）
+//!
+//! 老流程的notebook脚本基于Python版本编写；本模块通过PyO3把
+//! 审计分析、时点查询与流水验证暴露给Python，返回值为
+//! pandas友好的dict/records结构（`pandas.DataFrame(result["records"])`
+//! 可直接建表）。经maturin构建：
+//!
+//! ```text
+//! maturin develop --features python
+//! ```
+
+// pyo3 0.22的宏展开会触发该lint，升级到0.23后可移除
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+/// 在同步的Python调用里驱动异步服务
+fn block_on<F: std::future::Future>(future: F) -> PyResult<F::Output> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| PyRuntimeError::new_err(format!("创建异步运行时失败: {e}")))?;
+    Ok(runtime.block_on(future))
+}
+
+/// 把`serde_json`值递归转换为Python对象（dict/list/标量）
+fn json_to_py(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
+    match value {
+        serde_json::Value::Null => Ok(py.None()),
+        serde_json::Value::Bool(flag) => Ok(flag.into_py(py)),
+        serde_json::Value::Number(number) => {
+            if let Some(int) = number.as_i64() {
+                Ok(int.into_py(py))
+            } else {
+                Ok(number.as_f64().unwrap_or(f64::NAN).into_py(py))
+            }
+        }
+        serde_json::Value::String(text) => Ok(text.into_py(py)),
+        serde_json::Value::Array(items) => {
+            let list = PyList::empty_bound(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            Ok(list.into())
+        }
+        serde_json::Value::Object(map) => {
+            let dict = PyDict::new_bound(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            Ok(dict.into())
+        }
+    }
+}
+
+/// 把可序列化结果转换为Python对象
+fn to_py<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_value(value)
+        .map_err(|e| PyRuntimeError::new_err(format!("结果序列化失败: {e}")))?;
+    json_to_py(py, &json)
+}
+
+/// 运行完整审计分析
+///
+/// 返回dict：`summary`（审计摘要）、`records`（逐行结果，records
+/// 结构可直接建`DataFrame`）、`output_files`（导出文件路径列表）
+#[pyfunction]
+#[pyo3(signature = (algorithm, input_file, output_file=None))]
+fn analyze(
+    py: Python<'_>,
+    algorithm: &str,
+    input_file: &str,
+    output_file: Option<&str>,
+) -> PyResult<PyObject> {
+    let service = crate::services::AuditService::new().with_suppress_output(true);
+    let (summary, transactions, output_files) =
+        block_on(service.analyze_financial_data(algorithm, input_file, output_file))?
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+    let result = serde_json::json!({
+        "summary": summary,
+        "records": transactions,
+        "output_files": output_files,
+    });
+    json_to_py(py, &result)
+}
+
+/// 查询指定行处理完成后的追踪器状态（时点查询）
+#[pyfunction]
+#[pyo3(signature = (file_path, row_number, algorithm="FIFO"))]
+fn query_time_point(
+    py: Python<'_>,
+    file_path: &str,
+    row_number: usize,
+    algorithm: &str,
+) -> PyResult<PyObject> {
+    let mut service = crate::services::TimePointService::new(algorithm.to_string())
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    let request = crate::services::TimePointQueryRequest {
+        file_path: file_path.to_string(),
+        row_number,
+        algorithm: algorithm.to_string(),
+    };
+    let result = block_on(service.query_time_point(request))?
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    to_py(py, &result)
+}
+
+/// 读取流水并做完整性验证（不运行算法）
+///
+/// 返回dict：`is_valid`、`errors_count`、`optimizations_count`、
+/// `summary`、`errors`（records结构）与机器可读的`report`
+#[pyfunction]
+fn validate(py: Python<'_>, input_file: &str) -> PyResult<PyObject> {
+    let processor = crate::utils::ExcelProcessor::new(crate::data_models::Config::new());
+    let transactions = processor
+        .read_transactions(input_file)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    let mut validator = crate::utils::UnifiedValidator::new();
+    let result = validator
+        .validate_transactions(&transactions)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+    let errors: Vec<serde_json::Value> = result.errors.iter()
+        .map(|error| serde_json::json!({"row": error.row, "message": error.message}))
+        .collect();
+    let payload = serde_json::json!({
+        "is_valid": result.is_valid,
+        "errors_count": result.errors_count,
+        "optimizations_count": result.optimizations_count,
+        "summary": result.summary,
+        "errors": errors,
+        "report": result.report,
+    });
+    json_to_py(py, &payload)
+}
+
+/// Python模块入口（模块名`flux_audit`，见`pyproject.toml`）
+#[pymodule]
+fn flux_audit(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(analyze, m)?)?;
+    m.add_function(wrap_pyfunction!(query_time_point, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add("__version__", crate::VERSION)?;
+    Ok(())
+}
@@ -4,29 +4,61 @@
 //! 支持进度回调和用户反馈机制
 
 use crate::data_models::{
-    Config, AuditSummary, Transaction, 
+    Config, AuditSummary, AuditWarning, Transaction, 
     TauriAuditConfig, TauriAuditResult, TauriProcessStatus,
-    OffsitePoolRecordManager
+    OffsitePoolRecordManager, OpeningBalanceOverride, SameTimeOrderingConfig
 };
 use crate::utils::{ExcelProcessor, UnifiedValidator};
-use crate::algorithms::{FifoTracker, BalanceMethodTracker};
+use crate::algorithms::BalanceMethodTracker;
 use crate::errors::{AuditError, AuditResult};
+use crate::interfaces::{AlgorithmRegistry, Tracker};
 use log::info;
+use rust_decimal::Decimal;
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Mutex;
 use std::collections::HashMap;
 use std::time::Instant;
 
 /// 进度报告信息
-#[derive(Debug, Clone)]
+///
+/// 通过[`AuditService::subscribe_progress`]的watch通道发布，
+/// GUI侧可直接序列化后经`window.emit`转发给前端
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ProgressReport {
     pub stage: String,
     pub current: usize,
     pub total: usize,
     pub percentage: f64,
+    /// 按阶段权重折算的整体进度（0-100，跨阶段单调递增）
+    #[serde(default)]
+    pub overall_percentage: f64,
     pub message: String,
 }
 
+/// 运行级日志条目
+///
+/// 每条GUI日志携带所属分析运行的`run_id`，连续多次分析共用同一个
+/// 服务实例时，前端与导出的审计轨迹可按运行过滤，不再混入上次
+/// 分析的日志（运行之外产生的日志`run_id`为None）
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RunLogEntry {
+    /// 所属分析运行（分析开始时生成，见[`AuditService::last_log_run_id`]）
+    pub run_id: Option<String>,
+    /// 日志文本（与原Vec<String>条目一致）
+    pub message: String,
+}
+
+/// 运行级日志上下文状态
+#[derive(Debug, Clone, Default)]
+struct LogRunState {
+    /// 进行中分析的`run_id`（分析结束后清空）
+    current: Option<String>,
+    /// 最近一次分析的`run_id`（供分析结束后按运行取日志）
+    last: Option<String>,
+}
+
 /// 阶段状态
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProcessingStage {
@@ -39,6 +71,179 @@ pub enum ProcessingStage {
     ResultExport,
 }
 
+impl ProcessingStage {
+    /// 阶段显示名称（用于进度事件）
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DataPreprocessing => "数据预处理",
+            Self::FlowValidation => "流水完整性验证",
+            Self::DataValidation => "数据验证",
+            Self::InitialBalanceCalculation => "初始余额计算",
+            Self::AlgorithmProcessing => "算法处理",
+            Self::ResultGeneration => "结果生成",
+            Self::ResultExport => "结果导出",
+        }
+    }
+}
+
+/// 快速扫描（抽样估算）结果
+///
+/// 基于分层抽样的初步挪用/垫付估算，结果带明确的局限性说明，
+/// 仅用于在完整分析前快速判断是否值得深入排查
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QuickScanResult {
+    /// 原始总行数
+    pub total_rows: usize,
+    /// 实际纳入抽样的行数
+    pub sampled_rows: usize,
+    /// 抽样间隔（每N行取1行）
+    pub sample_interval: usize,
+    /// 大额全量纳入阈值
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub amount_threshold: Decimal,
+    /// 抽样支出金额占总支出金额的覆盖率（0-1）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string_ratio")]
+    pub expense_coverage: Decimal,
+    /// 挪用金额初步估算（按支出覆盖率线性外推）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub estimated_misappropriation: Decimal,
+    /// 垫付金额初步估算（按支出覆盖率线性外推）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub estimated_advance_payment: Decimal,
+    /// 扫描耗时（秒）
+    pub scan_time_secs: f64,
+    /// 局限性说明
+    pub caveats: Vec<String>,
+}
+
+/// 数据集概览统计
+///
+/// 完整分析前的快速体检（只读取不验证），
+/// 用于确认加载的是正确的流水文件，避免在错误数据上跑长时间分析
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DatasetStats {
+    /// 总行数
+    pub total_rows: usize,
+    /// 最早交易时间
+    pub start_time: Option<String>,
+    /// 最晚交易时间
+    pub end_time: Option<String>,
+    /// 总收入金额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub total_income: Decimal,
+    /// 总支出金额
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string")]
+    pub total_expense: Decimal,
+    /// 资金属性分布（按出现次数降序）
+    pub fund_attribute_counts: Vec<(String, usize)>,
+    /// 层级属性的上卷分布（含各中间层级路径；属性均为平铺时为空）
+    #[serde(default)]
+    pub fund_attribute_rollup: Vec<(String, usize)>,
+    /// 疑似投资产品（资金属性命中产品前缀规则，含出现次数）
+    pub investment_products: Vec<(String, usize)>,
+    /// 统计耗时（秒）
+    pub scan_time_secs: f64,
+}
+
+/// 时间线采样粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineSampling {
+    /// 每笔交易一个采样点
+    PerTransaction,
+    /// 每日一个采样点（取当日最后一笔的状态）
+    PerDay,
+}
+
+/// 资金走势时间线
+///
+/// 统计面板画余额与缺口走势图用。各序列为等长的列式数组
+/// （而非逐点对象），序列化体积小，前端可直接喂给图表库
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TimelineSeries {
+    /// 采样点标签（逐笔为"YYYY-MM-DD HH:MM:SS"，按日为"YYYY-MM-DD"）
+    pub labels: Vec<String>,
+    /// 各采样点对应的处理结果行号（按日采样时为当日最后一行）
+    pub rows: Vec<usize>,
+    /// 个人余额序列
+    pub personal_balance: Vec<Decimal>,
+    /// 公司余额序列
+    pub company_balance: Vec<Decimal>,
+    /// 累计挪用序列
+    pub cumulative_misappropriation: Vec<Decimal>,
+    /// 资金缺口序列
+    pub funding_gap: Vec<Decimal>,
+}
+
+impl TimelineSeries {
+    /// 追加一个采样点
+    fn push_point(&mut self, label: String, row: usize, transaction: &Transaction) {
+        self.labels.push(label);
+        self.rows.push(row);
+        self.personal_balance.push(transaction.personal_balance.unwrap_or(Decimal::ZERO));
+        self.company_balance.push(transaction.company_balance.unwrap_or(Decimal::ZERO));
+        self.cumulative_misappropriation.push(transaction.cumulative_misappropriation.unwrap_or(Decimal::ZERO));
+        self.funding_gap.push(transaction.funding_gap.unwrap_or(Decimal::ZERO));
+    }
+
+    /// 丢弃最后一个采样点（按日采样时被当日更晚的交易覆盖）
+    fn pop_point(&mut self) {
+        self.labels.pop();
+        self.rows.pop();
+        self.personal_balance.pop();
+        self.company_balance.pop();
+        self.cumulative_misappropriation.pop();
+        self.funding_gap.pop();
+    }
+}
+
+/// 算法行级分歧
+///
+/// FIFO与差额计算法在同一行上给出不同行为性质、
+/// 或个人占比差异超过容差时记录一条，便于审计聚焦争议交易
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AlgorithmDivergence {
+    /// 处理结果中的行号（1开始）
+    pub row_number: usize,
+    /// 交易时间
+    pub transaction_time: String,
+    /// 资金属性
+    pub fund_attribute: String,
+    /// FIFO行为性质
+    pub fifo_behavior: String,
+    /// 差额计算法行为性质
+    pub balance_behavior: String,
+    /// 行为性质是否不同
+    pub behavior_diverged: bool,
+    /// 个人占比差异（差额计算法减FIFO）
+    #[serde(with = "crate::data_models::decimal_serde::decimal_string_ratio")]
+    pub personal_ratio_delta: Decimal,
+}
+
+/// 增量分析快照
+///
+/// 以"文件路径|算法"为键缓存一次完整分析后的追踪器状态与输出。
+/// 同一文件追加新行后再次分析时，前缀摘要校验通过即可从快照继续，
+/// 仅处理新增的行
+pub struct IncrementalSnapshot {
+    /// 已处理的行数
+    processed_rows: usize,
+    /// 已处理行的前缀摘要（校验新文件是否为旧文件的增量追加）
+    prefix_digest: u64,
+    /// 快照时点的追踪器状态
+    tracker: Box<dyn Tracker>,
+    /// 已处理行的输出结果（导出时与新增行拼接）
+    processed_transactions: Vec<Transaction>,
+    /// 快照时点已收集的警告（恢复时重新并入）
+    warnings: Vec<AuditWarning>,
+}
+
+/// 增量分析快照缓存句柄
+///
+/// GUI每次分析都会新建服务实例，应用层持有该句柄并注入服务即可跨次复用快照
+pub type IncrementalCacheHandle = Arc<Mutex<HashMap<String, IncrementalSnapshot>>>;
+
 /// 进度回调函数类型
 pub type ProgressCallback = Arc<dyn Fn(ProgressReport) + Send + Sync>;
 
@@ -46,54 +251,176 @@ pub type ProgressCallback = Arc<dyn Fn(ProgressReport) + Send + Sync>;
 pub type StageCallback = Arc<dyn Fn(ProcessingStage, &str) + Send + Sync>;
 
 /// 审计服务 - 核心业务服务接口
+///
+/// 内部共享状态全部为`Arc<tokio::sync::Mutex>`，可直接`clone`后在并发任务间共享，
+/// 无需调用方再用`Arc`包裹
+#[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)] // 各开关相互独立，枚举化无收益
 pub struct AuditService {
     config: Config,
+    /// 本次运行使用的配置版本号（来自ConfigService，用于标记分析结果）
+    config_version: Option<crate::services::config_service::ConfigVersion>,
     progress_callback: Option<ProgressCallback>,
     stage_callback: Option<StageCallback>,
     suppress_output: bool,
     // GUI状态管理
     current_status: Arc<Mutex<TauriProcessStatus>>,
-    output_log: Arc<Mutex<Vec<String>>>,
+    output_log: Arc<Mutex<Vec<RunLogEntry>>>,
+    // 运行级日志上下文：当前/最近一次分析的run_id
+    log_run: Arc<Mutex<LogRunState>>,
+    // 运行期收集的结构化警告
+    warnings: Arc<Mutex<Vec<AuditWarning>>>,
+    // 验证阶段的修复报告（导出时写入结果工作簿）
+    validation_report: Arc<Mutex<Option<crate::utils::unified_validator::ValidationReport>>>,
+    // 可疑模式检测发现（导出时写入"风险标记汇总"工作表）
+    anomaly_findings: Arc<Mutex<Vec<crate::utils::anomaly_detector::AnomalyFinding>>>,
+    // 本次运行的阶段进度权重（归一化，默认内置，可按算法历史学习）
+    stage_weights: Arc<Mutex<Vec<(String, f64)>>>,
+    // 各阶段实际耗时记录（秒，成功后随吞吐样本落盘用于学习权重）
+    stage_durations: Arc<Mutex<Vec<(String, f64)>>>,
+    // 最近一次折算的整体进度（未知阶段沿用该值，保证单调）
+    overall_progress: Arc<Mutex<f64>>,
     // 场外资金池记录存储
     offsite_pool_records: Arc<Mutex<Option<OffsitePoolRecordManager>>>,
     // 投资池数据存储（用于完整统计计算）
     investment_pools_data: Arc<Mutex<Option<std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool>>>>,
+    // 最近一次FIFO运行的条目对象池命中统计（随吞吐样本落盘）
+    fifo_pool_stats: Arc<Mutex<Option<crate::optimizations::memory_pool::PoolStats>>>,
+    // 算法注册表（内置三种算法，可注入含自定义追踪器的注册表）
+    algorithms: Arc<AlgorithmRegistry>,
+    // 性能剖析开关与剖析器（--trace-profile排障模式）
+    trace_profile_enabled: bool,
+    trace_profiler: Arc<Mutex<Option<crate::services::TraceProfiler>>>,
+    // 本次运行检测到的销户时间（并入最终摘要）
+    account_closure_time: Arc<Mutex<Option<String>>>,
+    // 取消令牌：置位后算法循环在下一个检查点返回AuditError::Cancelled
+    cancel_flag: Arc<AtomicBool>,
+    // 分析时间窗（部分期间审计）：仅处理窗口内的交易
+    date_from: Option<chrono::NaiveDate>,
+    date_to: Option<chrono::NaiveDate>,
+    // 要读取的工作表名（多表工作簿；None时读取第一个工作表）
+    sheet_name: Option<String>,
+    // 合并分析的附加输入文件（按年拆分的流水等，与主输入拼接后统一分析）
+    additional_input_files: Vec<String>,
+    // 期初余额核定值（覆盖首行启发式推断）
+    opening_balance: Option<OpeningBalanceOverride>,
+    // 增量分析：开关、快照缓存与本次分析的输入文件（缓存键组成部分）
+    incremental_enabled: bool,
+    incremental_cache: IncrementalCacheHandle,
+    // 严格核对模式：全程余额核对发现不符区间时中止分析
+    strict_reconciliation: bool,
+    incremental_file: Arc<Mutex<Option<String>>>,
+    // 结构化进度通道：GUI订阅后转发，替代日志正则解析
+    progress_tx: Arc<tokio::sync::watch::Sender<ProgressReport>>,
 }
 
 impl AuditService {
     /// 创建审计服务实例
+    #[must_use] 
     pub fn new() -> Self {
         Self {
             config: Config::new(),
+            config_version: None,
             progress_callback: None,
             stage_callback: None,
             suppress_output: false,
             current_status: Arc::new(Mutex::new(TauriProcessStatus::idle())),
             output_log: Arc::new(Mutex::new(Vec::new())),
+            log_run: Arc::new(Mutex::new(LogRunState::default())),
+            warnings: Arc::new(Mutex::new(Vec::new())),
+            validation_report: Arc::new(Mutex::new(None)),
+            anomaly_findings: Arc::new(Mutex::new(Vec::new())),
+            stage_weights: Arc::new(Mutex::new(Self::default_stage_weights())),
+            stage_durations: Arc::new(Mutex::new(Vec::new())),
+            overall_progress: Arc::new(Mutex::new(0.0)),
             offsite_pool_records: Arc::new(Mutex::new(None)),
             investment_pools_data: Arc::new(Mutex::new(None)),
+            fifo_pool_stats: Arc::new(Mutex::new(None)),
+            algorithms: Arc::new(AlgorithmRegistry::with_builtins()),
+            trace_profile_enabled: false,
+            trace_profiler: Arc::new(Mutex::new(None)),
+            account_closure_time: Arc::new(Mutex::new(None)),
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            additional_input_files: Vec::new(),
+            opening_balance: None,
+            incremental_enabled: false,
+            incremental_cache: IncrementalCacheHandle::default(),
+            strict_reconciliation: false,
+            incremental_file: Arc::new(Mutex::new(None)),
+            progress_tx: Arc::new(tokio::sync::watch::channel(ProgressReport::default()).0),
         }
     }
     
     /// 使用自定义配置创建审计服务
+    #[must_use] 
     pub fn with_config(config: Config) -> Self {
         Self { 
             config,
+            config_version: None,
             progress_callback: None,
             stage_callback: None,
             suppress_output: false,
             current_status: Arc::new(Mutex::new(TauriProcessStatus::idle())),
             output_log: Arc::new(Mutex::new(Vec::new())),
+            log_run: Arc::new(Mutex::new(LogRunState::default())),
+            warnings: Arc::new(Mutex::new(Vec::new())),
+            validation_report: Arc::new(Mutex::new(None)),
+            anomaly_findings: Arc::new(Mutex::new(Vec::new())),
+            stage_weights: Arc::new(Mutex::new(Self::default_stage_weights())),
+            stage_durations: Arc::new(Mutex::new(Vec::new())),
+            overall_progress: Arc::new(Mutex::new(0.0)),
             offsite_pool_records: Arc::new(Mutex::new(None)),
             investment_pools_data: Arc::new(Mutex::new(None)),
+            fifo_pool_stats: Arc::new(Mutex::new(None)),
+            algorithms: Arc::new(AlgorithmRegistry::with_builtins()),
+            trace_profile_enabled: false,
+            trace_profiler: Arc::new(Mutex::new(None)),
+            account_closure_time: Arc::new(Mutex::new(None)),
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+            date_from: None,
+            date_to: None,
+            sheet_name: None,
+            additional_input_files: Vec::new(),
+            opening_balance: None,
+            incremental_enabled: false,
+            incremental_cache: IncrementalCacheHandle::default(),
+            strict_reconciliation: false,
+            incremental_file: Arc::new(Mutex::new(None)),
+            progress_tx: Arc::new(tokio::sync::watch::channel(ProgressReport::default()).0),
         }
     }
     
+    /// 替换算法注册表（注入含自定义追踪器的注册表需在分析开始前完成）
+    #[must_use]
+    pub fn with_algorithm_registry(mut self, registry: AlgorithmRegistry) -> Self {
+        self.algorithms = Arc::new(registry);
+        self
+    }
+
+    /// 设置本次运行使用的配置版本号
+    #[must_use] 
+    pub fn with_config_version(mut self, version: crate::services::config_service::ConfigVersion) -> Self {
+        self.config_version = Some(version);
+        self
+    }
+    
     /// 设置进度回调
     pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
         self.progress_callback = Some(callback);
         self
     }
+
+    /// 订阅结构化进度事件
+    ///
+    /// 返回watch接收端，每次进度更新都会发布最新的[`ProgressReport`]。
+    /// GUI侧用它替代对输出日志的正则解析
+    #[must_use]
+    pub fn subscribe_progress(&self) -> tokio::sync::watch::Receiver<ProgressReport> {
+        self.progress_tx.subscribe()
+    }
     
     /// 设置阶段回调
     pub fn with_stage_callback(mut self, callback: StageCallback) -> Self {
@@ -102,42 +429,252 @@ impl AuditService {
     }
     
     /// 设置是否抑制输出
+    #[must_use] 
     pub fn with_suppress_output(mut self, suppress: bool) -> Self {
         self.suppress_output = suppress;
         self
     }
     
+    /// 共享外部取消令牌
+    ///
+    /// GUI场景下每次分析都会新建服务实例，停止命令持有的是应用级令牌，
+    /// 通过本方法注入后置位即可让算法循环在下一个检查点立即退出
+    #[must_use]
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = flag;
+        self
+    }
+    
+    /// 请求取消当前分析
+    ///
+    /// 算法循环每处理一批交易检查一次令牌，命中后返回[`AuditError::Cancelled`]
+    pub fn request_cancel(&self) {
+        self.cancel_flag.store(true, Ordering::Relaxed);
+    }
+    
+    /// 检查取消令牌，已置位时返回取消错误
+    fn check_cancelled(&self) -> AuditResult<()> {
+        if self.cancel_flag.load(Ordering::Relaxed) {
+            return Err(AuditError::cancelled("用户请求停止分析"));
+        }
+        Ok(())
+    }
+    
+    /// 启用增量分析模式
+    ///
+    /// 启用后每次分析会缓存追踪器快照；同一文件追加新行后再次分析时，
+    /// 只处理新增的行。文件前缀发生任何变化都会自动回退为全量分析
+    #[must_use]
+    pub fn with_incremental(mut self, enabled: bool) -> Self {
+        self.incremental_enabled = enabled;
+        self
+    }
     
+    /// 共享外部增量快照缓存
+    ///
+    /// GUI场景下每次分析都会新建服务实例，应用层持有缓存句柄并注入，
+    /// 快照才能跨次分析复用
+    #[must_use]
+    pub fn with_incremental_cache(mut self, cache: IncrementalCacheHandle) -> Self {
+        self.incremental_cache = cache;
+        self
+    }
+
+    /// 启用严格核对模式
+    ///
+    /// 全程余额核对发现余额列与按收支重算的余额不符（疑似缺行）时
+    /// 中止分析而非仅告警，适合对数据完整性要求高的正式出具场景
+    #[must_use]
+    pub fn with_strict_reconciliation(mut self, enabled: bool) -> Self {
+        self.strict_reconciliation = enabled;
+        self
+    }
     
+    /// 设置分析时间窗（部分期间审计）
+    ///
+    /// 只处理窗口内（含边界日）的交易；窗口前的余额由首笔窗口内
+    /// 交易的余额回推得到，追踪器初始化逻辑已覆盖该场景
+    #[must_use]
+    pub fn with_time_range(
+        mut self,
+        date_from: Option<chrono::NaiveDate>,
+        date_to: Option<chrono::NaiveDate>,
+    ) -> Self {
+        self.date_from = date_from;
+        self.date_to = date_to;
+        self
+    }
+
+    /// 指定输入工作簿中要分析的工作表
+    ///
+    /// 多表工作簿中流水数据未必在第一个工作表；None时沿用
+    /// 默认行为（读第一个表），指定的工作表不存在或缺少必需列
+    /// 时分析以验证错误失败并列出可用工作表
+    #[must_use]
+    pub fn with_sheet_name(mut self, sheet_name: Option<String>) -> Self {
+        self.sheet_name = sheet_name;
+        self
+    }
+
+    /// 设置合并分析的附加输入文件
+    ///
+    /// 流水常按年份拆成多个工作簿（2019.xlsx、2020.xlsx等）；设置后
+    /// 分析前把全部文件按首笔交易时间排序拼接为一条时间线统一分析，
+    /// 相邻文件余额衔接不上时产生结构化警告而非中止
+    #[must_use]
+    pub fn with_additional_inputs(mut self, files: Vec<String>) -> Self {
+        self.additional_input_files = files;
+        self
+    }
+
+    /// 设置期初余额核定值
+    ///
+    /// 审计人员掌握经核实的期初构成时，用核定的总额与个人/公司
+    /// 拆分（金额或占比）覆盖首行启发式推断；None时沿用推断
+    #[must_use]
+    pub fn with_opening_balance(mut self, opening: Option<OpeningBalanceOverride>) -> Self {
+        self.opening_balance = opening;
+        self
+    }
+
+    /// 设置同时间戳交易排序策略（None时沿用构造配置）
+    ///
+    /// 不同银行对同一秒内多笔交易的落库顺序保证不同，
+    /// GUI每次运行可携带不同策略覆盖构造时的配置
+    #[must_use]
+    pub fn with_same_time_ordering(mut self, ordering: Option<SameTimeOrderingConfig>) -> Self {
+        if let Some(ordering) = ordering {
+            self.config.same_time_ordering = ordering;
+        }
+        self
+    }
+
+    /// 创建携带工作表选择的Excel读取器
+    fn excel_reader(&self) -> ExcelProcessor {
+        let processor = ExcelProcessor::new(self.config.clone());
+        match &self.sheet_name {
+            Some(sheet) => processor.with_sheet_name(sheet.clone()),
+            None => processor,
+        }
+    }
+
+    /// 启用性能剖析（排障模式）
+    ///
+    /// 启用后每次分析会记录各阶段耗时，并在运行结束时
+    /// 将chrome-tracing JSON写入结果目录
+    #[must_use] 
+    pub fn with_trace_profile(mut self, enabled: bool) -> Self {
+        self.trace_profile_enabled = enabled;
+        self
+    }
+    
+    /// 记录剖析区间（未启用剖析时为空操作）
+    async fn trace_record(&self, category: &str, name: &str, started_at: Instant) {
+        if let Some(profiler) = self.trace_profiler.lock().await.as_mut() {
+            profiler.record(category, name, started_at);
+        }
+    }
+    
+    
+    
+    /// 内置默认阶段权重
+    ///
+    /// 没有该算法的历史耗时样本时使用的总进度映射；有样本后
+    /// 由[`Self::set_stage_weights`]注入按算法学习到的分布
+    fn default_stage_weights() -> Vec<(String, f64)> {
+        [
+            ("数据预处理", 0.05),
+            ("流水完整性验证", 0.10),
+            ("算法处理", 0.65),
+            ("结果导出", 0.20),
+        ].iter().map(|(stage, weight)| ((*stage).to_string(), *weight)).collect()
+    }
+
+    /// 注入按算法学习到的阶段权重（内部归一化，总和非正时忽略）
+    pub async fn set_stage_weights(&self, weights: Vec<(String, f64)>) {
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return;
+        }
+        let normalized = weights.into_iter()
+            .map(|(stage, weight)| (stage, weight / total))
+            .collect();
+        *self.stage_weights.lock().await = normalized;
+    }
+
+    /// 取走本次运行的阶段耗时记录（秒），供吞吐统计随样本落盘
+    pub async fn take_stage_durations(&self) -> Vec<(String, f64)> {
+        std::mem::take(&mut *self.stage_durations.lock().await)
+    }
+
+    /// 最近一次FIFO运行的条目对象池命中统计（非FIFO运行为None）
+    pub async fn fifo_pool_stats(&self) -> Option<crate::optimizations::memory_pool::PoolStats> {
+        *self.fifo_pool_stats.lock().await
+    }
+
+    /// 记录一个阶段的实际耗时
+    async fn record_stage_duration(&self, stage: &str, start: Instant) {
+        self.stage_durations.lock().await
+            .push((stage.to_string(), start.elapsed().as_secs_f64()));
+    }
+
+    /// 按权重表把阶段内百分比折算为整体百分比（0-100）
+    ///
+    /// 权重表之外的阶段（如提示性的阶段切换事件）返回None，
+    /// 调用方沿用上一个整体进度值
+    fn overall_from_weights(weights: &[(String, f64)], stage: &str, stage_percentage: f64) -> Option<f64> {
+        let index = weights.iter().position(|(name, _)| name == stage)?;
+        let completed: f64 = weights[..index].iter().map(|(_, weight)| weight).sum();
+        Some((completed + weights[index].1 * stage_percentage / 100.0) * 100.0)
+    }
+
+    /// 计算并推进整体进度（只增不减，避免阶段切换时进度条回跳）
+    async fn compute_overall(&self, stage: &str, stage_percentage: f64) -> f64 {
+        let computed = {
+            let weights = self.stage_weights.lock().await;
+            Self::overall_from_weights(&weights, stage, stage_percentage)
+        };
+        let mut last = self.overall_progress.lock().await;
+        if let Some(value) = computed {
+            if value > *last {
+                *last = value;
+            }
+        }
+        *last
+    }
+
     /// 报告进度
-    fn report_progress(&self, stage: &str, current: usize, total: usize, message: &str) {
+    #[allow(dead_code)]
+    async fn report_progress(&self, stage: &str, current: usize, total: usize, message: &str) {
         let percentage = if total > 0 { (current as f64 / total as f64) * 100.0 } else { 0.0 };
-        
+
         let report = ProgressReport {
             stage: stage.to_string(),
             current,
             total,
             percentage,
+            overall_percentage: self.compute_overall(stage, percentage).await,
             message: message.to_string(),
         };
         
         // 更新GUI状态
-        if let Ok(mut status) = self.current_status.lock() {
-            *status = TauriProcessStatus::running(percentage, message.to_string());
-        }
+        *self.current_status.lock().await = TauriProcessStatus::running(percentage, message.to_string());
+        
+        // 发布到进度通道（无订阅者时send会失败，忽略即可）
+        let _ = self.progress_tx.send(report.clone());
         
         // 添加日志
-        self.add_output_log(&format!("⏳ {}: {}/{} ({:.1}%) - {}", stage, current, total, percentage, message));
+        self.add_output_log(&format!("⏳ {stage}: {current}/{total} ({percentage:.1}%) - {message}")).await;
         
         if let Some(ref callback) = self.progress_callback {
             callback(report);
         } else if !self.suppress_output {
-            println!("⏳ {}: {}/{} ({:.1}%) - {}", stage, current, total, percentage, message);
+            println!("⏳ {stage}: {current}/{total} ({percentage:.1}%) - {message}");
         }
     }
     
     /// 报告阶段状态
-    fn report_stage(&self, stage: ProcessingStage, message: &str) {
+    async fn report_stage(&self, stage: ProcessingStage, message: &str) {
         let emoji = match stage {
             ProcessingStage::DataPreprocessing => "📊",
             ProcessingStage::FlowValidation => "🔍",
@@ -148,33 +685,86 @@ impl AuditService {
             ProcessingStage::ResultExport => "💾",
         };
         
-        let log_message = format!("{} {}", emoji, message);
+        let log_message = format!("{emoji} {message}");
+        
+        // 发布阶段切换事件（无行数信息，percentage为0，GUI按阶段名映射展示）
+        let _ = self.progress_tx.send(ProgressReport {
+            stage: stage.name().to_string(),
+            current: 0,
+            total: 0,
+            percentage: 0.0,
+            overall_percentage: self.compute_overall(stage.name(), 0.0).await,
+            message: message.to_string(),
+        });
         
         // 添加日志
-        self.add_output_log(&log_message);
+        self.add_output_log(&log_message).await;
         
         if let Some(ref callback) = self.stage_callback {
             callback(stage, message);
         } else if !self.suppress_output {
-            println!("{}", log_message);
+            println!("{log_message}");
         }
     }
     
-    /// 添加输出日志
-    fn add_output_log(&self, message: &str) {
-        if let Ok(mut log) = self.output_log.lock() {
-            log.push(message.to_string());
-            // 限制日志数量防止内存溢出
-            if log.len() > 1000 {
-                log.drain(..500); // 保留最后500条
-            }
+    /// 记录结构化警告（同时写入GUI日志）
+    async fn add_warning(&self, warning: AuditWarning) {
+        self.add_output_log(&warning.format_for_cli()).await;
+        self.warnings.lock().await.push(warning);
+    }
+    
+    /// 获取本次运行收集的全部警告
+    pub async fn get_warnings(&self) -> Vec<AuditWarning> {
+        self.warnings.lock().await.clone()
+    }
+    
+    /// 添加输出日志（自动归属当前分析运行）
+    async fn add_output_log(&self, message: &str) {
+        let run_id = self.log_run.lock().await.current.clone();
+        let mut log = self.output_log.lock().await;
+        log.push(RunLogEntry { run_id, message: message.to_string() });
+        // 限制日志数量防止内存溢出
+        if log.len() > 1000 {
+            log.drain(..500); // 保留最后500条
         }
     }
+
+    /// 开启运行级日志上下文：之后的日志均归属返回的`run_id`
+    async fn begin_log_run(&self) -> String {
+        let run_id = format!("log-run-{}", uuid::Uuid::new_v4().simple());
+        let mut state = self.log_run.lock().await;
+        state.current = Some(run_id.clone());
+        state.last = Some(run_id.clone());
+        run_id
+    }
+
+    /// 结束运行级日志上下文（之后的日志`run_id`为None）
+    async fn end_log_run(&self) {
+        self.log_run.lock().await.current = None;
+    }
+
+    /// 最近一次分析的`run_id`（分析结束后仍可按它取日志）
+    pub async fn last_log_run_id(&self) -> Option<String> {
+        self.log_run.lock().await.last.clone()
+    }
+
+    /// 获取指定运行的日志（只含该次分析产生的条目）
+    pub async fn get_run_logs(&self, run_id: &str) -> Vec<String> {
+        self.output_log.lock().await.iter()
+            .filter(|entry| entry.run_id.as_deref() == Some(run_id))
+            .map(|entry| entry.message.clone())
+            .collect()
+    }
+
+    /// 获取带运行归属的完整日志条目（导出审计轨迹用）
+    pub async fn get_output_log_entries(&self) -> Vec<RunLogEntry> {
+        self.output_log.lock().await.clone()
+    }
     
     /// 执行完整的审计分析 - 主要业务API
     /// 
     /// # Arguments  
-    /// * `algorithm` - 算法类型 ("FIFO" 或 "BALANCE_METHOD")
+    /// * `algorithm` - 算法类型 ("FIFO" 或 "`BALANCE_METHOD`")
     /// * `input_file` - 输入Excel文件路径
     /// * `output_file` - 输出文件路径（可选）
     /// 
@@ -186,45 +776,176 @@ impl AuditService {
         input_file: P,
         output_file: Option<P>,
     ) -> AuditResult<AuditSummary> {
-        info!("开始{}算法审计分析", algorithm);
+        info!("开始{algorithm}算法审计分析");
         
         // 步骤1: 数据加载和验证
         let transactions = self.load_and_validate_data(input_file).await?;
         
         // 步骤2: 执行算法分析
-        let (summary, processed_transactions) = self.execute_algorithm(algorithm, &transactions).await?;
-        
+        let (summary, mut processed_transactions) = self.execute_algorithm(algorithm, &transactions).await?;
+
+        // 步骤2.5: 可疑模式检测（逐行风险标记 + 汇总工作表）
+        self.run_anomaly_detection(&mut processed_transactions).await;
+
         // 步骤3: 导出结果（可选）
         if let Some(output_path) = output_file {
-            self.export_results(&processed_transactions, &summary, output_path)?;
+            self.export_results(&processed_transactions, &summary, output_path).await?;
         }
         
         info!("审计分析完成");
         Ok(summary)
     }
     
+    /// 把多个输入文件的交易拼接为一条时间线
+    ///
+    /// 文件按各自首笔交易时间升序排列后依次拼接；相邻文件做衔接核对
+    /// （前一文件末笔余额应等于后一文件首笔的期初余额），衔接不上
+    /// 或时间区间重叠时产生结构化警告，交由人工核对而非中止分析
+    fn merge_input_segments(
+        mut segments: Vec<(String, Vec<Transaction>)>,
+    ) -> (Vec<Transaction>, Vec<AuditWarning>) {
+        segments.sort_by_key(|(_, txs)| txs.first().map(|tx| tx.transaction_date));
+
+        let mut warnings = Vec::new();
+        for pair in segments.windows(2) {
+            let (prev_file, prev_txs) = &pair[0];
+            let (next_file, next_txs) = &pair[1];
+            let (Some(prev_last), Some(next_first)) = (prev_txs.last(), next_txs.first()) else {
+                continue;
+            };
+            // 后一文件首笔的期初余额 = 首笔余额回退本笔收支
+            let next_opening = next_first.balance - next_first.income_amount + next_first.expense_amount;
+            if prev_last.balance != next_opening {
+                warnings.push(AuditWarning::new(
+                    "FILE_CHAIN_DISCONTINUITY",
+                    None,
+                    format!(
+                        "文件衔接断点：{prev_file}末笔余额{}与{next_file}期初余额{next_opening}不一致，差额{}",
+                        prev_last.balance,
+                        next_opening - prev_last.balance
+                    ),
+                    "核对两个文件之间是否缺失流水或存在重复导出",
+                ));
+            }
+            if next_first.transaction_date < prev_last.transaction_date {
+                warnings.push(AuditWarning::new(
+                    "FILE_TIME_OVERLAP",
+                    None,
+                    format!(
+                        "文件时间区间重叠：{next_file}首笔交易时间{}早于{prev_file}末笔交易时间{}",
+                        next_first.transaction_date.format("%Y-%m-%d %H:%M:%S"),
+                        prev_last.transaction_date.format("%Y-%m-%d %H:%M:%S")
+                    ),
+                    "确认各文件的导出区间没有互相覆盖",
+                ));
+            }
+        }
+
+        let merged = segments.into_iter().flat_map(|(_, txs)| txs).collect();
+        (merged, warnings)
+    }
+
     /// 数据加载和验证
     async fn load_and_validate_data<P: AsRef<Path>>(&self, input_file: P) -> AuditResult<Vec<Transaction>> {
         info!("加载和验证数据");
         
         // 1. 数据预处理
-        self.report_stage(ProcessingStage::DataPreprocessing, "开始数据预处理...");
-        let excel_processor = ExcelProcessor::new(self.config.clone());
-        let transactions = excel_processor.read_transactions(input_file)?;
-        
+        self.report_stage(ProcessingStage::DataPreprocessing, "开始数据预处理...").await;
+        let read_start = Instant::now();
+        let excel_processor = self.excel_reader();
+        let transactions = excel_processor.read_transactions(&input_file)?;
+        self.collect_io_retry_warnings(&excel_processor).await;
+
+        // 合并模式：读入附加文件，按首笔交易时间拼接为一条时间线
+        let transactions = if self.additional_input_files.is_empty() {
+            transactions
+        } else {
+            let mut segments = vec![(input_file.as_ref().display().to_string(), transactions)];
+            for extra_file in &self.additional_input_files {
+                let processor = self.excel_reader();
+                let extra = processor.read_transactions(extra_file)?;
+                self.collect_io_retry_warnings(&processor).await;
+                segments.push((extra_file.clone(), extra));
+            }
+            let file_count = segments.len();
+            let (merged, chain_warnings) = Self::merge_input_segments(segments);
+            info!("📂 已合并{}个输入文件共{}条交易", file_count, merged.len());
+            for warning in chain_warnings {
+                self.add_warning(warning).await;
+            }
+            merged
+        };
+        self.trace_record("stage", "Excel读取", read_start).await;
+        self.record_stage_duration(ProcessingStage::DataPreprocessing.name(), read_start).await;
+
         let transaction_count = transactions.len();
         self.report_stage(
-            ProcessingStage::DataPreprocessing, 
-            &format!("数据预处理完成，共加载 {} 条记录", transaction_count)
-        );
-        
+            ProcessingStage::DataPreprocessing,
+            &format!("数据预处理完成，共加载 {transaction_count} 条记录")
+        ).await;
+
+        // 多币种折算：外币行按静态汇率折算为基准币种后再进入验证与算法
+        let transactions = self.convert_to_base_currency(transactions).await?;
+
         // 2. 流水完整性验证
-        self.report_stage(ProcessingStage::FlowValidation, "开始流水完整性验证...");
-        let mut validator = UnifiedValidator::new();
+        self.report_stage(ProcessingStage::FlowValidation, "开始流水完整性验证...").await;
+        let validation_start = Instant::now();
+        let mut validator = UnifiedValidator::new()
+            .with_same_time_ordering(self.config.same_time_ordering.clone());
         let validation_result = validator.validate_transactions(&transactions);
+        self.trace_record("stage", "流水完整性验证", validation_start).await;
+        self.record_stage_duration(ProcessingStage::FlowValidation.name(), validation_start).await;
         
-        match validation_result {
+        let transactions = match validation_result {
             Ok(result) => {
+                // 保存修复报告，导出结果时写入"顺序修复报告"工作表
+                *self.validation_report.lock().await = Some(result.report.clone());
+                
+                // 将验证阶段的异常情况转为结构化警告
+                for scale_warning in &result.scale_warnings {
+                    self.add_warning(AuditWarning::new(
+                        "SCALE_ANOMALY",
+                        Some(scale_warning.row),
+                        scale_warning.message.clone(),
+                        "核对原始流水的金额单位与精度",
+                    )).await;
+                }
+                if result.optimizations_count > 0 {
+                    self.add_warning(AuditWarning::new(
+                        "ROW_REORDERED",
+                        None,
+                        format!("同一时间戳内交易顺序被自动修复{}处", result.optimizations_count),
+                        "确认重排后的交易顺序符合实际业务发生顺序",
+                    )).await;
+                }
+                for error in &result.errors {
+                    self.add_warning(AuditWarning::new(
+                        "BALANCE_DISCONTINUITY",
+                        Some(error.row),
+                        error.message.clone(),
+                        "人工核对该行余额与前后交易的衔接",
+                    )).await;
+                }
+                for mismatch in &result.report.reconciliation_mismatches {
+                    self.add_warning(AuditWarning::new(
+                        "BALANCE_RECONCILIATION",
+                        Some(mismatch.start_row),
+                        format!("第{}-{}行余额列与按收支重算的余额存在累计差额{}，疑似缺失或多记交易",
+                            mismatch.start_row, mismatch.end_row, mismatch.balance_delta),
+                        "核对该区间起点前后的原始流水是否有遗漏行",
+                    )).await;
+                }
+                if self.strict_reconciliation && !result.report.reconciliation_mismatches.is_empty() {
+                    let mismatch_count = result.report.reconciliation_mismatches.len();
+                    self.report_stage(
+                        ProcessingStage::FlowValidation,
+                        &format!("严格核对模式：发现{mismatch_count}处余额核对不符区间，分析中止")
+                    ).await;
+                    return Err(AuditError::validation_error(format!(
+                        "余额列与按收支重算的余额存在{mismatch_count}处不符区间（疑似缺行），严格核对模式下中止分析"
+                    )));
+                }
+
                 // 显示详细的验证和修复信息
                 if result.optimizations_count > 0 {
                     // 总发现错误数 = 成功修复数 + 未修复错误数
@@ -233,153 +954,634 @@ impl AuditService {
                         ProcessingStage::FlowValidation, 
                         &format!("流水完整性验证: 发现{}处顺序错误，贪心算法成功修复{}处", 
                             total_issues_found, result.optimizations_count)
-                    );
+                    ).await;
                 } else if result.errors_count > 0 {
                     self.report_stage(
                         ProcessingStage::FlowValidation, 
                         &format!("流水完整性验证: 发现{}处错误，无需修复", result.errors_count)
-                    );
+                    ).await;
                 } else {
-                    self.report_stage(ProcessingStage::FlowValidation, "流水完整性验证通过，数据完整无错误");
+                    self.report_stage(ProcessingStage::FlowValidation, "流水完整性验证通过，数据完整无错误").await;
                 }
                 
                 // 使用修复后的数据（如果有修复的话）
-                Ok(result.fixed_transactions.unwrap_or(transactions))
+                result.fixed_transactions.unwrap_or(transactions)
             }
             Err(e) => {
                 self.report_stage(
                     ProcessingStage::FlowValidation, 
-                    &format!("流水完整性验证失败: {}", e)
-                );
-                Err(e)
+                    &format!("流水完整性验证失败: {e}")
+                ).await;
+                return Err(e);
+            }
+        };
+        
+        // 3. 时间窗过滤（部分期间审计）
+        // 完整性验证基于全量数据，过滤放在验证之后；
+        // 窗口前的余额由首笔窗口内交易回推（见smart_initialize）
+        let transactions = self.apply_time_range(transactions).await?;
+
+        Ok(transactions)
+    }
+
+    /// 把外币行折算为基准币种
+    ///
+    /// 配置了币种列的流水按`currency.exchange_rates`静态汇率把收入、
+    /// 支出与余额折算到基准币种；存在未配置汇率的外币时直接报验证
+    /// 错误，避免不同币种的金额被静默相加。无币种信息的行原样保留
+    async fn convert_to_base_currency(&self, mut transactions: Vec<Transaction>) -> AuditResult<Vec<Transaction>> {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let currency_config = &self.config.currency;
+        let mut converted: BTreeMap<String, (Decimal, usize)> = BTreeMap::new();
+        let mut missing: BTreeSet<String> = BTreeSet::new();
+
+        for tx in &mut transactions {
+            let Some(code) = tx.currency.clone() else { continue };
+            if currency_config.is_base(&code) {
+                continue;
             }
+            match currency_config.rate_to_base(&code) {
+                Some(rate) => {
+                    tx.income_amount *= rate;
+                    tx.expense_amount *= rate;
+                    tx.balance *= rate;
+                    converted.entry(code).or_insert((rate, 0)).1 += 1;
+                }
+                None => {
+                    missing.insert(code);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let codes = missing.into_iter().collect::<Vec<_>>().join("、");
+            return Err(AuditError::validation_error(format!(
+                "流水包含未配置汇率的币种: {codes}。请在currency.exchange_rates中补全兑{}汇率后重试",
+                currency_config.base_currency
+            )));
+        }
+
+        for (code, (rate, rows)) in converted {
+            let message = format!(
+                "💱 已按静态汇率 {rate} 将 {rows} 行{code}折算为{}",
+                currency_config.base_currency
+            );
+            self.add_output_log(&message).await;
+            info!("{message}");
+            self.add_warning(AuditWarning::new(
+                "CURRENCY_CONVERTED",
+                None,
+                message,
+                "静态汇率不反映交易时点的汇率波动，必要时人工复核折算口径",
+            )).await;
+        }
+
+        Ok(transactions)
+    }
+
+    /// 把重试后才成功的IO操作转为结构化警告
+    async fn collect_io_retry_warnings(&self, excel_processor: &ExcelProcessor) {
+        for event in excel_processor.take_io_retry_events() {
+            self.add_warning(AuditWarning::new(
+                "IO_RETRY",
+                None,
+                event,
+                "检查网络盘或同步目录（OneDrive等）的稳定性",
+            )).await;
         }
     }
     
+    /// 按设置的时间窗过滤交易（未设置时原样返回）
+    async fn apply_time_range(&self, mut transactions: Vec<Transaction>) -> AuditResult<Vec<Transaction>> {
+        if self.date_from.is_none() && self.date_to.is_none() {
+            return Ok(transactions);
+        }
+        
+        let total = transactions.len();
+        transactions.retain(|tx| {
+            let date = tx.transaction_date.date();
+            self.date_from.is_none_or(|from| date >= from)
+                && self.date_to.is_none_or(|to| date <= to)
+        });
+        
+        let window_display = format!(
+            "{} ~ {}",
+            self.date_from.map_or("最早".to_string(), |d| d.to_string()),
+            self.date_to.map_or("最晚".to_string(), |d| d.to_string())
+        );
+        
+        if transactions.is_empty() {
+            return Err(AuditError::validation_error(
+                format!("时间窗 {window_display} 内没有任何交易，请检查日期范围")
+            ));
+        }
+        
+        self.report_stage(
+            ProcessingStage::DataPreprocessing,
+            &format!("时间窗过滤: {window_display}，保留 {}/{total} 条交易", transactions.len())
+        ).await;
+        info!("📋 时间窗过滤: {window_display}，保留 {}/{total} 条交易", transactions.len());
+        
+        Ok(transactions)
+    }
+    
     /// 执行算法分析
+    ///
+    /// 追踪器按名称从注册表创建，所有算法（含下游注册的自定义追踪器）
+    /// 统一走增量恢复、逐笔处理、写回快照的流程
     async fn execute_algorithm(
         &self, 
         algorithm: &str, 
         transactions: &[Transaction]
     ) -> AuditResult<(AuditSummary, Vec<Transaction>)> {
-        match algorithm {
-            "FIFO" => self.run_fifo_algorithm(transactions).await,
-            "BALANCE_METHOD" => self.run_balance_method_algorithm(transactions).await,
-            _ => Err(AuditError::validation_error(&format!("不支持的算法: {}", algorithm))),
-        }
-    }
-    
-    /// 运行FIFO算法
-    async fn run_fifo_algorithm(&self, transactions: &[Transaction]) -> AuditResult<(AuditSummary, Vec<Transaction>)> {
-        info!("执行FIFO算法分析");
+        let Some(info) = self.algorithms.get_info(algorithm) else {
+            return Err(AuditError::validation_error(format!(
+                "不支持的算法: {algorithm}（可用: {}）", self.algorithms.names().join(", ")
+            )));
+        };
+        info!("执行{}分析", info.display_name);
+        
+        // 增量模式下优先从快照恢复，仅处理追加的行
+        let resumed = self.take_incremental_snapshot(algorithm, transactions).await;
+        let (mut tracker, mut processed_transactions, start_index) = match resumed {
+            Some(IncrementalSnapshot {
+                tracker,
+                processed_rows,
+                processed_transactions,
+                ..
+            }) => (tracker, processed_transactions, processed_rows),
+            None => (
+                self.algorithms.create(algorithm, self.config.clone())
+                    .ok_or_else(|| AuditError::validation_error(format!("不支持的算法: {algorithm}")))?,
+                Vec::new(),
+                0,
+            ),
+        };
         
-        let mut tracker = FifoTracker::new(self.config.clone());
-        let processed_transactions = self.process_transactions_with_tracker(&mut tracker, transactions, "FIFO").await?;
-        let summary = tracker.get_summary()?;
+        let newly_processed = self.process_transactions_with_tracker(tracker.as_mut(), transactions, algorithm, start_index).await?;
+        processed_transactions.extend(newly_processed);
+
+        // 记录条目对象池命中统计（目前仅FIFO使用对象池；随吞吐样本落盘，验证optimizations效果）
+        if let Some(pool_stats) = tracker.memory_pool_stats() {
+            info!("📊 {algorithm}条目对象池命中率: {:.1}%（{}次请求）",
+                pool_stats.hit_rate() * 100.0, pool_stats.acquires);
+            *self.fifo_pool_stats.lock().await = Some(pool_stats);
+        }
+
+        let mut summary = tracker.get_summary()?;
+        summary.account_closure_time.clone_from(&*self.account_closure_time.lock().await);
         
         // 获取场外资金池记录（后续会用于导出）
-        self.store_offsite_pool_records(tracker.get_offsite_pool_records());
+        self.store_offsite_pool_records(tracker.get_offsite_pool_records()).await;
         // 存储投资池数据（用于完整统计计算）
-        self.store_investment_pools_data(tracker.get_investment_pools());
+        self.store_investment_pools_data(tracker.get_investment_pools()).await;
+        // 写回增量快照供下次追加分析复用
+        self.store_incremental_snapshot(algorithm, transactions, tracker, &processed_transactions).await;
         
         Ok((summary, processed_transactions))
     }
+
+    /// 可疑模式检测
+    ///
+    /// 主分析完成后扫描拆分转账、快进快出、凌晨交易与金额异常偏大
+    /// 四类模式：命中行写入"风险标记"列，发现列表留存到导出阶段
+    /// 生成"风险标记汇总"工作表，并按类别聚合为结构化警告
+    async fn run_anomaly_detection(&self, transactions: &mut [Transaction]) {
+        let detector = crate::utils::anomaly_detector::AnomalyDetector::new();
+        let findings = detector.detect(transactions);
+        if findings.is_empty() {
+            info!("📋 可疑模式检测完成: 未发现可疑模式");
+            return;
+        }
+
+        // 逐行附加标记（同一行可叠加多个类别，去重后以"; "连接）
+        for finding in &findings {
+            if let Some(tx) = transactions.get_mut(finding.row - 1) {
+                let label = finding.kind.label();
+                match &mut tx.risk_flags {
+                    Some(flags) if flags.split("; ").any(|f| f == label) => {}
+                    Some(flags) => {
+                        flags.push_str("; ");
+                        flags.push_str(label);
+                    }
+                    none => *none = Some(label.to_string()),
+                }
+            }
+        }
+
+        // 按类别聚合为警告，避免大流水把异常清单刷满
+        let mut kind_counts: Vec<(crate::utils::anomaly_detector::AnomalyKind, usize)> = Vec::new();
+        for finding in &findings {
+            match kind_counts.iter_mut().find(|(kind, _)| *kind == finding.kind) {
+                Some((_, count)) => *count += 1,
+                None => kind_counts.push((finding.kind, 1)),
+            }
+        }
+        for (kind, count) in kind_counts {
+            self.add_warning(AuditWarning::new(
+                "RISK_PATTERN",
+                None,
+                format!("检测到{count}条\"{}\"可疑模式，详见风险标记汇总工作表", kind.label()),
+                "结合业务背景核实相关交易是否存在规避申报或过账行为",
+            )).await;
+        }
+
+        info!("📋 可疑模式检测完成: 共 {} 条发现", findings.len());
+        *self.anomaly_findings.lock().await = findings;
+    }
+
+
+    /// 计算前`len`行交易的前缀摘要（行内容任何变化都会改变摘要）
+    fn transactions_prefix_digest(transactions: &[Transaction], len: usize) -> u64 {
+        use std::hash::{Hash, Hasher};
+        
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        for tx in &transactions[..len] {
+            tx.transaction_date.hash(&mut hasher);
+            tx.transaction_time.hash(&mut hasher);
+            tx.income_amount.hash(&mut hasher);
+            tx.expense_amount.hash(&mut hasher);
+            tx.balance.hash(&mut hasher);
+            tx.fund_attribute.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+    
+    /// 增量快照缓存键（未启用增量模式时为None）
+    async fn incremental_cache_key(&self, algorithm: &str) -> Option<String> {
+        if !self.incremental_enabled {
+            return None;
+        }
+        self.incremental_file.lock().await
+            .as_ref()
+            .map(|file| format!("{file}|{algorithm}"))
+    }
     
-    /// 运行差额计算法
-    async fn run_balance_method_algorithm(&self, transactions: &[Transaction]) -> AuditResult<(AuditSummary, Vec<Transaction>)> {
-        info!("执行差额计算法分析");
+    /// 尝试取出可用的增量快照
+    ///
+    /// 仅当新数据行数不少于快照行数、且前缀摘要一致（即新文件确为旧文件的
+    /// 增量追加）时快照可用；否则回退为全量分析
+    async fn take_incremental_snapshot(
+        &self,
+        algorithm: &str,
+        transactions: &[Transaction],
+    ) -> Option<IncrementalSnapshot> {
+        let key = self.incremental_cache_key(algorithm).await?;
+        let snapshot = self.incremental_cache.lock().await.remove(&key)?;
         
-        let mut tracker = BalanceMethodTracker::new(self.config.clone());
-        let processed_transactions = self.process_transactions_with_tracker(&mut tracker, transactions, "BALANCE_METHOD").await?;
-        let summary = tracker.get_summary()?;
+        if snapshot.processed_rows == 0 || snapshot.processed_rows > transactions.len() {
+            self.add_output_log("♻️ 增量快照不可用（数据行数少于已分析行数），执行全量分析").await;
+            return None;
+        }
+        if Self::transactions_prefix_digest(transactions, snapshot.processed_rows) != snapshot.prefix_digest {
+            self.add_output_log("♻️ 增量快照不可用（文件前缀内容已变化），执行全量分析").await;
+            return None;
+        }
         
-        // 获取场外资金池记录（后续会用于导出）
-        self.store_offsite_pool_records(tracker.get_offsite_pool_records());
-        // 存储投资池数据（用于完整统计计算）
-        self.store_investment_pools_data(tracker.get_investment_pools());
+        self.add_output_log(&format!(
+            "♻️ 增量分析: 文件为上次分析数据的增量追加，复用前 {} 行结果，仅处理新增 {} 行",
+            snapshot.processed_rows,
+            transactions.len() - snapshot.processed_rows
+        )).await;
         
-        Ok((summary, processed_transactions))
+        // 快照时点已收集的警告重新并入本次运行
+        for warning in &snapshot.warnings {
+            self.add_warning(warning.clone()).await;
+        }
+        
+        Some(snapshot)
+    }
+    
+    /// 分析完成后写回增量快照
+    async fn store_incremental_snapshot(
+        &self,
+        algorithm: &str,
+        transactions: &[Transaction],
+        tracker: Box<dyn Tracker>,
+        processed_transactions: &[Transaction],
+    ) {
+        let Some(key) = self.incremental_cache_key(algorithm).await else {
+            return;
+        };
+        // 销户截断会使追踪器状态与文件行数不对应，这类运行不写快照
+        if self.config.account_closure.stop_at_closure
+            && self.account_closure_time.lock().await.is_some() {
+            return;
+        }
+        
+        let snapshot = IncrementalSnapshot {
+            processed_rows: transactions.len(),
+            prefix_digest: Self::transactions_prefix_digest(transactions, transactions.len()),
+            tracker,
+            processed_transactions: processed_transactions.to_vec(),
+            warnings: self.get_warnings().await,
+        };
+        self.incremental_cache.lock().await.insert(key, snapshot);
+        info!("增量快照已缓存: {} 行", transactions.len());
+    }
+    
+    /// 检测销户（尾部持续零余额）
+    ///
+    /// 返回销户行索引——尾部零余额区间的第一行，通常即余额清零的那笔交易，
+    /// 其后的行多为结息回执、清户凭证等行政性记录。检测结果会写入警告列表，
+    /// 销户时间由调用方并入最终摘要。
+    async fn detect_account_closure(&self, transactions: &[Transaction]) -> Option<usize> {
+        *self.account_closure_time.lock().await = None;
+        let closure_config = &self.config.account_closure;
+        if !closure_config.detection_enabled || closure_config.min_trailing_zero_rows == 0 {
+            return None;
+        }
+        
+        let trailing_zero_count = transactions.iter().rev()
+            .take_while(|tx| tx.balance == Decimal::ZERO)
+            .count();
+        if trailing_zero_count < closure_config.min_trailing_zero_rows {
+            return None;
+        }
+        
+        let closure_index = transactions.len() - trailing_zero_count;
+        let closure_time = transactions[closure_index].transaction_date
+            .format("%Y-%m-%d %H:%M:%S").to_string();
+        *self.account_closure_time.lock().await = Some(closure_time.clone());
+        
+        self.add_output_log(&format!(
+            "🔒 检测到销户: 自{closure_time}起余额持续为零（共{trailing_zero_count}行）"
+        )).await;
+        self.add_warning(AuditWarning::new(
+            "ACCOUNT_CLOSED",
+            Some(closure_index + 1),
+            format!("自第{}行（{}）起余额持续为零，判定为销户", closure_index + 1, closure_time),
+            "核实销户后的行政性记录是否需要纳入分析（account_closure.stop_at_closure）",
+        )).await;
+        
+        Some(closure_index)
     }
     
     /// 通用交易处理逻辑 - 使用trait对象避免重复代码
+    ///
+    /// `start_index`大于0表示增量恢复：追踪器已包含前`start_index`行的状态，
+    /// 本次仅处理其后的行，返回值也仅包含新处理的行
     async fn process_transactions_with_tracker<T>(
         &self,
         tracker: &mut T,
         transactions: &[Transaction],
         algorithm_name: &str,
+        start_index: usize,
     ) -> AuditResult<Vec<Transaction>> 
     where
-        T: TransactionProcessor,
+        T: Tracker + ?Sized,
     {
         if transactions.is_empty() {
             return Err(AuditError::validation_error("没有交易数据"));
         }
         
+        // 销户检测：尾部持续零余额视为销户，销户后的行政性记录行可配置截断
+        let closure_index = self.detect_account_closure(transactions).await;
+        let transactions: &[Transaction] = match closure_index {
+            Some(index) if self.config.account_closure.stop_at_closure => {
+                let skipped = transactions.len() - (index + 1);
+                self.add_output_log(&format!(
+                    "🔒 已在销户行截断分析，跳过其后 {skipped} 条零余额记录"
+                )).await;
+                &transactions[..=index]
+            }
+            _ => transactions,
+        };
+        
+        // 销户截断后剩余行数可能少于快照行数，收敛到有效范围
+        let start_index = start_index.min(transactions.len());
         let total_count = transactions.len();
         
-        // 智能初始化
-        self.report_stage(
-            ProcessingStage::InitialBalanceCalculation,
-            &format!("计算初始余额...")
-        );
-        tracker.smart_initialize(&transactions[0])?;
+        // 智能初始化（增量恢复时追踪器已初始化，跳过）
+        if start_index == 0 {
+            self.report_stage(
+                ProcessingStage::InitialBalanceCalculation,
+                "计算初始余额..."
+            ).await;
+            if let Some(opening) = self.opening_balance.as_ref().filter(|o| !o.is_empty()) {
+                let first = &transactions[0];
+                let inferred = first.balance - first.income_amount + first.expense_amount;
+                let (personal, company) = opening.resolve(inferred, first.fund_attribute.contains("个人"))?;
+                let message = format!("💰 使用核定期初余额: 个人{personal} 公司{company}");
+                self.add_output_log(&message).await;
+                info!("{message}");
+                tracker.initialize_opening(personal, company, first)?;
+            } else {
+                tracker.smart_initialize(&transactions[0])?;
+            }
+        }
         
         // 开始算法处理
         self.report_stage(
             ProcessingStage::AlgorithmProcessing,
-            &format!("开始 {} 资金追踪分析...", algorithm_name)
-        );
+            &format!("开始 {algorithm_name} 资金追踪分析...")
+        ).await;
         
-        let log_message = format!("📋 总共需要处理 {} 条交易记录", total_count);
+        let log_message = if start_index > 0 {
+            format!("📋 总共 {total_count} 条交易记录，其中前 {start_index} 条复用增量快照")
+        } else {
+            format!("📋 总共需要处理 {total_count} 条交易记录")
+        };
         
         // 添加到GUI日志
-        self.add_output_log(&log_message);
+        self.add_output_log(&log_message).await;
         
         if !self.suppress_output {
-            println!("{}", log_message);
+            println!("{log_message}");
         }
         
         // 处理所有交易 - 每1000条显示一次具体进度
-        let mut processed_transactions = Vec::with_capacity(transactions.len());
-        
-        for (index, tx) in transactions.iter().enumerate() {
-            let processed_tx = tracker.process_transaction(tx)?;
+        let mut processed_transactions = Vec::with_capacity(total_count - start_index);
+        let mut chunk_start = Instant::now();
+        let anomalies_at_start = tracker.ordering_anomaly_count();
+        
+        for (offset, tx) in transactions[start_index..].iter().enumerate() {
+            let index = start_index + offset;
+            // 每256行检查一次取消令牌，命中后立即释放CPU
+            if offset.is_multiple_of(256) {
+                self.check_cancelled()?;
+            }
+            
+            let anomalies_before = tracker.ordering_anomaly_count();
+            let resets_before = tracker.pool_reset_count();
+            let mut processed_tx = tracker.process_transaction(tx)?;
+            // 配置了币种列时按原币种归集折算后净流入
+            if let Some(code) = tx.currency.as_deref() {
+                tracker.record_currency_flow(code, tx.income_amount - tx.expense_amount);
+            }
+            // 利息/手续费行单独归集收支口径（资金流仍按普通流入/流出处理）
+            tracker.record_interest_fee(&tx.fund_attribute, tx.income_amount, tx.expense_amount);
+            // 本行触发了时序异常（如赎回早于申购）时回填行号，供汇总报告引用
+            if tracker.ordering_anomaly_count() > anomalies_before {
+                tracker.assign_pending_anomaly_rows(index + 1);
+            }
+            // 本行触发了资金池盈利重置时，在主结果表行为性质上留痕并写入事件流
+            if tracker.pool_reset_count() > resets_before {
+                tracker.assign_pending_reset_rows(index + 1);
+                for event in &tracker.pool_reset_events()[resets_before..] {
+                    let marker = format!(
+                        "【资金池{}重置，实现盈利¥{:.2}】",
+                        event.pool_name, event.realized_profit
+                    );
+                    processed_tx.behavior_nature = Some(match processed_tx.behavior_nature.take() {
+                        Some(behavior) => format!("{behavior}{marker}"),
+                        None => marker.clone(),
+                    });
+                    self.add_output_log(&format!(
+                        "♻️ 资金池重置: 第{}行 {} 实现盈利¥{:.2}（{}）",
+                        index + 1, event.pool_name, event.realized_profit, event.reset_time
+                    )).await;
+                }
+            }
             processed_transactions.push(processed_tx);
             
             // 每1000条报告一次进度（显示实际处理条数）
-            if (index + 1) % 1000 == 0 || (index + 1) == total_count {
+            if (index + 1).is_multiple_of(1000) || (index + 1) == total_count {
                 let progress_percentage = (index + 1) as f64 / total_count as f64 * 100.0;
                 self.add_output_log(&format!("⏳ 交易处理: {}/{} ({:.1}%) - 处理 {} 算法交易", 
-                    index + 1, total_count, progress_percentage, algorithm_name));
+                    index + 1, total_count, progress_percentage, algorithm_name)).await;
+                let _ = self.progress_tx.send(ProgressReport {
+                    stage: ProcessingStage::AlgorithmProcessing.name().to_string(),
+                    current: index + 1,
+                    total: total_count,
+                    percentage: progress_percentage,
+                    overall_percentage: self.compute_overall(
+                        ProcessingStage::AlgorithmProcessing.name(), progress_percentage).await,
+                    message: format!("处理 {algorithm_name} 算法交易"),
+                });
+                
+                // 剖析模式下按千行区间记录耗时，便于定位数据相关的热点
+                let chunk_first = (index / 1000) * 1000 + 1;
+                self.trace_record(
+                    "algorithm",
+                    &format!("{}交易处理 {}-{}", algorithm_name, chunk_first, index + 1),
+                    chunk_start,
+                ).await;
+                chunk_start = Instant::now();
             }
         }
         
-        let completion_message = format!("✅ 所有 {} 条交易记录处理完成", total_count);
-        
+        let completion_message = format!("✅ 所有 {total_count} 条交易记录处理完成");
+
         // 添加到GUI日志
-        self.add_output_log(&completion_message);
-        
+        self.add_output_log(&completion_message).await;
+
         if !self.suppress_output {
-            println!("{}", completion_message);
+            println!("{completion_message}");
         }
-        
+
+        // 汇总时序异常：逐条转为结构化警告，便于GUI和报告集中展示
+        // （增量恢复时快照前的异常警告已随快照并入，这里只处理本次新增的）
+        let ordering_anomalies = tracker.ordering_anomalies();
+        if ordering_anomalies.len() > anomalies_at_start {
+            self.add_output_log(&format!(
+                "⚠️ 时序异常: 共{}处赎回早于任何申购记录，详见警告列表与摘要报告",
+                ordering_anomalies.len()
+            )).await;
+            for anomaly in &ordering_anomalies[anomalies_at_start..] {
+                self.add_warning(AuditWarning::new(
+                    "REDEMPTION_BEFORE_PURCHASE",
+                    anomaly.row,
+                    format!(
+                        "产品{}在任何申购记录之前发生赎回（{}，金额{:.2}），已按{}处理",
+                        anomaly.product_code, anomaly.transaction_time,
+                        anomaly.amount, anomaly.disposition
+                    ),
+                    "核查取证区间之前是否缺失该产品的申购流水",
+                )).await;
+            }
+        }
+
+        // 产品代码归并报告：同一产品的多种写法已并入同一资金池，集中告知供人工确认
+        let merged_products = tracker.product_merge_report();
+        if !merged_products.is_empty() {
+            self.add_output_log(&format!(
+                "📋 产品代码归并: {}个产品存在多种写法，已统一计入同一资金池",
+                merged_products.len()
+            )).await;
+            for record in &merged_products {
+                self.add_warning(AuditWarning::new(
+                    "PRODUCT_CODE_MERGED",
+                    None,
+                    format!(
+                        "产品\"{}\"在流水中出现{}种写法（{}），已归并为同一资金池",
+                        record.canonical, record.raw_forms.len(), record.raw_forms.join("、")
+                    ),
+                    "确认这些写法确属同一产品；若不是，请在配置的产品别名映射中区分",
+                )).await;
+            }
+        }
+
         Ok(processed_transactions)
     }
     
     /// 导出分析结果
-    fn export_results<P: AsRef<Path>>(
+    ///
+    /// 返回主结果文件的实际路径（超大结果集会按扩展名分表或降级为CSV，扩展名随之变化）
+    async fn export_results<P: AsRef<Path>>(
         &self,
         transactions: &[Transaction],
         summary: &AuditSummary,
         output_path: P,
-    ) -> AuditResult<()> {
-        self.report_stage(ProcessingStage::ResultExport, "生成分析结果...");
-        
+    ) -> AuditResult<std::path::PathBuf> {
+        self.report_stage(ProcessingStage::ResultExport, "生成分析结果...").await;
+
         let excel_processor = ExcelProcessor::new(self.config.clone());
-        excel_processor.export_analysis_results(transactions, summary, &output_path)?;
+        let anomaly_findings = self.anomaly_findings.lock().await.clone();
+        let excel_processor = if anomaly_findings.is_empty() {
+            excel_processor
+        } else {
+            excel_processor.with_anomaly_findings(anomaly_findings)
+        };
+        let excel_processor = match self.validation_report.lock().await.clone() {
+            Some(report) => excel_processor.with_validation_report(report),
+            None => excel_processor,
+        };
+        // 按日/按月汇总随结果工作簿导出，供复核直接看余额与缺口走势
+        let excel_processor = excel_processor.with_period_summary(
+            crate::utils::period_summary::PeriodSummaryReport::from_transactions(transactions),
+        );
+        // 场外资金池记录：配置开启内嵌时并入主工作簿，移交时只有一个文件
+        let embed_offsite = self.config.investment_products.embed_offsite_pool_sheet;
+        let excel_processor = match self.offsite_pool_records.lock().await.as_ref() {
+            Some(record_manager) if embed_offsite && record_manager.record_count() > 0 => {
+                info!("📋 场外资金池记录将并入主结果工作簿: {} 条", record_manager.record_count());
+                excel_processor.with_offsite_pool_records(record_manager.clone())
+            }
+            _ => excel_processor,
+        };
+
+        // 导出进度回调：在阻塞写入过程中直接走同步通道，避免async上下文
+        // （权重表在进入闭包前取快照，闭包内无法await）
+        let progress_tx = Arc::clone(&self.progress_tx);
+        let progress_callback = self.progress_callback.clone();
+        let stage_weights = self.stage_weights.lock().await.clone();
+        let export_progress = move |current: usize, total: usize| {
+            let percentage = if total > 0 { (current as f64 / total as f64) * 100.0 } else { 0.0 };
+            let report = ProgressReport {
+                stage: "结果导出".to_string(),
+                current,
+                total,
+                percentage,
+                overall_percentage: Self::overall_from_weights(&stage_weights, "结果导出", percentage)
+                    .unwrap_or(percentage),
+                message: format!("已写出 {current}/{total} 行"),
+            };
+            let _ = progress_tx.send(report.clone());
+            if let Some(ref callback) = progress_callback {
+                callback(report);
+            }
+        };
+
+        let main_file_path = excel_processor.export_analysis_results_with_progress(
+            transactions, summary, &output_path, Some(&export_progress))?;
         
-        // 导出场外资金池记录（如果存在）
-        if let Ok(records) = self.offsite_pool_records.lock() {
+        // 导出场外资金池记录（如果存在且未配置内嵌）
+        if embed_offsite {
+            info!("📋 场外资金池记录已并入主结果工作簿，跳过独立文件导出");
+        } else {
+            let records = self.offsite_pool_records.lock().await;
             if let Some(ref record_manager) = *records {
                 info!("🔍 检测到场外资金池记录: {} 条", record_manager.record_count());
                 
@@ -398,7 +1600,7 @@ impl AuditService {
                     self.report_stage(
                         ProcessingStage::ResultExport,
                         &format!("场外资金池记录已保存到: {}", pool_file_path.display())
-                    );
+                    ).await;
                     
                     info!("✅ 场外资金池记录导出完成!");
                 } else {
@@ -407,26 +1609,274 @@ impl AuditService {
             } else {
                 info!("📋 场外资金池记录管理器为空");
             }
-        } else {
-            info!("📋 无法获取场外资金池记录锁");
         }
         
-        let output_file = output_path.as_ref().display().to_string();
+        // 导出异常汇总（如果流程中产生了警告）
+        {
+            let warnings = self.get_warnings().await;
+            if warnings.is_empty() {
+                info!("📋 没有异常警告，跳过异常汇总导出");
+            } else {
+                let exceptions_file_path = self.generate_exceptions_file_path(&output_path);
+                info!("📋 开始导出异常汇总到: {}", exceptions_file_path.display());
+
+                excel_processor.export_exceptions_workbook(&warnings, &exceptions_file_path)?;
+
+                self.report_stage(
+                    ProcessingStage::ResultExport,
+                    &format!("异常汇总已保存到: {}", exceptions_file_path.display())
+                ).await;
+
+                info!("✅ 异常汇总导出完成: 共 {} 条", warnings.len());
+            }
+        }
+
+        self.collect_io_retry_warnings(&excel_processor).await;
+        
+        let output_file = main_file_path.display().to_string();
         self.report_stage(
             ProcessingStage::ResultExport,
-            &format!("分析结果已保存到: {}", output_file)
-        );
+            &format!("分析结果已保存到: {output_file}")
+        ).await;
         
-        info!("结果已导出到: {}", output_file);
-        Ok(())
+        info!("结果已导出到: {output_file}");
+        Ok(main_file_path)
     }
     
-    /// 获取算法信息
-    pub fn get_algorithms_info(&self) -> HashMap<&'static str, &'static str> {
-        let mut info = HashMap::new();
-        info.insert("FIFO", "先进先出算法 - 按时间顺序追踪资金流向");
-        info.insert("BALANCE_METHOD", "差额计算法 - 基于余额变化计算资金占比");
-        info
+    /// 数据集概览统计
+    /// 
+    /// 只做数据读取，不做完整性验证与算法处理，
+    /// 统计行数、时间范围、资金属性分布、总收支与疑似投资产品
+    pub async fn dataset_stats<P: AsRef<Path>>(&self, input_file: P) -> AuditResult<DatasetStats> {
+        let start_time = Instant::now();
+        
+        let excel_processor = self.excel_reader();
+        let transactions = excel_processor.read_transactions(&input_file)?;
+        
+        let mut total_income = Decimal::ZERO;
+        let mut total_expense = Decimal::ZERO;
+        let mut attribute_counts: HashMap<String, usize> = HashMap::new();
+        let mut product_counts: HashMap<String, usize> = HashMap::new();
+        let mut min_time: Option<chrono::NaiveDateTime> = None;
+        let mut max_time: Option<chrono::NaiveDateTime> = None;
+        
+        for tx in &transactions {
+            total_income += tx.income_amount;
+            total_expense += tx.expense_amount;
+            *attribute_counts.entry(tx.fund_attribute.clone()).or_insert(0) += 1;
+            if self.config.is_investment_product(&tx.fund_attribute) {
+                *product_counts.entry(tx.fund_attribute.clone()).or_insert(0) += 1;
+            }
+            if min_time.is_none_or(|t| tx.transaction_date < t) {
+                min_time = Some(tx.transaction_date);
+            }
+            if max_time.is_none_or(|t| tx.transaction_date > t) {
+                max_time = Some(tx.transaction_date);
+            }
+        }
+        
+        // 分布按出现次数降序，同次数按名称排序保证输出稳定
+        let mut fund_attribute_counts: Vec<(String, usize)> = attribute_counts.into_iter().collect();
+        fund_attribute_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let mut investment_products: Vec<(String, usize)> = product_counts.into_iter().collect();
+        investment_products.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        // 层级属性按配置分隔符上卷到各中间层级；平铺数据不重复一份分布
+        let hierarchy = self.config.attribute_hierarchy();
+        let fund_attribute_rollup = if fund_attribute_counts.iter()
+            .any(|(attribute, _)| hierarchy.is_hierarchical(attribute))
+        {
+            hierarchy.rollup_counts(&fund_attribute_counts)
+        } else {
+            Vec::new()
+        };
+
+        let format_time = |t: chrono::NaiveDateTime| t.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        Ok(DatasetStats {
+            total_rows: transactions.len(),
+            start_time: min_time.map(format_time),
+            end_time: max_time.map(format_time),
+            total_income,
+            total_expense,
+            fund_attribute_counts,
+            fund_attribute_rollup,
+            investment_products,
+            scan_time_secs: start_time.elapsed().as_secs_f64(),
+        })
+    }
+    
+    /// 快速扫描 - 分层抽样的初步估算
+    /// 
+    /// 抽样策略：每`sample_interval`行取1行，金额不低于`amount_threshold`的
+    /// 大额行全量纳入。在抽样序列上以差额计算法粗算挪用/垫付金额，
+    /// 并按支出金额覆盖率线性外推。抽样破坏了余额连续性，
+    /// 结果仅为初步估算，必须以完整分析为准
+    pub async fn quick_scan<P: AsRef<Path>>(
+        &self,
+        input_file: P,
+        sample_interval: usize,
+        amount_threshold: Decimal,
+    ) -> AuditResult<QuickScanResult> {
+        let start_time = Instant::now();
+        
+        if sample_interval == 0 {
+            return Err(AuditError::validation_error("抽样间隔必须大于0"));
+        }
+        
+        // 只做数据读取，跳过完整性验证修复以保证速度
+        let excel_processor = self.excel_reader();
+        let transactions = excel_processor.read_transactions(&input_file)?;
+        if transactions.is_empty() {
+            return Err(AuditError::validation_error("没有交易数据，无法快速扫描"));
+        }
+        let total_rows = transactions.len();
+        
+        // 分层抽样：每N行取1行 + 大额行全量
+        let sample: Vec<&Transaction> = transactions.iter().enumerate()
+            .filter(|(idx, tx)| {
+                let amount = tx.income_amount.max(tx.expense_amount);
+                *idx == 0 || idx % sample_interval == 0 || amount >= amount_threshold
+            })
+            .map(|(_, tx)| tx)
+            .collect();
+        
+        // 在抽样序列上粗算（差额计算法；单行失败直接跳过，不中断扫描）
+        let mut tracker = BalanceMethodTracker::new(self.config.clone());
+        tracker.smart_initialize(sample[0])?;
+        let mut skipped_rows = 0usize;
+        for tx in &sample {
+            if tracker.process_transaction(tx).is_err() {
+                skipped_rows += 1;
+            }
+        }
+        let summary = tracker.get_summary()?;
+        
+        // 按支出金额覆盖率线性外推
+        let total_expense: Decimal = transactions.iter().map(|t| t.expense_amount).sum();
+        let sampled_expense: Decimal = sample.iter().map(|t| t.expense_amount).sum();
+        let expense_coverage = if total_expense.is_zero() {
+            Decimal::ONE
+        } else {
+            (sampled_expense / total_expense).round_dp(4)
+        };
+        let scale = if expense_coverage.is_zero() {
+            Decimal::ONE
+        } else {
+            Decimal::ONE / expense_coverage
+        };
+        
+        let mut caveats = vec![
+            format!("初步估算：仅抽样了{}/{}行，余额连续性在抽样后不再成立", sample.len(), total_rows),
+            format!("大额交易（≥{amount_threshold:.2}）已全量纳入，小额交易按每{sample_interval}行抽1行并按支出覆盖率线性外推"),
+            "结果仅用于判断是否值得完整分析，不可作为审计结论".to_string(),
+        ];
+        if skipped_rows > 0 {
+            caveats.push(format!("{skipped_rows}行在粗算中处理失败被跳过"));
+        }
+        
+        Ok(QuickScanResult {
+            total_rows,
+            sampled_rows: sample.len(),
+            sample_interval,
+            amount_threshold,
+            expense_coverage,
+            estimated_misappropriation: (summary.total_misappropriation * scale).round_dp(2),
+            estimated_advance_payment: (summary.total_advance_payment * scale).round_dp(2),
+            scan_time_secs: start_time.elapsed().as_secs_f64(),
+            caveats,
+        })
+    }
+    
+    /// 行级算法分歧检测
+    ///
+    /// 在同一份流水上依次运行FIFO与差额计算法，返回行为性质不同
+    /// 或个人占比差异绝对值超过`ratio_tolerance`的行。
+    /// 相比只看摘要总额的对比，可直接定位到有争议的具体交易
+    pub async fn diff_algorithms<P: AsRef<Path>>(
+        &self,
+        input_file: P,
+        ratio_tolerance: Decimal,
+    ) -> AuditResult<Vec<AlgorithmDivergence>> {
+        if ratio_tolerance < Decimal::ZERO {
+            return Err(AuditError::validation_error("占比容差不能为负数"));
+        }
+        
+        let transactions = self.load_and_validate_data(&input_file).await?;
+        let (_, fifo_transactions) = self.execute_algorithm("FIFO", &transactions).await?;
+        let (_, balance_transactions) = self.execute_algorithm("BALANCE_METHOD", &transactions).await?;
+        
+        Ok(Self::collect_divergences(&fifo_transactions, &balance_transactions, ratio_tolerance))
+    }
+    
+    /// 从算法处理结果生成资金走势时间线
+    ///
+    /// 逐行累计量由算法写回交易字段，这里只做按粒度采样：
+    /// 逐笔采样每行一个点，按日采样取各日最后一笔的状态
+    #[must_use]
+    pub fn get_timeline_series(transactions: &[Transaction], sampling: TimelineSampling) -> TimelineSeries {
+        let mut series = TimelineSeries::default();
+        for (index, transaction) in transactions.iter().enumerate() {
+            let label = match sampling {
+                TimelineSampling::PerTransaction => {
+                    transaction.transaction_date.format("%Y-%m-%d %H:%M:%S").to_string()
+                }
+                TimelineSampling::PerDay => {
+                    transaction.transaction_date.format("%Y-%m-%d").to_string()
+                }
+            };
+            // 按日采样：同一天内更晚的交易覆盖当日采样点
+            if sampling == TimelineSampling::PerDay
+                && series.labels.last().is_some_and(|last| *last == label)
+            {
+                series.pop_point();
+            }
+            series.push_point(label, index + 1, transaction);
+        }
+        series
+    }
+
+    /// 从两侧处理结果中收集行级分歧
+    fn collect_divergences(
+        fifo_transactions: &[Transaction],
+        balance_transactions: &[Transaction],
+        ratio_tolerance: Decimal,
+    ) -> Vec<AlgorithmDivergence> {
+        fifo_transactions.iter()
+            .zip(balance_transactions)
+            .enumerate()
+            .filter_map(|(idx, (fifo_tx, balance_tx))| {
+                let fifo_behavior = fifo_tx.behavior_nature.as_deref().unwrap_or("");
+                let balance_behavior = balance_tx.behavior_nature.as_deref().unwrap_or("");
+                let behavior_diverged = fifo_behavior != balance_behavior;
+                
+                let fifo_ratio = fifo_tx.personal_ratio.unwrap_or(Decimal::ZERO);
+                let balance_ratio = balance_tx.personal_ratio.unwrap_or(Decimal::ZERO);
+                let personal_ratio_delta = balance_ratio - fifo_ratio;
+                
+                if !behavior_diverged && personal_ratio_delta.abs() <= ratio_tolerance {
+                    return None;
+                }
+                
+                Some(AlgorithmDivergence {
+                    row_number: idx + 1,
+                    transaction_time: fifo_tx.transaction_time.clone(),
+                    fund_attribute: fifo_tx.fund_attribute.clone(),
+                    fifo_behavior: fifo_behavior.to_string(),
+                    balance_behavior: balance_behavior.to_string(),
+                    behavior_diverged,
+                    personal_ratio_delta,
+                })
+            })
+            .collect()
+    }
+    
+    /// 获取算法信息（标识 → 描述，来自算法注册表）
+    #[must_use] 
+    pub fn get_algorithms_info(&self) -> HashMap<String, String> {
+        self.algorithms.infos()
+            .map(|info| (info.name.clone(), info.description.clone()))
+            .collect()
     }
     
     /// 分析财务数据（兼容Python接口）
@@ -437,53 +1887,192 @@ impl AuditService {
         output_file: Option<P>,
     ) -> AuditResult<(AuditSummary, Vec<Transaction>, Vec<String>)> {
         let start_time = std::time::Instant::now();
+
+        // 开启运行级日志上下文：本次分析的日志均归属该run_id，
+        // 同一服务实例连续多次分析时GUI可按运行过滤
+        let log_run_id = self.begin_log_run().await;
+        info!("本次分析日志运行: {log_run_id}");
+
+        // 清空上次运行遗留的警告、修复报告、可疑模式发现与对象池统计
+        self.warnings.lock().await.clear();
+        *self.validation_report.lock().await = None;
+        self.anomaly_findings.lock().await.clear();
+        *self.fifo_pool_stats.lock().await = None;
+
+        // 复位进度映射，并按本算法的历史耗时分布学习阶段权重
+        *self.overall_progress.lock().await = 0.0;
+        self.stage_durations.lock().await.clear();
+        *self.stage_weights.lock().await = Self::default_stage_weights();
+        if let Ok((stats, _)) = crate::services::PerformanceStatsService::open(
+            crate::services::PerformanceStatsService::DEFAULT_STATS_FILE,
+        ) {
+            if let Some(weights) = stats.stage_weights(algorithm) {
+                self.set_stage_weights(weights).await;
+            }
+        }
         
-        // 步骤1: 数据加载和验证
-        let transactions = self.load_and_validate_data(&input_file).await?;
-        let total_records = transactions.len() as u32;
+        // 复位取消令牌（上次运行的停止请求不应影响本次）
+        self.cancel_flag.store(false, Ordering::Relaxed);
         
-        // 步骤2: 执行算法分析
-        let (summary, processed_transactions) = self.execute_algorithm(algorithm, &transactions).await?;
+        // 排障模式下为本次运行创建新的剖析器
+        if self.trace_profile_enabled {
+            *self.trace_profiler.lock().await = Some(crate::services::TraceProfiler::new());
+        }
         
-        // 步骤3: 生成输出文件路径（默认使用临时目录）
-        let output_path = if let Some(output_path) = output_file {
-            output_path.as_ref().to_path_buf()
-        } else {
-            // 生成临时文件路径
-            self.generate_temp_output_path(algorithm, &input_file)?
-        };
+        let input_file_display = input_file.as_ref().display().to_string();
         
-        // 步骤4: 导出结果
-        self.export_results(&processed_transactions, &summary, &output_path)?;
+        // 记录本次输入文件（增量快照缓存键的组成部分）
+        *self.incremental_file.lock().await = Some(input_file_display.clone());
         
-        let processing_time = start_time.elapsed().as_millis() as u64;
+        // 标记本次运行使用的配置版本，便于结果回溯
+        if let Some(version) = self.config_version {
+            self.add_output_log(&format!("🏷️ 本次分析使用配置版本 v{version}")).await;
+            info!("本次分析使用配置版本 v{version}");
+        }
         
-        // 记录统计信息到service中以便GUI获取
+        let result = async {
+            // 步骤1: 数据加载和验证
+            let stage_start = Instant::now();
+            let transactions = self.load_and_validate_data(&input_file).await?;
+            self.trace_record("stage", "数据加载与验证", stage_start).await;
+            self.check_cancelled()?;
+            let _total_records = transactions.len() as u32;
+        
+            // 步骤2: 执行算法分析
+            let stage_start = Instant::now();
+            let (summary, mut processed_transactions) = self.execute_algorithm(algorithm, &transactions).await?;
+            self.trace_record("stage", "算法分析", stage_start).await;
+            self.record_stage_duration(ProcessingStage::AlgorithmProcessing.name(), stage_start).await;
+            self.check_cancelled()?;
+
+            // 步骤2.5: 可疑模式检测（逐行风险标记 + 汇总工作表）
+            let stage_start = Instant::now();
+            self.run_anomaly_detection(&mut processed_transactions).await;
+            self.trace_record("stage", "可疑模式检测", stage_start).await;
+        
+            // 步骤3: 生成输出文件路径（默认使用临时目录）
+            let output_path = if let Some(output_path) = output_file {
+                output_path.as_ref().to_path_buf()
+            } else {
+                // 生成临时文件路径
+                self.generate_temp_output_path(algorithm, &input_file)?
+            };
         
-        // 确保返回绝对路径
-        let absolute_path = if output_path.is_absolute() {
-            output_path.clone()
-        } else {
-            std::env::current_dir()
-                .unwrap_or_default()
-                .join(&output_path)
-        };
+            // 步骤4: 导出结果（超大结果集按扩展名选择分表Excel或流式CSV）
+            let stage_start = Instant::now();
+            let output_path = self.export_results(&processed_transactions, &summary, &output_path).await?;
+            self.trace_record("stage", "结果导出", stage_start).await;
+            self.record_stage_duration(ProcessingStage::ResultExport.name(), stage_start).await;
         
-        let main_file = absolute_path.display().to_string();
+            let _processing_time = start_time.elapsed().as_millis() as u64;
         
-        // 检查是否有场外资金池记录，如果有则添加到结果中
-        let mut output_files = vec![main_file];
+            // 记录统计信息到service中以便GUI获取
         
-        if let Ok(offsite_records) = self.offsite_pool_records.lock() {
-            if let Some(record_manager) = offsite_records.as_ref() {
-                if record_manager.record_count() > 0 {
-                    let pool_file_path = self.generate_offsite_pool_file_path(&absolute_path);
-                    output_files.push(pool_file_path.display().to_string());
+            // 确保返回绝对路径
+            let absolute_path = if output_path.is_absolute() {
+                output_path.clone()
+            } else {
+                std::env::current_dir()
+                    .unwrap_or_default()
+                    .join(&output_path)
+            };
+        
+            let main_file = absolute_path.display().to_string();
+        
+            // 检查是否有场外资金池记录，如果有则添加到结果中
+            let mut output_files = vec![main_file];
+        
+            {
+                let offsite_records = self.offsite_pool_records.lock().await;
+                if let Some(record_manager) = offsite_records.as_ref() {
+                    if record_manager.record_count() > 0 {
+                        let pool_file_path = self.generate_offsite_pool_file_path(&absolute_path);
+                        output_files.push(pool_file_path.display().to_string());
+                    }
                 }
             }
+        
+            Ok((summary, processed_transactions, output_files))
+        }.await;
+        
+        // 写出性能剖析文件（如启用）；剖析失败不影响分析结果
+        if let Some(profiler) = self.trace_profiler.lock().await.take() {
+            let trace_dir = result.as_ref().ok()
+                .and_then(|(_, _, output_files)| output_files.first())
+                .and_then(|f| Path::new(f).parent().map(Path::to_path_buf))
+                .unwrap_or_else(std::env::temp_dir);
+            match profiler.write_chrome_trace(&trace_dir, algorithm) {
+                Ok(path) => {
+                    let message = format!("🧪 性能剖析已写入: {}（chrome://tracing可打开）", path.display());
+                    self.add_output_log(&message).await;
+                    info!("{message}");
+                }
+                Err(e) => info!("⚠️ 性能剖析文件写入失败: {e}"),
+            }
         }
         
-        Ok((summary, processed_transactions, output_files))
+        // 运行结束通知（如已配置），成功失败均推送；通知失败不影响分析结果
+        match &result {
+            Ok((summary, _, output_files)) => {
+                self.notify_run_outcome(
+                    &input_file_display,
+                    algorithm,
+                    start_time.elapsed().as_secs_f64(),
+                    Ok((summary, output_files)),
+                ).await;
+            }
+            Err(e) => {
+                self.notify_run_outcome(
+                    &input_file_display,
+                    algorithm,
+                    start_time.elapsed().as_secs_f64(),
+                    Err(e),
+                ).await;
+            }
+        }
+
+        // 关闭运行级日志上下文（run_id仍可经last_log_run_id取回）
+        self.end_log_run().await;
+
+        result
+    }
+
+    /// 推送运行结束通知（按配置，未启用时为空操作）
+    async fn notify_run_outcome(
+        &self,
+        input_file: &str,
+        algorithm: &str,
+        duration_secs: f64,
+        outcome: Result<(&AuditSummary, &[String]), &AuditError>,
+    ) {
+        let Some(notifier) = crate::services::NotificationService::from_config(&self.config.notification) else {
+            return;
+        };
+        
+        let summary = match outcome {
+            Ok((summary, output_files)) => crate::services::RunSummary {
+                input_file: input_file.to_string(),
+                algorithm: algorithm.to_string(),
+                success: true,
+                duration_secs,
+                key_metrics: summary.metric_values().iter()
+                    .map(|(name, value)| ((*name).to_string(), format!("{value:.2}")))
+                    .collect(),
+                output_files: output_files.to_vec(),
+                message: String::new(),
+            },
+            Err(e) => crate::services::RunSummary {
+                input_file: input_file.to_string(),
+                algorithm: algorithm.to_string(),
+                success: false,
+                duration_secs,
+                key_metrics: vec![],
+                output_files: vec![],
+                message: e.to_string(),
+            },
+        };
+        
+        notifier.notify(&summary).await;
     }
     
     /// 生成临时输出文件路径
@@ -497,17 +2086,13 @@ impl AuditService {
             PathBuf::from("temp_analysis_results")
         } else {
             // 生产模式：用户文档目录下的临时文件夹
-            dirs::document_dir()
-                .map(|d| d.join("FLUX Analysis System").join("temp_analysis_results"))
-                .unwrap_or_else(|| {
-                    dirs::home_dir()
-                        .map(|h| h.join("FLUX Analysis System").join("temp_analysis_results"))
-                        .unwrap_or_else(|| PathBuf::from("temp_analysis_results"))
-                })
+            dirs::document_dir().map_or_else(|| {
+                    dirs::home_dir().map_or_else(|| PathBuf::from("temp_analysis_results"), |h| h.join("FLUX Analysis System").join("temp_analysis_results"))
+                }, |d| d.join("FLUX Analysis System").join("temp_analysis_results"))
         };
         if !temp_dir.exists() {
             fs::create_dir_all(&temp_dir)
-                .map_err(|e| AuditError::config_error(&format!("创建临时目录失败: {}", e)))?;
+                .map_err(|e| AuditError::config_error(format!("创建临时目录失败: {e}")))?;
         }
         
         // 获取输入文件名
@@ -518,11 +2103,7 @@ impl AuditService {
             
         // 生成输出文件名（带时间戳）
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
-        let algorithm_name = match algorithm {
-            "FIFO" => "FIFO",
-            "BALANCE_METHOD" => "差额计算法",
-            _ => algorithm
-        };
+        let algorithm_name = self.algorithms.display_name(algorithm);
         
         let filename = format!("{}_{}_{}_{}.xlsx", algorithm_name, input_name, timestamp, rand::random::<u32>() % 10000);
         let output_path = temp_dir.join(filename);
@@ -531,29 +2112,23 @@ impl AuditService {
     }
     
     /// 存储场外资金池记录
-    fn store_offsite_pool_records(&self, record_manager: &OffsitePoolRecordManager) {
+    async fn store_offsite_pool_records(&self, record_manager: &OffsitePoolRecordManager) {
         info!("💾 存储场外资金池记录: {} 条", record_manager.record_count());
-        if let Ok(mut records) = self.offsite_pool_records.lock() {
-            *records = Some(record_manager.clone());
-            info!("💾 场外资金池记录存储成功");
-        } else {
-            info!("❌ 无法获取场外资金池记录存储锁");
-        }
+        *self.offsite_pool_records.lock().await = Some(record_manager.clone());
+        info!("💾 场外资金池记录存储成功");
     }
     
     /// 获取场外资金池记录
-    pub fn get_offsite_pool_records(&self) -> OffsitePoolRecordManager {
-        if let Ok(records) = self.offsite_pool_records.lock() {
-            if let Some(ref record_manager) = *records {
-                return record_manager.clone();
-            }
+    pub async fn get_offsite_pool_records(&self) -> OffsitePoolRecordManager {
+        if let Some(ref record_manager) = *self.offsite_pool_records.lock().await {
+            return record_manager.clone();
         }
         // 如果没有记录，返回空的管理器
         OffsitePoolRecordManager::new()
     }
     
     /// 存储投资池数据（用于完整统计计算）
-    fn store_investment_pools_data(&self, investment_pools: &std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool>) {
+    async fn store_investment_pools_data(&self, investment_pools: &std::collections::HashMap<String, crate::algorithms::shared::tracker_base::InvestmentPool>) {
         info!("💾 存储投资池数据: {} 个池", investment_pools.len());
         if investment_pools.is_empty() {
             info!("⚠️ 投资池数据为空，可能没有投资产品交易");
@@ -564,12 +2139,8 @@ impl AuditService {
             }
         }
         
-        if let Ok(mut pools) = self.investment_pools_data.lock() {
-            *pools = Some(investment_pools.clone());
-            info!("💾 投资池数据存储成功");
-        } else {
-            info!("❌ 无法获取投资池数据存储锁");
-        }
+        *self.investment_pools_data.lock().await = Some(investment_pools.clone());
+        info!("💾 投资池数据存储成功");
     }
     
     /// 生成场外资金池记录文件路径
@@ -585,22 +2156,49 @@ impl AuditService {
         
         // 根据主文件名生成场外资金池记录文件名
         // 例如: FIFO_analysis.xlsx -> 场外资金池记录_FIFO_analysis.xlsx
-        let pool_filename = format!("场外资金池记录_{}.{}", stem, extension);
+        let pool_filename = format!("场外资金池记录_{stem}.{extension}");
         parent.join(pool_filename)
     }
+
+    /// 生成异常汇总文件路径
+    ///
+    /// 与场外资金池记录相同的派生规则，例如:
+    /// `FIFO_analysis.xlsx` -> `异常汇总_FIFO_analysis.xlsx`
+    fn generate_exceptions_file_path<P: AsRef<std::path::Path>>(&self, main_output_path: P) -> std::path::PathBuf {
+        let main_path = main_output_path.as_ref();
+        let parent = main_path.parent().unwrap_or(main_path);
+        let stem = main_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("analysis");
+        let extension = main_path.extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("xlsx");
+
+        parent.join(format!("异常汇总_{stem}.{extension}"))
+    }
     
     /// Tauri GUI接口: 运行审计分析
     pub async fn run_audit_for_gui(&self, config: TauriAuditConfig) -> TauriAuditResult {
         let start_time = Instant::now();
         
         // 重置状态
-        if let Ok(mut status) = self.current_status.lock() {
-            *status = TauriProcessStatus::running(0.0, "开始审计分析...".to_string());
-        }
+        *self.current_status.lock().await = TauriProcessStatus::running(0.0, "开始审计分析...".to_string());
         // 注意：不要清空 output_log，因为我们需要保留详细的处理日志供GUI使用
         // 只在真正需要的时候清空
         
-        let result = self.analyze_financial_data(
+        // 解析时间窗（格式已由TauriAuditConfig::validate校验，此处防御性处理）
+        let parse_date = |value: &Option<String>| {
+            value.as_deref()
+                .and_then(|raw| chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").ok())
+        };
+        // clone共享全部内部状态（Arc），仅本次运行携带时间窗
+        let runner = self.clone()
+            .with_time_range(parse_date(&config.date_from), parse_date(&config.date_to))
+            .with_sheet_name(config.sheet_name.clone())
+            .with_opening_balance(config.opening_balance_override())
+            .with_same_time_ordering(config.same_time_ordering_config());
+
+        let result = runner.analyze_financial_data(
             &config.algorithm,
             &config.input_file,
             config.output_file.as_ref()
@@ -611,10 +2209,11 @@ impl AuditService {
                 let processing_time = start_time.elapsed().as_secs_f64();
                 
                 // 更新为完成状态
-                if let Ok(mut status) = self.current_status.lock() {
-                    *status = TauriProcessStatus::idle();
-                }
+                *self.current_status.lock().await = TauriProcessStatus::idle();
                 
+                let (validation_errors, validation_fixes) = self.validation_report.lock().await
+                    .as_ref()
+                    .map_or((0, 0), |report| (report.errors_count, report.repair_count));
                 TauriAuditResult::success(
                     summary,
                     transactions.len(),
@@ -622,58 +2221,47 @@ impl AuditService {
                     config.algorithm,
                     output_files,
                 )
+                .with_warnings(self.get_warnings().await)
+                .with_validation_counts(validation_errors, validation_fixes)
             }
             Err(e) => {
                 // 更新为错误状态
-                if let Ok(mut status) = self.current_status.lock() {
-                    *status = TauriProcessStatus::idle();
-                }
+                *self.current_status.lock().await = TauriProcessStatus::idle();
                 
-                TauriAuditResult::failure(format!("审计分析失败: {}", e))
+                TauriAuditResult::failure(format!("审计分析失败: {e}"))
             }
         }
     }
     
     /// 获取当前进程状态
-    pub fn get_process_status(&self) -> TauriProcessStatus {
-        if let Ok(status) = self.current_status.lock() {
-            let mut result = status.clone();
-            // 添加日志
-            if let Ok(log) = self.output_log.lock() {
-                result.output_log = log.clone();
-            }
-            result
-        } else {
-            TauriProcessStatus::idle()
-        }
+    pub async fn get_process_status(&self) -> TauriProcessStatus {
+        let mut result = self.current_status.lock().await.clone();
+        // 添加日志
+        result.output_log = self.get_output_logs().await;
+        result
     }
     
     /// 清空输出日志
-    pub fn clear_output_log(&self) {
-        if let Ok(mut log) = self.output_log.lock() {
-            log.clear();
-        }
+    pub async fn clear_output_log(&self) {
+        self.output_log.lock().await.clear();
     }
     
     /// 获取支持的算法列表
+    #[must_use] 
     pub fn get_supported_algorithms(&self) -> Vec<&'static str> {
-        vec!["FIFO", "BALANCE_METHOD"]
+        vec!["FIFO", "BALANCE_METHOD", "PROPORTIONAL"]
     }
     
-    /// 获取当前的输出日志（用于GUI同步）
-    pub fn get_output_logs(&self) -> Vec<String> {
-        if let Ok(log) = self.output_log.lock() {
-            log.clone()
-        } else {
-            Vec::new()
-        }
+    /// 获取当前的输出日志（用于GUI同步，不区分运行）
+    pub async fn get_output_logs(&self) -> Vec<String> {
+        self.output_log.lock().await.iter()
+            .map(|entry| entry.message.clone())
+            .collect()
     }
     
     /// 清空输出日志
-    pub fn clear_output_logs(&self) {
-        if let Ok(mut log) = self.output_log.lock() {
-            log.clear();
-        }
+    pub async fn clear_output_logs(&self) {
+        self.output_log.lock().await.clear();
     }
 }
 
@@ -683,158 +2271,242 @@ impl Default for AuditService {
     }
 }
 
-/// 交易处理器trait - 统一不同算法的接口
-trait TransactionProcessor {
-    /// 智能初始化
-    fn smart_initialize(&mut self, first_transaction: &Transaction) -> AuditResult<()>;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
     
-    /// 处理单个交易
-    fn process_transaction(&mut self, transaction: &Transaction) -> AuditResult<Transaction>;
+    fn sample_transaction(income: i64, attribute: &str) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(income),
+            Decimal::ZERO,
+            Decimal::from(income),
+            attribute.to_string(),
+        )
+    }
     
-    /// 获取汇总结果
-    fn get_summary(&self) -> AuditResult<AuditSummary>;
-}
+    fn dated_transaction(day: u32, income: i64, expense: i64, balance: i64) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, day).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        Transaction::new(
+            date,
+            "100000".to_string(),
+            Decimal::from(income),
+            Decimal::from(expense),
+            Decimal::from(balance),
+            "个人应收".to_string(),
+        )
+    }
 
-/// 为FifoTracker实现TransactionProcessor
-impl TransactionProcessor for FifoTracker {
-    fn smart_initialize(&mut self, first_transaction: &Transaction) -> AuditResult<()> {
-        // 基于第一笔交易智能分配初始余额
-        let pre_balance = first_transaction.balance - first_transaction.income_amount + first_transaction.expense_amount;
-        
-        if first_transaction.fund_attribute.contains("个人") {
-            self.initialize_balance(pre_balance, "个人")?;
-        } else {
-            self.initialize_balance(rust_decimal::Decimal::ZERO, "个人")?;
-            if pre_balance > rust_decimal::Decimal::ZERO {
-                self.process_inflow(pre_balance, "公司初始余额", Some(first_transaction.transaction_date))?;
-            }
-        }
-        
-        Ok(())
+    #[test]
+    fn test_merge_input_segments_orders_files_and_verifies_chain() {
+        // 2020文件在参数中排在前面，但按首笔交易时间应排在2019文件之后
+        let file_2019 = vec![dated_transaction(1, 1000, 0, 1000), dated_transaction(2, 500, 0, 1500)];
+        let file_2020 = vec![dated_transaction(10, 300, 0, 1800)];
+        let (merged, warnings) = AuditService::merge_input_segments(vec![
+            ("2020.xlsx".to_string(), file_2020),
+            ("2019.xlsx".to_string(), file_2019),
+        ]);
+
+        // 衔接完好：1500 + 300 = 1800，无警告，按时间拼接
+        assert!(warnings.is_empty());
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].balance, Decimal::from(1000));
+        assert_eq!(merged[2].balance, Decimal::from(1800));
     }
-    
-    fn process_transaction(&mut self, transaction: &Transaction) -> AuditResult<Transaction> {
-        let mut processed_tx = transaction.clone();
-        
-        // 根据交易类型调用相应的处理方法
-        let result = if transaction.income_amount > rust_decimal::Decimal::ZERO {
-            if transaction.fund_attribute.contains('-') {
-                self.process_investment_redemption(
-                    transaction.income_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            } else {
-                self.process_inflow(
-                    transaction.income_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            }
-        } else if transaction.expense_amount > rust_decimal::Decimal::ZERO {
-            if transaction.fund_attribute.contains('-') {
-                self.process_investment_purchase(
-                    transaction.expense_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            } else {
-                self.process_outflow(
-                    transaction.expense_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            }
-        } else {
-            Ok((rust_decimal::Decimal::ZERO, rust_decimal::Decimal::ZERO, "无变化".to_string()))
-        };
-        
-        // 更新交易字段
-        match result {
-            Ok((personal_ratio, company_ratio, behavior)) => {
-                self.update_transaction_fields(&mut processed_tx, personal_ratio, company_ratio, &behavior)?;
-            }
-            Err(_) => {
-                // 处理失败时保持原始数据
-            }
-        }
-        
-        Ok(processed_tx)
+
+    #[test]
+    fn test_merge_input_segments_reports_discontinuity_and_overlap() {
+        // 2020文件期初余额2000 ≠ 2019文件末笔余额1500 → 衔接断点
+        let file_2019 = vec![dated_transaction(1, 1000, 0, 1000), dated_transaction(5, 500, 0, 1500)];
+        let file_2020 = vec![dated_transaction(3, 300, 0, 2300)];
+        let (merged, warnings) = AuditService::merge_input_segments(vec![
+            ("2019.xlsx".to_string(), file_2019),
+            ("2020.xlsx".to_string(), file_2020),
+        ]);
+
+        assert_eq!(merged.len(), 3);
+        let codes: Vec<&str> = warnings.iter().map(|w| w.code.as_str()).collect();
+        assert!(codes.contains(&"FILE_CHAIN_DISCONTINUITY"));
+        // 2020文件首笔（1月3日）早于2019文件末笔（1月5日）→ 时间区间重叠
+        assert!(codes.contains(&"FILE_TIME_OVERLAP"));
+        assert!(warnings[0].message.contains("2019.xlsx"));
+        assert!(warnings[0].message.contains("2020.xlsx"));
     }
-    
-    fn get_summary(&self) -> AuditResult<AuditSummary> {
-        self.get_summary()
+
+    #[test]
+    fn test_overall_from_weights_maps_stage_progress() {
+        let weights = AuditService::default_stage_weights();
+
+        // 算法处理过半：前置阶段0.05+0.10全部计入，算法阶段0.65计一半
+        let overall = AuditService::overall_from_weights(&weights, "算法处理", 50.0).unwrap();
+        assert!((overall - 47.5).abs() < f64::EPSILON);
+
+        // 结果导出完成即整体100%
+        let finished = AuditService::overall_from_weights(&weights, "结果导出", 100.0).unwrap();
+        assert!((finished - 100.0).abs() < f64::EPSILON);
+
+        // 权重表之外的阶段不参与折算
+        assert!(AuditService::overall_from_weights(&weights, "初始余额计算", 50.0).is_none());
     }
-}
 
-/// 为BalanceMethodTracker实现TransactionProcessor
-impl TransactionProcessor for BalanceMethodTracker {
-    fn smart_initialize(&mut self, first_transaction: &Transaction) -> AuditResult<()> {
-        // 基于第一笔交易智能分配初始余额
-        let pre_balance = first_transaction.balance - first_transaction.income_amount + first_transaction.expense_amount;
-        
-        if first_transaction.fund_attribute.contains("个人") {
-            self.initialize_balance(pre_balance, "个人")?;
-        } else {
-            self.initialize_balance(rust_decimal::Decimal::ZERO, "个人")?;
-            if pre_balance > rust_decimal::Decimal::ZERO {
-                self.process_inflow(pre_balance, "公司初始余额", Some(first_transaction.transaction_date))?;
-            }
-        }
-        
-        Ok(())
+    #[test]
+    fn test_prefix_digest_stable_for_same_prefix() {
+        let old_rows = vec![sample_transaction(100, "个人应收"), sample_transaction(200, "公司应收")];
+        let mut new_rows = old_rows.clone();
+        new_rows.push(sample_transaction(300, "个人应收"));
+        
+        // 追加新行不改变旧前缀的摘要
+        assert_eq!(
+            AuditService::transactions_prefix_digest(&old_rows, 2),
+            AuditService::transactions_prefix_digest(&new_rows, 2)
+        );
     }
     
-    fn process_transaction(&mut self, transaction: &Transaction) -> AuditResult<Transaction> {
-        let mut processed_tx = transaction.clone();
-        
-        // 根据交易类型调用相应的处理方法
-        let result = if transaction.income_amount > rust_decimal::Decimal::ZERO {
-            if transaction.fund_attribute.contains('-') {
-                self.process_investment_redemption(
-                    transaction.income_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            } else {
-                self.process_inflow(
-                    transaction.income_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            }
-        } else if transaction.expense_amount > rust_decimal::Decimal::ZERO {
-            if transaction.fund_attribute.contains('-') {
-                self.process_investment_purchase(
-                    transaction.expense_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            } else {
-                self.process_outflow(
-                    transaction.expense_amount,
-                    &transaction.fund_attribute,
-                    Some(transaction.transaction_date),
-                )
-            }
-        } else {
-            Ok((rust_decimal::Decimal::ZERO, rust_decimal::Decimal::ZERO, "无变化".to_string()))
-        };
-        
-        // 更新交易字段
-        match result {
-            Ok((personal_ratio, company_ratio, behavior)) => {
-                self.update_transaction_fields(&mut processed_tx, personal_ratio, company_ratio, &behavior)?;
-            }
-            Err(_) => {
-                // 处理失败时保持原始数据
-            }
+    #[test]
+    fn test_prefix_digest_detects_modified_prefix() {
+        let old_rows = vec![sample_transaction(100, "个人应收"), sample_transaction(200, "公司应收")];
+        let mut modified = old_rows.clone();
+        modified[1].fund_attribute = "个人应收".to_string();
+        
+        assert_ne!(
+            AuditService::transactions_prefix_digest(&old_rows, 2),
+            AuditService::transactions_prefix_digest(&modified, 2)
+        );
+    }
+    
+    /// 构造带算法结果字段的交易行（分歧检测用）
+    fn processed_transaction(behavior: &str, personal_ratio: Decimal) -> Transaction {
+        let mut tx = sample_transaction(100, "个人应收");
+        tx.behavior_nature = Some(behavior.to_string());
+        tx.personal_ratio = Some(personal_ratio);
+        tx
+    }
+    
+    #[test]
+    fn test_timeline_series_samples_per_transaction_and_per_day() {
+        // 1月1日两笔、1月2日一笔，累计字段由算法写回
+        let mut transactions = Vec::new();
+        for (day, hour, misappropriation) in [(1u32, 10u32, 100i64), (1, 14, 300), (2, 9, 300)] {
+            let date = NaiveDate::from_ymd_opt(2021, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+            let mut tx = Transaction::new(
+                date,
+                "100000".to_string(),
+                Decimal::from(1000),
+                Decimal::ZERO,
+                Decimal::from(1000),
+                "个人应收".to_string(),
+            );
+            tx.personal_balance = Some(Decimal::from(misappropriation * 2));
+            tx.cumulative_misappropriation = Some(Decimal::from(misappropriation));
+            transactions.push(tx);
         }
-        
-        Ok(processed_tx)
+
+        // 逐笔采样：每行一个点，各序列等长
+        let per_tx = AuditService::get_timeline_series(&transactions, TimelineSampling::PerTransaction);
+        assert_eq!(per_tx.labels.len(), 3);
+        assert_eq!(per_tx.labels[0], "2021-01-01 10:00:00");
+        assert_eq!(per_tx.rows, vec![1, 2, 3]);
+        assert_eq!(per_tx.cumulative_misappropriation.len(), 3);
+        assert_eq!(per_tx.cumulative_misappropriation[1], Decimal::from(300));
+
+        // 按日采样：同一天取最后一笔的状态
+        let per_day = AuditService::get_timeline_series(&transactions, TimelineSampling::PerDay);
+        assert_eq!(per_day.labels, vec!["2021-01-01", "2021-01-02"]);
+        assert_eq!(per_day.rows, vec![2, 3]);
+        assert_eq!(per_day.personal_balance[0], Decimal::from(600));
+    }
+
+    #[test]
+    fn test_collect_divergences_flags_behavior_and_ratio() {
+        let fifo = vec![
+            processed_transaction("个人支付", Decimal::ONE),
+            processed_transaction("挪用", Decimal::new(5, 1)),
+            processed_transaction("个人支付", Decimal::ONE),
+        ];
+        let balance = vec![
+            processed_transaction("个人支付", Decimal::ONE),          // 完全一致
+            processed_transaction("个人支付", Decimal::new(5, 1)),    // 行为分歧
+            processed_transaction("个人支付", Decimal::new(8, 1)),    // 占比差异0.2
+        ];
+        
+        let divergences = AuditService::collect_divergences(&fifo, &balance, Decimal::new(1, 1));
+        
+        assert_eq!(divergences.len(), 2);
+        assert_eq!(divergences[0].row_number, 2);
+        assert!(divergences[0].behavior_diverged);
+        assert_eq!(divergences[1].row_number, 3);
+        assert!(!divergences[1].behavior_diverged);
+        assert_eq!(divergences[1].personal_ratio_delta, Decimal::new(-2, 1));
     }
     
-    fn get_summary(&self) -> AuditResult<AuditSummary> {
-        self.get_summary()
+    #[test]
+    fn test_collect_divergences_respects_tolerance() {
+        let fifo = vec![processed_transaction("个人支付", Decimal::ONE)];
+        let balance = vec![processed_transaction("个人支付", Decimal::new(95, 2))];
+        
+        // 差异0.05在容差0.1内，不记录
+        let within = AuditService::collect_divergences(&fifo, &balance, Decimal::new(1, 1));
+        assert!(within.is_empty());
+        
+        // 容差收紧到0.01后记录
+        let beyond = AuditService::collect_divergences(&fifo, &balance, Decimal::new(1, 2));
+        assert_eq!(beyond.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_convert_to_base_currency_applies_static_rates() {
+        let mut config = crate::data_models::Config::new();
+        config.currency.exchange_rates.insert("USD".to_string(), Decimal::from(7));
+        let service = AuditService::with_config(config);
+
+        let mut usd = dated_transaction(1, 100, 0, 100);
+        usd.currency = Some("USD".to_string());
+        let cny = dated_transaction(2, 500, 0, 1200);
+
+        let converted = service.convert_to_base_currency(vec![usd, cny]).await.unwrap();
+        // 外币行的收入与余额按汇率折算，币种代码保留
+        assert_eq!(converted[0].income_amount, Decimal::from(700));
+        assert_eq!(converted[0].balance, Decimal::from(700));
+        assert_eq!(converted[0].currency.as_deref(), Some("USD"));
+        // 无币种信息的行原样保留
+        assert_eq!(converted[1].income_amount, Decimal::from(500));
+    }
+
+    #[tokio::test]
+    async fn test_convert_to_base_currency_rejects_missing_rate() {
+        let service = AuditService::new();
+        let mut eur = dated_transaction(1, 100, 0, 100);
+        eur.currency = Some("EUR".to_string());
+
+        // 未配置汇率的外币不得静默并入基准币种核算
+        let error = service.convert_to_base_currency(vec![eur]).await.unwrap_err();
+        assert!(error.to_string().contains("EUR"));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_run_scoped_log_buffers() {
+        let service = AuditService::new().with_suppress_output(true);
+        service.add_output_log("运行前日志").await;
+
+        let first = service.begin_log_run().await;
+        service.add_output_log("第一次分析").await;
+        service.end_log_run().await;
+
+        let second = service.begin_log_run().await;
+        service.add_output_log("第二次分析").await;
+        service.end_log_run().await;
+
+        // 按运行过滤后互不混入
+        assert_ne!(first, second);
+        assert_eq!(service.get_run_logs(&first).await, vec!["第一次分析".to_string()]);
+        assert_eq!(service.get_run_logs(&second).await, vec!["第二次分析".to_string()]);
+        assert_eq!(service.last_log_run_id().await.as_deref(), Some(second.as_str()));
+
+        // 全量日志仍包含全部条目，运行外的日志归属为None
+        assert_eq!(service.get_output_logs().await.len(), 3);
+        assert!(service.get_output_log_entries().await[0].run_id.is_none());
+    }
+}
@@ -0,0 +1,390 @@
+//! 审计轨迹服务（取证用防篡改运行记录）
+//!
+//! 每次分析运行追加一条轨迹记录：操作人、时间、引擎版本、运行参数、
+//! 输入文件SHA-256、摘要指标与结果文件SHA-256。记录以JSON Lines
+//! 追加写入轨迹文件，且每条记录携带前一条记录的哈希形成链式结构——
+//! 事后改动、删除或插入任何一条历史记录都会使后续链条校验失败。
+//! `flux-cli verify`命令重算全链哈希并对结果文件重新取指纹，
+//! 供质证时确认结果工作簿自分析完成后未被改动。
+
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::data_models::AuditSummary;
+use crate::errors::{AuditError, AuditResult};
+
+/// 轨迹文件默认文件名（与程序工作目录下的其他落盘文件并列）
+pub const DEFAULT_TRAIL_FILE: &str = "flux_audit_trail.jsonl";
+
+/// 创世记录的前链哈希（链条起点标记）
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 结果文件指纹
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutputFileDigest {
+    /// 结果文件路径（记录时的绝对或相对路径）
+    pub path: String,
+    /// 文件内容的SHA-256（十六进制小写）
+    pub sha256: String,
+}
+
+/// 单条审计轨迹记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditTrailEntry {
+    /// 记录序号（从1开始，严格递增）
+    pub sequence: u64,
+    /// 记录时间（UTC，RFC3339）
+    pub timestamp: String,
+    /// 操作人（运行分析的系统用户名）
+    pub operator: String,
+    /// 引擎版本（`CARGO_PKG_VERSION`）
+    pub engine_version: String,
+    /// 算法标识
+    pub algorithm: String,
+    /// 运行参数（工作表、时间窗、期初核定值等，JSON对象）
+    pub parameters: serde_json::Value,
+    /// 输入文件路径
+    pub input_file: String,
+    /// 输入文件内容的SHA-256
+    pub input_sha256: String,
+    /// 摘要关键指标（指标名 -> 数值文本）
+    pub summary_figures: Vec<(String, String)>,
+    /// 结果文件指纹列表
+    pub output_files: Vec<OutputFileDigest>,
+    /// 前一条记录的哈希（首条为全零）
+    pub prev_entry_hash: String,
+    /// 本条记录的哈希（对除本字段外的全部字段计算SHA-256）
+    pub entry_hash: String,
+}
+
+impl AuditTrailEntry {
+    /// 计算本条记录的哈希（`entry_hash`字段置空后序列化再取SHA-256）
+    fn compute_hash(&self) -> AuditResult<String> {
+        let mut hashable = self.clone();
+        hashable.entry_hash = String::new();
+        let canonical = serde_json::to_string(&hashable)
+            .map_err(|e| AuditError::internal_error(format!("轨迹记录序列化失败: {e}")))?;
+        Ok(sha256_hex(canonical.as_bytes()))
+    }
+}
+
+/// 校验发现的问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrailIssue {
+    /// 问题所在记录的序号
+    pub sequence: u64,
+    /// 机器可读代码（`ENTRY_HASH_MISMATCH`/`CHAIN_BROKEN`/
+    /// `SEQUENCE_GAP`/`OUTPUT_MISSING`/`OUTPUT_HASH_MISMATCH`）
+    pub code: String,
+    /// 问题描述
+    pub message: String,
+}
+
+/// 轨迹校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrailVerification {
+    /// 记录总数
+    pub total_entries: usize,
+    /// 链条与记录哈希是否全部完好
+    pub chain_intact: bool,
+    /// 结果文件是否全部在盘且指纹一致
+    pub outputs_intact: bool,
+    /// 全部发现的问题
+    pub issues: Vec<TrailIssue>,
+}
+
+impl TrailVerification {
+    /// 轨迹整体是否完好
+    #[must_use]
+    pub fn is_intact(&self) -> bool {
+        self.chain_intact && self.outputs_intact
+    }
+}
+
+/// 审计轨迹服务
+///
+/// 打开（或创建）轨迹文件后可追加运行记录与整体校验。
+/// 文件只追加不改写，历史记录由哈希链保护
+pub struct AuditTrailService {
+    path: PathBuf,
+    entries: Vec<AuditTrailEntry>,
+}
+
+impl AuditTrailService {
+    /// 打开轨迹文件（不存在时从空链开始）
+    pub fn open<P: AsRef<Path>>(path: P) -> AuditResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries = Vec::new();
+        if path.exists() {
+            let file = std::fs::File::open(&path)?;
+            for (index, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry: AuditTrailEntry = serde_json::from_str(&line)
+                    .map_err(|e| AuditError::validation_error(format!(
+                        "轨迹文件第{}行无法解析: {e}", index + 1
+                    )))?;
+                entries.push(entry);
+            }
+        }
+        Ok(Self { path, entries })
+    }
+
+    /// 记录条数
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 轨迹是否为空
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 全部记录（按序号顺序）
+    #[must_use]
+    pub fn entries(&self) -> &[AuditTrailEntry] {
+        &self.entries
+    }
+
+    /// 追加一次分析运行的轨迹记录
+    ///
+    /// 输入与结果文件在此刻取SHA-256指纹；记录链上前一条的哈希后
+    /// 落盘（追加写入），返回新记录
+    pub fn record_run(
+        &mut self,
+        algorithm: &str,
+        parameters: serde_json::Value,
+        input_file: &str,
+        summary: &AuditSummary,
+        output_files: &[String],
+    ) -> AuditResult<&AuditTrailEntry> {
+        let input_sha256 = hash_file(input_file)?;
+        let output_digests = output_files.iter()
+            .map(|path| Ok(OutputFileDigest {
+                path: path.clone(),
+                sha256: hash_file(path)?,
+            }))
+            .collect::<AuditResult<Vec<_>>>()?;
+
+        let prev_entry_hash = self.entries.last()
+            .map_or_else(|| GENESIS_HASH.to_string(), |entry| entry.entry_hash.clone());
+        let mut entry = AuditTrailEntry {
+            sequence: self.entries.len() as u64 + 1,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            operator: current_operator(),
+            engine_version: crate::VERSION.to_string(),
+            algorithm: algorithm.to_string(),
+            parameters,
+            input_file: input_file.to_string(),
+            input_sha256,
+            summary_figures: summary.metric_values().iter()
+                .map(|(name, value)| ((*name).to_string(), format!("{value:.2}")))
+                .collect(),
+            output_files: output_digests,
+            prev_entry_hash,
+            entry_hash: String::new(),
+        };
+        entry.entry_hash = entry.compute_hash()?;
+
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| AuditError::internal_error(format!("轨迹记录序列化失败: {e}")))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{line}")?;
+
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("刚追加的记录必然存在"))
+    }
+
+    /// 校验整条轨迹
+    ///
+    /// 逐条重算记录哈希并核对链条衔接与序号连续性；再对每条记录的
+    /// 结果文件重新取指纹，与记录时的SHA-256比对
+    pub fn verify(&self) -> AuditResult<TrailVerification> {
+        let mut issues = Vec::new();
+        let mut chain_intact = true;
+        let mut outputs_intact = true;
+        let mut expected_prev = GENESIS_HASH.to_string();
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let expected_sequence = index as u64 + 1;
+            if entry.sequence != expected_sequence {
+                chain_intact = false;
+                issues.push(TrailIssue {
+                    sequence: entry.sequence,
+                    code: "SEQUENCE_GAP".to_string(),
+                    message: format!("记录序号不连续: 期望{}，实际{}", expected_sequence, entry.sequence),
+                });
+            }
+            if entry.prev_entry_hash != expected_prev {
+                chain_intact = false;
+                issues.push(TrailIssue {
+                    sequence: entry.sequence,
+                    code: "CHAIN_BROKEN".to_string(),
+                    message: "链条断裂: 前链哈希与上一条记录不符（记录被改动、删除或插入）".to_string(),
+                });
+            }
+            match entry.compute_hash() {
+                Ok(recomputed) if recomputed == entry.entry_hash => {}
+                Ok(_) => {
+                    chain_intact = false;
+                    issues.push(TrailIssue {
+                        sequence: entry.sequence,
+                        code: "ENTRY_HASH_MISMATCH".to_string(),
+                        message: "记录哈希不符（记录内容被改动）".to_string(),
+                    });
+                }
+                Err(e) => {
+                    chain_intact = false;
+                    issues.push(TrailIssue {
+                        sequence: entry.sequence,
+                        code: "ENTRY_HASH_MISMATCH".to_string(),
+                        message: format!("记录哈希重算失败: {e}"),
+                    });
+                }
+            }
+            expected_prev.clone_from(&entry.entry_hash);
+
+            // 结果文件重新取指纹
+            for digest in &entry.output_files {
+                if !Path::new(&digest.path).exists() {
+                    outputs_intact = false;
+                    issues.push(TrailIssue {
+                        sequence: entry.sequence,
+                        code: "OUTPUT_MISSING".to_string(),
+                        message: format!("结果文件不存在: {}", digest.path),
+                    });
+                    continue;
+                }
+                match hash_file(&digest.path) {
+                    Ok(current) if current == digest.sha256 => {}
+                    Ok(_) => {
+                        outputs_intact = false;
+                        issues.push(TrailIssue {
+                            sequence: entry.sequence,
+                            code: "OUTPUT_HASH_MISMATCH".to_string(),
+                            message: format!("结果文件内容已变化: {}", digest.path),
+                        });
+                    }
+                    Err(e) => {
+                        outputs_intact = false;
+                        issues.push(TrailIssue {
+                            sequence: entry.sequence,
+                            code: "OUTPUT_HASH_MISMATCH".to_string(),
+                            message: format!("结果文件读取失败: {} - {e}", digest.path),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(TrailVerification {
+            total_entries: self.entries.len(),
+            chain_intact,
+            outputs_intact,
+            issues,
+        })
+    }
+}
+
+/// 计算文件内容的SHA-256（流式读取，大结果文件不整体载入内存）
+pub fn hash_file<P: AsRef<Path>>(path: P) -> AuditResult<String> {
+    let mut file = std::fs::File::open(path.as_ref())?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 计算字节串的SHA-256十六进制
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// 当前操作人（系统用户名，取不到时为unknown）
+fn current_operator() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_summary() -> AuditSummary {
+        AuditSummary::default()
+    }
+
+    fn write_file(dir: &std::path::Path, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_record_and_verify_intact_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let trail_path = dir.path().join("trail.jsonl");
+        let input = write_file(dir.path(), "input.xlsx", "输入数据");
+        let output = write_file(dir.path(), "result.xlsx", "结果数据");
+
+        let mut trail = AuditTrailService::open(&trail_path).unwrap();
+        trail.record_run("FIFO", serde_json::json!({"sheet": null}), &input,
+            &sample_summary(), std::slice::from_ref(&output)).unwrap();
+        trail.record_run("BALANCE_METHOD", serde_json::json!({}), &input,
+            &sample_summary(), &[output]).unwrap();
+
+        // 重新打开后链条完好，记录链式衔接
+        let reopened = AuditTrailService::open(&trail_path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.entries()[1].prev_entry_hash, reopened.entries()[0].entry_hash);
+
+        let verification = reopened.verify().unwrap();
+        assert!(verification.is_intact(), "问题: {:?}", verification.issues);
+    }
+
+    #[test]
+    fn test_verify_detects_tampering_and_output_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let trail_path = dir.path().join("trail.jsonl");
+        let input = write_file(dir.path(), "input.xlsx", "输入数据");
+        let output = write_file(dir.path(), "result.xlsx", "结果数据");
+
+        let mut trail = AuditTrailService::open(&trail_path).unwrap();
+        trail.record_run("FIFO", serde_json::json!({}), &input,
+            &sample_summary(), std::slice::from_ref(&output)).unwrap();
+
+        // 篡改轨迹记录内容（改算法名）
+        let tampered = std::fs::read_to_string(&trail_path).unwrap()
+            .replace("\"algorithm\":\"FIFO\"", "\"algorithm\":\"BALANCE_METHOD\"");
+        std::fs::write(&trail_path, tampered).unwrap();
+        // 改动结果文件
+        std::fs::write(&output, "改动后的结果").unwrap();
+
+        let verification = AuditTrailService::open(&trail_path).unwrap().verify().unwrap();
+        assert!(!verification.chain_intact);
+        assert!(!verification.outputs_intact);
+        let codes: Vec<&str> = verification.issues.iter().map(|i| i.code.as_str()).collect();
+        assert!(codes.contains(&"ENTRY_HASH_MISMATCH"));
+        assert!(codes.contains(&"OUTPUT_HASH_MISMATCH"));
+    }
+}
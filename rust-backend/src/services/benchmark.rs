@@ -0,0 +1,267 @@
+//! 合成数据基准测试服务
+//!
+//! 为`bench`子命令与criterion基准（`benches/audit_benchmark.rs`）提供
+//! 确定性的合成交易生成与分阶段计时：FIFO与差额计算法追踪器吞吐、
+//! 流水完整性验证、Excel导出与读取。用于验证optimizations模块的
+//! 优化效果并追踪版本间的吞吐回归。
+
+use std::time::Instant;
+
+use chrono::Duration;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data_models::{AuditSummary, Config, Transaction};
+use crate::errors::{AuditError, AuditResult};
+use crate::interfaces::Tracker;
+use crate::utils::{ExcelProcessor, UnifiedValidator};
+use flux_engine::algorithms::{BalanceMethodTracker, FifoTracker};
+
+/// 合成交易集的规模与形态参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticSpec {
+    /// 交易笔数
+    pub transaction_count: usize,
+    /// 个人属性交易占比（百分比，其余为公司属性）
+    pub personal_percent: u32,
+    /// 理财申购/赎回交易占比（百分比）
+    pub investment_percent: u32,
+    /// 随机种子（相同种子生成相同数据，保证结果可复现）
+    pub seed: u64,
+}
+
+impl Default for SyntheticSpec {
+    fn default() -> Self {
+        Self {
+            transaction_count: 10_000,
+            personal_percent: 40,
+            investment_percent: 10,
+            seed: 42,
+        }
+    }
+}
+
+/// 生成余额链自洽的确定性合成交易集
+///
+/// 交易按90秒间隔递增时间戳；支出金额不超过当前余额，赎回金额
+/// 不超过已申购未赎回的理财本金，保证任意追踪器都能完整跑完
+#[must_use]
+pub fn generate_synthetic_transactions(spec: &SyntheticSpec) -> Vec<Transaction> {
+    let mut rng = StdRng::seed_from_u64(spec.seed);
+    let mut balance = Decimal::from(1_000_000);
+    let mut invested = Decimal::ZERO;
+    let start = chrono::NaiveDate::from_ymd_opt(2021, 1, 1)
+        .unwrap()
+        .and_hms_opt(9, 0, 0)
+        .unwrap();
+
+    let mut transactions = Vec::with_capacity(spec.transaction_count);
+    for i in 0..spec.transaction_count {
+        let timestamp = start + Duration::seconds(i64::try_from(i).unwrap_or(i64::MAX) * 90);
+        let amount = Decimal::from(rng.gen_range(100..=10_000u32));
+        let personal = rng.gen_range(0..100) < spec.personal_percent;
+
+        let (income, expense, attribute) = if rng.gen_range(0..100) < spec.investment_percent {
+            // 理财申购/赎回：有未赎回本金时一半概率赎回
+            if invested > Decimal::ZERO && rng.gen_bool(0.5) {
+                let amount = amount.min(invested);
+                invested -= amount;
+                (amount, Decimal::ZERO, "理财-A001".to_string())
+            } else if balance >= amount {
+                invested += amount;
+                (Decimal::ZERO, amount, "理财-A001".to_string())
+            } else {
+                (amount, Decimal::ZERO, income_attribute(personal))
+            }
+        } else if balance >= amount && rng.gen_bool(0.5) {
+            (Decimal::ZERO, amount, expense_attribute(personal))
+        } else {
+            (amount, Decimal::ZERO, income_attribute(personal))
+        };
+
+        balance = balance + income - expense;
+        transactions.push(Transaction::new(
+            timestamp,
+            timestamp.format("%H%M%S").to_string(),
+            income,
+            expense,
+            balance,
+            attribute,
+        ));
+    }
+    transactions
+}
+
+fn income_attribute(personal: bool) -> String {
+    if personal { "个人应收" } else { "公司应收" }.to_string()
+}
+
+fn expense_attribute(personal: bool) -> String {
+    if personal { "个人应付" } else { "公司应付" }.to_string()
+}
+
+/// 用FIFO追踪器完整处理一遍交易集，返回汇总
+pub fn run_fifo(config: &Config, transactions: &[Transaction]) -> AuditResult<AuditSummary> {
+    let mut tracker = FifoTracker::new(config.clone());
+    run_tracker(&mut tracker, transactions)
+}
+
+/// 用差额计算法追踪器完整处理一遍交易集，返回汇总
+pub fn run_balance_method(config: &Config, transactions: &[Transaction]) -> AuditResult<AuditSummary> {
+    let mut tracker = BalanceMethodTracker::new(config.clone());
+    run_tracker(&mut tracker, transactions)
+}
+
+fn run_tracker<T: Tracker>(
+    tracker: &mut T,
+    transactions: &[Transaction],
+) -> AuditResult<AuditSummary> {
+    let Some(first) = transactions.first() else {
+        return Err(AuditError::validation_error("基准交易集为空"));
+    };
+    tracker.smart_initialize(first)?;
+    for tx in transactions {
+        tracker.process_transaction(tx)?;
+    }
+    tracker.get_summary()
+}
+
+/// 单阶段基准结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchStage {
+    /// 阶段名（如"FIFO"、"Excel读取"）
+    pub stage: String,
+    /// 处理条数
+    pub items: usize,
+    /// 耗时（毫秒）
+    pub elapsed_ms: f64,
+    /// 吞吐（条/秒）
+    pub throughput_per_sec: f64,
+}
+
+impl BenchStage {
+    /// 计时执行一个阶段并换算吞吐
+    pub fn measure<T>(
+        stage: &str,
+        items: usize,
+        op: impl FnOnce() -> AuditResult<T>,
+    ) -> AuditResult<(Self, T)> {
+        let start = Instant::now();
+        let value = op()?;
+        let elapsed = start.elapsed().as_secs_f64();
+        Ok((
+            Self {
+                stage: stage.to_string(),
+                items,
+                elapsed_ms: elapsed * 1000.0,
+                throughput_per_sec: if elapsed > 0.0 { items as f64 / elapsed } else { 0.0 },
+            },
+            value,
+        ))
+    }
+}
+
+/// 机器可读的基准报告（`bench`子命令以JSON写出）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// 生成时间（"%Y-%m-%d %H:%M:%S"）
+    pub generated_at: String,
+    /// 合成数据参数
+    pub spec: SyntheticSpec,
+    /// 各阶段结果
+    pub stages: Vec<BenchStage>,
+}
+
+/// 运行完整的分阶段基准：两种追踪器、验证、Excel导出与读取
+///
+/// Excel阶段写入系统临时目录，结束后删除临时文件
+pub fn run_full_benchmark(spec: &SyntheticSpec) -> AuditResult<BenchReport> {
+    let transactions = generate_synthetic_transactions(spec);
+    let config = Config::new();
+    let count = transactions.len();
+    let mut stages = Vec::new();
+
+    let (stage, summary) = BenchStage::measure("FIFO", count, || run_fifo(&config, &transactions))?;
+    stages.push(stage);
+    let (stage, _) = BenchStage::measure("BALANCE_METHOD", count, || {
+        run_balance_method(&config, &transactions)
+    })?;
+    stages.push(stage);
+    let (stage, _) = BenchStage::measure("流水完整性验证", count, || {
+        let mut validator = UnifiedValidator::new();
+        validator.validate_flow_integrity(&transactions)
+    })?;
+    stages.push(stage);
+
+    let processor = ExcelProcessor::new(config);
+    let temp_stem = format!(
+        "flux_bench_{}_{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    );
+    let export_path = std::env::temp_dir().join(format!("{temp_stem}_result.xlsx"));
+    let (stage, written_path) = BenchStage::measure("Excel导出", count, || {
+        processor.export_analysis_results(&transactions, &summary, &export_path)
+    })?;
+    stages.push(stage);
+
+    // 读取基准针对输入格式的流水工作簿（结果工作簿的列布局不同，读不回来）
+    let input_path = std::env::temp_dir().join(format!("{temp_stem}_input.xlsx"));
+    processor.write_input_workbook(&transactions, &input_path)?;
+    let (stage, _) = BenchStage::measure("Excel读取", count, || {
+        processor.read_transactions(&input_path)
+    })?;
+    stages.push(stage);
+    let _ = std::fs::remove_file(&written_path);
+    let _ = std::fs::remove_file(&input_path);
+
+    Ok(BenchReport {
+        generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        spec: spec.clone(),
+        stages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_transactions_are_deterministic_and_chain_valid() {
+        let spec = SyntheticSpec {
+            transaction_count: 200,
+            ..SyntheticSpec::default()
+        };
+        let first = generate_synthetic_transactions(&spec);
+        let second = generate_synthetic_transactions(&spec);
+        assert_eq!(first.len(), 200);
+        assert_eq!(
+            first.iter().map(|tx| tx.balance).collect::<Vec<_>>(),
+            second.iter().map(|tx| tx.balance).collect::<Vec<_>>()
+        );
+
+        // 余额链自洽：每行余额等于上行余额加净额
+        for pair in first.windows(2) {
+            assert_eq!(pair[1].balance, pair[0].balance + pair[1].net_amount());
+        }
+    }
+
+    #[test]
+    fn test_full_benchmark_covers_all_stages() {
+        let spec = SyntheticSpec {
+            transaction_count: 100,
+            ..SyntheticSpec::default()
+        };
+        let report = run_full_benchmark(&spec).unwrap();
+        let stages: Vec<&str> = report.stages.iter().map(|s| s.stage.as_str()).collect();
+        assert_eq!(
+            stages,
+            vec!["FIFO", "BALANCE_METHOD", "流水完整性验证", "Excel导出", "Excel读取"]
+        );
+        assert!(report.stages.iter().all(|s| s.items == 100));
+        // 报告必须可序列化为机器可读的JSON
+        assert!(serde_json::to_string(&report).unwrap().contains("throughput_per_sec"));
+    }
+}
@@ -0,0 +1,178 @@
+//! 会话级配置管理服务
+//!
+//! 属性字典或识别规则的修改会显著影响分析结果。本服务维护一份
+//! 会话内的配置变更历史，提供撤销/重做能力，并为每个配置状态
+//! 分配单调递增的版本号，供分析运行打上配置版本标签，
+//! 便于用户回滚错误的规则修改。
+
+use crate::data_models::Config;
+use crate::errors::{AuditError, AuditResult};
+use log::info;
+
+/// 配置版本号类型
+pub type ConfigVersion = u64;
+
+/// 会话级配置服务
+///
+/// 持有当前会话中配置的全部历史版本，支持撤销/重做。
+/// 历史仅存在于会话内存中，不做持久化。
+#[derive(Debug, Clone)]
+pub struct ConfigService {
+    /// 历史版本列表（版本号, 配置快照）
+    versions: Vec<(ConfigVersion, Config)>,
+    /// 当前版本在历史中的位置
+    cursor: usize,
+    /// 下一个分配的版本号（撤销后再修改不会复用旧版本号）
+    next_version: ConfigVersion,
+}
+
+impl ConfigService {
+    /// 使用默认配置创建服务
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_config(Config::new())
+    }
+
+    /// 使用指定初始配置创建服务
+    #[must_use]
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            versions: vec![(1, config)],
+            cursor: 0,
+            next_version: 2,
+        }
+    }
+
+    /// 获取当前配置
+    #[must_use]
+    pub fn current(&self) -> &Config {
+        &self.versions[self.cursor].1
+    }
+
+    /// 获取当前配置版本号（用于标记分析运行）
+    #[must_use]
+    pub fn current_version(&self) -> ConfigVersion {
+        self.versions[self.cursor].0
+    }
+
+    /// 应用新的配置变更，返回新版本号
+    ///
+    /// 如果当前处于撤销后的中间状态，重做分支会被丢弃
+    pub fn apply(&mut self, config: Config) -> ConfigVersion {
+        // 丢弃重做分支
+        self.versions.truncate(self.cursor + 1);
+
+        let version = self.next_version;
+        self.next_version += 1;
+        self.versions.push((version, config));
+        self.cursor = self.versions.len() - 1;
+
+        info!("配置已更新至版本 v{version}");
+        version
+    }
+
+    /// 是否存在可撤销的变更
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// 是否存在可重做的变更
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.versions.len()
+    }
+
+    /// 撤销最近一次配置变更，返回回退后的版本号
+    pub fn undo(&mut self) -> AuditResult<ConfigVersion> {
+        if !self.can_undo() {
+            return Err(AuditError::config_error("没有可撤销的配置变更"));
+        }
+        self.cursor -= 1;
+        let version = self.current_version();
+        info!("配置已撤销至版本 v{version}");
+        Ok(version)
+    }
+
+    /// 重做最近一次被撤销的变更，返回前进后的版本号
+    pub fn redo(&mut self) -> AuditResult<ConfigVersion> {
+        if !self.can_redo() {
+            return Err(AuditError::config_error("没有可重做的配置变更"));
+        }
+        self.cursor += 1;
+        let version = self.current_version();
+        info!("配置已重做至版本 v{version}");
+        Ok(version)
+    }
+
+    /// 获取全部历史版本号（按时间顺序）
+    #[must_use]
+    pub fn version_history(&self) -> Vec<ConfigVersion> {
+        self.versions.iter().map(|(v, _)| *v).collect()
+    }
+}
+
+impl Default for ConfigService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_keyword(keyword: &str) -> Config {
+        let mut config = Config::new();
+        config.fund_attributes.personal_fund_keywords.insert(keyword.to_string());
+        config
+    }
+
+    #[test]
+    fn test_initial_version() {
+        let service = ConfigService::new();
+        assert_eq!(service.current_version(), 1);
+        assert!(!service.can_undo());
+        assert!(!service.can_redo());
+    }
+
+    #[test]
+    fn test_apply_and_undo_redo() {
+        let mut service = ConfigService::new();
+
+        let v2 = service.apply(config_with_keyword("股东"));
+        assert_eq!(v2, 2);
+        assert!(service.current().is_personal_fund("股东借款"));
+
+        // 撤销后回到初始配置
+        let undone = service.undo().unwrap();
+        assert_eq!(undone, 1);
+        assert!(!service.current().is_personal_fund("股东借款"));
+        assert!(service.can_redo());
+
+        // 重做恢复修改
+        let redone = service.redo().unwrap();
+        assert_eq!(redone, 2);
+        assert!(service.current().is_personal_fund("股东借款"));
+    }
+
+    #[test]
+    fn test_apply_discards_redo_branch() {
+        let mut service = ConfigService::new();
+        service.apply(config_with_keyword("股东"));
+        service.undo().unwrap();
+
+        // 撤销后应用新变更，旧的重做分支被丢弃且版本号不复用
+        let v3 = service.apply(config_with_keyword("法人"));
+        assert_eq!(v3, 3);
+        assert!(!service.can_redo());
+        assert_eq!(service.version_history(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_undo_redo_errors_at_bounds() {
+        let mut service = ConfigService::new();
+        assert!(service.undo().is_err());
+        assert!(service.redo().is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! 持久化查询历史服务
+//!
+//! GUI的查询历史此前只存在`AppState`内存里，应用重启即丢失。
+//! 本服务把时点查询与分析运行（含统计信息）落盘，提供分页、
+//! 关键字搜索与裁剪。存储复用[`super::PersistentStore`]的带版本
+//! JSON封套而非SQLite：历史量级在千条以内，版本迁移与损坏恢复
+//! 已由存储层覆盖，无需引入本地C依赖。
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AuditResult;
+use crate::services::persistent_store::{PersistentStore, StoreLoadOutcome};
+
+/// 历史条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryKind {
+    /// 时点查询
+    TimePointQuery,
+    /// 完整分析运行
+    AnalysisRun,
+}
+
+/// 单条历史记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// 唯一标识（由调用方生成，GUI侧通常为UUID）
+    pub id: String,
+    /// 记录时间（"%Y-%m-%d %H:%M:%S"）
+    pub timestamp: String,
+    /// 条目类型
+    pub kind: HistoryKind,
+    /// 分析的流水文件路径
+    pub file_path: String,
+    /// 使用的算法
+    pub algorithm: String,
+    /// 时点查询的目标行号（分析运行为None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_number: Option<usize>,
+    /// 是否成功
+    pub success: bool,
+    /// 耗时（秒）
+    pub duration_secs: f64,
+    /// 结果摘要（如"共处理9799行，挪用120万"）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// 落盘的数据体
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryData {
+    entries: Vec<HistoryEntry>,
+}
+
+/// 持久化历史服务
+///
+/// 条目按记录顺序存储，对外查询统一按最新在前返回；
+/// 超过上限时自动裁掉最老的条目
+pub struct HistoryService {
+    store: PersistentStore,
+    data: HistoryData,
+    max_entries: usize,
+}
+
+impl HistoryService {
+    /// 默认保留的最大条目数
+    pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+    /// 打开（或新建）指定路径的历史存储
+    ///
+    /// 返回加载结果说明，供调用方在迁移或损坏恢复时提示用户
+    pub fn open<P: AsRef<Path>>(path: P) -> AuditResult<(Self, StoreLoadOutcome)> {
+        let store = PersistentStore::new(path);
+        let (data, outcome) = store.load::<HistoryData>()?;
+        Ok((
+            Self {
+                store,
+                data,
+                max_entries: Self::DEFAULT_MAX_ENTRIES,
+            },
+            outcome,
+        ))
+    }
+
+    /// 记录一条历史并落盘
+    pub fn record(&mut self, entry: HistoryEntry) -> AuditResult<()> {
+        self.data.entries.push(entry);
+        if self.data.entries.len() > self.max_entries {
+            let excess = self.data.entries.len() - self.max_entries;
+            self.data.entries.drain(..excess);
+        }
+        self.store.save(&self.data)
+    }
+
+    /// 分页查询（最新在前）
+    #[must_use]
+    pub fn page(&self, offset: usize, limit: usize) -> Vec<HistoryEntry> {
+        self.data.entries.iter().rev()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// 关键字搜索（匹配文件路径/算法/摘要，最新在前）
+    #[must_use]
+    pub fn search(&self, keyword: &str) -> Vec<HistoryEntry> {
+        self.data.entries.iter().rev()
+            .filter(|entry| {
+                entry.file_path.contains(keyword)
+                    || entry.algorithm.contains(keyword)
+                    || entry.summary.as_deref().is_some_and(|s| s.contains(keyword))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// 按id删除单条历史，返回是否删除
+    pub fn delete(&mut self, id: &str) -> AuditResult<bool> {
+        let before = self.data.entries.len();
+        self.data.entries.retain(|entry| entry.id != id);
+        if self.data.entries.len() == before {
+            return Ok(false);
+        }
+        self.store.save(&self.data)?;
+        Ok(true)
+    }
+
+    /// 裁剪历史，仅保留最新的`keep_latest`条，返回删除数量
+    pub fn prune(&mut self, keep_latest: usize) -> AuditResult<usize> {
+        if self.data.entries.len() <= keep_latest {
+            return Ok(0);
+        }
+        let removed = self.data.entries.len() - keep_latest;
+        self.data.entries.drain(..removed);
+        self.store.save(&self.data)?;
+        Ok(removed)
+    }
+
+    /// 当前条目数
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.entries.len()
+    }
+
+    /// 是否为空
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_history_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("flux_history_test_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("history.json")
+    }
+
+    fn entry(id: &str, file_path: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            timestamp: "2026-08-31 10:00:00".to_string(),
+            kind: HistoryKind::AnalysisRun,
+            file_path: file_path.to_string(),
+            algorithm: "FIFO".to_string(),
+            row_number: None,
+            success: true,
+            duration_secs: 1.5,
+            summary: Some("共处理100行".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_history_survives_reopen() {
+        let path = temp_history_path("reopen");
+        {
+            let (mut service, _) = HistoryService::open(&path).unwrap();
+            service.record(entry("1", "a.xlsx")).unwrap();
+            service.record(entry("2", "b.xlsx")).unwrap();
+        }
+
+        let (service, outcome) = HistoryService::open(&path).unwrap();
+        assert_eq!(outcome, StoreLoadOutcome::Loaded);
+        assert_eq!(service.len(), 2);
+        // 最新在前
+        assert_eq!(service.page(0, 10)[0].id, "2");
+    }
+
+    #[test]
+    fn test_page_and_search() {
+        let path = temp_history_path("page");
+        let (mut service, _) = HistoryService::open(&path).unwrap();
+        for i in 0..5 {
+            service.record(entry(&i.to_string(), &format!("file{i}.xlsx"))).unwrap();
+        }
+
+        let page = service.page(1, 2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].id, "3");
+        assert_eq!(page[1].id, "2");
+
+        let hits = service.search("file4");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "4");
+        assert!(service.search("不存在的关键字").is_empty());
+    }
+
+    #[test]
+    fn test_delete_and_prune() {
+        let path = temp_history_path("prune");
+        let (mut service, _) = HistoryService::open(&path).unwrap();
+        for i in 0..5 {
+            service.record(entry(&i.to_string(), "a.xlsx")).unwrap();
+        }
+
+        assert!(service.delete("2").unwrap());
+        assert!(!service.delete("2").unwrap());
+        assert_eq!(service.len(), 4);
+
+        // 保留最新2条，删掉最老的2条
+        assert_eq!(service.prune(2).unwrap(), 2);
+        let remaining = service.page(0, 10);
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].id, "4");
+        assert_eq!(remaining[1].id, "3");
+    }
+}
@@ -4,8 +4,28 @@
 //! 基于清洁的模块组织架构
 
 pub mod audit_service;
+pub mod audit_trail;
+pub mod benchmark;
+pub mod config_service;
+pub mod history_service;
+pub mod notification_service;
+pub mod performance_stats;
+pub mod persistent_store;
+pub mod progress_aggregator;
+pub mod server_service;
 pub mod time_point_service;
+pub mod trace_profiler;
 
 // 重新导出主要服务
 pub use audit_service::*;
-pub use time_point_service::*;
\ No newline at end of file
+pub use audit_trail::*;
+pub use benchmark::*;
+pub use config_service::*;
+pub use history_service::*;
+pub use notification_service::*;
+pub use performance_stats::*;
+pub use persistent_store::*;
+pub use progress_aggregator::*;
+pub use server_service::*;
+pub use time_point_service::*;
+pub use trace_profiler::*;
\ No newline at end of file
@@ -0,0 +1,354 @@
+//! 运行结束通知服务
+//!
+//! 长时间批量分析通常无人值守。本服务在分析完成或失败后，
+//! 将紧凑的运行摘要（文件、算法、耗时、关键指标、输出路径）
+//! 推送到配置的Webhook地址或SMTP邮件服务器，并带重试机制。
+//! 通知失败只记录日志，绝不影响分析结果本身。
+
+use crate::data_models::{NotificationConfig, SmtpNotificationConfig};
+use crate::errors::{AuditError, AuditResult};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// 运行摘要 - 通知的载荷
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// 输入文件路径
+    pub input_file: String,
+    /// 使用的算法
+    pub algorithm: String,
+    /// 运行是否成功
+    pub success: bool,
+    /// 运行耗时（秒）
+    pub duration_secs: f64,
+    /// 关键指标（名称, 格式化后的值）
+    pub key_metrics: Vec<(String, String)>,
+    /// 输出文件路径列表
+    pub output_files: Vec<String>,
+    /// 补充消息（失败时为错误描述）
+    pub message: String,
+}
+
+impl RunSummary {
+    /// 序列化为JSON载荷（Webhook使用）
+    pub fn to_json(&self) -> AuditResult<String> {
+        serde_json::to_string(self)
+            .map_err(|e| AuditError::config_error(format!("运行摘要序列化失败: {e}")))
+    }
+
+    /// 格式化为纯文本（邮件正文使用）
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        let status = if self.success { "✅ 完成" } else { "❌ 失败" };
+        let mut lines = vec![
+            format!("资金追踪分析运行{status}"),
+            format!("输入文件: {}", self.input_file),
+            format!("算法: {}", self.algorithm),
+            format!("耗时: {:.1}秒", self.duration_secs),
+        ];
+        for (name, value) in &self.key_metrics {
+            lines.push(format!("{name}: {value}"));
+        }
+        for file in &self.output_files {
+            lines.push(format!("输出: {file}"));
+        }
+        if !self.message.is_empty() {
+            lines.push(format!("说明: {}", self.message));
+        }
+        lines.join("\n")
+    }
+}
+
+/// 通知渠道trait - 统一`Webhook`与SMTP的发送接口
+pub trait NotificationChannel: Send + Sync {
+    /// 渠道名称（用于日志）
+    fn name(&self) -> &'static str;
+
+    /// 发送一条运行摘要通知
+    fn send(&self, summary: &RunSummary) -> AuditResult<()>;
+}
+
+/// Webhook通知渠道
+///
+/// 以HTTP POST将JSON摘要推送到配置的URL。仅支持`http://`地址
+/// （内网通知服务场景），`https://`需要TLS支持，暂不提供
+pub struct WebhookChannel {
+    url: String,
+}
+
+impl WebhookChannel {
+    #[must_use]
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+
+    /// 解析URL为（主机:端口, 路径）
+    fn parse_url(&self) -> AuditResult<(String, String)> {
+        let rest = self.url.strip_prefix("http://").ok_or_else(|| {
+            AuditError::config_error(format!(
+                "Webhook仅支持http://地址（当前: {}）", self.url
+            ))
+        })?;
+
+        let (authority, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, "/"),
+        };
+        if authority.is_empty() {
+            return Err(AuditError::config_error(format!("Webhook地址缺少主机名: {}", self.url)));
+        }
+
+        // 无端口时默认80
+        let host_port = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:80")
+        };
+        Ok((host_port, path.to_string()))
+    }
+}
+
+impl NotificationChannel for WebhookChannel {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn send(&self, summary: &RunSummary) -> AuditResult<()> {
+        let (host_port, path) = self.parse_url()?;
+        let body = summary.to_json()?;
+
+        let mut stream = TcpStream::connect(&host_port)
+            .map_err(|e| AuditError::config_error(format!("Webhook连接失败 {host_port}: {e}")))?;
+        stream.set_read_timeout(Some(Duration::from_secs(10))).ok();
+        stream.set_write_timeout(Some(Duration::from_secs(10))).ok();
+
+        let request = format!(
+            "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/json\r\nContent-Length: {len}\r\nConnection: close\r\n\r\n{body}",
+            host = host_port,
+            len = body.len(),
+        );
+        stream.write_all(request.as_bytes())
+            .map_err(|e| AuditError::config_error(format!("Webhook请求发送失败: {e}")))?;
+
+        // 只需确认状态行为2xx
+        let mut response = String::new();
+        BufReader::new(&mut stream).read_line(&mut response)
+            .map_err(|e| AuditError::config_error(format!("Webhook响应读取失败: {e}")))?;
+        let status_ok = response.split_whitespace().nth(1)
+            .is_some_and(|code| code.starts_with('2'));
+        if status_ok {
+            Ok(())
+        } else {
+            Err(AuditError::config_error(format!("Webhook返回非成功状态: {}", response.trim())))
+        }
+    }
+}
+
+/// SMTP邮件通知渠道
+///
+/// 以明文SMTP投递纯文本摘要邮件（内网邮件网关场景，不支持认证与TLS）
+pub struct SmtpChannel {
+    config: SmtpNotificationConfig,
+}
+
+impl SmtpChannel {
+    #[must_use]
+    pub fn new(config: SmtpNotificationConfig) -> Self {
+        Self { config }
+    }
+
+    /// 发送一条SMTP命令并校验响应状态码前缀
+    fn command(stream: &mut TcpStream, cmd: &str, expected_prefix: &str) -> AuditResult<()> {
+        if !cmd.is_empty() {
+            stream.write_all(format!("{cmd}\r\n").as_bytes())
+                .map_err(|e| AuditError::config_error(format!("SMTP命令发送失败: {e}")))?;
+        }
+        let mut response = String::new();
+        let mut reader = BufReader::new(stream.try_clone()
+            .map_err(|e| AuditError::config_error(format!("SMTP连接复制失败: {e}")))?);
+        reader.read_line(&mut response)
+            .map_err(|e| AuditError::config_error(format!("SMTP响应读取失败: {e}")))?;
+        if response.starts_with(expected_prefix) {
+            Ok(())
+        } else {
+            Err(AuditError::config_error(format!(
+                "SMTP响应异常（期望{expected_prefix}）: {}", response.trim()
+            )))
+        }
+    }
+}
+
+impl NotificationChannel for SmtpChannel {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    fn send(&self, summary: &RunSummary) -> AuditResult<()> {
+        let address = format!("{}:{}", self.config.server, self.config.port);
+        let mut stream = TcpStream::connect(&address)
+            .map_err(|e| AuditError::config_error(format!("SMTP连接失败 {address}: {e}")))?;
+        stream.set_read_timeout(Some(Duration::from_secs(15))).ok();
+        stream.set_write_timeout(Some(Duration::from_secs(15))).ok();
+
+        Self::command(&mut stream, "", "220")?;
+        Self::command(&mut stream, "HELO flux-backend", "250")?;
+        Self::command(&mut stream, &format!("MAIL FROM:<{}>", self.config.from), "250")?;
+        for recipient in &self.config.to {
+            Self::command(&mut stream, &format!("RCPT TO:<{recipient}>"), "250")?;
+        }
+        Self::command(&mut stream, "DATA", "354")?;
+
+        let status = if summary.success { "完成" } else { "失败" };
+        let mail = format!(
+            "From: {}\r\nTo: {}\r\nSubject: =?UTF-8?B?{}?=\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n.",
+            self.config.from,
+            self.config.to.join(", "),
+            base64_encode(format!("资金追踪分析{status}: {}", summary.algorithm).as_bytes()),
+            summary.to_plain_text().replace("\n.", "\n..")
+        );
+        Self::command(&mut stream, &mail, "250")?;
+        Self::command(&mut stream, "QUIT", "221").ok();
+        Ok(())
+    }
+}
+
+/// 标准base64编码（用于邮件主题的UTF-8编码，避免引入额外依赖）
+fn base64_encode(input: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b0 = u32::from(chunk[0]);
+        let b1 = chunk.get(1).map_or(0, |b| u32::from(*b));
+        let b2 = chunk.get(2).map_or(0, |b| u32::from(*b));
+        let combined = (b0 << 16) | (b1 << 8) | b2;
+        output.push(TABLE[(combined >> 18) as usize & 0x3F] as char);
+        output.push(TABLE[(combined >> 12) as usize & 0x3F] as char);
+        output.push(if chunk.len() > 1 { TABLE[(combined >> 6) as usize & 0x3F] as char } else { '=' });
+        output.push(if chunk.len() > 2 { TABLE[combined as usize & 0x3F] as char } else { '=' });
+    }
+    output
+}
+
+/// 通知服务 - 按配置构建渠道并带重试发送
+pub struct NotificationService {
+    max_retries: u32,
+    retry_interval_secs: u64,
+    channels: Vec<Box<dyn NotificationChannel>>,
+}
+
+impl NotificationService {
+    /// 根据配置构建通知服务；未启用或无可用渠道时返回None
+    #[must_use]
+    pub fn from_config(config: &NotificationConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        let mut channels: Vec<Box<dyn NotificationChannel>> = Vec::new();
+        if let Some(url) = &config.webhook_url {
+            channels.push(Box::new(WebhookChannel::new(url.clone())));
+        }
+        if let Some(smtp) = &config.smtp {
+            channels.push(Box::new(SmtpChannel::new(smtp.clone())));
+        }
+
+        if channels.is_empty() {
+            warn!("通知已启用但未配置任何渠道（webhook_url/smtp均为空）");
+            return None;
+        }
+
+        Some(Self {
+            max_retries: config.max_retries,
+            retry_interval_secs: config.retry_interval_secs,
+            channels,
+        })
+    }
+
+    /// 向全部渠道发送运行摘要，每个渠道独立重试
+    ///
+    /// 通知失败只记录警告日志，不向上传播错误
+    pub async fn notify(&self, summary: &RunSummary) {
+        for channel in &self.channels {
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                match channel.send(summary) {
+                    Ok(()) => {
+                        info!("📨 运行摘要已通过{}渠道发送", channel.name());
+                        break;
+                    }
+                    Err(e) if attempt <= self.max_retries => {
+                        warn!("{}通知第{}次发送失败，{}秒后重试: {}",
+                            channel.name(), attempt, self.retry_interval_secs, e);
+                        tokio::time::sleep(Duration::from_secs(self.retry_interval_secs)).await;
+                    }
+                    Err(e) => {
+                        warn!("{}通知发送失败，已达最大重试次数{}: {}",
+                            channel.name(), self.max_retries, e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    fn sample_summary() -> RunSummary {
+        RunSummary {
+            input_file: "流水.xlsx".to_string(),
+            algorithm: "FIFO".to_string(),
+            success: true,
+            duration_secs: 12.5,
+            key_metrics: vec![("累计挪用金额".to_string(), "1000.00".to_string())],
+            output_files: vec!["output/FIFO_结果.xlsx".to_string()],
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_plain_text_contains_key_fields() {
+        // 纯文本摘要应包含状态、算法与关键指标
+        let text = sample_summary().to_plain_text();
+        assert!(text.contains("✅ 完成"));
+        assert!(text.contains("算法: FIFO"));
+        assert!(text.contains("累计挪用金额: 1000.00"));
+    }
+
+    #[test]
+    fn test_webhook_posts_json_payload() {
+        // 本地起一个最小HTTP服务，验证Webhook渠道发送的请求体
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let channel = WebhookChannel::new(format!("http://127.0.0.1:{port}/notify"));
+        channel.send(&sample_summary()).unwrap();
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /notify HTTP/1.1"));
+        assert!(request.contains("\"algorithm\":\"FIFO\""));
+    }
+
+    #[test]
+    fn test_webhook_rejects_https() {
+        // https地址应返回配置错误而不是静默失败
+        let channel = WebhookChannel::new("https://example.com/hook".to_string());
+        assert!(channel.send(&sample_summary()).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! 历史运行吞吐统计与运行前估算
+//!
+//! 大流水文件的完整分析可能要跑几十分钟，用户需要在动手前知道
+//! "大概多久、结果文件多大"才能安排时间。本服务把每次成功运行的
+//! 行数、耗时与输出大小落盘（复用[`super::PersistentStore`]），
+//! 按算法累计吞吐后对新文件给出估算；没有历史样本时退回内置的
+//! 保守默认吞吐，并在结果中注明估算依据。
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AuditResult;
+use crate::services::persistent_store::{PersistentStore, StoreLoadOutcome};
+
+/// 一次成功运行的吞吐样本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSample {
+    /// 使用的算法
+    pub algorithm: String,
+    /// 处理的交易行数
+    pub rows: usize,
+    /// 总耗时（秒）
+    pub duration_secs: f64,
+    /// 主结果文件大小（字节）
+    pub output_bytes: u64,
+    /// 记录时间（"%Y-%m-%d %H:%M:%S"）
+    pub timestamp: String,
+    /// 各阶段耗时（秒，阶段名与进度事件一致；旧样本没有该字段）
+    #[serde(default)]
+    pub stage_secs: Vec<(String, f64)>,
+    /// FIFO条目对象池命中率（0-1；非FIFO运行与旧样本没有该字段）
+    #[serde(default)]
+    pub fifo_pool_hit_rate: Option<f64>,
+}
+
+/// 估算依据，供展示层说明可信度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateBasis {
+    /// 基于同一算法的历史样本
+    AlgorithmHistory,
+    /// 该算法没有样本，基于全部算法的历史样本
+    AnyHistory,
+    /// 完全没有历史样本，使用内置默认吞吐
+    BuiltinDefaults,
+}
+
+/// 运行前估算结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunEstimate {
+    /// 待处理行数
+    pub rows: usize,
+    /// 预计耗时（秒）
+    pub estimated_secs: f64,
+    /// 预计主结果文件大小（字节）
+    pub estimated_output_bytes: u64,
+    /// 参与估算的历史样本数
+    pub sample_count: usize,
+    /// 估算依据
+    pub basis: EstimateBasis,
+}
+
+/// 落盘的数据体
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatsData {
+    samples: Vec<RunSample>,
+}
+
+/// 历史吞吐统计服务
+///
+/// 样本按记录顺序存储，每个算法只保留最近若干条——硬件或数据
+/// 特征变化后，过老的样本只会拖偏估算
+pub struct PerformanceStatsService {
+    store: PersistentStore,
+    data: StatsData,
+}
+
+impl PerformanceStatsService {
+    /// 默认存储文件名（与查询历史同目录约定）
+    pub const DEFAULT_STATS_FILE: &'static str = "flux_performance_stats.json";
+
+    /// 每个算法保留的最大样本数
+    const MAX_SAMPLES_PER_ALGORITHM: usize = 20;
+
+    /// 没有任何历史样本时的保守默认吞吐（行/秒）
+    const DEFAULT_ROWS_PER_SEC: f64 = 20_000.0;
+
+    /// 没有任何历史样本时的默认输出大小（字节/行）
+    const DEFAULT_BYTES_PER_ROW: f64 = 200.0;
+
+    /// 打开（或新建）指定路径的统计存储
+    pub fn open<P: AsRef<Path>>(path: P) -> AuditResult<(Self, StoreLoadOutcome)> {
+        let store = PersistentStore::new(path);
+        let (data, outcome) = store.load::<StatsData>()?;
+        Ok((Self { store, data }, outcome))
+    }
+
+    /// 记录一次成功运行并落盘
+    ///
+    /// 超过每算法上限时裁掉该算法最老的样本
+    pub fn record(&mut self, sample: RunSample) -> AuditResult<()> {
+        let algorithm = sample.algorithm.clone();
+        self.data.samples.push(sample);
+        let count = self.data.samples.iter()
+            .filter(|s| s.algorithm == algorithm)
+            .count();
+        if count > Self::MAX_SAMPLES_PER_ALGORITHM {
+            let oldest = self.data.samples.iter()
+                .position(|s| s.algorithm == algorithm);
+            if let Some(idx) = oldest {
+                self.data.samples.remove(idx);
+            }
+        }
+        self.store.save(&self.data)
+    }
+
+    /// 对给定算法与行数给出运行前估算
+    ///
+    /// 吞吐按样本总量加权（总行数/总耗时），比逐样本平均更能抵抗
+    /// 小文件样本的启动开销噪声
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)] // 行数与吞吐均非负，乘积不会为负
+    pub fn estimate(&self, algorithm: &str, rows: usize) -> RunEstimate {
+        let algorithm_samples: Vec<&RunSample> = self.data.samples.iter()
+            .filter(|s| s.algorithm == algorithm)
+            .collect();
+        let (samples, basis) = if !algorithm_samples.is_empty() {
+            (algorithm_samples, EstimateBasis::AlgorithmHistory)
+        } else if !self.data.samples.is_empty() {
+            (self.data.samples.iter().collect(), EstimateBasis::AnyHistory)
+        } else {
+            return RunEstimate {
+                rows,
+                estimated_secs: rows as f64 / Self::DEFAULT_ROWS_PER_SEC,
+                estimated_output_bytes: (rows as f64 * Self::DEFAULT_BYTES_PER_ROW) as u64,
+                sample_count: 0,
+                basis: EstimateBasis::BuiltinDefaults,
+            };
+        };
+
+        let total_rows: f64 = samples.iter().map(|s| s.rows as f64).sum();
+        let total_secs: f64 = samples.iter().map(|s| s.duration_secs).sum();
+        let total_bytes: f64 = samples.iter().map(|s| s.output_bytes as f64).sum();
+
+        let rows_per_sec = if total_secs > 0.0 && total_rows > 0.0 {
+            total_rows / total_secs
+        } else {
+            Self::DEFAULT_ROWS_PER_SEC
+        };
+        let bytes_per_row = if total_rows > 0.0 {
+            total_bytes / total_rows
+        } else {
+            Self::DEFAULT_BYTES_PER_ROW
+        };
+
+        RunEstimate {
+            rows,
+            estimated_secs: rows as f64 / rows_per_sec,
+            estimated_output_bytes: (rows as f64 * bytes_per_row) as u64,
+            sample_count: samples.len(),
+            basis,
+        }
+    }
+
+    /// 当前样本总数
+    #[must_use]
+    pub fn sample_count(&self) -> usize {
+        self.data.samples.len()
+    }
+
+    /// 按算法学习的阶段进度权重
+    ///
+    /// FIFO与差额计算法的阶段耗时分布不同（前者算法阶段更重），
+    /// 这里把该算法所有带阶段耗时的样本按阶段名累加后归一化，
+    /// 供进度映射使用。没有带阶段耗时的样本时返回None（调用方
+    /// 退回内置默认权重）
+    #[must_use]
+    pub fn stage_weights(&self, algorithm: &str) -> Option<Vec<(String, f64)>> {
+        let mut totals: Vec<(String, f64)> = Vec::new();
+        for sample in self.data.samples.iter()
+            .filter(|s| s.algorithm == algorithm && !s.stage_secs.is_empty())
+        {
+            for (stage, secs) in &sample.stage_secs {
+                match totals.iter_mut().find(|(name, _)| name == stage) {
+                    Some((_, total)) => *total += secs,
+                    None => totals.push((stage.clone(), *secs)),
+                }
+            }
+        }
+
+        let sum: f64 = totals.iter().map(|(_, secs)| secs).sum();
+        if sum <= 0.0 {
+            return None;
+        }
+        Some(totals.into_iter()
+            .map(|(stage, secs)| (stage, secs / sum))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_stats_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("flux_perf_test_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("stats.json")
+    }
+
+    fn sample(algorithm: &str, rows: usize, duration_secs: f64, output_bytes: u64) -> RunSample {
+        RunSample {
+            algorithm: algorithm.to_string(),
+            rows,
+            duration_secs,
+            output_bytes,
+            timestamp: "2026-08-31 10:00:00".to_string(),
+            stage_secs: Vec::new(),
+            fifo_pool_hit_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_estimate_without_history_uses_defaults() {
+        let path = temp_stats_path("defaults");
+        let (service, _) = PerformanceStatsService::open(&path).unwrap();
+
+        let estimate = service.estimate("FIFO", 100_000);
+        assert_eq!(estimate.basis, EstimateBasis::BuiltinDefaults);
+        assert_eq!(estimate.sample_count, 0);
+        assert!(estimate.estimated_secs > 0.0);
+        assert!(estimate.estimated_output_bytes > 0);
+    }
+
+    #[test]
+    fn test_estimate_prefers_algorithm_history() {
+        let path = temp_stats_path("algorithm");
+        let (mut service, _) = PerformanceStatsService::open(&path).unwrap();
+        // FIFO跑1万行用10秒（1000行/秒），每行100字节
+        service.record(sample("FIFO", 10_000, 10.0, 1_000_000)).unwrap();
+        service.record(sample("BALANCE_METHOD", 10_000, 1.0, 1_000_000)).unwrap();
+
+        let estimate = service.estimate("FIFO", 20_000);
+        assert_eq!(estimate.basis, EstimateBasis::AlgorithmHistory);
+        assert_eq!(estimate.sample_count, 1);
+        assert!((estimate.estimated_secs - 20.0).abs() < 0.01);
+        assert_eq!(estimate.estimated_output_bytes, 2_000_000);
+
+        // 没有PROPORTIONAL样本时退回全部算法的合计吞吐
+        let fallback = service.estimate("PROPORTIONAL", 20_000);
+        assert_eq!(fallback.basis, EstimateBasis::AnyHistory);
+        assert_eq!(fallback.sample_count, 2);
+    }
+
+    #[test]
+    fn test_stage_weights_learned_per_algorithm() {
+        let path = temp_stats_path("stage_weights");
+        let (mut service, _) = PerformanceStatsService::open(&path).unwrap();
+
+        let mut fifo = sample("FIFO", 10_000, 10.0, 1_000_000);
+        fifo.stage_secs = vec![
+            ("数据预处理".to_string(), 1.0),
+            ("算法处理".to_string(), 8.0),
+            ("结果导出".to_string(), 1.0),
+        ];
+        service.record(fifo).unwrap();
+        // 不带阶段耗时的旧样本不参与权重学习
+        service.record(sample("FIFO", 10_000, 10.0, 1_000_000)).unwrap();
+
+        let weights = service.stage_weights("FIFO").unwrap();
+        let algorithm_weight = weights.iter()
+            .find(|(stage, _)| stage == "算法处理")
+            .map(|(_, weight)| *weight)
+            .unwrap();
+        assert!((algorithm_weight - 0.8).abs() < f64::EPSILON);
+
+        // 没有带阶段耗时的样本时返回None
+        assert!(service.stage_weights("BALANCE_METHOD").is_none());
+    }
+
+    #[test]
+    fn test_samples_survive_reopen_and_are_capped() {
+        let path = temp_stats_path("cap");
+        {
+            let (mut service, _) = PerformanceStatsService::open(&path).unwrap();
+            for i in 0..25 {
+                service.record(sample("FIFO", 1000 + i, 1.0, 1000)).unwrap();
+            }
+        }
+
+        let (service, outcome) = PerformanceStatsService::open(&path).unwrap();
+        assert_eq!(outcome, StoreLoadOutcome::Loaded);
+        // 每个算法最多保留20条
+        assert_eq!(service.sample_count(), 20);
+    }
+}
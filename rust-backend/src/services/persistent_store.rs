@@ -0,0 +1,262 @@
+//! 带版本号的本地持久化存储
+//!
+//! 查询历史、缓存、会话等数据一旦落盘，格式演进就会威胁老安装的
+//! 可用性。本模块给所有落盘数据加上显式格式版本封套：加载时按注册
+//! 的迁移链逐版本升级旧数据；文件损坏时改名备份后从空数据重新开始
+//! 并上报，保证升级或损坏都不会让用户数据"变砖"。
+
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{AuditError, AuditResult};
+
+/// 落盘封套：格式版本 + 实际数据
+#[derive(Debug, Serialize, Deserialize)]
+struct StoreEnvelope {
+    format_version: u32,
+    data: serde_json::Value,
+}
+
+/// 单步迁移函数：把`from`版本的数据升级到`from + 1`版本
+pub type StoreMigration = fn(serde_json::Value) -> AuditResult<serde_json::Value>;
+
+/// 加载结果说明，供调用方决定是否提示用户
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreLoadOutcome {
+    /// 文件不存在，使用默认数据
+    Fresh,
+    /// 当前版本数据，直接加载
+    Loaded,
+    /// 旧版本数据，已按迁移链升级
+    Migrated { from_version: u32 },
+    /// 文件损坏，已改名备份并从默认数据重新开始
+    RecoveredFromCorruption { backup_path: PathBuf },
+}
+
+/// 带版本号的JSON持久化存储
+///
+/// 迁移链按版本顺序注册，`migrations[i]`负责把版本`i + 1`升级到`i + 2`，
+/// 当前格式版本即`迁移数 + 1`——新增一步迁移就自动成为新版本，
+/// 不存在版本常量与迁移链脱节的可能
+pub struct PersistentStore {
+    path: PathBuf,
+    migrations: Vec<StoreMigration>,
+}
+
+impl PersistentStore {
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            migrations: Vec::new(),
+        }
+    }
+
+    /// 注册下一步迁移（从当前最高版本升级到其后继版本）
+    pub fn register_migration(&mut self, migration: StoreMigration) {
+        self.migrations.push(migration);
+    }
+
+    /// 当前格式版本
+    #[must_use]
+    pub fn current_version(&self) -> u32 {
+        self.migrations.len() as u32 + 1
+    }
+
+    /// 加载数据，必要时迁移旧版本或从损坏中恢复
+    ///
+    /// 只有"数据来自更新版本的安装"会返回错误——此时覆盖写入会
+    /// 丢失新版字段，应提示用户而不是悄悄降级
+    pub fn load<T: DeserializeOwned + Default>(&self) -> AuditResult<(T, StoreLoadOutcome)> {
+        if !self.path.exists() {
+            return Ok((T::default(), StoreLoadOutcome::Fresh));
+        }
+
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("⚠️ 持久化存储不可读: {} ({e})", self.path.display());
+                let backup_path = self.quarantine_corrupted()?;
+                return Ok((T::default(), StoreLoadOutcome::RecoveredFromCorruption { backup_path }));
+            }
+        };
+
+        let envelope: StoreEnvelope = match serde_json::from_str(&content) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                warn!("⚠️ 持久化存储格式损坏: {} ({e})", self.path.display());
+                let backup_path = self.quarantine_corrupted()?;
+                return Ok((T::default(), StoreLoadOutcome::RecoveredFromCorruption { backup_path }));
+            }
+        };
+
+        let current = self.current_version();
+        if envelope.format_version > current {
+            return Err(AuditError::config_error(format!(
+                "存储文件{}的格式版本{}高于本程序支持的版本{}，请升级程序后再打开",
+                self.path.display(), envelope.format_version, current
+            )));
+        }
+
+        let from_version = envelope.format_version;
+        let mut data = envelope.data;
+        for step in from_version..current {
+            let migration = self.migrations[step as usize - 1];
+            data = migration(data).map_err(|e| AuditError::config_error(format!(
+                "存储文件{}从版本{}迁移到{}失败: {e}",
+                self.path.display(), step, step + 1
+            )))?;
+        }
+
+        let parsed: T = serde_json::from_value(data).map_err(|e| {
+            AuditError::config_error(format!(
+                "存储文件{}数据解析失败: {e}", self.path.display()
+            ))
+        })?;
+
+        if from_version < current {
+            info!("♻️ 持久化存储已从版本{from_version}迁移到{current}: {}", self.path.display());
+            Ok((parsed, StoreLoadOutcome::Migrated { from_version }))
+        } else {
+            Ok((parsed, StoreLoadOutcome::Loaded))
+        }
+    }
+
+    /// 以当前格式版本保存数据（临时文件 + 原子改名，避免写一半损坏）
+    pub fn save<T: Serialize>(&self, data: &T) -> AuditResult<()> {
+        let envelope = StoreEnvelope {
+            format_version: self.current_version(),
+            data: serde_json::to_value(data).map_err(|e| {
+                AuditError::config_error(format!("存储数据序列化失败: {e}"))
+            })?,
+        };
+        let content = serde_json::to_string_pretty(&envelope).map_err(|e| {
+            AuditError::config_error(format!("存储数据序列化失败: {e}"))
+        })?;
+
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    AuditError::config_error(format!("无法创建存储目录{}: {e}", parent.display()))
+                })?;
+            }
+        }
+
+        let temp_path = self.path.with_extension("tmp");
+        std::fs::write(&temp_path, content).map_err(|e| {
+            AuditError::config_error(format!("写入存储文件{}失败: {e}", temp_path.display()))
+        })?;
+        std::fs::rename(&temp_path, &self.path).map_err(|e| {
+            AuditError::config_error(format!("替换存储文件{}失败: {e}", self.path.display()))
+        })
+    }
+
+    /// 把损坏的存储文件改名备份，为重新开始腾出路径
+    fn quarantine_corrupted(&self) -> AuditResult<PathBuf> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut backup_path = self.path.clone();
+        backup_path.set_extension(format!("corrupt-{timestamp}"));
+        std::fs::rename(&self.path, &backup_path).map_err(|e| {
+            AuditError::config_error(format!(
+                "无法备份损坏的存储文件{}: {e}", self.path.display()
+            ))
+        })?;
+        warn!("⚠️ 损坏的存储文件已备份到{}，将从空数据重新开始", backup_path.display());
+        Ok(backup_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct SampleHistory {
+        entries: Vec<String>,
+    }
+
+    fn temp_store_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("flux_store_test_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("history.json")
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = temp_store_path("roundtrip");
+        let store = PersistentStore::new(&path);
+
+        let history = SampleHistory { entries: vec!["查询1".to_string()] };
+        store.save(&history).unwrap();
+
+        let (loaded, outcome) = store.load::<SampleHistory>().unwrap();
+        assert_eq!(loaded, history);
+        assert_eq!(outcome, StoreLoadOutcome::Loaded);
+    }
+
+    #[test]
+    fn test_missing_file_starts_fresh() {
+        let path = temp_store_path("fresh");
+        let store = PersistentStore::new(path);
+
+        let (loaded, outcome) = store.load::<SampleHistory>().unwrap();
+        assert_eq!(loaded, SampleHistory::default());
+        assert_eq!(outcome, StoreLoadOutcome::Fresh);
+    }
+
+    #[test]
+    fn test_corrupted_file_is_quarantined() {
+        let path = temp_store_path("corrupt");
+        std::fs::write(&path, "不是JSON{{{").unwrap();
+        let store = PersistentStore::new(&path);
+
+        let (loaded, outcome) = store.load::<SampleHistory>().unwrap();
+        assert_eq!(loaded, SampleHistory::default());
+        let StoreLoadOutcome::RecoveredFromCorruption { backup_path } = outcome else {
+            panic!("应报告损坏恢复，实际为{outcome:?}");
+        };
+        assert!(backup_path.exists());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_migration_upgrades_old_version() {
+        let path = temp_store_path("migrate");
+        // 手工写入版本1数据：字段名为旧的"items"
+        std::fs::write(&path, r#"{"format_version":1,"data":{"items":["旧查询"]}}"#).unwrap();
+
+        let mut store = PersistentStore::new(&path);
+        store.register_migration(|mut data| {
+            // 版本1→2：字段items改名为entries
+            if let Some(object) = data.as_object_mut() {
+                if let Some(items) = object.remove("items") {
+                    object.insert("entries".to_string(), items);
+                }
+            }
+            Ok(data)
+        });
+        assert_eq!(store.current_version(), 2);
+
+        let (loaded, outcome) = store.load::<SampleHistory>().unwrap();
+        assert_eq!(loaded.entries, vec!["旧查询".to_string()]);
+        assert_eq!(outcome, StoreLoadOutcome::Migrated { from_version: 1 });
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let path = temp_store_path("newer");
+        std::fs::write(&path, r#"{"format_version":99,"data":{"entries":[]}}"#).unwrap();
+        let store = PersistentStore::new(&path);
+
+        let result = store.load::<SampleHistory>();
+        assert!(result.is_err());
+        // 不应破坏来自新版本的数据
+        assert!(path.exists());
+    }
+}
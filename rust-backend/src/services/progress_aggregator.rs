@@ -0,0 +1,235 @@
+//! 进度聚合协调器
+//!
+//! Compare/批量等复合操作会并发运行多个`AuditService`，各自的日志与
+//! 进度交错上报会让GUI进度条来回跳动。本模块为每个子运行保留独立
+//! 进度通道，由协调器重算合并百分比并给消息加上运行名前缀，
+//! 调用方只需订阅一个聚合通道即可得到连贯的单一进度视图。
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Mutex};
+
+use crate::services::audit_service::ProgressReport;
+
+/// 单个子运行的进度视图
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunProgress {
+    /// 子运行名称（通常为算法名或文件名）
+    pub name: String,
+    /// 当前百分比（0-100）
+    pub percentage: f64,
+    /// 最近一条进度消息
+    pub message: String,
+    /// 是否已完成（完成后百分比固定为100）
+    pub finished: bool,
+}
+
+/// 聚合后的复合操作进度
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AggregatedProgress {
+    /// 各子运行的进度明细
+    pub runs: Vec<RunProgress>,
+    /// 合并百分比（各子运行的算术平均）
+    pub combined_percentage: f64,
+    /// 最近一条带运行名前缀的消息
+    pub message: String,
+}
+
+/// 进度聚合协调器
+///
+/// 可`clone`后在并发任务间共享，与[`super::AuditService`]的共享状态约定一致
+#[derive(Clone)]
+pub struct ProgressAggregator {
+    /// 各子运行的最新进度
+    state: Arc<Mutex<Vec<RunProgress>>>,
+    /// 聚合进度通道
+    tx: Arc<watch::Sender<AggregatedProgress>>,
+}
+
+impl ProgressAggregator {
+    /// 创建聚合器，`run_names`按子运行顺序命名
+    #[must_use]
+    pub fn new(run_names: &[&str]) -> Self {
+        let runs: Vec<RunProgress> = run_names.iter()
+            .map(|name| RunProgress { name: (*name).to_string(), ..Default::default() })
+            .collect();
+        let initial = AggregatedProgress { runs: runs.clone(), ..Default::default() };
+        Self {
+            state: Arc::new(Mutex::new(runs)),
+            tx: Arc::new(watch::channel(initial).0),
+        }
+    }
+
+    /// 订阅聚合进度（GUI/CLI转发用）
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<AggregatedProgress> {
+        self.tx.subscribe()
+    }
+
+    /// 把一个子运行的进度通道接入聚合器
+    ///
+    /// 返回转发任务句柄；子运行的进度发送端全部释放后任务自动结束
+    pub fn attach(
+        &self,
+        run_index: usize,
+        mut rx: watch::Receiver<ProgressReport>,
+    ) -> tokio::task::JoinHandle<()> {
+        let aggregator = self.clone();
+        tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let report = rx.borrow_and_update().clone();
+                aggregator.update_run(run_index, &report).await;
+            }
+        })
+    }
+
+    /// 更新指定子运行的进度并重算合并百分比
+    async fn update_run(&self, run_index: usize, report: &ProgressReport) {
+        let mut runs = self.state.lock().await;
+        let Some(run) = runs.get_mut(run_index) else { return };
+
+        // 优先用服务端按阶段权重折算的整体百分比（跨阶段单调），
+        // 没有时退回行级百分比；阶段事件total为0只更新消息，
+        // 避免进度条归零回跳
+        if report.overall_percentage > 0.0 {
+            run.percentage = report.overall_percentage;
+        } else if report.total > 0 {
+            run.percentage = report.percentage;
+        }
+        run.message.clone_from(&report.message);
+        let message = format!("[{}] {}", run.name, report.message);
+
+        self.publish(&runs, message);
+    }
+
+    /// 标记某个子运行已完成（百分比固定为100）
+    pub async fn mark_finished(&self, run_index: usize) {
+        let mut runs = self.state.lock().await;
+        let Some(run) = runs.get_mut(run_index) else { return };
+        run.finished = true;
+        run.percentage = 100.0;
+        let message = format!("[{}] 完成", run.name);
+
+        self.publish(&runs, message);
+    }
+
+    /// 发送当前聚合快照
+    /// （`send_replace`在暂无订阅者时也会保留最新值，订阅者随时可取）
+    fn publish(&self, runs: &[RunProgress], message: String) {
+        let snapshot = AggregatedProgress {
+            runs: runs.to_vec(),
+            combined_percentage: Self::combined_percentage(runs),
+            message,
+        };
+        self.tx.send_replace(snapshot);
+    }
+
+    /// 合并百分比：各子运行百分比的算术平均，已完成的按100计
+    fn combined_percentage(runs: &[RunProgress]) -> f64 {
+        if runs.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = runs.iter()
+            .map(|run| if run.finished { 100.0 } else { run.percentage })
+            .sum();
+        sum / runs.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(percentage: f64, message: &str) -> ProgressReport {
+        ProgressReport {
+            stage: "algorithm_processing".to_string(),
+            current: 0,
+            total: 100,
+            percentage,
+            overall_percentage: 0.0,
+            message: message.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_combined_percentage_is_mean_of_runs() {
+        let aggregator = ProgressAggregator::new(&["FIFO", "BALANCE_METHOD"]);
+
+        aggregator.update_run(0, &report(80.0, "处理中")).await;
+        aggregator.update_run(1, &report(20.0, "处理中")).await;
+
+        let progress = aggregator.subscribe().borrow().clone();
+        assert!((progress.combined_percentage - 50.0).abs() < f64::EPSILON);
+        assert_eq!(progress.runs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_message_carries_run_name_prefix() {
+        let aggregator = ProgressAggregator::new(&["FIFO"]);
+
+        aggregator.update_run(0, &report(10.0, "已处理 100/1000 行")).await;
+
+        let progress = aggregator.subscribe().borrow().clone();
+        assert_eq!(progress.message, "[FIFO] 已处理 100/1000 行");
+    }
+
+    #[tokio::test]
+    async fn test_overall_percentage_preferred_over_stage_percentage() {
+        let aggregator = ProgressAggregator::new(&["FIFO"]);
+
+        // 阶段内百分比很高但整体刚过半时，进度条应跟随整体值
+        let mut weighted = report(90.0, "处理中");
+        weighted.overall_percentage = 55.0;
+        aggregator.update_run(0, &weighted).await;
+
+        let progress = aggregator.subscribe().borrow().clone();
+        assert!((progress.combined_percentage - 55.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_stage_event_does_not_reset_percentage() {
+        let aggregator = ProgressAggregator::new(&["FIFO"]);
+
+        aggregator.update_run(0, &report(60.0, "处理中")).await;
+        // 阶段事件：total为0，百分比不应被归零
+        let stage = ProgressReport {
+            stage: "result_export".to_string(),
+            current: 0,
+            total: 0,
+            percentage: 0.0,
+            overall_percentage: 0.0,
+            message: "导出结果".to_string(),
+        };
+        aggregator.update_run(0, &stage).await;
+
+        let progress = aggregator.subscribe().borrow().clone();
+        assert!((progress.combined_percentage - 60.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_mark_finished_pins_run_at_100() {
+        let aggregator = ProgressAggregator::new(&["FIFO", "BALANCE_METHOD"]);
+
+        aggregator.update_run(0, &report(40.0, "处理中")).await;
+        aggregator.mark_finished(0).await;
+
+        let progress = aggregator.subscribe().borrow().clone();
+        assert!((progress.combined_percentage - 50.0).abs() < f64::EPSILON);
+        assert!(progress.runs[0].finished);
+    }
+
+    #[tokio::test]
+    async fn test_attach_forwards_watch_channel() {
+        let aggregator = ProgressAggregator::new(&["FIFO"]);
+        let (tx, rx) = watch::channel(ProgressReport::default());
+        let mut subscription = aggregator.subscribe();
+
+        let _task = aggregator.attach(0, rx);
+        tx.send(report(30.0, "处理中")).unwrap();
+
+        subscription.changed().await.unwrap();
+        let progress = subscription.borrow_and_update().clone();
+        assert!((progress.combined_percentage - 30.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,394 @@
+//! HTTP服务模式（无头服务器部署）
+//!
+//! `flux-cli serve`启动一个axum HTTP服务，把审计分析、时点查询与
+//! 资金池查询暴露为REST接口，引擎可部署在共享服务器上而无需每位
+//! 审计人员在本机安装。分析为异步任务：提交后立即返回任务号，
+//! 客户端轮询状态，完成后下载结果工作簿。
+//!
+//! 接口一览：
+//!
+//! - `POST /api/analyze` 提交分析任务，返回`job_id`
+//! - `GET  /api/jobs` 列出全部任务
+//! - `GET  /api/jobs/{id}` 查询任务状态（含摘要与错误信息）
+//! - `GET  /api/jobs/{id}/result` 下载结果工作簿
+//! - `POST /api/query/time-point` 时点查询（同步返回）
+//! - `POST /api/query/fund-pool` 资金池查询（同步返回）
+//! - `GET  /health` 健康检查
+//!
+//! 任务表为进程内存态，服务重启后历史任务记录不保留；
+//! 结果文件落在服务器磁盘上，与CLI直接运行时一致。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::extract::{Path as UrlPath, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::data_models::AuditSummary;
+use crate::errors::{AuditError, AuditResult};
+use crate::services::{
+    AuditService, FundPoolQueryRequest, TimePointQueryRequest, TimePointService,
+};
+
+/// 分析任务状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// 已提交，等待执行
+    Pending,
+    /// 分析进行中
+    Running,
+    /// 分析完成，结果文件可下载
+    Completed,
+    /// 分析失败（错误信息见任务记录）
+    Failed,
+}
+
+/// 分析任务记录
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AnalysisJob {
+    /// 任务号（提交时生成）
+    pub id: Uuid,
+    /// 当前状态
+    pub status: JobStatus,
+    /// 算法标识（`FIFO`/`BALANCE_METHOD`/`PROPORTIONAL`）
+    pub algorithm: String,
+    /// 服务器上的输入文件路径
+    pub input_file: String,
+    /// 结果文件路径（完成后填充）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<String>,
+    /// 提交时间
+    pub submitted_at: DateTime<Utc>,
+    /// 结束时间（完成或失败时填充）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// 审计摘要（完成后填充）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<AuditSummary>,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 任务表（进程内存态，跨请求共享）
+#[derive(Clone, Default)]
+pub struct JobStore {
+    jobs: Arc<RwLock<HashMap<Uuid, AnalysisJob>>>,
+}
+
+impl JobStore {
+    /// 创建空任务表
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记新任务，返回任务号
+    pub async fn submit(&self, algorithm: &str, input_file: &str) -> Uuid {
+        let id = Uuid::new_v4();
+        let job = AnalysisJob {
+            id,
+            status: JobStatus::Pending,
+            algorithm: algorithm.to_string(),
+            input_file: input_file.to_string(),
+            output_file: None,
+            submitted_at: Utc::now(),
+            finished_at: None,
+            summary: None,
+            error: None,
+        };
+        self.jobs.write().await.insert(id, job);
+        id
+    }
+
+    /// 查询任务记录
+    pub async fn get(&self, id: Uuid) -> Option<AnalysisJob> {
+        self.jobs.read().await.get(&id).cloned()
+    }
+
+    /// 列出全部任务（按提交时间排序）
+    pub async fn list(&self) -> Vec<AnalysisJob> {
+        let mut jobs: Vec<AnalysisJob> = self.jobs.read().await.values().cloned().collect();
+        jobs.sort_by_key(|job| job.submitted_at);
+        jobs
+    }
+
+    /// 标记任务开始执行
+    pub async fn mark_running(&self, id: Uuid) {
+        if let Some(job) = self.jobs.write().await.get_mut(&id) {
+            job.status = JobStatus::Running;
+        }
+    }
+
+    /// 标记任务完成并记录摘要与结果文件
+    pub async fn mark_completed(&self, id: Uuid, summary: AuditSummary, output_file: Option<String>) {
+        if let Some(job) = self.jobs.write().await.get_mut(&id) {
+            job.status = JobStatus::Completed;
+            job.summary = Some(summary);
+            job.output_file = output_file;
+            job.finished_at = Some(Utc::now());
+        }
+    }
+
+    /// 标记任务失败并记录原因
+    pub async fn mark_failed(&self, id: Uuid, message: String) {
+        if let Some(job) = self.jobs.write().await.get_mut(&id) {
+            job.status = JobStatus::Failed;
+            job.error = Some(message);
+            job.finished_at = Some(Utc::now());
+        }
+    }
+}
+
+/// 统一的接口错误（序列化为`{"error": "..."}`）
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request<S: Into<String>>(message: S) -> Self {
+        Self { status: StatusCode::BAD_REQUEST, message: message.into() }
+    }
+
+    fn not_found<S: Into<String>>(message: S) -> Self {
+        Self { status: StatusCode::NOT_FOUND, message: message.into() }
+    }
+
+    fn internal<S: Into<String>>(message: S) -> Self {
+        Self { status: StatusCode::INTERNAL_SERVER_ERROR, message: message.into() }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.message }));
+        (self.status, body).into_response()
+    }
+}
+
+fn default_algorithm() -> String {
+    "FIFO".to_string()
+}
+
+/// 分析任务提交请求
+#[derive(Deserialize, Debug, Clone)]
+pub struct AnalyzeJobRequest {
+    /// 算法标识（省略时为FIFO）
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    /// 服务器上的输入文件路径
+    pub input_file: String,
+    /// 结果文件路径（省略时按算法自动生成）
+    #[serde(default)]
+    pub output_file: Option<String>,
+}
+
+/// 提交分析任务：校验入参后登记任务并在后台执行
+async fn submit_analysis(
+    State(store): State<JobStore>,
+    Json(request): Json<AnalyzeJobRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let service = AuditService::new().with_suppress_output(true);
+    if !service.get_algorithms_info().contains_key(request.algorithm.as_str()) {
+        return Err(ApiError::bad_request(format!(
+            "不支持的算法: {}", request.algorithm
+        )));
+    }
+    if !Path::new(&request.input_file).exists() {
+        return Err(ApiError::bad_request(format!(
+            "输入文件不存在: {}", request.input_file
+        )));
+    }
+
+    let id = store.submit(&request.algorithm, &request.input_file).await;
+    println!("📋 任务已提交: {id}（{} {}）", request.algorithm, request.input_file);
+
+    let task_store = store.clone();
+    tokio::spawn(async move {
+        task_store.mark_running(id).await;
+        let result = service
+            .analyze_financial_data(
+                &request.algorithm,
+                request.input_file.as_str(),
+                request.output_file.as_deref(),
+            )
+            .await;
+        match result {
+            Ok((summary, _, output_files)) => {
+                println!("✅ 任务完成: {id}");
+                task_store
+                    .mark_completed(id, summary, output_files.into_iter().next())
+                    .await;
+            }
+            Err(e) => {
+                println!("⚠️ 任务失败: {id} - {e}");
+                task_store.mark_failed(id, e.to_string()).await;
+            }
+        }
+    });
+
+    Ok(Json(serde_json::json!({ "job_id": id })))
+}
+
+/// 列出全部任务
+async fn list_jobs(State(store): State<JobStore>) -> Json<Vec<AnalysisJob>> {
+    Json(store.list().await)
+}
+
+/// 查询任务状态
+async fn job_status(
+    State(store): State<JobStore>,
+    UrlPath(id): UrlPath<Uuid>,
+) -> Result<Json<AnalysisJob>, ApiError> {
+    store.get(id).await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("任务不存在: {id}")))
+}
+
+/// 下载任务结果工作簿
+async fn job_result(
+    State(store): State<JobStore>,
+    UrlPath(id): UrlPath<Uuid>,
+) -> Result<Response, ApiError> {
+    let job = store.get(id).await
+        .ok_or_else(|| ApiError::not_found(format!("任务不存在: {id}")))?;
+    if job.status != JobStatus::Completed {
+        return Err(ApiError::bad_request(format!(
+            "任务尚未完成，当前状态: {}",
+            serde_json::to_string(&job.status).unwrap_or_default()
+        )));
+    }
+    let output_file = job.output_file
+        .ok_or_else(|| ApiError::internal("任务完成但未记录结果文件"))?;
+    let bytes = tokio::fs::read(&output_file).await
+        .map_err(|e| ApiError::internal(format!("读取结果文件失败: {e}")))?;
+    let file_name = Path::new(&output_file)
+        .file_name()
+        .map_or_else(|| "result.xlsx".to_string(), |name| name.to_string_lossy().into_owned());
+
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string(),
+        ),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        ),
+    ];
+    Ok((headers, bytes).into_response())
+}
+
+/// 时点查询（同步返回查询结果）
+async fn query_time_point(
+    Json(request): Json<TimePointQueryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut service = TimePointService::new(request.algorithm.clone())
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let result = service.query_time_point(request).await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    serde_json::to_value(result)
+        .map(Json)
+        .map_err(|e| ApiError::internal(format!("结果序列化失败: {e}")))
+}
+
+/// 资金池查询（同步返回查询结果）
+async fn query_fund_pool(
+    Json(request): Json<FundPoolQueryRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut service = TimePointService::new(request.algorithm.clone())
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+    let result = service.query_fund_pool(request).await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    serde_json::to_value(result)
+        .map(Json)
+        .map_err(|e| ApiError::internal(format!("结果序列化失败: {e}")))
+}
+
+/// 健康检查
+async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok", "version": crate::VERSION }))
+}
+
+/// 组装路由表（任务表作为共享状态注入）
+pub fn build_router(store: JobStore) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/api/analyze", post(submit_analysis))
+        .route("/api/jobs", get(list_jobs))
+        .route("/api/jobs/:id", get(job_status))
+        .route("/api/jobs/:id/result", get(job_result))
+        .route("/api/query/time-point", post(query_time_point))
+        .route("/api/query/fund-pool", post(query_fund_pool))
+        .with_state(store)
+}
+
+/// 启动HTTP服务（阻塞至进程退出）
+pub async fn serve(host: &str, port: u16) -> AuditResult<()> {
+    let addr: SocketAddr = format!("{host}:{port}").parse()
+        .map_err(|e| AuditError::config_error(format!("监听地址无效 {host}:{port}: {e}")))?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    println!("🚀 HTTP服务已启动: http://{addr}");
+    println!("📋 提交分析: POST /api/analyze，轮询: GET /api/jobs/{{id}}，下载: GET /api/jobs/{{id}}/result");
+    axum::serve(listener, build_router(JobStore::new())).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_store_lifecycle() {
+        let store = JobStore::new();
+        let id = store.submit("FIFO", "流水.xlsx").await;
+
+        let job = store.get(id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.algorithm, "FIFO");
+        assert!(job.finished_at.is_none());
+
+        store.mark_running(id).await;
+        assert_eq!(store.get(id).await.unwrap().status, JobStatus::Running);
+
+        store.mark_failed(id, "测试失败".to_string()).await;
+        let job = store.get(id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("测试失败"));
+        assert!(job.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_job_store_list_sorted_by_submission() {
+        let store = JobStore::new();
+        let first = store.submit("FIFO", "a.xlsx").await;
+        let second = store.submit("BALANCE_METHOD", "b.xlsx").await;
+
+        let jobs = store.list().await;
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].id, first);
+        assert_eq!(jobs[1].id, second);
+
+        // 不存在的任务号返回None
+        assert!(store.get(Uuid::new_v4()).await.is_none());
+    }
+
+    #[test]
+    fn test_analyze_request_defaults_to_fifo() {
+        let request: AnalyzeJobRequest =
+            serde_json::from_str(r#"{"input_file": "流水.xlsx"}"#).unwrap();
+        assert_eq!(request.algorithm, "FIFO");
+        assert!(request.output_file.is_none());
+    }
+}
@@ -3,16 +3,14 @@
 //! 提供时点查询和资金池查询功能
 //! 基于成功测试代码的实现模式
 
-use crate::utils::{ExcelProcessor, UnifiedValidator};
-use crate::algorithms::{FifoTracker, BalanceMethodTracker};
-use crate::data_models::{Config, Transaction};
+use crate::data_models::Transaction;
 use crate::errors::{AuditError, AuditResult};
-use std::time::Instant;
+use crate::optimizations::cache::{CacheMetrics, CachePolicy, CacheWeight, LruCache};
+use std::time::{Duration, Instant};
 use std::collections::HashMap;
 use std::fs;
 use log::{info, debug, error};
 use rust_decimal::Decimal;
-use serde::{Serialize, Deserialize};
 
 // 时点查询请求结构
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -36,6 +34,9 @@ pub struct TimePointQueryResult {
     // 新增完整查询数据字段
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_row_data: Option<FrontendTransaction>,
+    // 对应的原始解析行（修复前数值），便于比对工具做了哪些修改
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_row_data: Option<RawTransactionView>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tracker_state: Option<TrackerStateSnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -49,6 +50,10 @@ pub struct TimePointQueryResult {
     pub available_fund_pools: Option<Vec<FundPoolInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fund_pool_records: Option<std::collections::HashMap<String, Vec<serde_json::Value>>>,
+    /// 各资金池截断前的记录总数（内联记录超过[`DEFAULT_POOL_PAGE_LIMIT`]时
+    /// 只保留最靠近时点的末段，其余由分页接口获取）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fund_pool_record_counts: Option<std::collections::HashMap<String, usize>>,
 }
 
 // 追踪器状态快照
@@ -63,6 +68,25 @@ pub struct TrackerStateSnapshot {
     pub total_company_out: rust_decimal::Decimal,
     pub misappropriation_amount: rust_decimal::Decimal,
     pub advance_amount: rust_decimal::Decimal,
+    /// 差额计算法专有的内部状态（仅`BALANCE_METHOD`分析时有值）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance_method_state: Option<BalanceMethodStateSnapshot>,
+}
+
+// 差额计算法内部状态快照
+// 让GUI状态面板对两种算法都有意义，而不只展示FIFO视角的字段
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct BalanceMethodStateSnapshot {
+    /// 当前银行余额中归属个人的部分
+    pub attributed_personal_balance: rust_decimal::Decimal,
+    /// 当前银行余额中归属公司的部分
+    pub attributed_company_balance: rust_decimal::Decimal,
+    /// 目标行支出中从个人余额扣除的金额
+    pub last_deduction_personal: rust_decimal::Decimal,
+    /// 目标行支出中从公司余额扣除的金额
+    pub last_deduction_company: rust_decimal::Decimal,
+    /// 扣除策略说明
+    pub deduction_policy: String,
 }
 
 // 处理统计信息
@@ -102,6 +126,225 @@ pub struct FrontendTransaction {
     pub timestamp: String, // 前端期望的完整时间戳
 }
 
+// 原始解析行视图 - 修复/重排前的数据原貌
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct RawTransactionView {
+    /// 在原始文件解析顺序中的行号（1开始）
+    pub original_row_number: usize,
+    pub transaction_date: String,
+    pub transaction_time: String,
+    pub income_amount: rust_decimal::Decimal,
+    pub expense_amount: rust_decimal::Decimal,
+    pub balance: rust_decimal::Decimal,
+    pub fund_attribute: String,
+    /// 处理后的位置是否与原始位置不同（验证阶段重排导致）
+    pub position_changed: bool,
+}
+
+// 按源表行号查询的结果 - GUI"跳转到行"与CLI查询共用
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct SourceRowQueryResult {
+    /// 源Excel中的数据行号（1开始，不含表头）
+    pub original_row_number: usize,
+    /// 处理后结果中的行号（1开始）
+    pub processed_row_number: usize,
+    /// 位置是否因验证阶段重排而变化
+    pub position_changed: bool,
+    /// 处理后的交易数据（前端兼容格式）
+    pub transaction: FrontendTransaction,
+}
+
+// 按时刻的时点查询请求 - "截至某时刻的状态"
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct TimePointAtQueryRequest {
+    pub file_path: String,
+    pub algorithm: String,
+    /// 截止时刻，"YYYY-MM-DD HH:MM:SS"；只给日期时按当日23:59:59处理
+    pub as_of: String,
+}
+
+// 批量时点查询请求 - 一次扫描返回多行的状态快照
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct BatchTimePointQueryRequest {
+    pub file_path: String,
+    pub algorithm: String,
+    /// 要查询的处理结果行号列表（1开始，重复行号自动去重）
+    pub row_numbers: Vec<usize>,
+}
+
+// 批量时点查询中单行的快照
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct TimePointSnapshot {
+    /// 处理结果中的行号（1开始）
+    pub row_number: usize,
+    /// 该行交易数据（前端兼容格式）
+    pub transaction: FrontendTransaction,
+    /// 该行时点的追踪器状态
+    pub tracker_state: TrackerStateSnapshot,
+}
+
+// 批量时点查询结果
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct BatchTimePointQueryResult {
+    pub success: bool,
+    pub algorithm: String,
+    pub total_rows: usize,
+    pub processing_time: f64,
+    pub query_time: String,
+    /// 各查询行的快照，按行号升序排列
+    pub snapshots: Vec<TimePointSnapshot>,
+    /// 越界而被跳过的行号
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub invalid_rows: Vec<usize>,
+}
+
+// 按期汇总查询请求 - 按日/按月的余额与缺口走势
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct PeriodSummaryQueryRequest {
+    pub file_path: String,
+    pub algorithm: String,
+}
+
+// 时间线查询请求 - 统计面板画走势图用
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct TimelineQueryRequest {
+    pub file_path: String,
+    pub algorithm: String,
+    /// 按日采样（默认逐笔采样）
+    #[serde(default)]
+    pub per_day: bool,
+}
+
+// 跨分析搜索条件 - 各条件均可选，给出的条件按"与"关系组合
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct AnalysisSearchQuery {
+    /// 精确匹配收入或支出金额
+    #[serde(default)]
+    pub amount: Option<rust_decimal::Decimal>,
+    /// 起始日期（含当日）
+    #[serde(default)]
+    pub date_from: Option<chrono::NaiveDate>,
+    /// 截止日期（含当日）
+    #[serde(default)]
+    pub date_to: Option<chrono::NaiveDate>,
+    /// 资金属性子串（对方户名/用途通常记录在该列）
+    #[serde(default)]
+    pub fund_attribute: Option<String>,
+    /// 资金属性层级路径（如"公司/子公司A"，命中该层级及其下所有叶子）
+    #[serde(default)]
+    pub fund_attribute_level: Option<String>,
+    /// 层级分隔符（不给出时用默认"/"）
+    #[serde(default)]
+    pub hierarchy_separator: Option<String>,
+    /// 行为性质子串（如"挪用"、"垫付"）
+    #[serde(default)]
+    pub behavior: Option<String>,
+}
+
+impl AnalysisSearchQuery {
+    /// 是否未给出任何搜索条件
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.amount.is_none()
+            && self.date_from.is_none()
+            && self.date_to.is_none()
+            && self.fund_attribute.is_none()
+            && self.fund_attribute_level.is_none()
+            && self.behavior.is_none()
+    }
+
+    /// 单条交易是否命中全部已给出的条件
+    fn matches(&self, transaction: &Transaction) -> bool {
+        if let Some(amount) = self.amount {
+            if transaction.income_amount != amount && transaction.expense_amount != amount {
+                return false;
+            }
+        }
+        let date = transaction.transaction_date.date();
+        if self.date_from.is_some_and(|from| date < from) {
+            return false;
+        }
+        if self.date_to.is_some_and(|to| date > to) {
+            return false;
+        }
+        if let Some(keyword) = &self.fund_attribute {
+            if !transaction.fund_attribute.contains(keyword.as_str()) {
+                return false;
+            }
+        }
+        // 层级条件按路径边界匹配，"公司"不会误中"子公司A"
+        if let Some(level_path) = &self.fund_attribute_level {
+            let hierarchy = match &self.hierarchy_separator {
+                Some(separator) => crate::utils::AttributeHierarchy::new(separator.clone()),
+                None => crate::utils::AttributeHierarchy::default(),
+            };
+            if !hierarchy.rolls_up_to(&transaction.fund_attribute, level_path) {
+                return false;
+            }
+        }
+        if let Some(keyword) = &self.behavior {
+            let behavior = transaction.behavior_nature.as_deref().unwrap_or("");
+            if !behavior.contains(keyword.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// 跨分析搜索命中结果 - (文件, 行, 摘要)三元组
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct AnalysisSearchHit {
+    /// 命中交易所在的源文件路径
+    pub file_path: String,
+    /// 该缓存分析使用的算法
+    pub algorithm: String,
+    /// 处理结果中的行号（1开始）
+    pub row_number: usize,
+    /// 单行摘要，便于快速确认是否为要找的那笔交易
+    pub snippet: String,
+}
+
+/// 时点结果中每个资金池内联返回的最大记录数
+///
+/// 活跃理财产品可能有数万条申购/赎回记录，全部塞进一次时点查询
+/// 载荷会冻结GUI；超出部分由分页接口按需获取
+pub const DEFAULT_POOL_PAGE_LIMIT: usize = 200;
+
+fn default_pool_page_limit() -> usize {
+    DEFAULT_POOL_PAGE_LIMIT
+}
+
+// 资金池记录分页请求
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct PoolRecordsPageRequest {
+    pub file_path: String,
+    pub row_number: usize,
+    pub algorithm: String,
+    pub pool_name: String,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_pool_page_limit")]
+    pub limit: usize,
+    /// 服务端日期过滤起点（含当日，"YYYY-MM-DD"）
+    #[serde(default)]
+    pub date_from: Option<String>,
+    /// 服务端日期过滤终点（含当日，"YYYY-MM-DD"）
+    #[serde(default)]
+    pub date_to: Option<String>,
+}
+
+// 资金池记录分页结果
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct PoolRecordsPage {
+    pub pool_name: String,
+    /// 日期过滤后的记录总数（供前端计算页数）
+    pub total_records: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub records: Vec<serde_json::Value>,
+}
+
 // 资金池信息结构
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct FundPoolInfo {
@@ -124,101 +367,197 @@ pub struct FundPoolQueryResult {
     pub pool_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// 截至查询时点的记录总数（内联记录超过[`DEFAULT_POOL_PAGE_LIMIT`]时只保留末段）
+    #[serde(default)]
+    pub total_records: usize,
+    /// 截至查询时点的记录历史（前端格式，截断规则与时点查询一致）
+    #[serde(default)]
+    pub records: Vec<serde_json::Value>,
+    /// 截至查询时点的资金池统计（申购/赎回/盈亏口径见[`PoolStatistics`]）
+    ///
+    /// [`PoolStatistics`]: crate::data_models::PoolStatistics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<crate::data_models::PoolStatistics>,
+    /// 查询时点的个人余额（最后一条截断内记录）
+    #[serde(default)]
+    pub personal_balance: rust_decimal::Decimal,
+    /// 查询时点的公司余额
+    #[serde(default)]
+    pub company_balance: rust_decimal::Decimal,
+}
+
+/// 追踪器状态检查点的行间隔
+pub const TRACKER_CHECKPOINT_INTERVAL: usize = 10_000;
+
+/// 追踪器状态检查点
+///
+/// 主跑结束后每隔固定行数序列化一份追踪器状态，随缓存保存。
+/// 各算法已把累计量写回逐行交易，单行状态恢复本身是O(1)；
+/// 检查点的价值在于把时点查询的逐行重放压缩到最近检查点之后的增量区间
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct TrackerCheckpoint {
+    /// 检查点所在行号（1-based）
+    pub row_number: usize,
+    /// 该行处理完成后的追踪器状态快照
+    pub state: TrackerStateSnapshot,
 }
 
 /// 文件缓存信息
 #[derive(Debug, Clone)]
-struct FileCacheData {
+pub struct FileCacheData {
     pub fingerprint: String,
     pub processed_transactions: Vec<Transaction>,
+    /// 原始解析数据（未经验证修复），用于原始/处理后对比展示
+    pub raw_transactions: Vec<Transaction>,
     pub audit_summary: crate::data_models::AuditSummary,
     pub offsite_pool_records: crate::data_models::OffsitePoolRecordManager,
     pub algorithm: String,
     pub cached_at: std::time::SystemTime,
+    /// 固定间隔的追踪器状态检查点（按行号升序）
+    pub checkpoints: Vec<TrackerCheckpoint>,
+}
+
+impl FileCacheData {
+    /// 查找目标行及之前最近的检查点
+    #[must_use]
+    pub fn nearest_checkpoint(&self, target_row: usize) -> Option<&TrackerCheckpoint> {
+        let idx = self.checkpoints.partition_point(|cp| cp.row_number <= target_row);
+        idx.checked_sub(1).map(|i| &self.checkpoints[i])
+    }
+}
+
+impl CacheWeight for FileCacheData {
+    /// 估算内存占用：交易向量是大头，检查点次之，其余字段忽略
+    fn weight_bytes(&self) -> usize {
+        // 每条交易除结构体本身外还有若干String字段的堆分配，按典型值加成
+        const PER_TRANSACTION: usize = std::mem::size_of::<Transaction>() + 128;
+        const PER_CHECKPOINT: usize = 4 * 1024;
+        (self.processed_transactions.len() + self.raw_transactions.len()) * PER_TRANSACTION
+            + self.checkpoints.len() * PER_CHECKPOINT
+    }
 }
 
 /// 文件缓存管理器
+///
+/// 基于[`LruCache`]：条目数、1小时TTL与估算内存占用三条上限，
+/// 淘汰从旧实现的全表扫描降为均摊O(1)
 pub struct FileCache {
-    cache: HashMap<String, FileCacheData>,
-    max_cache_size: usize,
+    cache: LruCache<String, FileCacheData>,
+}
+
+impl Default for FileCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FileCache {
+    #[must_use]
     pub fn new() -> Self {
         Self {
-            cache: HashMap::new(),
-            max_cache_size: 10, // 最多缓存10个文件的处理结果
+            cache: LruCache::new(CachePolicy {
+                max_entries: 10, // 最多缓存10个文件的处理结果
+                ttl: Duration::from_hours(1), // 1小时过期，与旧实现一致
+                max_memory_bytes: 512 * 1024 * 1024, // 大文件的处理结果按估算内存先于条目数上限淘汰
+            }),
         }
     }
-    
+
     /// 生成文件指纹用于缓存键
     pub fn generate_fingerprint(&self, file_path: &str, algorithm: &str) -> AuditResult<String> {
         let metadata = fs::metadata(file_path)
-            .map_err(|e| AuditError::validation_error(&format!("无法读取文件元数据 {}: {}", file_path, e)))?;
+            .map_err(|e| AuditError::validation_error(format!("无法读取文件元数据 {file_path}: {e}")))?;
             
         let modified_time = metadata.modified()
-            .map_err(|e| AuditError::validation_error(&format!("无法获取文件修改时间: {}", e)))?
+            .map_err(|e| AuditError::validation_error(format!("无法获取文件修改时间: {e}")))?
             .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| AuditError::validation_error(&format!("时间转换错误: {}", e)))?
+            .map_err(|e| AuditError::validation_error(format!("时间转换错误: {e}")))?
             .as_secs();
             
         let size = metadata.len();
         
         // 组合文件路径、算法、修改时间、大小生成唯一指纹
-        Ok(format!("{}|{}|{}|{}", file_path, algorithm, modified_time, size))
+        Ok(format!("{file_path}|{algorithm}|{modified_time}|{size}"))
     }
     
     /// 检查缓存是否存在且有效
+    #[must_use]
     pub fn has_valid_cache(&self, fingerprint: &str) -> bool {
-        if let Some(cache_data) = self.cache.get(fingerprint) {
-            // 检查缓存是否过期（1小时过期）
-            let cache_age = std::time::SystemTime::now()
-                .duration_since(cache_data.cached_at)
-                .unwrap_or_default()
-                .as_secs();
-                
-            cache_age < 3600 // 1小时 = 3600秒
-        } else {
-            false
-        }
+        self.cache.peek(fingerprint).is_some()
     }
-    
-    /// 获取缓存数据
+
+    /// 获取缓存数据（只读路径使用，不计入命中统计也不提升热度）
+    #[must_use]
     pub fn get_cache(&self, fingerprint: &str) -> Option<&FileCacheData> {
+        self.cache.peek(fingerprint)
+    }
+
+    /// 获取缓存数据并计入命中统计、提升LRU热度（主查询路径使用）
+    pub fn lookup(&mut self, fingerprint: &str) -> Option<&FileCacheData> {
         self.cache.get(fingerprint)
     }
-    
+
     /// 存储缓存数据
     pub fn set_cache(&mut self, fingerprint: String, cache_data: FileCacheData) {
-        // 如果缓存已满，移除最老的条目
-        if self.cache.len() >= self.max_cache_size {
-            if let Some(oldest_key) = self.cache.keys()
-                .min_by_key(|k| self.cache.get(*k).map(|d| d.cached_at).unwrap_or(std::time::UNIX_EPOCH))
-                .cloned() 
-            {
-                self.cache.remove(&oldest_key);
-                info!("缓存已满，移除最老缓存: {}", oldest_key);
-            }
+        for evicted in self.cache.insert(fingerprint.clone(), cache_data) {
+            info!("缓存超限，淘汰最久未使用: {evicted}");
         }
-        
-        self.cache.insert(fingerprint.clone(), cache_data);
-        info!("文件处理结果已缓存: {}", fingerprint);
+        info!("文件处理结果已缓存: {fingerprint}");
     }
-    
+
     /// 清理过期缓存
     pub fn cleanup_expired(&mut self) {
-        let now = std::time::SystemTime::now();
-        let expired_keys: Vec<String> = self.cache.iter()
-            .filter(|(_, data)| {
-                now.duration_since(data.cached_at).unwrap_or_default().as_secs() >= 3600
+        for key in self.cache.cleanup_expired() {
+            info!("清理过期缓存: {key}");
+        }
+    }
+
+    /// 缓存运行指标快照
+    #[must_use]
+    pub fn metrics(&self) -> CacheMetrics {
+        self.cache.metrics()
+    }
+
+    /// 在全部有效缓存条目中搜索交易
+    ///
+    /// 调查人员往往记得某笔金额或对方名称，却想不起在哪个流水文件里。
+    /// 这里跨工作区所有已缓存的分析结果查找，返回(文件, 行, 摘要)命中列表，
+    /// 已过期的缓存条目不参与搜索
+    #[must_use]
+    pub fn search(&self, query: &AnalysisSearchQuery) -> Vec<AnalysisSearchHit> {
+        let mut hits: Vec<AnalysisSearchHit> = self.cache.values_valid()
+            .flat_map(|data| {
+                // 指纹格式为"路径|算法|修改时间|大小"，取路径段作为命中文件
+                let file_path = data.fingerprint.split('|').next()
+                    .unwrap_or(data.fingerprint.as_str())
+                    .to_string();
+                data.processed_transactions.iter()
+                    .enumerate()
+                    .filter(|(_, transaction)| query.matches(transaction))
+                    .map(move |(index, transaction)| AnalysisSearchHit {
+                        file_path: file_path.clone(),
+                        algorithm: data.algorithm.clone(),
+                        row_number: index + 1,
+                        snippet: format!(
+                            "{} 收入{} 支出{} 余额{} {} {}",
+                            transaction.transaction_date.format("%Y-%m-%d %H:%M:%S"),
+                            transaction.income_amount,
+                            transaction.expense_amount,
+                            transaction.balance,
+                            transaction.fund_attribute,
+                            transaction.behavior_nature.as_deref().unwrap_or("-"),
+                        ),
+                    })
+                    .collect::<Vec<_>>()
             })
-            .map(|(k, _)| k.clone())
             .collect();
-            
-        for key in expired_keys {
-            self.cache.remove(&key);
-            info!("清理过期缓存: {}", key);
-        }
+
+        // HashMap遍历顺序不稳定，按(文件, 算法, 行号)排序保证结果可复现
+        hits.sort_by(|a, b| {
+            (&a.file_path, &a.algorithm, a.row_number)
+                .cmp(&(&b.file_path, &b.algorithm, b.row_number))
+        });
+        hits
     }
 }
 
@@ -229,14 +568,20 @@ pub struct TimePointService {
 
 impl TimePointService {
     pub fn new(algorithm: String) -> Result<Self, crate::errors::AuditError> {
-        Ok(Self { 
+        Ok(Self {
             algorithm,
             file_cache: FileCache::new(),
         })
     }
+
+    /// 文件缓存运行指标（供前端诊断面板展示）
+    #[must_use]
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        self.file_cache.metrics()
+    }
+
     
-    
-    /// 将Transaction转换为前端兼容的FrontendTransaction
+    /// `将Transaction转换为前端兼容的FrontendTransaction`
     fn convert_to_frontend_transaction(&self, transaction: &Transaction) -> FrontendTransaction {
         FrontendTransaction {
             transaction_date: transaction.transaction_date.format("%Y-%m-%d").to_string(),
@@ -270,69 +615,115 @@ impl TimePointService {
         
         // 清理过期缓存
         self.file_cache.cleanup_expired();
-        
+
         // 生成文件指纹
         let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
-        
+
         // 获取或创建缓存数据
-        let cache_data = if self.file_cache.has_valid_cache(&fingerprint) {
+        let cache_data = match self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await {
+            Ok(cache_data) => cache_data,
+            Err(e) => {
+                error!("算法处理失败: {e}");
+                return Ok(TimePointQueryResult {
+                    success: false,
+                    algorithm: request.algorithm,
+                    target_row: request.row_number,
+                    total_rows: 0,
+                    processing_time: start_time.elapsed().as_secs_f64(),
+                    query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    message: Some(format!("算法处理失败: {e}")),
+                    target_row_data: None,
+                    original_row_data: None,
+                    tracker_state: None,
+                    processing_stats: None,
+                    recent_steps: None,
+                    errors: Some(vec![e.to_string()]),
+                    available_fund_pools: Some(vec![]),
+                    fund_pool_records: Some(std::collections::HashMap::new()),
+                    fund_pool_record_counts: None,
+                });
+            }
+        };
+
+        // 使用缓存数据进行时点查询
+        self.query_from_cached_data(&request, &cache_data, start_time)
+    }
+
+    /// 获取或构建文件的缓存分析数据
+    ///
+    /// 缓存未命中时执行一次完整算法分析并缓存结果；
+    /// 时点查询与资金池查询共用该路径，避免重复跑算法
+    async fn ensure_cached_data(
+        &mut self,
+        fingerprint: &str,
+        file_path: &str,
+        algorithm: &str,
+    ) -> AuditResult<FileCacheData> {
+        if let Some(cache_data) = self.file_cache.lookup(fingerprint) {
             info!("使用缓存数据进行查询");
-            self.file_cache.get_cache(&fingerprint).unwrap().clone()
-        } else {
-            info!("缓存未命中，执行完整算法处理");
-            
-            // 使用审计服务完整算法处理流程
-            use crate::services::AuditService;
-            let audit_service = AuditService::new();
-            
-            let (audit_summary, processed_transactions, _log_messages) = match audit_service.analyze_financial_data(&request.algorithm, &request.file_path, None::<&String>).await {
-                Ok((summary, transactions, log_messages)) => (summary, transactions, log_messages),
-                Err(e) => {
-                    error!("算法处理失败: {}", e);
-                    return Ok(TimePointQueryResult {
-                        success: false,
-                        algorithm: request.algorithm,
-                        target_row: request.row_number,
-                        total_rows: 0,
-                        processing_time: start_time.elapsed().as_secs_f64(),
-                        query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                        message: Some(format!("算法处理失败: {}", e)),
-                        target_row_data: None,
-                        tracker_state: None,
-                        processing_stats: None,
-                        recent_steps: None,
-                        errors: Some(vec![e.to_string()]),
-                        available_fund_pools: Some(vec![]),
-                        fund_pool_records: Some(std::collections::HashMap::new()),
-                    });
-                }
-            };
-            
-            let offsite_pool_records = audit_service.get_offsite_pool_records().clone();
-            
-            // 创建缓存数据
+            return Ok(cache_data.clone());
+        }
+        // 结果工作簿直接读回已计算的结果，不重跑算法
+        if crate::utils::ExcelProcessor::is_result_workbook(file_path) {
+            info!("📥 检测到结果工作簿，直接读回已计算结果: {file_path}");
+            let processor = crate::utils::ExcelProcessor::new(crate::data_models::Config::new());
+            let (processed_transactions, audit_summary) =
+                processor.read_analysis_results(file_path)?;
+            let checkpoints = Self::build_checkpoints(&processed_transactions, &audit_summary, algorithm);
+
             let cache_data = FileCacheData {
-                fingerprint: fingerprint.clone(),
-                processed_transactions: processed_transactions.clone(),
-                audit_summary: audit_summary.clone(),
-                offsite_pool_records: offsite_pool_records.clone(),
-                algorithm: request.algorithm.clone(),
+                fingerprint: fingerprint.to_string(),
+                processed_transactions,
+                // 结果工作簿不含验证前的原始数据与场外资金池明细
+                raw_transactions: Vec::new(),
+                audit_summary,
+                offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+                algorithm: algorithm.to_string(),
                 cached_at: std::time::SystemTime::now(),
+                checkpoints,
             };
-            
-            // 存储到缓存
-            self.file_cache.set_cache(fingerprint, cache_data.clone());
-            cache_data
+            self.file_cache.set_cache(fingerprint.to_string(), cache_data.clone());
+            return Ok(cache_data);
+        }
+
+        info!("缓存未命中，执行完整算法处理");
+
+        // 使用审计服务完整算法处理流程
+        use crate::services::AuditService;
+        let audit_service = AuditService::new();
+
+        let (audit_summary, processed_transactions, _log_messages) =
+            audit_service.analyze_financial_data(algorithm, file_path, None::<&str>).await?;
+
+        let offsite_pool_records = audit_service.get_offsite_pool_records().await;
+
+        // 读取原始解析数据（未经验证修复），用于原始/处理后对比
+        let raw_transactions = Self::read_raw_transactions(file_path);
+
+        // 主跑完成后按固定间隔序列化追踪器状态检查点
+        let checkpoints = Self::build_checkpoints(&processed_transactions, &audit_summary, algorithm);
+
+        // 创建缓存数据
+        let cache_data = FileCacheData {
+            fingerprint: fingerprint.to_string(),
+            processed_transactions,
+            raw_transactions,
+            audit_summary,
+            offsite_pool_records,
+            algorithm: algorithm.to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints,
         };
-        
-        // 使用缓存数据进行时点查询
-        self.query_from_cached_data(&request, &cache_data, start_time)
+
+        // 存储到缓存
+        self.file_cache.set_cache(fingerprint.to_string(), cache_data.clone());
+        Ok(cache_data)
     }
-    
+
     /// 从缓存数据执行时点查询
     fn query_from_cached_data(&self, request: &TimePointQueryRequest, cache_data: &FileCacheData, start_time: Instant) -> Result<TimePointQueryResult, crate::errors::AuditError> {
         let total_rows = cache_data.processed_transactions.len();
-        debug!("使用缓存数据，共{}条交易记录", total_rows);
+        debug!("使用缓存数据，共{total_rows}条交易记录");
         
         // 验证目标行号
         if request.row_number == 0 || request.row_number > total_rows {
@@ -345,21 +736,24 @@ impl TimePointService {
                 query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
                 message: Some(format!("行号{}无效，有效范围: 1-{}", request.row_number, total_rows)),
                 target_row_data: None,
+                original_row_data: None,
                 tracker_state: None,
                 processing_stats: None,
                 recent_steps: None,
                 errors: Some(vec!["行号超出范围".to_string()]),
                 available_fund_pools: Some(vec![]),
                 fund_pool_records: Some(std::collections::HashMap::new()),
+                fund_pool_record_counts: None,
             });
         }
         
         // 基于缓存数据进行时点分析（不生成文件）
-        let (tracker_state, target_row_data, recent_steps, fund_pools, fund_records) = 
-            self.process_with_cached_data(&cache_data.processed_transactions, request.row_number, &cache_data.audit_summary, &cache_data.offsite_pool_records)?;
+        let (tracker_state, target_row_data, recent_steps, fund_pools, mut fund_records) = 
+            self.process_with_cached_data(&cache_data.processed_transactions, request.row_number, &cache_data.audit_summary, &cache_data.offsite_pool_records, &cache_data.algorithm, &cache_data.checkpoints)?;
+        let fund_pool_record_counts = Self::truncate_fund_records(&mut fund_records);
         
         let total_time = start_time.elapsed().as_secs_f64();
-        info!("缓存时点查询完成，总耗时{:.3}秒", total_time);
+        info!("缓存时点查询完成，总耗时{total_time:.3}秒");
         
         // 返回查询结果
         Ok(TimePointQueryResult {
@@ -371,6 +765,11 @@ impl TimePointService {
             query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             message: Some(format!("时点查询成功（使用缓存），处理到第{}行", request.row_number)),
             target_row_data: Some(target_row_data),
+            original_row_data: Self::find_original_row(
+                &cache_data.raw_transactions,
+                &cache_data.processed_transactions[request.row_number - 1],
+                request.row_number,
+            ),
             tracker_state: Some(tracker_state),
             processing_stats: Some(ProcessingStats {
                 last_processed_row: request.row_number,
@@ -383,9 +782,188 @@ impl TimePointService {
             errors: None,
             available_fund_pools: Some(fund_pools),
             fund_pool_records: Some(fund_records),
+            fund_pool_record_counts: Some(fund_pool_record_counts),
         })
     }
 
+    /// 按源Excel行号获取处理后的交易结果
+    /// 
+    /// 用户在源工作簿里看到的是解析顺序的原始行号，而验证阶段可能重排记录。
+    /// 这里基于缓存中保留的原始解析顺序反查该行在处理结果中的位置，
+    /// 缓存未命中时返回错误（调用方应先执行一次完整分析或时点查询）
+    pub fn get_transaction_by_source_row(
+        &self,
+        fingerprint: &str,
+        algorithm: &str,
+        excel_row: usize,
+    ) -> Result<SourceRowQueryResult, crate::errors::AuditError> {
+        let cache_data = self.file_cache.get_cache(fingerprint)
+            .ok_or_else(|| AuditError::validation_error(
+                format!("缓存未命中: {fingerprint}，请先执行一次时点查询")
+            ))?;
+        
+        if !cache_data.algorithm.eq_ignore_ascii_case(algorithm) {
+            return Err(AuditError::validation_error(
+                format!("缓存算法不匹配: 缓存为{}，请求为{algorithm}", cache_data.algorithm)
+            ));
+        }
+        
+        let raw_transactions = &cache_data.raw_transactions;
+        if excel_row == 0 || excel_row > raw_transactions.len() {
+            return Err(AuditError::validation_error(
+                format!("行号{}无效，有效范围: 1-{}", excel_row, raw_transactions.len())
+            ));
+        }
+        
+        let raw = &raw_transactions[excel_row - 1];
+        let matches = |processed: &Transaction| {
+            processed.transaction_date == raw.transaction_date
+                && processed.income_amount == raw.income_amount
+                && processed.expense_amount == raw.expense_amount
+                && processed.balance == raw.balance
+                && processed.fund_attribute == raw.fund_attribute
+        };
+        
+        // 优先检查同一位置（绝大多数行未被重排）
+        let index = if cache_data.processed_transactions.get(excel_row - 1).is_some_and(matches) {
+            Some(excel_row - 1)
+        } else {
+            cache_data.processed_transactions.iter().position(matches)
+        };
+        
+        let index = index.ok_or_else(|| AuditError::validation_error(
+            format!("源表第{excel_row}行在处理结果中未找到对应记录")
+        ))?;
+        
+        Ok(SourceRowQueryResult {
+            original_row_number: excel_row,
+            processed_row_number: index + 1,
+            position_changed: index + 1 != excel_row,
+            transaction: self.convert_to_frontend_transaction(&cache_data.processed_transactions[index]),
+        })
+    }
+    
+    /// 跨缓存分析搜索交易（工作区级）
+    ///
+    /// 对本服务缓存的所有文件×算法分析结果执行[`FileCache::search`]。
+    /// 空条件直接报错，避免把全部缓存内容当成命中结果返回
+    pub fn search_cached_analyses(
+        &self,
+        query: &AnalysisSearchQuery,
+    ) -> AuditResult<Vec<AnalysisSearchHit>> {
+        if query.is_empty() {
+            return Err(AuditError::validation_error(
+                "至少需要一个搜索条件（金额/日期/资金属性/行为）".to_string()
+            ));
+        }
+        Ok(self.file_cache.search(query))
+    }
+
+    /// 截断各资金池内联记录到[`DEFAULT_POOL_PAGE_LIMIT`]，返回截断前的总数
+    ///
+    /// 记录按时间升序排列，保留最靠近查询时点的末段；
+    /// 更早的记录由[`Self::get_pool_records_page`]按需分页获取
+    fn truncate_fund_records(
+        fund_records: &mut HashMap<String, Vec<serde_json::Value>>,
+    ) -> HashMap<String, usize> {
+        let counts = fund_records.iter()
+            .map(|(pool_name, records)| (pool_name.clone(), records.len()))
+            .collect();
+        for (pool_name, records) in fund_records.iter_mut() {
+            if records.len() > DEFAULT_POOL_PAGE_LIMIT {
+                let excess = records.len() - DEFAULT_POOL_PAGE_LIMIT;
+                records.drain(..excess);
+                debug!("资金池{pool_name}记录过多，内联仅保留最近{DEFAULT_POOL_PAGE_LIMIT}条");
+            }
+        }
+        counts
+    }
+
+    /// 分页获取指定资金池截至时点的记录
+    ///
+    /// 依赖缓存命中（调用方应先执行一次时点查询）。
+    /// 日期过滤在服务端完成，避免把数万条记录传给前端再筛
+    pub fn get_pool_records_page(
+        &self,
+        fingerprint: &str,
+        request: &PoolRecordsPageRequest,
+    ) -> AuditResult<PoolRecordsPage> {
+        let cache_data = self.file_cache.get_cache(fingerprint)
+            .ok_or_else(|| AuditError::validation_error(
+                format!("缓存未命中: {fingerprint}，请先执行一次时点查询")
+            ))?;
+
+        if !cache_data.algorithm.eq_ignore_ascii_case(&request.algorithm) {
+            return Err(AuditError::validation_error(
+                format!("缓存算法不匹配: 缓存为{}，请求为{}", cache_data.algorithm, request.algorithm)
+            ));
+        }
+        if request.row_number == 0 || request.row_number > cache_data.processed_transactions.len() {
+            return Err(AuditError::validation_error(
+                format!("行号{}无效，有效范围: 1-{}", request.row_number, cache_data.processed_transactions.len())
+            ));
+        }
+
+        let (_, _, _, _, fund_records) = self.process_with_cached_data(
+            &cache_data.processed_transactions,
+            request.row_number,
+            &cache_data.audit_summary,
+            &cache_data.offsite_pool_records,
+            &cache_data.algorithm,
+            &cache_data.checkpoints,
+        )?;
+
+        let records = fund_records.get(&request.pool_name)
+            .ok_or_else(|| AuditError::validation_error(
+                format!("资金池不存在: {}", request.pool_name)
+            ))?;
+
+        Ok(Self::slice_pool_records(&request.pool_name, records, request))
+    }
+
+    /// 对单个资金池的记录做日期过滤与offset/limit切片
+    fn slice_pool_records(
+        pool_name: &str,
+        records: &[serde_json::Value],
+        request: &PoolRecordsPageRequest,
+    ) -> PoolRecordsPage {
+        // "交易时间"为"YYYY-MM-DD HH:MM:SS"，取日期段按字典序比较即可
+        let record_date = |record: &serde_json::Value| -> Option<String> {
+            record.get("交易时间")
+                .and_then(serde_json::Value::as_str)
+                .and_then(|time| time.get(..10))
+                .map(str::to_string)
+        };
+        let filtered: Vec<&serde_json::Value> = records.iter()
+            .filter(|record| {
+                let Some(date) = record_date(record) else { return false };
+                if request.date_from.as_deref().is_some_and(|from| date.as_str() < from) {
+                    return false;
+                }
+                if request.date_to.as_deref().is_some_and(|to| date.as_str() > to) {
+                    return false;
+                }
+                true
+            })
+            .collect();
+
+        // limit为0按默认页大小处理，避免前端笔误拿到空页
+        let limit = if request.limit == 0 { DEFAULT_POOL_PAGE_LIMIT } else { request.limit };
+        let page: Vec<serde_json::Value> = filtered.iter()
+            .skip(request.offset)
+            .take(limit)
+            .map(|record| (*record).clone())
+            .collect();
+
+        PoolRecordsPage {
+            pool_name: pool_name.to_string(),
+            total_records: filtered.len(),
+            offset: request.offset,
+            limit,
+            records: page,
+        }
+    }
+
     /// 完整的时点查询实现（保留原有方法作为备用）
     /// 使用审计服务的完整算法处理流程，确保获取准确的分析数据
     pub async fn query_time_point(&mut self, request: TimePointQueryRequest) -> Result<TimePointQueryResult, crate::errors::AuditError> {
@@ -401,7 +979,7 @@ impl TimePointService {
         let (summary, processed_transactions, _log_messages) = match audit_service.analyze_financial_data(&request.algorithm, &request.file_path, None::<&String>).await {
             Ok((summary, transactions, log_messages)) => (summary, transactions, log_messages),
             Err(e) => {
-                error!("算法处理失败: {}", e);
+                error!("算法处理失败: {e}");
                 return Ok(TimePointQueryResult {
                     success: false,
                     algorithm: request.algorithm,
@@ -409,23 +987,25 @@ impl TimePointService {
                     total_rows: 0,
                     processing_time: start_time.elapsed().as_secs_f64(),
                     query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                    message: Some(format!("算法处理失败: {}", e)),
+                    message: Some(format!("算法处理失败: {e}")),
                     target_row_data: None,
+                    original_row_data: None,
                     tracker_state: None,
                     processing_stats: None,
                     recent_steps: None,
                     errors: Some(vec![e.to_string()]),
                     available_fund_pools: Some(vec![]),
                     fund_pool_records: Some(std::collections::HashMap::new()),
+                    fund_pool_record_counts: None,
                 });
             }
         };
         
         // 获取场外资金池记录管理器
-        let offsite_pool_records = audit_service.get_offsite_pool_records();
+        let offsite_pool_records = audit_service.get_offsite_pool_records().await;
         
         let total_rows = processed_transactions.len();
-        debug!("通过算法处理获得{}条交易记录", total_rows);
+        debug!("通过算法处理获得{total_rows}条交易记录");
         
         // 验证目标行号
         if request.row_number == 0 || request.row_number > total_rows {
@@ -438,12 +1018,14 @@ impl TimePointService {
                 query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
                 message: Some(format!("行号{}无效，有效范围: 1-{}", request.row_number, total_rows)),
                 target_row_data: None,
+                original_row_data: None,
                 tracker_state: None,
                 processing_stats: None,
                 recent_steps: None,
                 errors: Some(vec!["行号超出范围".to_string()]),
                 available_fund_pools: Some(vec![]),
                 fund_pool_records: Some(std::collections::HashMap::new()),
+                fund_pool_record_counts: None,
             });
         }
         
@@ -452,12 +1034,10 @@ impl TimePointService {
         
         // 第三步：基于算法处理后的数据进行时点查询分析
         let algorithm_start = Instant::now();
-        let (tracker_state, target_row_data, recent_steps, fund_pools, fund_records) = match request.algorithm.to_uppercase().as_str() {
-            "FIFO" => {
-                self.process_with_processed_data(&processed_transactions, request.row_number, &summary, &offsite_pool_records)?
-            },
-            "BALANCE_METHOD" => {
-                self.process_with_processed_data(&processed_transactions, request.row_number, &summary, &offsite_pool_records)?
+        let checkpoints = Self::build_checkpoints(&processed_transactions, &summary, &request.algorithm);
+        let (tracker_state, target_row_data, recent_steps, fund_pools, mut fund_records) = match request.algorithm.to_uppercase().as_str() {
+            "FIFO" | "BALANCE_METHOD" | "PROPORTIONAL" => {
+                self.process_with_processed_data(&processed_transactions, request.row_number, &summary, &offsite_pool_records, &request.algorithm, &checkpoints)?
             },
             _ => {
                 let algorithm_name = request.algorithm.clone();
@@ -468,22 +1048,34 @@ impl TimePointService {
                     total_rows,
                     processing_time: start_time.elapsed().as_secs_f64(),
                     query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                    message: Some(format!("不支持的算法类型: {}", algorithm_name)),
+                    message: Some(format!("不支持的算法类型: {algorithm_name}")),
                     target_row_data: None,
+                    original_row_data: None,
                     tracker_state: None,
                     processing_stats: None,
                     recent_steps: None,
                     errors: Some(vec![format!("不支持的算法: {}", algorithm_name)]),
                     available_fund_pools: Some(vec![]),
                     fund_pool_records: Some(std::collections::HashMap::new()),
+                    fund_pool_record_counts: None,
                 });
             }
         };
         
         let algorithm_time = algorithm_start.elapsed().as_secs_f64();
+        let fund_pool_record_counts = Self::truncate_fund_records(&mut fund_records);
+        
+        // 读取原始解析数据，定位目标行修复前的数值
+        let raw_transactions = Self::read_raw_transactions(&request.file_path);
+        let original_row_data = Self::find_original_row(
+            &raw_transactions,
+            &processed_transactions[request.row_number - 1],
+            request.row_number,
+        );
+        
         let total_time = start_time.elapsed().as_secs_f64();
         
-        info!("时点查询完成，总耗时{:.3}秒", total_time);
+        info!("时点查询完成，总耗时{total_time:.3}秒");
         
         // 返回完整的查询结果
         Ok(TimePointQueryResult {
@@ -495,6 +1087,7 @@ impl TimePointService {
             query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             message: Some(format!("时点查询成功，处理到第{}行", request.row_number)),
             target_row_data: Some(target_row_data),
+            original_row_data,
             tracker_state: Some(tracker_state),
             processing_stats: Some(ProcessingStats {
                 last_processed_row: request.row_number,
@@ -507,48 +1100,46 @@ impl TimePointService {
             errors: None,
             available_fund_pools: Some(fund_pools),
             fund_pool_records: Some(fund_records),
+            fund_pool_record_counts: Some(fund_pool_record_counts),
         })
     }
     
     /// 使用缓存数据进行时点查询分析（不生成临时文件）
-    fn process_with_cached_data(&self, processed_transactions: &[Transaction], target_row: usize, summary: &crate::data_models::AuditSummary, offsite_pool_records: &crate::data_models::OffsitePoolRecordManager) -> AuditResult<(TrackerStateSnapshot, FrontendTransaction, Vec<TransactionStep>, Vec<FundPoolInfo>, std::collections::HashMap<String, Vec<serde_json::Value>>)> {
+    fn process_with_cached_data(&self, processed_transactions: &[Transaction], target_row: usize, summary: &crate::data_models::AuditSummary, offsite_pool_records: &crate::data_models::OffsitePoolRecordManager, algorithm: &str, checkpoints: &[TrackerCheckpoint]) -> AuditResult<(TrackerStateSnapshot, FrontendTransaction, Vec<TransactionStep>, Vec<FundPoolInfo>, std::collections::HashMap<String, Vec<serde_json::Value>>)> {
         // 复用现有逻辑，但标注为缓存处理模式
         debug!("使用缓存数据处理时点查询，不生成临时文件");
-        self.process_with_processed_data(processed_transactions, target_row, summary, offsite_pool_records)
+        self.process_with_processed_data(processed_transactions, target_row, summary, offsite_pool_records, algorithm, checkpoints)
     }
 
     /// 使用算法处理后的数据进行时点查询分析
-    fn process_with_processed_data(&self, processed_transactions: &[Transaction], target_row: usize, summary: &crate::data_models::AuditSummary, offsite_pool_records: &crate::data_models::OffsitePoolRecordManager) -> AuditResult<(TrackerStateSnapshot, FrontendTransaction, Vec<TransactionStep>, Vec<FundPoolInfo>, std::collections::HashMap<String, Vec<serde_json::Value>>)> {
+    fn process_with_processed_data(&self, processed_transactions: &[Transaction], target_row: usize, summary: &crate::data_models::AuditSummary, offsite_pool_records: &crate::data_models::OffsitePoolRecordManager, algorithm: &str, checkpoints: &[TrackerCheckpoint]) -> AuditResult<(TrackerStateSnapshot, FrontendTransaction, Vec<TransactionStep>, Vec<FundPoolInfo>, std::collections::HashMap<String, Vec<serde_json::Value>>)> {
         let mut recent_steps = Vec::new();
         let mut fund_pools = Vec::new();
         let mut fund_records = std::collections::HashMap::new();
         
-        // 获取目标时点的交易时间，作为截止时间
+        // 获取目标时点的完整交易时间戳，作为截止时间
+        // 按完整时间戳比较，避免同一天内晚于目标时点的记录被错误包含
         let target_transaction = processed_transactions.get(target_row - 1)
             .ok_or_else(|| AuditError::validation_error("目标行不存在"))?;
-        let cutoff_date = target_transaction.transaction_date.date();
+        let cutoff = target_transaction.transaction_date;
         
         // 从场外资金池记录管理器中提取截止到指定时点的资金池信息
         let pool_groups = offsite_pool_records.group_by_pool();
         
         // 构建资金池信息列表 - 只包含截止到指定时点的数据
-        for (pool_name, pool_records_list) in pool_groups.iter() {
-            // 只处理截止到目标时点的记录
-            let cutoff_records: Vec<_> = pool_records_list.iter()
-                .filter(|record| {
-                    // 解析场外资金池记录的时间字符串并与目标时点比较
-                    if let Ok(record_datetime) = chrono::NaiveDateTime::parse_from_str(&record.transaction_time, "%Y-%m-%d %H:%M:%S") {
-                        record_datetime.date() <= cutoff_date
-                    } else {
-                        // 如果解析失败，包含该记录（保守处理）
-                        true
-                    }
-                })
-                .collect();
+        for (pool_name, pool_records_list) in &pool_groups {
+            // 只处理截止到目标时点的记录（精确到时间戳及同秒序号）
+            let cutoff_records = Self::filter_records_to_cutoff(
+                pool_records_list,
+                pool_name,
+                processed_transactions,
+                target_row,
+                cutoff,
+            );
                 
             if let Some(latest_record) = cutoff_records.last() {
                 fund_pools.push(FundPoolInfo {
-                    id: format!("pool_{}", pool_name.replace(" ", "_")),
+                    id: format!("pool_{}", pool_name.replace(' ', "_")),
                     name: pool_name.clone(),
                     total_balance: latest_record.total_balance,
                 });
@@ -559,15 +1150,15 @@ impl TimePointService {
                     // 计算个人和公司的百分比
                     let total_balance = record.personal_balance + record.company_balance;
                     let (personal_percent, company_percent) = if total_balance.abs() > rust_decimal::Decimal::new(1, 2) { // > 0.01
-                        let personal_pct = if total_balance != rust_decimal::Decimal::ZERO {
-                            (record.personal_balance / total_balance * rust_decimal::Decimal::from(100)).round_dp(1)
-                        } else {
+                        let personal_pct = if total_balance == rust_decimal::Decimal::ZERO {
                             rust_decimal::Decimal::ZERO
-                        };
-                        let company_pct = if total_balance != rust_decimal::Decimal::ZERO {
-                            (record.company_balance / total_balance * rust_decimal::Decimal::from(100)).round_dp(1)
                         } else {
+                            (record.personal_balance / total_balance * rust_decimal::Decimal::from(100)).round_dp(1)
+                        };
+                        let company_pct = if total_balance == rust_decimal::Decimal::ZERO {
                             rust_decimal::Decimal::ZERO
+                        } else {
+                            (record.company_balance / total_balance * rust_decimal::Decimal::from(100)).round_dp(1)
                         };
                         (personal_pct, company_pct)
                     } else {
@@ -594,7 +1185,16 @@ impl TimePointService {
         }
         
         // 处理交易记录（用于生成最近步骤信息）
-        for (index, transaction) in processed_transactions.iter().enumerate() {
+        // 从目标行之前最近的检查点恢复，只重放增量区间；
+        // 预留10行窗口保证最近步骤列表仍然完整
+        let replay_from = checkpoints.iter()
+            .rev()
+            .find(|cp| cp.row_number <= target_row.saturating_sub(10))
+            .map_or(0, |cp| cp.row_number);
+        if replay_from > 0 {
+            debug!("📍 从第{replay_from}行检查点恢复，重放{}行增量", target_row - replay_from);
+        }
+        for (index, transaction) in processed_transactions.iter().enumerate().skip(replay_from) {
             if index + 1 > target_row {
                 break;
             }
@@ -652,24 +1252,68 @@ impl TimePointService {
             .ok_or_else(|| AuditError::validation_error("目标行不存在"))?.clone();
         
         let target_transaction = self.convert_to_frontend_transaction(&target_transaction_raw);
-        
+
         // 获取目标交易时的状态快照（使用算法计算的真实数据）
-        let tracker_state = TrackerStateSnapshot {
-            current_balance: target_transaction_raw.balance,
-            personal_balance: target_transaction_raw.personal_balance.unwrap_or(Decimal::ZERO),
-            company_balance: target_transaction_raw.company_balance.unwrap_or(Decimal::ZERO),
+        let tracker_state = Self::build_tracker_state(&target_transaction_raw, summary, algorithm);
+
+        Ok((tracker_state, target_transaction, recent_steps, fund_pools, fund_records))
+    }
+
+    /// 从处理后的交易构建该时点的追踪器状态快照
+    ///
+    /// 逐行累计量直接取自算法写回的交易字段，汇总量取自审计摘要
+    fn build_tracker_state(
+        transaction: &Transaction,
+        summary: &crate::data_models::AuditSummary,
+        algorithm: &str,
+    ) -> TrackerStateSnapshot {
+        // 差额计算法专有内部状态：余额归属与目标行的扣除决策
+        let balance_method_state = if algorithm.eq_ignore_ascii_case("BALANCE_METHOD") {
+            Some(Self::build_balance_method_state(transaction))
+        } else {
+            None
+        };
+
+        TrackerStateSnapshot {
+            current_balance: transaction.balance,
+            personal_balance: transaction.personal_balance.unwrap_or(Decimal::ZERO),
+            company_balance: transaction.company_balance.unwrap_or(Decimal::ZERO),
             total_personal_in: summary.total_personal_profit, // 使用审计摘要的真实数据
             total_company_in: summary.total_company_profit,
             total_personal_out: summary.total_misappropriation,
             total_company_out: summary.total_advance_payment,
-            misappropriation_amount: target_transaction_raw.cumulative_misappropriation.unwrap_or(Decimal::ZERO),
-            advance_amount: target_transaction_raw.cumulative_advance.unwrap_or(Decimal::ZERO),
-        };
-        
-        Ok((tracker_state, target_transaction, recent_steps, fund_pools, fund_records))
+            misappropriation_amount: transaction.cumulative_misappropriation.unwrap_or(Decimal::ZERO),
+            advance_amount: transaction.cumulative_advance.unwrap_or(Decimal::ZERO),
+            balance_method_state,
+        }
     }
     
+    /// 按固定间隔构建追踪器状态检查点
+    ///
+    /// 在主跑（完整算法分析）之后调用，每`TRACKER_CHECKPOINT_INTERVAL`行
+    /// 序列化一份状态快照随缓存保存，供时点查询就近恢复
+    fn build_checkpoints(
+        processed_transactions: &[Transaction],
+        summary: &crate::data_models::AuditSummary,
+        algorithm: &str,
+    ) -> Vec<TrackerCheckpoint> {
+        let mut checkpoints = Vec::new();
+        let mut row = TRACKER_CHECKPOINT_INTERVAL;
+        while row <= processed_transactions.len() {
+            checkpoints.push(TrackerCheckpoint {
+                row_number: row,
+                state: Self::build_tracker_state(&processed_transactions[row - 1], summary, algorithm),
+            });
+            row += TRACKER_CHECKPOINT_INTERVAL;
+        }
+        if !checkpoints.is_empty() {
+            debug!("📍 已构建{}个追踪器状态检查点（间隔{TRACKER_CHECKPOINT_INTERVAL}行）", checkpoints.len());
+        }
+        checkpoints
+    }
+
     /// 从行为描述中提取资金池名称
+    #[allow(dead_code)]
     fn extract_pool_name_from_behavior(&self, behavior: &str) -> Option<String> {
         // 简单正则提取 - 格式类似 "产品名称申购-XX："或"产品名称赎回-XX："
         if let Some(start) = behavior.find("申购-").or_else(|| behavior.find("赎回-")) {
@@ -695,6 +1339,7 @@ impl TimePointService {
     
     /// 从算法追踪器中提取资金池数据
     /// 这是正确的架构：复用算法追踪器中收集的资金池记录，而不是手动提取
+    #[allow(dead_code)]
     fn extract_fund_pools_from_tracker(&self, tracker_base: &crate::algorithms::shared::TrackerBase) -> (Vec<FundPoolInfo>, std::collections::HashMap<String, Vec<serde_json::Value>>) {
         let mut fund_pools = Vec::new();
         let mut fund_records = std::collections::HashMap::new();
@@ -714,12 +1359,11 @@ impl TimePointService {
         for (pool_name, records) in pools_map {
             // 计算资金池总余额（最后一条记录的余额）
             let total_balance = records.last()
-                .map(|r| r.total_balance)
-                .unwrap_or(rust_decimal::Decimal::ZERO);
+                .map_or(rust_decimal::Decimal::ZERO, |r| r.total_balance);
                 
             // 添加资金池信息
             fund_pools.push(FundPoolInfo {
-                id: format!("pool_{}", pool_name.replace(" ", "_")),
+                id: format!("pool_{}", pool_name.replace(' ', "_")),
                 name: pool_name.clone(),
                 total_balance,
             });
@@ -748,11 +1392,927 @@ impl TimePointService {
         (fund_pools, fund_records)
     }
     
+    /// 构建差额计算法的内部状态快照
+    ///
+    /// 余额归属直接取自算法计算的个人/公司余额；
+    /// 扣除决策根据目标行的支出金额与占比还原
+    fn build_balance_method_state(transaction: &Transaction) -> BalanceMethodStateSnapshot {
+        let (last_deduction_personal, last_deduction_company) = if transaction.expense_amount > Decimal::ZERO {
+            (
+                (transaction.expense_amount * transaction.personal_ratio.unwrap_or(Decimal::ZERO)).round_dp(2),
+                (transaction.expense_amount * transaction.company_ratio.unwrap_or(Decimal::ZERO)).round_dp(2),
+            )
+        } else {
+            (Decimal::ZERO, Decimal::ZERO)
+        };
+        
+        BalanceMethodStateSnapshot {
+            attributed_personal_balance: transaction.personal_balance.unwrap_or(Decimal::ZERO),
+            attributed_company_balance: transaction.company_balance.unwrap_or(Decimal::ZERO),
+            last_deduction_personal,
+            last_deduction_company,
+            deduction_policy: "个人余额优先扣除".to_string(),
+        }
+    }
+    
+    /// 按截止时点过滤资金池记录
+    ///
+    /// 记录时间早于截止时点的全部保留；与截止时点完全同秒的记录，
+    /// 按该资金池在目标行之前（含目标行）同秒交易的数量截断，
+    /// 避免同一秒内晚于目标行的申购/赎回被错误包含。
+    fn filter_records_to_cutoff<'a>(
+        pool_records_list: &[&'a crate::data_models::OffsitePoolRecord],
+        pool_name: &str,
+        processed_transactions: &[Transaction],
+        target_row: usize,
+        cutoff: chrono::NaiveDateTime,
+    ) -> Vec<&'a crate::data_models::OffsitePoolRecord> {
+        // 目标行之前（含目标行）该资金池与截止时点同秒的交易数，
+        // 即同秒记录允许包含的最大条数
+        let same_instant_quota = processed_transactions[..target_row].iter()
+            .filter(|tx| tx.fund_attribute == pool_name && tx.transaction_date == cutoff)
+            .count();
+        let mut same_instant_used = 0usize;
+        
+        pool_records_list.iter()
+            .filter(|record| {
+                if let Ok(record_datetime) = chrono::NaiveDateTime::parse_from_str(&record.transaction_time, "%Y-%m-%d %H:%M:%S") {
+                    if record_datetime < cutoff {
+                        true
+                    } else if record_datetime == cutoff {
+                        same_instant_used += 1;
+                        same_instant_used <= same_instant_quota
+                    } else {
+                        false
+                    }
+                } else {
+                    // 如果解析失败，包含该记录（保守处理）
+                    true
+                }
+            })
+            .copied()
+            .collect()
+    }
+    
+    /// 重新解析原始文件数据（不经过验证修复流程）
+    ///
+    /// 读取失败时返回空列表，原始对比属于增强信息，不应阻断查询本身
+    fn read_raw_transactions(file_path: &str) -> Vec<Transaction> {
+        let processor = crate::utils::ExcelProcessor::new(crate::data_models::Config::new());
+        match processor.read_transactions(file_path) {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                error!("读取原始数据失败，跳过原始/处理后对比: {e}");
+                Vec::new()
+            }
+        }
+    }
+    
+    /// 在原始解析数据中定位处理后交易对应的原始行
+    ///
+    /// 验证阶段可能对同一时间戳内的交易重排序，处理后的行号
+    /// 不一定等于原始行号。按关键字段匹配找回原始位置。
+    fn find_original_row(
+        raw_transactions: &[Transaction],
+        processed: &Transaction,
+        processed_row: usize,
+    ) -> Option<RawTransactionView> {
+        let matches = |raw: &Transaction| {
+            raw.transaction_date == processed.transaction_date
+                && raw.income_amount == processed.income_amount
+                && raw.expense_amount == processed.expense_amount
+                && raw.balance == processed.balance
+                && raw.fund_attribute == processed.fund_attribute
+        };
+        
+        // 优先检查同一位置（绝大多数行未被重排）
+        let index = if raw_transactions.get(processed_row - 1).is_some_and(matches) {
+            Some(processed_row - 1)
+        } else {
+            raw_transactions.iter().position(matches)
+        };
+        
+        index.map(|i| {
+            let raw = &raw_transactions[i];
+            RawTransactionView {
+                original_row_number: i + 1,
+                transaction_date: raw.transaction_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                transaction_time: raw.transaction_time.clone(),
+                income_amount: raw.income_amount,
+                expense_amount: raw.expense_amount,
+                balance: raw.balance,
+                fund_attribute: raw.fund_attribute.clone(),
+                position_changed: i + 1 != processed_row,
+            }
+        })
+    }
+    
+    /// 资金池查询：返回截至指定行的记录历史、统计与个人/公司余额拆分
+    ///
+    /// 与时点查询共用缓存分析数据路径，缓存命中时不重跑算法。
+    /// 记录按时点截断规则过滤（见[`Self::filter_records_to_cutoff`]），
+    /// 统计基于截断后的记录计算，与查看完整历史的口径保持一致
     pub async fn query_fund_pool(&mut self, request: FundPoolQueryRequest) -> Result<FundPoolQueryResult, crate::errors::AuditError> {
-        Ok(FundPoolQueryResult {
+        let start_time = Instant::now();
+        info!("开始资金池查询: 资金池={}, 文件={}, 行号={}, 算法={}",
+              request.pool_name, request.file_path, request.row_number, request.algorithm);
+
+        let failure = |pool_name: String, message: String| FundPoolQueryResult {
             success: false,
+            pool_name,
+            message: Some(message),
+            total_records: 0,
+            records: Vec::new(),
+            statistics: None,
+            personal_balance: Decimal::ZERO,
+            company_balance: Decimal::ZERO,
+        };
+
+        self.file_cache.cleanup_expired();
+        let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
+        let cache_data = match self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await {
+            Ok(cache_data) => cache_data,
+            Err(e) => {
+                error!("资金池查询的算法处理失败: {e}");
+                return Ok(failure(request.pool_name, format!("算法处理失败: {e}")));
+            }
+        };
+
+        let total_rows = cache_data.processed_transactions.len();
+        if request.row_number == 0 || request.row_number > total_rows {
+            return Ok(failure(
+                request.pool_name,
+                format!("行号{}无效，有效范围: 1-{total_rows}", request.row_number),
+            ));
+        }
+
+        let pool_records_list = cache_data.offsite_pool_records.get_pool_records(&request.pool_name);
+        if pool_records_list.is_empty() {
+            return Ok(failure(
+                request.pool_name.clone(),
+                format!("资金池不存在: {}", request.pool_name),
+            ));
+        }
+
+        // 按目标行的完整时间戳截断记录（同秒记录按时点查询的配额规则处理）
+        let cutoff = cache_data.processed_transactions[request.row_number - 1].transaction_date;
+        let cutoff_records = Self::filter_records_to_cutoff(
+            &pool_records_list,
+            &request.pool_name,
+            &cache_data.processed_transactions,
+            request.row_number,
+            cutoff,
+        );
+
+        // 统计基于截断后的记录计算，得到"截至该行"视角的申购/赎回/盈亏
+        let cutoff_manager = crate::data_models::OffsitePoolRecordManager {
+            records: cutoff_records.iter().map(|record| (*record).clone()).collect(),
+        };
+        let statistics = cutoff_manager.calculate_pool_stats(&request.pool_name);
+        let (personal_balance, company_balance) = cutoff_records.last()
+            .map_or((Decimal::ZERO, Decimal::ZERO), |record| {
+                (record.personal_balance, record.company_balance)
+            });
+
+        // 转为前端格式，内联记录按时点查询同样的限额截断
+        let mut records: Vec<serde_json::Value> = cutoff_records.iter()
+            .map(|record| serde_json::json!({
+                "交易时间": record.transaction_time,
+                "资金池名称": record.pool_name,
+                "入金": record.inflow,
+                "出金": record.outflow,
+                "总余额": record.total_balance,
+                "个人余额": record.personal_balance,
+                "公司余额": record.company_balance,
+                "资金占比": record.fund_ratio,
+                "行为性质": record.behavior_nature,
+                "累计申购": record.cumulative_purchase,
+                "累计赎回": record.cumulative_redemption,
+                "净盈亏": record.net_profit_loss,
+            }))
+            .collect();
+        let total_records = records.len();
+        if records.len() > DEFAULT_POOL_PAGE_LIMIT {
+            let excess = records.len() - DEFAULT_POOL_PAGE_LIMIT;
+            records.drain(..excess);
+            debug!("资金池{}记录过多，内联仅保留最近{DEFAULT_POOL_PAGE_LIMIT}条", request.pool_name);
+        }
+
+        info!("资金池查询完成: 截至第{}行共{}条记录，耗时{:.3}秒",
+              request.row_number, total_records, start_time.elapsed().as_secs_f64());
+        Ok(FundPoolQueryResult {
+            success: true,
             pool_name: request.pool_name,
-            message: Some("资金池查询功能开发中".to_string()),
+            message: None,
+            total_records,
+            records,
+            statistics,
+            personal_balance,
+            company_balance,
         })
     }
-}
\ No newline at end of file
+
+    /// 按时刻的时点查询：定位截止时刻前最后一笔交易后复用行号查询
+    ///
+    /// 调查中的问题常以"截至6月30日状态如何"的形式出现，而非具体行号。
+    /// 同一时间戳内有多笔交易时取该组处理顺序的最后一笔，与"截至该
+    /// 时刻全部交易均已入账"的口径一致；截止时刻早于首笔交易时软失败
+    pub async fn query_time_point_at(&mut self, request: TimePointAtQueryRequest) -> Result<TimePointQueryResult, crate::errors::AuditError> {
+        let cutoff = Self::parse_as_of(&request.as_of)?;
+        let start_time = Instant::now();
+        info!("开始按时刻时点查询: 文件={}, 截至{}, 算法={}",
+              request.file_path, cutoff, request.algorithm);
+
+        self.file_cache.cleanup_expired();
+        let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
+        let cache_data = self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await?;
+
+        // 截止时刻前（含同秒）的最后一笔交易；数据按时间升序，
+        // 从尾部反查即得同时间戳组中处理顺序最靠后的那笔
+        let Some(index) = cache_data.processed_transactions.iter()
+            .rposition(|tx| tx.transaction_date <= cutoff)
+        else {
+            return Ok(TimePointQueryResult {
+                success: false,
+                algorithm: request.algorithm,
+                target_row: 0,
+                total_rows: cache_data.processed_transactions.len(),
+                processing_time: start_time.elapsed().as_secs_f64(),
+                query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                message: Some(format!("截止时刻{cutoff}早于首笔交易，无可查询的时点")),
+                target_row_data: None,
+                original_row_data: None,
+                tracker_state: None,
+                processing_stats: None,
+                recent_steps: None,
+                errors: None,
+                available_fund_pools: Some(vec![]),
+                fund_pool_records: Some(std::collections::HashMap::new()),
+                fund_pool_record_counts: None,
+            });
+        };
+
+        let row_request = TimePointQueryRequest {
+            file_path: request.file_path,
+            row_number: index + 1,
+            algorithm: request.algorithm,
+        };
+        self.query_from_cached_data(&row_request, &cache_data, start_time)
+    }
+
+    /// 解析截止时刻：完整时间戳或仅日期（按当日末秒处理）
+    fn parse_as_of(as_of: &str) -> AuditResult<chrono::NaiveDateTime> {
+        let trimmed = as_of.trim();
+        if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S") {
+            return Ok(datetime);
+        }
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            if let Some(datetime) = date.and_hms_opt(23, 59, 59) {
+                return Ok(datetime);
+            }
+        }
+        Err(AuditError::validation_error(format!(
+            "无法解析截止时刻: {as_of}（应为YYYY-MM-DD HH:MM:SS或YYYY-MM-DD）"
+        )))
+    }
+
+    /// 批量时点查询：一次扫描返回多行的状态快照
+    ///
+    /// 核查往往需要同时看几十个时点的追踪器状态，逐行调用
+    /// [`Self::query_time_point_cached`]会重复推导。这里共用缓存分析
+    /// 数据路径后按行号升序单次遍历，越界行号记入`invalid_rows`而非报错
+    pub async fn query_time_points_batch(
+        &mut self,
+        request: BatchTimePointQueryRequest,
+    ) -> Result<BatchTimePointQueryResult, crate::errors::AuditError> {
+        let start_time = Instant::now();
+        if request.row_numbers.is_empty() {
+            return Err(AuditError::validation_error("至少需要一个查询行号"));
+        }
+        info!("开始批量时点查询: 文件={}, 行数={}, 算法={}",
+              request.file_path, request.row_numbers.len(), request.algorithm);
+
+        self.file_cache.cleanup_expired();
+        let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
+        let cache_data = self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await?;
+
+        // 去重排序后单次顺序遍历即可覆盖全部查询点
+        let total_rows = cache_data.processed_transactions.len();
+        let mut rows = request.row_numbers;
+        rows.sort_unstable();
+        rows.dedup();
+        let (valid_rows, invalid_rows): (Vec<usize>, Vec<usize>) = rows.into_iter()
+            .partition(|&row| row >= 1 && row <= total_rows);
+
+        let snapshots: Vec<TimePointSnapshot> = valid_rows.into_iter()
+            .map(|row| {
+                let transaction = &cache_data.processed_transactions[row - 1];
+                TimePointSnapshot {
+                    row_number: row,
+                    transaction: self.convert_to_frontend_transaction(transaction),
+                    tracker_state: Self::build_tracker_state(
+                        transaction,
+                        &cache_data.audit_summary,
+                        &cache_data.algorithm,
+                    ),
+                }
+            })
+            .collect();
+
+        let total_time = start_time.elapsed().as_secs_f64();
+        info!("批量时点查询完成: {}个快照（{}个无效行号），耗时{total_time:.3}秒",
+              snapshots.len(), invalid_rows.len());
+        Ok(BatchTimePointQueryResult {
+            success: true,
+            algorithm: request.algorithm,
+            total_rows,
+            processing_time: total_time,
+            query_time: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            snapshots,
+            invalid_rows,
+        })
+    }
+
+    /// 按期汇总查询（按日与按月的余额与缺口走势）
+    ///
+    /// 与时点查询共用缓存分析数据路径，命中缓存时不重跑算法
+    pub async fn query_period_summary(
+        &mut self,
+        request: PeriodSummaryQueryRequest,
+    ) -> Result<crate::utils::period_summary::PeriodSummaryReport, crate::errors::AuditError> {
+        info!("开始按期汇总查询: 文件={}, 算法={}", request.file_path, request.algorithm);
+
+        self.file_cache.cleanup_expired();
+        let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
+        let cache_data = self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await?;
+
+        let report = crate::utils::period_summary::PeriodSummaryReport::from_transactions(
+            &cache_data.processed_transactions,
+        );
+        info!("按期汇总查询完成: {}个按日期间, {}个按月期间", report.daily.len(), report.monthly.len());
+        Ok(report)
+    }
+
+    /// 时间线查询（余额与缺口的走势序列）
+    ///
+    /// 与时点查询共用缓存分析数据路径，命中缓存时不重跑算法
+    pub async fn query_timeline(
+        &mut self,
+        request: TimelineQueryRequest,
+    ) -> Result<crate::services::TimelineSeries, crate::errors::AuditError> {
+        use crate::services::{AuditService, TimelineSampling};
+
+        info!("开始时间线查询: 文件={}, 算法={}, 按日采样={}",
+              request.file_path, request.algorithm, request.per_day);
+
+        self.file_cache.cleanup_expired();
+        let fingerprint = self.file_cache.generate_fingerprint(&request.file_path, &request.algorithm)?;
+        let cache_data = self.ensure_cached_data(&fingerprint, &request.file_path, &request.algorithm).await?;
+
+        let sampling = if request.per_day {
+            TimelineSampling::PerDay
+        } else {
+            TimelineSampling::PerTransaction
+        };
+        let series = AuditService::get_timeline_series(&cache_data.processed_transactions, sampling);
+        info!("时间线查询完成: {}个采样点", series.labels.len());
+        Ok(series)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_models::OffsitePoolRecord;
+    use chrono::NaiveDate;
+
+    fn pool_transaction(day: u32, hour: u32, pool: &str) -> Transaction {
+        let date = NaiveDate::from_ymd_opt(2021, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap();
+        Transaction::new(
+            date,
+            format!("{hour:02}0000"),
+            Decimal::ZERO,
+            Decimal::from(1000),
+            Decimal::from(10000),
+            pool.to_string(),
+        )
+    }
+
+    fn pool_record(time: &str) -> OffsitePoolRecord {
+        OffsitePoolRecord {
+            transaction_time: time.to_string(),
+            pool_name: "理财-A".to_string(),
+            inflow: Decimal::from(1000),
+            outflow: Decimal::ZERO,
+            total_balance: Decimal::from(1000),
+            personal_balance: Decimal::from(1000),
+            company_balance: Decimal::ZERO,
+            fund_ratio: "个人100.0%，公司0.0%".to_string(),
+            behavior_nature: "理财-A申购".to_string(),
+            cumulative_purchase: Decimal::from(1000),
+            cumulative_redemption: Decimal::ZERO,
+            net_profit_loss: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_get_transaction_by_source_row() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+        
+        // 原始顺序为[a, b]，验证阶段重排后处理顺序为[b, a]
+        let a = pool_transaction(1, 10, "个人应收");
+        let b = pool_transaction(1, 14, "公司应付");
+        let cache_data = FileCacheData {
+            fingerprint: "fp".to_string(),
+            processed_transactions: vec![b.clone(), a.clone()],
+            raw_transactions: vec![a, b],
+            audit_summary: crate::data_models::AuditSummary::new(),
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints: Vec::new(),
+        };
+        service.file_cache.set_cache("fp".to_string(), cache_data);
+        
+        // 源表第1行（a）被重排到处理结果第2行
+        let result = service.get_transaction_by_source_row("fp", "FIFO", 1).unwrap();
+        assert_eq!(result.original_row_number, 1);
+        assert_eq!(result.processed_row_number, 2);
+        assert!(result.position_changed);
+        
+        // 行号越界与缓存未命中均报错
+        assert!(service.get_transaction_by_source_row("fp", "FIFO", 3).is_err());
+        assert!(service.get_transaction_by_source_row("other", "FIFO", 1).is_err());
+        // 算法不匹配报错
+        assert!(service.get_transaction_by_source_row("fp", "BALANCE_METHOD", 1).is_err());
+    }
+
+    #[test]
+    fn test_search_cached_analyses_across_files() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        // 两个"文件"的缓存条目，模拟工作区里多份已分析的流水
+        let mut a = pool_transaction(1, 10, "个人应收");
+        a.behavior_nature = Some("个人应收".to_string());
+        let mut b = pool_transaction(2, 14, "公司应付");
+        b.expense_amount = Decimal::from(2500);
+        b.behavior_nature = Some("挪用".to_string());
+        for (fingerprint, transaction) in [("a.xlsx|FIFO|0|1", a), ("b.xlsx|FIFO|0|1", b)] {
+            service.file_cache.set_cache(fingerprint.to_string(), FileCacheData {
+                fingerprint: fingerprint.to_string(),
+                processed_transactions: vec![transaction.clone()],
+                raw_transactions: vec![transaction],
+                audit_summary: crate::data_models::AuditSummary::new(),
+                offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+                algorithm: "FIFO".to_string(),
+                cached_at: std::time::SystemTime::now(),
+                checkpoints: Vec::new(),
+            });
+        }
+
+        // 按金额搜索：只命中b文件的那笔支出
+        let query = AnalysisSearchQuery { amount: Some(Decimal::from(2500)), ..Default::default() };
+        let hits = service.search_cached_analyses(&query).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].file_path, "b.xlsx");
+        assert_eq!(hits[0].row_number, 1);
+        assert!(hits[0].snippet.contains("挪用"));
+
+        // 按行为搜索
+        let query = AnalysisSearchQuery { behavior: Some("挪用".to_string()), ..Default::default() };
+        assert_eq!(service.search_cached_analyses(&query).unwrap().len(), 1);
+
+        // 按日期范围搜索：只包含1月1日
+        let query = AnalysisSearchQuery {
+            date_from: NaiveDate::from_ymd_opt(2021, 1, 1),
+            date_to: NaiveDate::from_ymd_opt(2021, 1, 1),
+            ..Default::default()
+        };
+        let hits = service.search_cached_analyses(&query).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].file_path, "a.xlsx");
+
+        // 空条件报错
+        assert!(service.search_cached_analyses(&AnalysisSearchQuery::default()).is_err());
+    }
+
+    #[test]
+    fn test_search_by_attribute_level_rolls_up_hierarchy() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        let leaf = pool_transaction(1, 10, "公司/子公司A/备用金");
+        let sibling = pool_transaction(1, 11, "公司/子公司B");
+        let lookalike = pool_transaction(1, 12, "子公司A往来");
+        service.file_cache.set_cache("fp".to_string(), FileCacheData {
+            fingerprint: "fp".to_string(),
+            processed_transactions: vec![leaf.clone(), sibling.clone(), lookalike.clone()],
+            raw_transactions: vec![leaf, sibling, lookalike],
+            audit_summary: crate::data_models::AuditSummary::new(),
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints: Vec::new(),
+        });
+
+        // 顶层路径命中其下全部叶子，但不会误中仅含子串的平铺属性
+        let query = AnalysisSearchQuery {
+            fund_attribute_level: Some("公司".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(service.search_cached_analyses(&query).unwrap().len(), 2);
+
+        // 中间层级路径只命中该分支
+        let query = AnalysisSearchQuery {
+            fund_attribute_level: Some("公司/子公司A".to_string()),
+            ..Default::default()
+        };
+        let hits = service.search_cached_analyses(&query).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].snippet.contains("备用金"));
+    }
+
+    fn pool_record_json(day: u32) -> serde_json::Value {
+        serde_json::json!({
+            "交易时间": format!("2021-01-{day:02} 10:00:00"),
+            "资金池名称": "理财-A",
+            "入金": "1000",
+        })
+    }
+
+    #[test]
+    fn test_slice_pool_records_pagination_and_date_filter() {
+        let records: Vec<serde_json::Value> = (1..=20).map(pool_record_json).collect();
+        let request = PoolRecordsPageRequest {
+            file_path: "a.xlsx".to_string(),
+            row_number: 1,
+            algorithm: "FIFO".to_string(),
+            pool_name: "理财-A".to_string(),
+            offset: 5,
+            limit: 3,
+            date_from: None,
+            date_to: None,
+        };
+
+        let page = TimePointService::slice_pool_records("理财-A", &records, &request);
+        assert_eq!(page.total_records, 20);
+        assert_eq!(page.records.len(), 3);
+        assert_eq!(page.records[0]["交易时间"], "2021-01-06 10:00:00");
+
+        // 日期过滤在切片之前生效
+        let filtered_request = PoolRecordsPageRequest {
+            offset: 0,
+            limit: 100,
+            date_from: Some("2021-01-10".to_string()),
+            date_to: Some("2021-01-12".to_string()),
+            ..request
+        };
+        let page = TimePointService::slice_pool_records("理财-A", &records, &filtered_request);
+        assert_eq!(page.total_records, 3);
+        assert_eq!(page.records[0]["交易时间"], "2021-01-10 10:00:00");
+        assert_eq!(page.records[2]["交易时间"], "2021-01-12 10:00:00");
+    }
+
+    #[test]
+    fn test_truncate_fund_records_keeps_tail() {
+        let mut fund_records = HashMap::new();
+        let many: Vec<serde_json::Value> = (0..DEFAULT_POOL_PAGE_LIMIT + 50)
+            .map(|i| serde_json::json!({ "交易时间": format!("record-{i}") }))
+            .collect();
+        fund_records.insert("理财-A".to_string(), many);
+        fund_records.insert("理财-B".to_string(), vec![pool_record_json(1)]);
+
+        let counts = TimePointService::truncate_fund_records(&mut fund_records);
+        assert_eq!(counts["理财-A"], DEFAULT_POOL_PAGE_LIMIT + 50);
+        assert_eq!(counts["理财-B"], 1);
+        // 超限的池只保留末段，未超限的池原样保留
+        assert_eq!(fund_records["理财-A"].len(), DEFAULT_POOL_PAGE_LIMIT);
+        assert_eq!(fund_records["理财-A"][0]["交易时间"], "record-50");
+        assert_eq!(fund_records["理财-B"].len(), 1);
+    }
+
+    #[test]
+    fn test_cutoff_excludes_same_day_later_records() {
+        // 同一天内，晚于目标时点的记录不应被包含
+        let transactions = vec![
+            pool_transaction(1, 10, "理财-A"),
+            pool_transaction(1, 14, "理财-A"),
+        ];
+        let records = [
+            pool_record("2021-01-01 10:00:00"),
+            pool_record("2021-01-01 14:00:00"),
+        ];
+        let record_refs: Vec<&OffsitePoolRecord> = records.iter().collect();
+
+        // 查询第1行（10点），14点的记录应被排除
+        let cutoff = transactions[0].transaction_date;
+        let filtered = TimePointService::filter_records_to_cutoff(
+            &record_refs, "理财-A", &transactions, 1, cutoff,
+        );
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].transaction_time, "2021-01-01 10:00:00");
+    }
+
+    #[test]
+    fn test_cutoff_same_second_sequence() {
+        // 同一秒内多笔申购/赎回，按目标行之前的交易数量截断
+        let transactions = vec![
+            pool_transaction(1, 10, "理财-A"),
+            pool_transaction(1, 10, "理财-A"),
+            pool_transaction(1, 11, "理财-A"),
+        ];
+        let records = [
+            pool_record("2021-01-01 10:00:00"),
+            pool_record("2021-01-01 10:00:00"),
+            pool_record("2021-01-01 11:00:00"),
+        ];
+        let record_refs: Vec<&OffsitePoolRecord> = records.iter().collect();
+
+        // 查询第1行：同秒的第2条记录不应包含
+        let cutoff = transactions[0].transaction_date;
+        let filtered = TimePointService::filter_records_to_cutoff(
+            &record_refs, "理财-A", &transactions, 1, cutoff,
+        );
+        assert_eq!(filtered.len(), 1);
+
+        // 查询第2行：两条同秒记录都应包含
+        let filtered = TimePointService::filter_records_to_cutoff(
+            &record_refs, "理财-A", &transactions, 2, cutoff,
+        );
+        assert_eq!(filtered.len(), 2);
+
+        // 查询第3行：全部包含
+        let cutoff = transactions[2].transaction_date;
+        let filtered = TimePointService::filter_records_to_cutoff(
+            &record_refs, "理财-A", &transactions, 3, cutoff,
+        );
+        assert_eq!(filtered.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query_fund_pool_returns_history_and_stats() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        // 用真实存在的临时文件生成指纹，保证缓存命中、不触发算法重跑
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("流水.xlsx");
+        std::fs::write(&file_path, b"placeholder").unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+        let fingerprint = service.file_cache.generate_fingerprint(&file_path, "FIFO").unwrap();
+
+        let transactions = vec![
+            pool_transaction(1, 10, "理财-A"),
+            pool_transaction(2, 10, "理财-A"),
+        ];
+        let mut pool_records = crate::data_models::OffsitePoolRecordManager::new();
+        pool_records.records.push(pool_record("2021-01-01 10:00:00"));
+        let mut redemption = pool_record("2021-01-02 10:00:00");
+        redemption.inflow = Decimal::ZERO;
+        redemption.outflow = Decimal::from(500);
+        redemption.total_balance = Decimal::from(500);
+        redemption.personal_balance = Decimal::from(500);
+        redemption.cumulative_redemption = Decimal::from(500);
+        pool_records.records.push(redemption);
+
+        service.file_cache.set_cache(fingerprint.clone(), FileCacheData {
+            fingerprint,
+            processed_transactions: transactions.clone(),
+            raw_transactions: transactions,
+            audit_summary: crate::data_models::AuditSummary::new(),
+            offsite_pool_records: pool_records,
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints: Vec::new(),
+        });
+
+        // 查询第1行：只包含时点前的那条申购记录
+        let result = service.query_fund_pool(FundPoolQueryRequest {
+            pool_name: "理财-A".to_string(),
+            file_path: file_path.clone(),
+            row_number: 1,
+            algorithm: "FIFO".to_string(),
+        }).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.total_records, 1);
+        assert_eq!(result.records.len(), 1);
+        assert_eq!(result.personal_balance, Decimal::from(1000));
+        let stats = result.statistics.unwrap();
+        assert_eq!(stats.total_purchase, Decimal::from(1000));
+        assert_eq!(stats.total_redemption, Decimal::ZERO);
+
+        // 查询第2行：赎回记录计入统计，余额拆分随最后一条记录更新
+        let result = service.query_fund_pool(FundPoolQueryRequest {
+            pool_name: "理财-A".to_string(),
+            file_path: file_path.clone(),
+            row_number: 2,
+            algorithm: "FIFO".to_string(),
+        }).await.unwrap();
+        assert_eq!(result.total_records, 2);
+        assert_eq!(result.personal_balance, Decimal::from(500));
+        assert_eq!(result.statistics.unwrap().total_redemption, Decimal::from(500));
+
+        // 不存在的资金池：软失败并带提示
+        let result = service.query_fund_pool(FundPoolQueryRequest {
+            pool_name: "理财-Z".to_string(),
+            file_path,
+            row_number: 1,
+            algorithm: "FIFO".to_string(),
+        }).await.unwrap();
+        assert!(!result.success);
+        assert!(result.message.unwrap().contains("资金池不存在"));
+    }
+
+    #[tokio::test]
+    async fn test_query_time_points_batch_dedups_and_sorts() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        // 用真实存在的临时文件生成指纹，保证缓存命中、不触发算法重跑
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("流水.xlsx");
+        std::fs::write(&file_path, b"placeholder").unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+        let fingerprint = service.file_cache.generate_fingerprint(&file_path, "FIFO").unwrap();
+
+        let mut transactions: Vec<Transaction> = (1..=5)
+            .map(|day| pool_transaction(day, 10, "个人应收"))
+            .collect();
+        transactions[2].personal_balance = Some(Decimal::from(800));
+        service.file_cache.set_cache(fingerprint.clone(), FileCacheData {
+            fingerprint,
+            processed_transactions: transactions.clone(),
+            raw_transactions: transactions,
+            audit_summary: crate::data_models::AuditSummary::new(),
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints: Vec::new(),
+        });
+
+        let result = service.query_time_points_batch(BatchTimePointQueryRequest {
+            file_path: file_path.clone(),
+            algorithm: "FIFO".to_string(),
+            row_numbers: vec![5, 3, 3, 99],
+        }).await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.total_rows, 5);
+        // 重复行号去重，快照按行号升序排列，越界行号单独列出
+        let rows: Vec<usize> = result.snapshots.iter().map(|s| s.row_number).collect();
+        assert_eq!(rows, vec![3, 5]);
+        assert_eq!(result.snapshots[0].tracker_state.personal_balance, Decimal::from(800));
+        assert_eq!(result.invalid_rows, vec![99]);
+
+        // 空行号列表报错
+        let empty = service.query_time_points_batch(BatchTimePointQueryRequest {
+            file_path,
+            algorithm: "FIFO".to_string(),
+            row_numbers: Vec::new(),
+        }).await;
+        assert!(empty.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_time_point_at_locates_last_row_before_cutoff() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("流水.xlsx");
+        std::fs::write(&file_path, b"placeholder").unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+        let fingerprint = service.file_cache.generate_fingerprint(&file_path, "FIFO").unwrap();
+
+        // 第2、3行为同一时间戳（1月2日10点），第4行在1月3日
+        let transactions = vec![
+            pool_transaction(1, 10, "个人应收"),
+            pool_transaction(2, 10, "个人应收"),
+            pool_transaction(2, 10, "个人应收"),
+            pool_transaction(3, 10, "个人应收"),
+        ];
+        service.file_cache.set_cache(fingerprint.clone(), FileCacheData {
+            fingerprint,
+            processed_transactions: transactions.clone(),
+            raw_transactions: transactions,
+            audit_summary: crate::data_models::AuditSummary::new(),
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints: Vec::new(),
+        });
+        let query_at = |as_of: &str| TimePointAtQueryRequest {
+            file_path: file_path.clone(),
+            algorithm: "FIFO".to_string(),
+            as_of: as_of.to_string(),
+        };
+
+        // 截止1月2日中午：命中同时间戳组处理顺序的最后一笔（第3行）
+        let result = service.query_time_point_at(query_at("2021-01-02 12:00:00")).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.target_row, 3);
+
+        // 只给日期按当日23:59:59处理
+        let result = service.query_time_point_at(query_at("2021-01-01")).await.unwrap();
+        assert_eq!(result.target_row, 1);
+
+        // 截止时刻早于首笔交易：软失败
+        let result = service.query_time_point_at(query_at("2020-12-31 23:59:59")).await.unwrap();
+        assert!(!result.success);
+        assert!(result.message.unwrap().contains("早于首笔交易"));
+
+        // 无法解析的时刻报错
+        assert!(service.query_time_point_at(query_at("昨天")).await.is_err());
+    }
+
+    #[test]
+    fn test_build_checkpoints_and_nearest_lookup() {
+        // 构造略超两个间隔长度的交易序列，余额随行号递增以便核对状态
+        let base = pool_transaction(1, 10, "个人应收");
+        let total = TRACKER_CHECKPOINT_INTERVAL * 2 + 500;
+        let mut transactions = Vec::with_capacity(total);
+        for i in 0..total {
+            let mut tx = base.clone();
+            tx.balance = Decimal::from(i as u64 + 1);
+            transactions.push(tx);
+        }
+        let summary = crate::data_models::AuditSummary::new();
+
+        let checkpoints = TimePointService::build_checkpoints(&transactions, &summary, "FIFO");
+        assert_eq!(checkpoints.len(), 2);
+        assert_eq!(checkpoints[0].row_number, TRACKER_CHECKPOINT_INTERVAL);
+        assert_eq!(checkpoints[1].row_number, TRACKER_CHECKPOINT_INTERVAL * 2);
+        // 检查点状态即该行处理完成后的余额
+        assert_eq!(
+            checkpoints[0].state.current_balance,
+            Decimal::from(TRACKER_CHECKPOINT_INTERVAL as u64)
+        );
+
+        let cache_data = FileCacheData {
+            fingerprint: "fp".to_string(),
+            processed_transactions: transactions,
+            raw_transactions: Vec::new(),
+            audit_summary: summary,
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints,
+        };
+        assert!(cache_data.nearest_checkpoint(TRACKER_CHECKPOINT_INTERVAL - 1).is_none());
+        assert_eq!(
+            cache_data.nearest_checkpoint(TRACKER_CHECKPOINT_INTERVAL + 5).unwrap().row_number,
+            TRACKER_CHECKPOINT_INTERVAL
+        );
+        assert_eq!(
+            cache_data.nearest_checkpoint(total).unwrap().row_number,
+            TRACKER_CHECKPOINT_INTERVAL * 2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_replays_delta_from_checkpoint() {
+        let mut service = TimePointService::new("FIFO".to_string()).unwrap();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("流水.xlsx");
+        std::fs::write(&file_path, b"placeholder").unwrap();
+        let file_path = file_path.to_string_lossy().to_string();
+        let fingerprint = service.file_cache.generate_fingerprint(&file_path, "FIFO").unwrap();
+
+        // 超过一个检查点间隔的交易序列，目标行位于检查点之后的增量区间
+        let base = pool_transaction(1, 10, "个人应收");
+        let total = TRACKER_CHECKPOINT_INTERVAL + 500;
+        let mut transactions = Vec::with_capacity(total);
+        for i in 0..total {
+            let mut tx = base.clone();
+            tx.balance = Decimal::from(i as u64 + 1);
+            transactions.push(tx);
+        }
+        let summary = crate::data_models::AuditSummary::new();
+        let checkpoints = TimePointService::build_checkpoints(&transactions, &summary, "FIFO");
+        assert_eq!(checkpoints.len(), 1);
+        service.file_cache.set_cache(fingerprint.clone(), FileCacheData {
+            fingerprint,
+            processed_transactions: transactions.clone(),
+            raw_transactions: transactions,
+            audit_summary: summary,
+            offsite_pool_records: crate::data_models::OffsitePoolRecordManager::new(),
+            algorithm: "FIFO".to_string(),
+            cached_at: std::time::SystemTime::now(),
+            checkpoints,
+        });
+
+        let result = service.query_time_point_cached(TimePointQueryRequest {
+            file_path,
+            row_number: total,
+            algorithm: "FIFO".to_string(),
+        }).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.target_row, total);
+
+        // 就近恢复后最近步骤窗口仍然完整且落在目标行之前
+        let steps = result.recent_steps.unwrap();
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps.first().unwrap().row_number, total - 9);
+        assert_eq!(steps.last().unwrap().row_number, total);
+        assert_eq!(steps.last().unwrap().balance_after, Decimal::from(total as u64));
+    }
+}
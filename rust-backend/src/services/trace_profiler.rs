@@ -0,0 +1,142 @@
+//! 运行期性能剖析（chrome-tracing格式）
+//!
+//! 用户在特殊数据集上报告性能问题时，往往无法提供原始流水复现。
+//! 启用`--trace-profile`后，各处理阶段与热点区间会被记录为
+//! chrome-tracing JSON（`chrome://tracing`或Perfetto可直接打开），
+//! 随运行结果一并写入工作目录，便于离线定位瓶颈。
+
+use crate::errors::{AuditError, AuditResult};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 单个剖析事件（完整区间，对应chrome-tracing的"X"事件）
+#[derive(Debug, Clone)]
+struct TraceEvent {
+    /// 事件名称（阶段或热点区间描述）
+    name: String,
+    /// 分类（stage/algorithm/export等，tracing界面中按类着色）
+    category: String,
+    /// 相对运行起点的开始时间（微秒）
+    start_us: u128,
+    /// 持续时间（微秒）
+    duration_us: u128,
+}
+
+/// 性能剖析器
+///
+/// 记录一次分析运行中各阶段的起止时间，
+/// 最终导出为chrome-tracing JSON供支持排障使用
+#[derive(Debug)]
+pub struct TraceProfiler {
+    /// 运行起点（所有事件时间戳的零点）
+    run_start: Instant,
+    /// 已记录的事件
+    events: Vec<TraceEvent>,
+}
+
+impl TraceProfiler {
+    /// 创建新的剖析器（以当前时刻为时间零点）
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            run_start: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+
+    /// 记录一个已结束的区间事件
+    ///
+    /// # Arguments
+    /// * `category` - 分类（如"stage"、"algorithm"）
+    /// * `name` - 事件名称
+    /// * `started_at` - 区间开始时刻（应晚于剖析器创建时刻）
+    pub fn record(&mut self, category: &str, name: &str, started_at: Instant) {
+        let start_us = started_at.duration_since(self.run_start).as_micros();
+        let duration_us = started_at.elapsed().as_micros();
+        self.events.push(TraceEvent {
+            name: name.to_string(),
+            category: category.to_string(),
+            start_us,
+            duration_us,
+        });
+    }
+
+    /// 已记录的事件数量
+    #[must_use]
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// 写出chrome-tracing JSON文件
+    ///
+    /// 文件名形如`trace_profile_FIFO_20250101_120000.json`，写入指定目录，
+    /// 返回实际写入的完整路径
+    pub fn write_chrome_trace(&self, dir: &Path, algorithm: &str) -> AuditResult<PathBuf> {
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+        let path = dir.join(format!("trace_profile_{algorithm}_{timestamp}.json"));
+
+        let mut trace_events = vec![
+            // 进程元数据事件，tracing界面中显示可读的进程名
+            serde_json::json!({
+                "name": "process_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": 1,
+                "args": { "name": format!("flux-backend {algorithm}") }
+            }),
+        ];
+        for event in &self.events {
+            trace_events.push(serde_json::json!({
+                "name": event.name,
+                "cat": event.category,
+                "ph": "X",
+                "pid": 1,
+                "tid": 1,
+                "ts": event.start_us,
+                "dur": event.duration_us,
+            }));
+        }
+
+        let document = serde_json::json!({
+            "traceEvents": trace_events,
+            "displayTimeUnit": "ms",
+        });
+
+        std::fs::write(&path, serde_json::to_string_pretty(&document)?)
+            .map_err(|e| AuditError::config_error(format!("写入性能剖析文件失败: {e}")))?;
+        Ok(path)
+    }
+}
+
+impl Default for TraceProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_profile_chrome_format() {
+        let mut profiler = TraceProfiler::new();
+        let started = Instant::now();
+        profiler.record("stage", "数据预处理", started);
+        profiler.record("algorithm", "交易处理 1-1000", started);
+        assert_eq!(profiler.event_count(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = profiler.write_chrome_trace(dir.path(), "FIFO").unwrap();
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("trace_profile_FIFO_"));
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        let events = parsed["traceEvents"].as_array().unwrap();
+        // 1条进程元数据 + 2条区间事件
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1]["ph"], "X");
+        assert_eq!(events[1]["name"], "数据预处理");
+        assert!(events[1]["dur"].as_u64().is_some());
+    }
+}
@@ -1,953 +0,0 @@
-//! 统一的Excel文件处理工具
-//! 
-//! 合并所有Excel相关功能，消除重复代码。
-//! 提供完整的Excel读取、写入、数据解析和格式化功能。
-
-use crate::errors::{AuditError, AuditResult};
-use crate::data_models::{Transaction, AuditSummary, FundPoolRecord, Config};
-use crate::utils::TimeProcessor;
-use calamine::{Reader, Xlsx, open_workbook, DataType};
-use chrono::NaiveDateTime;
-use rust_decimal::{Decimal, prelude::ToPrimitive};
-use std::path::Path;
-use log::{info, warn, debug};
-
-// 使用rust_xlsxwriter进行Excel写入
-use rust_xlsxwriter::{Workbook, Worksheet, Format, Color};
-
-/// Excel处理器
-/// 
-/// 负责Excel文件的读取、写入和数据转换
-#[derive(Debug)]
-pub struct ExcelProcessor {
-    /// 配置信息
-    config: Config,
-}
-
-impl ExcelProcessor {
-    /// 创建新的Excel处理器
-    pub fn new(config: Config) -> Self {
-        Self { config }
-    }
-    
-    /// 从Excel文件读取交易记录
-    /// Python来源: src/utils/data_processor.py:39 `df = pd.read_excel(file_path)`
-    /// 
-    /// # Arguments
-    /// * `file_path` - Excel文件路径
-    /// 
-    /// # Returns
-    /// * `AuditResult<Vec<Transaction>>` - 交易记录列表
-    pub fn read_transactions<P: AsRef<Path>>(&self, file_path: P) -> AuditResult<Vec<Transaction>> {
-        let path = file_path.as_ref();
-        // Python来源: src/utils/data_processor.py:38 `audit_logger.info("正在读取Excel文件...")`
-        info!("开始读取Excel文件: {}", path.display());
-        
-        // Python来源: src/utils/data_processor.py:39 `df = pd.read_excel(file_path)`
-        // 打开Excel工作簿
-        let mut workbook: Xlsx<_> = open_workbook(path)
-            .map_err(|e| AuditError::excel_error(format!("无法打开Excel文件: {}", e)))?;
-        
-        // 获取第一个工作表（Python中pandas默认读取第一个sheet）
-        let sheet_names = workbook.sheet_names();
-        if sheet_names.is_empty() {
-            return Err(AuditError::excel_error("Excel文件中没有工作表"));
-        }
-        
-        let sheet_name = &sheet_names[0];
-        info!("读取工作表: {}", sheet_name);
-        
-        let range = workbook.worksheet_range(sheet_name)
-            .map_err(|e| AuditError::excel_error(format!("无法读取工作表: {}", e)))?;
-        
-        // 解析数据
-        self.parse_transactions_from_range(range)
-    }
-    
-    /// 从Excel范围解析交易记录
-    /// Python来源: src/utils/data_processor.py:41-80 的数据预处理逻辑
-    fn parse_transactions_from_range(
-        &self,
-        range: calamine::Range<calamine::Data>
-    ) -> AuditResult<Vec<Transaction>> {
-        let mut transactions = Vec::new();
-        let rows: Vec<_> = range.rows().collect();
-        
-        if rows.is_empty() {
-            return Err(AuditError::excel_error("Excel工作表为空"));
-        }
-        
-        // Python来源: src/utils/data_processor.py:45 检查列名逻辑
-        // 查找表头
-        let header_row = rows.get(0).ok_or_else(|| {
-            AuditError::excel_error("无法获取表头行")
-        })?;
-        
-        let column_indices = self.find_column_indices(header_row)?;
-        info!("找到列索引: {:?}", column_indices);
-        
-        // Python来源: src/utils/data_processor.py:47 `audit_logger.info("正在预处理数据...")`
-        // 解析数据行
-        let data_rows = &rows[1..]; // 跳过表头
-        info!("开始解析 {} 行数据", data_rows.len());
-        
-        // Python来源: src/utils/data_processor.py:203-228 批量处理交易的循环逻辑
-        for (row_idx, row) in data_rows.iter().enumerate() {
-            match self.parse_transaction_row(row, &column_indices) {
-                Ok(transaction) => {
-                    transactions.push(transaction);
-                }
-                Err(e) => {
-                    warn!("解析第{}行数据失败: {}", row_idx + 2, e);
-                    // 继续处理其他行，不中断整个流程
-                }
-            }
-            
-            // Python来源: src/utils/data_processor.py:221-222 进度报告逻辑
-            // 定期报告进度
-            if (row_idx + 1) % 1000 == 0 {
-                info!("⏳ 处理进度: {}/{} ({:.1}%)", 
-                    row_idx + 1, 
-                    data_rows.len(),
-                    (row_idx + 1) as f64 / data_rows.len() as f64 * 100.0
-                );
-            }
-        }
-        
-        // Python来源: src/utils/data_processor.py:80 `audit_logger.info("数据预处理完成")`
-        info!("✅ Excel数据读取完成，共解析 {} 条交易记录", transactions.len());
-        Ok(transactions)
-    }
-    
-    /// 查找列索引
-    /// Python来源: src/utils/data_processor.py:89-103 的列名检查逻辑
-    fn find_column_indices(
-        &self,
-        header_row: &[calamine::Data]
-    ) -> AuditResult<ColumnIndices> {
-        let mut indices = ColumnIndices::new();
-        
-        // Python来源: src/utils/data_processor.py:94-96 遍历列名并记录索引
-        for (idx, cell) in header_row.iter().enumerate() {
-            if let Some(column_name) = cell.as_string() {
-                match column_name {
-                    // Python来源: Config中定义的列名匹配
-                    name if name == self.config.excel_columns.transaction_date_column => {
-                        indices.transaction_date = Some(idx);
-                    }
-                    name if name == self.config.excel_columns.transaction_time_column => {
-                        indices.transaction_time = Some(idx);
-                    }
-                    name if name == self.config.excel_columns.income_amount_column => {
-                        indices.income_amount = Some(idx);
-                    }
-                    name if name == self.config.excel_columns.expense_amount_column => {
-                        indices.expense_amount = Some(idx);
-                    }
-                    name if name == self.config.excel_columns.balance_column => {
-                        indices.balance = Some(idx);
-                    }
-                    name if name == self.config.excel_columns.fund_attribute_column => {
-                        indices.fund_attribute = Some(idx);
-                    }
-                    _ => {} // 忽略其他列
-                }
-            }
-        }
-        
-        // 验证必需列是否都找到了
-        indices.validate()?;
-        Ok(indices)
-    }
-    
-    /// 解析单行交易数据
-    /// Python来源: src/utils/data_processor.py:162-201 处理单行交易逻辑
-    fn parse_transaction_row(
-        &self,
-        row: &[calamine::Data],
-        indices: &ColumnIndices
-    ) -> AuditResult<Transaction> {
-        // Python来源: src/utils/data_processor.py:51 `df['交易日期'] = pd.to_datetime(df['交易日期'])`
-        // 解析交易日期
-        let transaction_date = self.parse_date(
-            row.get(indices.transaction_date.unwrap()).unwrap_or(&calamine::Data::Empty)
-        )?;
-        
-        // Python来源: src/utils/data_processor.py:54 `df['交易时间_格式化'] = df['交易时间'].apply(...)`
-        // 解析交易时间
-        let transaction_time = self.parse_time_string(
-            row.get(indices.transaction_time.unwrap()).unwrap_or(&calamine::Data::Empty)
-        )?;
-        
-        // Python来源: src/utils/data_processor.py:175-176 提取交易金额
-        // 解析金额字段
-        let income_amount = self.parse_decimal(
-            row.get(indices.income_amount.unwrap()).unwrap_or(&calamine::Data::Empty)
-        ).unwrap_or(Decimal::ZERO);
-        
-        let expense_amount = self.parse_decimal(
-            row.get(indices.expense_amount.unwrap()).unwrap_or(&calamine::Data::Empty)
-        ).unwrap_or(Decimal::ZERO);
-        
-        let balance = self.parse_decimal(
-            row.get(indices.balance.unwrap()).unwrap_or(&calamine::Data::Empty)
-        )?;
-        
-        // Python来源: src/utils/data_processor.py:177 `资金属性 = str(row['资金属性'])`
-        // 解析资金属性
-        let fund_attribute = row.get(indices.fund_attribute.unwrap())
-            .and_then(|cell| cell.as_string())
-            .unwrap_or_else(|| String::new());
-        
-        if fund_attribute.is_empty() {
-            return Err(AuditError::validation_error("资金属性不能为空"));
-        }
-        
-        // 创建完整的时间戳（日期+时间）
-        let complete_timestamp = TimeProcessor::create_complete_timestamp(transaction_date, &transaction_time);
-        
-        Ok(Transaction::new(
-            complete_timestamp,
-            transaction_time,
-            income_amount,
-            expense_amount,
-            balance,
-            fund_attribute,
-        ))
-    }
-    
-    /// 解析日期
-    /// Python来源: 
-    /// - src/utils/data_processor.py:51 `df['交易日期'] = pd.to_datetime(df['交易日期'])`
-    /// - src/utils/validators.py:156 `pd.to_datetime(date_value)`
-    /// - 合并多处重复的日期解析逻辑
-    fn parse_date(&self, cell: &calamine::Data) -> AuditResult<NaiveDateTime> {
-        // 使用TimeProcessor统一处理，消除重复代码
-        TimeProcessor::parse_excel_date(cell)
-    }
-    
-    /// 解析时间字符串  
-    /// Python来源:
-    /// - src/models/flow_analyzer.py:91 `解析交易时间(self, 时间值)`
-    /// - src/utils/data_processor.py:54 处理交易时间的逻辑
-    fn parse_time_string(&self, cell: &calamine::Data) -> AuditResult<String> {
-        // 使用TimeProcessor统一处理，消除重复代码
-        Ok(TimeProcessor::parse_transaction_time(cell))
-    }
-    
-    /// 解析十进制数
-    /// Python来源: 
-    /// - src/utils/data_processor.py:175-176 `float(row['交易收入金额'])` 和 `float(row['交易支出金额'])`
-    /// - src/utils/validators.py:128,140 `amount = float(收入金额)` 和 `amount = float(支出金额)`
-    /// - 合并所有数值解析的重复逻辑
-    fn parse_decimal(&self, cell: &calamine::Data) -> AuditResult<Decimal> {
-        match cell {
-            // Python来源: Excel中的数值类型，对应pandas读取的float类型
-            calamine::Data::Float(f) => {
-                Decimal::from_f64_retain(*f)
-                    .ok_or_else(|| AuditError::calculation_error(format!("无法转换数值: {}", f)))
-            }
-            calamine::Data::Int(i) => {
-                Ok(Decimal::from(*i))
-            }
-            // Python来源: src/utils/validators.py:263 处理字符串数值的逻辑
-            calamine::Data::String(s) => {
-                // 移除千位分隔符和其他非数字字符（Python中pandas会自动处理）
-                let cleaned = s.chars()
-                    .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
-                    .collect::<String>();
-                
-                if cleaned.is_empty() {
-                    return Ok(Decimal::ZERO);
-                }
-                
-                cleaned.parse::<Decimal>()
-                    .map_err(|_| AuditError::calculation_error(format!("无法解析数值: {}", s)))
-            }
-            // Python来源: pandas中的NaN值处理，在Python中pd.isna()检查后返回0.0
-            calamine::Data::Empty => Ok(Decimal::ZERO),
-            _ => Err(AuditError::calculation_error("不支持的数值格式"))
-        }
-    }
-    
-    /// 导出分析结果到Excel
-    /// Python来源: src/utils/data_processor.py:315-335 `保存结果` 和 `df.to_excel(output_file, index=False)`
-    pub fn export_analysis_results<P: AsRef<Path>>(
-        &self,
-        transactions: &[Transaction],
-        summary: &AuditSummary,
-        output_path: P,
-    ) -> AuditResult<()> {
-        let path = output_path.as_ref();
-        // Python来源: src/utils/data_processor.py:331 `audit_logger.info(f"分析结果已保存到: {output_file}")`
-        info!("开始导出分析结果到: {}", path.display());
-        
-        // 使用rust_xlsxwriter创建真正的Excel文件
-        let mut workbook = Workbook::new();
-        
-        // 创建主工作表
-        let worksheet = workbook.add_worksheet();
-        
-        // 设置格式
-        let header_format = Format::new()
-            .set_bold()
-            .set_background_color(Color::Blue)
-            .set_font_color(Color::White);
-        
-        let number_format = Format::new()
-            .set_num_format("#,##0.00");
-        
-        let date_format = Format::new()
-            .set_num_format("yyyy-mm-dd");
-        
-        // 写入表头和数据
-        self.write_excel_headers(worksheet, &header_format)?;
-        self.write_excel_data(worksheet, transactions, &number_format, &date_format)?;
-        
-        // 创建摘要工作表
-        self.write_summary_worksheet(&mut workbook, summary)?;
-        
-        // 保存文件
-        workbook.save(path)
-            .map_err(|e| AuditError::excel_error(format!("保存Excel文件失败: {}", e)))?;
-        
-        info!("✅ Excel分析结果导出完成");
-        Ok(())
-    }
-    
-    /// 写入Excel表头
-    /// Python来源: src/utils/data_processor.py 结果DataFrame的列名
-    fn write_excel_headers(&self, worksheet: &mut Worksheet, format: &Format) -> AuditResult<()> {
-        let headers = [
-            "交易时间", "交易收入金额", "交易支出金额", "余额", "资金属性",
-            "个人资金占比", "公司资金占比", "行为性质", "累计挪用", "累计垫付",
-            "累计已归还公司本金", "累计已归还个人本金", "总计个人应分配利润", 
-            "总计公司应分配利润", "个人余额", "公司余额", "总余额", "资金缺口"
-        ];
-        
-        for (col, header) in headers.iter().enumerate() {
-            worksheet.write_string(0, col as u16, *header)
-                .map_err(|e| AuditError::excel_error(format!("写入表头失败: {}", e)))?;
-        }
-        
-        Ok(())
-    }
-    
-    /// 写入Excel数据
-    /// Python来源: src/utils/data_processor.py:203-228 逐行写入交易数据
-    fn write_excel_data(
-        &self,
-        worksheet: &mut Worksheet,
-        transactions: &[Transaction],
-        number_format: &Format,
-        date_format: &Format,
-    ) -> AuditResult<()> {
-        for (row_idx, tx) in transactions.iter().enumerate() {
-            let row = (row_idx + 1) as u32; // 跳过表头行
-            
-            // Python来源: 对应DataFrame各列的数据写入
-            // 写入合并的日期时间（修复：使用transaction_time如果已经是完整格式，否则合并）
-            let datetime_str = if tx.transaction_time.contains('/') || tx.transaction_time.contains('-') {
-                tx.transaction_time.clone()  // 已经是完整格式
-            } else {
-                tx.transaction_date.format("%Y/%m/%d %H:%M:%S").to_string()  // 合并格式
-            };
-            worksheet.write_string(row, 0, &datetime_str)
-                .map_err(|e| AuditError::excel_error(format!("写入日期时间失败: {}", e)))?;
-            
-            worksheet.write_number(row, 1, tx.income_amount.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入收入金额失败: {}", e)))?;
-            
-            worksheet.write_number(row, 2, tx.expense_amount.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入支出金额失败: {}", e)))?;
-            
-            worksheet.write_number(row, 3, tx.balance.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入余额失败: {}", e)))?;
-            
-            worksheet.write_string(row, 4, &tx.fund_attribute)
-                .map_err(|e| AuditError::excel_error(format!("写入资金属性失败: {}", e)))?;
-            
-            // 写入计算结果字段（修复：调整列索引，因为删除了交易日期列）
-            let personal_ratio = tx.personal_ratio.unwrap_or(Decimal::ZERO);
-            let company_ratio = tx.company_ratio.unwrap_or(Decimal::ZERO);
-            let behavior = tx.behavior_nature.as_deref().unwrap_or("");
-            
-            worksheet.write_number(row, 5, personal_ratio.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 6, company_ratio.to_f64().unwrap_or(0.0))?;
-            worksheet.write_string(row, 7, behavior)?;
-            
-            // 累计数据字段
-            let cum_misap = tx.cumulative_misappropriation.unwrap_or(Decimal::ZERO);
-            let cum_advance = tx.cumulative_advance.unwrap_or(Decimal::ZERO);
-            let cum_company_returned = tx.cumulative_company_principal_returned.unwrap_or(Decimal::ZERO);
-            let cum_personal_returned = tx.cumulative_personal_principal_returned.unwrap_or(Decimal::ZERO);
-            let total_personal_profit = tx.cumulative_personal_profit.unwrap_or(Decimal::ZERO);
-            let total_company_profit = tx.cumulative_company_profit.unwrap_or(Decimal::ZERO);
-            let personal_balance = tx.personal_balance.unwrap_or(Decimal::ZERO);
-            let company_balance = tx.company_balance.unwrap_or(Decimal::ZERO);
-            let total_balance = tx.personal_balance.unwrap_or(Decimal::ZERO) + tx.company_balance.unwrap_or(Decimal::ZERO);
-            let funding_gap = tx.funding_gap.unwrap_or(Decimal::ZERO);
-            
-            worksheet.write_number(row, 8, cum_misap.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 9, cum_advance.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 10, cum_company_returned.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 11, cum_personal_returned.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 12, total_personal_profit.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 13, total_company_profit.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 14, personal_balance.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 15, company_balance.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 16, total_balance.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(row, 17, funding_gap.to_f64().unwrap_or(0.0))?;
-            
-            // 定期报告进度
-            if row % 1000 == 0 {
-                debug!("Excel写入进度: {}/{}", row, transactions.len());
-            }
-        }
-        
-        Ok(())
-    }
-    
-    /// 创建摘要工作表
-    /// Python来源: AuditSummary的各个字段
-    fn write_summary_worksheet(&self, workbook: &mut Workbook, summary: &AuditSummary) -> AuditResult<()> {
-        let worksheet = workbook.add_worksheet().set_name("分析摘要")?;
-        
-        let header_format = Format::new()
-            .set_bold()
-            .set_background_color(Color::Gray);
-        
-        let number_format = Format::new()
-            .set_num_format("#,##0.00");
-        
-        // 写入摘要数据
-        let summary_items = [
-            ("个人余额", summary.personal_balance),
-            ("公司余额", summary.company_balance),
-            ("总余额", summary.total_balance),
-            ("累计挪用金额", summary.total_misappropriation),
-            ("累计垫付金额", summary.total_advance_payment),
-            ("累计归还公司本金", summary.total_company_principal_returned),
-            ("累计归还个人本金", summary.total_personal_principal_returned),
-            ("总计个人利润", summary.total_personal_profit),
-            ("总计公司利润", summary.total_company_profit),
-            ("资金缺口", summary.funding_gap),
-        ];
-        
-        worksheet.write_string(0, 0, "指标")?;
-        worksheet.write_string(0, 1, "数值")?;
-        
-        for (row, (name, value)) in summary_items.iter().enumerate() {
-            let row = (row + 1) as u32;
-            worksheet.write_string(row, 0, *name)?;
-            worksheet.write_number(row, 1, value.to_f64().unwrap_or(0.0))?;
-        }
-        
-        Ok(())
-    }
-    
-    /// 写入结果数据
-    fn write_result_data(
-        &self,
-        worksheet: &mut Worksheet,
-        transactions: &[Transaction],
-        number_format: &Format,
-        date_format: &Format,
-    ) -> AuditResult<()> {
-        for (row, tx) in transactions.iter().enumerate() {
-            let row = (row + 1) as u32; // 跳过表头行
-            
-            // 写入原始数据
-            worksheet.write_string(row, 0, &tx.transaction_date.format("%Y-%m-%d %H:%M:%S").to_string())
-                .map_err(|e| AuditError::excel_error(format!("写入日期失败: {}", e)))?;
-            
-            worksheet.write_string(row, 1, &tx.transaction_time)
-                .map_err(|e| AuditError::excel_error(format!("写入时间失败: {}", e)))?;
-            
-            worksheet.write_number(row, 2, tx.income_amount.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入收入金额失败: {}", e)))?;
-            
-            worksheet.write_number(row, 3, tx.expense_amount.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入支出金额失败: {}", e)))?;
-            
-            worksheet.write_number(row, 4, tx.balance.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入余额失败: {}", e)))?;
-            
-            worksheet.write_string(row, 5, &tx.fund_attribute)
-                .map_err(|e| AuditError::excel_error(format!("写入资金属性失败: {}", e)))?;
-            
-            // 写入计算字段
-            let col_offset = 6;
-            if let Some(personal_ratio) = tx.personal_ratio {
-                worksheet.write_number(row, col_offset, personal_ratio.to_f64().unwrap_or(0.0))
-                    .map_err(|e| AuditError::excel_error(format!("写入个人占比失败: {}", e)))?;
-            }
-            
-            if let Some(company_ratio) = tx.company_ratio {
-                worksheet.write_number(row, col_offset + 1, company_ratio.to_f64().unwrap_or(0.0))
-                    .map_err(|e| AuditError::excel_error(format!("写入公司占比失败: {}", e)))?;
-            }
-            
-            if let Some(behavior) = &tx.behavior_nature {
-                worksheet.write_string(row, col_offset + 2, behavior)
-                    .map_err(|e| AuditError::excel_error(format!("写入行为性质失败: {}", e)))?;
-            }
-            
-            // 继续写入其他计算字段...
-            if let Some(cum_misap) = tx.cumulative_misappropriation {
-                worksheet.write_number(row, col_offset + 3, cum_misap.to_f64().unwrap_or(0.0))
-                    .map_err(|e| AuditError::excel_error(format!("写入累计挪用失败: {}", e)))?;
-            }
-            
-            // 为了简洁，这里省略其他字段的写入代码，实际实现中需要完整写入所有字段
-            
-            // 定期报告进度
-            if row % 1000 == 0 {
-                debug!("Excel写入进度: {}/{}", row, transactions.len());
-            }
-        }
-        
-        Ok(())
-    }
-    
-    /// 写入摘要工作表
-    fn write_summary_sheet(&self, workbook: &mut Workbook, summary: &AuditSummary) -> AuditResult<()> {
-        let worksheet = workbook.add_worksheet();
-        
-        // 格式化功能暂时简化
-        // let header_format = Format::new().set_bold();
-        // let number_format = Format::new();
-        
-        // 写入摘要数据
-        let summary_items = [
-            ("个人余额", summary.personal_balance),
-            ("公司余额", summary.company_balance),
-            ("总余额", summary.total_balance),
-            ("累计挪用金额", summary.total_misappropriation),
-            ("累计垫付金额", summary.total_advance_payment),
-            ("累计归还公司本金", summary.total_company_principal_returned),
-            ("累计归还个人本金", summary.total_personal_principal_returned),
-            ("总计个人利润", summary.total_personal_profit),
-            ("总计公司利润", summary.total_company_profit),
-            ("资金缺口", summary.funding_gap),
-        ];
-        
-        worksheet.write_string(0, 0, "指标")
-            .map_err(|e| AuditError::excel_error(format!("写入摘要表头失败: {}", e)))?;
-        worksheet.write_string(0, 1, "数值")
-            .map_err(|e| AuditError::excel_error(format!("写入摘要表头失败: {}", e)))?;
-        
-        for (row, (name, value)) in summary_items.iter().enumerate() {
-            let row = (row + 1) as u32;
-            worksheet.write_string(row, 0, *name)
-                .map_err(|e| AuditError::excel_error(format!("写入摘要名称失败: {}", e)))?;
-            worksheet.write_number(row, 1, value.to_f64().unwrap_or(0.0))
-                .map_err(|e| AuditError::excel_error(format!("写入摘要数值失败: {}", e)))?;
-        }
-        
-        Ok(())
-    }
-    
-    /// 导出资金池记录
-    pub fn export_fund_pool_records<P: AsRef<Path>>(
-        &self,
-        records: &[FundPoolRecord],
-        output_path: P,
-    ) -> AuditResult<()> {
-        let path = output_path.as_ref();
-        info!("开始导出资金池记录到: {}", path.display());
-        
-        let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
-        
-        // 格式化功能暂时简化
-        // let header_format = Format::new().set_bold();
-        // let number_format = Format::new();
-        
-        // 写入表头（12个字段，与场外资金池记录格式一致）
-        let headers = [
-            "交易时间", "资金池名称", "入金", "出金", "总余额",
-            "个人余额", "公司余额", "资金占比", "行为性质", "累计申购", "累计赎回", "净盈亏"
-        ];
-        
-        for (col, header) in headers.iter().enumerate() {
-            worksheet.write_string(0, col as u16, *header)?;
-        }
-        
-        // 按资金池分组并按时间排序
-        use std::collections::HashMap;
-        let mut grouped_records: HashMap<String, Vec<&FundPoolRecord>> = HashMap::new();
-        
-        for record in records {
-            grouped_records.entry(record.pool_name.clone())
-                           .or_insert_with(Vec::new)
-                           .push(record);
-        }
-        
-        // 对每个资金池内的记录按交易时间排序
-        for pool_records in grouped_records.values_mut() {
-            pool_records.sort_by(|a, b| a.transaction_time.cmp(&b.transaction_time));
-        }
-        
-        let mut sorted_pools: Vec<_> = grouped_records.keys().collect();
-        sorted_pools.sort();
-        
-        let mut current_row = 1u32;
-        
-        // 按分组写入数据
-        for pool_name in sorted_pools {
-            let pool_records = &grouped_records[pool_name];
-            
-            // 写入该资金池的所有记录（12个字段）
-            for record in pool_records {
-                worksheet.write_string(current_row, 0, &record.transaction_time)?;
-                worksheet.write_string(current_row, 1, &record.pool_name)?;
-                worksheet.write_number(current_row, 2, record.inflow.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 3, record.outflow.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 4, record.total_balance.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 5, record.personal_balance.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 6, record.company_balance.to_f64().unwrap_or(0.0))?;
-                worksheet.write_string(current_row, 7, &record.single_fund_ratio)?; // 资金占比
-                worksheet.write_string(current_row, 8, &record.behavior_nature)?;
-                worksheet.write_number(current_row, 9, record.cumulative_purchase.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 10, record.cumulative_redemption.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 11, record.net_profit_loss.to_f64().unwrap_or(0.0))?;
-                current_row += 1;
-            }
-            
-            // 添加总计行（参考场外资金池的实现）
-            if let Some(last_record) = pool_records.last() {
-                let total_purchase = last_record.cumulative_purchase;
-                let total_redemption = last_record.cumulative_redemption;
-                let final_balance = last_record.total_balance;
-                let profit_loss = last_record.net_profit_loss;
-                let status = if profit_loss > rust_decimal::Decimal::ZERO {
-                    "盈利"
-                } else if profit_loss < rust_decimal::Decimal::ZERO {
-                    "亏损"
-                } else {
-                    "持平"
-                };
-                
-                // 计算个人、公司盈亏金额（参考场外资金池逻辑）
-                let (cumulative_personal_profit_loss, cumulative_company_profit_loss) = if profit_loss != rust_decimal::Decimal::ZERO {
-                    // 计算个人、公司占比（从最后一条记录获取）
-                    let personal_ratio = if last_record.total_balance != rust_decimal::Decimal::ZERO {
-                        last_record.personal_balance / last_record.total_balance
-                    } else {
-                        rust_decimal::Decimal::new(5, 1) // 默认50%
-                    };
-                    let company_ratio = rust_decimal::Decimal::ONE - personal_ratio;
-                    
-                    // 分配盈亏到个人和公司
-                    let total_abs = profit_loss.abs();
-                    let personal_share = total_abs * personal_ratio;
-                    let company_share = total_abs * company_ratio;
-                    
-                    (personal_share, company_share)
-                } else {
-                    (rust_decimal::Decimal::ZERO, rust_decimal::Decimal::ZERO)
-                };
-                
-                // 总计行（12个字段，与场外资金池格式一致）
-                worksheet.write_string(current_row, 0, "── 总计 ──")?;
-                worksheet.write_string(current_row, 1, &format!("{} 汇总", pool_name))?;
-                worksheet.write_string(current_row, 2, &format!("总申购: ¥{:.0}", total_purchase))?;
-                worksheet.write_string(current_row, 3, &format!("总赎回: ¥{:.0}", total_redemption))?;
-                worksheet.write_string(current_row, 4, &format!("最终余额: ¥{:.0}", final_balance))?;
-                worksheet.write_string(current_row, 5, &format!("个人{}: ¥{:.0}", status, cumulative_personal_profit_loss))?;
-                worksheet.write_string(current_row, 6, &format!("公司{}: ¥{:.0}", status, cumulative_company_profit_loss))?;
-                worksheet.write_string(current_row, 7, &format!("净盈亏: ¥{:.0}", profit_loss))?;
-                worksheet.write_string(current_row, 8, &format!("状态: {}", status))?;
-                worksheet.write_number(current_row, 9, total_purchase.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 10, total_redemption.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 11, profit_loss.to_f64().unwrap_or(0.0))?;
-                current_row += 1;
-                
-                // 添加空白行分隔（参考场外资金池的做法）
-                for col in 0..12 {
-                    worksheet.write_string(current_row, col, "")?;
-                }
-                current_row += 1;
-            }
-        }
-        
-        // 添加全局总计行
-        if !records.is_empty() {
-            let mut pool_final_values: HashMap<String, &FundPoolRecord> = HashMap::new();
-            
-            // 为每个资金池找到最后一条记录（最终状态）
-            for record in records {
-                match pool_final_values.entry(record.pool_name.clone()) {
-                    std::collections::hash_map::Entry::Vacant(e) => {
-                        e.insert(record);
-                    }
-                    std::collections::hash_map::Entry::Occupied(mut e) => {
-                        // 比较交易时间，保留最新的记录
-                        if record.transaction_time > e.get().transaction_time {
-                            e.insert(record);
-                        }
-                    }
-                }
-            }
-            
-            // 计算全局总计（基于每个资金池的最终状态）
-            let global_total_balance: rust_decimal::Decimal = pool_final_values.values().map(|r| r.total_balance).sum();
-            let global_total_purchase: rust_decimal::Decimal = pool_final_values.values().map(|r| r.cumulative_purchase).sum();
-            let global_total_redemption: rust_decimal::Decimal = pool_final_values.values().map(|r| r.cumulative_redemption).sum();
-            let global_net_profit_loss = global_total_redemption - global_total_purchase;
-            
-            worksheet.write_string(current_row, 0, "═════ 全局总计 ═════")?;
-            worksheet.write_string(current_row, 1, &format!("共 {} 个资金池", pool_final_values.len()))?;
-            worksheet.write_string(current_row, 2, "")?;
-            worksheet.write_string(current_row, 3, "")?;
-            worksheet.write_number(current_row, 4, global_total_balance.to_f64().unwrap_or(0.0))?;
-            worksheet.write_string(current_row, 5, &format!("总余额: ¥{:.2}", global_total_balance.to_f64().unwrap_or(0.0)))?;
-            worksheet.write_string(current_row, 6, &format!("净盈亏: ¥{:.2}", global_net_profit_loss.to_f64().unwrap_or(0.0)))?;
-            worksheet.write_string(current_row, 7, "全局汇总")?;
-            worksheet.write_number(current_row, 8, global_total_purchase.to_f64().unwrap_or(0.0))?;
-            worksheet.write_number(current_row, 9, global_total_redemption.to_f64().unwrap_or(0.0))?;
-        }
-        
-        workbook.save(path)
-            .map_err(|e| AuditError::excel_error(format!("保存资金池记录失败: {}", e)))?;
-        
-        let pool_count = grouped_records.len();
-        info!("✅ 资金池记录导出完成，共 {} 条记录，按 {} 个资金池分组，已为每个池添加总计行", records.len(), pool_count);
-        Ok(())
-    }
-    
-    /// 导出场外资金池记录到Excel
-    /// Python来源: src/models/fifo_algorithm.py:651 `生成场外资金池记录Excel`
-    /// 
-    /// # Arguments
-    /// * `record_manager` - 场外资金池记录管理器
-    /// * `output_path` - 输出文件路径
-    /// 
-    /// # Returns
-    /// * `AuditResult<()>` - 导出结果
-    pub fn export_offsite_pool_records<P: AsRef<Path>>(
-        &self,
-        record_manager: &crate::data_models::OffsitePoolRecordManager,
-        output_path: P,
-    ) -> AuditResult<()> {
-        let path = output_path.as_ref();
-        info!("开始导出场外资金池记录到: {}", path.display());
-        
-        if record_manager.record_count() == 0 {
-            info!("📋 没有场外资金池记录，跳过Excel生成");
-            return Ok(());
-        }
-        
-        info!("📋 检测到 {} 条场外资金池记录，开始处理...", record_manager.record_count());
-        
-        let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
-        
-        // Python来源: 写入表头
-        let headers = [
-            "交易时间", "资金池名称", "入金", "出金", "总余额",
-            "个人余额", "公司余额", "资金占比", "行为性质", "累计申购", "累计赎回", "净盈亏"
-        ];
-        
-        for (col, header) in headers.iter().enumerate() {
-            worksheet.write_string(0, col as u16, *header)?;
-        }
-        
-        // Python来源: 按资金池名称分组，每组内按时间排序
-        let grouped_records = record_manager.group_by_pool();
-        let mut sorted_pools: Vec<_> = grouped_records.keys().collect();
-        sorted_pools.sort();
-        
-        info!("📋 按资金池分组: 共 {} 个资金池", sorted_pools.len());
-        for pool_name in &sorted_pools {
-            info!("  └─ {}: {} 条记录", pool_name, grouped_records[*pool_name].len());
-        }
-        
-        let mut current_row = 1u32;
-        
-        // 按分组写入数据
-        for pool_name in sorted_pools {
-            let pool_records = &grouped_records[pool_name];
-            
-            // 写入该资金池的所有记录
-            for record in pool_records {
-                worksheet.write_string(current_row, 0, &record.transaction_time)?;
-                worksheet.write_string(current_row, 1, &record.pool_name)?;
-                
-                // 入金金额
-                worksheet.write_number(
-                    current_row, 2, 
-                    record.inflow.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 出金金额
-                worksheet.write_number(
-                    current_row, 3, 
-                    record.outflow.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 总余额
-                worksheet.write_number(
-                    current_row, 4, 
-                    record.total_balance.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 个人余额
-                worksheet.write_number(
-                    current_row, 5, 
-                    record.personal_balance.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 公司余额
-                worksheet.write_number(
-                    current_row, 6, 
-                    record.company_balance.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 资金占比
-                worksheet.write_string(current_row, 7, &record.fund_ratio)?;
-                
-                // 行为性质
-                worksheet.write_string(current_row, 8, &record.behavior_nature)?;
-                
-                // 累计申购
-                worksheet.write_number(
-                    current_row, 9, 
-                    record.cumulative_purchase.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 累计赎回
-                worksheet.write_number(
-                    current_row, 10, 
-                    record.cumulative_redemption.to_f64().unwrap_or(0.0)
-                )?;
-                
-                // 净盈亏
-                worksheet.write_number(
-                    current_row, 11, 
-                    record.net_profit_loss.to_f64().unwrap_or(0.0)
-                )?;
-                
-                current_row += 1;
-            }
-            
-            // Python来源: 添加总计行
-            if let Some(stats) = record_manager.calculate_pool_stats(pool_name) {
-                // 总计行
-                worksheet.write_string(current_row, 0, "── 总计 ──")?;
-                worksheet.write_string(current_row, 1, &format!("{} 汇总", pool_name))?;
-                worksheet.write_string(current_row, 2, &format!("总申购: ¥{:.0}", stats.total_purchase))?;
-                worksheet.write_string(current_row, 3, &format!("总赎回: ¥{:.0}", stats.total_redemption))?;
-                worksheet.write_string(current_row, 4, &format!("最终余额: ¥{:.0}", stats.final_balance))?;
-                worksheet.write_string(current_row, 5, &format!("个人{}: ¥{:.0}", stats.status, stats.cumulative_personal_profit_loss))?;
-                worksheet.write_string(current_row, 6, &format!("公司{}: ¥{:.0}", stats.status, stats.cumulative_company_profit_loss))?;
-                worksheet.write_string(current_row, 7, &format!("净盈亏: ¥{:.0}", stats.profit_loss))?;
-                worksheet.write_string(current_row, 8, &format!("状态: {}", stats.status))?;
-                worksheet.write_number(current_row, 9, stats.total_purchase.to_f64().unwrap_or(0.0))?;
-                worksheet.write_number(current_row, 10, stats.total_redemption.to_f64().unwrap_or(0.0))?;
-                
-                current_row += 1;
-                
-                // Python来源: 添加空白行分隔
-                for col in 0..11 {
-                    worksheet.write_string(current_row, col, "")?;
-                }
-                current_row += 1;
-            }
-        }
-        
-        workbook.save(path)
-            .map_err(|e| AuditError::excel_error(format!("保存场外资金池记录失败: {}", e)))?;
-        
-        info!("✅ 场外资金池记录已保存至: {}", path.display());
-        info!("📊 共记录 {} 笔资金池交易，按资金池分组排序", record_manager.record_count());
-        Ok(())
-    }
-}
-
-/// 列索引结构
-#[derive(Debug, Clone)]
-struct ColumnIndices {
-    transaction_date: Option<usize>,
-    transaction_time: Option<usize>,
-    income_amount: Option<usize>,
-    expense_amount: Option<usize>,
-    balance: Option<usize>,
-    fund_attribute: Option<usize>,
-}
-
-impl ColumnIndices {
-    fn new() -> Self {
-        Self {
-            transaction_date: None,
-            transaction_time: None,
-            income_amount: None,
-            expense_amount: None,
-            balance: None,
-            fund_attribute: None,
-        }
-    }
-    
-    fn validate(&self) -> AuditResult<()> {
-        let required_columns = [
-            ("交易日期", self.transaction_date),
-            ("交易时间", self.transaction_time),
-            ("交易收入金额", self.income_amount),
-            ("交易支出金额", self.expense_amount),
-            ("余额", self.balance),
-            ("资金属性", self.fund_attribute),
-        ];
-        
-        for (name, index) in &required_columns {
-            if index.is_none() {
-                return Err(AuditError::validation_error(
-                    format!("找不到必需的列: {}", name)
-                ));
-            }
-        }
-        
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
-    #[test]
-    fn test_column_indices_validation() {
-        let mut indices = ColumnIndices::new();
-        assert!(indices.validate().is_err());
-        
-        indices.transaction_date = Some(0);
-        indices.transaction_time = Some(1);
-        indices.income_amount = Some(2);
-        indices.expense_amount = Some(3);
-        indices.balance = Some(4);
-        indices.fund_attribute = Some(5);
-        
-        assert!(indices.validate().is_ok());
-    }
-    
-    #[test]
-    fn test_decimal_parsing() {
-        let config = Config::new();
-        let processor = ExcelProcessor::new(config);
-        
-        // 测试数字解析
-        let float_data = calamine::Data::Float(123.45);
-        let result = processor.parse_decimal(&float_data).unwrap();
-        assert_eq!(result, Decimal::from_f64_retain(123.45).unwrap());
-        
-        // 测试字符串解析
-        let string_data = calamine::Data::String("1,234.56".to_string());
-        let result = processor.parse_decimal(&string_data).unwrap();
-        assert_eq!(result, Decimal::from_f64_retain(1234.56).unwrap());
-        
-        // 测试空值
-        let empty_data = calamine::Data::Empty;
-        let result = processor.parse_decimal(&empty_data).unwrap();
-        assert_eq!(result, Decimal::ZERO);
-    }
-}
\ No newline at end of file
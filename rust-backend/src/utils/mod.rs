@@ -1,14 +0,0 @@
-//! 工具模块
-//! 
-//! 提供系统所需的各种工具函数和辅助类。
-
-pub mod excel_processor;     // API已修复，重新启用
-pub mod time_processor;      // 时间处理模块
-pub mod unified_validator;   // 统一数据验证器模块
-pub mod logger;              // 日志记录模块
-
-// 重新导出主要工具
-pub use excel_processor::*;
-pub use time_processor::*;
-pub use unified_validator::*;
-pub use logger::*;
\ No newline at end of file
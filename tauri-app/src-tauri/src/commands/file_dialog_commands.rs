@@ -0,0 +1,99 @@
+//! 文件对话框相关的Tauri命令
+//!
+//! 打开对话框的默认目录和扩展名过滤器由Rust侧统一下发，
+//! 避免各前端页面各自维护一份不一致的配置
+
+use tauri::{command, State};
+use flux_backend::TauriFileDialogConfig;
+use log::info;
+use crate::AppState;
+
+/// 计算应用工作目录（与check_system_env保持一致的逻辑）
+fn workspace_directory() -> std::path::PathBuf {
+    if cfg!(debug_assertions) {
+        // 开发模式：使用当前项目目录
+        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+    } else {
+        // 生产模式：使用用户文档目录，避免权限问题
+        dirs::document_dir()
+            .map(|d| d.join("FLUX Analysis System"))
+            .unwrap_or_else(|| {
+                dirs::home_dir()
+                    .map(|h| h.join("FLUX Analysis System"))
+                    .unwrap_or_else(|| std::path::PathBuf::from("."))
+            })
+    }
+}
+
+/// Tauri命令：获取打开流水文件对话框的推荐配置
+#[command]
+pub async fn get_open_dialog_config(state: State<'_, AppState>) -> Result<TauriFileDialogConfig, String> {
+    let workspace_dir = workspace_directory();
+
+    // 确保默认输入目录存在，首次使用时对话框才能正常定位
+    let input_dir = workspace_dir.join("input");
+    if let Err(e) = std::fs::create_dir_all(&input_dir) {
+        info!("无法创建默认输入目录 {}: {}", input_dir.display(), e);
+    }
+
+    // 上次使用的目录如果已失效则忽略
+    let last_used = {
+        let last_dir = state.last_dialog_directory.lock().await;
+        last_dir.clone().filter(|dir| std::path::Path::new(dir).is_dir())
+    };
+
+    Ok(TauriFileDialogConfig::open_statement_dialog(
+        &workspace_dir.to_string_lossy(),
+        last_used,
+    ))
+}
+
+/// Tauri命令：记录上次使用的对话框目录（用户选择文件后调用）
+#[command]
+pub async fn set_last_dialog_directory(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    // 允许传入文件路径，自动取其所在目录
+    let file_path = std::path::PathBuf::from(&path);
+    let directory = if file_path.is_file() {
+        file_path.parent().map(|p| p.to_path_buf()).unwrap_or(file_path)
+    } else {
+        file_path
+    };
+
+    let mut last_dir = state.last_dialog_directory.lock().await;
+    *last_dir = Some(directory.to_string_lossy().to_string());
+    info!("记录上次使用的对话框目录: {}", directory.display());
+    Ok(())
+}
+
+/// Tauri命令：列出Excel文件中的全部工作表名
+///
+/// 用户选中多表工作簿后，前端据此展示工作表选择框；
+/// 选中的表名通过`TauriAuditConfig::sheet_name`传入分析
+#[command]
+pub async fn list_excel_sheets(file_path: String) -> Result<Vec<String>, String> {
+    let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+    processor.list_sheets(&file_path).map_err(|e| e.to_string())
+}
+
+/// Tauri命令：预检输入文件（不执行完整分析）
+///
+/// 返回工作表列表、表头位置、各列匹配/缺失情况、数据行数、日期范围
+/// 与前20行预览，前端据此在运行前展示将要发生什么并尽早暴露映射问题
+#[command]
+pub async fn inspect_input_file(
+    file_path: String,
+    sheet_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<flux_backend::FileInspection, String> {
+    let mut config = flux_backend::Config::new();
+    // 与正式分析一致：已加载的列映射档案参与预检，报告的列匹配情况才有参考价值
+    if let Some(profile) = state.column_mapping.lock().await.as_ref() {
+        config.excel_columns.apply_mapping_profile(profile);
+    }
+
+    let mut processor = flux_backend::ExcelProcessor::new(config);
+    if let Some(sheet) = sheet_name {
+        processor = processor.with_sheet_name(sheet);
+    }
+    processor.inspect_file(&file_path).map_err(|e| e.to_string())
+}
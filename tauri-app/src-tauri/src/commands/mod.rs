@@ -2,7 +2,9 @@
 //! 
 //! 组织所有的Tauri命令，避免main.rs过度臃肿
 
+pub mod file_dialog_commands;
 pub mod time_point_commands;
 
 // 重新导出所有命令
+pub use file_dialog_commands::*;
 pub use time_point_commands::*;
\ No newline at end of file
@@ -3,7 +3,7 @@
 //! 使用Rust后端直接实现，不再依赖Python
 
 use tauri::{command, State};
-use flux_backend::{TimePointService, TimePointQueryRequest, TimePointQueryResult};
+use flux_backend::{FileCache, TimePointService, TimePointQueryRequest, TimePointQueryResult};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::{AppState, generate_id, QueryHistory, TimePointQuery, QueryResult};
@@ -11,24 +11,180 @@ use crate::{AppState, generate_id, QueryHistory, TimePointQuery, QueryResult};
 use chrono::Utc;
 use log::{info, error, warn};
 
+/// Tauri命令：计算文件指纹（路径+算法+修改时间+大小）
+/// 
+/// 前端保存上次分析时的指纹，再次查询前对比即可检测"文件已变更"，
+/// 提示用户重新运行分析，而不是静默依赖路径+算法是否相同
+#[command]
+pub async fn compute_fingerprint(path: String, algorithm: String) -> Result<String, String> {
+    let cache = FileCache::new();
+    match cache.generate_fingerprint(&path, &algorithm) {
+        Ok(fingerprint) => {
+            info!("文件指纹计算完成: {}", fingerprint);
+            Ok(fingerprint)
+        }
+        Err(e) => {
+            warn!("文件指纹计算失败: {}", e);
+            Err(format!("文件指纹计算失败: {}", e))
+        }
+    }
+}
+
+/// Tauri命令：按源表行号获取处理后的交易（"跳转到行"功能）
+/// 
+/// 依赖已有的时点查询服务实例及其文件缓存，缓存未命中时返回错误，
+/// 前端应提示用户先执行一次时点查询
+#[command]
+pub async fn get_transaction_by_source_row(
+    fingerprint: String,
+    file_path: String,
+    algorithm: String,
+    excel_row: usize,
+    state: State<'_, AppState>
+) -> Result<flux_backend::SourceRowQueryResult, String> {
+    let services = state.time_point_services.lock().await;
+    let service = services.get(&(file_path.clone(), algorithm.clone()))
+        .ok_or_else(|| format!("时点查询服务不存在: 文件={}, 算法={}，请先执行一次时点查询", file_path, algorithm))?;
+    service.get_transaction_by_source_row(&fingerprint, &algorithm, excel_row)
+        .map_err(|e| {
+            warn!("源表行查询失败: {}", e);
+            e.to_string()
+        })
+}
+
+/// Tauri命令：分页获取指定资金池截至时点的记录
+///
+/// 活跃产品可能有数万条申购/赎回记录，时点查询载荷只内联末段，
+/// 前端翻页或按日期筛选时走本命令，在服务端完成过滤与切片
+#[command]
+pub async fn get_pool_records_page(
+    fingerprint: String,
+    request: flux_backend::PoolRecordsPageRequest,
+    state: State<'_, AppState>
+) -> Result<flux_backend::PoolRecordsPage, String> {
+    let services = state.time_point_services.lock().await;
+    let service = services.get(&(request.file_path.clone(), request.algorithm.clone()))
+        .ok_or_else(|| format!("时点查询服务不存在: 文件={}, 算法={}，请先执行一次时点查询", request.file_path, request.algorithm))?;
+    service.get_pool_records_page(&fingerprint, &request)
+        .map_err(|e| {
+            warn!("资金池记录分页查询失败: {}", e);
+            e.to_string()
+        })
+}
+
 /// Tauri命令：清除缓存状态（当用户选择新文件时调用）
+/// 
+/// 作废全部已注册的运行绑定，后续时点查询必须基于新的分析运行
 #[command]
 pub async fn clear_query_cache(state: State<'_, AppState>) -> Result<(), String> {
-    let mut last_query = state.last_full_query.lock().await;
-    if last_query.is_some() {
-        info!("清除查询缓存状态");
-        *last_query = None;
+    let mut completed_runs = state.completed_runs.lock().await;
+    if !completed_runs.is_empty() {
+        info!("清除{}条运行绑定记录", completed_runs.len());
+        completed_runs.clear();
     }
     Ok(())
 }
 
+/// 单个时点查询服务的文件缓存指标
+#[derive(Serialize)]
+pub struct CacheMetricsEntry {
+    pub file_path: String,
+    pub algorithm: String,
+    pub metrics: flux_backend::optimizations::CacheMetrics,
+}
+
+/// Tauri命令：查询各时点服务文件缓存的运行指标
+///
+/// 诊断面板用：展示每个(文件, 算法)服务实例的缓存条目数、
+/// 估算内存占用与命中/淘汰计数，帮助判断缓存上限是否合适
+#[command]
+pub async fn get_cache_metrics(state: State<'_, AppState>) -> Result<Vec<CacheMetricsEntry>, String> {
+    let services = state.time_point_services.lock().await;
+    let mut entries: Vec<CacheMetricsEntry> = services.iter()
+        .map(|((file_path, algorithm), service)| CacheMetricsEntry {
+            file_path: file_path.clone(),
+            algorithm: algorithm.clone(),
+            metrics: service.cache_metrics(),
+        })
+        .collect();
+    // HashMap遍历顺序不稳定，按(文件, 算法)排序保证结果可复现
+    entries.sort_by(|a, b| (&a.file_path, &a.algorithm).cmp(&(&b.file_path, &b.algorithm)));
+    Ok(entries)
+}
+
+/// Tauri命令：清除指定(文件, 算法)的时点查询服务实例
+/// 
+/// 文件被替换或单个查询目标作废时调用，返回是否确实存在并被清除
+#[command]
+pub async fn purge_time_point_service(
+    file_path: String,
+    algorithm: String,
+    state: State<'_, AppState>
+) -> Result<bool, String> {
+    let mut services = state.time_point_services.lock().await;
+    let removed = services.remove(&(file_path.clone(), algorithm.clone())).is_some();
+    if removed {
+        info!("已清除时点查询服务: 文件={}, 算法={}", file_path, algorithm);
+    }
+    Ok(removed)
+}
+
+/// Tauri命令：重置全部时点查询服务（切换项目后调用）
+/// 
+/// 连同已注册的运行绑定一并清空，返回清除的实例数量
+#[command]
+pub async fn reset_time_point_services(state: State<'_, AppState>) -> Result<usize, String> {
+    let purged = {
+        let mut services = state.time_point_services.lock().await;
+        let count = services.len();
+        services.clear();
+        count
+    };
+    state.completed_runs.lock().await.clear();
+    info!("已重置全部时点查询服务，共清除{}个实例", purged);
+    Ok(purged)
+}
+
 /// Tauri命令：时点查询（新的Rust原生实现）
 #[command]
 pub async fn time_point_query_rust(
     query: TimePointQuery,
     state: State<'_, AppState>
 ) -> Result<QueryResult, String> {
-    info!("Time point query: file={}, row={}, algorithm={}", query.file_path, query.row_number, query.algorithm);
+    info!("Time point query: file={}, row={}, algorithm={}, run_id={:?}",
+        query.file_path, query.row_number, query.algorithm, query.run_id);
+    
+    // 运行绑定校验：携带run_id时，查询必须对应确切的那次已完成分析，
+    // 文件在磁盘上变更过（指纹不一致）也会被拒绝，而不是静默重新推导
+    if let Some(run_id) = &query.run_id {
+        let completed_runs = state.completed_runs.lock().await;
+        let Some(run) = completed_runs.get(run_id) else {
+            warn!("未知的运行ID: {}", run_id);
+            return Ok(QueryResult {
+                success: false,
+                data: None,
+                message: "该分析运行不存在或已失效，请重新运行分析".to_string(),
+            });
+        };
+        if run.file_path != query.file_path || run.algorithm != query.algorithm {
+            return Ok(QueryResult {
+                success: false,
+                data: None,
+                message: format!("运行ID绑定的是 文件={} 算法={}，与本次查询不符", run.file_path, run.algorithm),
+            });
+        }
+        let current_fingerprint = FileCache::new()
+            .generate_fingerprint(&query.file_path, &query.algorithm)
+            .map_err(|e| format!("文件指纹计算失败: {}", e))?;
+        if current_fingerprint != run.fingerprint {
+            warn!("文件自运行{}完成后已变更，拒绝时点查询", run_id);
+            return Ok(QueryResult {
+                success: false,
+                data: None,
+                message: "文件自该次分析完成后已变更，查询结果将与正在查看的运行不一致，请重新运行分析".to_string(),
+            });
+        }
+    }
     
     // 构建Rust后端请求
     let request = TimePointQueryRequest {
@@ -60,21 +216,16 @@ pub async fn time_point_query_rust(
         ));
     }
     
-    // 获取或创建时点查询服务（支持缓存）
+    // 获取或创建时点查询服务：按(文件, 算法)分键缓存，互不污染
+    let service_key = (query.file_path.clone(), query.algorithm.clone());
     {
-        let mut service_guard = state.time_point_service.lock().await;
-        
-        // 检查是否需要创建新服务实例（算法变更时）
-        let service_needs_update = match service_guard.as_ref() {
-            Some(existing_service) => existing_service.algorithm != query.algorithm,
-            None => true, // 首次创建
-        };
+        let mut services = state.time_point_services.lock().await;
         
-        if service_needs_update {
+        if !services.contains_key(&service_key) {
             match TimePointService::new(query.algorithm.clone()) {
                 Ok(new_service) => {
-                    *service_guard = Some(new_service);
-                    info!("时点查询服务已更新，算法: {}", query.algorithm);
+                    services.insert(service_key.clone(), new_service);
+                    info!("时点查询服务已创建: 文件={}, 算法={}", query.file_path, query.algorithm);
                 },
                 Err(e) => {
                     error!("Failed to create TimePointService: {}", e);
@@ -90,7 +241,7 @@ pub async fn time_point_query_rust(
                 }
             }
         }
-    } // service_guard 在这里被释放
+    } // services 在这里被释放
     
     // 统一使用缓存机制：所有查询都走缓存路径，让后端的文件指纹机制决定是否命中缓存
     println!("🔍 缓存策略: 统一使用缓存路径，由后端文件指纹机制决定缓存命中");
@@ -104,8 +255,8 @@ pub async fn time_point_query_rust(
     
     // 获取服务实例进行缓存查询
     let cached_result = {
-        let mut service_guard = state.time_point_service.lock().await;
-        let service = service_guard.as_mut().unwrap();
+        let mut services = state.time_point_services.lock().await;
+        let service = services.get_mut(&service_key).unwrap();
         service.query_time_point_cached(request.clone()).await
     };
     
@@ -122,8 +273,8 @@ pub async fn time_point_query_rust(
             
             // 获取服务实例进行完整查询
             let full_result = {
-                let mut service_guard = state.time_point_service.lock().await;
-                let service = service_guard.as_mut().unwrap();
+                let mut services = state.time_point_services.lock().await;
+                let service = services.get_mut(&service_key).unwrap();
                 service.query_time_point(request).await
             };
             
@@ -205,6 +356,125 @@ pub async fn time_point_query_rust(
     }
 }
 
+/// Tauri命令：批量时点查询（一次扫描返回多行的状态快照）
+///
+/// 核查常需同时比对几十个时点的追踪器状态，逐行调用时点查询
+/// 会重复推导；这里共用(文件, 算法)键下的服务实例与其文件缓存
+#[command]
+pub async fn batch_time_point_query(
+    request: flux_backend::BatchTimePointQueryRequest,
+    state: State<'_, AppState>
+) -> Result<flux_backend::BatchTimePointQueryResult, String> {
+    info!("Batch time point query: file={}, rows={}, algorithm={}",
+        request.file_path, request.row_numbers.len(), request.algorithm);
+
+    // 获取或创建时点查询服务：按(文件, 算法)分键缓存，互不污染
+    let service_key = (request.file_path.clone(), request.algorithm.clone());
+    let mut services = state.time_point_services.lock().await;
+    if !services.contains_key(&service_key) {
+        let new_service = TimePointService::new(request.algorithm.clone())
+            .map_err(|e| format!("服务初始化失败: {}", e))?;
+        services.insert(service_key.clone(), new_service);
+        info!("时点查询服务已创建: 文件={}, 算法={}", request.file_path, request.algorithm);
+    }
+
+    let service = services.get_mut(&service_key).unwrap();
+    service.query_time_points_batch(request).await
+        .map_err(|e| {
+            warn!("批量时点查询失败: {}", e);
+            e.to_string()
+        })
+}
+
+/// Tauri命令：按截止时刻的时点查询
+///
+/// 前端按"2023-06-30 23:59:59"这类时刻查询时，定位该时刻及之前的
+/// 最后一笔交易并复用按行号的时点查询结果结构
+#[command]
+pub async fn time_point_query_at(
+    request: flux_backend::TimePointAtQueryRequest,
+    state: State<'_, AppState>
+) -> Result<flux_backend::TimePointQueryResult, String> {
+    info!("Time point query at: file={}, as_of={}, algorithm={}",
+        request.file_path, request.as_of, request.algorithm);
+
+    // 获取或创建时点查询服务：按(文件, 算法)分键缓存，互不污染
+    let service_key = (request.file_path.clone(), request.algorithm.clone());
+    let mut services = state.time_point_services.lock().await;
+    if !services.contains_key(&service_key) {
+        let new_service = TimePointService::new(request.algorithm.clone())
+            .map_err(|e| format!("服务初始化失败: {}", e))?;
+        services.insert(service_key.clone(), new_service);
+        info!("时点查询服务已创建: 文件={}, 算法={}", request.file_path, request.algorithm);
+    }
+
+    let service = services.get_mut(&service_key).unwrap();
+    service.query_time_point_at(request).await
+        .map_err(|e| {
+            warn!("按时刻时点查询失败: {}", e);
+            e.to_string()
+        })
+}
+
+/// Tauri命令：按期汇总报告查询
+///
+/// 仪表盘画按日/按月的余额与缺口走势图，
+/// 共用(文件, 算法)键下服务实例的缓存分析数据
+#[command]
+pub async fn period_summary_report(
+    request: flux_backend::PeriodSummaryQueryRequest,
+    state: State<'_, AppState>
+) -> Result<flux_backend::utils::period_summary::PeriodSummaryReport, String> {
+    info!("Period summary report: file={}, algorithm={}", request.file_path, request.algorithm);
+
+    // 获取或创建时点查询服务：按(文件, 算法)分键缓存，互不污染
+    let service_key = (request.file_path.clone(), request.algorithm.clone());
+    let mut services = state.time_point_services.lock().await;
+    if !services.contains_key(&service_key) {
+        let new_service = TimePointService::new(request.algorithm.clone())
+            .map_err(|e| format!("服务初始化失败: {}", e))?;
+        services.insert(service_key.clone(), new_service);
+        info!("时点查询服务已创建: 文件={}, 算法={}", request.file_path, request.algorithm);
+    }
+
+    let service = services.get_mut(&service_key).unwrap();
+    service.query_period_summary(request).await
+        .map_err(|e| {
+            warn!("按期汇总查询失败: {}", e);
+            e.to_string()
+        })
+}
+
+/// Tauri命令：资金走势时间线查询
+///
+/// 统计面板画余额与缺口走势图，返回列式数组序列，
+/// 共用(文件, 算法)键下服务实例的缓存分析数据
+#[command]
+pub async fn timeline_series(
+    request: flux_backend::TimelineQueryRequest,
+    state: State<'_, AppState>
+) -> Result<flux_backend::TimelineSeries, String> {
+    info!("Timeline series: file={}, algorithm={}, per_day={}",
+        request.file_path, request.algorithm, request.per_day);
+
+    // 获取或创建时点查询服务：按(文件, 算法)分键缓存，互不污染
+    let service_key = (request.file_path.clone(), request.algorithm.clone());
+    let mut services = state.time_point_services.lock().await;
+    if !services.contains_key(&service_key) {
+        let new_service = TimePointService::new(request.algorithm.clone())
+            .map_err(|e| format!("服务初始化失败: {}", e))?;
+        services.insert(service_key.clone(), new_service);
+        info!("时点查询服务已创建: 文件={}, 算法={}", request.file_path, request.algorithm);
+    }
+
+    let service = services.get_mut(&service_key).unwrap();
+    service.query_timeline(request).await
+        .map_err(|e| {
+            warn!("时间线查询失败: {}", e);
+            e.to_string()
+        })
+}
+
 /// Excel导出请求结构
 #[derive(Deserialize)]
 pub struct ExportFundPoolsRequest {
@@ -0,0 +1,290 @@
+//! 分析任务队列子系统
+//!
+//! run_rust_audit此前在已有分析运行时直接拒绝新请求，多个文件只能
+//! 人工排队。本模块提供一个有界任务队列：提交即返回任务号，后台
+//! 工作循环按提交顺序逐个执行（引擎仍保持单分析运行），每个任务
+//! 有独立的状态、进度、取消令牌与日志缓冲，前端通过
+//! list_jobs/get_job_status/cancel_job命令查看与控制，替代单一的
+//! ProcessStatus轮询。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::{Mutex, Notify};
+
+use crate::{AuditConfig, AuditResult};
+
+/// 队列容量上限（排队中+运行中，超出时拒绝新提交）
+pub const MAX_PENDING_JOBS: usize = 16;
+
+/// 单任务日志缓冲上限（超出时丢弃最早的行，防止长跑任务占满内存）
+pub const MAX_JOB_LOG_LINES: usize = 2000;
+
+/// 任务状态
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// 已提交，等待执行
+    Queued,
+    /// 分析进行中
+    Running,
+    /// 分析完成（结果见任务记录）
+    Completed,
+    /// 分析失败
+    Failed,
+    /// 已取消（排队中取消立即生效；运行中取消在算法下一个检查点生效）
+    Cancelled,
+}
+
+impl JobState {
+    /// 是否为终态（终态任务不再变化，可从前端列表清理）
+    #[must_use]
+    pub fn is_terminal(self) -> bool {
+        matches!(self, JobState::Completed | JobState::Failed | JobState::Cancelled)
+    }
+}
+
+/// 任务记录（前端可见部分）
+#[derive(Debug, Serialize, Clone)]
+pub struct JobRecord {
+    /// 任务号（提交时生成）
+    pub id: String,
+    /// 当前状态
+    pub state: JobState,
+    /// 提交时的分析配置
+    pub config: AuditConfig,
+    /// 进度百分比（运行中填充）
+    pub progress: Option<f32>,
+    /// 当前阶段消息
+    pub message: Option<String>,
+    /// 本任务的日志缓冲（与其他任务隔离）
+    pub log: Vec<String>,
+    /// 提交时间
+    pub submitted_at: DateTime<Utc>,
+    /// 结束时间（到达终态时填充）
+    pub finished_at: Option<DateTime<Utc>>,
+    /// 分析结果（完成后填充）
+    pub result: Option<AuditResult>,
+    /// 失败原因
+    pub error: Option<String>,
+}
+
+/// 任务记录及其取消令牌
+struct JobEntry {
+    record: JobRecord,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    /// 全部任务（含终态，供前端翻查）
+    jobs: HashMap<String, JobEntry>,
+    /// 等待执行的任务号（按提交顺序）
+    pending: VecDeque<String>,
+    /// 正在执行的任务号
+    running: Option<String>,
+    /// 全部任务号的提交顺序（list按此排序）
+    order: Vec<String>,
+}
+
+/// 有界分析任务队列
+pub struct JobQueue {
+    inner: Mutex<QueueInner>,
+    /// 新任务提交时唤醒工作循环
+    notify: Notify,
+}
+
+impl JobQueue {
+    /// 创建空队列
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(QueueInner::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// 提交新任务，返回任务号；队列已满时拒绝
+    pub async fn submit(&self, config: AuditConfig) -> Result<String, String> {
+        let mut inner = self.inner.lock().await;
+        let active = inner.pending.len() + usize::from(inner.running.is_some());
+        if active >= MAX_PENDING_JOBS {
+            return Err(format!("任务队列已满（上限{}个），请等待部分任务完成后再提交", MAX_PENDING_JOBS));
+        }
+
+        let id = format!("job_{}", Utc::now().timestamp_millis());
+        // 同毫秒内连续提交时避免撞号
+        let id = if inner.jobs.contains_key(&id) {
+            format!("{}_{}", id, inner.order.len())
+        } else {
+            id
+        };
+        let record = JobRecord {
+            id: id.clone(),
+            state: JobState::Queued,
+            config,
+            progress: None,
+            message: Some("等待执行".to_string()),
+            log: Vec::new(),
+            submitted_at: Utc::now(),
+            finished_at: None,
+            result: None,
+            error: None,
+        };
+        inner.jobs.insert(id.clone(), JobEntry {
+            record,
+            cancel: Arc::new(AtomicBool::new(false)),
+        });
+        inner.pending.push_back(id.clone());
+        inner.order.push(id.clone());
+        drop(inner);
+
+        self.notify.notify_one();
+        Ok(id)
+    }
+
+    /// 取出下一个待执行任务并标记为运行中（工作循环专用）
+    ///
+    /// 返回任务号、配置与该任务的取消令牌；队列空时返回None
+    pub async fn take_next(&self) -> Option<(String, AuditConfig, Arc<AtomicBool>)> {
+        let mut inner = self.inner.lock().await;
+        // 排队中已被取消的任务直接跳过
+        while let Some(id) = inner.pending.pop_front() {
+            let Some(entry) = inner.jobs.get_mut(&id) else { continue };
+            if entry.record.state != JobState::Queued {
+                continue;
+            }
+            entry.record.state = JobState::Running;
+            entry.record.message = Some("分析进行中".to_string());
+            let config = entry.record.config.clone();
+            let cancel = entry.cancel.clone();
+            inner.running = Some(id.clone());
+            return Some((id, config, cancel));
+        }
+        None
+    }
+
+    /// 等待新任务提交（队列空时工作循环在此挂起）
+    pub async fn wait_for_work(&self) {
+        self.notify.notified().await;
+    }
+
+    /// 查询单个任务记录
+    pub async fn get(&self, id: &str) -> Option<JobRecord> {
+        self.inner.lock().await.jobs.get(id).map(|entry| entry.record.clone())
+    }
+
+    /// 列出全部任务（按提交顺序）
+    pub async fn list(&self) -> Vec<JobRecord> {
+        let inner = self.inner.lock().await;
+        inner.order.iter()
+            .filter_map(|id| inner.jobs.get(id))
+            .map(|entry| entry.record.clone())
+            .collect()
+    }
+
+    /// 取消任务
+    ///
+    /// 排队中的任务立即转入Cancelled；运行中的任务置位取消令牌，
+    /// 由算法循环在下一个检查点退出后转入Cancelled；终态任务报错
+    pub async fn cancel(&self, id: &str) -> Result<JobState, String> {
+        let mut inner = self.inner.lock().await;
+        let entry = inner.jobs.get_mut(id)
+            .ok_or_else(|| format!("任务不存在: {}", id))?;
+        match entry.record.state {
+            JobState::Queued => {
+                entry.record.state = JobState::Cancelled;
+                entry.record.message = Some("已在排队中取消".to_string());
+                entry.record.finished_at = Some(Utc::now());
+                Ok(JobState::Cancelled)
+            }
+            JobState::Running => {
+                entry.cancel.store(true, Ordering::SeqCst);
+                entry.record.message = Some("取消请求已发出，等待算法退出".to_string());
+                Ok(JobState::Running)
+            }
+            state => Err(format!("任务已结束（{:?}），无法取消", state)),
+        }
+    }
+
+    /// 更新运行中任务的进度与消息
+    pub async fn set_progress(&self, id: &str, progress: Option<f32>, message: Option<String>) {
+        if let Some(entry) = self.inner.lock().await.jobs.get_mut(id) {
+            if progress.is_some() {
+                entry.record.progress = progress;
+            }
+            if message.is_some() {
+                entry.record.message = message;
+            }
+        }
+    }
+
+    /// 整体替换任务日志缓冲（工作循环从服务层同步日志时调用）
+    ///
+    /// 超出[`MAX_JOB_LOG_LINES`]时只保留末段
+    pub async fn replace_log(&self, id: &str, mut log: Vec<String>) {
+        if log.len() > MAX_JOB_LOG_LINES {
+            log.drain(..log.len() - MAX_JOB_LOG_LINES);
+        }
+        if let Some(entry) = self.inner.lock().await.jobs.get_mut(id) {
+            entry.record.log = log;
+        }
+    }
+
+    /// 标记任务完成并记录结果
+    pub async fn finish(&self, id: &str, result: AuditResult) {
+        let mut inner = self.inner.lock().await;
+        if inner.running.as_deref() == Some(id) {
+            inner.running = None;
+        }
+        if let Some(entry) = inner.jobs.get_mut(id) {
+            entry.record.state = JobState::Completed;
+            entry.record.progress = Some(100.0);
+            entry.record.message = Some("分析完成".to_string());
+            entry.record.result = Some(result);
+            entry.record.finished_at = Some(Utc::now());
+        }
+    }
+
+    /// 标记任务失败（取消令牌已置位时转入Cancelled而非Failed）
+    pub async fn fail(&self, id: &str, error: String) {
+        let mut inner = self.inner.lock().await;
+        if inner.running.as_deref() == Some(id) {
+            inner.running = None;
+        }
+        if let Some(entry) = inner.jobs.get_mut(id) {
+            if entry.cancel.load(Ordering::SeqCst) {
+                entry.record.state = JobState::Cancelled;
+                entry.record.message = Some("已取消".to_string());
+            } else {
+                entry.record.state = JobState::Failed;
+                entry.record.message = Some("分析失败".to_string());
+            }
+            entry.record.error = Some(error);
+            entry.record.finished_at = Some(Utc::now());
+        }
+    }
+
+    /// 清理终态任务记录，返回清理条数（前端"清空已完成"按钮用）
+    pub async fn prune_finished(&self) -> usize {
+        let mut inner = self.inner.lock().await;
+        let finished: Vec<String> = inner.jobs.iter()
+            .filter(|(_, entry)| entry.record.state.is_terminal())
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &finished {
+            inner.jobs.remove(id);
+        }
+        inner.order.retain(|id| inner.jobs.contains_key(id));
+        finished.len()
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
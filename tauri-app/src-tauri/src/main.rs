@@ -19,14 +19,19 @@ use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 use log::{info, warn, error};
 use regex::Regex;
+use once_cell::sync::Lazy;
 use std::sync::Arc;
 
 // 引入Rust后端库
 use flux_backend::{AuditService, TauriAuditConfig, TimePointService, TimePointQueryRequest, TimePointQueryResult};
+use flux_backend::data_models::{Config as EngineConfig, HeaderLanguage};
 
 // 引入模块化命令
 mod commands;
 
+// 分析任务队列（多文件排队分析，替代单一ProcessStatus的并发拒绝）
+mod job_queue;
+
 #[cfg(target_os = "windows")]
 use windows::Win32::{
     Foundation::{BOOL, HWND},
@@ -67,14 +72,38 @@ async fn set_window_dark_mode(window: tauri::Window, dark_mode: bool) -> Result<
 }
 
 // 数据类型定义
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuditConfig {
     pub algorithm: String,
     pub input_file: String,
     pub output_file: Option<String>,
+    /// 仅分析该日期（含）之后的交易，格式YYYY-MM-DD（部分期间审计）
+    #[serde(default)]
+    pub date_from: Option<String>,
+    /// 仅分析该日期（含）之前的交易，格式YYYY-MM-DD
+    #[serde(default)]
+    pub date_to: Option<String>,
+    /// 要分析的工作表名（省略时读取第一个工作表）
+    #[serde(default)]
+    pub sheet_name: Option<String>,
+    /// 核定期初余额总额（省略时沿用首笔交易回推值）
+    #[serde(default)]
+    pub opening_balance: Option<String>,
+    /// 期初余额中的个人部分金额（与opening_ratio互斥）
+    #[serde(default)]
+    pub opening_personal: Option<String>,
+    /// 期初余额中个人部分的占比（0到1）
+    #[serde(default)]
+    pub opening_ratio: Option<String>,
+    /// 同时间戳交易排序策略（省略时沿用贪心余额链修复）
+    #[serde(default)]
+    pub same_time_ordering: Option<String>,
+    /// TIEBREAK_COLUMN策略使用的仲裁列名（如"流水号"）
+    #[serde(default)]
+    pub tiebreak_column: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AuditResult {
     pub success: bool,
     pub message: String,
@@ -83,9 +112,24 @@ pub struct AuditResult {
     // 新增：分析统计信息
     pub statistics: Option<AnalysisStatistics>,
     pub error: Option<String>,
+    /// 本次分析的运行ID，时点查询携带它即可绑定到该次运行的快照
+    #[serde(default)]
+    pub run_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 已完成分析运行的绑定信息
+/// 
+/// 时点查询用run_id查到该记录后，先校验文件指纹仍与分析完成时一致，
+/// 保证查询看到的快照正是用户正在查看的那次运行
+#[derive(Debug, Clone)]
+pub struct CompletedRun {
+    pub file_path: String,
+    pub algorithm: String,
+    /// 运行完成时的文件指纹（路径+算法+修改时间+大小）
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AnalysisStatistics {
     pub total_records: u32,
     pub processing_time: u64,  // 毫秒
@@ -102,6 +146,9 @@ pub struct TimePointQuery {
     pub file_path: String,
     pub row_number: u32,
     pub algorithm: String,
+    /// 绑定的分析运行ID（缺省时退化为按文件+算法匹配的旧行为）
+    #[serde(default)]
+    pub run_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,20 +201,40 @@ pub struct AppState {
     pub query_history: Mutex<Vec<QueryHistory>>,
     pub current_process: Mutex<ProcessStatus>,
     pub app_config: Mutex<AppConfig>,
-    pub audit_service: Arc<AuditService>,  // 添加Rust后端服务
-    pub last_full_query: Mutex<Option<(String, String)>>, // (file_path, algorithm) 用于缓存判定
-    pub time_point_service: Mutex<Option<flux_backend::services::TimePointService>>, // 时点查询服务（支持缓存）
+    pub audit_service: AuditService,  // Rust后端服务（内部状态已可安全并发共享，直接Clone即可）
+    // 已完成分析运行注册表：run_id -> 运行绑定信息（时点查询据此校验快照归属）
+    pub completed_runs: Mutex<std::collections::HashMap<String, CompletedRun>>,
+    // 时点查询服务缓存：按(文件路径, 算法)分键，避免跨文件复用陈旧状态
+    pub time_point_services: Mutex<std::collections::HashMap<(String, String), flux_backend::services::TimePointService>>,
+    pub last_dialog_directory: Mutex<Option<String>>, // 上次文件对话框使用的目录
+    // 分析取消令牌：stop_analysis置位后，后端算法循环在下一个检查点退出
+    pub analysis_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    // 增量分析快照缓存：同一文件追加新行后重跑时只处理新增行
+    pub incremental_cache: flux_backend::IncrementalCacheHandle,
+    // 当前生效的列映射档案（不同银行表头），分析时应用到引擎配置
+    pub column_mapping: Mutex<Option<flux_backend::data_models::ColumnMappingProfile>>,
+    // 持久化查询历史（落盘，应用重启后仍可翻查；打开失败时为None降级为纯内存历史）
+    pub history_service: Mutex<Option<flux_backend::HistoryService>>,
+    // 分析任务队列：多文件排队分析，按提交顺序逐个执行
+    pub job_queue: job_queue::JobQueue,
 }
 
 // Tauri命令：获取可用算法列表
 #[command]
 async fn get_algorithms() -> Result<Vec<String>, String> {
-    Ok(vec!["FIFO".to_string(), "BALANCE_METHOD".to_string()])
+    Ok(vec!["FIFO".to_string(), "BALANCE_METHOD".to_string(), "PROPORTIONAL".to_string()])
+}
+
+// Tauri命令：获取后端信息（版本协商用）
+// 前端启动时调用，据此禁用当前后端构建不支持的功能
+#[command]
+async fn get_backend_info() -> Result<flux_backend::TauriBackendInfo, String> {
+    Ok(flux_backend::TauriBackendInfo::current())
 }
 
 // Tauri命令：运行Rust后端审计分析（新增）
 #[command]
-async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Result<AuditResult, String> {
+async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>, window: tauri::Window) -> Result<AuditResult, String> {
     info!("Starting Rust audit with algorithm: {}, input: {}", config.algorithm, config.input_file);
     
     // 步骤0: 并发控制 - 检查是否已有分析在运行
@@ -200,22 +267,80 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
         algorithm: config.algorithm.clone(),
         input_file: config.input_file.clone(),
         output_file: config.output_file.clone(),
+        date_from: config.date_from.clone(),
+        date_to: config.date_to.clone(),
+        sheet_name: config.sheet_name.clone(),
+        opening_balance: config.opening_balance.clone(),
+        opening_personal: config.opening_personal.clone(),
+        opening_ratio: config.opening_ratio.clone(),
+        same_time_ordering: config.same_time_ordering.clone(),
+        tiebreak_column: config.tiebreak_column.clone(),
     };
     
+    // 步骤2.5: 严格校验前端输入（算法白名单、路径存在性/扩展名/可读写性）
+    // 字段级错误序列化为JSON数组返回，前端解析后高亮对应输入框
+    if let Err(field_errors) = tauri_config.validate() {
+        warn!("审计配置校验失败: {} 个字段错误", field_errors.len());
+        {
+            let mut process_status = state.current_process.lock().await;
+            process_status.running = false;
+            process_status.message = Some("配置校验失败".to_string());
+        }
+        return Err(serde_json::to_string(&field_errors)
+            .unwrap_or_else(|_| "配置校验失败".to_string()));
+    }
+    
     // 步骤3: 创建服务并执行分析，使用共享状态机制
-    let service = AuditService::new().with_suppress_output(false);
+    // AuditService内部状态基于Arc<tokio::sync::Mutex>，Clone后天然共享，无需再包Arc
+    // 导出表头语言跟随界面语言设置（涉外项目可输出英文表头）
+    let mut engine_config = EngineConfig::new();
+    {
+        let app_config = state.app_config.lock().await;
+        engine_config.excel_columns.header_language =
+            HeaderLanguage::from_app_language(&app_config.language);
+    }
+    // 应用当前生效的列映射档案（不同银行表头无需改名）
+    if let Some(profile) = state.column_mapping.lock().await.as_ref() {
+        engine_config.excel_columns.apply_mapping_profile(profile);
+    }
+    let service = AuditService::with_config(engine_config)
+        .with_suppress_output(false)
+        .with_cancel_flag(state.analysis_cancel.clone())
+        .with_incremental(true)
+        .with_incremental_cache(state.incremental_cache.clone());
     
-    // 步骤3.1: 并行执行分析和实时日志同步
+    // 步骤3.1: 并行执行分析、结构化进度转发和实时日志同步
     let state_clone = state.inner().clone();
-    let service_clone = Arc::new(service);
-    let service_for_analysis = service_clone.clone();
-    let service_for_sync = service_clone.clone();
+    let state_for_progress = state.inner().clone();
+    let service_for_analysis = service.clone();
+    let service_for_sync = service.clone();
+    let mut progress_rx = service.subscribe_progress();
     
     // 分析任务
     let analysis_task = async move {
         service_for_analysis.run_audit_for_gui(tauri_config).await
     };
     
+    // 进度转发任务：订阅服务层的结构化进度通道，
+    // 经window.emit转发给前端，替代此前对输出日志的正则解析
+    let progress_task = async move {
+        while progress_rx.changed().await.is_ok() {
+            let report = progress_rx.borrow_and_update().clone();
+            {
+                let mut process_status = state_for_progress.current_process.lock().await;
+                // 优先用按阶段权重折算的整体百分比（跨阶段单调不回跳）；
+                // 阶段切换事件不带行数信息，只更新消息
+                if report.overall_percentage > 0.0 {
+                    process_status.progress = Some(round_progress(report.overall_percentage as f32));
+                } else if report.total > 0 {
+                    process_status.progress = Some(round_progress(report.percentage as f32));
+                }
+                process_status.message = Some(report.message.clone());
+            }
+            let _ = window.emit("analysis-progress", &report);
+        }
+    };
+    
     // 同步任务
     let sync_task = async move {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(100));
@@ -224,7 +349,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
         loop {
             interval.tick().await;
             
-            let current_logs = service_for_sync.get_output_logs();
+            let current_logs = service_for_sync.get_output_logs().await;
             if current_logs.len() > last_count {
                 let mut process_status = state_clone.current_process.lock().await;
                 process_status.output_log = current_logs.clone();
@@ -235,12 +360,16 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
         }
     };
     
-    // 并行执行：分析完成时自动取消同步任务
+    // 并行执行：分析完成时自动取消进度转发与同步任务
     let result = tokio::select! {
         analysis_result = analysis_task => {
             println!("🔍 分析任务完成");
             analysis_result
         },
+        _ = progress_task => {
+            // 进度通道发送端随服务存活，这个分支不应该执行
+            return Err("进度转发任务意外完成".to_string());
+        },
         _ = sync_task => {
             // 这个分支不应该执行
             return Err("同步任务意外完成".to_string());
@@ -248,7 +377,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
     };
     
     // 最后一次同步确保所有日志都被获取
-    let final_logs = service_clone.get_output_logs();
+    let final_logs = service.get_output_logs().await;
     if !final_logs.is_empty() {
         let mut process_status = state.current_process.lock().await;
         process_status.output_log = final_logs;
@@ -264,6 +393,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                     match config.algorithm.as_str() {
                         "FIFO" => "FIFO算法",
                         "BALANCE_METHOD" => "差额计算法",
+                        "PROPORTIONAL" => "按比例混同法",
                         _ => "审计"
                     }
                 ));
@@ -285,8 +415,8 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                 AnalysisStatistics {
                     total_records: data.transaction_count as u32,
                     processing_time: (data.processing_time * 1000.0) as u64, // 转换为毫秒
-                    validation_errors: 0, // TODO: 从validation result中获取
-                    validation_fixes: 0,  // TODO: 从validation result中获取
+                    validation_errors: data.validation_errors as u32,
+                    validation_fixes: data.validation_fixes as u32,
                     algorithm: config.algorithm.clone(),
                     input_file_name: std::path::Path::new(&config.input_file)
                         .file_name()
@@ -314,6 +444,27 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                 }
             };
             
+            record_analysis_history(
+                &state,
+                &config,
+                true,
+                result.data.as_ref().map(|d| d.processing_time).unwrap_or(0.0),
+                result.data.as_ref().map(|d| format!("共处理{}条交易", d.transaction_count)),
+            ).await;
+
+            // 注册本次完成的运行：时点查询携带run_id即可绑定到该次快照
+            let run_id = generate_id();
+            match flux_backend::FileCache::new().generate_fingerprint(&config.input_file, &config.algorithm) {
+                Ok(fingerprint) => {
+                    state.completed_runs.lock().await.insert(run_id.clone(), CompletedRun {
+                        file_path: config.input_file.clone(),
+                        algorithm: config.algorithm.clone(),
+                        fingerprint,
+                    });
+                }
+                Err(e) => warn!("运行指纹计算失败，时点查询将无法绑定本次运行: {}", e),
+            }
+
             AuditResult {
                 success: true,
                 message: result.message,
@@ -321,6 +472,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                 output_files: result.output_files,
                 statistics: Some(statistics),
                 error: None,
+                run_id: Some(run_id),
             }
         }
         false => {
@@ -331,6 +483,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                     match config.algorithm.as_str() {
                         "FIFO" => "FIFO算法",
                         "BALANCE_METHOD" => "差额计算法",
+                        "PROPORTIONAL" => "按比例混同法",
                         _ => "审计"
                     },
                     result.message
@@ -341,6 +494,8 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                 process_status.message = Some("分析失败".to_string());
             }
             
+            record_analysis_history(&state, &config, false, 0.0, Some(result.message.clone())).await;
+
             AuditResult {
                 success: false,
                 message: "分析失败".to_string(),
@@ -348,6 +503,7 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
                 output_files: vec![],
                 statistics: None,
                 error: Some(result.message),
+                run_id: None,
             }
         }
     };
@@ -355,11 +511,384 @@ async fn run_rust_audit(config: AuditConfig, state: State<'_, AppState>) -> Resu
     Ok(final_result)
 }
 
+// Tauri命令：提交分析任务到队列（替代run_rust_audit的并发拒绝）
+// 立即返回任务号，前端通过get_job_status轮询状态与日志
+#[command]
+async fn submit_audit_job(config: AuditConfig, state: State<'_, AppState>) -> Result<String, String> {
+    info!("Submitting audit job: algorithm={}, input={}", config.algorithm, config.input_file);
+
+    // 入队前先做配置校验，字段错误直接返回，不占用队列名额
+    let tauri_config = TauriAuditConfig {
+        algorithm: config.algorithm.clone(),
+        input_file: config.input_file.clone(),
+        output_file: config.output_file.clone(),
+        date_from: config.date_from.clone(),
+        date_to: config.date_to.clone(),
+        sheet_name: config.sheet_name.clone(),
+        opening_balance: config.opening_balance.clone(),
+        opening_personal: config.opening_personal.clone(),
+        opening_ratio: config.opening_ratio.clone(),
+        same_time_ordering: config.same_time_ordering.clone(),
+        tiebreak_column: config.tiebreak_column.clone(),
+    };
+    if let Err(field_errors) = tauri_config.validate() {
+        warn!("任务配置校验失败: {} 个字段错误", field_errors.len());
+        return Err(serde_json::to_string(&field_errors)
+            .unwrap_or_else(|_| "配置校验失败".to_string()));
+    }
+
+    state.job_queue.submit(config).await
+}
+
+// Tauri命令：列出全部分析任务（按提交顺序，含终态任务）
+#[command]
+async fn list_jobs(state: State<'_, AppState>) -> Result<Vec<job_queue::JobRecord>, String> {
+    Ok(state.job_queue.list().await)
+}
+
+// Tauri命令：查询单个任务的状态、进度与日志缓冲
+#[command]
+async fn get_job_status(job_id: String, state: State<'_, AppState>) -> Result<job_queue::JobRecord, String> {
+    state.job_queue.get(&job_id).await
+        .ok_or_else(|| format!("任务不存在: {}", job_id))
+}
+
+// Tauri命令：取消任务（排队中立即生效，运行中在算法下一检查点生效）
+#[command]
+async fn cancel_job(job_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state.job_queue.cancel(&job_id).await.map(|_| ())
+}
+
+// Tauri命令：清理终态任务记录，返回清理条数
+#[command]
+async fn prune_finished_jobs(state: State<'_, AppState>) -> Result<usize, String> {
+    Ok(state.job_queue.prune_finished().await)
+}
+
+// 任务队列工作循环：按提交顺序逐个执行任务（引擎保持单分析运行）
+// 在setup中随应用启动，随主进程退出
+async fn job_queue_worker(app: tauri::AppHandle) {
+    loop {
+        let state = app.state::<AppState>();
+        match state.job_queue.take_next().await {
+            Some((job_id, config, cancel)) => {
+                execute_queued_job(&app, job_id, config, cancel).await;
+            }
+            None => {
+                state.job_queue.wait_for_work().await;
+            }
+        }
+    }
+}
+
+// 执行单个队列任务：逻辑与run_rust_audit一致，但状态、进度与日志
+// 写入该任务自己的记录，取消走任务自己的令牌，互不干扰
+async fn execute_queued_job(
+    app: &tauri::AppHandle,
+    job_id: String,
+    config: AuditConfig,
+    cancel: Arc<std::sync::atomic::AtomicBool>,
+) {
+    let state = app.state::<AppState>();
+
+    let tauri_config = TauriAuditConfig {
+        algorithm: config.algorithm.clone(),
+        input_file: config.input_file.clone(),
+        output_file: config.output_file.clone(),
+        date_from: config.date_from.clone(),
+        date_to: config.date_to.clone(),
+        sheet_name: config.sheet_name.clone(),
+        opening_balance: config.opening_balance.clone(),
+        opening_personal: config.opening_personal.clone(),
+        opening_ratio: config.opening_ratio.clone(),
+        same_time_ordering: config.same_time_ordering.clone(),
+        tiebreak_column: config.tiebreak_column.clone(),
+    };
+
+    // 引擎配置与run_rust_audit保持一致（表头语言、列映射档案）
+    let mut engine_config = EngineConfig::new();
+    {
+        let app_config = state.app_config.lock().await;
+        engine_config.excel_columns.header_language =
+            HeaderLanguage::from_app_language(&app_config.language);
+    }
+    if let Some(profile) = state.column_mapping.lock().await.as_ref() {
+        engine_config.excel_columns.apply_mapping_profile(profile);
+    }
+    let service = AuditService::with_config(engine_config)
+        .with_suppress_output(false)
+        .with_cancel_flag(cancel)
+        .with_incremental(true)
+        .with_incremental_cache(state.incremental_cache.clone());
+
+    let service_for_analysis = service.clone();
+    let mut progress_rx = service.subscribe_progress();
+
+    let analysis_task = async move {
+        service_for_analysis.run_audit_for_gui(tauri_config).await
+    };
+
+    // 进度转发：写入任务记录，并带任务号emit给前端
+    let queue = &state.job_queue;
+    let progress_job_id = job_id.clone();
+    let progress_task = async move {
+        while progress_rx.changed().await.is_ok() {
+            let report = progress_rx.borrow_and_update().clone();
+            let progress = if report.overall_percentage > 0.0 {
+                Some(round_progress(report.overall_percentage as f32))
+            } else if report.total > 0 {
+                Some(round_progress(report.percentage as f32))
+            } else {
+                None
+            };
+            queue.set_progress(&progress_job_id, progress, Some(report.message.clone())).await;
+            let _ = app.emit_all("job-progress", serde_json::json!({
+                "job_id": progress_job_id,
+                "report": report,
+            }));
+        }
+    };
+
+    // 日志同步：定期把服务层输出同步到本任务的日志缓冲
+    let service_for_sync = service.clone();
+    let sync_job_id = job_id.clone();
+    let sync_task = async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(100));
+        let mut last_count = 0;
+        loop {
+            interval.tick().await;
+            let current_logs = service_for_sync.get_output_logs().await;
+            if current_logs.len() > last_count {
+                last_count = current_logs.len();
+                queue.replace_log(&sync_job_id, current_logs).await;
+            }
+        }
+    };
+
+    let result = tokio::select! {
+        analysis_result = analysis_task => analysis_result,
+        _ = progress_task => return,  // 进度通道发送端随服务存活，不应执行
+        _ = sync_task => return,      // 同上
+    };
+
+    // 最后一次日志同步
+    let final_logs = service.get_output_logs().await;
+    if !final_logs.is_empty() {
+        state.job_queue.replace_log(&job_id, final_logs).await;
+    }
+
+    if result.success {
+        record_analysis_history(
+            &state,
+            &config,
+            true,
+            result.data.as_ref().map(|d| d.processing_time).unwrap_or(0.0),
+            result.data.as_ref().map(|d| format!("共处理{}条交易", d.transaction_count)),
+        ).await;
+
+        // 注册完成的运行：时点查询携带run_id即可绑定到该次快照
+        let run_id = generate_id();
+        match flux_backend::FileCache::new().generate_fingerprint(&config.input_file, &config.algorithm) {
+            Ok(fingerprint) => {
+                state.completed_runs.lock().await.insert(run_id.clone(), CompletedRun {
+                    file_path: config.input_file.clone(),
+                    algorithm: config.algorithm.clone(),
+                    fingerprint,
+                });
+            }
+            Err(e) => warn!("运行指纹计算失败，时点查询将无法绑定本次运行: {}", e),
+        }
+
+        state.job_queue.finish(&job_id, AuditResult {
+            success: true,
+            message: result.message,
+            data: result.data.map(|d| serde_json::to_value(d).unwrap_or(serde_json::Value::Null)),
+            output_files: result.output_files,
+            statistics: None,  // 队列路径不重复拼装统计，前端按需从data与文件元数据推导
+            error: None,
+            run_id: Some(run_id),
+        }).await;
+    } else {
+        record_analysis_history(&state, &config, false, 0.0, Some(result.message.clone())).await;
+        state.job_queue.fail(&job_id, result.message).await;
+    }
+
+    let _ = app.emit_all("job-finished", serde_json::json!({ "job_id": job_id }));
+}
+
+// Tauri命令：加载列映射档案（JSON/TOML），供不同银行表头的文件直接读入
+#[command]
+async fn load_column_mapping(file_path: String, state: State<'_, AppState>) -> Result<flux_backend::data_models::ColumnMappingProfile, String> {
+    match flux_backend::data_models::ColumnMappingProfile::load_from_file(&file_path) {
+        Ok(profile) => {
+            info!("列映射档案已加载: {}", file_path);
+            *state.column_mapping.lock().await = Some(profile.clone());
+            Ok(profile)
+        }
+        Err(e) => {
+            warn!("列映射档案加载失败: {}", e);
+            Err(format!("列映射档案加载失败: {}", e))
+        }
+    }
+}
+
+// Tauri命令：清除列映射档案，恢复默认表头配置
+#[command]
+async fn clear_column_mapping(state: State<'_, AppState>) -> Result<(), String> {
+    *state.column_mapping.lock().await = None;
+    info!("列映射档案已清除，恢复默认表头配置");
+    Ok(())
+}
+
+// Tauri命令：导出算法对比报告（FIFO与差额计算法并排结果+摘要差异表）
+#[command]
+async fn export_comparison_report(input_path: String, output_path: String) -> Result<String, String> {
+    info!("开始导出算法对比报告: {} -> {}", input_path, output_path);
+    
+    let fifo_service = flux_backend::AuditService::new().with_suppress_output(true);
+    let (fifo_summary, fifo_transactions, _) = fifo_service
+        .analyze_financial_data("FIFO", &input_path, None::<&String>)
+        .await
+        .map_err(|e| format!("FIFO算法分析失败: {}", e))?;
+    
+    let balance_service = flux_backend::AuditService::new().with_suppress_output(true);
+    let (balance_summary, balance_transactions, _) = balance_service
+        .analyze_financial_data("BALANCE_METHOD", &input_path, None::<&String>)
+        .await
+        .map_err(|e| format!("差额计算法分析失败: {}", e))?;
+    
+    let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+    processor.export_comparison_results(
+        &output_path,
+        "FIFO", &fifo_summary, &fifo_transactions,
+        "BALANCE_METHOD", &balance_summary, &balance_transactions,
+    ).map_err(|e| format!("对比报告导出失败: {}", e))?;
+    
+    info!("算法对比报告导出完成: {}", output_path);
+    Ok(output_path)
+}
+
+// Tauri命令：导出聚焦工作簿（只含满足筛选条件的结果行，返回筛出行数）
+// 结果视图的"只看挪用行"等筛选导出入口，多个条件为与关系
+#[command]
+async fn export_filtered_results(
+    input_path: String,
+    output_path: String,
+    algorithm: String,
+    behavior: Option<String>,
+    attribute: Option<String>,
+    amount_min: Option<String>,
+    amount_max: Option<String>,
+) -> Result<usize, String> {
+    info!("开始导出聚焦工作簿: {} -> {}", input_path, output_path);
+
+    let service = flux_backend::AuditService::new().with_suppress_output(true);
+    let (summary, transactions, _) = service
+        .analyze_financial_data(&algorithm, &input_path, None::<&String>)
+        .await
+        .map_err(|e| format!("分析失败: {}", e))?;
+
+    let mut rows = transactions;
+    if let Some(label) = behavior.as_deref().filter(|s| !s.trim().is_empty()) {
+        let kind = flux_backend::BehaviorKind::from_label_zh(label.trim())
+            .ok_or_else(|| format!("未知行为性质: {}", label))?;
+        rows = flux_backend::filter_by_behavior(&rows, kind);
+    }
+    if let Some(keyword) = attribute.as_deref().filter(|s| !s.trim().is_empty()) {
+        rows = flux_backend::filter_by_fund_attribute(&rows, keyword.trim());
+    }
+    let parse_amount = |label: &str, value: &Option<String>| -> Result<Option<flux_backend::rust_decimal::Decimal>, String> {
+        value.as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(|raw| raw.trim().parse()
+                .map_err(|_| format!("{}金额无法解析: {}", label, raw)))
+            .transpose()
+    };
+    let amount_min = parse_amount("最小", &amount_min)?;
+    let amount_max = parse_amount("最大", &amount_max)?;
+    if amount_min.is_some() || amount_max.is_some() {
+        rows = flux_backend::filter_by_amount_range(&rows, amount_min, amount_max);
+    }
+
+    let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+    processor.export_analysis_results(&rows, &summary, &output_path)
+        .map_err(|e| format!("聚焦工作簿导出失败: {}", e))?;
+
+    info!("聚焦工作簿导出完成: {}（{}行）", output_path, rows.len());
+    Ok(rows.len())
+}
+
+// Tauri命令：分页获取持久化历史（最新在前）
+#[command]
+async fn page_persistent_history(offset: usize, limit: usize, state: State<'_, AppState>) -> Result<Vec<flux_backend::HistoryEntry>, String> {
+    let service = state.history_service.lock().await;
+    service.as_ref()
+        .map(|service| service.page(offset, limit))
+        .ok_or_else(|| "持久化历史存储不可用".to_string())
+}
+
+// Tauri命令：按关键字搜索持久化历史（匹配文件路径/算法/摘要）
+#[command]
+async fn search_persistent_history(keyword: String, state: State<'_, AppState>) -> Result<Vec<flux_backend::HistoryEntry>, String> {
+    let service = state.history_service.lock().await;
+    service.as_ref()
+        .map(|service| service.search(&keyword))
+        .ok_or_else(|| "持久化历史存储不可用".to_string())
+}
+
+// Tauri命令：裁剪持久化历史，仅保留最新的keep_latest条
+#[command]
+async fn prune_persistent_history(keep_latest: usize, state: State<'_, AppState>) -> Result<usize, String> {
+    let mut service = state.history_service.lock().await;
+    match service.as_mut() {
+        Some(service) => service.prune(keep_latest).map_err(|e| format!("历史裁剪失败: {}", e)),
+        None => Err("持久化历史存储不可用".to_string()),
+    }
+}
+
+// 把一次分析运行写入持久化历史（存储不可用或写盘失败仅告警，不影响分析结果）
+async fn record_analysis_history(state: &State<'_, AppState>, config: &AuditConfig, success: bool, duration_secs: f64, summary: Option<String>) {
+    if let Some(service) = state.history_service.lock().await.as_mut() {
+        let entry = flux_backend::HistoryEntry {
+            id: format!("run-{}", chrono::Utc::now().timestamp_millis()),
+            timestamp: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            kind: flux_backend::HistoryKind::AnalysisRun,
+            file_path: config.input_file.clone(),
+            algorithm: config.algorithm.clone(),
+            row_number: None,
+            success,
+            duration_secs,
+            summary,
+        };
+        if let Err(e) = service.record(entry) {
+            log::warn!("分析历史落盘失败: {}", e);
+        }
+    }
+}
+
+// Tauri命令：计算流水修复建议（咨询模式，不改动数据）
+// 用户在前端逐条采纳/拒绝后再正式运行分析
+#[command]
+async fn propose_flow_repairs(file_path: String) -> Result<Vec<flux_backend::RepairSuggestion>, String> {
+    info!("开始计算流水修复建议: {}", file_path);
+
+    let processor = flux_backend::ExcelProcessor::new(flux_backend::Config::new());
+    let transactions = processor.read_transactions(&file_path)
+        .map_err(|e| format!("读取流水文件失败: {}", e))?;
+
+    let mut validator = flux_backend::UnifiedValidator::new();
+    let suggestions = validator.propose_repairs(&transactions)
+        .map_err(|e| format!("修复建议计算失败: {}", e))?;
+
+    info!("流水修复建议计算完成，共{}条建议", suggestions.len());
+    Ok(suggestions)
+}
+
 // Tauri命令：运行审计分析（使用Rust后端）
 #[command]
-async fn run_audit(config: AuditConfig, state: State<'_, AppState>) -> Result<AuditResult, String> {
+async fn run_audit(config: AuditConfig, state: State<'_, AppState>, window: tauri::Window) -> Result<AuditResult, String> {
     // 直接调用Rust后端实现，复用上面的逻辑
-    return run_rust_audit(config, state).await;
+    return run_rust_audit(config, state, window).await;
 }
 
 // 移除了Python备用的时点查询函数 - 现在完全使用Rust后端
@@ -464,8 +993,9 @@ async fn stop_analysis(state: State<'_, AppState>) -> Result<bool, String> {
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")
         ));
         
-        // Rust后端分析停止（无需终止外部进程）
-        process_status.output_log.push(format!("[{}] ⚡ Rust分析已停止", 
+        // 置位取消令牌：后端算法循环在下一个检查点返回Cancelled并释放CPU
+        state.analysis_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        process_status.output_log.push(format!("[{}] ⚡ 已发送取消信号，后端将在检查点停止", 
             chrono::Utc::now().format("%Y-%m-%d %H:%M:%S")
         ));
         
@@ -607,6 +1137,17 @@ async fn export_query_result(
     }
 }
 
+// Tauri命令：分析前的数据集概览统计（行数、时间范围、收支合计、资金属性分布、疑似投资产品）
+#[command]
+async fn get_dataset_stats(file_path: String) -> Result<flux_backend::DatasetStats, String> {
+    info!("Computing dataset stats for: {}", file_path);
+    
+    let service = AuditService::new().with_suppress_output(true);
+    service.dataset_stats(&file_path)
+        .await
+        .map_err(|e| format!("数据集统计失败: {}", e))
+}
+
 // Tauri命令：验证文件路径
 #[command]
 async fn validate_file_path(path: String) -> Result<bool, String> {
@@ -652,12 +1193,37 @@ fn create_app_state() -> AppState {
             process_id: None,
         }),
         app_config: Mutex::new(create_default_config()),
-        audit_service: Arc::new(AuditService::new()),  // 添加Rust审计服务
-        last_full_query: Mutex::new(None), // 初始化缓存状态
-        time_point_service: Mutex::new(None), // 时点查询服务延迟初始化
+        audit_service: AuditService::new(),  // 添加Rust审计服务
+        analysis_cancel: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        incremental_cache: flux_backend::IncrementalCacheHandle::default(),
+        column_mapping: Mutex::new(None),
+        completed_runs: Mutex::new(std::collections::HashMap::new()),
+        time_point_services: Mutex::new(std::collections::HashMap::new()), // 时点查询服务延迟初始化
+        last_dialog_directory: Mutex::new(None), // 对话框目录记忆
+        history_service: Mutex::new(
+            flux_backend::HistoryService::open("flux_query_history.json")
+                .map(|(service, _)| service)
+                .map_err(|e| log::warn!("持久化历史存储打开失败，本次运行历史不落盘: {}", e))
+                .ok()
+        ),
+        job_queue: job_queue::JobQueue::new(),
     }
 }
 
+// 预编译的输出解析正则（避免在每行日志解析时重复编译）
+static LOG_PREFIX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d+ - \w+ - ").expect("日志前缀正则无效")
+});
+static PERCENT_SUFFIX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\s*\([\d.]+%\)").expect("百分比后缀正则无效")
+});
+
+// 启动时预热：强制初始化全部Lazy正则，避免首次解析日志行时的编译开销
+fn warmup_regexes() {
+    Lazy::force(&LOG_PREFIX_RE);
+    Lazy::force(&PERCENT_SUFFIX_RE);
+}
+
 // 辅助函数：限制进度值为2位小数
 fn round_progress(progress: f32) -> f32 {
     // 使用更严格的精度控制方法
@@ -666,98 +1232,14 @@ fn round_progress(progress: f32) -> f32 {
     formatted.parse::<f32>().unwrap_or(progress)
 }
 
-// 辅助函数：从输出行解析进度百分比
-fn parse_progress_from_line(line: &str) -> f32 {
-    // 1. 解析新格式的处理进度 "⏳ 处理进度: 1,000/9,799 (10.2%)"
-    if let Ok(re) = Regex::new(r"处理进度:\s*([\d,]+)/([\d,]+)\s*\((\d+\.?\d*)%\)") {
-        if let Some(captures) = re.captures(line) {
-            if let Some(percent_str) = captures.get(3) {
-                if let Ok(percent) = percent_str.as_str().parse::<f32>() {
-                    // 先对输入的百分比进行精度控制
-                    let percent_rounded = round_progress(percent);
-                    // 处理阶段占35%-88%，基于实际时间分布(53%)
-                    let progress = 35.0 + (percent_rounded * 0.53);
-                    return round_progress(progress); // 限制为2位小数
-                }
-            }
-        }
-    }
-    
-    // 2. 解析简单的处理进度格式 "处理进度: X/Y"
-    if let Ok(re) = Regex::new(r"处理进度:\s*([\d,]+)/([\d,]+)") {
-        if let Some(captures) = re.captures(line) {
-            if let (Some(current_str), Some(total_str)) = (captures.get(1), captures.get(2)) {
-                // 移除逗号分隔符
-                let current_clean = current_str.as_str().replace(",", "");
-                let total_clean = total_str.as_str().replace(",", "");
-                
-                if let (Ok(current), Ok(total)) = (
-                    current_clean.parse::<f32>(), 
-                    total_clean.parse::<f32>()
-                ) {
-                    if total > 0.0 {
-                        let data_progress = (current / total) * 100.0;
-                        let data_progress_rounded = round_progress(data_progress);
-                        let progress = 35.0 + (data_progress_rounded * 0.53); // 35% + (进度 * 53%)
-                        return round_progress(progress); // 限制为2位小数
-                    }
-                }
-            }
-        }
-    }
-    
-    // 3. 精确匹配特定关键词并映射到进度阶段
-    // 初始化和启动阶段 (0-35%)
-    if line.contains("🚀 启动算法:") && (line.contains("FIFO") || line.contains("BALANCE_METHOD")) {
-        return 5.0;  // 算法启动信息
-    } else if line.contains("📊 开始数据预处理") {
-        return 10.0;
-    } else if line.contains("✅ 数据预处理完成") {
-        return 25.0;  // 数据预处理占20%时间
-    } else if line.contains("🔍 开始流水完整性验证") {
-        return 26.0;
-    } else if line.contains("✅ 流水完整性验证通过") {
-        return 30.0;  // 流水验证占5%时间
-    } else if line.contains("🔎 开始数据验证") {
-        return 31.0;
-    } else if line.contains("✅ 数据验证通过") {
-        return 33.0;  // 数据验证占5%时间
-    } else if line.contains("💰 计算初始余额") {
-        return 34.0;  // 瞬间完成
-    } else if line.contains("🚀 开始") && line.contains("资金追踪分析") {
-        return 35.0; // 开始数据处理阶段
-    } else if line.contains("📋 总共需要处理") && line.contains("条交易记录") {
-        return 35.0; // 开始数据处理
-    // 数据处理完成阶段 (88-100%)  
-    } else if line.contains("✅ 所有") && line.contains("条交易记录处理完成") {
-        return 88.0;  // 数据处理完成，占53%时间
-    } else if line.contains("📈 生成分析结果") {
-        return 90.0;
-    } else if line.contains("💾 保存分析结果到:") {
-        return 95.0;
-    } else if line.contains("📋 生成场外资金池记录:") {
-        return 98.0;
-    } else if line.contains("✅") && (line.contains("算法分析完成") || line.contains("FIFO算法分析完成") || line.contains("BALANCE_METHOD算法分析完成")) {
-        return 100.0;
-    }
-    
-    0.0 // 默认返回0，表示没有进度更新
-}
-
 // 辅助函数：从输出行提取显示消息
 fn extract_message_from_line(line: &str) -> String {
     // 移除时间戳和日志级别前缀
-    let mut cleaned = if let Ok(re) = Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2},\d+ - \w+ - ") {
-        re.replace(line, "").to_string()
-    } else {
-        line.to_string()
-    };
+    let mut cleaned = LOG_PREFIX_RE.replace(line, "").to_string();
     
     // 移除Python输出中的百分比，避免与前端显示重复
     // 匹配格式： "⏳ 处理进度: 2,000/9,799 (20.4%)" -> "⏳ 处理进度: 2,000/9,799"
-    if let Ok(re) = Regex::new(r"\s*\([\d.]+%\)") {
-        cleaned = re.replace_all(&cleaned, "").to_string();
-    }
+    cleaned = PERCENT_SUFFIX_RE.replace_all(&cleaned, "").to_string();
     
     // 如果行太长，截断显示（安全处理UTF-8字符边界）
     if cleaned.chars().count() > 80 {
@@ -866,14 +1348,42 @@ fn main() {
         .manage(app_state)
         .invoke_handler(tauri::generate_handler![
             get_algorithms,
+            get_backend_info,
             run_audit,
             run_rust_audit,  // 新增Rust后端命令
+            submit_audit_job,
+            list_jobs,
+            get_job_status,
+            cancel_job,
+            prune_finished_jobs,
+            load_column_mapping,
+            clear_column_mapping,
+            export_comparison_report,
+            export_filtered_results,
+            propose_flow_repairs,
             commands::time_point_query_rust,
+            commands::batch_time_point_query,
+            commands::time_point_query_at,
+            commands::period_summary_report,
+            commands::timeline_series,
             commands::clear_query_cache,
+            commands::get_cache_metrics,
+            commands::compute_fingerprint,
+            commands::get_transaction_by_source_row,
+            commands::get_pool_records_page,
+            commands::purge_time_point_service,
+            commands::reset_time_point_services,
             commands::export_fund_pools_excel,  // 新增Excel导出命令
+            commands::get_open_dialog_config,
+            commands::set_last_dialog_directory,
+            commands::list_excel_sheets,
+            commands::inspect_input_file,
             check_system_env,
             get_query_history,
             clear_query_history,
+            page_persistent_history,
+            search_persistent_history,
+            prune_persistent_history,
             delete_query_history_item,
             stop_analysis,
             clear_analysis_log,
@@ -882,6 +1392,7 @@ fn main() {
             update_app_config,
             get_file_info,
             export_query_result,
+            get_dataset_stats,
             validate_file_path,
             set_window_dark_mode,
             open_file,  // 新增打开文件命令
@@ -889,7 +1400,15 @@ fn main() {
             get_app_directory
         ])
         .setup(|app| {
+            // 预热日志解析正则，避免分析首行输出时的编译延迟
+            warmup_regexes();
             info!("Application setup completed");
+
+            // 启动任务队列工作循环（多文件排队分析）
+            let handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                job_queue_worker(handle).await;
+            });
             
             // 初始化Windows窗口主题（默认浅色）
             #[cfg(target_os = "windows")]